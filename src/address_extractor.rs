@@ -2,10 +2,116 @@ use anyhow::Result;
 use std::collections::HashSet;
 use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
 
+use crate::database::ExtractedAddresses;
+
 /// 地址提取器
 pub struct AddressExtractor;
 
 impl AddressExtractor {
+    /// 提取所有相关地址，并按消息头和指令划分签名者、可写、只读账户和调用的程序
+    ///
+    /// 静态账户按消息头排列：前 `num_required_signatures` 个为签名者，其中最后
+    /// `num_readonly_signed_accounts` 个只读；其余账户中最后 `num_readonly_unsigned_accounts` 个只读。
+    /// 通过地址查找表加载的账户按元数据中的可写、只读列表划分。
+    pub fn extract(transaction_update: &SubscribeUpdateTransaction) -> Result<ExtractedAddresses> {
+        let mut extracted = ExtractedAddresses {
+            all_addresses: Self::extract_all_addresses(transaction_update)?,
+            ..Default::default()
+        };
+        let Some(tx_info) = &transaction_update.transaction else {
+            return Ok(extracted);
+        };
+        let Some(message) = tx_info.transaction.as_ref().and_then(|transaction| transaction.message.as_ref()) else {
+            return Ok(extracted);
+        };
+
+        let (num_signers, readonly_signed, readonly_unsigned) = message
+            .header
+            .as_ref()
+            .map(|header| {
+                (
+                    header.num_required_signatures as usize,
+                    header.num_readonly_signed_accounts as usize,
+                    header.num_readonly_unsigned_accounts as usize,
+                )
+            })
+            .unwrap_or((1, 0, 0));
+        let static_count = message.account_keys.len();
+        for (index, key) in message.account_keys.iter().enumerate() {
+            let address = bs58::encode(key).into_string();
+            let writable = if index < num_signers {
+                index < num_signers.saturating_sub(readonly_signed)
+            } else {
+                index < static_count.saturating_sub(readonly_unsigned)
+            };
+            if index < num_signers {
+                extracted.signers.push(address.clone());
+            }
+            if writable {
+                extracted.writable.push(address);
+            } else {
+                extracted.readonly.push(address);
+            }
+        }
+
+        // 程序索引指向静态账户与加载账户合并后的列表
+        let mut account_keys: Vec<&[u8]> = message.account_keys.iter().map(Vec::as_slice).collect();
+        if let Some(meta) = &tx_info.meta {
+            account_keys.extend(meta.loaded_writable_addresses.iter().map(Vec::as_slice));
+            account_keys.extend(meta.loaded_readonly_addresses.iter().map(Vec::as_slice));
+            extracted
+                .writable
+                .extend(meta.loaded_writable_addresses.iter().map(|key| bs58::encode(key).into_string()));
+            extracted
+                .readonly
+                .extend(meta.loaded_readonly_addresses.iter().map(|key| bs58::encode(key).into_string()));
+        }
+
+        let inner_program_indexes = tx_info.meta.iter().flat_map(|meta| {
+            meta.inner_instructions
+                .iter()
+                .flat_map(|group| group.instructions.iter().map(|instruction| instruction.program_id_index))
+        });
+        let mut seen = HashSet::new();
+        for program_index in message
+            .instructions
+            .iter()
+            .map(|instruction| instruction.program_id_index)
+            .chain(inner_program_indexes)
+        {
+            let Some(key) = account_keys.get(program_index as usize) else {
+                continue;
+            };
+            if seen.insert(program_index) {
+                extracted.programs.push(bs58::encode(key).into_string());
+            }
+        }
+
+        Ok(extracted)
+    }
+
+    /// 交易的顶层指令是否全部调用 `excluded` 中的程序；没有指令或排除列表为空时为 false
+    pub fn only_invokes(transaction_update: &SubscribeUpdateTransaction, excluded: &[String]) -> bool {
+        if excluded.is_empty() {
+            return false;
+        }
+        let Some(tx_info) = &transaction_update.transaction else {
+            return false;
+        };
+        let Some(message) = tx_info.transaction.as_ref().and_then(|transaction| transaction.message.as_ref()) else {
+            return false;
+        };
+
+        // 程序只能是静态账户，无需合并加载的地址
+        !message.instructions.is_empty()
+            && message.instructions.iter().all(|instruction| {
+                message
+                    .account_keys
+                    .get(instruction.program_id_index as usize)
+                    .is_some_and(|key| excluded.contains(&bs58::encode(key).into_string()))
+            })
+    }
+
     /// 从交易更新中提取所有相关地址，返回 base58 编码的地址列表
     pub fn extract_all_addresses(transaction_update: &SubscribeUpdateTransaction) -> Result<Vec<String>> {
         let mut addresses = HashSet::new();
@@ -74,4 +180,63 @@ impl AddressExtractor {
         // 转换为 Vec 并返回
         Ok(addresses.into_iter().collect())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yellowstone_grpc_proto::prelude::{
+        CompiledInstruction, InnerInstruction, InnerInstructions, Message, MessageHeader, SubscribeUpdateTransactionInfo,
+        Transaction, TransactionStatusMeta,
+    };
+
+    #[test]
+    fn test_extract_classifies_roles() {
+        let key = |byte: u8| vec![byte; 32];
+        let address = |byte: u8| bs58::encode([byte; 32]).into_string();
+        let update = SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                signature: vec![9; 64],
+                is_vote: false,
+                transaction: Some(Transaction {
+                    signatures: vec![vec![9; 64]],
+                    message: Some(Message {
+                        // 付款人、只读签名者、可写账户、程序
+                        header: Some(MessageHeader {
+                            num_required_signatures: 2,
+                            num_readonly_signed_accounts: 1,
+                            num_readonly_unsigned_accounts: 1,
+                        }),
+                        account_keys: vec![key(1), key(2), key(3), key(4)],
+                        instructions: vec![CompiledInstruction { program_id_index: 3, accounts: vec![0, 2], data: vec![] }],
+                        ..Default::default()
+                    }),
+                }),
+                meta: Some(TransactionStatusMeta {
+                    loaded_writable_addresses: vec![key(5)],
+                    loaded_readonly_addresses: vec![key(6)],
+                    // 内部指令调用通过查找表加载的程序
+                    inner_instructions: vec![InnerInstructions {
+                        index: 0,
+                        instructions: vec![InnerInstruction { program_id_index: 5, ..Default::default() }],
+                    }],
+                    ..Default::default()
+                }),
+                index: 0,
+            }),
+            slot: 1,
+        };
+
+        let extracted = AddressExtractor::extract(&update).unwrap();
+        assert_eq!(extracted.signers, vec![address(1), address(2)]);
+        assert_eq!(extracted.writable, vec![address(1), address(3), address(5)]);
+        assert_eq!(extracted.readonly, vec![address(2), address(4), address(6)]);
+        assert_eq!(extracted.programs, vec![address(4), address(6)]);
+        assert_eq!(extracted.all_addresses.len(), 6);
+
+        // 排除判断只看顶层指令，内部指令调用的程序不影响
+        assert!(AddressExtractor::only_invokes(&update, &[address(4)]));
+        assert!(!AddressExtractor::only_invokes(&update, &[address(6)]));
+        assert!(!AddressExtractor::only_invokes(&update, &[]));
+    }
+}
@@ -0,0 +1,114 @@
+//! 金额类型
+//!
+//! 链上金额统一以最小单位的整数保存，只在展示时换算。`Lamports` 表示 SOL 金额，
+//! `TokenAmount` 表示带精度的代币金额。换算集中在这里，格式化按字符串插入小数点，
+//! 不经过浮点数，不会丢失精度。
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 1 SOL 对应的 lamports
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// SOL 的精度
+pub const SOL_DECIMALS: u8 = 9;
+
+/// SOL 金额（lamports），序列化为整数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Lamports(pub u64);
+
+impl Lamports {
+    /// 按 SOL 数量换算，小数部分超出精度时截断
+    pub fn from_sol(sol: f64) -> Self {
+        Self((sol * LAMPORTS_PER_SOL as f64) as u64)
+    }
+
+    /// 换算为 SOL（浮点数，仅用于展示和阈值比较）
+    pub fn as_sol(self) -> f64 {
+        self.0 as f64 / LAMPORTS_PER_SOL as f64
+    }
+}
+
+impl From<u64> for Lamports {
+    fn from(lamports: u64) -> Self {
+        Self(lamports)
+    }
+}
+
+/// 以 SOL 为单位的精确小数，如 `1.5`
+impl fmt::Display for Lamports {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_units(self.0, SOL_DECIMALS))
+    }
+}
+
+/// 代币金额：原始整数金额及精度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TokenAmount {
+    pub raw: u64,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(raw: u64, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// 按精度换算后的数量（浮点数，仅用于展示和估值）
+    pub fn ui_amount(self) -> f64 {
+        self.raw as f64 / 10_f64.powi(self.decimals as i32)
+    }
+}
+
+impl From<Lamports> for TokenAmount {
+    fn from(lamports: Lamports) -> Self {
+        Self::new(lamports.0, SOL_DECIMALS)
+    }
+}
+
+/// 按精度换算后的精确小数，如 `12.345`
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_units(self.raw, self.decimals))
+    }
+}
+
+/// 把最小单位的整数金额格式化为小数，去掉末尾的 0
+fn format_units(raw: u64, decimals: u8) -> String {
+    let digits = raw.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_formatting_and_conversion() {
+        assert_eq!(Lamports(1_500_000_000).to_string(), "1.5");
+        assert_eq!(Lamports(1).to_string(), "0.000000001");
+        assert_eq!(Lamports(0).to_string(), "0");
+        // 超过 f64 精确表示范围的金额也不丢位
+        assert_eq!(Lamports(u64::MAX).to_string(), "18446744073.709551615");
+        assert_eq!(TokenAmount::new(12_345, 3).to_string(), "12.345");
+        assert_eq!(TokenAmount::new(42, 0).to_string(), "42");
+        assert_eq!(TokenAmount::from(Lamports(2_000_000_000)), TokenAmount::new(2_000_000_000, 9));
+
+        assert_eq!(Lamports::from_sol(0.25), Lamports(250_000_000));
+        assert_eq!(Lamports(250_000_000).as_sol(), 0.25);
+        assert_eq!(TokenAmount::new(1_500_000, 6).ui_amount(), 1.5);
+        assert_eq!(serde_json::to_string(&Lamports(7)).unwrap(), "7");
+    }
+}
@@ -0,0 +1,88 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+use crate::clock::Clock;
+use super::models::{ApiResponse, ErrorResponse};
+
+/// 接口处理结果，错误时返回对应的 HTTP 状态码
+pub type ApiResult<T> = Result<Json<ApiResponse<T>>, ApiError>;
+
+/// API 错误
+///
+/// 转换为响应时使用对应的 HTTP 状态码，响应体统一为 `ApiResponse<ErrorResponse>`
+/// （`success = false`，`data.error` 与 `message` 相同）。
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    body: ApiResponse<ErrorResponse>,
+}
+
+impl ApiError {
+    /// 创建指定状态码的错误
+    pub fn new(status: StatusCode, message: impl Into<String>, clock: &dyn Clock) -> Self {
+        Self {
+            status,
+            body: ApiResponse::<ErrorResponse>::error_with_clock(message.into(), clock),
+        }
+    }
+
+    /// 400：请求参数无效
+    pub fn bad_request(message: impl Into<String>, clock: &dyn Clock) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message, clock)
+    }
+
+    /// 401：鉴权失败
+    pub fn unauthorized(message: impl Into<String>, clock: &dyn Clock) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, message, clock)
+    }
+
+    /// 403：禁止访问
+    pub fn forbidden(message: impl Into<String>, clock: &dyn Clock) -> Self {
+        Self::new(StatusCode::FORBIDDEN, message, clock)
+    }
+
+    /// 404：资源不存在
+    pub fn not_found(message: impl Into<String>, clock: &dyn Clock) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message, clock)
+    }
+
+    /// 500：服务器内部错误
+    pub fn internal(message: impl Into<String>, clock: &dyn Clock) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, message, clock)
+    }
+
+    /// HTTP 状态码
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// 错误信息
+    pub fn message(&self) -> &str {
+        &self.body.message
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_error_response_status_and_body() {
+        let clock = MockClock::new(1_704_067_200);
+        let error = ApiError::not_found("Transaction not found", &clock);
+        assert_eq!(error.status(), StatusCode::NOT_FOUND);
+        assert_eq!(error.message(), "Transaction not found");
+
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
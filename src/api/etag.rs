@@ -0,0 +1,86 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use tracing::warn;
+
+/// 为 GET 列表接口添加 ETag，并响应 If-None-Match 条件请求
+///
+/// 响应外层的 `timestamp` 每次请求都不同，ETag 只按 `data` 计算，数据不变时轮询返回 304。
+/// ETag 在压缩前计算，因此是弱校验值。
+pub async fn etag(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("读取响应体失败，无法计算 ETag: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let Some(tag) = compute_etag(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let tag = HeaderValue::from_str(&tag).expect("ETag 只包含十六进制字符");
+
+    if if_none_match.is_some_and(|value| etag_matches(&value, &tag)) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, tag)]).into_response();
+    }
+    parts.headers.insert(header::ETAG, tag);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// 按响应中的 `data` 计算弱 ETag，响应不是 JSON 时返回空
+fn compute_etag(body: &[u8]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let data = value.get("data").unwrap_or(&value);
+    let mut hasher = DefaultHasher::new();
+    data.to_string().hash(&mut hasher);
+    Some(format!("W/\"{:016x}\"", hasher.finish()))
+}
+
+/// If-None-Match 是否包含该 ETag（弱比较）
+fn etag_matches(if_none_match: &HeaderValue, tag: &HeaderValue) -> bool {
+    let Ok(value) = if_none_match.to_str() else {
+        return false;
+    };
+    let tag = tag.to_str().unwrap_or_default().trim_start_matches("W/");
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_ignores_envelope_timestamp() {
+        let first = compute_etag(br#"{"success":true,"data":[1,2],"timestamp":1704067200}"#).unwrap();
+        let second = compute_etag(br#"{"success":true,"data":[1,2],"timestamp":1704067260}"#).unwrap();
+        let changed = compute_etag(br#"{"success":true,"data":[1,2,3],"timestamp":1704067260}"#).unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, changed);
+        assert!(compute_etag(b"not json").is_none());
+
+        let tag = HeaderValue::from_str(&first).unwrap();
+        let strong = first.trim_start_matches("W/").to_string();
+        assert!(etag_matches(&HeaderValue::from_str(&format!("\"other\", {}", strong)).unwrap(), &tag));
+        assert!(etag_matches(&HeaderValue::from_static("*"), &tag));
+        assert!(!etag_matches(&HeaderValue::from_static("\"other\""), &tag));
+    }
+}
@@ -1,20 +1,58 @@
 use axum::{
-    extract::{Path, Query, State},
-    response::Json,
+    extract::{Path, Query, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
 };
+use futures::{Stream, StreamExt};
 use serde::Deserialize;
+use std::convert::Infallible;
 use std::sync::Arc;
 use tracing::{info, warn, error};
 
-use crate::database::DatabaseManager;
+use crate::backup::Backup;
+use crate::clock::{Clock, SharedClock};
+use crate::config_reload::ConfigReloader;
+use crate::database::{
+    ActivityGranularity, AddressTagStorage, SOL_ASSET, DailyAddressRollup, DepositStorage, ExpectedWithdrawal, StatsSnapshot, AddressTags, AlertRule, IngestionEvent, DatabaseManager, FinalityStatus, MintRanking, RecordDirection, FlowTraceQuery, WatchlistStorage, TransferQuery, TransferSearch, TransferDirection, TransferCategory, TransferHit,
+};
+use crate::database::largest_transfer_storage::MAX_DAILY_LARGEST_TRANSFERS;
+use crate::degradation::DegradationController;
+use crate::health::HealthMonitor;
+use crate::live_feed::TransferEvent;
+use crate::rpc_fetch::TransactionFetcher;
+use crate::throughput::ThroughputMeter;
+use super::error::{ApiError, ApiResult};
 use super::models::{
-    ApiResponse, SignatureQueryResponse, 
+    ApiResponse, SignatureQueryResponse, SignatureSearchResponse,
     DatabaseStatsResponse, AddressQueryResponse, AddressStatsResponse,
+    WatchlistImportResponse, IngestStatusResponse, SlotSummaryResponse, SlotTransactionsResponse, ProgramTransactionsResponse,
+    BalanceHistoryResponse, TokenBalancesResponse, TokenBalanceHistoryResponse,
+    DbStatsResponse, CompactResponse, BackupRequest, BackupResponse, DeleteSignatureResponse,
+    CounterpartiesResponse, FeeRelationsResponse, GraphResponse, FlowTraceRequest, FlowTraceResponse, StakeEventsResponse, SwapsResponse, NftTransfersResponse, AccountUpdatesResponse,
+    AddressBatchQueryRequest, AddressBatchQueryResponse, AddressBatchEntryResponse,
+    TransferSearchResponse, TransfersBetweenResponse, Labeled, AddressLabelResponse, LabelUpdateRequest, AddressTagsResponse, AddressTagsRequest, AlertsResponse,
+    WebhookSubscriptionRequest, WebhookSubscriptionResponse, WebhookSubscriptionsResponse, LargestTransfersResponse,
+    DepositAddressRequest, DepositAddressResponse, UserDepositsResponse, WithdrawalRequest, WithdrawalStatusResponse,
+    HasFinality, DeadLetterResponse, ConfigReloadResponse, IngestionLogResponse, ActiveAddressesResponse, AmountHistogramResponse, ThroughputResponse, ActivityResponse, AddressDailySummaryResponse, PaginatedResponse, HealthResponse, MintStatsResponse, TopMintsResponse,
 };
 
 /// API 应用状态
 pub struct AppState {
     pub db_manager: DatabaseManager,
+    pub clock: SharedClock,
+    pub degradation: DegradationController,
+    pub health: HealthMonitor,
+    pub throughput: ThroughputMeter,
+    /// 配置热更新，未设置时重新加载接口不可用
+    pub config_reloader: Option<ConfigReloader>,
+    /// 交易补查来源，未设置时 `fetch=true` 不生效
+    pub transaction_fetcher: Option<Arc<dyn TransactionFetcher>>,
+    /// 管理接口 API Key，未配置时管理接口不可用
+    pub admin_api_key: Option<String>,
 }
 
 /// 查询参数
@@ -24,147 +62,735 @@ pub struct QueryParams {
     pub offset: Option<usize>,
 }
 
+/// 地址交易记录查询参数
+#[derive(Debug, Deserialize)]
+pub struct AddressTransactionsParams {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    /// 按转账方向过滤：in、out 或 self
+    pub direction: Option<String>,
+}
+
+/// 分页列表查询参数（cursor 优先于 offset）
+#[derive(Debug, Deserialize)]
+pub struct ListParams {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+impl ListParams {
+    /// 本页的起始偏移量，游标无效时返回 400
+    fn start(&self, clock: &dyn Clock) -> Result<usize, ApiError> {
+        page_start(self.cursor.as_deref(), self.offset, clock)
+    }
+}
+
+/// 签名列表查询参数
+#[derive(Debug, Deserialize)]
+pub struct SignatureListParams {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+    /// 排序：key（按签名字典序，默认）或 ts_desc（按时间从新到旧）
+    pub sort: Option<String>,
+    pub min_slot: Option<u64>,
+    pub max_slot: Option<u64>,
+}
+
+/// 分页起始偏移量：cursor 优先于 offset，游标无效时返回 400
+fn page_start(cursor: Option<&str>, offset: Option<usize>, clock: &dyn Clock) -> Result<usize, ApiError> {
+    match cursor {
+        Some(cursor) => cursor
+            .parse()
+            .map_err(|_| ApiError::bad_request("cursor 无效 / Invalid cursor", clock)),
+        None => Ok(offset.unwrap_or(0)),
+    }
+}
+
+/// 代币余额查询参数
+#[derive(Debug, Deserialize)]
+pub struct TokenBalanceQueryParams {
+    pub mint: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// 关系图查询参数
+#[derive(Debug, Deserialize)]
+pub struct GraphQueryParams {
+    pub root: String,
+    pub depth: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+/// 转账搜索参数
+#[derive(Debug, Deserialize)]
+pub struct TransferSearchParams {
+    pub address: Option<String>,
+    pub mint: Option<String>,
+    pub min_amount: Option<u64>,
+    pub max_amount: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub direction: Option<String>,
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+/// 地址对转账查询参数
+#[derive(Debug, Deserialize)]
+pub struct TransfersBetweenParams {
+    pub from: String,
+    pub to: String,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+/// 告警查询参数
+#[derive(Debug, Deserialize)]
+pub struct AlertsParams {
+    pub rule: Option<String>,
+    pub address: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// 用户充值查询参数
+#[derive(Debug, Deserialize)]
+pub struct DepositsParams {
+    pub status: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Webhook 订阅查询参数
+#[derive(Debug, Deserialize)]
+pub struct WebhooksParams {
+    pub address: Option<String>,
+}
+
+/// 最大转账榜查询参数
+#[derive(Debug, Deserialize)]
+pub struct LargestTransfersParams {
+    pub period: Option<String>,
+    pub mint: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// 活跃度时间线查询参数
+#[derive(Debug, Deserialize)]
+pub struct ActivityParams {
+    pub granularity: Option<String>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+/// 地址每日汇总查询参数
+#[derive(Debug, Deserialize)]
+pub struct DailySummaryParams {
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+/// 摄取审计日志查询参数
+#[derive(Debug, Deserialize)]
+pub struct IngestionLogParams {
+    pub kind: Option<String>,
+    pub since: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// 活跃地址统计查询参数
+#[derive(Debug, Deserialize)]
+pub struct ActiveAddressesParams {
+    pub days: Option<usize>,
+}
+
+/// 金额分布查询参数
+#[derive(Debug, Deserialize)]
+pub struct HistogramParams {
+    /// 资产：SOL 或代币 mint，默认 SOL
+    pub asset: Option<String>,
+    /// 统计周期，如 `7d`，默认 7d
+    pub period: Option<String>,
+}
+
+/// 代币排行查询参数
+#[derive(Debug, Deserialize)]
+pub struct TopMintsParams {
+    pub by: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// 签名前缀搜索参数
+#[derive(Debug, Deserialize)]
+pub struct SignatureSearchParams {
+    pub prefix: String,
+    pub limit: Option<usize>,
+}
+
+/// 签名前缀搜索最多返回的签名数
+const MAX_SIGNATURE_SEARCH_LIMIT: usize = 100;
+
+/// 活跃度时间线最多返回的时间桶数
+const MAX_ACTIVITY_BUCKETS: usize = 1000;
+
+/// 地址每日汇总最多返回的天数
+const MAX_DAILY_SUMMARY_DAYS: usize = 3650;
+
+/// 关系图最大展开深度
+const MAX_GRAPH_DEPTH: usize = 3;
+
+/// 资金流追踪最多跳数
+const MAX_TRACE_HOPS: usize = 10;
+
+/// 资金流追踪最多展开的节点数
+const MAX_TRACE_NODES: usize = 2000;
+
+/// 代币排行最多返回的代币数
+const MAX_TOP_MINTS: usize = 100;
+
+/// 批量查询单次最多的地址数
+const MAX_BATCH_ADDRESSES: usize = 100;
+
+/// 批量查询每个地址最多返回的记录数
+const MAX_BATCH_RECORDS: usize = 100;
+
+/// 最大转账榜可查询的最长时间窗口（秒）
+const MAX_LARGEST_PERIOD_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// 解析 `1h` / `24h` / `7d` 形式的时间窗口，返回秒数
+fn parse_period(period: &str) -> Option<i64> {
+    let (value, unit) = period.split_at(period.len().checked_sub(1)?);
+    let value: i64 = value.parse().ok().filter(|value| *value > 0)?;
+    let seconds = match unit {
+        "h" => value.checked_mul(60 * 60)?,
+        "d" => value.checked_mul(24 * 60 * 60)?,
+        _ => return None,
+    };
+    (seconds <= MAX_LARGEST_PERIOD_SECS).then_some(seconds)
+}
+
+/// 查找响应中涉及地址的标签并填充
+fn with_labels<T: Labeled>(db: &DatabaseManager, mut response: T) -> anyhow::Result<T> {
+    let names = db.label_storage().resolve_names(response.label_addresses())?;
+    response.apply_labels(&names);
+    Ok(response)
+}
+
+/// 按当前槽位最终性快照填充响应中的 finality 字段
+fn with_finality<T: HasFinality>(db: &DatabaseManager, mut response: T) -> anyhow::Result<T> {
+    let view = db.slot_finality_storage().view()?;
+    response.apply_finality(&view);
+    Ok(response)
+}
+
+/// 校验地址格式，无效时返回 400
+fn validate_address(address: &str, clock: &dyn Clock) -> Result<(), ApiError> {
+    if address.is_empty() || address.len() < 32 {
+        warn!("无效的地址格式: {}", address);
+        return Err(ApiError::bad_request("地址格式无效 / Invalid address format", clock));
+    }
+    Ok(())
+}
+
+/// 单笔交易查询参数
+#[derive(Debug, Deserialize)]
+pub struct TransactionQueryParams {
+    /// 本地没有该交易时从配置的 RPC 端点补查并入库
+    #[serde(default)]
+    pub fetch: bool,
+}
+
 /// 根据签名查询交易数据
 #[utoipa::path(
     get,
     path = "/api/v1/transaction/{signature}",
     params(
-        ("signature" = String, Path, description = "Transaction signature in base58 format")
+        ("signature" = String, Path, description = "Transaction signature in base58 format",
+            example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"),
+        ("fetch" = Option<bool>, Query, description = "Fetch the transaction from the configured RPC endpoint and store it when it is missing locally (default false)")
     ),
     responses(
         (status = 200, description = "Transaction data found", body = ApiResponse<SignatureQueryResponse>),
-        (status = 404, description = "Transaction not found"),
-        (status = 400, description = "Invalid signature format"),
-        (status = 500, description = "Internal server error")
+        (status = 400, description = "Invalid signature format, or fetch requested without a configured RPC endpoint", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "Invalid signature format", "data": {"error": "Invalid signature format"}, "timestamp": 1704067200})),
+        (status = 404, description = "Transaction not found", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "Transaction not found", "data": {"error": "Transaction not found"}, "timestamp": 1704067200})),
+        (status = 500, description = "Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "Database error", "data": {"error": "Database error"}, "timestamp": 1704067200})),
+        (status = 502, description = "RPC fetch failed", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "RPC 补查失败 / RPC fetch failed", "data": {"error": "RPC 补查失败 / RPC fetch failed"}, "timestamp": 1704067200}))
     ),
     tag = "Transactions"
 )]
 pub async fn get_transaction_by_signature(
     State(state): State<Arc<AppState>>,
     Path(signature): Path<String>,
-) -> Json<ApiResponse<SignatureQueryResponse>> {
+    Query(params): Query<TransactionQueryParams>,
+) -> ApiResult<SignatureQueryResponse> {
     info!("Querying transaction by signature: {}", signature);
+    let clock = state.clock.as_ref();
 
     // 验证签名格式
     if signature.is_empty() || signature.len() < 32 {
         warn!("Invalid signature format: {}", signature);
-        return Json(ApiResponse::success(
-            SignatureQueryResponse {
-                signature: "".to_string(),
-                sol_transfers: vec![],
-                token_transfers: vec![],
-                extracted_addresses: Default::default(),
-                timestamp: 0,
-                slot: 0,
-                is_successful: false,
-            },
-            "Invalid signature format".to_string(),
-        ));
+        return Err(ApiError::bad_request("Invalid signature format", clock));
+    }
+
+    let mut response_data = lookup_transaction(&state, &signature).await?;
+    if response_data.is_none() && params.fetch {
+        let Some(fetcher) = &state.transaction_fetcher else {
+            return Err(ApiError::bad_request("未配置 RPC 端点 / RPC endpoint is not configured", clock));
+        };
+        match fetcher.fetch_and_store(&state.db_manager, &signature).await {
+            Ok(true) => response_data = lookup_transaction(&state, &signature).await?,
+            Ok(false) => info!("Transaction not found on RPC either: {}", signature),
+            Err(e) => {
+                error!("RPC fetch failed for signature {}: {:#}", signature, e);
+                return Err(ApiError::new(StatusCode::BAD_GATEWAY, "RPC 补查失败 / RPC fetch failed", clock));
+            }
+        }
     }
 
-    // 查询数据库
-    match state.db_manager.signature_storage().get_signature_data(&signature) {
-        Ok(Some(data)) => {
+    match response_data {
+        Some(response_data) => {
             info!("Transaction found for signature: {}", signature);
-            let response_data: SignatureQueryResponse = data.into();
-            Json(ApiResponse::success(
+            Ok(Json(ApiResponse::success_with_clock(
                 response_data,
                 "Transaction data retrieved successfully.".to_string(),
-            ))
+                clock,
+            )))
         }
-        Ok(None) => {
+        None => {
             info!("Transaction not found for signature: {}", signature);
-            Json(ApiResponse::success(
-                SignatureQueryResponse {
-                    signature: signature.clone(),
-                    sol_transfers: vec![],
-                    token_transfers: vec![],
-                    extracted_addresses: Default::default(),
-                    timestamp: 0,
-                    slot: 0,
-                    is_successful: false,
-                },
-                "Transaction not found".to_string(),
-            ))
+            Err(ApiError::not_found("Transaction not found", clock))
         }
-        Err(e) => {
+    }
+}
+
+/// 从数据库读取单笔交易（带标签和最终性）
+async fn lookup_transaction(state: &AppState, signature: &str) -> Result<Option<SignatureQueryResponse>, ApiError> {
+    let lookup = signature.to_string();
+    state
+        .db_manager
+        .run_blocking(move |db| {
+            db.query_cache()
+                .signature_data(&lookup, || db.signature_storage().get_signature_data(&lookup))?
+                .map(|data| with_finality(db, with_labels(db, SignatureQueryResponse::from(data))?))
+                .transpose()
+        })
+        .await
+        .map_err(|e| {
             error!("Database error while querying signature {}: {}", signature, e);
-            Json(ApiResponse::success(
-                SignatureQueryResponse {
-                    signature: signature.clone(),
-                    sol_transfers: vec![],
-                    token_transfers: vec![],
-                    extracted_addresses: Default::default(),
-                    timestamp: 0,
-                    slot: 0,
-                    is_successful: false,
-                },
-                "Database error".to_string(),
-            ))
+            ApiError::internal("Database error", state.clock.as_ref())
+        })
+}
+
+/// 获取归档的原始交易 / Get the archived raw transaction
+///
+/// 返回订阅收到的 `SubscribeUpdateTransaction` protobuf 字节，需开启 `database.store_raw_transactions`。
+#[utoipa::path(
+    get,
+    path = "/api/v1/transaction/{signature}/raw",
+    params(
+        ("signature" = String, Path, description = "交易签名（base58格式）/ Transaction signature in base58 format",
+            example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")
+    ),
+    responses(
+        (status = 200, description = "原始 protobuf 字节 / Raw protobuf bytes", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 400, description = "签名格式无效 / Invalid signature format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "签名格式无效 / Invalid signature format", "data": {"error": "签名格式无效 / Invalid signature format"}, "timestamp": 1704067200})),
+        (status = 404, description = "未归档该交易 / Raw transaction not archived", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "未归档该交易的原始数据 / Raw transaction not archived", "data": {"error": "未归档该交易的原始数据 / Raw transaction not archived"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Transactions"
+)]
+pub async fn get_raw_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(signature): Path<String>,
+) -> Result<Response, ApiError> {
+    let clock = state.clock.as_ref();
+    if signature.is_empty() || signature.len() < 32 {
+        warn!("无效的签名格式: {}", signature);
+        return Err(ApiError::bad_request("签名格式无效 / Invalid signature format", clock));
+    }
+
+    let lookup = signature.clone();
+    match state.db_manager.run_blocking(move |db| db.raw_transaction_storage().get(&lookup)).await {
+        Ok(Some(payload)) => {
+            info!("返回交易 {} 的原始数据: {} bytes", signature, payload.len());
+            Ok(([(header::CONTENT_TYPE, "application/octet-stream")], payload).into_response())
+        }
+        Ok(None) => Err(ApiError::not_found("未归档该交易的原始数据 / Raw transaction not archived", clock)),
+        Err(e) => {
+            error!("读取交易 {} 的原始数据时数据库错误: {}", signature, e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
         }
     }
 }
 
+/// 统计接口返回的每日统计天数
+const DAILY_STATS_DAYS: usize = 7;
+
 /// 获取数据库统计信息
 #[utoipa::path(
     get,
     path = "/api/v1/stats",
     responses(
         (status = 200, description = "Database statistics", body = ApiResponse<DatabaseStatsResponse>),
-        (status = 500, description = "Internal server error")
+        (status = 500, description = "Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "Database error", "data": {"error": "Database error"}, "timestamp": 1704067200}))
     ),
     tag = "Statistics"
 )]
 pub async fn get_database_stats(
     State(state): State<Arc<AppState>>,
-) -> Json<ApiResponse<DatabaseStatsResponse>> {
+) -> ApiResult<DatabaseStatsResponse> {
     info!("Querying database statistics");
+    let clock = state.clock.as_ref();
 
-    match state.db_manager.signature_storage().get_statistics() {
-        Ok(stats) => {
+    // 读取摄取时维护的计数器，不扫描签名数据
+    let stats = state
+        .db_manager
+        .run_blocking(|db| {
+            db.query_cache().stats(|| {
+                let daily_fees = db.fee_stats_storage().get_recent_days(DAILY_STATS_DAYS).unwrap_or_else(|e| {
+                    error!("Database error while getting daily fee statistics: {}", e);
+                    vec![]
+                });
+                let storage = db.stats_storage();
+                Ok(StatsSnapshot {
+                    totals: storage.get_totals()?,
+                    daily_transfers: storage.get_recent_days(DAILY_STATS_DAYS)?,
+                    daily_fees,
+                })
+            })
+        })
+        .await;
+
+    match stats {
+        Ok(StatsSnapshot { totals, daily_transfers, daily_fees }) => {
             let response_data = DatabaseStatsResponse {
-                total_signatures: stats.total_signatures,
-                total_sol_transfers: stats.total_sol_transfers,
-                total_token_transfers: stats.total_token_transfers,
-                successful_transactions: stats.successful_transactions,
-                failed_transactions: stats.failed_transactions,
+                total_signatures: totals.total_signatures as usize,
+                total_sol_transfers: totals.total_sol_transfers as usize,
+                total_token_transfers: totals.total_token_transfers as usize,
+                successful_transactions: totals.successful_transactions as usize,
+                failed_transactions: totals.failed_transactions as usize,
+                daily_fees: daily_fees.into_iter().map(Into::into).collect(),
+                daily_transfers: daily_transfers.into_iter().map(Into::into).collect(),
+                total_usd_volume: totals.total_usd_volume,
+                failures_by_category: totals.failures_by_category,
             };
-            Json(ApiResponse::success(
+            Ok(Json(ApiResponse::success_with_clock(
                 response_data,
                 "Database statistics retrieved successfully.".to_string(),
-            ))
+                clock,
+            )))
         }
         Err(e) => {
             error!("Database error while getting statistics: {}", e);
-            Json(ApiResponse::success(
-                DatabaseStatsResponse {
-                    total_signatures: 0,
-                    total_sol_transfers: 0,
-                    total_token_transfers: 0,
-                    successful_transactions: 0,
-                    failed_transactions: 0,
-                },
-                "Database error".to_string(),
-            ))
+            Err(ApiError::internal("Database error", clock))
+        }
+    }
+}
+
+/// 活跃地址统计最多返回的天数
+const MAX_ACTIVE_ADDRESS_DAYS: usize = 365;
+
+/// 获取每日活跃地址数 / Get daily active addresses
+///
+/// 统计每天参与过 SOL 或代币转账的不同地址数，摄取时精确去重。
+/// / Counts distinct addresses that sent or received a SOL or token transfer each day, deduplicated exactly at ingest time.
+#[utoipa::path(
+    get,
+    path = "/api/v1/stats/active-addresses",
+    params(
+        ("days" = Option<usize>, Query, description = "返回最近的天数（默认30，最大365）/ Number of recent days (default 30, max 365)", example = 30)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<ActiveAddressesResponse>),
+        (status = 400, description = "参数无效 / Invalid parameters", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "days 必须在 1 到 365 之间 / days must be between 1 and 365", "data": {"error": "days 必须在 1 到 365 之间 / days must be between 1 and 365"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Statistics"
+)]
+pub async fn get_active_addresses(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ActiveAddressesParams>,
+) -> ApiResult<ActiveAddressesResponse> {
+    info!("Querying daily active addresses");
+    let clock = state.clock.as_ref();
+    let days = params.days.unwrap_or(30);
+    if days == 0 || days > MAX_ACTIVE_ADDRESS_DAYS {
+        return Err(ApiError::bad_request(
+            format!(
+                "days 必须在 1 到 {} 之间 / days must be between 1 and {}",
+                MAX_ACTIVE_ADDRESS_DAYS, MAX_ACTIVE_ADDRESS_DAYS
+            ),
+            clock,
+        ));
+    }
+
+    match state
+        .db_manager
+        .run_blocking(move |db| db.active_address_storage().get_recent_days(days))
+        .await
+    {
+        Ok(stats) => Ok(Json(ApiResponse::success_with_clock(
+            ActiveAddressesResponse {
+                days: stats.into_iter().map(Into::into).collect(),
+            },
+            "成功获取活跃地址统计 / Successfully retrieved active address statistics".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("查询活跃地址统计失败: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 金额分布最多统计的天数
+const MAX_HISTOGRAM_DAYS: i64 = 90;
+
+/// 获取转账金额分布 / Get transfer amount histogram
+///
+/// 按金额区间统计一段时间内的转账笔数，摄取时按天增量累加，周期按 UTC 日期计算（含今天）。
+/// / Counts transfers per amount range over a period; maintained incrementally per UTC day at ingest time (the period includes today).
+#[utoipa::path(
+    get,
+    path = "/api/v1/stats/histogram",
+    params(
+        ("asset" = Option<String>, Query, description = "资产：SOL 或代币 mint，默认 SOL / Asset: SOL or a token mint, default SOL", example = "SOL"),
+        ("period" = Option<String>, Query, description = "统计周期（天），如 7d，默认 7d，最大 90d / Period in days such as 7d, default 7d, max 90d", example = "7d")
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<AmountHistogramResponse>),
+        (status = 400, description = "参数无效 / Invalid parameters", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "period 格式应为 1d 到 90d / period must be between 1d and 90d", "data": {"error": "period 格式应为 1d 到 90d / period must be between 1d and 90d"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Statistics"
+)]
+pub async fn get_amount_histogram(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HistogramParams>,
+) -> ApiResult<AmountHistogramResponse> {
+    let clock = state.clock.as_ref();
+    let asset = params.asset.unwrap_or_else(|| SOL_ASSET.to_string());
+    let period = params.period.unwrap_or_else(|| "7d".to_string());
+    info!("查询转账金额分布: asset={}, period={}", asset, period);
+
+    let days = period
+        .strip_suffix('d')
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| (1..=MAX_HISTOGRAM_DAYS).contains(days))
+        .ok_or_else(|| {
+            ApiError::bad_request(
+                format!(
+                    "period 格式应为 1d 到 {}d / period must be between 1d and {}d",
+                    MAX_HISTOGRAM_DAYS, MAX_HISTOGRAM_DAYS
+                ),
+                clock,
+            )
+        })?;
+    if asset != SOL_ASSET {
+        validate_address(&asset, clock)?;
+    }
+
+    let since_date = (clock.now() - chrono::Duration::days(days - 1)).format("%Y-%m-%d").to_string();
+    let lookup_asset = asset.clone();
+    let lookup_since = since_date.clone();
+    match state
+        .db_manager
+        .run_blocking(move |db| db.amount_histogram_storage().get_daily(&lookup_asset, &lookup_since))
+        .await
+    {
+        Ok(daily) => {
+            let response = AmountHistogramResponse::from_daily(asset, days as u32, since_date, &daily);
+            let message = format!(
+                "成功获取转账金额分布 / Successfully retrieved amount histogram: {} transfers",
+                response.total_transfers
+            );
+            Ok(Json(ApiResponse::success_with_clock(response, message, clock)))
+        }
+        Err(e) => {
+            error!("查询 {} 的转账金额分布失败: {}", asset, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 获取摄取吞吐量 / Get ingestion throughput
+///
+/// 返回 gRPC 客户端在 1 分钟、5 分钟、15 分钟滑动窗口内的消息、转账笔数和金额速率。
+/// / Returns message, transfer and lamport rates of the gRPC client over 1m, 5m and 15m sliding windows.
+#[utoipa::path(
+    get,
+    path = "/api/v1/stats/throughput",
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<ThroughputResponse>)
+    ),
+    tag = "Statistics"
+)]
+pub async fn get_throughput(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<ThroughputResponse> {
+    let response_data = ThroughputResponse {
+        ingest_enabled: state.health.ingest().enabled,
+        windows: state.throughput.rates().into_iter().map(Into::into).collect(),
+    };
+    Ok(Json(ApiResponse::success_with_clock(
+        response_data,
+        "成功获取吞吐量统计 / Successfully retrieved throughput statistics".to_string(),
+        state.clock.as_ref(),
+    )))
+}
+
+/// Prometheus 指标 / Prometheus metrics
+///
+/// 以 Prometheus 文本格式导出槽位落后、摄取连接状态、吞吐量、过载降级计数和死信队列长度。
+/// / Exports slot lag, ingestion connectivity, throughput, overload degradation counters and dead letter queue size in the Prometheus text format.
+#[utoipa::path(
+    get,
+    path = "/api/v1/metrics",
+    responses(
+        (status = 200, description = "Prometheus 文本格式指标 / Metrics in the Prometheus text format", body = String, content_type = "text/plain",
+            example = json!("# TYPE ledger_slot_lag gauge\nledger_slot_lag 2\n"))
+    ),
+    tag = "Health"
+)]
+pub async fn get_metrics(
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    let ingest = state.health.ingest();
+    let dead_letters = state.db_manager.run_blocking(|db| db.dead_letter_storage().count()).await;
+    if let Err(e) = &dead_letters {
+        error!("导出指标时读取死信队列失败: {}", e);
+    }
+
+    let mut metrics = PrometheusText::default();
+    metrics.gauge("ledger_latest_slot", "Latest slot seen on the subscription", ingest.latest_slot);
+    metrics.gauge("ledger_last_stored_slot", "Slot of the last stored transaction", ingest.last_stored_slot);
+    metrics.gauge("ledger_slot_lag", "Slots the last stored transaction is behind the latest slot", ingest.slot_lag);
+    metrics.gauge("ledger_slot_lag_threshold", "Slot lag above which ingestion is reported as lagging", ingest.max_slot_lag);
+    metrics.gauge("ledger_ingest_enabled", "Whether this process runs ingestion", ingest.enabled as u8);
+    metrics.gauge("ledger_ingest_connected", "Whether the subscription stream is connected", ingest.connected as u8);
+    metrics.gauge("ledger_ingest_stalled", "Whether ingestion has stalled", ingest.stalled as u8);
+    metrics.gauge("ledger_ingest_lagging", "Whether the slot lag exceeds the threshold", ingest.lagging as u8);
+    metrics.gauge("ledger_excluded_transactions", "Transactions skipped because they only invoke excluded programs", ingest.excluded_transactions);
+    if let Some(mb) = ingest.disk_available_mb {
+        metrics.gauge("ledger_disk_available_megabytes", "Free space on the disk holding the database", mb);
+    }
+    if let Some(mb) = ingest.db_size_mb {
+        metrics.gauge("ledger_db_size_megabytes", "Size of the database directory", mb);
+    }
+    metrics.gauge("ledger_ingest_paused", "Whether writes are paused by the disk usage guardrail", ingest.ingestion_paused as u8);
+    metrics.gauge("ledger_paused_transactions", "Transactions dropped while writes were paused", ingest.paused_transactions);
+    if let Some(at) = ingest.last_message_at {
+        metrics.gauge("ledger_last_message_timestamp_seconds", "Time of the last subscription message", at);
+    }
+    if let Some(at) = ingest.last_write_at {
+        metrics.gauge("ledger_last_write_timestamp_seconds", "Time of the last stored transaction", at);
+    }
+    let rates = state.throughput.rates();
+    metrics.labeled_gauge(
+        "ledger_messages_per_second",
+        "Subscription messages per second over a sliding window",
+        rates.iter().map(|rate| (rate.window_secs, rate.messages_per_sec)),
+    );
+    metrics.labeled_gauge(
+        "ledger_transfers_per_second",
+        "Parsed transfers per second over a sliding window",
+        rates.iter().map(|rate| (rate.window_secs, rate.transfers_per_sec)),
+    );
+    let degradation = state.degradation.status();
+    metrics.gauge("ledger_summarized_transactions", "Transactions stored only as slot summaries during overload", degradation.summarized_transactions);
+    metrics.gauge("ledger_sampled_out_transactions", "Transactions dropped by overload sampling", degradation.sampled_out_transactions);
+    metrics.gauge("ledger_sampled_out_transfers", "Transfers in transactions dropped by overload sampling", degradation.sampled_out_transfers);
+    metrics.gauge("ledger_sampled_out_lamports", "Lamports transferred in transactions dropped by overload sampling", degradation.sampled_out_lamports);
+    if let Ok(count) = dead_letters {
+        metrics.gauge("ledger_dead_letters", "Transactions waiting in the dead letter queue", count);
+    }
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], metrics.0).into_response()
+}
+
+/// Prometheus 文本格式输出
+#[derive(Default)]
+struct PrometheusText(String);
+
+impl PrometheusText {
+    fn gauge(&mut self, name: &str, help: &str, value: impl std::fmt::Display) {
+        self.0.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n{} {}\n", name, help, name, name, value));
+    }
+
+    /// 按滑动窗口（秒）打标签的指标
+    fn labeled_gauge(&mut self, name: &str, help: &str, values: impl Iterator<Item = (u64, f64)>) {
+        self.0.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n", name, help, name));
+        for (window_secs, value) in values {
+            self.0.push_str(&format!("{}{{window_secs=\"{}\"}} {}\n", name, window_secs, value));
         }
     }
 }
 
 /// 健康检查接口
+///
+/// 摄取停滞、磁盘用量保护暂停写入或数据库不可读时返回 503，供负载均衡器摘除实例。
 #[utoipa::path(
     get,
     path = "/api/v1/health",
     responses(
-        (status = 200, description = "Service is healthy", body = ApiResponse<String>)
+        (status = 200, description = "Service is healthy or degraded", body = ApiResponse<HealthResponse>),
+        (status = 503, description = "Ingestion stalled, paused by the disk usage guardrail, or database unavailable", body = ApiResponse<HealthResponse>)
     ),
     tag = "Health"
 )]
-pub async fn health_check() -> Json<ApiResponse<String>> {
+pub async fn health_check(
+    State(state): State<Arc<AppState>>,
+) -> Response {
     info!("Health check requested");
-    Json(ApiResponse::success(
-        "OK".to_string(),
-        "Service is running normally.".to_string(),
-    ))
+    let ingest = state.health.ingest();
+    let dead_letters = state.db_manager.run_blocking(|db| db.dead_letter_storage().count()).await;
+    if let Err(e) = &dead_letters {
+        error!("健康检查读取数据库失败: {}", e);
+    }
+
+    let database_ok = dead_letters.is_ok();
+    let (status, message) = if !database_ok {
+        ("unavailable", "Database is unavailable.")
+    } else if ingest.ingestion_paused {
+        ("paused", "Ingestion paused: disk usage guardrail triggered.")
+    } else if ingest.stalled {
+        ("unavailable", "Ingestion has stalled.")
+    } else if ingest.lagging {
+        ("degraded", "Ingestion is lagging behind the latest slot.")
+    } else {
+        ("ok", "Service is running normally.")
+    };
+
+    let health = HealthResponse {
+        status: status.to_string(),
+        database_ok,
+        read_only: state.db_manager.is_read_only(),
+        ingest: ingest.into(),
+        ingest_mode: IngestStatusResponse::from(state.degradation.status()).mode,
+        dead_letter_queue: dead_letters.unwrap_or(0),
+    };
+    let mut body = ApiResponse::success_with_clock(health, message.to_string(), state.clock.as_ref());
+    if matches!(status, "unavailable" | "paused") {
+        body.success = false;
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
+    }
+    Json(body).into_response()
 }
 
 /// 获取所有签名列表（带分页）
@@ -172,95 +798,186 @@ pub async fn health_check() -> Json<ApiResponse<String>> {
     get,
     path = "/api/v1/signatures",
     params(
-        ("limit" = Option<usize>, Query, description = "Maximum number of signatures to return (default: 100)"),
-        ("offset" = Option<usize>, Query, description = "Number of signatures to skip (default: 0)")
+        ("limit" = Option<usize>, Query, description = "Maximum number of signatures to return (default: 100)", example = 100),
+        ("offset" = Option<usize>, Query, description = "Number of signatures to skip (default: 0)", example = 0),
+        ("cursor" = Option<String>, Query, description = "next_cursor from the previous page, takes precedence over offset", example = "100"),
+        ("sort" = Option<String>, Query, description = "key (lexicographic, default) or ts_desc (newest first)", example = "ts_desc"),
+        ("min_slot" = Option<u64>, Query, description = "Only signatures at or above this slot", example = 250000000),
+        ("max_slot" = Option<u64>, Query, description = "Only signatures at or below this slot", example = 250001000)
     ),
     responses(
-        (status = 200, description = "Signatures list", body = ApiResponse<Vec<String>>),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Signatures list", body = ApiResponse<PaginatedResponse<String>>,
+            example = json!({"success": true, "message": "Retrieved 1 signatures successfully.", "data": {"items": ["5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"], "total": 1, "next_cursor": null, "has_more": false}, "timestamp": 1704067200})),
+        (status = 400, description = "Invalid cursor or sort", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "cursor 无效 / Invalid cursor", "data": {"error": "cursor 无效 / Invalid cursor"}, "timestamp": 1704067200})),
+        (status = 500, description = "Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "Database error", "data": {"error": "Database error"}, "timestamp": 1704067200}))
     ),
     tag = "Signatures"
 )]
 pub async fn get_all_signatures(
     State(state): State<Arc<AppState>>,
-    Query(params): Query<QueryParams>,
-) -> Json<ApiResponse<Vec<String>>> {
+    Query(params): Query<SignatureListParams>,
+) -> ApiResult<PaginatedResponse<String>> {
+    let clock = state.clock.as_ref();
     let limit = params.limit.unwrap_or(100).min(1000); // 最大限制1000
-    let offset = params.offset.unwrap_or(0);
-    
-    info!("Querying signatures with limit: {}, offset: {}", limit, offset);
+    let offset = page_start(params.cursor.as_deref(), params.offset, clock)?;
+    let newest_first = match params.sort.as_deref() {
+        None | Some("key") => false,
+        Some("ts_desc") => true,
+        Some(_) => return Err(ApiError::bad_request("sort 只能为 key 或 ts_desc / sort must be key or ts_desc", clock)),
+    };
+    let (min_slot, max_slot) = (params.min_slot, params.max_slot);
 
-    match state.db_manager.signature_storage().get_all_signature_keys() {
-        Ok(mut signatures) => {
-            // 应用分页
-            let total = signatures.len();
-            if offset >= total {
-                signatures.clear();
+    info!("Querying signatures with limit: {}, offset: {}, sort: {:?}", limit, offset, params.sort);
+
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let index = db.signature_time_index_storage();
+            if min_slot.is_some() || max_slot.is_some() {
+                // 槽位过滤只扫描时间索引的键
+                let mut signatures = index.signatures_in_slot_range(min_slot, max_slot)?;
+                if !newest_first {
+                    signatures.sort();
+                }
+                Ok(PaginatedResponse::from_offset(signatures, offset, limit))
+            } else if newest_first {
+                // 最近的交易只读取前 offset + limit + 1 个索引键
+                let signatures = index.recent_signatures(offset, limit + 1)?;
+                let total = db.stats_storage().get_totals()?.total_signatures as usize;
+                Ok(PaginatedResponse::from_page(signatures, offset, limit, Some(total)))
             } else {
-                let end = (offset + limit).min(total);
-                signatures = signatures[offset..end].to_vec();
+                Ok(PaginatedResponse::from_offset(db.signature_storage().get_all_signature_keys()?, offset, limit))
             }
+        })
+        .await;
 
-            let count = signatures.len();
-            info!("Returning {} signatures (total: {})", count, total);
-            Json(ApiResponse::success(
-                signatures,
+    match result {
+        Ok(page) => {
+            let count = page.items.len();
+            info!("Returning {} signatures (total: {:?})", count, page.total);
+            Ok(Json(ApiResponse::success_with_clock(
+                page,
                 format!("Retrieved {} signatures successfully.", count),
-            ))
+                clock,
+            )))
         }
         Err(e) => {
             error!("Database error while getting signatures: {}", e);
-            Json(ApiResponse::success(
-                vec![],
-                "Database error".to_string(),
-            ))
+            Err(ApiError::internal("Database error", clock))
+        }
+    }
+}
+
+/// 按签名前缀搜索 / Search signatures by prefix
+#[utoipa::path(
+    get,
+    path = "/api/v1/signatures/search",
+    params(
+        ("prefix" = String, Query, description = "签名前缀（base58，最长88个字符）/ Signature prefix (base58, at most 88 characters)", example = "5VER"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认20，最大100 / Limit of returned signatures, default 20, max 100", example = 20)
+    ),
+    responses(
+        (status = 200, description = "搜索成功（没有匹配时返回空列表）/ Search successful (empty list when nothing matches)", body = ApiResponse<SignatureSearchResponse>),
+        (status = 400, description = "签名前缀无效 / Invalid signature prefix", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "签名前缀无效 / Invalid signature prefix", "data": {"error": "签名前缀无效 / Invalid signature prefix"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Signatures"
+)]
+pub async fn search_signatures(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SignatureSearchParams>,
+) -> ApiResult<SignatureSearchResponse> {
+    let prefix = params.prefix.trim().to_string();
+    let limit = params.limit.unwrap_or(20).clamp(1, MAX_SIGNATURE_SEARCH_LIMIT);
+    info!("按前缀搜索签名: prefix={}, limit={}", prefix, limit);
+    let clock = state.clock.as_ref();
+
+    // 签名是 base58 编码，前缀也只能包含 base58 字符
+    let is_base58 = |c: char| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l');
+    if prefix.is_empty() || prefix.len() > 88 || !prefix.chars().all(is_base58) {
+        warn!("无效的签名前缀: {}", prefix);
+        return Err(ApiError::bad_request("签名前缀无效 / Invalid signature prefix", clock));
+    }
+
+    // 多取一个用于判断是否还有更多匹配
+    let search_prefix = prefix.clone();
+    match state
+        .db_manager
+        .run_blocking(move |db| db.signature_storage().search_signatures_by_prefix(&search_prefix, limit + 1))
+        .await
+    {
+        Ok(mut signatures) => {
+            let has_more = signatures.len() > limit;
+            signatures.truncate(limit);
+            let count = signatures.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                SignatureSearchResponse { prefix, signatures, has_more },
+                format!("签名搜索完成 / Signature search finished: {} matches", count),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("按前缀 {} 搜索签名时数据库错误: {}", prefix, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
         }
     }
-} 
+}
 
 /// 根据地址查询交易记录 / Query transaction records by address
 #[utoipa::path(
     get,
     path = "/api/v1/address/{address}/transactions",
     params(
-        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)"),
-        ("limit" = Option<usize>, Query, description = "返回记录数量限制，默认100，最大1000 / Limit of returned records, default 100, max 1000"),
-        ("offset" = Option<usize>, Query, description = "跳过的记录数量，用于分页，默认0 / Number of records to skip for pagination, default 0")
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回记录数量限制，默认100，最大1000 / Limit of returned records, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的记录数量，用于分页，默认0 / Number of records to skip for pagination, default 0", example = 0),
+        ("direction" = Option<String>, Query, description = "按转账方向过滤：in、out 或 self / Filter by transfer direction: in, out or self", example = "out")
     ),
     responses(
-        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<AddressQueryResponse>),
-        (status = 400, description = "地址格式无效 / Invalid address format"),
-        (status = 500, description = "服务器内部错误 / Internal server error")
+        (status = 200, description = "查询成功（没有记录时返回空列表）/ Query successful (empty list when the address has no records)", body = ApiResponse<AddressQueryResponse>),
+        (status = 400, description = "地址格式或方向无效 / Invalid address format or direction", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
     ),
     tag = "Addresses"
 )]
 pub async fn get_address_transactions(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
-    Query(params): Query<QueryParams>,
-) -> Json<ApiResponse<AddressQueryResponse>> {
+    Query(params): Query<AddressTransactionsParams>,
+) -> ApiResult<AddressQueryResponse> {
     info!("查询地址交易记录: {}", address);
+    let clock = state.clock.as_ref();
 
     // 验证地址格式
-    if address.is_empty() || address.len() < 32 {
-        warn!("无效的地址格式: {}", address);
-        return Json(ApiResponse::success(
-            AddressQueryResponse {
-                address: address.clone(),
-                total_records: 0,
-                records: vec![],
-                last_updated: 0,
-            },
-            "地址格式无效 / Invalid address format".to_string(),
-        ));
-    }
+    validate_address(&address, clock)?;
 
     let limit = params.limit.unwrap_or(100).min(1000);
     let offset = params.offset.unwrap_or(0);
+    let direction = match params.direction.as_deref() {
+        Some(value) => Some(RecordDirection::parse(value).ok_or_else(|| {
+            ApiError::bad_request("direction 只能是 in、out 或 self / direction must be in, out or self", clock)
+        })?),
+        None => None,
+    };
 
     // 查询地址交易记录
-    match state.db_manager.address_storage().get_address_records(&address) {
-        Ok(Some(mut address_list)) => {
+    let lookup = address.clone();
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let Some(mut address_list) = db.address_storage().get_address_records(&lookup)? else {
+                return Ok(None);
+            };
+            if let Some(direction) = direction {
+                address_list.records.retain(|record| record.direction() == direction);
+            }
             // 应用分页
             let total = address_list.records.len();
             if offset >= total {
@@ -269,37 +986,36 @@ pub async fn get_address_transactions(
                 let end = (offset + limit).min(total);
                 address_list.records = address_list.records[offset..end].to_vec();
             }
-
-            info!("找到地址 {} 的 {} 条记录（总共 {} 条）", address, address_list.records.len(), total);
-            let response_data: AddressQueryResponse = address_list.into();
-            Json(ApiResponse::success(
+            let response = with_labels(db, AddressQueryResponse::from(address_list))?;
+            with_finality(db, response).map(|response| Some((response, total)))
+        })
+        .await;
+    match result {
+        Ok(Some((response_data, total))) => {
+            info!("找到地址 {} 的 {} 条记录（总共 {} 条）", address, response_data.records.len(), total);
+            Ok(Json(ApiResponse::success_with_clock(
                 response_data,
                 format!("成功获取地址交易记录 / Successfully retrieved address transaction records: {} records", total),
-            ))
+                clock,
+            )))
         }
         Ok(None) => {
             info!("地址 {} 没有找到交易记录", address);
-            Json(ApiResponse::success(
+            Ok(Json(ApiResponse::success_with_clock(
                 AddressQueryResponse {
                     address,
+                    label: None,
                     total_records: 0,
                     records: vec![],
                     last_updated: 0,
                 },
                 "该地址没有交易记录 / No transaction records found for this address".to_string(),
-            ))
+                clock,
+            )))
         }
         Err(e) => {
             error!("查询地址 {} 时数据库错误: {}", address, e);
-            Json(ApiResponse::success(
-                AddressQueryResponse {
-                    address,
-                    total_records: 0,
-                    records: vec![],
-                    last_updated: 0,
-                },
-                "数据库查询错误 / Database query error".to_string(),
-            ))
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
         }
     }
 }
@@ -309,68 +1025,126 @@ pub async fn get_address_transactions(
     get,
     path = "/api/v1/address/{address}/stats",
     params(
-        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)")
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")
     ),
     responses(
         (status = 200, description = "统计信息获取成功 / Statistics retrieved successfully", body = ApiResponse<AddressStatsResponse>),
-        (status = 400, description = "地址格式无效 / Invalid address format"),
-        (status = 500, description = "服务器内部错误 / Internal server error")
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "获取统计信息失败 / Failed to retrieve statistics", "data": {"error": "获取统计信息失败 / Failed to retrieve statistics"}, "timestamp": 1704067200}))
     ),
     tag = "Addresses"
 )]
 pub async fn get_address_stats(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
-) -> Json<ApiResponse<AddressStatsResponse>> {
+) -> ApiResult<AddressStatsResponse> {
     info!("获取地址统计信息: {}", address);
+    let clock = state.clock.as_ref();
 
     // 验证地址格式
-    if address.is_empty() || address.len() < 32 {
-        warn!("无效的地址格式: {}", address);
-        return Json(ApiResponse::success(
-            AddressStatsResponse {
-                address: address.clone(),
-                total_records: 0,
-                sol_sent_count: 0,
-                sol_received_count: 0,
-                token_sent_count: 0,
-                token_received_count: 0,
-                total_sol_sent: 0,
-                total_sol_received: 0,
-                total_sol_sent_formatted: 0.0,
-                total_sol_received_formatted: 0.0,
-            },
-            "地址格式无效 / Invalid address format".to_string(),
-        ));
-    }
+    validate_address(&address, clock)?;
 
     // 获取地址统计信息
-    match state.db_manager.address_storage().get_address_stats(&address) {
-        Ok(stats) => {
+    let lookup = address.clone();
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let stats = db
+                .query_cache()
+                .address_stats(&lookup, || db.address_storage().get_address_stats(&lookup))?;
+            with_labels(db, AddressStatsResponse::from(stats))
+        })
+        .await;
+    match result {
+        Ok(response_data) => {
             info!("成功获取地址 {} 的统计信息", address);
-            let response_data: AddressStatsResponse = stats.into();
-            Json(ApiResponse::success(
+            Ok(Json(ApiResponse::success_with_clock(
                 response_data,
                 "成功获取地址统计信息 / Successfully retrieved address statistics".to_string(),
-            ))
+                clock,
+            )))
         }
         Err(e) => {
             error!("获取地址 {} 统计信息时错误: {}", address, e);
-            Json(ApiResponse::success(
-                AddressStatsResponse {
+            Err(ApiError::internal("获取统计信息失败 / Failed to retrieve statistics", clock))
+        }
+    }
+}
+
+/// 批量查询多个地址 / Query stats and recent records for multiple addresses
+#[utoipa::path(
+    post,
+    path = "/api/v1/addresses/query",
+    request_body = AddressBatchQueryRequest,
+    responses(
+        (status = 200, description = "查询成功（没有记录的地址返回空统计）/ Query successful (addresses without records get empty stats)", body = ApiResponse<AddressBatchQueryResponse>),
+        (status = 400, description = "地址列表为空、超过上限或包含无效地址 / Empty, oversized or invalid address list", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址数量超过上限 / Too many addresses: max 100", "data": {"error": "地址数量超过上限 / Too many addresses: max 100"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn query_addresses(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AddressBatchQueryRequest>,
+) -> ApiResult<AddressBatchQueryResponse> {
+    let clock = state.clock.as_ref();
+
+    // 去重并保持请求顺序
+    let mut addresses: Vec<String> = Vec::with_capacity(request.addresses.len());
+    for address in request.addresses {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    info!("批量查询 {} 个地址", addresses.len());
+
+    if addresses.is_empty() {
+        return Err(ApiError::bad_request("地址列表为空 / Address list is empty", clock));
+    }
+    if addresses.len() > MAX_BATCH_ADDRESSES {
+        return Err(ApiError::bad_request(
+            format!("地址数量超过上限 / Too many addresses: max {}", MAX_BATCH_ADDRESSES),
+            clock,
+        ));
+    }
+    for address in &addresses {
+        validate_address(address, clock)?;
+    }
+
+    let records_limit = request.records_limit.unwrap_or(10).min(MAX_BATCH_RECORDS);
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let mut results = Vec::with_capacity(addresses.len());
+            for address in addresses {
+                let (stats, records) = db.address_storage().get_address_overview(&address, records_limit)?;
+                results.push(AddressBatchEntryResponse {
                     address,
-                    total_records: 0,
-                    sol_sent_count: 0,
-                    sol_received_count: 0,
-                    token_sent_count: 0,
-                    token_received_count: 0,
-                    total_sol_sent: 0,
-                    total_sol_received: 0,
-                    total_sol_sent_formatted: 0.0,
-                    total_sol_received_formatted: 0.0,
-                },
-                "获取统计信息失败 / Failed to retrieve statistics".to_string(),
-            ))
+                    stats: stats.into(),
+                    recent_records: records.into_iter().map(Into::into).collect(),
+                });
+            }
+            let total_addresses = results.len();
+            with_finality(db, with_labels(db, AddressBatchQueryResponse { total_addresses, results })?)
+        })
+        .await;
+
+    match result {
+        Ok(response_data) => {
+            let message = format!(
+                "批量查询完成 / Batch query finished: {} addresses",
+                response_data.total_addresses
+            );
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("批量查询地址时数据库错误: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
         }
     }
 }
@@ -380,48 +1154,2472 @@ pub async fn get_address_stats(
     get,
     path = "/api/v1/addresses",
     params(
-        ("limit" = Option<usize>, Query, description = "返回地址数量限制，默认100，最大1000 / Limit of returned addresses, default 100, max 1000"),
-        ("offset" = Option<usize>, Query, description = "跳过的地址数量，用于分页，默认0 / Number of addresses to skip for pagination, default 0")
+        ("limit" = Option<usize>, Query, description = "返回地址数量限制，默认100，最大1000 / Limit of returned addresses, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的地址数量，用于分页，默认0 / Number of addresses to skip for pagination, default 0", example = 0),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor，优先于 offset / next_cursor from the previous page, takes precedence over offset", example = "100")
     ),
     responses(
-        (status = 200, description = "地址列表获取成功 / Address list retrieved successfully", body = ApiResponse<Vec<String>>),
-        (status = 500, description = "服务器内部错误 / Internal server error")
+        (status = 200, description = "地址列表获取成功 / Address list retrieved successfully", body = ApiResponse<PaginatedResponse<String>>,
+            example = json!({"success": true, "message": "成功获取地址列表 / Successfully retrieved address list: 1 addresses", "data": {"items": ["9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"], "total": 1, "next_cursor": null, "has_more": false}, "timestamp": 1704067200})),
+        (status = 400, description = "游标无效 / Invalid cursor", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "cursor 无效 / Invalid cursor", "data": {"error": "cursor 无效 / Invalid cursor"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
     ),
     tag = "Addresses"
 )]
 pub async fn get_all_addresses(
     State(state): State<Arc<AppState>>,
-    Query(params): Query<QueryParams>,
-) -> Json<ApiResponse<Vec<String>>> {
+    Query(params): Query<ListParams>,
+) -> ApiResult<PaginatedResponse<String>> {
     let limit = params.limit.unwrap_or(100).min(1000);
-    let offset = params.offset.unwrap_or(0);
-    
+    let offset = params.start(state.clock.as_ref())?;
+
     info!("获取地址列表，limit: {}, offset: {}", limit, offset);
 
-    match state.db_manager.address_storage().get_all_addresses() {
-        Ok(mut addresses) => {
+    match state.db_manager.run_blocking(|db| db.address_storage().get_all_addresses()).await {
+        Ok(addresses) => {
             // 应用分页
-            let total = addresses.len();
-            if offset >= total {
-                addresses.clear();
-            } else {
-                let end = (offset + limit).min(total);
-                addresses = addresses[offset..end].to_vec();
-            }
-
-            let count = addresses.len();
-            info!("返回 {} 个地址（总共 {} 个）", count, total);
-            Json(ApiResponse::success(
-                addresses,
+            let page = PaginatedResponse::from_offset(addresses, offset, limit);
+            let count = page.items.len();
+            info!("返回 {} 个地址（总共 {:?} 个）", count, page.total);
+            Ok(Json(ApiResponse::success_with_clock(
+                page,
                 format!("成功获取地址列表 / Successfully retrieved address list: {} addresses", count),
-            ))
+                state.clock.as_ref(),
+            )))
         }
         Err(e) => {
             error!("获取地址列表时数据库错误: {}", e);
-            Json(ApiResponse::success(
-                vec![],
-                "数据库错误 / Database error".to_string(),
+            Err(ApiError::internal("数据库错误 / Database error", state.clock.as_ref()))
+        }
+    }
+}
+
+/// 批量导入监控地址 / Bulk import watchlist addresses
+#[utoipa::path(
+    post,
+    path = "/api/v1/watchlist/import",
+    request_body(
+        content = String,
+        description = "CSV（每行 `address,label`，可带表头）或 JSON 数组 `[{\"address\": \"...\", \"label\": \"...\"}]` / CSV (`address,label` per line, optional header) or JSON array",
+        content_type = "text/csv",
+        example = "address,label\n9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM,hot wallet\nnot-an-address,typo"
+    ),
+    responses(
+        (status = 200, description = "导入完成（无效行列在 rejected 中）/ Import finished (invalid rows are listed in rejected)", body = ApiResponse<WatchlistImportResponse>),
+        (status = 400, description = "JSON 格式无效 / Invalid JSON body", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "JSON 格式无效 / Invalid JSON: expected value at line 1 column 1", "data": {"error": "JSON 格式无效 / Invalid JSON: expected value at line 1 column 1"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Watchlist"
+)]
+pub async fn import_watchlist(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: String,
+) -> ApiResult<WatchlistImportResponse> {
+    let clock = state.clock.as_ref();
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.contains("json"))
+        .unwrap_or_else(|| body.trim_start().starts_with('['));
+
+    info!("导入监控列表，格式: {}, 大小: {} bytes", if is_json { "JSON" } else { "CSV" }, body.len());
+
+    let rows = if is_json {
+        WatchlistStorage::parse_json(&body).map_err(|e| {
+            warn!("监控列表 JSON 解析失败: {}", e);
+            ApiError::bad_request(format!("JSON 格式无效 / Invalid JSON: {}", e), clock)
+        })?
+    } else {
+        WatchlistStorage::parse_csv(&body)
+    };
+
+    match state.db_manager.run_blocking(move |db| db.watchlist_storage().import(rows)).await {
+        Ok(summary) => {
+            let response_data: WatchlistImportResponse = summary.into();
+            let message = format!(
+                "监控列表导入完成 / Watchlist import finished: {} accepted, {} duplicates, {} rejected",
+                response_data.accepted,
+                response_data.duplicates,
+                response_data.rejected.len()
+            );
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("导入监控列表时数据库错误: {}", e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
+
+/// 获取地址标签 / Get the known-entity label of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/labels/{address}",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9")
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<AddressLabelResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 404, description = "地址没有标签 / Address has no label", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址没有标签 / Address has no label", "data": {"error": "地址没有标签 / Address has no label"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Labels"
+)]
+pub async fn get_address_label(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<AddressLabelResponse> {
+    let clock = state.clock.as_ref();
+    validate_address(&address, clock)?;
+
+    let lookup = address.clone();
+    match state.db_manager.run_blocking(move |db| db.label_storage().get_label(&lookup)).await {
+        Ok(Some(label)) => Ok(Json(ApiResponse::success_with_clock(
+            label.into(),
+            "成功获取地址标签 / Successfully retrieved address label".to_string(),
+            clock,
+        ))),
+        Ok(None) => Err(ApiError::not_found("地址没有标签 / Address has no label", clock)),
+        Err(e) => {
+            error!("查询地址 {} 标签时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 设置地址标签 / Create or replace the known-entity label of an address
+#[utoipa::path(
+    put,
+    path = "/api/v1/labels/{address}",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9")
+    ),
+    request_body = LabelUpdateRequest,
+    responses(
+        (status = 200, description = "标签已保存 / Label saved", body = ApiResponse<AddressLabelResponse>),
+        (status = 400, description = "地址格式无效或名称为空 / Invalid address or empty name", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "标签名称不能为空 / Label name must not be empty", "data": {"error": "标签名称不能为空 / Label name must not be empty"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Labels"
+)]
+pub async fn put_address_label(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Json(request): Json<LabelUpdateRequest>,
+) -> ApiResult<AddressLabelResponse> {
+    info!("设置地址标签: {} -> {}", address, request.name);
+    let clock = state.clock.as_ref();
+    validate_address(&address, clock)?;
+
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ApiError::bad_request("标签名称不能为空 / Label name must not be empty", clock));
+    }
+    let kind = request.kind.map(|kind| kind.trim().to_string()).filter(|kind| !kind.is_empty());
+
+    let target = address.clone();
+    match state.db_manager.run_blocking(move |db| db.label_storage().set_label(&target, &name, kind)).await {
+        Ok(label) => Ok(Json(ApiResponse::success_with_clock(
+            label.into(),
+            "地址标签已保存 / Address label saved".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("保存地址 {} 标签时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
+
+/// 获取地址标记和备注 / Get the tags and note of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/tags",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有标记时返回空列表）/ Query successful (empty list when untagged)", body = ApiResponse<AddressTagsResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Labels"
+)]
+pub async fn get_address_tags(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<AddressTagsResponse> {
+    let clock = state.clock.as_ref();
+    validate_address(&address, clock)?;
+
+    let lookup = address.clone();
+    match state.db_manager.run_blocking(move |db| db.address_tag_storage().get_tags(&lookup)).await {
+        Ok(entry) => {
+            let entry = entry.unwrap_or_else(|| AddressTags { address, ..Default::default() });
+            Ok(Json(ApiResponse::success_with_clock(
+                entry.into(),
+                "成功获取地址标记 / Successfully retrieved address tags".to_string(),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} 标记时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 为地址追加标记或设置备注 / Add tags or set the note of an address
+#[utoipa::path(
+    post,
+    path = "/api/v1/address/{address}/tags",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")
+    ),
+    request_body = AddressTagsRequest,
+    responses(
+        (status = 200, description = "标记已保存 / Tags saved", body = ApiResponse<AddressTagsResponse>),
+        (status = 400, description = "地址格式无效、标记无效或请求为空 / Invalid address, invalid tag or empty request", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "标记无效 / Invalid tag", "data": {"error": "标记无效 / Invalid tag"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Labels"
+)]
+pub async fn add_address_tags(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Json(request): Json<AddressTagsRequest>,
+) -> ApiResult<AddressTagsResponse> {
+    info!("添加地址标记: {} -> {:?}", address, request.tags);
+    let clock = state.clock.as_ref();
+    validate_address(&address, clock)?;
+
+    let tags = request
+        .tags
+        .iter()
+        .map(|tag| AddressTagStorage::normalize_tag(tag))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| ApiError::bad_request("标记无效 / Invalid tag", clock))?;
+    let note = request.note.map(|note| note.trim().to_string()).filter(|note| !note.is_empty());
+    if tags.is_empty() && note.is_none() {
+        return Err(ApiError::bad_request("tags 和 note 至少指定一个 / Either tags or note is required", clock));
+    }
+
+    let target = address.clone();
+    match state.db_manager.run_blocking(move |db| db.address_tag_storage().add_tags(&target, &tags, note)).await {
+        Ok(entry) => Ok(Json(ApiResponse::success_with_clock(
+            entry.into(),
+            "地址标记已保存 / Address tags saved".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("保存地址 {} 标记时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
+
+/// 移除地址的一个标记 / Remove a tag from an address
+#[utoipa::path(
+    delete,
+    path = "/api/v1/address/{address}/tags/{tag}",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("tag" = String, Path, description = "要移除的标记 / Tag to remove", example = "case-42")
+    ),
+    responses(
+        (status = 200, description = "标记已移除 / Tag removed", body = ApiResponse<AddressTagsResponse>),
+        (status = 400, description = "地址格式无效或标记无效 / Invalid address or tag", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "标记无效 / Invalid tag", "data": {"error": "标记无效 / Invalid tag"}, "timestamp": 1704067200})),
+        (status = 404, description = "地址没有该标记 / Address does not have the tag", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址没有该标记 / Address does not have the tag", "data": {"error": "地址没有该标记 / Address does not have the tag"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Labels"
+)]
+pub async fn remove_address_tag(
+    State(state): State<Arc<AppState>>,
+    Path((address, tag)): Path<(String, String)>,
+) -> ApiResult<AddressTagsResponse> {
+    info!("移除地址标记: {} -> {}", address, tag);
+    let clock = state.clock.as_ref();
+    validate_address(&address, clock)?;
+    let tag = AddressTagStorage::normalize_tag(&tag).ok_or_else(|| ApiError::bad_request("标记无效 / Invalid tag", clock))?;
+
+    let target = address.clone();
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let storage = db.address_tag_storage();
+            if !storage.remove_tag(&target, &tag)? {
+                return Ok(None);
+            }
+            let entry = storage.get_tags(&target)?.unwrap_or_else(|| AddressTags { address: target, ..Default::default() });
+            Ok(Some(entry))
+        })
+        .await;
+    match result {
+        Ok(Some(entry)) => Ok(Json(ApiResponse::success_with_clock(
+            entry.into(),
+            "地址标记已移除 / Address tag removed".to_string(),
+            clock,
+        ))),
+        Ok(None) => Err(ApiError::not_found("地址没有该标记 / Address does not have the tag", clock)),
+        Err(e) => {
+            error!("移除地址 {} 标记时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
+
+/// 查询可疑模式告警 / List suspicious-pattern alerts
+#[utoipa::path(
+    get,
+    path = "/api/v1/alerts",
+    params(
+        ("rule" = Option<String>, Query, description = "规则 fan_out/peel_chain/round_trip/structuring / Rule", example = "round_trip"),
+        ("address" = Option<String>, Query, description = "只返回涉及该地址的告警 / Only alerts involving this address",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量，默认100，最大1000 / Number of alerts, default 100, max 1000", example = 100)
+    ),
+    responses(
+        (status = 200, description = "查询成功（最新的在前）/ Query successful (newest first)", body = ApiResponse<AlertsResponse>),
+        (status = 400, description = "规则或地址无效 / Invalid rule or address", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "规则无效 / Invalid rule", "data": {"error": "规则无效 / Invalid rule"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Alerts"
+)]
+pub async fn get_alerts(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AlertsParams>,
+) -> ApiResult<AlertsResponse> {
+    let clock = state.clock.as_ref();
+    let rule = match params.rule.as_deref() {
+        None => None,
+        Some(value) => Some(AlertRule::parse(value).ok_or_else(|| ApiError::bad_request("规则无效 / Invalid rule", clock))?),
+    };
+    if let Some(address) = &params.address {
+        validate_address(address, clock)?;
+    }
+    let limit = params.limit.unwrap_or(100).clamp(1, 1000);
+
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let alerts = db.alert_storage().list_alerts(rule, params.address.as_deref(), limit)?;
+            Ok(AlertsResponse {
+                rules_enabled: db.rules_engine().is_some(),
+                count: alerts.len(),
+                alerts: alerts.into_iter().map(Into::into).collect(),
+            })
+        })
+        .await;
+    match result {
+        Ok(response_data) => {
+            let message = format!("成功获取告警 / Successfully retrieved alerts: {}", response_data.count);
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("查询告警时数据库错误: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 登记充值地址 / Register a deposit address
+///
+/// 之后转入该地址的 SOL 和代币转账记为该用户的充值；地址已登记时改为新用户。
+#[utoipa::path(
+    post,
+    path = "/api/v1/deposits/addresses",
+    request_body = DepositAddressRequest,
+    responses(
+        (status = 200, description = "登记成功 / Address registered", body = ApiResponse<DepositAddressResponse>),
+        (status = 400, description = "地址或用户ID无效 / Invalid address or user ID", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "用户ID无效 / Invalid user ID", "data": {"error": "用户ID无效 / Invalid user ID"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Deposits"
+)]
+pub async fn register_deposit_address(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<DepositAddressRequest>,
+) -> ApiResult<DepositAddressResponse> {
+    info!("登记充值地址: {} -> {}", request.address, request.user_id);
+    let clock = state.clock.as_ref();
+    validate_address(&request.address, clock)?;
+    if !DepositStorage::is_valid_user_id(&request.user_id) {
+        return Err(ApiError::bad_request("用户ID无效 / Invalid user ID", clock));
+    }
+
+    let result = state
+        .db_manager
+        .run_blocking(move |db| db.deposit_storage().register_address(&request.address, &request.user_id))
+        .await;
+    match result {
+        Ok(entry) => Ok(Json(ApiResponse::success_with_clock(
+            entry.into(),
+            "充值地址已登记 / Deposit address registered".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("登记充值地址时数据库错误: {}", e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
+
+/// 取消登记充值地址 / Unregister a deposit address
+#[utoipa::path(
+    delete,
+    path = "/api/v1/deposits/addresses/{address}",
+    params(
+        ("address" = String, Path, description = "充值地址 / Deposit address", example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")
+    ),
+    responses(
+        (status = 200, description = "已取消登记，已有充值记录保留 / Unregistered, existing deposits are kept", body = ApiResponse<String>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "无效的地址格式 / Invalid address format", "data": {"error": "无效的地址格式 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 404, description = "地址未登记 / Address not registered", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址未登记 / Address not registered", "data": {"error": "地址未登记 / Address not registered"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Deposits"
+)]
+pub async fn unregister_deposit_address(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<String> {
+    info!("取消登记充值地址: {}", address);
+    let clock = state.clock.as_ref();
+    validate_address(&address, clock)?;
+
+    let target = address.clone();
+    match state.db_manager.run_blocking(move |db| db.deposit_storage().unregister_address(&target)).await {
+        Ok(true) => Ok(Json(ApiResponse::success_with_clock(
+            address,
+            "充值地址已取消登记 / Deposit address unregistered".to_string(),
+            clock,
+        ))),
+        Ok(false) => Err(ApiError::not_found("地址未登记 / Address not registered", clock)),
+        Err(e) => {
+            error!("取消登记充值地址 {} 时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
+
+/// 查询用户的充值 / Query deposits of a user
+#[utoipa::path(
+    get,
+    path = "/api/v1/deposits/user/{user_id}",
+    params(
+        ("user_id" = String, Path, description = "用户ID / User ID", example = "user-1001"),
+        ("status" = Option<String>, Query, description = "状态 processed/confirmed/finalized/orphaned / Status", example = "finalized"),
+        ("limit" = Option<usize>, Query, description = "返回数量，默认100，最大1000 / Number of deposits, default 100, max 1000", example = 100)
+    ),
+    responses(
+        (status = 200, description = "查询成功（最新的在前）/ Query successful (newest first)", body = ApiResponse<UserDepositsResponse>),
+        (status = 400, description = "用户ID或状态无效 / Invalid user ID or status", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "状态无效 / Invalid status", "data": {"error": "状态无效 / Invalid status"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Deposits"
+)]
+pub async fn get_user_deposits(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<String>,
+    Query(params): Query<DepositsParams>,
+) -> ApiResult<UserDepositsResponse> {
+    let clock = state.clock.as_ref();
+    if !DepositStorage::is_valid_user_id(&user_id) {
+        return Err(ApiError::bad_request("用户ID无效 / Invalid user ID", clock));
+    }
+    let status = match params.status.as_deref() {
+        None => None,
+        Some(value) => Some(FinalityStatus::parse(value).ok_or_else(|| ApiError::bad_request("状态无效 / Invalid status", clock))?),
+    };
+    let limit = params.limit.unwrap_or(100).clamp(1, 1000);
+
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let storage = db.deposit_storage();
+            let deposits = storage.user_deposits(&user_id, status, limit)?;
+            Ok(UserDepositsResponse {
+                addresses: storage.user_addresses(&user_id)?,
+                user_id,
+                count: deposits.len(),
+                deposits: deposits.into_iter().map(Into::into).collect(),
+            })
+        })
+        .await;
+    match result {
+        Ok(response_data) => {
+            let message = format!("成功获取充值 / Successfully retrieved deposits: {}", response_data.count);
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("查询用户充值时数据库错误: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 登记预期提现 / Register an expected withdrawal
+///
+/// 按签名登记时，若交易已写入则立即标记为 observed。
+#[utoipa::path(
+    post,
+    path = "/api/v1/withdrawals",
+    request_body = WithdrawalRequest,
+    responses(
+        (status = 200, description = "登记成功 / Withdrawal registered", body = ApiResponse<WithdrawalStatusResponse>),
+        (status = 400, description = "匹配条件无效 / Invalid match criteria", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "需要 signature，或 from、to 和 amount / signature, or from, to and amount are required", "data": {"error": "需要 signature，或 from、to 和 amount / signature, or from, to and amount are required"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Withdrawals"
+)]
+pub async fn register_withdrawal(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<WithdrawalRequest>,
+) -> ApiResult<WithdrawalStatusResponse> {
+    let clock = state.clock.as_ref();
+    let expected = match (request.signature, request.from, request.to, request.amount) {
+        (Some(signature), None, None, None) => {
+            if signature.len() < 32 {
+                return Err(ApiError::bad_request("签名格式无效 / Invalid signature format", clock));
+            }
+            ExpectedWithdrawal::Signature { signature }
+        }
+        (None, Some(from), Some(to), Some(amount)) => {
+            validate_address(&from, clock)?;
+            validate_address(&to, clock)?;
+            if let Some(mint) = &request.mint {
+                validate_address(mint, clock)?;
+            }
+            ExpectedWithdrawal::Transfer { from, to, amount, mint: request.mint }
+        }
+        _ => {
+            return Err(ApiError::bad_request(
+                "需要 signature，或 from、to 和 amount / signature, or from, to and amount are required",
+                clock,
             ))
         }
+    };
+    info!("登记预期提现: {:?}", expected);
+
+    let reference = request.reference;
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let storage = db.withdrawal_storage();
+            let withdrawal = storage.register(reference, expected)?;
+            // 交易可能在登记前已写入
+            if let ExpectedWithdrawal::Signature { signature } = &withdrawal.expected {
+                if let Some(data) = db.signature_storage().get_signature_data(signature)? {
+                    storage.record_transfers(&data)?;
+                }
+            }
+            storage.get(&withdrawal.id).map(|observed| observed.unwrap_or(withdrawal))
+        })
+        .await;
+    match result {
+        Ok(withdrawal) => Ok(Json(ApiResponse::success_with_clock(
+            withdrawal.into(),
+            "提现已登记 / Withdrawal registered".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("登记提现时数据库错误: {}", e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
+
+/// 查询提现状态 / Get withdrawal status
+#[utoipa::path(
+    get,
+    path = "/api/v1/withdrawals/{id}/status",
+    params(
+        ("id" = String, Path, description = "提现ID / Withdrawal ID")
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<WithdrawalStatusResponse>),
+        (status = 404, description = "提现不存在 / Withdrawal not found", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "提现不存在 / Withdrawal not found", "data": {"error": "提现不存在 / Withdrawal not found"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Withdrawals"
+)]
+pub async fn get_withdrawal_status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> ApiResult<WithdrawalStatusResponse> {
+    let clock = state.clock.as_ref();
+    let target = id.clone();
+    match state.db_manager.run_blocking(move |db| db.withdrawal_storage().get(&target)).await {
+        Ok(Some(withdrawal)) => {
+            let message = format!("提现状态 / Withdrawal status: {}", withdrawal.status.as_str());
+            Ok(Json(ApiResponse::success_with_clock(withdrawal.into(), message, clock)))
+        }
+        Ok(None) => Err(ApiError::not_found("提现不存在 / Withdrawal not found", clock)),
+        Err(e) => {
+            error!("查询提现 {} 时数据库错误: {}", id, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 创建 Webhook 订阅 / Create a webhook subscription
+///
+/// 匹配的转账和告警以 JSON POST 到回调地址，请求头 `X-Ledger-Signature` 为
+/// `sha256=<HMAC-SHA256(secret, body)>`；非 2xx 响应按指数退避重试。
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks",
+    request_body = WebhookSubscriptionRequest,
+    responses(
+        (status = 200, description = "订阅已创建 / Subscription created", body = ApiResponse<WebhookSubscriptionResponse>),
+        (status = 400, description = "地址、方向、回调地址或密钥无效 / Invalid address, direction, callback URL or secret", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "回调地址必须是 http(s) URL / callback_url must be an http(s) URL", "data": {"error": "回调地址必须是 http(s) URL / callback_url must be an http(s) URL"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Webhooks"
+)]
+pub async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<WebhookSubscriptionRequest>,
+) -> ApiResult<WebhookSubscriptionResponse> {
+    info!("创建 Webhook 订阅: {} -> {}", request.address, request.callback_url);
+    let clock = state.clock.as_ref();
+    validate_address(&request.address, clock)?;
+    let direction = match request.direction.as_deref() {
+        None => None,
+        Some(value) => Some(TransferDirection::parse(value).ok_or_else(|| {
+            ApiError::bad_request("direction 只能是 in 或 out / direction must be in or out", clock)
+        })?),
+    };
+    let callback_url = request.callback_url.trim().to_string();
+    if !(callback_url.starts_with("http://") || callback_url.starts_with("https://")) {
+        return Err(ApiError::bad_request("回调地址必须是 http(s) URL / callback_url must be an http(s) URL", clock));
+    }
+    if request.secret.is_empty() {
+        return Err(ApiError::bad_request("secret 不能为空 / secret must not be empty", clock));
+    }
+
+    let min_amount = request.min_amount.unwrap_or(0);
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            db.webhook_storage().subscribe(&request.address, min_amount, direction, &callback_url, &request.secret)
+        })
+        .await;
+    match result {
+        Ok(subscription) => Ok(Json(ApiResponse::success_with_clock(
+            subscription.into(),
+            "Webhook 订阅已创建 / Webhook subscription created".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("创建 Webhook 订阅时数据库错误: {}", e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
     }
-} 
\ No newline at end of file
+}
+
+/// 列出 Webhook 订阅 / List webhook subscriptions
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks",
+    params(
+        ("address" = Option<String>, Query, description = "只返回该地址的订阅 / Only subscriptions for this address",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<WebhookSubscriptionsResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "无效的地址格式 / Invalid address format", "data": {"error": "无效的地址格式 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Webhooks"
+)]
+pub async fn list_webhooks(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<WebhooksParams>,
+) -> ApiResult<WebhookSubscriptionsResponse> {
+    let clock = state.clock.as_ref();
+    if let Some(address) = &params.address {
+        validate_address(address, clock)?;
+    }
+
+    let result = state
+        .db_manager
+        .run_blocking(move |db| db.webhook_storage().list_subscriptions(params.address.as_deref()))
+        .await;
+    match result {
+        Ok(subscriptions) => {
+            let response_data = WebhookSubscriptionsResponse {
+                count: subscriptions.len(),
+                subscriptions: subscriptions.into_iter().map(Into::into).collect(),
+            };
+            let message = format!("成功获取 Webhook 订阅 / Successfully retrieved webhook subscriptions: {}", response_data.count);
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("查询 Webhook 订阅时数据库错误: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 删除 Webhook 订阅 / Delete a webhook subscription
+#[utoipa::path(
+    delete,
+    path = "/api/v1/webhooks/{id}",
+    params(
+        ("id" = String, Path, description = "订阅ID / Subscription ID", example = "0f8e2d4c-1b7a-4e36-9c55-2a1f0b3d6e71")
+    ),
+    responses(
+        (status = 200, description = "订阅已删除 / Subscription deleted", body = ApiResponse<String>),
+        (status = 404, description = "订阅不存在 / Subscription not found", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "订阅不存在 / Subscription not found", "data": {"error": "订阅不存在 / Subscription not found"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Webhooks"
+)]
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> ApiResult<String> {
+    info!("删除 Webhook 订阅: {}", id);
+    let clock = state.clock.as_ref();
+
+    let target = id.clone();
+    match state.db_manager.run_blocking(move |db| db.webhook_storage().unsubscribe(&target)).await {
+        Ok(true) => Ok(Json(ApiResponse::success_with_clock(
+            id,
+            "Webhook 订阅已删除 / Webhook subscription deleted".to_string(),
+            clock,
+        ))),
+        Ok(false) => Err(ApiError::not_found("订阅不存在 / Subscription not found", clock)),
+        Err(e) => {
+            error!("删除 Webhook 订阅 {} 时数据库错误: {}", id, e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
+
+/// 订阅地址的实时转账（SSE）/ Stream new transfers of an address (Server-Sent Events)
+///
+/// 每笔转账一个 `transfer` 事件，`id` 为交易签名，`data` 为 JSON；
+/// 客户端处理过慢时跳过的事件数以 `lagged` 事件通知。
+#[utoipa::path(
+    get,
+    path = "/api/v1/stream/address/{address}",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")
+    ),
+    responses(
+        (status = 200, description = "事件流 / Event stream", content_type = "text/event-stream", body = String,
+            example = json!("event: transfer\nid: 5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW\ndata: {\"signature\":\"5VERv8NM...\",\"slot\":245000000,\"timestamp\":1704067200,\"from\":\"9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM\",\"to\":\"5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9\",\"amount\":1000000000,\"mint\":null,\"decimals\":9}\n\n")),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "无效的地址格式 / Invalid address format", "data": {"error": "无效的地址格式 / Invalid address format"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn stream_address_transfers(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    validate_address(&address, state.clock.as_ref())?;
+    info!("开始推送地址 {} 的实时转账", address);
+
+    let receiver = state.db_manager.transfer_feed().subscribe();
+    let stream = futures::stream::unfold((receiver, address), |(mut receiver, address)| async move {
+        loop {
+            let event = match receiver.recv().await {
+                Ok(transfer) if transfer.involves(&address) => transfer_event(&transfer),
+                Ok(_) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    Event::default().event("lagged").data(skipped.to_string())
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+            };
+            return Some((Ok(event), (receiver, address)));
+        }
+    });
+    Ok(Sse::new(stream.boxed()).keep_alive(KeepAlive::default()))
+}
+
+fn transfer_event(transfer: &TransferEvent) -> Event {
+    let data = serde_json::to_string(transfer).unwrap_or_default();
+    Event::default().event("transfer").id(transfer.signature.as_str()).data(data)
+}
+
+/// 获取数据摄取状态 / Get ingest status
+#[utoipa::path(
+    get,
+    path = "/api/v1/status",
+    responses(
+        (status = 200, description = "摄取状态 / Ingest status", body = ApiResponse<IngestStatusResponse>)
+    ),
+    tag = "Health"
+)]
+pub async fn get_ingest_status(
+    State(state): State<Arc<AppState>>,
+) -> Json<ApiResponse<IngestStatusResponse>> {
+    let status: IngestStatusResponse = state.degradation.status().into();
+    Json(ApiResponse::success_with_clock(
+        status,
+        "成功获取摄取状态 / Successfully retrieved ingest status".to_string(),
+        state.clock.as_ref(),
+    ))
+}
+
+/// 获取槽位汇总（过载降级期间保存）/ Get slot summary (stored during overload degradation)
+#[utoipa::path(
+    get,
+    path = "/api/v1/slot/{slot}/summary",
+    params(
+        ("slot" = u64, Path, description = "槽位 / Slot", example = 245_000_000)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<SlotSummaryResponse>),
+        (status = 404, description = "该槽位没有汇总数据 / No summary stored for this slot", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "该槽位没有汇总数据 / No summary found for this slot", "data": {"error": "该槽位没有汇总数据 / No summary found for this slot"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Statistics"
+)]
+pub async fn get_slot_summary(
+    State(state): State<Arc<AppState>>,
+    Path(slot): Path<u64>,
+) -> ApiResult<SlotSummaryResponse> {
+    info!("查询槽位汇总: {}", slot);
+    let clock = state.clock.as_ref();
+
+    match state.db_manager.run_blocking(move |db| db.slot_summary_storage().get_summary(slot)).await {
+        Ok(Some(summary)) => Ok(Json(ApiResponse::success_with_clock(
+            summary.into(),
+            "成功获取槽位汇总 / Successfully retrieved slot summary".to_string(),
+            clock,
+        ))),
+        Ok(None) => Err(ApiError::not_found(
+            "该槽位没有汇总数据 / No summary found for this slot",
+            clock,
+        )),
+        Err(e) => {
+            error!("查询槽位 {} 汇总时数据库错误: {}", slot, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 获取槽位内采集到的交易 / Get transactions captured for a slot
+#[utoipa::path(
+    get,
+    path = "/api/v1/slot/{slot}/transactions",
+    params(
+        ("slot" = u64, Path, description = "槽位 / Slot", example = 245_000_000),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned transactions, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of transactions to skip for pagination, default 0", example = 0),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor，优先于 offset / next_cursor from the previous page, takes precedence over offset", example = "100")
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有采集到交易时返回空列表）/ Query successful (empty list when nothing was captured)", body = ApiResponse<SlotTransactionsResponse>),
+        (status = 400, description = "游标无效 / Invalid cursor", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "cursor 无效 / Invalid cursor", "data": {"error": "cursor 无效 / Invalid cursor"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Statistics"
+)]
+pub async fn get_slot_transactions(
+    State(state): State<Arc<AppState>>,
+    Path(slot): Path<u64>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<SlotTransactionsResponse> {
+    info!("查询槽位交易: {}", slot);
+    let clock = state.clock.as_ref();
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.start(clock)?;
+
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let entries = db.slot_index_storage().get_slot_transactions(slot)?;
+            let response = SlotTransactionsResponse {
+                slot,
+                finality: FinalityStatus::Unknown.as_str().to_string(),
+                page: PaginatedResponse::from_offset(entries, offset, limit).map(Into::into),
+            };
+            with_finality(db, response)
+        })
+        .await;
+    match result {
+        Ok(response_data) => {
+            let message = format!(
+                "成功获取槽位交易 / Successfully retrieved slot transactions: {} total",
+                response_data.page.total.unwrap_or_default()
+            );
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("查询槽位 {} 交易时数据库错误: {}", slot, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询调用程序的交易 / Query transactions that invoked a program
+#[utoipa::path(
+    get,
+    path = "/api/v1/program/{program_id}/transactions",
+    params(
+        ("program_id" = String, Path, description = "程序 ID（base58格式）/ Program ID (base58 format)",
+            example = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned transactions, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of transactions to skip for pagination, default 0", example = 0),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor，优先于 offset / next_cursor from the previous page, takes precedence over offset", example = "100")
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有采集到调用时返回空列表）/ Query successful (empty list when no invocation was captured)", body = ApiResponse<ProgramTransactionsResponse>),
+        (status = 400, description = "程序 ID 或游标无效 / Invalid program ID format or cursor", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Transactions"
+)]
+pub async fn get_program_transactions(
+    State(state): State<Arc<AppState>>,
+    Path(program_id): Path<String>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<ProgramTransactionsResponse> {
+    info!("查询程序调用交易: {}", program_id);
+    let clock = state.clock.as_ref();
+    validate_address(&program_id, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.start(clock)?;
+
+    let lookup = program_id.clone();
+    // 多取一条判断是否还有下一页
+    let result = state
+        .db_manager
+        .run_blocking(move |db| db.program_index_storage().get_program_transactions(&lookup, offset, limit + 1))
+        .await;
+    match result {
+        Ok(entries) => {
+            let response_data = ProgramTransactionsResponse {
+                program_id,
+                page: PaginatedResponse::from_page(entries, offset, limit, None).map(Into::into),
+            };
+            let message = format!(
+                "成功获取程序调用交易 / Successfully retrieved program transactions: {} returned",
+                response_data.page.items.len()
+            );
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("查询程序 {} 调用交易时数据库错误: {}", program_id, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询地址余额历史 / Query address balance history
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/balance-history",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned points, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of points to skip for pagination, default 0", example = 0)
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有历史时返回空列表）/ Query successful (empty list when there is no history)", body = ApiResponse<BalanceHistoryResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_balance_history(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<QueryParams>,
+) -> ApiResult<BalanceHistoryResponse> {
+    info!("查询地址余额历史: {}", address);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.offset.unwrap_or(0);
+
+    let lookup = address.clone();
+    match state.db_manager.run_blocking(move |db| db.balance_storage().get_balance_history(&lookup)).await {
+        Ok(Some(history)) => {
+            let total = history.points.len();
+            let points = history.points.into_iter().skip(offset).take(limit).map(Into::into).collect();
+            Ok(Json(ApiResponse::success_with_clock(
+                BalanceHistoryResponse {
+                    address,
+                    total_points: total,
+                    points,
+                },
+                format!("成功获取余额历史 / Successfully retrieved balance history: {} points", total),
+                clock,
+            )))
+        }
+        Ok(None) => Ok(Json(ApiResponse::success_with_clock(
+            BalanceHistoryResponse {
+                address,
+                total_points: 0,
+                points: vec![],
+            },
+            "该地址没有余额历史 / No balance history found for this address".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("查询地址 {} 余额历史时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询地址的质押操作 / Query stake events of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/stake-events",
+    params(
+        ("address" = String, Path, description = "Solana地址（质押账户、授权者、投票账户或接收方）/ Solana address (stake account, authority, vote account or recipient)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned events, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of events to skip for pagination, default 0", example = 0),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor，优先于 offset / next_cursor from the previous page, takes precedence over offset", example = "100")
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有质押操作时返回空列表）/ Query successful (empty list when there are no stake events)", body = ApiResponse<StakeEventsResponse>),
+        (status = 400, description = "地址格式或游标无效 / Invalid address format or cursor", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_stake_events(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<StakeEventsResponse> {
+    info!("查询地址质押操作: {}", address);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.start(clock)?;
+
+    let lookup = address.clone();
+    match state.db_manager.run_blocking(move |db| db.stake_event_storage().get_events(&lookup)).await {
+        Ok(events) => {
+            let total = events.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                StakeEventsResponse {
+                    address,
+                    page: PaginatedResponse::from_offset(events, offset, limit).map(Into::into),
+                },
+                format!("成功获取质押操作 / Successfully retrieved stake events: {} events", total),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} 质押操作时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询地址的 NFT 转账 / Query NFT transfers of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/nft-transfers",
+    params(
+        ("address" = String, Path, description = "钱包地址（发送方或接收方）/ Wallet address (sender or receiver)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned transfers, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of transfers to skip for pagination, default 0", example = 0),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor，优先于 offset / next_cursor from the previous page, takes precedence over offset", example = "100")
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有 NFT 转账时返回空列表）/ Query successful (empty list when there are no NFT transfers)", body = ApiResponse<NftTransfersResponse>),
+        (status = 400, description = "地址格式或游标无效 / Invalid address format or cursor", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_nft_transfers(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<NftTransfersResponse> {
+    info!("查询地址 NFT 转账: {}", address);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.start(clock)?;
+
+    let lookup = address.clone();
+    match state.db_manager.run_blocking(move |db| db.nft_transfer_storage().get_transfers(&lookup)).await {
+        Ok(transfers) => {
+            let total = transfers.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                NftTransfersResponse {
+                    address,
+                    page: PaginatedResponse::from_offset(transfers, offset, limit).map(Into::into),
+                },
+                format!("成功获取 NFT 转账 / Successfully retrieved NFT transfers: {} transfers", total),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} NFT 转账时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询账户更新 / Query account updates
+///
+/// 需要在 `[accounts]` 中启用跟踪并配置账户。
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{pubkey}/updates",
+    params(
+        ("pubkey" = String, Path, description = "账户地址（base58格式）/ Account address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned updates, default 100, max 1000", example = 100)
+    ),
+    responses(
+        (status = 200, description = "查询成功（账户未跟踪时返回空列表）/ Query successful (empty list when the account is not tracked)", body = ApiResponse<AccountUpdatesResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_account_updates(
+    State(state): State<Arc<AppState>>,
+    Path(pubkey): Path<String>,
+    Query(params): Query<QueryParams>,
+) -> ApiResult<AccountUpdatesResponse> {
+    info!("查询账户更新: {}", pubkey);
+    let clock = state.clock.as_ref();
+    validate_address(&pubkey, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let lookup = pubkey.clone();
+    match state.db_manager.run_blocking(move |db| db.account_update_storage().get_updates(&lookup, limit)).await {
+        Ok(updates) => {
+            let count = updates.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                AccountUpdatesResponse {
+                    pubkey,
+                    count,
+                    updates: updates.into_iter().map(Into::into).collect(),
+                },
+                format!("成功获取账户更新 / Successfully retrieved account updates: {}", count),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询账户 {} 更新时数据库错误: {}", pubkey, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询地址的兑换记录 / Query swaps of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/swaps",
+    params(
+        ("address" = String, Path, description = "发起兑换的所有者地址（base58格式）/ Owner address that performed the swaps (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned swaps, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of swaps to skip for pagination, default 0", example = 0),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor，优先于 offset / next_cursor from the previous page, takes precedence over offset", example = "100")
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有兑换记录时返回空列表）/ Query successful (empty list when there are no swaps)", body = ApiResponse<SwapsResponse>),
+        (status = 400, description = "地址格式或游标无效 / Invalid address format or cursor", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_swaps(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<SwapsResponse> {
+    info!("查询地址兑换记录: {}", address);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.start(clock)?;
+
+    let lookup = address.clone();
+    match state.db_manager.run_blocking(move |db| db.swap_storage().get_swaps(&lookup)).await {
+        Ok(swaps) => {
+            let total = swaps.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                SwapsResponse {
+                    address,
+                    page: PaginatedResponse::from_offset(swaps, offset, limit).map(Into::into),
+                },
+                format!("成功获取兑换记录 / Successfully retrieved swaps: {} swaps", total),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} 兑换记录时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询地址的代币余额历史 / Query token balance history of an address
+///
+/// 每个历史点是该交易涉及的所有者代币账户的执行后余额之和，不包含交易未涉及的其他代币账户。
+/// Each point is the post-transaction balance summed over the owner's token accounts touched by that transaction; accounts the transaction did not touch are not included.
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/token-balances",
+    params(
+        ("address" = String, Path, description = "代币账户所有者地址（base58格式）/ Token account owner address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("mint" = Option<String>, Query, description = "只返回指定代币 / Only return the given token mint",
+            example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+        ("limit" = Option<usize>, Query, description = "每个代币返回的历史点数量，默认100，最大1000 / Number of points per token, default 100, max 1000", example = 100)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<TokenBalancesResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_token_balances(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<TokenBalanceQueryParams>,
+) -> ApiResult<TokenBalancesResponse> {
+    info!("查询地址代币余额历史: {}", address);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let lookup = address.clone();
+    let mint = params.mint.clone();
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let storage = db.token_balance_storage();
+            match &mint {
+                Some(mint) => storage.get_token_balance(&lookup, mint).map(|history| history.into_iter().collect()),
+                None => storage.get_token_balances(&lookup),
+            }
+        })
+        .await;
+
+    match result {
+        Ok(histories) => {
+            let tokens: Vec<TokenBalanceHistoryResponse> = histories
+                .into_iter()
+                .map(|history| TokenBalanceHistoryResponse::from_history(history, limit))
+                .collect();
+            let count = tokens.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                TokenBalancesResponse { address, tokens },
+                format!("成功获取代币余额历史 / Successfully retrieved token balances: {} tokens", count),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} 代币余额时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询地址的交易对手 / Query address counterparties
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/counterparties",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned counterparties, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of counterparties to skip for pagination, default 0", example = 0),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor，优先于 offset / next_cursor from the previous page, takes precedence over offset", example = "100")
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<CounterpartiesResponse>),
+        (status = 400, description = "地址格式或游标无效 / Invalid address format or cursor", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_counterparties(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<CounterpartiesResponse> {
+    info!("查询地址交易对手: {}", address);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.start(clock)?;
+
+    let lookup = address.clone();
+    match state.db_manager.run_blocking(move |db| db.counterparty_storage().get_counterparties(&lookup)).await {
+        Ok(counterparties) => {
+            let total = counterparties.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                CounterpartiesResponse {
+                    address,
+                    page: PaginatedResponse::from_offset(counterparties, offset, limit).map(Into::into),
+                },
+                format!("成功获取交易对手 / Successfully retrieved counterparties: {} total", total),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} 交易对手时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询为地址代付手续费的支付者 / Query fee payers that paid for an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/fee-payers",
+    params(
+        ("address" = String, Path, description = "Solana地址（被代付的签名者）/ Solana address (co-signer whose fees were paid)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned addresses, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of addresses to skip for pagination, default 0", example = 0)
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有代付关系时返回空列表）/ Query successful (empty list when no other address paid its fees)", body = ApiResponse<FeeRelationsResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_fee_payers(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<QueryParams>,
+) -> ApiResult<FeeRelationsResponse> {
+    info!("查询地址的手续费支付者: {}", address);
+    query_fee_relations(state, address, params, false).await
+}
+
+/// 查询地址代付过手续费的账户 / Query accounts whose fees were paid by an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/funded-accounts",
+    params(
+        ("address" = String, Path, description = "Solana地址（手续费支付者）/ Solana address (fee payer)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned addresses, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of addresses to skip for pagination, default 0", example = 0)
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有代付关系时返回空列表）/ Query successful (empty list when the address paid for no other signer)", body = ApiResponse<FeeRelationsResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_funded_accounts(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<QueryParams>,
+) -> ApiResult<FeeRelationsResponse> {
+    info!("查询地址代付的账户: {}", address);
+    query_fee_relations(state, address, params, true).await
+}
+
+/// 查询手续费代付关系，`funded` 为 true 时查询地址代付的账户，否则查询为地址付费的支付者
+async fn query_fee_relations(
+    state: Arc<AppState>,
+    address: String,
+    params: QueryParams,
+    funded: bool,
+) -> ApiResult<FeeRelationsResponse> {
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.offset.unwrap_or(0);
+
+    let lookup = address.clone();
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            if funded {
+                db.fee_payer_storage().get_funded_accounts(&lookup)
+            } else {
+                db.fee_payer_storage().get_fee_payers(&lookup)
+            }
+        })
+        .await;
+    match result {
+        Ok(relations) => {
+            let total = relations.len();
+            let relations = relations.into_iter().skip(offset).take(limit).map(Into::into).collect();
+            Ok(Json(ApiResponse::success_with_clock(
+                FeeRelationsResponse {
+                    address,
+                    total_relations: total,
+                    relations,
+                },
+                format!("成功获取手续费代付关系 / Successfully retrieved fee payer relations: {} total", total),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} 手续费代付关系时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询地址活跃度时间线 / Query address activity over time
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/activity",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("granularity" = Option<String>, Query, description = "时间桶粒度：hour 或 day，默认day / Bucket granularity: hour or day, default day", example = "day"),
+        ("start_time" = Option<u64>, Query, description = "起始时间戳（含所在的桶）/ Start timestamp (its bucket is included)", example = 1704067200),
+        ("end_time" = Option<u64>, Query, description = "结束时间戳（含）/ End timestamp (inclusive)", example = 1706745600),
+        ("limit" = Option<usize>, Query, description = "返回最近的桶数，默认100，最大1000 / Number of most recent buckets, default 100, max 1000", example = 100)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<ActivityResponse>),
+        (status = 400, description = "地址格式或粒度无效 / Invalid address format or granularity", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "granularity 只能为 hour 或 day / granularity must be hour or day", "data": {"error": "granularity 只能为 hour 或 day / granularity must be hour or day"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_activity(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<ActivityParams>,
+) -> ApiResult<ActivityResponse> {
+    info!("查询地址活跃度时间线: {}", address);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let granularity = match params.granularity.as_deref() {
+        None => ActivityGranularity::Day,
+        Some(value) => ActivityGranularity::parse(value).ok_or_else(|| {
+            ApiError::bad_request("granularity 只能为 hour 或 day / granularity must be hour or day", clock)
+        })?,
+    };
+    let limit = params.limit.unwrap_or(100).min(MAX_ACTIVITY_BUCKETS);
+
+    let lookup = address.clone();
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            db.activity_storage()
+                .get_activity(&lookup, granularity, params.start_time, params.end_time, limit)
+        })
+        .await;
+
+    match result {
+        Ok(buckets) => {
+            let count = buckets.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                ActivityResponse {
+                    address,
+                    granularity: granularity.as_str().to_string(),
+                    buckets: buckets.into_iter().map(Into::into).collect(),
+                },
+                format!("成功获取活跃度时间线 / Successfully retrieved activity: {} buckets", count),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} 活跃度时间线时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询地址每日汇总 / Query daily per-address summaries
+#[utoipa::path(
+    get,
+    path = "/api/v1/address/{address}/daily",
+    params(
+        ("address" = String, Path, description = "Solana地址（base58格式）/ Solana address (base58 format)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("start_time" = Option<u64>, Query, description = "起始时间戳（含所在的日期）/ Start timestamp (its day is included)", example = 1704067200),
+        ("end_time" = Option<u64>, Query, description = "结束时间戳（含所在的日期）/ End timestamp (its day is included)", example = 1706745600),
+        ("limit" = Option<usize>, Query, description = "返回最近的天数，默认365，最大3650 / Number of most recent days, default 365, max 3650", example = 365)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<AddressDailySummaryResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_address_daily_summary(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<DailySummaryParams>,
+) -> ApiResult<AddressDailySummaryResponse> {
+    info!("查询地址每日汇总: {}", address);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&address, clock)?;
+
+    let limit = params.limit.unwrap_or(365).min(MAX_DAILY_SUMMARY_DAYS);
+    let start_date = params.start_time.map(DailyAddressRollup::date_of);
+    let end_date = params.end_time.map(DailyAddressRollup::date_of);
+
+    let lookup = address.clone();
+    let result = state
+        .db_manager
+        .run_blocking(move |db| db.get_address_daily_summaries(&lookup, start_date.as_deref(), end_date.as_deref()))
+        .await;
+
+    match result {
+        Ok(mut days) => {
+            // 只保留最近的 limit 天
+            let skip = days.len().saturating_sub(limit);
+            days.drain(..skip);
+            let count = days.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                AddressDailySummaryResponse {
+                    address,
+                    days: days.into_iter().map(Into::into).collect(),
+                },
+                format!("成功获取地址每日汇总 / Successfully retrieved daily summaries: {} days", count),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询地址 {} 每日汇总时数据库错误: {}", address, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 获取最大转账榜 / Get the largest transfers in a recent time window
+#[utoipa::path(
+    get,
+    path = "/api/v1/transfers/largest",
+    params(
+        ("period" = Option<String>, Query, description = "时间窗口，如 1h、24h、7d，默认24h，最长30d / Time window such as 1h, 24h, 7d; default 24h, max 30d",
+            example = "24h"),
+        ("mint" = Option<String>, Query, description = "代币 mint；不指定时返回 SOL 转账 / Token mint; SOL transfers are returned when omitted",
+            example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+        ("limit" = Option<usize>, Query, description = "返回数量，默认50，最大100 / Number of transfers, default 50, max 100", example = 50)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<LargestTransfersResponse>),
+        (status = 400, description = "时间窗口无效 / Invalid period", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "period 格式无效，应为 1h~720h 或 1d~30d / Invalid period, expected 1h-720h or 1d-30d", "data": {"error": "period 格式无效，应为 1h~720h 或 1d~30d / Invalid period, expected 1h-720h or 1d-30d"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_largest_transfers(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<LargestTransfersParams>,
+) -> ApiResult<LargestTransfersResponse> {
+    info!("查询最大转账榜: {:?}", params);
+    let clock = state.clock.as_ref();
+
+    let period = params.period.unwrap_or_else(|| "24h".to_string());
+    let seconds = parse_period(&period).ok_or_else(|| {
+        ApiError::bad_request("period 格式无效，应为 1h~720h 或 1d~30d / Invalid period, expected 1h-720h or 1d-30d", clock)
+    })?;
+    if let Some(mint) = &params.mint {
+        validate_address(mint, clock)?;
+    }
+    let limit = params.limit.unwrap_or(50).clamp(1, MAX_DAILY_LARGEST_TRANSFERS);
+
+    let now = clock.timestamp();
+    let mint = params.mint;
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let transfers: Vec<_> = db
+                .largest_transfer_storage()
+                .get_largest(mint.as_deref(), now - seconds, now, limit)?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            let count = transfers.len();
+            with_finality(db, with_labels(db, LargestTransfersResponse { period, mint, count, transfers })?)
+        })
+        .await;
+    match result {
+        Ok(response_data) => {
+            let message = format!("成功获取最大转账榜 / Successfully retrieved largest transfers: {} transfers", response_data.count);
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("查询最大转账榜时数据库错误: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 获取代币统计 / Get aggregate transfer statistics of a token mint
+#[utoipa::path(
+    get,
+    path = "/api/v1/mint/{mint}/stats",
+    params(
+        ("mint" = String, Path, description = "代币 mint / Token mint", example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<MintStatsResponse>),
+        (status = 400, description = "mint 格式无效 / Invalid mint format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 404, description = "没有该代币的转账 / No transfers recorded for this mint", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "没有该代币的转账 / No transfers recorded for this mint", "data": {"error": "没有该代币的转账 / No transfers recorded for this mint"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Statistics"
+)]
+pub async fn get_mint_stats(
+    State(state): State<Arc<AppState>>,
+    Path(mint): Path<String>,
+) -> ApiResult<MintStatsResponse> {
+    info!("查询代币统计: {}", mint);
+    let clock = state.clock.as_ref();
+    validate_address(&mint, clock)?;
+
+    let lookup = mint.clone();
+    match state.db_manager.run_blocking(move |db| db.mint_stats_storage().get_stats(&lookup)).await {
+        Ok(Some(stats)) => Ok(Json(ApiResponse::success_with_clock(
+            stats.into(),
+            "成功获取代币统计 / Successfully retrieved mint statistics".to_string(),
+            clock,
+        ))),
+        Ok(None) => Err(ApiError::not_found("没有该代币的转账 / No transfers recorded for this mint", clock)),
+        Err(e) => {
+            error!("查询代币 {} 统计时数据库错误: {}", mint, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 获取最活跃的代币 / Rank mints by transfer activity
+#[utoipa::path(
+    get,
+    path = "/api/v1/mints/top",
+    params(
+        ("by" = Option<String>, Query, description = "排行依据：transfers（默认）或 participants（不同发送方与接收方之和）/ Ranking: transfers (default) or participants (unique senders plus receivers)",
+            example = "transfers"),
+        ("limit" = Option<usize>, Query, description = "返回数量，默认20，最大100 / Number of mints, default 20, max 100", example = 20)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<TopMintsResponse>),
+        (status = 400, description = "排行依据无效 / Invalid ranking", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "by 只能为 transfers 或 participants / by must be transfers or participants", "data": {"error": "by 只能为 transfers 或 participants / by must be transfers or participants"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Statistics"
+)]
+pub async fn get_top_mints(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TopMintsParams>,
+) -> ApiResult<TopMintsResponse> {
+    info!("查询代币排行: {:?}", params);
+    let clock = state.clock.as_ref();
+
+    let by = params.by.unwrap_or_else(|| "transfers".to_string());
+    let ranking = MintRanking::parse(&by).ok_or_else(|| {
+        ApiError::bad_request("by 只能为 transfers 或 participants / by must be transfers or participants", clock)
+    })?;
+    let limit = params.limit.unwrap_or(20).clamp(1, MAX_TOP_MINTS);
+
+    match state.db_manager.run_blocking(move |db| db.mint_stats_storage().get_top(ranking, limit)).await {
+        Ok(stats) => {
+            let mints: Vec<MintStatsResponse> = stats.into_iter().map(Into::into).collect();
+            let count = mints.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                TopMintsResponse { by, count, mints },
+                format!("成功获取代币排行 / Successfully retrieved top mints: {} mints", count),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询代币排行时数据库错误: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 按组合条件搜索转账 / Search transfers with compound filters
+#[utoipa::path(
+    get,
+    path = "/api/v1/transfers/search",
+    params(
+        ("address" = Option<String>, Query, description = "按地址搜索（与 mint 至少指定一个）/ Search by address (address or mint is required)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("mint" = Option<String>, Query, description = "代币 mint；未指定 address 时按 mint 索引搜索 / Token mint; searches the mint index when address is omitted",
+            example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+        ("min_amount" = Option<u64>, Query, description = "最小金额（最小单位，包含）/ Minimum amount in raw units (inclusive)"),
+        ("max_amount" = Option<u64>, Query, description = "最大金额（最小单位，包含）/ Maximum amount in raw units (inclusive)"),
+        ("start_time" = Option<u64>, Query, description = "起始时间戳（包含）/ Start timestamp (inclusive)"),
+        ("end_time" = Option<u64>, Query, description = "结束时间戳（包含）/ End timestamp (inclusive)"),
+        ("direction" = Option<String>, Query, description = "方向 in/out，需要指定 address / Direction in/out, requires address", example = "out"),
+        ("category" = Option<String>, Query, description = "类别 sol/token / Category sol/token", example = "sol"),
+        ("tag" = Option<String>, Query, description = "地址标记，转出方或接收方带有该标记时匹配 / Address tag; matches when the sender or receiver has it", example = "case-42"),
+        ("limit" = Option<usize>, Query, description = "每页数量，默认100，最大1000 / Page size, default 100, max 1000", example = 100),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor / next_cursor returned by the previous page")
+    ),
+    responses(
+        (status = 200, description = "搜索成功（没有匹配时返回空列表）/ Search successful (empty list when nothing matches)", body = ApiResponse<TransferSearchResponse>),
+        (status = 400, description = "搜索条件无效 / Invalid search filters", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "必须指定 address 或 mint / Either address or mint is required", "data": {"error": "必须指定 address 或 mint / Either address or mint is required"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn search_transfers(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TransferSearchParams>,
+) -> ApiResult<TransferSearchResponse> {
+    info!("搜索转账: {:?}", params);
+    let clock = state.clock.as_ref();
+
+    if params.address.is_none() && params.mint.is_none() {
+        return Err(ApiError::bad_request("必须指定 address 或 mint / Either address or mint is required", clock));
+    }
+    if let Some(address) = &params.address {
+        validate_address(address, clock)?;
+    }
+    let direction = match params.direction.as_deref() {
+        None => None,
+        Some(_) if params.address.is_none() => {
+            return Err(ApiError::bad_request("direction 需要指定 address / direction requires address", clock));
+        }
+        Some(value) => Some(TransferDirection::parse(value).ok_or_else(|| {
+            ApiError::bad_request("direction 只能是 in 或 out / direction must be in or out", clock)
+        })?),
+    };
+    let category = match params.category.as_deref() {
+        None => None,
+        Some(value) => Some(TransferCategory::parse(value).ok_or_else(|| {
+            ApiError::bad_request("category 只能是 sol 或 token / category must be sol or token", clock)
+        })?),
+    };
+    let tag = match params.tag.as_deref() {
+        None => None,
+        Some(value) => Some(AddressTagStorage::normalize_tag(value).ok_or_else(|| {
+            ApiError::bad_request("标记无效 / Invalid tag", clock)
+        })?),
+    };
+
+    if let Some(cursor) = &params.cursor {
+        if let Err(e) = TransferSearch::decode_cursor(cursor) {
+            warn!("无效的转账搜索游标 {}: {}", cursor, e);
+            return Err(ApiError::bad_request("游标无效 / Invalid cursor", clock));
+        }
+    }
+
+    let query = TransferQuery {
+        address: params.address,
+        mint: params.mint,
+        min_amount: params.min_amount,
+        max_amount: params.max_amount,
+        start_time: params.start_time,
+        end_time: params.end_time,
+        direction,
+        category,
+        tag,
+        limit: params.limit.unwrap_or(100).clamp(1, 1000),
+        cursor: params.cursor,
+    };
+
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            let page = db.search_transfers(&query)?;
+            let page = PaginatedResponse::from_cursor(page.transfers, page.next_cursor).map(Into::into);
+            let response = with_labels(db, TransferSearchResponse { page })?;
+            with_finality(db, response)
+        })
+        .await;
+    match result {
+        Ok(response_data) => {
+            let message = format!("转账搜索完成 / Transfer search finished: {} transfers", response_data.page.items.len());
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("搜索转账时数据库错误: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询两个地址之间的转账 / Query transfers between two addresses
+#[utoipa::path(
+    get,
+    path = "/api/v1/transfers/between",
+    params(
+        ("from" = String, Query, description = "发送方地址（代币转账为所有者钱包）/ Sender address (owner wallet for token transfers)",
+            example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("to" = String, Query, description = "接收方地址（代币转账为所有者钱包）/ Receiver address (owner wallet for token transfers)",
+            example = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU"),
+        ("limit" = Option<usize>, Query, description = "返回数量限制，默认100，最大1000 / Limit of returned transfers, default 100, max 1000", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of transfers to skip for pagination, default 0", example = 0)
+    ),
+    responses(
+        (status = 200, description = "查询成功（没有转账时返回空列表）/ Query successful (empty list when there are no transfers)", body = ApiResponse<TransfersBetweenResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Transactions"
+)]
+pub async fn get_transfers_between(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TransfersBetweenParams>,
+) -> ApiResult<TransfersBetweenResponse> {
+    info!("查询地址对转账: {} -> {}", params.from, params.to);
+    let clock = state.clock.as_ref();
+    validate_address(&params.from, clock)?;
+    validate_address(&params.to, clock)?;
+
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.offset.unwrap_or(0);
+
+    let (from, to) = (params.from.clone(), params.to.clone());
+    let result = state
+        .db_manager
+        .run_blocking(move |db| {
+            // 多取一条判断是否还有下一页
+            let mut records = db.pair_transfer_storage().get_transfers_between(&from, &to, offset, limit + 1)?;
+            let has_more = records.len() > limit;
+            records.truncate(limit);
+            let transfers: Vec<_> = records
+                .into_iter()
+                .map(|record| TransferHit::from(record).into())
+                .collect();
+            let response = TransfersBetweenResponse { from, to, count: transfers.len(), has_more, transfers };
+            let response = with_labels(db, response)?;
+            with_finality(db, response)
+        })
+        .await;
+    match result {
+        Ok(response_data) => {
+            let message = format!(
+                "成功获取地址对转账 / Successfully retrieved transfers between addresses: {} transfers",
+                response_data.count
+            );
+            Ok(Json(ApiResponse::success_with_clock(response_data, message, clock)))
+        }
+        Err(e) => {
+            error!("查询 {} -> {} 转账时数据库错误: {}", params.from, params.to, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查询转账关系图 / Query transfer graph
+#[utoipa::path(
+    get,
+    path = "/api/v1/graph",
+    params(
+        ("root" = String, Query, description = "根地址 / Root address", example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        ("depth" = Option<usize>, Query, description = "展开深度，默认2，最大3 / Expansion depth, default 2, max 3", example = 2),
+        ("limit" = Option<usize>, Query, description = "每个节点展开的交易对手数，默认10，最大50 / Counterparties expanded per node, default 10, max 50", example = 10)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<GraphResponse>),
+        (status = 400, description = "地址格式无效 / Invalid address format", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn get_transfer_graph(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<GraphQueryParams>,
+) -> ApiResult<GraphResponse> {
+    let root = params.root;
+    let depth = params.depth.unwrap_or(2).clamp(1, MAX_GRAPH_DEPTH);
+    let limit = params.limit.unwrap_or(10).clamp(1, 50);
+    info!("查询转账关系图: root={}, depth={}, limit={}", root, depth, limit);
+    let clock = state.clock.as_ref();
+
+    // 验证地址格式
+    validate_address(&root, clock)?;
+
+    let graph_root = root.clone();
+    match state
+        .db_manager
+        .run_blocking(move |db| db.counterparty_storage().build_graph(&graph_root, depth, limit))
+        .await
+    {
+        Ok(graph) => {
+            let response = GraphResponse::from_graph(graph, depth);
+            let message = format!(
+                "成功获取转账关系图 / Successfully built transfer graph: {} nodes, {} edges",
+                response.nodes.len(),
+                response.edges.len()
+            );
+            Ok(Json(ApiResponse::success_with_clock(response, message, clock)))
+        }
+        Err(e) => {
+            error!("构建地址 {} 的转账关系图时数据库错误: {}", root, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 追踪资金流向 / Trace the flow of funds
+///
+/// 从起点地址按时间向后沿转出记录逐跳展开，按比例分摊金额，返回资金流树。
+#[utoipa::path(
+    post,
+    path = "/api/v1/trace",
+    request_body = FlowTraceRequest,
+    responses(
+        (status = 200, description = "追踪完成（起点没有转出时返回只有根节点的树）/ Trace finished (a lone root when the source has no outflows)", body = ApiResponse<FlowTraceResponse>),
+        (status = 400, description = "地址或资产格式无效 / Invalid address or asset", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "地址格式无效 / Invalid address format", "data": {"error": "地址格式无效 / Invalid address format"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    tag = "Addresses"
+)]
+pub async fn trace_flow(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<FlowTraceRequest>,
+) -> ApiResult<FlowTraceResponse> {
+    let clock = state.clock.as_ref();
+    let asset = request.asset.unwrap_or_else(|| SOL_ASSET.to_string());
+    let max_hops = request.max_hops.unwrap_or(3).clamp(1, MAX_TRACE_HOPS);
+    let max_nodes = request.max_nodes.unwrap_or(200).clamp(1, MAX_TRACE_NODES);
+    info!("追踪资金流向: source={}, asset={}, max_hops={}, max_nodes={}", request.source, asset, max_hops, max_nodes);
+
+    validate_address(&request.source, clock)?;
+    if asset != SOL_ASSET {
+        validate_address(&asset, clock)?;
+    }
+
+    let query = FlowTraceQuery {
+        source: request.source.clone(),
+        asset: asset.clone(),
+        max_hops,
+        start_time: request.start_time,
+        max_nodes,
+    };
+    match state.db_manager.run_blocking(move |db| db.trace_flow(&query)).await {
+        Ok(trace) => {
+            let response = FlowTraceResponse::from_trace(trace, asset, max_hops);
+            let message = format!(
+                "资金流追踪完成 / Flow trace finished: {} nodes{}",
+                response.nodes_visited,
+                if response.truncated { " (truncated)" } else { "" }
+            );
+            Ok(Json(ApiResponse::success_with_clock(response, message, clock)))
+        }
+        Err(e) => {
+            error!("追踪地址 {} 的资金流向时数据库错误: {}", request.source, e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 管理接口鉴权中间件
+///
+/// 请求需携带 `X-Admin-Key: <key>` 或 `Authorization: Bearer <key>`。
+/// 未配置 `admin_api_key` 时管理接口一律返回 403。
+pub async fn require_admin_key(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = state.admin_api_key.as_deref().filter(|key| !key.is_empty()) else {
+        warn!("管理接口未启用，拒绝请求: {}", request.uri());
+        return ApiError::forbidden("管理接口未启用 / Admin endpoints are disabled", state.clock.as_ref())
+            .into_response();
+    };
+
+    let headers = request.headers();
+    let provided = headers
+        .get("x-admin-key")
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+        });
+
+    match provided {
+        Some(key) if keys_match(key.trim(), expected) => next.run(request).await,
+        _ => {
+            warn!("管理接口鉴权失败: {}", request.uri());
+            ApiError::unauthorized(
+                "管理接口鉴权失败 / Invalid or missing admin API key",
+                state.clock.as_ref(),
+            )
+            .into_response()
+        }
+    }
+}
+
+/// 比较 API Key，耗时与第一个不同字节的位置无关
+fn keys_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// 手动压缩数据库 / Manually compact the database
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/compact",
+    responses(
+        (status = 200, description = "压缩完成 / Compaction finished", body = ApiResponse<CompactResponse>),
+        (status = 401, description = "鉴权失败 / Invalid admin API key", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口鉴权失败 / Invalid or missing admin API key", "data": {"error": "管理接口鉴权失败 / Invalid or missing admin API key"}, "timestamp": 1704067200})),
+        (status = 403, description = "管理接口未启用 / Admin endpoints disabled", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口未启用 / Admin endpoints are disabled", "data": {"error": "管理接口未启用 / Admin endpoints are disabled"}, "timestamp": 1704067200})),
+        (status = 500, description = "压缩失败 / Compaction failed", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库压缩失败 / Database compaction failed", "data": {"error": "数据库压缩失败 / Database compaction failed"}, "timestamp": 1704067200}))
+    ),
+    security(("admin_api_key" = [])),
+    tag = "Admin"
+)]
+pub async fn compact_database(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<CompactResponse> {
+    info!("管理接口: 开始压缩数据库");
+    let clock = state.clock.as_ref();
+
+    let started = std::time::Instant::now();
+    // 全量压缩可能耗时较长，放到阻塞线程池中执行
+    let result = state.db_manager.run_blocking(|db| db.compact_database()).await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(_) => Ok(Json(ApiResponse::success_with_clock(
+            CompactResponse { compacted: true, duration_ms },
+            "数据库压缩完成 / Database compaction finished".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("数据库压缩失败: {}", e);
+            Err(ApiError::internal("数据库压缩失败 / Database compaction failed", clock))
+        }
+    }
+}
+
+/// 创建数据库快照 / Create a consistent database snapshot
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/backup",
+    request_body = BackupRequest,
+    responses(
+        (status = 200, description = "快照已创建 / Snapshot created", body = ApiResponse<BackupResponse>),
+        (status = 401, description = "鉴权失败 / Invalid admin API key", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口鉴权失败 / Invalid or missing admin API key", "data": {"error": "管理接口鉴权失败 / Invalid or missing admin API key"}, "timestamp": 1704067200})),
+        (status = 403, description = "管理接口未启用 / Admin endpoints disabled", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口未启用 / Admin endpoints are disabled", "data": {"error": "管理接口未启用 / Admin endpoints are disabled"}, "timestamp": 1704067200})),
+        (status = 500, description = "快照失败 / Snapshot failed", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "创建快照失败 / Failed to create snapshot", "data": {"error": "创建快照失败 / Failed to create snapshot"}, "timestamp": 1704067200}))
+    ),
+    security(("admin_api_key" = [])),
+    tag = "Admin"
+)]
+pub async fn create_backup(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<BackupRequest>,
+) -> ApiResult<BackupResponse> {
+    info!("管理接口: 创建快照到 {}", request.target_dir);
+    let clock = state.clock.as_ref();
+
+    let started = std::time::Instant::now();
+    let target_dir = std::path::PathBuf::from(&request.target_dir);
+    let compress = request.compress;
+    // checkpoint 与压缩都是磁盘 I/O，放到阻塞线程池中执行
+    let result = state
+        .db_manager
+        .run_blocking(move |db| Backup::create_snapshot(db, &target_dir, compress))
+        .await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(snapshot) => Ok(Json(ApiResponse::success_with_clock(
+            BackupResponse {
+                path: snapshot.path.to_string_lossy().to_string(),
+                signature_count: snapshot.manifest.signature_count,
+                duration_ms,
+            },
+            "快照已创建 / Snapshot created".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("创建快照失败: {:#}", e);
+            Err(ApiError::internal("创建快照失败 / Failed to create snapshot", clock))
+        }
+    }
+}
+
+/// 获取 RocksDB 属性 / Get parsed RocksDB properties
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/db-stats",
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<DbStatsResponse>),
+        (status = 401, description = "鉴权失败 / Invalid admin API key", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口鉴权失败 / Invalid or missing admin API key", "data": {"error": "管理接口鉴权失败 / Invalid or missing admin API key"}, "timestamp": 1704067200})),
+        (status = 403, description = "管理接口未启用 / Admin endpoints disabled", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口未启用 / Admin endpoints are disabled", "data": {"error": "管理接口未启用 / Admin endpoints are disabled"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    security(("admin_api_key" = [])),
+    tag = "Admin"
+)]
+pub async fn get_db_stats(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<DbStatsResponse> {
+    info!("管理接口: 查询 RocksDB 属性");
+    let clock = state.clock.as_ref();
+
+    match state.db_manager.run_blocking(|db| db.get_db_properties()).await {
+        Ok(properties) => Ok(Json(ApiResponse::success_with_clock(
+            properties.into(),
+            "成功获取数据库属性 / Successfully retrieved database properties".to_string(),
+            clock,
+        ))),
+        Err(e) => {
+            error!("获取数据库属性失败: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查看死信队列 / Inspect dead-lettered transactions awaiting retry
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/dead-letters",
+    params(
+        ("limit" = Option<usize>, Query, description = "返回数量限制（默认100）/ Limit (default 100)", example = 100),
+        ("offset" = Option<usize>, Query, description = "跳过的数量，用于分页，默认0 / Number of transactions to skip for pagination, default 0", example = 0),
+        ("cursor" = Option<String>, Query, description = "上一页返回的 next_cursor，优先于 offset / next_cursor from the previous page, takes precedence over offset", example = "100")
+    ),
+    responses(
+        (status = 200, description = "查询成功（按下次重试时间排序）/ Query successful (ordered by next retry time)", body = ApiResponse<PaginatedResponse<DeadLetterResponse>>),
+        (status = 400, description = "游标无效 / Invalid cursor", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "cursor 无效 / Invalid cursor", "data": {"error": "cursor 无效 / Invalid cursor"}, "timestamp": 1704067200})),
+        (status = 401, description = "鉴权失败 / Invalid admin API key", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口鉴权失败 / Invalid or missing admin API key", "data": {"error": "管理接口鉴权失败 / Invalid or missing admin API key"}, "timestamp": 1704067200})),
+        (status = 403, description = "管理接口未启用 / Admin endpoints disabled", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口未启用 / Admin endpoints are disabled", "data": {"error": "管理接口未启用 / Admin endpoints are disabled"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    security(("admin_api_key" = [])),
+    tag = "Admin"
+)]
+pub async fn get_dead_letters(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<PaginatedResponse<DeadLetterResponse>> {
+    info!("管理接口: 查看死信队列");
+    let clock = state.clock.as_ref();
+    let limit = params.limit.unwrap_or(100);
+    let offset = params.start(clock)?;
+
+    match state.db_manager.run_blocking(|db| db.dead_letter_storage().list()).await {
+        Ok(mut letters) => {
+            letters.sort_by_key(|letter| letter.next_retry_at);
+            let total = letters.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                PaginatedResponse::from_offset(letters, offset, limit).map(Into::into),
+                format!("死信队列中有 {} 笔交易 / {} transactions in the dead letter queue", total, total),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询死信队列失败: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 查看摄取审计日志 / Inspect the ingestion audit log
+///
+/// 记录订阅、重连、断开、槽位缺口和跳过的重复交易，用于事故后判断数据是否完整。
+/// / Records subscriptions, reconnects, disconnects, slot gaps and skipped duplicates so operators can verify completeness after an incident.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/ingestion-log",
+    params(
+        ("kind" = Option<String>, Query, description = "事件类型（subscribed、reconnected、disconnected、slot_gap、duplicates_skipped、slot_lag、slot_lag_recovered、storage_errors、disk_space_low、disk_space_recovered、disk_guard_triggered、disk_guard_released）/ Event kind", example = "slot_gap"),
+        ("since" = Option<i64>, Query, description = "起始时间戳（含）/ Only entries at or after this timestamp", example = 1704067200),
+        ("limit" = Option<usize>, Query, description = "返回数量限制（默认100）/ Limit (default 100)", example = 100)
+    ),
+    responses(
+        (status = 200, description = "查询成功 / Query successful", body = ApiResponse<IngestionLogResponse>),
+        (status = 400, description = "事件类型无效 / Invalid event kind", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "无效的事件类型 / Invalid event kind: foo", "data": {"error": "无效的事件类型 / Invalid event kind: foo"}, "timestamp": 1704067200})),
+        (status = 401, description = "鉴权失败 / Invalid admin API key", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口鉴权失败 / Invalid or missing admin API key", "data": {"error": "管理接口鉴权失败 / Invalid or missing admin API key"}, "timestamp": 1704067200})),
+        (status = 403, description = "管理接口未启用 / Admin endpoints disabled", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口未启用 / Admin endpoints are disabled", "data": {"error": "管理接口未启用 / Admin endpoints are disabled"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库查询错误 / Database query error", "data": {"error": "数据库查询错误 / Database query error"}, "timestamp": 1704067200}))
+    ),
+    security(("admin_api_key" = [])),
+    tag = "Admin"
+)]
+pub async fn get_ingestion_log(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<IngestionLogParams>,
+) -> ApiResult<IngestionLogResponse> {
+    info!("管理接口: 查看摄取审计日志");
+    let clock = state.clock.as_ref();
+    let limit = params.limit.unwrap_or(100);
+    if let Some(ref kind) = params.kind {
+        if !IngestionEvent::KINDS.contains(&kind.as_str()) {
+            return Err(ApiError::bad_request(format!("无效的事件类型 / Invalid event kind: {}", kind), clock));
+        }
+    }
+
+    let (since, kind) = (params.since, params.kind);
+    match state
+        .db_manager
+        .run_blocking(move |db| db.ingestion_log_storage().list(since, kind.as_deref(), limit))
+        .await
+    {
+        Ok(entries) => {
+            let count = entries.len();
+            Ok(Json(ApiResponse::success_with_clock(
+                IngestionLogResponse {
+                    count,
+                    entries: entries.into_iter().map(Into::into).collect(),
+                },
+                format!("返回 {} 条摄取审计日志 / Returned {} ingestion log entries", count, count),
+                clock,
+            )))
+        }
+        Err(e) => {
+            error!("查询摄取审计日志失败: {}", e);
+            Err(ApiError::internal("数据库查询错误 / Database query error", clock))
+        }
+    }
+}
+
+/// 重新加载配置文件 / Reload the config file
+///
+/// 立即应用监控过滤条件和解析参数的变化，其他配置仍需重启。
+/// / Applies changes to monitor filters and parser thresholds immediately; other sections still need a restart.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/reload-config",
+    responses(
+        (status = 200, description = "重新加载完成 / Reload finished", body = ApiResponse<ConfigReloadResponse>),
+        (status = 400, description = "配置无效 / Invalid configuration", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "配置无效 / Invalid configuration: 配置项 parser.dust_floor_lamports 无效: invalid type: string \"abc\", expected u64", "data": {"error": "配置无效 / Invalid configuration: 配置项 parser.dust_floor_lamports 无效: invalid type: string \"abc\", expected u64"}, "timestamp": 1704067200})),
+        (status = 401, description = "鉴权失败 / Invalid admin API key", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口鉴权失败 / Invalid or missing admin API key", "data": {"error": "管理接口鉴权失败 / Invalid or missing admin API key"}, "timestamp": 1704067200})),
+        (status = 403, description = "管理接口未启用 / Admin endpoints disabled", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口未启用 / Admin endpoints are disabled", "data": {"error": "管理接口未启用 / Admin endpoints are disabled"}, "timestamp": 1704067200})),
+        (status = 503, description = "配置热更新未启用 / Config reload unavailable", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "配置热更新未启用 / Config reload is not enabled", "data": {"error": "配置热更新未启用 / Config reload is not enabled"}, "timestamp": 1704067200}))
+    ),
+    security(("admin_api_key" = [])),
+    tag = "Admin"
+)]
+pub async fn reload_config(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<ConfigReloadResponse> {
+    info!("管理接口: 重新加载配置");
+    let clock = state.clock.as_ref();
+    let Some(reloader) = state.config_reloader.clone() else {
+        return Err(ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "配置热更新未启用 / Config reload is not enabled",
+            clock,
+        ));
+    };
+
+    match tokio::task::spawn_blocking(move || reloader.reload()).await {
+        Ok(Ok(changed)) => {
+            info!("配置已重新加载，变化的配置段: {:?}", changed);
+            let message = if changed.is_empty() {
+                "配置没有变化 / No reloadable changes".to_string()
+            } else {
+                format!("已应用 {} 的变化 / Applied changes to {}", changed.join(", "), changed.join(", "))
+            };
+            Ok(Json(ApiResponse::success_with_clock(
+                ConfigReloadResponse {
+                    changed: changed.into_iter().map(str::to_string).collect(),
+                },
+                message,
+                clock,
+            )))
+        }
+        Ok(Err(e)) => {
+            warn!("重新加载配置失败: {:#}", e);
+            Err(ApiError::bad_request(format!("配置无效 / Invalid configuration: {:#}", e), clock))
+        }
+        Err(e) => {
+            error!("重新加载配置任务异常退出: {}", e);
+            Err(ApiError::internal("重新加载配置失败 / Config reload failed", clock))
+        }
+    }
+}
+
+/// 删除签名数据 / Delete signature data
+///
+/// 交易的地址记录、槽位索引、程序索引和地址对转账索引一并删除。
+/// / The transaction's address records and its slot, program and pair transfer index entries are removed with it.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/signature/{signature}",
+    params(
+        ("signature" = String, Path, description = "交易签名 / Transaction signature",
+            example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")
+    ),
+    responses(
+        (status = 200, description = "删除完成 / Deletion finished", body = ApiResponse<DeleteSignatureResponse>),
+        (status = 401, description = "鉴权失败 / Invalid admin API key", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口鉴权失败 / Invalid or missing admin API key", "data": {"error": "管理接口鉴权失败 / Invalid or missing admin API key"}, "timestamp": 1704067200})),
+        (status = 403, description = "管理接口未启用 / Admin endpoints disabled", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "管理接口未启用 / Admin endpoints are disabled", "data": {"error": "管理接口未启用 / Admin endpoints are disabled"}, "timestamp": 1704067200})),
+        (status = 404, description = "签名不存在 / Signature not found", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "签名不存在 / Signature not found", "data": {"error": "签名不存在 / Signature not found"}, "timestamp": 1704067200})),
+        (status = 500, description = "服务器内部错误 / Internal server error", body = ApiResponse<ErrorResponse>,
+            example = json!({"success": false, "message": "数据库错误 / Database error", "data": {"error": "数据库错误 / Database error"}, "timestamp": 1704067200}))
+    ),
+    security(("admin_api_key" = [])),
+    tag = "Admin"
+)]
+pub async fn delete_signature(
+    State(state): State<Arc<AppState>>,
+    Path(signature): Path<String>,
+) -> ApiResult<DeleteSignatureResponse> {
+    info!("管理接口: 删除签名 {}", signature);
+    let clock = state.clock.as_ref();
+
+    let target = signature.clone();
+    let result = state.db_manager.run_blocking(move |db| db.delete_signature(&target)).await;
+
+    match result {
+        Ok(true) => Ok(Json(ApiResponse::success_with_clock(
+            DeleteSignatureResponse { signature, deleted: true },
+            "签名数据已删除 / Signature data deleted".to_string(),
+            clock,
+        ))),
+        Ok(false) => Err(ApiError::not_found("签名不存在 / Signature not found", clock)),
+        Err(e) => {
+            error!("删除签名 {} 时数据库错误: {}", signature, e);
+            Err(ApiError::internal("数据库错误 / Database error", clock))
+        }
+    }
+}
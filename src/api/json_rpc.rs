@@ -0,0 +1,387 @@
+//! Solana JSON-RPC 兼容接口
+//!
+//! 在本地账本上实现 `getSignaturesForAddress` 与 `getTransaction`，现有 SDK 把 RPC 地址指向
+//! `/api/v1/rpc` 即可查询已跟踪地址的历史。账本只保存解析后的转账和地址角色，不保存指令、
+//! 日志和余额数组，`getTransaction` 中这些字段返回空数组；地址签名列表只包含该地址有转账
+//! 记录的交易。
+
+use axum::{body::Bytes, extract::State, response::Json};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::database::{AddressTransactionRecord, FinalityStatus, FinalityView, SignatureTransactionData};
+use crate::transfer_types::{InstructionFailure, TransactionFailure};
+use super::handlers::AppState;
+
+/// `getSignaturesForAddress` 单次返回的最大条数（与 Solana RPC 相同）
+const MAX_SIGNATURES_LIMIT: u64 = 1000;
+
+/// JSON-RPC 错误
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn parse_error() -> Self {
+        Self { code: -32700, message: "Parse error".to_string() }
+    }
+
+    fn invalid_request() -> Self {
+        Self { code: -32600, message: "Invalid request".to_string() }
+    }
+
+    fn method_not_found() -> Self {
+        Self { code: -32601, message: "Method not found".to_string() }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self { code: -32602, message: message.into() }
+    }
+
+    fn internal() -> Self {
+        Self { code: -32603, message: "Internal error".to_string() }
+    }
+}
+
+/// Solana JSON-RPC 兼容接口 / Solana JSON-RPC compatible endpoint
+///
+/// 支持 `getSignaturesForAddress` 和 `getTransaction`（仅 `json` 编码），以及批量请求。
+#[utoipa::path(
+    post,
+    path = "/api/v1/rpc",
+    request_body(
+        content = Object,
+        description = "JSON-RPC 2.0 请求或批量请求数组 / JSON-RPC 2.0 request or batch array",
+        example = json!({"jsonrpc": "2.0", "id": 1, "method": "getSignaturesForAddress", "params": ["9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM", {"limit": 10}]})
+    ),
+    responses(
+        (status = 200, description = "JSON-RPC 响应，错误放在 error 字段中 / JSON-RPC response, errors are reported in the error member", body = Object,
+            example = json!({"jsonrpc": "2.0", "id": 1, "result": [{"signature": "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW", "slot": 250000000, "err": null, "memo": null, "blockTime": 1704067200, "confirmationStatus": "finalized"}]}))
+    ),
+    tag = "Transactions"
+)]
+pub async fn handle_json_rpc(State(state): State<Arc<AppState>>, body: Bytes) -> Json<Value> {
+    let request: Value = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(_) => return Json(error_response(Value::Null, RpcError::parse_error())),
+    };
+
+    match request {
+        Value::Array(requests) if requests.is_empty() => Json(error_response(Value::Null, RpcError::invalid_request())),
+        Value::Array(requests) => {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(dispatch(&state, request).await);
+            }
+            Json(Value::Array(responses))
+        }
+        request => Json(dispatch(&state, request).await),
+    }
+}
+
+/// 处理单个请求
+async fn dispatch(state: &AppState, request: Value) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return error_response(id, RpcError::invalid_request());
+    };
+    let params = match request.get("params") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(params)) => params.clone(),
+        Some(_) => return error_response(id, RpcError::invalid_params("params must be an array")),
+    };
+
+    info!("JSON-RPC 请求: {}", method);
+    let result = match method {
+        "getSignaturesForAddress" => get_signatures_for_address(state, params).await,
+        "getTransaction" => get_transaction(state, params).await,
+        _ => Err(RpcError::method_not_found()),
+    };
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+        Err(e) => error_response(id, e),
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "error": { "code": error.code, "message": error.message }, "id": id })
+}
+
+/// 第一个参数：地址或签名
+fn key_param(params: &[Value], name: &str) -> Result<String, RpcError> {
+    params
+        .first()
+        .and_then(Value::as_str)
+        .filter(|value| value.len() >= 32)
+        .map(str::to_string)
+        .ok_or_else(|| RpcError::invalid_params(format!("Invalid param: {}", name)))
+}
+
+/// 配置中的 `commitment`，未指定时不按最终性过滤
+fn commitment_param(config: Option<&Value>) -> Result<Option<FinalityStatus>, RpcError> {
+    match config.and_then(|config| config.get("commitment")).and_then(Value::as_str) {
+        None => Ok(None),
+        Some(value) => match FinalityStatus::parse(value) {
+            Some(status @ (FinalityStatus::Processed | FinalityStatus::Confirmed | FinalityStatus::Finalized)) => {
+                Ok(Some(status))
+            }
+            _ => Err(RpcError::invalid_params(format!("Invalid commitment: {}", value))),
+        },
+    }
+}
+
+/// 槽位是否达到请求的最终性：孤立槽位中的交易不返回，尚未跟踪最终性时不过滤
+fn meets_commitment(status: FinalityStatus, commitment: Option<FinalityStatus>) -> bool {
+    match (status, commitment) {
+        (FinalityStatus::Orphaned, _) => false,
+        (FinalityStatus::Unknown, _) | (_, None) => true,
+        (_, Some(FinalityStatus::Finalized)) => status == FinalityStatus::Finalized,
+        (_, Some(FinalityStatus::Confirmed)) => status != FinalityStatus::Processed,
+        _ => true,
+    }
+}
+
+fn confirmation_status(status: FinalityStatus) -> Value {
+    match status {
+        FinalityStatus::Processed | FinalityStatus::Confirmed | FinalityStatus::Finalized => json!(status.as_str()),
+        FinalityStatus::Unknown | FinalityStatus::Orphaned => Value::Null,
+    }
+}
+
+async fn get_signatures_for_address(state: &AppState, params: Vec<Value>) -> Result<Value, RpcError> {
+    let address = key_param(&params, "address")?;
+    let config = params.get(1);
+    let limit = match config.and_then(|config| config.get("limit")) {
+        None | Some(Value::Null) => MAX_SIGNATURES_LIMIT,
+        Some(limit) => limit
+            .as_u64()
+            .filter(|limit| (1..=MAX_SIGNATURES_LIMIT).contains(limit))
+            .ok_or_else(|| RpcError::invalid_params(format!("Invalid limit; max {}", MAX_SIGNATURES_LIMIT)))?,
+    };
+    let before = config.and_then(|config| config.get("before")).and_then(Value::as_str).map(str::to_string);
+    let until = config.and_then(|config| config.get("until")).and_then(Value::as_str).map(str::to_string);
+    let commitment = commitment_param(config)?;
+
+    state
+        .db_manager
+        .run_blocking(move |db| {
+            let records = db.address_storage().get_address_records(&address)?.map(|list| list.records).unwrap_or_default();
+            let view = db.slot_finality_storage().view()?;
+            let mut signatures = Vec::new();
+            for record in signature_window(&records, before.as_deref(), until.as_deref()) {
+                let status = view.status_of(record.slot);
+                if !meets_commitment(status, commitment) {
+                    continue;
+                }
+                let data = db.signature_storage().get_signature_data(&record.signature)?;
+                let err = data.as_ref().and_then(|data| data.failure.as_ref()).map(rpc_error);
+                // 与 getTransaction 一致优先使用区块时间；精简模式没有签名数据时退回到记录的时间戳
+                let block_time = data
+                    .as_ref()
+                    .map_or(record.timestamp as i64, |data| data.block_time.unwrap_or(data.timestamp));
+                signatures.push(json!({
+                    "signature": record.signature,
+                    "slot": record.slot,
+                    "err": err,
+                    "memo": null,
+                    "blockTime": block_time,
+                    "confirmationStatus": confirmation_status(status),
+                }));
+                if signatures.len() as u64 >= limit {
+                    break;
+                }
+            }
+            Ok(Value::Array(signatures))
+        })
+        .await
+        .map_err(|e| {
+            error!("JSON-RPC getSignaturesForAddress 数据库错误: {}", e);
+            RpcError::internal()
+        })
+}
+
+/// 按签名去重的地址记录（从新到旧），从 `before` 之后开始，到 `until` 之前结束
+///
+/// `before` 不在记录中时返回空列表。
+fn signature_window<'a>(
+    records: &'a [AddressTransactionRecord],
+    before: Option<&str>,
+    until: Option<&str>,
+) -> Vec<&'a AddressTransactionRecord> {
+    let mut seen = HashSet::new();
+    let mut started = before.is_none();
+    let mut window = Vec::new();
+    for record in records {
+        if !seen.insert(record.signature.as_str()) {
+            continue;
+        }
+        if until == Some(record.signature.as_str()) {
+            break;
+        }
+        if started {
+            window.push(record);
+        } else if before == Some(record.signature.as_str()) {
+            started = true;
+        }
+    }
+    window
+}
+
+async fn get_transaction(state: &AppState, params: Vec<Value>) -> Result<Value, RpcError> {
+    let signature = key_param(&params, "signature")?;
+    // 第二个参数可以是编码字符串（旧格式）或配置对象
+    let config = params.get(1);
+    let encoding = match config {
+        Some(Value::String(encoding)) => Some(encoding.as_str()),
+        Some(config) => config.get("encoding").and_then(Value::as_str),
+        None => None,
+    };
+    if let Some(encoding) = encoding.filter(|encoding| *encoding != "json") {
+        return Err(RpcError::invalid_params(format!("Unsupported encoding: {}; only json is supported", encoding)));
+    }
+    let commitment = commitment_param(config)?;
+
+    state
+        .db_manager
+        .run_blocking(move |db| {
+            let Some(data) = db.signature_storage().get_signature_data(&signature)? else {
+                return Ok(Value::Null);
+            };
+            let view: FinalityView = db.slot_finality_storage().view()?;
+            if !meets_commitment(view.status_of(data.slot), commitment) {
+                return Ok(Value::Null);
+            }
+            Ok(transaction_result(&data))
+        })
+        .await
+        .map_err(|e| {
+            error!("JSON-RPC getTransaction 数据库错误: {}", e);
+            RpcError::internal()
+        })
+}
+
+/// `getTransaction` 的结果
+///
+/// 账户按消息顺序排列（可写签名者、只读签名者、可写非签名者、只读非签名者），
+/// 通过地址查找表加载的地址也在其中；没有角色信息的旧数据退回全部地址。
+fn transaction_result(data: &SignatureTransactionData) -> Value {
+    let addresses = &data.extracted_addresses;
+    let signers: HashSet<&str> = addresses.signers.iter().map(String::as_str).collect();
+    let writable: HashSet<&str> = addresses.writable.iter().map(String::as_str).collect();
+    let (account_keys, header) = if signers.is_empty() {
+        (addresses.all_addresses.clone(), json!(null))
+    } else {
+        let readonly_signers: Vec<&String> =
+            addresses.signers.iter().filter(|key| !writable.contains(key.as_str())).collect();
+        let readonly: Vec<&String> =
+            addresses.readonly.iter().filter(|key| !signers.contains(key.as_str())).collect();
+        let account_keys = addresses
+            .signers
+            .iter()
+            .filter(|key| writable.contains(key.as_str()))
+            .chain(readonly_signers.iter().copied())
+            .chain(addresses.writable.iter().filter(|key| !signers.contains(key.as_str())))
+            .chain(readonly.iter().copied())
+            .cloned()
+            .collect();
+        let header = json!({
+            "numRequiredSignatures": addresses.signers.len(),
+            "numReadonlySignedAccounts": readonly_signers.len(),
+            "numReadonlyUnsignedAccounts": readonly.len(),
+        });
+        (account_keys, header)
+    };
+
+    let err = data.failure.as_ref().map(rpc_error);
+    let status = match &err {
+        Some(err) => json!({ "Err": err }),
+        None => json!({ "Ok": null }),
+    };
+    json!({
+        "slot": data.slot,
+        "blockTime": data.block_time.unwrap_or(data.timestamp),
+        "meta": {
+            "err": err,
+            "status": status,
+            "fee": data.fee_lamports,
+            "computeUnitsConsumed": data.compute_units,
+            "preBalances": [],
+            "postBalances": [],
+            "preTokenBalances": [],
+            "postTokenBalances": [],
+            "innerInstructions": [],
+            "logMessages": [],
+            "rewards": [],
+        },
+        "transaction": {
+            "signatures": [data.signature],
+            "message": {
+                "accountKeys": account_keys,
+                "header": header,
+                "instructions": [],
+                "recentBlockhash": "",
+            },
+        },
+    })
+}
+
+/// 按 Solana RPC 的格式输出交易错误，如 `{"InstructionError": [2, {"Custom": 1}]}`
+fn rpc_error(failure: &TransactionFailure) -> Value {
+    match failure {
+        TransactionFailure::InstructionError { index, error } => {
+            let error = match error {
+                InstructionFailure::Custom { code } => json!({ "Custom": code }),
+                other => json!(other.name()),
+            };
+            json!({ "InstructionError": [index, error] })
+        }
+        TransactionFailure::InsufficientFundsForRent { account_index } => {
+            json!({ "InsufficientFundsForRent": { "account_index": account_index } })
+        }
+        TransactionFailure::DuplicateInstruction { index } => json!({ "DuplicateInstruction": index }),
+        other => json!(other.category()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::RecordType;
+
+    fn record(signature: &str, slot: u64) -> AddressTransactionRecord {
+        AddressTransactionRecord {
+            transfer_id: format!("{}:{}", signature, slot),
+            signature: signature.to_string(),
+            timestamp: slot * 10,
+            slot,
+            sol_transfer: None,
+            token_transfer: None,
+            record_type: RecordType::Sender,
+            direction: None,
+        }
+    }
+
+    #[test]
+    fn test_signature_window_and_error_format() {
+        // 从新到旧，sig3 有两条转账记录
+        let records = vec![record("sig4", 40), record("sig3", 30), record("sig3", 30), record("sig2", 20), record("sig1", 10)];
+        let signatures = |before, until| -> Vec<String> {
+            signature_window(&records, before, until).iter().map(|record| record.signature.clone()).collect()
+        };
+        assert_eq!(signatures(None, None), ["sig4", "sig3", "sig2", "sig1"]);
+        assert_eq!(signatures(Some("sig4"), Some("sig1")), ["sig3", "sig2"]);
+        assert!(signatures(Some("missing"), None).is_empty());
+
+        assert!(!meets_commitment(FinalityStatus::Orphaned, None));
+        assert!(!meets_commitment(FinalityStatus::Confirmed, Some(FinalityStatus::Finalized)));
+        assert!(meets_commitment(FinalityStatus::Unknown, Some(FinalityStatus::Finalized)));
+
+        let custom = TransactionFailure::InstructionError { index: 2, error: InstructionFailure::Custom { code: 1 } };
+        assert_eq!(rpc_error(&custom), json!({ "InstructionError": [2, { "Custom": 1 }] }));
+        assert_eq!(rpc_error(&TransactionFailure::BlockhashNotFound), json!("BlockhashNotFound"));
+    }
+}
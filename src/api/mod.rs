@@ -1,7 +1,13 @@
 pub mod models;
+pub mod error;
+pub mod etag;
 pub mod handlers;
+pub mod json_rpc;
 pub mod server;
+pub mod tenant;
+pub mod tls;
 
 pub use models::*;
+pub use error::*;
 pub use handlers::*;
-pub use server::*; 
\ No newline at end of file
+pub use server::*;
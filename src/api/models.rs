@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use crate::database::{SignatureTransactionData, SolTransfer, TokenTransfer};
+use crate::amount::{Lamports, TokenAmount, LAMPORTS_PER_SOL};
+use crate::clock::{Clock, SystemClock};
+use crate::database::{FinalityStatus, FinalityView};
 
 /// API 响应基础结构
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -12,6 +14,7 @@ pub struct ApiResponse<T> {
     /// Response data
     pub data: Option<T>,
     /// Request timestamp
+    #[schema(example = 1704067200)]
     pub timestamp: i64,
 }
 
@@ -19,62 +22,177 @@ pub struct ApiResponse<T> {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
     /// Error message
+    #[schema(example = "Transaction not found")]
     pub error: String,
 }
 
+/// 分页列表响应 / Paginated List Response
+///
+/// 列表接口共用的分页信封；带查询上下文的列表（如地址、槽位）把它展开到响应顶层，与上下文字段并列。
+/// / Pagination envelope shared by listing endpoints; listings with query context (such as an address or slot) flatten it into the top level next to the context fields.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedResponse<T> {
+    /// 本页数据 / Items in this page
+    pub items: Vec<T>,
+    /// 全部数据条数；需要完整扫描才能得到时为空 / Total number of items; null when counting would require a full scan
+    #[schema(example = 1)]
+    pub total: Option<usize>,
+    /// 下一页游标，作为 cursor 参数传入；没有更多数据时为空 / Cursor for the next page, pass it as the cursor parameter; null when there are no more items
+    #[schema(example = "100")]
+    pub next_cursor: Option<String>,
+    /// 是否还有更多数据 / Whether more items exist
+    pub has_more: bool,
+}
+
 /// 签名查询响应数据
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SignatureQueryResponse {
     /// Transaction signature (base58 encoded)
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
     pub signature: String,
     /// SOL transfers in this transaction
     pub sol_transfers: Vec<SolTransferResponse>,
     /// Token transfers in this transaction
     pub token_transfers: Vec<TokenTransferResponse>,
+    /// Stake program operations in this transaction
+    pub stake_events: Vec<StakeEventResponse>,
+    /// DEX swaps in this transaction (paired outgoing and incoming legs)
+    pub swaps: Vec<SwapResponse>,
+    /// NFT transfers in this transaction
+    pub nft_transfers: Vec<NftTransferResponse>,
     /// Extracted addresses from this transaction
     pub extracted_addresses: ExtractedAddressesResponse,
     /// Transaction timestamp
+    #[schema(example = 1704067200)]
     pub timestamp: i64,
     /// Block slot number
+    #[schema(example = 245000000)]
     pub slot: u64,
+    /// Finality of the slot: unknown, processed, confirmed, finalized or orphaned
+    #[schema(example = "finalized")]
+    pub finality: String,
     /// Whether transaction was successful
     pub is_successful: bool,
+    /// Decoded error of a failed transaction (absent for successful transactions and data stored before errors were recorded)
+    pub failure: Option<TransactionFailureResponse>,
+    /// Transaction fee in lamports
+    #[schema(example = 5000)]
+    pub fee_lamports: u64,
+    /// Compute units consumed (absent for transactions without this metadata)
+    #[schema(example = 150)]
+    pub compute_units: Option<u64>,
+    /// Fee payer address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub fee_payer: String,
+    /// Index of the transaction within its block
+    #[schema(example = 42)]
+    pub transaction_index: Option<u64>,
+    /// Block time (present when ingested from full blocks)
+    #[schema(example = 1704067200)]
+    pub block_time: Option<i64>,
+    /// Approximate USD value of the priced transfers at ingest time (absent when price enrichment is disabled or no price was available)
+    #[schema(example = 305.0)]
+    pub total_usd_value: Option<f64>,
+    /// Price source used for the USD values
+    #[schema(example = "coingecko")]
+    pub price_provider: Option<String>,
+}
+
+/// 失败交易的错误 / Transaction failure
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransactionFailureResponse {
+    /// 错误分类，指令错误细分为 `InstructionError/<原因>` / Error category; instruction errors are refined as `InstructionError/<reason>`
+    #[schema(example = "InstructionError/Custom")]
+    pub category: String,
+    /// 失败或重复的指令序号 / Index of the failing or duplicated instruction
+    #[schema(example = 2)]
+    pub instruction_index: Option<u8>,
+    /// 程序自定义错误码 / Custom program error code
+    #[schema(example = 1)]
+    pub custom_code: Option<u32>,
+    /// 余额不足以免租的账户序号 / Index of the account left below rent exemption
+    pub account_index: Option<u8>,
 }
 
 /// SOL 转账响应
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SolTransferResponse {
     /// Sender address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
     pub from: String,
     /// Recipient address
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
     pub to: String,
     /// Transfer amount in lamports
+    #[schema(example = 1500000000)]
     pub amount: u64,
     /// Transfer amount in SOL (calculated)
+    #[schema(example = 1.5)]
     pub amount_sol: f64,
+    /// Transfer amount in SOL as an exact decimal string
+    #[schema(example = "1.5")]
+    pub amount_sol_decimal: String,
     /// Transfer type description
+    #[schema(example = "SOL Transfer")]
     pub transfer_type: String,
+    /// Whether this movement came from wrapping, unwrapping or transferring wSOL
+    pub is_wrapped: bool,
+    /// Instruction path of the System transfer (`2` for a top-level instruction, `2.1` for an inner instruction); empty when inferred from balance changes
+    #[schema(example = "2.1")]
+    pub instruction_path: Option<String>,
+    /// Approximate USD value at ingest time
+    #[schema(example = 300.0)]
+    pub usd_value: Option<f64>,
+    /// Known-entity label of the sender
+    #[schema(example = "Binance Hot Wallet")]
+    pub from_label: Option<String>,
+    /// Known-entity label of the recipient
+    pub to_label: Option<String>,
 }
 
 /// 代币转账响应
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TokenTransferResponse {
-    /// Sender address
+    /// Sender token account
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
     pub from: String,
-    /// Recipient address
+    /// Recipient token account
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
     pub to: String,
     /// Transfer amount (raw)
+    #[schema(example = 25000000)]
     pub amount: u64,
     /// Transfer amount (human readable)
+    #[schema(example = 25.0)]
     pub amount_formatted: f64,
+    /// Transfer amount (human readable) as an exact decimal string
+    #[schema(example = "25")]
+    pub amount_decimal: String,
     /// Token decimals
+    #[schema(example = 6)]
     pub decimals: u8,
     /// Token mint address
+    #[schema(example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")]
     pub mint: String,
     /// Token program ID
+    #[schema(example = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
     pub program_id: String,
     /// Transfer type description
+    #[schema(example = "SPL Token Transfer")]
     pub transfer_type: String,
+    /// Wallet that owns the sender token account
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub from_owner: Option<String>,
+    /// Wallet that owns the recipient token account
+    pub to_owner: Option<String>,
+    /// Approximate USD value at ingest time
+    #[schema(example = 25.0)]
+    pub usd_value: Option<f64>,
+    /// Known-entity label of the sender
+    #[schema(example = "Binance Hot Wallet")]
+    pub from_label: Option<String>,
+    /// Known-entity label of the recipient
+    pub to_label: Option<String>,
 }
 
 /// 提取的地址响应
@@ -82,6 +200,15 @@ pub struct TokenTransferResponse {
 pub struct ExtractedAddressesResponse {
     /// All addresses involved in the transaction
     pub all_addresses: Vec<String>,
+    /// 签名者，第一个为手续费支付者 / Signers, the first one is the fee payer
+    pub signers: Vec<String>,
+    /// 可写账户 / Writable accounts
+    pub writable: Vec<String>,
+    /// 只读账户 / Read-only accounts
+    pub readonly: Vec<String>,
+    /// 调用的程序 / Invoked programs
+    #[schema(example = json!(["11111111111111111111111111111111"]))]
+    pub programs: Vec<String>,
 }
 
 /// 签名查询请求
@@ -92,6 +219,19 @@ pub struct SignatureQueryRequest {
     pub signature: String,
 }
 
+/// 签名前缀搜索响应 / Signature Prefix Search Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SignatureSearchResponse {
+    /// 查询的签名前缀 / Queried signature prefix
+    #[schema(example = "5VER")]
+    pub prefix: String,
+    /// 匹配的完整签名（按字典序）/ Matching full signatures (lexicographic order)
+    #[schema(example = json!(["5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"]))]
+    pub signatures: Vec<String>,
+    /// 是否还有更多匹配（结果被 limit 截断）/ Whether more signatures match beyond the limit
+    pub has_more: bool,
+}
+
 /// 数据库统计响应
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DatabaseStatsResponse {
@@ -105,13 +245,175 @@ pub struct DatabaseStatsResponse {
     pub successful_transactions: usize,
     /// Number of failed transactions
     pub failed_transactions: usize,
+    /// Daily fee totals for the most recent days (newest first)
+    pub daily_fees: Vec<DailyFeeStatsResponse>,
+    /// Daily transfer counts for the most recent days (newest first)
+    pub daily_transfers: Vec<DailyTransferStatsResponse>,
+    /// USD volume of transfers priced at ingest time (0 unless price enrichment is enabled)
+    #[schema(example = 1250000.5)]
+    pub total_usd_volume: f64,
+    /// Failed transactions by error category, e.g. `BlockhashNotFound` or `InstructionError/Custom`
+    #[schema(example = json!({"InstructionError/Custom": 120, "BlockhashNotFound": 3}))]
+    pub failures_by_category: std::collections::BTreeMap<String, u64>,
+}
+
+/// 每日活跃地址数 / Daily Active Addresses
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DailyActiveAddressesResponse {
+    /// 日期（UTC）/ Date (UTC, YYYY-MM-DD)
+    #[schema(example = "2024-01-01")]
+    pub date: String,
+    /// 当天参与过转账的不同地址数 / Distinct addresses that sent or received a transfer that day
+    #[schema(example = 125000)]
+    pub active_addresses: u64,
+}
+
+/// 活跃地址统计响应 / Active Addresses Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ActiveAddressesResponse {
+    /// 每日活跃地址数（最新的在前）/ Daily active addresses, newest first
+    pub days: Vec<DailyActiveAddressesResponse>,
+}
+
+/// 转账金额分布响应 / Amount Histogram Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AmountHistogramResponse {
+    /// 资产（SOL 或代币 mint）/ Asset (SOL or a token mint)
+    #[schema(example = "SOL")]
+    pub asset: String,
+    /// 统计天数 / Number of days covered
+    #[schema(example = 7)]
+    pub period_days: u32,
+    /// 起始日期（UTC，含）/ First date covered (UTC, inclusive)
+    #[schema(example = "2024-01-01")]
+    pub since_date: String,
+    /// 周期内的转账总笔数 / Total transfers in the period
+    #[schema(example = 125000)]
+    pub total_transfers: u64,
+    /// 各金额区间的笔数，按金额从小到大 / Transfer counts per amount range, smallest first
+    pub buckets: Vec<AmountBucketResponse>,
+}
+
+/// 金额区间 / Amount Bucket
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AmountBucketResponse {
+    /// 区间名 / Bucket label
+    #[schema(example = "0.1-1")]
+    pub label: String,
+    /// 下限（整数单位，含）/ Lower bound in whole units (inclusive)
+    #[schema(example = 0.1)]
+    pub min: f64,
+    /// 上限（整数单位，不含），最后一个区间为空 / Upper bound in whole units (exclusive), null for the last bucket
+    #[schema(example = 1.0)]
+    pub max: Option<f64>,
+    /// 转账笔数 / Number of transfers
+    #[schema(example = 42000)]
+    pub count: u64,
+}
+
+impl AmountHistogramResponse {
+    /// 汇总周期内每天的分布
+    pub fn from_daily(
+        asset: String,
+        period_days: u32,
+        since_date: String,
+        daily: &[crate::database::DailyAmountHistogram],
+    ) -> Self {
+        let buckets: Vec<AmountBucketResponse> = crate::database::AMOUNT_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(index, bucket)| AmountBucketResponse {
+                label: bucket.label.to_string(),
+                min: bucket.min,
+                max: bucket.max,
+                count: daily.iter().map(|day| day.counts[index]).sum(),
+            })
+            .collect();
+        Self {
+            asset,
+            period_days,
+            since_date,
+            total_transfers: buckets.iter().map(|bucket| bucket.count).sum(),
+            buckets,
+        }
+    }
+}
+
+/// 吞吐量统计响应 / Throughput Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ThroughputResponse {
+    /// 本进程是否运行数据摄取（api-only 模式下速率恒为 0）/ Whether this process runs ingestion (rates are always 0 in api-only mode)
+    pub ingest_enabled: bool,
+    /// 1 分钟、5 分钟、15 分钟窗口的平均速率 / Average rates over 1m, 5m and 15m windows
+    pub windows: Vec<ThroughputWindowResponse>,
+}
+
+/// 滑动窗口速率 / Sliding Window Rate
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ThroughputWindowResponse {
+    /// 窗口长度（秒）/ Window length in seconds
+    #[schema(example = 60)]
+    pub window_secs: u64,
+    /// 每秒 gRPC 消息数 / gRPC messages per second
+    #[schema(example = 850.5)]
+    pub messages_per_sec: f64,
+    /// 每秒转账笔数（SOL 与代币）/ SOL and token transfers per second
+    #[schema(example = 120.3)]
+    pub transfers_per_sec: f64,
+    /// 每秒转账的 lamports / Lamports transferred per second
+    #[schema(example = 52000000000.0)]
+    pub lamports_per_sec: f64,
+    /// 每秒转账的 SOL / SOL transferred per second
+    #[schema(example = 52.0)]
+    pub sol_per_sec: f64,
+}
+
+/// 每日转账计数响应 / Daily Transfer Statistics Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DailyTransferStatsResponse {
+    /// 日期（UTC）/ Date (UTC, YYYY-MM-DD)
+    #[schema(example = "2024-01-01")]
+    pub date: String,
+    /// 交易数 / Number of transactions
+    pub transaction_count: u64,
+    /// SOL转账笔数 / Number of SOL transfers
+    pub sol_transfer_count: u64,
+    /// 代币转账笔数 / Number of token transfers
+    pub token_transfer_count: u64,
+    /// 已估值转账的 USD 成交额 / USD volume of transfers priced at ingest time
+    #[schema(example = 42000.0)]
+    pub usd_volume: f64,
+}
+
+/// 每日手续费统计响应 / Daily Fee Statistics Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DailyFeeStatsResponse {
+    /// 日期（UTC）/ Date (UTC, YYYY-MM-DD)
+    #[schema(example = "2024-01-01")]
+    pub date: String,
+    /// 交易数 / Number of transactions
+    pub transaction_count: u64,
+    /// 手续费总额（lamports）/ Total fees (lamports)
+    pub total_fee_lamports: u64,
+    /// 手续费总额（SOL）/ Total fees (SOL)
+    pub total_fee_sol: f64,
+    /// 手续费总额（SOL，精确小数字符串）/ Total fees (SOL) as an exact decimal string
+    pub total_fee_sol_decimal: String,
+    /// 平均手续费（lamports）/ Average fee per transaction (lamports)
+    pub average_fee_lamports: u64,
+    /// 计算单元消耗总量 / Total compute units consumed
+    pub total_compute_units: u64,
 }
 
 /// 地址查询响应 / Address Query Response
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AddressQueryResponse {
     /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
     pub address: String,
+    /// 地址标签 / Known-entity label of the address
+    #[schema(example = "Binance Hot Wallet")]
+    pub label: Option<String>,
     /// 交易记录总数 / Total number of transaction records
     pub total_records: usize,
     /// 交易记录列表（按时间倒序，最新的在前）/ Transaction records list (newest first)
@@ -123,25 +425,40 @@ pub struct AddressQueryResponse {
 /// 地址交易记录响应 / Address Transaction Record Response
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AddressTransactionRecordResponse {
+    /// 确定性转账ID（签名:类型序号:方向）/ Deterministic transfer ID (signature:kind index:leg)
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW:sol0:out")]
+    pub transfer_id: String,
     /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
     pub signature: String,
     /// 交易时间戳 / Transaction timestamp
     pub timestamp: u64,
     /// 交易槽位 / Transaction slot
     pub slot: u64,
+    /// 槽位最终性：unknown/processed/confirmed/finalized/orphaned / Slot finality: unknown/processed/confirmed/finalized/orphaned
+    #[schema(example = "finalized")]
+    pub finality: String,
     /// SOL转账记录（如果有）/ SOL transfer record (if any)
     pub sol_transfer: Option<SolTransferResponse>,
     /// 代币转账记录（如果有）/ Token transfer record (if any)
     pub token_transfer: Option<TokenTransferResponse>,
     /// 记录类型：发送方或接收方 / Record type: sender or receiver
+    #[schema(example = "sender")]
     pub record_type: String,
+    /// 转账方向：in/out/self（发送方与接收方为同一地址）/ Transfer direction: in/out/self (sender and receiver are the same address)
+    #[schema(example = "out")]
+    pub direction: String,
 }
 
 /// 地址统计信息响应 / Address Statistics Response
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AddressStatsResponse {
     /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
     pub address: String,
+    /// 地址标签 / Known-entity label of the address
+    #[schema(example = "Binance Hot Wallet")]
+    pub label: Option<String>,
     /// 总记录数 / Total number of records
     pub total_records: usize,
     /// SOL发送次数 / Number of SOL sent transactions
@@ -158,140 +475,2420 @@ pub struct AddressStatsResponse {
     pub total_sol_received: u64,
     /// 总SOL发送数量（SOL）/ Total SOL sent amount (SOL)
     pub total_sol_sent_formatted: f64,
+    /// 总SOL发送数量（SOL，精确小数字符串）/ Total SOL sent (SOL) as an exact decimal string
+    pub total_sol_sent_decimal: String,
     /// 总SOL接收数量（SOL）/ Total SOL received amount (SOL)
     pub total_sol_received_formatted: f64,
+    /// 总SOL接收数量（SOL，精确小数字符串）/ Total SOL received (SOL) as an exact decimal string
+    pub total_sol_received_decimal: String,
 }
 
-impl<T> ApiResponse<T> {
-    /// Create success response
-    pub fn success(data: T, message: String) -> Self {
-        Self {
-            success: true,
-            message,
-            data: Some(data),
-            timestamp: chrono::Utc::now().timestamp(),
-        }
-    }
+/// 多地址批量查询请求 / Multi-address Batch Query Request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddressBatchQueryRequest {
+    /// 要查询的地址列表（重复地址只返回一次）/ Addresses to query (duplicates are returned once)
+    #[schema(example = json!(["9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"]))]
+    pub addresses: Vec<String>,
+    /// 每个地址返回的最近记录数，默认10，最大100 / Recent records per address, default 10, max 100
+    #[serde(default)]
+    #[schema(example = 10)]
+    pub records_limit: Option<usize>,
+}
 
-    /// Create error response
-    pub fn error(message: String) -> ApiResponse<ErrorResponse> {
-        ApiResponse {
-            success: false,
-            message: message.clone(),
-            data: Some(ErrorResponse { 
-                error: message,
-            }),
-            timestamp: chrono::Utc::now().timestamp(),
-        }
-    }
+/// 多地址批量查询响应 / Multi-address Batch Query Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddressBatchQueryResponse {
+    /// 查询的地址数量 / Number of queried addresses
+    pub total_addresses: usize,
+    /// 按请求顺序返回的各地址结果 / Per-address results in request order
+    pub results: Vec<AddressBatchEntryResponse>,
 }
 
-// 类型转换实现
+/// 批量查询中单个地址的结果 / Single Address Entry in a Batch Query
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddressBatchEntryResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 地址统计信息 / Address statistics
+    pub stats: AddressStatsResponse,
+    /// 最近的交易记录（最新的在前）/ Recent transaction records (newest first)
+    pub recent_records: Vec<AddressTransactionRecordResponse>,
+}
 
-impl From<crate::database::signature_storage::SignatureTransactionData> for SignatureQueryResponse {
-    fn from(data: crate::database::signature_storage::SignatureTransactionData) -> Self {
-        Self {
-            signature: data.signature,
-            sol_transfers: data.sol_transfers.into_iter().map(Into::into).collect(),
-            token_transfers: data.token_transfers.into_iter().map(Into::into).collect(),
-            extracted_addresses: data.extracted_addresses.into(),
-            timestamp: data.timestamp,
-            slot: data.slot,
-            is_successful: data.is_successful,
-        }
-    }
+/// 地址标签响应 / Address Label Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddressLabelResponse {
+    /// 地址 / Address
+    #[schema(example = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9")]
+    pub address: String,
+    /// 显示名称 / Display name
+    #[schema(example = "Binance Hot Wallet")]
+    pub name: String,
+    /// 实体类型，如 exchange / program / bridge / Entity kind, e.g. exchange / program / bridge
+    #[schema(example = "exchange")]
+    pub kind: Option<String>,
+    /// 最后更新时间戳 / Last updated timestamp
+    pub updated_at: i64,
 }
 
-impl From<crate::database::signature_storage::SolTransfer> for SolTransferResponse {
-    fn from(data: crate::database::signature_storage::SolTransfer) -> Self {
-        Self {
-            from: data.from,
-            to: data.to,
-            amount: data.amount,
-            amount_sol: data.amount as f64 / 1_000_000_000.0,
-            transfer_type: data.transfer_type,
-        }
-    }
+/// 设置地址标签请求 / Set Address Label Request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LabelUpdateRequest {
+    /// 显示名称 / Display name
+    #[schema(example = "Binance Hot Wallet")]
+    pub name: String,
+    /// 实体类型（可选）/ Entity kind (optional)
+    #[serde(default)]
+    #[schema(example = "exchange")]
+    pub kind: Option<String>,
 }
 
-impl From<crate::database::signature_storage::TokenTransfer> for TokenTransferResponse {
-    fn from(data: crate::database::signature_storage::TokenTransfer) -> Self {
-        Self {
-            from: data.from,
-            to: data.to,
-            amount: data.amount,
-            amount_formatted: data.amount as f64 / 10_f64.powi(data.decimals as i32),
-            decimals: data.decimals,
-            mint: data.mint,
-            program_id: data.program_id,
-            transfer_type: data.transfer_type,
-        }
-    }
+/// 地址标记响应 / Address Tags Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddressTagsResponse {
+    /// 地址 / Address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 标记（小写，按字母顺序）/ Tags (lowercase, sorted)
+    #[schema(example = json!(["case-42", "mixer"]))]
+    pub tags: Vec<String>,
+    /// 备注 / Note
+    #[schema(example = "first hop after the exploit")]
+    pub note: Option<String>,
+    /// 最后更新时间戳（没有标记时为 0）/ Last updated timestamp (0 when untagged)
+    pub updated_at: i64,
 }
 
-impl From<crate::database::signature_storage::ExtractedAddresses> for ExtractedAddressesResponse {
-    fn from(data: crate::database::signature_storage::ExtractedAddresses) -> Self {
-        Self {
-            all_addresses: data.all_addresses,
-        }
-    }
+/// 添加地址标记请求 / Add Address Tags Request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddressTagsRequest {
+    /// 要追加的标记，不区分大小写，不能包含 `:` / Tags to add, case-insensitive, must not contain `:`
+    #[serde(default)]
+    #[schema(example = json!(["case-42"]))]
+    pub tags: Vec<String>,
+    /// 备注（可选，提供时覆盖原备注）/ Note (optional, replaces the existing note)
+    #[serde(default)]
+    #[schema(example = "first hop after the exploit")]
+    pub note: Option<String>,
 }
 
-impl From<crate::database::address_storage::AddressTransactionRecord> for AddressTransactionRecordResponse {
-    fn from(record: crate::database::address_storage::AddressTransactionRecord) -> Self {
-        Self {
-            signature: record.signature,
-            timestamp: record.timestamp,
-            slot: record.slot,
-            sol_transfer: record.sol_transfer.map(|st| SolTransferResponse {
-                from: st.from,
-                to: st.to,
-                amount: st.amount,
-                amount_sol: st.amount as f64 / 1_000_000_000.0,
-                transfer_type: st.transfer_type,
-            }),
-            token_transfer: record.token_transfer.map(|tt| TokenTransferResponse {
-                from: tt.from,
-                to: tt.to,
-                amount: tt.amount,
-                amount_formatted: tt.amount as f64 / 10_f64.powi(tt.decimals as i32),
-                decimals: tt.decimals as u8,
-                mint: tt.mint,
-                program_id: tt.program_id,
-                transfer_type: tt.transfer_type,
-            }),
-            record_type: match record.record_type {
-                crate::database::address_storage::RecordType::Sender => "sender".to_string(),
-                crate::database::address_storage::RecordType::Receiver => "receiver".to_string(),
-            },
-        }
-    }
+/// 告警 / Alert
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AlertResponse {
+    /// 告警ID / Alert ID
+    pub id: String,
+    /// 规则：fan_out / peel_chain / round_trip / structuring / Rule
+    #[schema(example = "round_trip")]
+    pub rule: String,
+    /// 触发告警的交易签名 / Signature of the triggering transaction
+    pub signature: String,
+    /// 槽位 / Slot
+    pub slot: u64,
+    /// 时间戳 / Timestamp
+    pub timestamp: i64,
+    /// 相关地址，第一个为主要地址 / Related addresses, the primary one first
+    pub addresses: Vec<String>,
+    /// 触发告警的转账金额（lamports）/ Transfer amount that triggered the alert (lamports)
+    pub amount: u64,
+    /// 说明 / Description
+    pub description: String,
 }
 
-impl From<crate::database::address_storage::AddressTransactionList> for AddressQueryResponse {
-    fn from(list: crate::database::address_storage::AddressTransactionList) -> Self {
-        Self {
-            address: list.address,
-            total_records: list.records.len(),
-            records: list.records.into_iter().map(Into::into).collect(),
-            last_updated: list.last_updated,
-        }
-    }
+/// 告警列表响应 / Alerts Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AlertsResponse {
+    /// 规则引擎是否启用 / Whether the rules engine is enabled
+    pub rules_enabled: bool,
+    /// 返回数量 / Number of alerts returned
+    pub count: usize,
+    /// 告警（最新的在前）/ Alerts (newest first)
+    pub alerts: Vec<AlertResponse>,
 }
 
-impl From<crate::database::address_storage::AddressStats> for AddressStatsResponse {
-    fn from(stats: crate::database::address_storage::AddressStats) -> Self {
-        Self {
-            address: stats.address,
-            total_records: stats.total_records,
-            sol_sent_count: stats.sol_sent_count,
-            sol_received_count: stats.sol_received_count,
-            token_sent_count: stats.token_sent_count,
-            token_received_count: stats.token_received_count,
-            total_sol_sent: stats.total_sol_sent,
-            total_sol_received: stats.total_sol_received,
-            total_sol_sent_formatted: stats.total_sol_sent as f64 / 1_000_000_000.0,
-            total_sol_received_formatted: stats.total_sol_received as f64 / 1_000_000_000.0,
-        }
-    }
-} 
\ No newline at end of file
+/// 登记充值地址请求 / Register Deposit Address Request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DepositAddressRequest {
+    /// 充值地址 / Deposit address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 用户ID，不能包含 `:` / User ID, must not contain `:`
+    #[schema(example = "user-1001")]
+    pub user_id: String,
+}
+
+/// 充值地址 / Deposit Address
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DepositAddressResponse {
+    /// 充值地址 / Deposit address
+    pub address: String,
+    /// 用户ID / User ID
+    pub user_id: String,
+    /// 登记时间 / Registration time
+    pub created_at: i64,
+}
+
+/// 充值状态变化 / Deposit Status Transition
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DepositTransitionResponse {
+    /// 状态 / Status
+    #[schema(example = "confirmed")]
+    pub status: String,
+    /// 变化时间 / Transition time
+    pub at: i64,
+}
+
+/// 充值 / Deposit
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DepositResponse {
+    /// 充值ID（签名:序号）/ Deposit ID (signature:index)
+    pub id: String,
+    /// 充值地址 / Deposit address
+    pub address: String,
+    /// 交易签名 / Transaction signature
+    pub signature: String,
+    /// 槽位 / Slot
+    pub slot: u64,
+    /// 时间戳 / Timestamp
+    pub timestamp: i64,
+    /// 转出方 / Sender
+    pub from: String,
+    /// 金额（原始单位）/ Amount (raw units)
+    pub amount: u64,
+    /// 代币 mint，SOL 充值为空 / Token mint, null for SOL deposits
+    pub mint: Option<String>,
+    /// 小数位数 / Decimals
+    pub decimals: u8,
+    /// 状态：processed / confirmed / finalized / orphaned / Status
+    #[schema(example = "finalized")]
+    pub status: String,
+    /// 状态变化记录 / Status transitions
+    pub transitions: Vec<DepositTransitionResponse>,
+}
+
+/// 用户充值查询响应 / User Deposits Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserDepositsResponse {
+    /// 用户ID / User ID
+    pub user_id: String,
+    /// 用户登记的充值地址 / Deposit addresses of the user
+    pub addresses: Vec<String>,
+    /// 返回数量 / Number of deposits returned
+    pub count: usize,
+    /// 充值（最新的在前）/ Deposits (newest first)
+    pub deposits: Vec<DepositResponse>,
+}
+
+/// 登记预期提现请求 / Register Expected Withdrawal Request
+///
+/// 提供 `signature`，或同时提供 `from`、`to` 和 `amount`。
+/// Provide either `signature`, or all of `from`, `to` and `amount`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WithdrawalRequest {
+    /// 运营方的提现编号 / Operator's own withdrawal reference
+    #[schema(example = "wd-20240101-0001")]
+    pub reference: Option<String>,
+    /// 交易签名 / Transaction signature
+    pub signature: Option<String>,
+    /// 转出方 / Sender
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub from: Option<String>,
+    /// 接收方 / Recipient
+    #[schema(example = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9")]
+    pub to: Option<String>,
+    /// 金额（原始单位）/ Amount (raw units)
+    #[schema(example = 1000000000)]
+    pub amount: Option<u64>,
+    /// 代币 mint，SOL 提现不填 / Token mint, omit for SOL withdrawals
+    pub mint: Option<String>,
+}
+
+/// 提现状态变化 / Withdrawal Status Transition
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WithdrawalTransitionResponse {
+    /// 状态 / Status
+    #[schema(example = "observed")]
+    pub status: String,
+    /// 变化时间 / Transition time
+    pub at: i64,
+}
+
+/// 提现状态 / Withdrawal Status
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WithdrawalStatusResponse {
+    /// 提现ID / Withdrawal ID
+    pub id: String,
+    /// 运营方的提现编号 / Operator's own withdrawal reference
+    pub reference: Option<String>,
+    /// 状态：pending / observed / confirmed / finalized / orphaned / Status
+    #[schema(example = "finalized")]
+    pub status: String,
+    /// 预期的交易签名 / Expected transaction signature
+    pub expected_signature: Option<String>,
+    /// 预期的转出方 / Expected sender
+    pub expected_from: Option<String>,
+    /// 预期的接收方 / Expected recipient
+    pub expected_to: Option<String>,
+    /// 预期的金额 / Expected amount
+    pub expected_amount: Option<u64>,
+    /// 预期的代币 mint / Expected token mint
+    pub expected_mint: Option<String>,
+    /// 观察到的交易签名 / Observed transaction signature
+    pub signature: Option<String>,
+    /// 观察到的交易所在槽位 / Slot of the observed transaction
+    pub slot: Option<u64>,
+    /// 登记时间 / Registration time
+    pub created_at: i64,
+    /// 状态变化记录 / Status transitions
+    pub transitions: Vec<WithdrawalTransitionResponse>,
+}
+
+/// 创建 Webhook 订阅请求 / Create Webhook Subscription Request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSubscriptionRequest {
+    /// 订阅的地址，代币转账按所有者钱包匹配 / Address to watch, token transfers match the owner wallet
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 最小金额（原始单位，包含），默认0 / Minimum amount (raw units, inclusive), default 0
+    #[serde(default)]
+    #[schema(example = 1_000_000_000u64)]
+    pub min_amount: Option<u64>,
+    /// 方向 in/out，不指定时都通知 / Direction in/out, both when omitted
+    #[serde(default)]
+    #[schema(example = "in")]
+    pub direction: Option<String>,
+    /// 回调地址（http/https）/ Callback URL (http/https)
+    #[schema(example = "https://example.com/hooks/ledger")]
+    pub callback_url: String,
+    /// HMAC-SHA256 签名密钥 / HMAC-SHA256 signing secret
+    #[schema(example = "change-me")]
+    pub secret: String,
+}
+
+/// Webhook 订阅（不返回密钥）/ Webhook Subscription (secret is never returned)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSubscriptionResponse {
+    /// 订阅ID / Subscription ID
+    pub id: String,
+    /// 订阅的地址 / Watched address
+    pub address: String,
+    /// 最小金额（原始单位）/ Minimum amount (raw units)
+    pub min_amount: u64,
+    /// 方向，为空表示转入转出都通知 / Direction, null means both
+    #[schema(example = "in")]
+    pub direction: Option<String>,
+    /// 回调地址 / Callback URL
+    pub callback_url: String,
+    /// 创建时间 / Creation time
+    pub created_at: i64,
+}
+
+/// Webhook 订阅列表响应 / Webhook Subscriptions Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSubscriptionsResponse {
+    /// 订阅数量 / Number of subscriptions
+    pub count: usize,
+    /// 订阅 / Subscriptions
+    pub subscriptions: Vec<WebhookSubscriptionResponse>,
+}
+
+/// 监控列表导入响应 / Watchlist Import Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WatchlistImportResponse {
+    /// 总行数 / Total number of rows
+    pub total_rows: usize,
+    /// 新增的地址数 / Number of newly added addresses
+    pub accepted: usize,
+    /// 重复的地址数（文件内重复或已在列表中）/ Number of duplicates (within the file or already watched)
+    pub duplicates: usize,
+    /// 被拒绝的行 / Rejected rows
+    pub rejected: Vec<WatchlistRejectedRowResponse>,
+}
+
+/// 被拒绝的导入行 / Rejected Import Row
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WatchlistRejectedRowResponse {
+    /// 行号（从1开始）/ Row number (1-based)
+    pub row: usize,
+    /// 原始地址 / Original address value
+    #[schema(example = "not-an-address")]
+    pub address: String,
+    /// 拒绝原因 / Rejection reason
+    pub reason: String,
+}
+
+/// 摄取状态响应 / Ingest Status Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngestStatusResponse {
+    /// 当前摄取模式：full（完整明细）、summary（槽位汇总）或 sampled（抽样）/ Current ingest mode: full (full detail), summary (per-slot summaries) or sampled
+    #[schema(example = "full")]
+    pub mode: String,
+    /// 进入当前模式的时间戳 / Timestamp when the current mode was entered
+    pub mode_since: i64,
+    /// 模式切换次数 / Number of mode changes
+    pub mode_changes: u64,
+    /// 以汇总形式保存的交易数 / Number of transactions stored as summaries
+    pub summarized_transactions: u64,
+    /// 抽样模式下丢弃的交易数 / Number of transactions dropped by sampling
+    pub sampled_out_transactions: u64,
+    /// 丢弃的交易中的转账笔数 / Number of transfers in dropped transactions
+    pub sampled_out_transfers: u64,
+    /// 丢弃的交易中的 SOL 转账金额（lamports）/ SOL transferred in dropped transactions (lamports)
+    pub sampled_out_lamports: u64,
+    /// 最近一次观测到的处理延迟（毫秒）/ Last observed processing lag (ms)
+    pub last_lag_ms: u64,
+}
+
+/// 健康检查响应 / Health Check Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HealthResponse {
+    /// 总体状态：ok、degraded（槽位落后）、paused（磁盘用量保护暂停写入）或 unavailable（摄取停滞或数据库不可用）/ Overall status: ok, degraded (slot lag), paused (writes paused by the disk usage guardrail) or unavailable (ingestion stalled or database down)
+    #[schema(example = "ok")]
+    pub status: String,
+    /// 数据库是否可读 / Whether the database is readable
+    pub database_ok: bool,
+    /// 数据库是否为只读副本 / Whether the database is a read-only replica
+    pub read_only: bool,
+    /// 摄取链路状态 / Ingestion pipeline status
+    pub ingest: IngestHealthResponse,
+    /// 当前摄取模式：full 或 summary / Current ingest mode: full or summary
+    #[schema(example = "full")]
+    pub ingest_mode: String,
+    /// 死信队列中等待重试的交易数 / Transactions waiting in the dead letter queue
+    pub dead_letter_queue: usize,
+}
+
+/// 摄取链路健康状况 / Ingestion Pipeline Health
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngestHealthResponse {
+    /// 本进程是否运行数据摄取 / Whether this process runs ingestion
+    pub enabled: bool,
+    /// gRPC 订阅流是否已连接 / Whether the gRPC stream is connected
+    pub connected: bool,
+    /// 最近收到 gRPC 消息的时间戳 / Timestamp of the last gRPC message
+    pub last_message_at: Option<i64>,
+    /// 最近成功存储交易的时间戳 / Timestamp of the last successful write
+    pub last_write_at: Option<i64>,
+    /// 最新槽位 / Latest slot seen on the stream
+    #[schema(example = 250000150)]
+    pub latest_slot: u64,
+    /// 最近存储的交易所在槽位 / Slot of the last stored transaction
+    #[schema(example = 250000148)]
+    pub last_stored_slot: u64,
+    /// 落后的槽位数 / Slots behind the latest slot
+    #[schema(example = 2)]
+    pub slot_lag: u64,
+    /// 槽位落后阈值，超过时状态为 degraded / Slot lag threshold above which status is degraded
+    #[schema(example = 150)]
+    pub max_slot_lag: u64,
+    /// 是否停滞 / Whether ingestion has stalled
+    pub stalled: bool,
+    /// 槽位落后是否超过阈值 / Whether the slot lag exceeds the threshold
+    pub lagging: bool,
+    /// 因只调用排除的程序而跳过的交易数 / Transactions skipped because they only invoke excluded programs
+    pub excluded_transactions: u64,
+    /// 数据库所在磁盘的可用空间（MB），未检查时为空 / Free space (MB) on the database disk, absent until checked
+    #[schema(example = 51200)]
+    pub disk_available_mb: Option<u64>,
+    /// 磁盘可用空间是否低于阈值 / Whether free disk space is below the threshold
+    pub disk_low: bool,
+    /// 数据库目录大小（MB），未检查时为空 / Size (MB) of the database directory, absent until checked
+    #[schema(example = 20480)]
+    pub db_size_mb: Option<u64>,
+    /// 是否因磁盘用量保护暂停写入 / Whether writes are paused by the disk usage guardrail
+    pub ingestion_paused: bool,
+    /// 暂停期间丢弃的交易数 / Transactions dropped while writes were paused
+    pub paused_transactions: u64,
+}
+
+/// 槽位汇总响应 / Slot Summary Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SlotSummaryResponse {
+    /// 槽位 / Slot
+    pub slot: u64,
+    /// 汇总的交易数 / Number of summarized transactions
+    pub transaction_count: u64,
+    /// SOL转账笔数 / Number of SOL transfers
+    pub sol_transfer_count: u64,
+    /// 代币转账笔数 / Number of token transfers
+    pub token_transfer_count: u64,
+    /// SOL转账总金额（lamports）/ Total SOL transferred (lamports)
+    pub total_lamports: u64,
+}
+
+/// 槽位交易列表响应 / Slot Transactions Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SlotTransactionsResponse {
+    /// 槽位 / Slot
+    #[schema(example = 245000000)]
+    pub slot: u64,
+    /// 槽位最终性：unknown/processed/confirmed/finalized/orphaned / Slot finality: unknown/processed/confirmed/finalized/orphaned
+    #[schema(example = "finalized")]
+    pub finality: String,
+    /// 交易（按块内序号排序），total 为该槽位采集到的交易总数 / Transactions (ordered by index within the block); total is the number of captured transactions in this slot
+    #[serde(flatten)]
+    pub page: PaginatedResponse<SlotTransactionResponse>,
+}
+
+/// 槽位内的交易 / Transaction in a Slot
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SlotTransactionResponse {
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 块内序号 / Index within the block
+    #[schema(example = 42)]
+    pub transaction_index: Option<u64>,
+    /// 交易时间戳 / Transaction timestamp
+    #[schema(example = 1704067200)]
+    pub timestamp: i64,
+    /// 交易是否成功 / Whether the transaction succeeded
+    pub is_successful: bool,
+    /// SOL转账笔数 / Number of SOL transfers
+    pub sol_transfer_count: usize,
+    /// 代币转账笔数 / Number of token transfers
+    pub token_transfer_count: usize,
+}
+
+/// 程序调用交易列表响应 / Program Transactions Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProgramTransactionsResponse {
+    /// 程序 ID / Program ID
+    #[schema(example = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4")]
+    pub program_id: String,
+    /// 调用该程序的交易（按槽位从新到旧），total 为空 / Transactions invoking the program (newest slot first); total is null
+    #[serde(flatten)]
+    pub page: PaginatedResponse<ProgramTransactionResponse>,
+}
+
+/// 调用程序的交易 / Transaction Invoking a Program
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProgramTransactionResponse {
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 槽位 / Slot
+    #[schema(example = 245000000)]
+    pub slot: u64,
+    /// 交易时间戳 / Transaction timestamp
+    #[schema(example = 1704067200)]
+    pub timestamp: i64,
+    /// 交易是否成功 / Whether the transaction succeeded
+    pub is_successful: bool,
+}
+
+/// 余额历史响应 / Balance History Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BalanceHistoryResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 余额历史点总数 / Total number of balance points
+    pub total_points: usize,
+    /// 余额历史点（按时间倒序，最新的在前）/ Balance points (newest first)
+    pub points: Vec<BalancePointResponse>,
+}
+
+/// 余额历史点响应 / Balance Point Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BalancePointResponse {
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 交易槽位 / Transaction slot
+    pub slot: u64,
+    /// 交易时间戳 / Transaction timestamp
+    pub timestamp: u64,
+    /// 执行前余额（lamports）/ Balance before the transaction (lamports)
+    pub pre_balance: u64,
+    /// 执行后余额（lamports）/ Balance after the transaction (lamports)
+    pub post_balance: u64,
+    /// 执行后余额（SOL）/ Balance after the transaction (SOL)
+    pub post_balance_sol: f64,
+    /// 执行后余额（SOL，精确小数字符串）/ Balance after the transaction (SOL) as an exact decimal string
+    pub post_balance_sol_decimal: String,
+}
+
+/// 账户更新查询响应 / Account Updates Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountUpdatesResponse {
+    /// 账户地址 / Account address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub pubkey: String,
+    /// 返回数量 / Number of updates returned
+    pub count: usize,
+    /// 余额、所有者或数据有变化的更新（最新的在前）/ Updates that changed lamports, owner or data (newest first)
+    pub updates: Vec<AccountUpdateResponse>,
+}
+
+/// 账户更新 / Account Update
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountUpdateResponse {
+    /// 槽位 / Slot
+    pub slot: u64,
+    /// 余额（lamports）/ Balance (lamports)
+    pub lamports: u64,
+    /// 所有者程序 / Owner program
+    #[schema(example = "11111111111111111111111111111111")]
+    pub owner: String,
+    /// 是否为可执行账户 / Whether the account is executable
+    pub executable: bool,
+    /// 配置的数据片段（十六进制）/ Configured data slices (hex)
+    pub data: String,
+    /// 同一槽位内的写入顺序 / Write order within the slot
+    pub write_version: u64,
+    /// 引起更新的交易签名 / Signature of the transaction that caused the update
+    pub txn_signature: Option<String>,
+}
+
+/// 质押操作查询响应 / Stake Events Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StakeEventsResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 质押操作（最新的在前）/ Stake events (newest first)
+    #[serde(flatten)]
+    pub page: PaginatedResponse<StakeEventResponse>,
+}
+
+/// 质押操作响应 / Stake Event Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StakeEventResponse {
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 交易槽位 / Transaction slot
+    #[schema(example = 245000000)]
+    pub slot: u64,
+    /// 操作类型：delegate、deactivate、withdraw / Event kind: delegate, deactivate or withdraw
+    #[schema(example = "delegate")]
+    pub kind: String,
+    /// 质押账户 / Stake account
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
+    pub stake_account: String,
+    /// 授权者（委托/停用为质押授权者，提取为提取授权者）/ Authority (staker for delegate/deactivate, withdrawer for withdraw)
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub authority: String,
+    /// 投票账户（仅委托）/ Vote account (delegate only)
+    pub vote_account: Option<String>,
+    /// 接收方（仅提取）/ Recipient (withdraw only)
+    pub recipient: Option<String>,
+    /// 提取金额（lamports，仅提取）/ Withdrawn amount in lamports (withdraw only)
+    pub lamports: Option<u64>,
+    /// 提取金额（SOL，仅提取）/ Withdrawn amount in SOL (withdraw only)
+    pub amount_sol: Option<f64>,
+    /// 提取金额（SOL，精确小数字符串，仅提取）/ Withdrawn amount in SOL as an exact decimal string (withdraw only)
+    pub amount_sol_decimal: Option<String>,
+    /// 交易时间戳 / Transaction timestamp
+    #[schema(example = 1704067200)]
+    pub timestamp: u32,
+}
+
+/// 转账搜索响应 / Transfer Search Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransferSearchResponse {
+    /// 匹配的转账（最新的在前），total 为空 / Matching transfers (newest first); total is null
+    #[serde(flatten)]
+    pub page: PaginatedResponse<TransferSearchHitResponse>,
+}
+
+/// 地址对转账历史响应 / Transfers Between Addresses Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransfersBetweenResponse {
+    /// 发送方地址 / Sender address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub from: String,
+    /// 接收方地址 / Receiver address
+    #[schema(example = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU")]
+    pub to: String,
+    /// 本页转账数量 / Number of transfers in this page
+    pub count: usize,
+    /// 是否还有更晚的转账 / Whether later transfers exist
+    pub has_more: bool,
+    /// 转账（按时间从早到晚）/ Transfers (oldest first)
+    pub transfers: Vec<TransferSearchHitResponse>,
+}
+
+/// 代币统计响应 / Mint Statistics Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MintStatsResponse {
+    /// 代币 mint / Token mint
+    #[schema(example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")]
+    pub mint: String,
+    /// 代币精度 / Token decimals
+    #[schema(example = 6)]
+    pub decimals: u8,
+    /// 转账笔数 / Number of transfers
+    #[schema(example = 15230)]
+    pub transfer_count: u64,
+    /// 不同发送方数量 / Unique senders
+    #[schema(example = 812)]
+    pub unique_senders: u64,
+    /// 不同接收方数量 / Unique receivers
+    #[schema(example = 1045)]
+    pub unique_receivers: u64,
+    /// 累计转账量（原始单位，十进制字符串）/ Total volume in raw units as a decimal string
+    #[schema(example = "982340000000")]
+    pub total_volume: String,
+    /// 累计转账量（按精度换算）/ Total volume adjusted for decimals
+    #[schema(example = 982340.0)]
+    pub total_volume_ui: f64,
+    /// 累计转账量（按精度换算的精确小数字符串）/ Total volume adjusted for decimals as an exact decimal string
+    #[schema(example = "982340")]
+    pub total_volume_decimal: String,
+    /// 首次转账时间戳 / First transfer timestamp
+    pub first_seen: u64,
+    /// 最近转账时间戳 / Last transfer timestamp
+    pub last_seen: u64,
+}
+
+/// 代币排行响应 / Top Mints Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TopMintsResponse {
+    /// 排行依据：transfers 或 participants / Ranking: transfers or participants
+    #[schema(example = "transfers")]
+    pub by: String,
+    /// 返回的代币数量 / Number of mints returned
+    pub count: usize,
+    /// 代币统计 / Mint statistics
+    pub mints: Vec<MintStatsResponse>,
+}
+
+/// 最大转账榜响应 / Largest Transfers Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LargestTransfersResponse {
+    /// 统计时间窗口 / Time window
+    #[schema(example = "24h")]
+    pub period: String,
+    /// 代币 mint（SOL 榜单为空）/ Token mint (null for the SOL board)
+    pub mint: Option<String>,
+    /// 返回的转账数量 / Number of transfers returned
+    pub count: usize,
+    /// 转账（按金额从大到小）/ Transfers (largest first)
+    pub transfers: Vec<TransferSearchHitResponse>,
+}
+
+/// 转账搜索结果项 / Transfer Search Hit
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransferSearchHitResponse {
+    /// 确定性转账ID / Deterministic transfer ID
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW:sol0:out")]
+    pub transfer_id: String,
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 交易槽位 / Transaction slot
+    #[schema(example = 245000000)]
+    pub slot: u64,
+    /// 槽位最终性：unknown/processed/confirmed/finalized/orphaned / Slot finality: unknown/processed/confirmed/finalized/orphaned
+    #[schema(example = "finalized")]
+    pub finality: String,
+    /// 交易时间戳 / Transaction timestamp
+    pub timestamp: u64,
+    /// 类别：sol 或 token / Category: sol or token
+    #[schema(example = "sol")]
+    pub category: String,
+    /// 发送方地址 / Sender address
+    pub from: String,
+    /// 接收方地址 / Receiver address
+    pub to: String,
+    /// 发送方标签 / Known-entity label of the sender
+    pub from_label: Option<String>,
+    /// 接收方标签 / Known-entity label of the receiver
+    pub to_label: Option<String>,
+    /// 金额（最小单位）/ Amount (raw units)
+    #[schema(example = 1000000000)]
+    pub amount: u64,
+    /// 金额（按小数位换算）/ Amount (decimal adjusted)
+    #[schema(example = 1.0)]
+    pub amount_formatted: f64,
+    /// 金额（按小数位换算的精确小数字符串）/ Amount (decimal adjusted) as an exact decimal string
+    #[schema(example = "1")]
+    pub amount_decimal: String,
+    /// 代币 mint（SOL 转账为空）/ Token mint (null for SOL transfers)
+    pub mint: Option<String>,
+    /// 相对于查询地址的方向：in 或 out（按 mint 搜索时为空）/ Direction relative to the queried address: in or out (null when searching by mint)
+    #[schema(example = "out")]
+    pub direction: Option<String>,
+}
+
+/// 兑换记录查询响应 / Swaps Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SwapsResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 兑换记录（最新的在前）/ Swaps (newest first)
+    #[serde(flatten)]
+    pub page: PaginatedResponse<SwapResponse>,
+}
+
+/// 兑换记录响应 / Swap Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SwapResponse {
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 交易槽位 / Transaction slot
+    #[schema(example = 245000000)]
+    pub slot: u64,
+    /// 发起兑换的所有者 / Owner who performed the swap
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub owner: String,
+    /// DEX 名称 / DEX name
+    #[schema(example = "Jupiter v6")]
+    pub program: String,
+    /// DEX 程序ID / DEX program ID
+    #[schema(example = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4")]
+    pub program_id: String,
+    /// 付出的代币 mint（原生 SOL 记为 wSOL mint）/ Mint given (native SOL is reported as the wSOL mint)
+    #[schema(example = "So11111111111111111111111111111111111111112")]
+    pub token_in: String,
+    /// 付出的数量（最小单位）/ Amount given (raw units)
+    #[schema(example = 1000000000)]
+    pub amount_in: u64,
+    /// 付出的数量（按小数位换算）/ Amount given (decimal adjusted)
+    #[schema(example = 1.0)]
+    pub amount_in_formatted: f64,
+    /// 付出的数量（按小数位换算的精确小数字符串）/ Amount given (decimal adjusted) as an exact decimal string
+    #[schema(example = "1")]
+    pub amount_in_decimal: String,
+    /// 收到的代币 mint / Mint received
+    #[schema(example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")]
+    pub token_out: String,
+    /// 收到的数量（最小单位）/ Amount received (raw units)
+    #[schema(example = 150000000)]
+    pub amount_out: u64,
+    /// 收到的数量（按小数位换算）/ Amount received (decimal adjusted)
+    #[schema(example = 150.0)]
+    pub amount_out_formatted: f64,
+    /// 收到的数量（按小数位换算的精确小数字符串）/ Amount received (decimal adjusted) as an exact decimal string
+    #[schema(example = "150")]
+    pub amount_out_decimal: String,
+    /// 交易时间戳 / Transaction timestamp
+    #[schema(example = 1704067200)]
+    pub timestamp: u32,
+}
+
+/// NFT 转账查询响应 / NFT Transfers Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NftTransfersResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// NFT 转账（最新的在前）/ NFT transfers (newest first)
+    #[serde(flatten)]
+    pub page: PaginatedResponse<NftTransferResponse>,
+}
+
+/// NFT 转账响应 / NFT Transfer Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NftTransferResponse {
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 交易槽位 / Transaction slot
+    #[schema(example = 245000000)]
+    pub slot: u64,
+    /// NFT mint / NFT mint
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
+    pub mint: String,
+    /// 所属合集 mint（交易中能解析到时）/ Collection mint (when resolvable from the transaction)
+    pub collection: Option<String>,
+    /// 发送方代币账户 / Sender token account
+    pub from: String,
+    /// 接收方代币账户 / Receiver token account
+    pub to: String,
+    /// 发送方钱包 / Sender wallet
+    pub from_owner: Option<String>,
+    /// 接收方钱包 / Receiver wallet
+    pub to_owner: Option<String>,
+    /// 交易时间戳 / Transaction timestamp
+    #[schema(example = 1704067200)]
+    pub timestamp: u32,
+}
+
+/// 代币余额查询响应 / Token Balances Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenBalancesResponse {
+    /// 所有者地址 / Owner address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 各代币的余额历史 / Balance history per token
+    pub tokens: Vec<TokenBalanceHistoryResponse>,
+}
+
+/// 单个代币的余额历史响应 / Token Balance History Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenBalanceHistoryResponse {
+    /// 代币mint地址 / Token mint address
+    #[schema(example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")]
+    pub mint: String,
+    /// 代币小数位数 / Token decimals
+    #[schema(example = 6)]
+    pub decimals: u32,
+    /// 最新余额（原始值），为最近一笔交易涉及的代币账户余额之和 / Latest balance (raw), summed over the token accounts touched by the latest transaction
+    pub current_amount: u64,
+    /// 最新余额（可读格式）/ Latest balance (human readable)
+    pub current_amount_formatted: f64,
+    /// 最新余额（精确小数字符串）/ Latest balance as an exact decimal string
+    pub current_amount_decimal: String,
+    /// 余额历史点总数 / Total number of balance points
+    pub total_points: usize,
+    /// 余额历史点（按时间倒序，最新的在前）/ Balance points (newest first)
+    pub points: Vec<TokenBalancePointResponse>,
+}
+
+/// 代币余额历史点响应 / Token Balance Point Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenBalancePointResponse {
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 交易槽位 / Transaction slot
+    pub slot: u64,
+    /// 交易时间戳 / Transaction timestamp
+    pub timestamp: u64,
+    /// 执行后余额（原始值），为本交易涉及的代币账户余额之和 / Balance after the transaction (raw), summed over the token accounts it touched
+    pub amount: u64,
+    /// 执行后余额（可读格式）/ Balance after the transaction (human readable)
+    pub amount_formatted: f64,
+    /// 执行后余额（精确小数字符串）/ Balance after the transaction as an exact decimal string
+    pub amount_decimal: String,
+}
+
+/// 交易对手查询响应 / Counterparties Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CounterpartiesResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 交易对手（按往来笔数倒序）/ Counterparties (most interactions first)
+    #[serde(flatten)]
+    pub page: PaginatedResponse<CounterpartyResponse>,
+}
+
+/// 交易对手响应 / Counterparty Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CounterpartyResponse {
+    /// 交易对手地址 / Counterparty address
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
+    pub address: String,
+    /// 往来转账笔数 / Number of transfers between the two addresses
+    pub interaction_count: u64,
+    /// 转给对方的 SOL（lamports）/ SOL sent to the counterparty (lamports)
+    pub sol_sent_lamports: u64,
+    /// 从对方收到的 SOL（lamports）/ SOL received from the counterparty (lamports)
+    pub sol_received_lamports: u64,
+    /// 双向 SOL 总额（SOL）/ Total SOL volume in both directions (SOL)
+    pub sol_volume: f64,
+    /// 双向 SOL 总额（SOL，精确小数字符串）/ Total SOL volume in both directions (SOL) as an exact decimal string
+    pub sol_volume_decimal: String,
+    /// 代币转账笔数 / Number of token transfers
+    pub token_transfer_count: u64,
+    /// 首次往来时间戳 / First interaction timestamp
+    pub first_seen: u64,
+    /// 最近往来时间戳 / Last interaction timestamp
+    pub last_seen: u64,
+}
+
+/// 手续费代付关系响应 / Fee Payer Relations Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FeeRelationsResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 关联地址总数 / Total number of related addresses
+    pub total_relations: usize,
+    /// 关联地址（按交易笔数倒序）/ Related addresses (most transactions first)
+    pub relations: Vec<FeeRelationResponse>,
+}
+
+/// 手续费代付关系 / Fee Payer Relation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FeeRelationResponse {
+    /// 关联地址（支付者或被代付的账户）/ Related address (fee payer or funded account)
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
+    pub address: String,
+    /// 代付的交易笔数 / Number of transactions whose fee was paid
+    pub transaction_count: u64,
+    /// 这些交易的手续费合计（lamports）/ Total fees of these transactions (lamports)
+    pub fee_lamports: u64,
+    /// 这些交易的手续费合计（SOL）/ Total fees of these transactions (SOL)
+    pub fee_sol: f64,
+    /// 这些交易的手续费合计（SOL，精确小数字符串）/ Total fees of these transactions (SOL) as an exact decimal string
+    pub fee_sol_decimal: String,
+    /// 首次代付时间戳 / First seen timestamp
+    pub first_seen: u64,
+    /// 最近代付时间戳 / Last seen timestamp
+    pub last_seen: u64,
+}
+
+/// 地址活跃度时间线响应 / Address Activity Timeline Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ActivityResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 时间桶粒度（hour 或 day）/ Bucket granularity (hour or day)
+    #[schema(example = "day")]
+    pub granularity: String,
+    /// 有活动的时间桶（按时间升序，无活动的桶省略）/ Buckets with activity, oldest first (empty buckets are omitted)
+    pub buckets: Vec<ActivityBucketResponse>,
+}
+
+/// 活跃度时间桶 / Activity Bucket
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ActivityBucketResponse {
+    /// 桶起始时间戳（UTC）/ Bucket start timestamp (UTC)
+    #[schema(example = 1704067200)]
+    pub bucket_start: u64,
+    /// 涉及该地址的交易数 / Transactions involving the address
+    pub transaction_count: u64,
+    /// SOL 转账笔数 / Number of SOL transfers
+    pub sol_transfer_count: u64,
+    /// 转出 SOL（lamports）/ SOL sent (lamports)
+    pub sol_sent_lamports: u64,
+    /// 转入 SOL（lamports）/ SOL received (lamports)
+    pub sol_received_lamports: u64,
+    /// 双向 SOL 总额（SOL）/ Total SOL volume in both directions (SOL)
+    pub sol_volume: f64,
+    /// 双向 SOL 总额（SOL，精确小数字符串）/ Total SOL volume in both directions (SOL) as an exact decimal string
+    pub sol_volume_decimal: String,
+    /// 代币转账笔数 / Number of token transfers
+    pub token_transfer_count: u64,
+}
+
+/// 地址每日汇总响应 / Address Daily Summary Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddressDailySummaryResponse {
+    /// 查询的地址 / Queried address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub address: String,
+    /// 有转账的日期（按日期升序，已汇总的历史与保留的原始记录合并）/ Days with transfers, oldest first (rolled-up history merged with retained raw records)
+    pub days: Vec<DailyAddressSummaryResponse>,
+}
+
+/// 地址单日汇总 / Single-Day Address Summary
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DailyAddressSummaryResponse {
+    /// 日期（UTC，YYYY-MM-DD）/ Date (UTC, YYYY-MM-DD)
+    #[schema(example = "2024-01-01")]
+    pub date: String,
+    /// 转账记录数 / Number of transfer records
+    pub total_records: u64,
+    /// SOL发送次数 / SOL send count
+    pub sol_sent_count: u64,
+    /// SOL接收次数 / SOL receive count
+    pub sol_received_count: u64,
+    /// 代币发送次数 / Token send count
+    pub token_sent_count: u64,
+    /// 代币接收次数 / Token receive count
+    pub token_received_count: u64,
+    /// 转出 SOL（lamports）/ SOL sent (lamports)
+    pub total_sol_sent: u64,
+    /// 转入 SOL（lamports）/ SOL received (lamports)
+    pub total_sol_received: u64,
+    /// 当天最早的转账时间戳 / Earliest transfer timestamp of the day
+    pub first_timestamp: u64,
+    /// 当天最晚的转账时间戳 / Latest transfer timestamp of the day
+    pub last_timestamp: u64,
+}
+
+/// 转账关系图响应 / Transfer Graph Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GraphResponse {
+    /// 根地址 / Root address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub root: String,
+    /// 展开深度 / Expansion depth
+    pub depth: usize,
+    /// 节点 / Nodes
+    pub nodes: Vec<GraphNodeResponse>,
+    /// 边 / Edges
+    pub edges: Vec<GraphEdgeResponse>,
+}
+
+/// 转账关系图节点 / Graph Node
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GraphNodeResponse {
+    /// 地址 / Address
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
+    pub id: String,
+    /// 与根地址的距离 / Distance from the root address
+    pub depth: usize,
+}
+
+/// 转账关系图边（无向）/ Graph Edge (undirected)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GraphEdgeResponse {
+    /// 离根地址较近的一端 / End closer to the root
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub source: String,
+    /// 另一端 / Other end
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
+    pub target: String,
+    /// 往来转账笔数 / Number of transfers
+    pub interaction_count: u64,
+    /// 双向 SOL 总额（lamports）/ Total SOL volume (lamports)
+    pub sol_volume_lamports: u64,
+    /// 代币转账笔数 / Number of token transfers
+    pub token_transfer_count: u64,
+}
+
+/// 资金流追踪请求 / Flow Trace Request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FlowTraceRequest {
+    /// 起点地址 / Source address
+    #[schema(example = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")]
+    pub source: String,
+    /// 资产：SOL 或代币 mint，默认 SOL / Asset: SOL or a token mint, default SOL
+    #[serde(default)]
+    #[schema(example = "SOL")]
+    pub asset: Option<String>,
+    /// 最多追踪的跳数，默认3，最大10 / Maximum hops to follow, default 3, max 10
+    #[serde(default)]
+    #[schema(example = 3)]
+    pub max_hops: Option<usize>,
+    /// 只追踪该时间戳（含）之后起点转出的资金 / Only follow funds leaving the source at or after this timestamp
+    #[serde(default)]
+    #[schema(example = 1704067200)]
+    pub start_time: Option<u64>,
+    /// 节点预算（含起点），默认200，最大2000 / Node budget including the source, default 200, max 2000
+    #[serde(default)]
+    #[schema(example = 200)]
+    pub max_nodes: Option<usize>,
+}
+
+/// 资金流追踪响应 / Flow Trace Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FlowTraceResponse {
+    /// 追踪的资产 / Traced asset
+    #[schema(example = "SOL")]
+    pub asset: String,
+    /// 资产精度 / Asset decimals
+    #[schema(example = 9)]
+    pub decimals: u8,
+    /// 最多追踪的跳数 / Maximum hops followed
+    pub max_hops: usize,
+    /// 展开的节点数 / Number of nodes expanded
+    pub nodes_visited: usize,
+    /// 是否因节点预算用完而提前停止 / Whether expansion stopped because the node budget ran out
+    pub truncated: bool,
+    /// 资金流树，根为起点地址 / Flow tree rooted at the source address
+    pub root: FlowNodeResponse,
+}
+
+/// 资金流节点 / Flow Node
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FlowNodeResponse {
+    /// 地址 / Address
+    #[schema(example = "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy")]
+    pub address: String,
+    /// 与起点的跳数 / Hops from the source
+    pub hop: usize,
+    /// 追踪到该地址的金额（原始单位），起点为其转出总额 / Traced amount in raw units (total outflow for the source)
+    #[schema(example = 1000000000)]
+    pub amount: u64,
+    /// 追踪到该地址的金额（按精度换算）/ Traced amount adjusted for decimals
+    #[schema(example = 1.0)]
+    pub amount_formatted: f64,
+    /// 追踪到该地址的金额（按精度换算的精确小数字符串）/ Traced amount adjusted for decimals as an exact decimal string
+    #[schema(example = "1")]
+    pub amount_decimal: String,
+    /// 从上一跳收到的转账笔数 / Transfers received from the previous hop
+    pub transfer_count: usize,
+    /// 首次从上一跳收到资金的时间戳 / Timestamp of the first transfer from the previous hop
+    pub first_timestamp: u64,
+    /// 首笔转入的签名 / Signature of the first transfer from the previous hop
+    pub first_signature: Option<String>,
+    /// 下一跳（按金额倒序）/ Next hops (largest amount first)
+    pub children: Vec<FlowNodeResponse>,
+}
+
+/// 数据库属性响应（解析后的 RocksDB 属性）/ Database Properties Response (parsed RocksDB properties)
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DbStatsResponse {
+    /// 估算的键数量 / Estimated number of keys
+    pub estimate_num_keys: u64,
+    /// SST 文件总大小（字节）/ Total SST files size (bytes)
+    pub total_sst_files_size: u64,
+    /// 活跃 SST 文件大小（字节）/ Live SST files size (bytes)
+    pub live_sst_files_size: u64,
+    /// 估算的有效数据大小（字节）/ Estimated live data size (bytes)
+    pub estimate_live_data_size: u64,
+    /// 内存表大小（字节）/ Size of all memtables (bytes)
+    pub cur_size_all_mem_tables: u64,
+    /// 估算的待压缩字节数 / Estimated pending compaction bytes
+    pub estimate_pending_compaction_bytes: u64,
+    /// 是否有等待中的压缩 / Whether a compaction is pending
+    pub compaction_pending: bool,
+    /// 运行中的压缩数量 / Number of running compactions
+    pub num_running_compactions: u64,
+    /// 运行中的刷写数量 / Number of running flushes
+    pub num_running_flushes: u64,
+    /// 每层的文件数量（索引即层号）/ Number of files per level (index is the level)
+    pub num_files_at_level: Vec<u64>,
+}
+
+/// 数据库压缩响应 / Compaction Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CompactResponse {
+    /// 是否完成压缩 / Whether compaction completed
+    pub compacted: bool,
+    /// 压缩耗时（毫秒）/ Compaction duration (milliseconds)
+    pub duration_ms: u64,
+}
+
+/// 创建快照请求 / Create Snapshot Request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BackupRequest {
+    /// 快照输出目录（服务端路径，需与数据库位于同一文件系统）/ Output directory on the server (same filesystem as the database)
+    #[schema(example = "data/backups")]
+    pub target_dir: String,
+    /// 是否压缩为 tar.zst / Whether to compress the snapshot as tar.zst
+    #[serde(default)]
+    #[schema(example = true)]
+    pub compress: bool,
+}
+
+/// 创建快照响应 / Create Snapshot Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BackupResponse {
+    /// 快照路径 / Snapshot path
+    #[schema(example = "data/backups/snapshot-20240101-000000.tar.zst")]
+    pub path: String,
+    /// 快照中的签名数量 / Number of signatures in the snapshot
+    #[schema(example = 125000)]
+    pub signature_count: usize,
+    /// 创建耗时（毫秒）/ Duration (milliseconds)
+    pub duration_ms: u64,
+}
+
+/// 删除签名响应 / Delete Signature Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeleteSignatureResponse {
+    /// 签名 / Signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 是否删除了已存在的数据 / Whether existing data was deleted
+    pub deleted: bool,
+}
+
+/// 死信队列中的交易 / Dead-lettered Transaction
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterResponse {
+    /// 交易签名 / Transaction signature
+    #[schema(example = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW")]
+    pub signature: String,
+    /// 槽位 / Slot
+    #[schema(example = 250000000)]
+    pub slot: u64,
+    /// 接收时间戳 / Received timestamp
+    pub timestamp: i64,
+    /// 最近一次错误 / Last error
+    #[schema(example = "IO error: No space left on device")]
+    pub last_error: String,
+    /// 已失败次数 / Number of failed attempts
+    #[schema(example = 3)]
+    pub attempts: u32,
+    /// 首次失败时间戳 / First failure timestamp
+    pub first_failed_at: i64,
+    /// 下次重试时间戳 / Next retry timestamp
+    pub next_retry_at: i64,
+    /// 原始数据大小（字节）/ Raw payload size (bytes)
+    pub payload_bytes: usize,
+}
+
+/// 配置重新加载响应 / Config Reload Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConfigReloadResponse {
+    /// 发生变化并已生效的配置段（monitor、parser）/ Sections that changed and were applied (monitor, parser)
+    pub changed: Vec<String>,
+}
+
+/// 摄取审计日志条目 / Ingestion Audit Log Entry
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngestionLogEntryResponse {
+    /// 条目编号（按时间递增）/ Entry id (increasing over time)
+    #[schema(example = "001704067200000:000042")]
+    pub id: String,
+    /// 记录时间戳 / Recorded timestamp
+    pub timestamp: i64,
+    /// 事件类型 / Event kind
+    #[schema(example = "slot_gap")]
+    pub kind: String,
+    /// 事件详情 / Event details
+    #[schema(value_type = Object, example = json!({"last_seen_slot": 250000000, "parent_slot": 250000012, "slot": 250000013}))]
+    pub details: serde_json::Value,
+}
+
+/// 摄取审计日志响应 / Ingestion Audit Log Response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngestionLogResponse {
+    /// 返回的条目数 / Number of entries returned
+    pub count: usize,
+    /// 条目列表（最新的在前）/ Entries, newest first
+    pub entries: Vec<IngestionLogEntryResponse>,
+}
+
+impl<T> ApiResponse<T> {
+    /// Create success response
+    pub fn success(data: T, message: String) -> Self {
+        Self::success_with_clock(data, message, &SystemClock)
+    }
+
+    /// Create success response stamped by the given clock
+    pub fn success_with_clock(data: T, message: String, clock: &dyn Clock) -> Self {
+        Self {
+            success: true,
+            message,
+            data: Some(data),
+            timestamp: clock.timestamp(),
+        }
+    }
+
+    /// Create error response
+    pub fn error(message: String) -> ApiResponse<ErrorResponse> {
+        Self::error_with_clock(message, &SystemClock)
+    }
+
+    /// Create error response stamped by the given clock
+    pub fn error_with_clock(message: String, clock: &dyn Clock) -> ApiResponse<ErrorResponse> {
+        ApiResponse {
+            success: false,
+            message: message.clone(),
+            data: Some(ErrorResponse { 
+                error: message,
+            }),
+            timestamp: clock.timestamp(),
+        }
+    }
+}
+
+impl<T> PaginatedResponse<T> {
+    /// 从完整列表中切出 `offset` 开始的一页
+    ///
+    /// 游标即下一页的起始偏移量，客户端只需原样传回，不必自行计算。
+    pub fn from_offset(mut all: Vec<T>, offset: usize, limit: usize) -> Self {
+        let total = all.len();
+        let end = offset.saturating_add(limit).min(total);
+        let items: Vec<T> = if offset >= total { Vec::new() } else { all.drain(offset..end).collect() };
+        let has_more = end < total && offset < total;
+        Self {
+            items,
+            total: Some(total),
+            next_cursor: has_more.then(|| end.to_string()),
+            has_more,
+        }
+    }
+
+    /// 由多取一条的查询结果构建一页：`items` 最多含 `limit + 1` 条，多出的一条只用于判断是否还有更多
+    pub fn from_page(mut items: Vec<T>, offset: usize, limit: usize, total: Option<usize>) -> Self {
+        let has_more = items.len() > limit;
+        items.truncate(limit);
+        Self {
+            next_cursor: has_more.then(|| (offset + items.len()).to_string()),
+            items,
+            total,
+            has_more,
+        }
+    }
+
+    /// 由存储层按游标分页的结果构建一页，游标原样返回，总数未知
+    pub fn from_cursor(items: Vec<T>, next_cursor: Option<String>) -> Self {
+        Self {
+            items,
+            total: None,
+            has_more: next_cursor.is_some(),
+            next_cursor,
+        }
+    }
+
+    /// 转换本页数据，分页信息不变
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PaginatedResponse<U> {
+        PaginatedResponse {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            next_cursor: self.next_cursor,
+            has_more: self.has_more,
+        }
+    }
+}
+
+// 类型转换实现
+
+impl From<crate::database::signature_storage::SignatureTransactionData> for SignatureQueryResponse {
+    fn from(data: crate::database::signature_storage::SignatureTransactionData) -> Self {
+        let valuation = data.usd_valuation.unwrap_or_default();
+        let with_usd = |values: &[Option<f64>], index: usize| values.get(index).copied().flatten();
+        Self {
+            signature: data.signature,
+            sol_transfers: data
+                .sol_transfers
+                .into_iter()
+                .enumerate()
+                .map(|(index, transfer)| SolTransferResponse {
+                    usd_value: with_usd(&valuation.sol_transfers, index),
+                    ..transfer.into()
+                })
+                .collect(),
+            token_transfers: data
+                .token_transfers
+                .into_iter()
+                .enumerate()
+                .map(|(index, transfer)| TokenTransferResponse {
+                    usd_value: with_usd(&valuation.token_transfers, index),
+                    ..transfer.into()
+                })
+                .collect(),
+            stake_events: data
+                .stake_events
+                .into_iter()
+                .map(|event| crate::database::StakeSlotEvent { slot: data.slot, event }.into())
+                .collect(),
+            swaps: data
+                .swaps
+                .into_iter()
+                .map(|swap| crate::database::SwapSlotRecord { slot: data.slot, swap }.into())
+                .collect(),
+            nft_transfers: data
+                .nft_transfers
+                .into_iter()
+                .map(|transfer| crate::database::NftSlotTransfer { slot: data.slot, transfer }.into())
+                .collect(),
+            extracted_addresses: data.extracted_addresses.into(),
+            timestamp: data.timestamp,
+            slot: data.slot,
+            finality: FinalityStatus::Unknown.as_str().to_string(),
+            is_successful: data.is_successful,
+            failure: data.failure.map(Into::into),
+            fee_lamports: data.fee_lamports,
+            compute_units: data.compute_units,
+            fee_payer: data.fee_payer,
+            transaction_index: data.transaction_index,
+            block_time: data.block_time,
+            total_usd_value: (!valuation.provider.is_empty()).then_some(valuation.total_usd),
+            price_provider: (!valuation.provider.is_empty()).then_some(valuation.provider),
+        }
+    }
+}
+
+impl From<crate::transfer_types::TransactionFailure> for TransactionFailureResponse {
+    fn from(failure: crate::transfer_types::TransactionFailure) -> Self {
+        use crate::transfer_types::{InstructionFailure, TransactionFailure};
+
+        let category = failure.category();
+        let (instruction_index, custom_code, account_index) = match failure {
+            TransactionFailure::InstructionError { index, error: InstructionFailure::Custom { code } } => {
+                (Some(index), Some(code), None)
+            }
+            TransactionFailure::InstructionError { index, .. } | TransactionFailure::DuplicateInstruction { index } => {
+                (Some(index), None, None)
+            }
+            TransactionFailure::InsufficientFundsForRent { account_index } => (None, None, Some(account_index)),
+            _ => (None, None, None),
+        };
+        Self { category, instruction_index, custom_code, account_index }
+    }
+}
+
+impl From<crate::database::DailyTransferStats> for DailyTransferStatsResponse {
+    fn from(stats: crate::database::DailyTransferStats) -> Self {
+        Self {
+            date: stats.date,
+            transaction_count: stats.transaction_count,
+            sol_transfer_count: stats.sol_transfer_count,
+            token_transfer_count: stats.token_transfer_count,
+            usd_volume: stats.usd_volume,
+        }
+    }
+}
+
+impl From<crate::database::DailyActiveAddresses> for DailyActiveAddressesResponse {
+    fn from(stats: crate::database::DailyActiveAddresses) -> Self {
+        Self {
+            date: stats.date,
+            active_addresses: stats.active_addresses,
+        }
+    }
+}
+
+impl From<crate::database::DailyFeeStats> for DailyFeeStatsResponse {
+    fn from(stats: crate::database::DailyFeeStats) -> Self {
+        Self {
+            total_fee_sol: Lamports(stats.total_fee_lamports).as_sol(),
+            total_fee_sol_decimal: Lamports(stats.total_fee_lamports).to_string(),
+            average_fee_lamports: stats.total_fee_lamports.checked_div(stats.transaction_count).unwrap_or(0),
+            date: stats.date,
+            transaction_count: stats.transaction_count,
+            total_fee_lamports: stats.total_fee_lamports,
+            total_compute_units: stats.total_compute_units,
+        }
+    }
+}
+
+impl From<crate::database::signature_storage::SolTransfer> for SolTransferResponse {
+    fn from(data: crate::database::signature_storage::SolTransfer) -> Self {
+        Self {
+            from: data.from,
+            to: data.to,
+            amount: data.amount,
+            amount_sol: Lamports(data.amount).as_sol(),
+            amount_sol_decimal: Lamports(data.amount).to_string(),
+            transfer_type: data.transfer_type,
+            is_wrapped: data.is_wrapped,
+            instruction_path: data.instruction_path,
+            usd_value: None,
+            from_label: None,
+            to_label: None,
+        }
+    }
+}
+
+impl From<crate::database::signature_storage::TokenTransfer> for TokenTransferResponse {
+    fn from(data: crate::database::signature_storage::TokenTransfer) -> Self {
+        Self {
+            from: data.from,
+            to: data.to,
+            amount: data.amount,
+            amount_formatted: TokenAmount::new(data.amount, data.decimals).ui_amount(),
+            amount_decimal: TokenAmount::new(data.amount, data.decimals).to_string(),
+            decimals: data.decimals,
+            mint: data.mint,
+            program_id: data.program_id,
+            transfer_type: data.transfer_type,
+            from_owner: data.from_owner,
+            to_owner: data.to_owner,
+            usd_value: None,
+            from_label: None,
+            to_label: None,
+        }
+    }
+}
+
+impl From<crate::database::signature_storage::ExtractedAddresses> for ExtractedAddressesResponse {
+    fn from(data: crate::database::signature_storage::ExtractedAddresses) -> Self {
+        Self {
+            all_addresses: data.all_addresses,
+            signers: data.signers,
+            writable: data.writable,
+            readonly: data.readonly,
+            programs: data.programs,
+        }
+    }
+}
+
+impl From<crate::database::address_storage::AddressTransactionRecord> for AddressTransactionRecordResponse {
+    fn from(record: crate::database::address_storage::AddressTransactionRecord) -> Self {
+        let direction = record.direction().as_str().to_string();
+        Self {
+            transfer_id: record.transfer_id,
+            signature: record.signature,
+            timestamp: record.timestamp,
+            slot: record.slot,
+            finality: FinalityStatus::Unknown.as_str().to_string(),
+            sol_transfer: record.sol_transfer.map(|st| SolTransferResponse {
+                from: st.from,
+                to: st.to,
+                amount: st.amount,
+                amount_sol: Lamports(st.amount).as_sol(),
+                amount_sol_decimal: Lamports(st.amount).to_string(),
+                transfer_type: st.transfer_type,
+                is_wrapped: st.is_wrapped,
+                instruction_path: st.instruction_path,
+                usd_value: None,
+                from_label: None,
+                to_label: None,
+            }),
+            token_transfer: record.token_transfer.map(|tt| TokenTransferResponse {
+                from: tt.from,
+                to: tt.to,
+                amount: tt.amount,
+                amount_formatted: TokenAmount::new(tt.amount, tt.decimals as u8).ui_amount(),
+                amount_decimal: TokenAmount::new(tt.amount, tt.decimals as u8).to_string(),
+                decimals: tt.decimals as u8,
+                mint: tt.mint,
+                program_id: tt.program_id,
+                transfer_type: tt.transfer_type,
+                from_owner: tt.from_owner,
+                to_owner: tt.to_owner,
+                usd_value: None,
+                from_label: None,
+                to_label: None,
+            }),
+            record_type: match record.record_type {
+                crate::database::address_storage::RecordType::Sender => "sender".to_string(),
+                crate::database::address_storage::RecordType::Receiver => "receiver".to_string(),
+            },
+            direction,
+        }
+    }
+}
+
+impl From<crate::database::address_storage::AddressTransactionList> for AddressQueryResponse {
+    fn from(list: crate::database::address_storage::AddressTransactionList) -> Self {
+        Self {
+            address: list.address,
+            label: None,
+            total_records: list.records.len(),
+            records: list.records.into_iter().map(Into::into).collect(),
+            last_updated: list.last_updated,
+        }
+    }
+}
+
+impl From<crate::database::address_storage::AddressStats> for AddressStatsResponse {
+    fn from(stats: crate::database::address_storage::AddressStats) -> Self {
+        Self {
+            address: stats.address,
+            label: None,
+            total_records: stats.total_records,
+            sol_sent_count: stats.sol_sent_count,
+            sol_received_count: stats.sol_received_count,
+            token_sent_count: stats.token_sent_count,
+            token_received_count: stats.token_received_count,
+            total_sol_sent: stats.total_sol_sent,
+            total_sol_received: stats.total_sol_received,
+            total_sol_sent_formatted: Lamports(stats.total_sol_sent).as_sol(),
+            total_sol_sent_decimal: Lamports(stats.total_sol_sent).to_string(),
+            total_sol_received_formatted: Lamports(stats.total_sol_received).as_sol(),
+            total_sol_received_decimal: Lamports(stats.total_sol_received).to_string(),
+        }
+    }
+} 
+impl From<crate::health::IngestHealth> for IngestHealthResponse {
+    fn from(health: crate::health::IngestHealth) -> Self {
+        Self {
+            enabled: health.enabled,
+            connected: health.connected,
+            last_message_at: health.last_message_at,
+            last_write_at: health.last_write_at,
+            latest_slot: health.latest_slot,
+            last_stored_slot: health.last_stored_slot,
+            slot_lag: health.slot_lag,
+            max_slot_lag: health.max_slot_lag,
+            stalled: health.stalled,
+            lagging: health.lagging,
+            excluded_transactions: health.excluded_transactions,
+            disk_available_mb: health.disk_available_mb,
+            disk_low: health.disk_low,
+            db_size_mb: health.db_size_mb,
+            ingestion_paused: health.ingestion_paused,
+            paused_transactions: health.paused_transactions,
+        }
+    }
+}
+
+impl From<crate::database::MintStats> for MintStatsResponse {
+    fn from(stats: crate::database::MintStats) -> Self {
+        Self {
+            total_volume: stats.total_volume.to_string(),
+            total_volume_ui: TokenAmount::new(stats.total_volume, stats.decimals).ui_amount(),
+            total_volume_decimal: TokenAmount::new(stats.total_volume, stats.decimals).to_string(),
+            mint: stats.mint,
+            decimals: stats.decimals,
+            transfer_count: stats.transfer_count,
+            unique_senders: stats.unique_senders,
+            unique_receivers: stats.unique_receivers,
+            first_seen: stats.first_seen,
+            last_seen: stats.last_seen,
+        }
+    }
+}
+
+impl From<crate::database::DeadLetter> for DeadLetterResponse {
+    fn from(letter: crate::database::DeadLetter) -> Self {
+        Self {
+            signature: letter.signature,
+            slot: letter.slot,
+            timestamp: letter.timestamp,
+            last_error: letter.last_error,
+            attempts: letter.attempts,
+            first_failed_at: letter.first_failed_at,
+            next_retry_at: letter.next_retry_at,
+            payload_bytes: letter.payload.len(),
+        }
+    }
+}
+
+impl From<crate::database::AddressLabel> for AddressLabelResponse {
+    fn from(label: crate::database::AddressLabel) -> Self {
+        Self {
+            address: label.address,
+            name: label.name,
+            kind: label.kind,
+            updated_at: label.updated_at,
+        }
+    }
+}
+
+impl From<crate::database::Alert> for AlertResponse {
+    fn from(alert: crate::database::Alert) -> Self {
+        Self {
+            id: alert.id,
+            rule: alert.rule.as_str().to_string(),
+            signature: alert.signature,
+            slot: alert.slot,
+            timestamp: alert.timestamp,
+            addresses: alert.addresses,
+            amount: alert.amount,
+            description: alert.description,
+        }
+    }
+}
+
+impl From<crate::database::DepositAddress> for DepositAddressResponse {
+    fn from(entry: crate::database::DepositAddress) -> Self {
+        Self {
+            address: entry.address,
+            user_id: entry.user_id,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+impl From<crate::database::Deposit> for DepositResponse {
+    fn from(deposit: crate::database::Deposit) -> Self {
+        Self {
+            id: deposit.id,
+            address: deposit.address,
+            signature: deposit.signature,
+            slot: deposit.slot,
+            timestamp: deposit.timestamp,
+            from: deposit.from,
+            amount: deposit.amount,
+            mint: deposit.mint,
+            decimals: deposit.decimals,
+            status: deposit.status.as_str().to_string(),
+            transitions: deposit
+                .transitions
+                .into_iter()
+                .map(|transition| DepositTransitionResponse {
+                    status: transition.status.as_str().to_string(),
+                    at: transition.at,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<crate::database::Withdrawal> for WithdrawalStatusResponse {
+    fn from(withdrawal: crate::database::Withdrawal) -> Self {
+        let (expected_signature, expected_from, expected_to, expected_amount, expected_mint) = match withdrawal.expected {
+            crate::database::ExpectedWithdrawal::Signature { signature } => (Some(signature), None, None, None, None),
+            crate::database::ExpectedWithdrawal::Transfer { from, to, amount, mint } => {
+                (None, Some(from), Some(to), Some(amount), mint)
+            }
+        };
+        Self {
+            id: withdrawal.id,
+            reference: withdrawal.reference,
+            status: withdrawal.status.as_str().to_string(),
+            expected_signature,
+            expected_from,
+            expected_to,
+            expected_amount,
+            expected_mint,
+            signature: withdrawal.signature,
+            slot: withdrawal.slot,
+            created_at: withdrawal.created_at,
+            transitions: withdrawal
+                .transitions
+                .into_iter()
+                .map(|transition| WithdrawalTransitionResponse {
+                    status: transition.status.as_str().to_string(),
+                    at: transition.at,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<crate::database::AccountUpdate> for AccountUpdateResponse {
+    fn from(update: crate::database::AccountUpdate) -> Self {
+        Self {
+            slot: update.slot,
+            lamports: update.lamports,
+            owner: update.owner,
+            executable: update.executable,
+            data: update.data,
+            write_version: update.write_version,
+            txn_signature: update.txn_signature,
+        }
+    }
+}
+
+impl From<crate::database::WebhookSubscription> for WebhookSubscriptionResponse {
+    fn from(subscription: crate::database::WebhookSubscription) -> Self {
+        Self {
+            id: subscription.id,
+            address: subscription.address,
+            min_amount: subscription.min_amount,
+            direction: subscription.direction.map(|direction| direction.as_str().to_string()),
+            callback_url: subscription.callback_url,
+            created_at: subscription.created_at,
+        }
+    }
+}
+
+impl From<crate::database::AddressTags> for AddressTagsResponse {
+    fn from(entry: crate::database::AddressTags) -> Self {
+        Self {
+            address: entry.address,
+            tags: entry.tags.into_iter().collect(),
+            note: entry.note,
+            updated_at: entry.updated_at,
+        }
+    }
+}
+
+impl From<crate::database::watchlist_storage::WatchlistImportSummary> for WatchlistImportResponse {
+    fn from(summary: crate::database::watchlist_storage::WatchlistImportSummary) -> Self {
+        Self {
+            total_rows: summary.total_rows,
+            accepted: summary.accepted,
+            duplicates: summary.duplicates,
+            rejected: summary
+                .rejected
+                .into_iter()
+                .map(|row| WatchlistRejectedRowResponse {
+                    row: row.row,
+                    address: row.address,
+                    reason: row.reason,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<crate::degradation::IngestStatus> for IngestStatusResponse {
+    fn from(status: crate::degradation::IngestStatus) -> Self {
+        Self {
+            mode: match status.mode {
+                crate::degradation::IngestMode::Full => "full".to_string(),
+                crate::degradation::IngestMode::Summary => "summary".to_string(),
+                crate::degradation::IngestMode::Sampled => "sampled".to_string(),
+            },
+            mode_since: status.mode_since,
+            mode_changes: status.mode_changes,
+            summarized_transactions: status.summarized_transactions,
+            sampled_out_transactions: status.sampled_out_transactions,
+            sampled_out_transfers: status.sampled_out_transfers,
+            sampled_out_lamports: status.sampled_out_lamports,
+            last_lag_ms: status.last_lag_ms,
+        }
+    }
+}
+
+impl From<crate::database::Counterparty> for CounterpartyResponse {
+    fn from(counterparty: crate::database::Counterparty) -> Self {
+        Self {
+            sol_volume: Lamports(counterparty.sol_volume_lamports()).as_sol(),
+            sol_volume_decimal: Lamports(counterparty.sol_volume_lamports()).to_string(),
+            address: counterparty.address,
+            interaction_count: counterparty.interaction_count,
+            sol_sent_lamports: counterparty.sol_sent_lamports,
+            sol_received_lamports: counterparty.sol_received_lamports,
+            token_transfer_count: counterparty.token_transfer_count,
+            first_seen: counterparty.first_seen,
+            last_seen: counterparty.last_seen,
+        }
+    }
+}
+
+impl From<crate::database::FeeRelation> for FeeRelationResponse {
+    fn from(relation: crate::database::FeeRelation) -> Self {
+        Self {
+            fee_sol: Lamports(relation.fee_lamports).as_sol(),
+            fee_sol_decimal: Lamports(relation.fee_lamports).to_string(),
+            address: relation.address,
+            transaction_count: relation.transaction_count,
+            fee_lamports: relation.fee_lamports,
+            first_seen: relation.first_seen,
+            last_seen: relation.last_seen,
+        }
+    }
+}
+
+impl From<crate::database::ActivityBucket> for ActivityBucketResponse {
+    fn from(bucket: crate::database::ActivityBucket) -> Self {
+        let sol_volume = Lamports(bucket.sol_sent_lamports.saturating_add(bucket.sol_received_lamports));
+        Self {
+            sol_volume: sol_volume.as_sol(),
+            sol_volume_decimal: sol_volume.to_string(),
+            bucket_start: bucket.bucket_start,
+            transaction_count: bucket.transaction_count,
+            sol_transfer_count: bucket.sol_transfer_count,
+            sol_sent_lamports: bucket.sol_sent_lamports,
+            sol_received_lamports: bucket.sol_received_lamports,
+            token_transfer_count: bucket.token_transfer_count,
+        }
+    }
+}
+
+impl From<crate::database::DailyAddressRollup> for DailyAddressSummaryResponse {
+    fn from(rollup: crate::database::DailyAddressRollup) -> Self {
+        Self {
+            date: rollup.date,
+            total_records: rollup.total_records,
+            sol_sent_count: rollup.sol_sent_count,
+            sol_received_count: rollup.sol_received_count,
+            token_sent_count: rollup.token_sent_count,
+            token_received_count: rollup.token_received_count,
+            total_sol_sent: rollup.total_sol_sent,
+            total_sol_received: rollup.total_sol_received,
+            first_timestamp: rollup.first_timestamp,
+            last_timestamp: rollup.last_timestamp,
+        }
+    }
+}
+
+impl GraphResponse {
+    /// 由存储层的关系图构建响应
+    pub fn from_graph(graph: crate::database::TransferGraph, depth: usize) -> Self {
+        Self {
+            root: graph.root,
+            depth,
+            nodes: graph
+                .nodes
+                .into_iter()
+                .map(|node| GraphNodeResponse { id: node.address, depth: node.depth })
+                .collect(),
+            edges: graph
+                .edges
+                .into_iter()
+                .map(|edge| GraphEdgeResponse {
+                    source: edge.source,
+                    target: edge.target,
+                    interaction_count: edge.interaction_count,
+                    sol_volume_lamports: edge.sol_volume_lamports,
+                    token_transfer_count: edge.token_transfer_count,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl FlowTraceResponse {
+    /// 由存储层的追踪结果构建响应
+    pub fn from_trace(trace: crate::database::FlowTrace, asset: String, max_hops: usize) -> Self {
+        Self {
+            asset,
+            decimals: trace.decimals,
+            max_hops,
+            nodes_visited: trace.nodes_visited,
+            truncated: trace.truncated,
+            root: FlowNodeResponse::from_node(trace.root, trace.decimals),
+        }
+    }
+}
+
+impl FlowNodeResponse {
+    fn from_node(node: crate::database::FlowNode, decimals: u8) -> Self {
+        Self {
+            amount_formatted: TokenAmount::new(node.amount, decimals).ui_amount(),
+            amount_decimal: TokenAmount::new(node.amount, decimals).to_string(),
+            address: node.address,
+            hop: node.hop,
+            amount: node.amount,
+            transfer_count: node.transfer_count,
+            first_timestamp: node.first_timestamp,
+            first_signature: node.first_signature,
+            children: node.children.into_iter().map(|child| Self::from_node(child, decimals)).collect(),
+        }
+    }
+}
+
+impl From<crate::database::DbProperties> for DbStatsResponse {
+    fn from(properties: crate::database::DbProperties) -> Self {
+        Self {
+            estimate_num_keys: properties.estimate_num_keys,
+            total_sst_files_size: properties.total_sst_files_size,
+            live_sst_files_size: properties.live_sst_files_size,
+            estimate_live_data_size: properties.estimate_live_data_size,
+            cur_size_all_mem_tables: properties.cur_size_all_mem_tables,
+            estimate_pending_compaction_bytes: properties.estimate_pending_compaction_bytes,
+            compaction_pending: properties.compaction_pending > 0,
+            num_running_compactions: properties.num_running_compactions,
+            num_running_flushes: properties.num_running_flushes,
+            num_files_at_level: properties.num_files_at_level,
+        }
+    }
+}
+
+impl From<crate::database::slot_summary_storage::SlotSummary> for SlotSummaryResponse {
+    fn from(summary: crate::database::slot_summary_storage::SlotSummary) -> Self {
+        Self {
+            slot: summary.slot,
+            transaction_count: summary.transaction_count,
+            sol_transfer_count: summary.sol_transfer_count,
+            token_transfer_count: summary.token_transfer_count,
+            total_lamports: summary.total_lamports,
+        }
+    }
+}
+
+impl From<crate::database::slot_index_storage::SlotTransactionEntry> for SlotTransactionResponse {
+    fn from(entry: crate::database::slot_index_storage::SlotTransactionEntry) -> Self {
+        Self {
+            signature: entry.signature,
+            transaction_index: entry.transaction_index,
+            timestamp: entry.timestamp,
+            is_successful: entry.is_successful,
+            sol_transfer_count: entry.sol_transfer_count,
+            token_transfer_count: entry.token_transfer_count,
+        }
+    }
+}
+
+impl From<crate::database::ProgramInvocationEntry> for ProgramTransactionResponse {
+    fn from(entry: crate::database::ProgramInvocationEntry) -> Self {
+        Self {
+            signature: entry.signature,
+            slot: entry.slot,
+            timestamp: entry.timestamp,
+            is_successful: entry.is_successful,
+        }
+    }
+}
+
+impl From<crate::database::balance_storage::BalancePoint> for BalancePointResponse {
+    fn from(point: crate::database::balance_storage::BalancePoint) -> Self {
+        Self {
+            signature: point.signature,
+            slot: point.slot,
+            timestamp: point.timestamp,
+            pre_balance: point.pre_balance,
+            post_balance: point.post_balance,
+            post_balance_sol: Lamports(point.post_balance).as_sol(),
+            post_balance_sol_decimal: Lamports(point.post_balance).to_string(),
+        }
+    }
+}
+
+impl From<crate::database::StakeSlotEvent> for StakeEventResponse {
+    fn from(slot_event: crate::database::StakeSlotEvent) -> Self {
+        let event = slot_event.event;
+        Self {
+            signature: event.signature,
+            slot: slot_event.slot,
+            kind: event.kind.as_str().to_string(),
+            stake_account: event.stake_account,
+            authority: event.authority,
+            vote_account: event.vote_account,
+            recipient: event.recipient,
+            lamports: event.lamports,
+            amount_sol: event.lamports.map(|lamports| Lamports(lamports).as_sol()),
+            amount_sol_decimal: event.lamports.map(|lamports| Lamports(lamports).to_string()),
+            timestamp: event.timestamp,
+        }
+    }
+}
+
+impl From<crate::database::SwapSlotRecord> for SwapResponse {
+    fn from(record: crate::database::SwapSlotRecord) -> Self {
+        let swap = record.swap;
+        Self {
+            signature: swap.signature,
+            slot: record.slot,
+            owner: swap.owner,
+            program: swap.program,
+            program_id: swap.program_id,
+            amount_in_formatted: TokenAmount::new(swap.amount_in, swap.decimals_in as u8).ui_amount(),
+            amount_in_decimal: TokenAmount::new(swap.amount_in, swap.decimals_in as u8).to_string(),
+            token_in: swap.token_in,
+            amount_in: swap.amount_in,
+            amount_out_formatted: TokenAmount::new(swap.amount_out, swap.decimals_out as u8).ui_amount(),
+            amount_out_decimal: TokenAmount::new(swap.amount_out, swap.decimals_out as u8).to_string(),
+            token_out: swap.token_out,
+            amount_out: swap.amount_out,
+            timestamp: swap.timestamp,
+        }
+    }
+}
+
+impl From<crate::database::NftSlotTransfer> for NftTransferResponse {
+    fn from(record: crate::database::NftSlotTransfer) -> Self {
+        let transfer = record.transfer;
+        Self {
+            signature: transfer.signature,
+            slot: record.slot,
+            mint: transfer.mint,
+            collection: transfer.collection,
+            from: transfer.from,
+            to: transfer.to,
+            from_owner: transfer.from_owner,
+            to_owner: transfer.to_owner,
+            timestamp: transfer.timestamp,
+        }
+    }
+}
+
+impl From<crate::database::TransferHit> for TransferSearchHitResponse {
+    fn from(hit: crate::database::TransferHit) -> Self {
+        Self {
+            transfer_id: hit.transfer_id,
+            signature: hit.signature,
+            slot: hit.slot,
+            finality: FinalityStatus::Unknown.as_str().to_string(),
+            timestamp: hit.timestamp,
+            category: hit.category.as_str().to_string(),
+            from: hit.from,
+            to: hit.to,
+            from_label: None,
+            to_label: None,
+            amount: hit.amount,
+            amount_formatted: TokenAmount::new(hit.amount, hit.decimals).ui_amount(),
+            amount_decimal: TokenAmount::new(hit.amount, hit.decimals).to_string(),
+            mint: hit.mint,
+            direction: hit.direction.map(|direction| direction.as_str().to_string()),
+        }
+    }
+}
+
+impl From<crate::database::IngestionLogEntry> for IngestionLogEntryResponse {
+    fn from(entry: crate::database::IngestionLogEntry) -> Self {
+        let mut details = serde_json::to_value(&entry.event).unwrap_or_default();
+        if let Some(fields) = details.as_object_mut() {
+            fields.remove("kind");
+        }
+        Self {
+            id: entry.id,
+            timestamp: entry.timestamp,
+            kind: entry.event.kind().to_string(),
+            details,
+        }
+    }
+}
+
+impl From<crate::throughput::ThroughputRate> for ThroughputWindowResponse {
+    fn from(rate: crate::throughput::ThroughputRate) -> Self {
+        Self {
+            window_secs: rate.window_secs,
+            messages_per_sec: rate.messages_per_sec,
+            transfers_per_sec: rate.transfers_per_sec,
+            lamports_per_sec: rate.lamports_per_sec,
+            sol_per_sec: rate.lamports_per_sec / LAMPORTS_PER_SOL as f64,
+        }
+    }
+}
+
+impl TokenBalanceHistoryResponse {
+    /// 从存储的余额历史构建响应，只保留最近 `limit` 个历史点
+    pub fn from_history(history: crate::database::token_balance_storage::TokenBalanceHistory, limit: usize) -> Self {
+        let decimals = history.decimals as u8;
+        let current_amount = history.points.first().map(|point| point.amount).unwrap_or(0);
+        Self {
+            mint: history.mint,
+            decimals: history.decimals,
+            current_amount,
+            current_amount_formatted: TokenAmount::new(current_amount, decimals).ui_amount(),
+            current_amount_decimal: TokenAmount::new(current_amount, decimals).to_string(),
+            total_points: history.points.len(),
+            points: history
+                .points
+                .into_iter()
+                .take(limit)
+                .map(|point| TokenBalancePointResponse {
+                    signature: point.signature,
+                    slot: point.slot,
+                    timestamp: point.timestamp,
+                    amount: point.amount,
+                    amount_formatted: TokenAmount::new(point.amount, decimals).ui_amount(),
+                    amount_decimal: TokenAmount::new(point.amount, decimals).to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 地址 → 标签名称 / Address → label name
+pub type LabelNames = std::collections::HashMap<String, String>;
+
+/// 可以补充地址标签的响应 / Responses that can be enriched with known-entity labels
+pub trait Labeled {
+    /// 响应中需要查找标签的地址 / Addresses referenced by the response
+    fn label_addresses(&self) -> Vec<&str>;
+    /// 填充查到的标签 / Fill in resolved labels
+    fn apply_labels(&mut self, names: &LabelNames);
+}
+
+impl Labeled for SolTransferResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        vec![self.from.as_str(), self.to.as_str()]
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.from_label = names.get(&self.from).cloned();
+        self.to_label = names.get(&self.to).cloned();
+    }
+}
+
+impl Labeled for TokenTransferResponse {
+    // 标签对应钱包，优先使用代币账户的所有者
+    fn label_addresses(&self) -> Vec<&str> {
+        vec![
+            self.from_owner.as_deref().unwrap_or(&self.from),
+            self.to_owner.as_deref().unwrap_or(&self.to),
+        ]
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.from_label = names.get(self.from_owner.as_ref().unwrap_or(&self.from)).cloned();
+        self.to_label = names.get(self.to_owner.as_ref().unwrap_or(&self.to)).cloned();
+    }
+}
+
+impl Labeled for TransferSearchHitResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        vec![self.from.as_str(), self.to.as_str()]
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.from_label = names.get(&self.from).cloned();
+        self.to_label = names.get(&self.to).cloned();
+    }
+}
+
+impl Labeled for AddressTransactionRecordResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        let mut addresses = Vec::new();
+        if let Some(transfer) = &self.sol_transfer {
+            addresses.extend(transfer.label_addresses());
+        }
+        if let Some(transfer) = &self.token_transfer {
+            addresses.extend(transfer.label_addresses());
+        }
+        addresses
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        if let Some(transfer) = &mut self.sol_transfer {
+            transfer.apply_labels(names);
+        }
+        if let Some(transfer) = &mut self.token_transfer {
+            transfer.apply_labels(names);
+        }
+    }
+}
+
+impl Labeled for SignatureQueryResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        self.sol_transfers
+            .iter()
+            .flat_map(Labeled::label_addresses)
+            .chain(self.token_transfers.iter().flat_map(Labeled::label_addresses))
+            .collect()
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.sol_transfers.iter_mut().for_each(|transfer| transfer.apply_labels(names));
+        self.token_transfers.iter_mut().for_each(|transfer| transfer.apply_labels(names));
+    }
+}
+
+impl Labeled for AddressQueryResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        std::iter::once(self.address.as_str())
+            .chain(self.records.iter().flat_map(Labeled::label_addresses))
+            .collect()
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.label = names.get(&self.address).cloned();
+        self.records.iter_mut().for_each(|record| record.apply_labels(names));
+    }
+}
+
+impl Labeled for AddressStatsResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        vec![self.address.as_str()]
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.label = names.get(&self.address).cloned();
+    }
+}
+
+impl Labeled for AddressBatchQueryResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .stats
+                    .label_addresses()
+                    .into_iter()
+                    .chain(entry.recent_records.iter().flat_map(Labeled::label_addresses))
+            })
+            .collect()
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        for entry in &mut self.results {
+            entry.stats.apply_labels(names);
+            entry.recent_records.iter_mut().for_each(|record| record.apply_labels(names));
+        }
+    }
+}
+
+impl Labeled for LargestTransfersResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        self.transfers.iter().flat_map(Labeled::label_addresses).collect()
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.transfers.iter_mut().for_each(|transfer| transfer.apply_labels(names));
+    }
+}
+
+impl Labeled for TransferSearchResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        self.page.items.iter().flat_map(Labeled::label_addresses).collect()
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.page.items.iter_mut().for_each(|transfer| transfer.apply_labels(names));
+    }
+}
+
+impl Labeled for TransfersBetweenResponse {
+    fn label_addresses(&self) -> Vec<&str> {
+        self.transfers.iter().flat_map(Labeled::label_addresses).collect()
+    }
+
+    fn apply_labels(&mut self, names: &LabelNames) {
+        self.transfers.iter_mut().for_each(|transfer| transfer.apply_labels(names));
+    }
+}
+
+/// 可以补充槽位最终性的响应 / Responses that can be enriched with slot finality
+pub trait HasFinality {
+    /// 按快照填充最终性 / Fill in finality from a snapshot
+    fn apply_finality(&mut self, view: &FinalityView);
+}
+
+impl HasFinality for SignatureQueryResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        self.finality = view.status_of(self.slot).as_str().to_string();
+    }
+}
+
+impl HasFinality for AddressTransactionRecordResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        self.finality = view.status_of(self.slot).as_str().to_string();
+    }
+}
+
+impl HasFinality for TransferSearchHitResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        self.finality = view.status_of(self.slot).as_str().to_string();
+    }
+}
+
+impl HasFinality for SlotTransactionsResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        self.finality = view.status_of(self.slot).as_str().to_string();
+    }
+}
+
+impl HasFinality for AddressQueryResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        self.records.iter_mut().for_each(|record| record.apply_finality(view));
+    }
+}
+
+impl HasFinality for AddressBatchQueryResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        for entry in &mut self.results {
+            entry.recent_records.iter_mut().for_each(|record| record.apply_finality(view));
+        }
+    }
+}
+
+impl HasFinality for TransferSearchResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        self.page.items.iter_mut().for_each(|transfer| transfer.apply_finality(view));
+    }
+}
+
+impl HasFinality for TransfersBetweenResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        self.transfers.iter_mut().for_each(|transfer| transfer.apply_finality(view));
+    }
+}
+
+impl HasFinality for LargestTransfersResponse {
+    fn apply_finality(&mut self, view: &FinalityView) {
+        self.transfers.iter_mut().for_each(|transfer| transfer.apply_finality(view));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::signature_storage::{SolTransfer, TokenTransfer};
+
+    #[test]
+    fn test_amount_decimal_fields() {
+        let sol = SolTransferResponse::from(SolTransfer {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: 1_234_567_890_123,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        });
+        assert_eq!(sol.amount_sol_decimal, "1234.567890123");
+
+        let token = TokenTransferResponse::from(TokenTransfer {
+            from: "alice-ata".to_string(),
+            to: "bob-ata".to_string(),
+            amount: 42,
+            decimals: 0,
+            mint: "mint".to_string(),
+            program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            transfer_type: "Token Transfer".to_string(),
+            from_owner: None,
+            to_owner: None,
+        });
+        assert_eq!(token.amount_decimal, "42");
+        assert_eq!(token.amount_formatted, 42.0);
+
+        // 转出与转入合计溢出时取上限，不会 panic
+        let bucket = ActivityBucketResponse::from(crate::database::ActivityBucket {
+            sol_sent_lamports: u64::MAX,
+            sol_received_lamports: 1,
+            ..Default::default()
+        });
+        assert_eq!(bucket.sol_volume_decimal, "18446744073.709551615");
+    }
+
+    #[test]
+    fn test_paginated_response_pages() {
+        let page = PaginatedResponse::from_offset(vec![1, 2, 3], 1, 1).map(|item| item * 10);
+        assert_eq!((page.items, page.total, page.next_cursor.as_deref(), page.has_more), (vec![20], Some(3), Some("2"), true));
+
+        let page = PaginatedResponse::from_page(vec![1, 2, 3], 4, 2, None);
+        assert_eq!((page.items, page.total, page.next_cursor.as_deref()), (vec![1, 2], None, Some("6")));
+
+        let page = PaginatedResponse::from_cursor(vec!["a"], None);
+        assert_eq!((page.total, page.has_more), (None, false));
+    }
+
+    #[test]
+    fn test_listing_flattens_page_next_to_context() {
+        let response = CounterpartiesResponse {
+            address: "alice".to_string(),
+            page: PaginatedResponse::from_offset(Vec::<CounterpartyResponse>::new(), 0, 10),
+        };
+        let value = serde_json::to_value(&response).unwrap();
+        assert_eq!(value["address"], "alice");
+        assert_eq!(value["items"], serde_json::json!([]));
+        assert_eq!(value["total"], 0);
+        assert_eq!(value["has_more"], false);
+        assert!(value.get("page").is_none());
+    }
+}
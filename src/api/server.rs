@@ -1,30 +1,77 @@
 use axum::{
     extract::DefaultBodyLimit,
-    routing::get,
+    http::HeaderName,
+    middleware,
+    routing::{delete, get, post},
     Router,
 };
+use std::collections::HashMap;
 use std::sync::Arc;
-use tower::ServiceBuilder;
+use std::time::Duration;
+use tower::{limit::ConcurrencyLimitLayer, ServiceBuilder};
 use tower_http::{
+    compression::CompressionLayer,
     cors::CorsLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    timeout::TimeoutLayer,
     trace::TraceLayer,
 };
-use tracing::info;
-use utoipa::OpenApi;
+use tracing::{info, info_span, warn, Span};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
-use crate::config::ApiConfig;
+use crate::clock::{SharedClock, SystemClock};
+use crate::config_reload::ConfigReloader;
+use crate::config::{ApiConfig, DegradationConfig, HealthConfig};
 use crate::database::DatabaseManager;
+use crate::degradation::DegradationController;
+use crate::health::HealthMonitor;
+use crate::rpc_fetch::TransactionFetcher;
+use crate::throughput::ThroughputMeter;
+use super::etag::etag;
+use super::json_rpc::handle_json_rpc;
+use super::tenant::{dispatch, TenantRouter};
+use super::tls::{serve_tls, TlsReloader};
 use super::handlers::{
-    AppState, get_transaction_by_signature,
-    get_database_stats, health_check, get_all_signatures,
+    AppState, get_transaction_by_signature, get_raw_transaction,
+    get_database_stats, get_active_addresses, get_throughput, get_metrics, health_check, get_all_signatures, search_signatures,
     get_address_transactions, get_address_stats, get_all_addresses,
+    import_watchlist, get_ingest_status, get_slot_summary, get_slot_transactions, get_program_transactions,
+    get_address_balance_history, get_address_token_balances, get_address_stake_events,
+    get_address_swaps, get_address_nft_transfers, get_account_updates, query_addresses, search_transfers, get_transfers_between, get_address_label, put_address_label,
+    get_address_tags, add_address_tags, remove_address_tag, get_alerts,
+    create_webhook, list_webhooks, delete_webhook, stream_address_transfers,
+    register_deposit_address, unregister_deposit_address, get_user_deposits,
+    register_withdrawal, get_withdrawal_status,
+    get_largest_transfers, get_mint_stats, get_top_mints,
+    require_admin_key, compact_database, create_backup, get_db_stats, delete_signature, get_dead_letters,
+    reload_config, get_ingestion_log, get_address_counterparties, get_address_fee_payers, get_address_funded_accounts, get_address_activity, get_address_daily_summary, get_transfer_graph, trace_flow, get_amount_histogram,
 };
 use super::models::{
-    ApiResponse, ErrorResponse, SignatureQueryResponse, SignatureQueryRequest,
+    ApiResponse, ErrorResponse, PaginatedResponse, HealthResponse, IngestHealthResponse, SignatureQueryResponse, SignatureQueryRequest, SignatureSearchResponse,
     DatabaseStatsResponse, SolTransferResponse,
-    TokenTransferResponse, ExtractedAddressesResponse,
+    TokenTransferResponse, ExtractedAddressesResponse, TransactionFailureResponse,
     AddressQueryResponse, AddressStatsResponse, AddressTransactionRecordResponse,
+    AddressBatchQueryRequest, AddressBatchQueryResponse, AddressBatchEntryResponse,
+    WatchlistImportResponse, WatchlistRejectedRowResponse,
+    IngestStatusResponse, SlotSummaryResponse, SlotTransactionsResponse, SlotTransactionResponse,
+    ProgramTransactionsResponse, ProgramTransactionResponse,
+    BalanceHistoryResponse, BalancePointResponse,
+    TokenBalancesResponse, TokenBalanceHistoryResponse, TokenBalancePointResponse,
+    StakeEventsResponse, StakeEventResponse, SwapsResponse, SwapResponse, NftTransfersResponse, NftTransferResponse, AccountUpdatesResponse, AccountUpdateResponse,
+    TransferSearchResponse, TransferSearchHitResponse, TransfersBetweenResponse, AddressLabelResponse, LabelUpdateRequest,
+    AddressTagsResponse, AddressTagsRequest, AlertsResponse, AlertResponse,
+    WebhookSubscriptionRequest, WebhookSubscriptionResponse, WebhookSubscriptionsResponse,
+    DepositAddressRequest, DepositAddressResponse, DepositResponse, DepositTransitionResponse, UserDepositsResponse,
+    WithdrawalRequest, WithdrawalStatusResponse, WithdrawalTransitionResponse,
+    LargestTransfersResponse, MintStatsResponse, TopMintsResponse,
+    DbStatsResponse, CompactResponse, BackupRequest, BackupResponse, DeleteSignatureResponse, DailyFeeStatsResponse, DailyTransferStatsResponse,
+    ActiveAddressesResponse, DailyActiveAddressesResponse, AmountHistogramResponse, AmountBucketResponse, ThroughputResponse, ThroughputWindowResponse,
+    DeadLetterResponse, ConfigReloadResponse, IngestionLogResponse, IngestionLogEntryResponse,
+    CounterpartiesResponse, CounterpartyResponse, FeeRelationsResponse, FeeRelationResponse, ActivityResponse, ActivityBucketResponse,
+    AddressDailySummaryResponse, DailyAddressSummaryResponse,
+    GraphResponse, GraphNodeResponse, GraphEdgeResponse, FlowTraceRequest, FlowTraceResponse, FlowNodeResponse,
 };
 
 /// API 文档结构
@@ -32,39 +79,225 @@ use super::models::{
 #[openapi(
     paths(
         super::handlers::get_transaction_by_signature,
+        super::handlers::get_raw_transaction,
         super::handlers::get_database_stats,
+        super::handlers::get_active_addresses,
+        super::handlers::get_amount_histogram,
+        super::handlers::get_throughput,
         super::handlers::health_check,
+        super::handlers::get_metrics,
         super::handlers::get_all_signatures,
+        super::handlers::search_signatures,
         super::handlers::get_address_transactions,
         super::handlers::get_address_stats,
         super::handlers::get_all_addresses,
+        super::handlers::query_addresses,
+        super::handlers::import_watchlist,
+        super::handlers::get_address_label,
+        super::handlers::put_address_label,
+        super::handlers::get_address_tags,
+        super::handlers::add_address_tags,
+        super::handlers::remove_address_tag,
+        super::handlers::get_alerts,
+        super::handlers::create_webhook,
+        super::handlers::list_webhooks,
+        super::handlers::delete_webhook,
+        super::handlers::stream_address_transfers,
+        super::handlers::register_deposit_address,
+        super::handlers::unregister_deposit_address,
+        super::handlers::get_user_deposits,
+        super::handlers::register_withdrawal,
+        super::handlers::get_withdrawal_status,
+        super::handlers::get_ingest_status,
+        super::handlers::get_slot_summary,
+        super::handlers::get_slot_transactions,
+        super::handlers::get_program_transactions,
+        super::handlers::get_address_balance_history,
+        super::handlers::get_address_token_balances,
+        super::handlers::get_address_stake_events,
+        super::handlers::get_account_updates,
+        super::handlers::get_address_swaps,
+        super::handlers::get_address_nft_transfers,
+        super::handlers::get_address_counterparties,
+        super::handlers::get_address_fee_payers,
+        super::handlers::get_address_funded_accounts,
+        super::handlers::get_address_activity,
+        super::handlers::get_address_daily_summary,
+        super::handlers::search_transfers,
+        super::handlers::get_transfers_between,
+        super::handlers::get_largest_transfers,
+        super::handlers::get_mint_stats,
+        super::handlers::get_top_mints,
+        super::handlers::get_transfer_graph,
+        super::handlers::trace_flow,
+        super::json_rpc::handle_json_rpc,
+        super::handlers::compact_database,
+        super::handlers::create_backup,
+        super::handlers::get_db_stats,
+        super::handlers::delete_signature,
+        super::handlers::get_dead_letters,
+        super::handlers::reload_config,
+        super::handlers::get_ingestion_log,
     ),
     components(
         schemas(
             ApiResponse<SignatureQueryResponse>,
             ApiResponse<ErrorResponse>,
             ApiResponse<DatabaseStatsResponse>,
-            ApiResponse<Vec<String>>,
+            ApiResponse<ActiveAddressesResponse>,
+            ApiResponse<AmountHistogramResponse>,
+            ApiResponse<ThroughputResponse>,
+            ApiResponse<PaginatedResponse<String>>,
+            ApiResponse<SignatureSearchResponse>,
             ApiResponse<String>,
+            ApiResponse<HealthResponse>,
             ApiResponse<AddressQueryResponse>,
             ApiResponse<AddressStatsResponse>,
+            ApiResponse<AddressBatchQueryResponse>,
+            ApiResponse<WatchlistImportResponse>,
+            ApiResponse<IngestStatusResponse>,
+            ApiResponse<SlotSummaryResponse>,
+            ApiResponse<SlotTransactionsResponse>,
+            ApiResponse<ProgramTransactionsResponse>,
+            ApiResponse<BalanceHistoryResponse>,
+            ApiResponse<TokenBalancesResponse>,
+            ApiResponse<StakeEventsResponse>,
+            ApiResponse<SwapsResponse>,
+            ApiResponse<NftTransfersResponse>,
+            ApiResponse<CounterpartiesResponse>,
+            ApiResponse<FeeRelationsResponse>,
+            ApiResponse<ActivityResponse>,
+            ApiResponse<AddressDailySummaryResponse>,
+            ApiResponse<GraphResponse>,
+            ApiResponse<FlowTraceResponse>,
+            ApiResponse<TransferSearchResponse>,
+            ApiResponse<TransfersBetweenResponse>,
+            ApiResponse<LargestTransfersResponse>,
+            ApiResponse<MintStatsResponse>,
+            ApiResponse<TopMintsResponse>,
+            ApiResponse<AddressLabelResponse>,
+            ApiResponse<AddressTagsResponse>,
+            ApiResponse<AlertsResponse>,
+            ApiResponse<AccountUpdatesResponse>,
+            ApiResponse<WebhookSubscriptionResponse>,
+            ApiResponse<WebhookSubscriptionsResponse>,
+            ApiResponse<DepositAddressResponse>,
+            ApiResponse<UserDepositsResponse>,
+            ApiResponse<WithdrawalStatusResponse>,
+            ApiResponse<DbStatsResponse>,
+            ApiResponse<CompactResponse>,
+            ApiResponse<BackupResponse>,
+            ApiResponse<DeleteSignatureResponse>,
+            ApiResponse<PaginatedResponse<DeadLetterResponse>>,
+            ApiResponse<ConfigReloadResponse>,
+            ApiResponse<IngestionLogResponse>,
             SignatureQueryResponse,
             ErrorResponse,
+            HealthResponse,
+            IngestHealthResponse,
             SignatureQueryRequest,
+            SignatureSearchResponse,
             DatabaseStatsResponse,
+            DailyFeeStatsResponse,
+            ActiveAddressesResponse,
+            DailyActiveAddressesResponse,
+            AmountHistogramResponse,
+            AmountBucketResponse,
+            ThroughputResponse,
+            ThroughputWindowResponse,
+            DailyTransferStatsResponse,
             SolTransferResponse,
             TokenTransferResponse,
             ExtractedAddressesResponse,
+            TransactionFailureResponse,
             AddressQueryResponse,
             AddressStatsResponse,
+            AddressBatchQueryRequest,
+            AddressBatchQueryResponse,
+            AddressBatchEntryResponse,
             AddressTransactionRecordResponse,
+            WatchlistImportResponse,
+            WatchlistRejectedRowResponse,
+            IngestStatusResponse,
+            SlotSummaryResponse,
+            SlotTransactionsResponse,
+            SlotTransactionResponse,
+            ProgramTransactionsResponse,
+            ProgramTransactionResponse,
+            BalanceHistoryResponse,
+            BalancePointResponse,
+            TokenBalancesResponse,
+            TokenBalanceHistoryResponse,
+            TokenBalancePointResponse,
+            StakeEventsResponse,
+            StakeEventResponse,
+            SwapsResponse,
+            SwapResponse,
+            NftTransfersResponse,
+            NftTransferResponse,
+            CounterpartiesResponse,
+            CounterpartyResponse,
+            FeeRelationsResponse,
+            FeeRelationResponse,
+            ActivityResponse,
+            ActivityBucketResponse,
+            AddressDailySummaryResponse,
+            DailyAddressSummaryResponse,
+            GraphResponse,
+            FlowTraceRequest,
+            FlowTraceResponse,
+            FlowNodeResponse,
+            TransferSearchResponse,
+            TransfersBetweenResponse,
+            TransferSearchHitResponse,
+            LargestTransfersResponse,
+            MintStatsResponse,
+            TopMintsResponse,
+            AddressLabelResponse,
+            LabelUpdateRequest,
+            AddressTagsResponse,
+            AddressTagsRequest,
+            AlertsResponse,
+            AlertResponse,
+            AccountUpdatesResponse,
+            AccountUpdateResponse,
+            WebhookSubscriptionRequest,
+            WebhookSubscriptionResponse,
+            WebhookSubscriptionsResponse,
+            DepositAddressRequest,
+            DepositAddressResponse,
+            DepositResponse,
+            DepositTransitionResponse,
+            UserDepositsResponse,
+            WithdrawalRequest,
+            WithdrawalStatusResponse,
+            WithdrawalTransitionResponse,
+            GraphNodeResponse,
+            GraphEdgeResponse,
+            DbStatsResponse,
+            CompactResponse,
+            BackupRequest,
+            BackupResponse,
+            DeleteSignatureResponse,
+            DeadLetterResponse,
+            ConfigReloadResponse,
+            IngestionLogResponse,
+            IngestionLogEntryResponse,
         )
     ),
+    modifiers(&AdminSecurityAddon),
     tags(
         (name = "Transactions", description = "Transaction query endpoints"),
         (name = "Addresses", description = "Address-related query endpoints"),
         (name = "Signatures", description = "Signature management endpoints"),
+        (name = "Watchlist", description = "Watchlist management endpoints"),
+        (name = "Labels", description = "Known-entity address labels and investigation tags"),
+        (name = "Alerts", description = "Suspicious-pattern detection alerts"),
+        (name = "Webhooks", description = "Per-address webhook subscriptions"),
+        (name = "Deposits", description = "Exchange deposit address registry and deposit tracking"),
+        (name = "Withdrawals", description = "Expected withdrawal registration and reconciliation"),
         (name = "Statistics", description = "Database statistics endpoints"),
+        (name = "Admin", description = "Admin endpoints (require admin API key)"),
         (name = "Health", description = "Health check endpoints")
     ),
     info(
@@ -83,72 +316,336 @@ use super::models::{
 )]
 pub struct ApiDoc;
 
+/// 在文档中注册管理接口使用的 API Key 鉴权方式
+struct AdminSecurityAddon;
+
+impl Modify for AdminSecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "admin_api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Admin-Key"))),
+            );
+        }
+    }
+}
+
+/// 每个请求一个 span，带上请求 ID，便于关联日志与链路追踪
+fn request_span<B>(request: &axum::http::Request<B>) -> Span {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    info_span!("http_request", method = %request.method(), uri = %request.uri(), request_id)
+}
+
 /// API 服务器
 pub struct ApiServer {
     db_manager: DatabaseManager,
     config: ApiConfig,
+    clock: SharedClock,
+    degradation: DegradationController,
+    health: HealthMonitor,
+    throughput: ThroughputMeter,
+    config_reloader: Option<ConfigReloader>,
+    transaction_fetcher: Option<Arc<dyn TransactionFetcher>>,
 }
 
 impl ApiServer {
     /// 创建新的 API 服务器
     pub fn new(db_manager: DatabaseManager, config: ApiConfig) -> Self {
-        Self { db_manager, config }
+        Self {
+            db_manager,
+            config,
+            clock: SystemClock::shared(),
+            degradation: DegradationController::new(DegradationConfig::default()),
+            health: HealthMonitor::new(HealthConfig::default(), false),
+            throughput: ThroughputMeter::default(),
+            config_reloader: None,
+            transaction_fetcher: None,
+        }
+    }
+
+    /// 共享 gRPC 客户端的降级控制器，用于在状态接口中展示摄取模式
+    pub fn with_degradation(mut self, degradation: DegradationController) -> Self {
+        self.degradation = degradation;
+        self
+    }
+
+    /// 共享 gRPC 客户端的健康监视器，用于在健康检查中报告摄取状态
+    pub fn with_health(mut self, health: HealthMonitor) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// 共享 gRPC 客户端的吞吐量统计，用于吞吐量接口
+    pub fn with_throughput(mut self, throughput: ThroughputMeter) -> Self {
+        self.throughput = throughput;
+        self
+    }
+
+    /// 启用 `POST /admin/reload-config` 配置热更新接口
+    pub fn with_config_reloader(mut self, reloader: ConfigReloader) -> Self {
+        self.config_reloader = Some(reloader);
+        self
+    }
+
+    /// 启用 `GET /api/v1/transaction/{signature}?fetch=true` 按签名补查交易
+    pub fn with_transaction_fetcher(mut self, fetcher: Arc<dyn TransactionFetcher>) -> Self {
+        self.transaction_fetcher = Some(fetcher);
+        self
+    }
+
+    /// 替换时钟（用于测试）
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// 创建应用路由
     pub fn create_app(&self) -> Router {
-        let state = Arc::new(AppState {
-            db_manager: self.db_manager.clone(),
-        });
-
-        // 创建 API 路由
-        let api_routes = Router::new()
-            .route("/health", get(health_check))
-            .route("/transaction/:signature", get(get_transaction_by_signature))
-            .route("/signatures", get(get_all_signatures))
-            .route("/stats", get(get_database_stats))
-            .route("/addresses", get(get_all_addresses))
-            .route("/address/:address/transactions", get(get_address_transactions))
-            .route("/address/:address/stats", get(get_address_stats));
+        let routes = if self.config.tenants.api_keys.is_empty() {
+            self.api_router(self.db_manager.clone())
+        } else {
+            self.tenant_router()
+        };
 
         // 主路由
-        let app = Router::new()
-            .nest("/api/v1", api_routes)
+        let app = routes
             // Swagger UI
             .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()))
-            .with_state(state)
             .layer(
                 ServiceBuilder::new()
-                    .layer(TraceLayer::new_for_http())
+                    // 沿用客户端传入的 x-request-id，没有时生成，并在响应头中返回
+                    .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                    .layer(TraceLayer::new_for_http().make_span_with(request_span))
+                    .layer(PropagateRequestIdLayer::x_request_id())
                     .layer(if self.config.enable_cors {
                         CorsLayer::permissive()
                     } else {
                         CorsLayer::new()
                     })
-                    .layer(DefaultBodyLimit::max(1024 * 1024)) // 1MB
+                    .layer(DefaultBodyLimit::max(self.config.max_body_bytes))
+                    // 按 Accept-Encoding 压缩，SSE 事件流不压缩
+                    .layer(
+                        CompressionLayer::new()
+                            .gzip(self.config.enable_compression)
+                            .br(self.config.enable_compression),
+                    )
             );
 
+        // 限制同时处理的请求数，超出的请求排队等待
+        if self.config.max_concurrent_requests > 0 {
+            return app.layer(ConcurrencyLimitLayer::new(self.config.max_concurrent_requests));
+        }
+
         app
     }
 
+    /// 多租户路由：每个租户一套 `/api/v1` 路由，按 API Key 分派
+    fn tenant_router(&self) -> Router {
+        let mut by_tenant: HashMap<&str, Router> = HashMap::new();
+        let mut routes = HashMap::new();
+        for (key, tenant) in &self.config.tenants.api_keys {
+            let router = match by_tenant.get(tenant.as_str()) {
+                Some(router) => router.clone(),
+                None => match self.db_manager.for_tenant(tenant) {
+                    Ok(db_manager) => {
+                        let router = self.api_router(db_manager);
+                        by_tenant.insert(tenant, router.clone());
+                        router
+                    }
+                    Err(e) => {
+                        warn!("跳过租户 {}: {}", tenant, e);
+                        continue;
+                    }
+                },
+            };
+            routes.insert(key.clone(), router);
+        }
+        info!("🏢 多租户模式: {} 个租户，{} 个 API Key", by_tenant.len(), routes.len());
+
+        let header = HeaderName::from_bytes(self.config.tenants.header.as_bytes()).unwrap_or_else(|_| {
+            warn!("无效的租户请求头 {:?}，改用 X-Api-Key", self.config.tenants.header);
+            HeaderName::from_static("x-api-key")
+        });
+        let tenants = TenantRouter::new(header, routes, self.api_router(self.db_manager.clone()), self.clock.clone());
+        Router::new().fallback(dispatch).with_state(tenants)
+    }
+
+    /// 指定数据库管理器的 `/api/v1` 路由
+    fn api_router(&self, db_manager: DatabaseManager) -> Router {
+        let state = Arc::new(AppState {
+            db_manager,
+            clock: self.clock.clone(),
+            degradation: self.degradation.clone(),
+            health: self.health.clone(),
+            throughput: self.throughput.clone(),
+            config_reloader: self.config_reloader.clone(),
+            transaction_fetcher: self.transaction_fetcher.clone(),
+            admin_api_key: self.config.admin_api_key.clone(),
+        });
+
+        // 管理接口，需要 API Key
+        let admin_routes = Router::new()
+            .route("/compact", post(compact_database))
+            .route("/backup", post(create_backup))
+            .route("/db-stats", get(get_db_stats))
+            .route("/signature/:signature", delete(delete_signature))
+            .route("/dead-letters", get(get_dead_letters))
+            .route("/reload-config", post(reload_config))
+            .route("/ingestion-log", get(get_ingestion_log))
+            .route_layer(middleware::from_fn_with_state(state.clone(), require_admin_key));
+
+        // 大列表接口，支持 ETag 条件请求，轮询时数据未变化返回 304
+        let listing_routes = Router::new()
+            .route("/signatures", get(get_all_signatures))
+            .route("/signatures/search", get(search_signatures))
+            .route("/addresses", get(get_all_addresses))
+            .route("/address/:address/transactions", get(get_address_transactions))
+            .route("/transfers/search", get(search_transfers))
+            .route("/transfers/between", get(get_transfers_between))
+            .route_layer(middleware::from_fn(etag));
+
+        // 创建 API 路由
+        let api_routes = Router::new()
+            .merge(listing_routes)
+            .route("/health", get(health_check))
+            .route("/metrics", get(get_metrics))
+            .route("/transaction/:signature", get(get_transaction_by_signature))
+            .route("/transaction/:signature/raw", get(get_raw_transaction))
+            .route("/stats", get(get_database_stats))
+            .route("/stats/active-addresses", get(get_active_addresses))
+            .route("/stats/throughput", get(get_throughput))
+            .route("/stats/histogram", get(get_amount_histogram))
+            .route("/addresses/query", post(query_addresses))
+            .route("/address/:address/stats", get(get_address_stats))
+            .route("/address/:address/balance-history", get(get_address_balance_history))
+            .route("/address/:address/token-balances", get(get_address_token_balances))
+            .route("/address/:address/stake-events", get(get_address_stake_events))
+            .route("/address/:address/swaps", get(get_address_swaps))
+            .route("/address/:address/nft-transfers", get(get_address_nft_transfers))
+            .route("/account/:pubkey/updates", get(get_account_updates))
+            .route("/address/:address/counterparties", get(get_address_counterparties))
+            .route("/address/:address/fee-payers", get(get_address_fee_payers))
+            .route("/address/:address/funded-accounts", get(get_address_funded_accounts))
+            .route("/address/:address/activity", get(get_address_activity))
+            .route("/address/:address/daily", get(get_address_daily_summary))
+            .route("/address/:address/tags", get(get_address_tags).post(add_address_tags))
+            .route("/address/:address/tags/:tag", delete(remove_address_tag))
+            .route("/alerts", get(get_alerts))
+            .route("/webhooks", get(list_webhooks).post(create_webhook))
+            .route("/webhooks/:id", delete(delete_webhook))
+            .route("/stream/address/:address", get(stream_address_transfers))
+            .route("/deposits/addresses", post(register_deposit_address))
+            .route("/deposits/addresses/:address", delete(unregister_deposit_address))
+            .route("/deposits/user/:user_id", get(get_user_deposits))
+            .route("/withdrawals", post(register_withdrawal))
+            .route("/withdrawals/:id/status", get(get_withdrawal_status))
+            .route("/graph", get(get_transfer_graph))
+            .route("/trace", post(trace_flow))
+            .route("/rpc", post(handle_json_rpc))
+            .route("/transfers/largest", get(get_largest_transfers))
+            .route("/mint/:mint/stats", get(get_mint_stats))
+            .route("/mints/top", get(get_top_mints))
+            .route("/watchlist/import", post(import_watchlist))
+            .route("/labels/:address", get(get_address_label).put(put_address_label))
+            .route("/status", get(get_ingest_status))
+            .route("/slot/:slot/summary", get(get_slot_summary))
+            .route("/slot/:slot/transactions", get(get_slot_transactions))
+            .route("/program/:program_id/transactions", get(get_program_transactions))
+            // 压缩、备份等管理操作可能较慢，只对查询接口设置超时
+            .layer(TimeoutLayer::new(Duration::from_secs(self.config.request_timeout_secs)))
+            .nest("/admin", admin_routes);
+
+        Router::new().nest("/api/v1", api_routes).with_state(state)
+    }
+
     /// 启动服务器
     pub async fn start(&self) -> anyhow::Result<()> {
         let app = self.create_app();
         let addr = format!("{}:{}", self.config.host, self.config.port);
-        
-        info!("🚀 Starting API server on {}", addr);
-        info!("📚 Swagger documentation available at: http://{}/docs", addr);
+        // 先加载证书，配置错误时启动失败而不是退回 HTTP
+        let tls = match &self.config.tls {
+            Some(tls_config) => Some(TlsReloader::load(tls_config.clone())?),
+            None => None,
+        };
+        let scheme = if tls.is_some() { "https" } else { "http" };
+
+        info!("🚀 Starting API server on {}://{}", scheme, addr);
+        info!("📚 Swagger documentation available at: {}://{}/docs", scheme, addr);
         info!("🔍 API endpoints:");
-        info!("  GET  /api/v1/health                        - Health check");
-        info!("  GET  /api/v1/transaction/{{signature}}       - Get transaction by signature");
-        info!("  GET  /api/v1/signatures                     - Get all signatures (paginated)");
+        info!("  GET  /api/v1/health                        - Component health check (503 when ingestion stalls)");
+        info!("  GET  /api/v1/metrics                       - Prometheus metrics (slot lag, connectivity, throughput)");
+        info!("  GET  /api/v1/transaction/{{signature}}       - Get transaction by signature (?fetch=true falls back to RPC)");
+        info!("  GET  /api/v1/transaction/{{signature}}/raw   - Get archived raw transaction protobuf (store_raw_transactions)");
+        info!("  GET  /api/v1/signatures                     - Get all signatures (paginated, sort=ts_desc, min_slot, max_slot)");
+        info!("  GET  /api/v1/signatures/search?prefix=5VER  - Search signatures by prefix");
         info!("  GET  /api/v1/stats                          - Get database statistics");
+        info!("  GET  /api/v1/stats/active-addresses?days=30 - Get daily unique active addresses");
+        info!("  GET  /api/v1/stats/throughput               - Get ingestion rates over 1m/5m/15m windows");
+        info!("  GET  /api/v1/stats/histogram?period=7d      - Get transfer counts by amount range (SOL or ?asset=<mint>)");
         info!("  GET  /api/v1/addresses                      - Get all addresses with records");
+        info!("  POST /api/v1/addresses/query                - Batch query stats and recent records for many addresses");
         info!("  GET  /api/v1/address/{{address}}/transactions - Get transactions by address");
         info!("  GET  /api/v1/address/{{address}}/stats       - Get address statistics");
+        info!("  GET  /api/v1/address/{{address}}/balance-history - Get address balance history");
+        info!("  GET  /api/v1/address/{{address}}/token-balances  - Get token balance history by owner");
+        info!("  GET  /api/v1/address/{{address}}/stake-events    - Get stake delegate/deactivate/withdraw events");
+        info!("  GET  /api/v1/address/{{address}}/swaps           - Get DEX swaps performed by an address");
+        info!("  GET  /api/v1/address/{{address}}/nft-transfers   - Get NFT transfers sent or received by a wallet");
+        info!("  GET  /api/v1/account/{{pubkey}}/updates          - Get lamports/owner/data changes of a tracked account");
+        info!("  GET  /api/v1/address/{{address}}/counterparties  - Get address counterparties");
+        info!("  GET  /api/v1/address/{{address}}/fee-payers      - Get addresses that paid fees for this signer");
+        info!("  GET  /api/v1/address/{{address}}/funded-accounts - Get signers whose fees this address paid");
+        info!("  GET  /api/v1/address/{{address}}/activity?granularity=day - Get hourly/daily activity buckets");
+        info!("  GET  /api/v1/address/{{address}}/daily           - Get daily summaries including rolled-up history");
+        info!("  GET  /api/v1/graph?root=X&depth=2          - Get transfer graph around an address");
+        info!("  POST /api/v1/trace                         - Follow funds forward from an address");
+        info!("  POST /api/v1/rpc                           - Solana JSON-RPC facade (getSignaturesForAddress, getTransaction)");
+        info!("  GET  /api/v1/transfers/search               - Search transfers by address/mint with compound filters");
+        info!("  GET  /api/v1/transfers/between              - Transfers from one address to another in time order");
+        info!("  GET  /api/v1/transfers/largest              - Largest SOL/token transfers in a recent window");
+        info!("  GET  /api/v1/mint/{{mint}}/stats              - Aggregate transfer statistics of a token mint");
+        info!("  GET  /api/v1/mints/top                      - Rank mints by transfers or unique participants");
+        info!("  POST /api/v1/watchlist/import               - Bulk import watchlist addresses (CSV/JSON)");
+        info!("  GET  /api/v1/labels/{{address}}               - Get known-entity label of an address");
+        info!("  PUT  /api/v1/labels/{{address}}               - Create or replace an address label");
+        info!("  GET  /api/v1/address/{{address}}/tags         - Get investigation tags and note of an address");
+        info!("  POST /api/v1/address/{{address}}/tags         - Add tags or set the note of an address");
+        info!("  DELETE /api/v1/address/{{address}}/tags/{{tag}} - Remove a tag from an address");
+        info!("  GET  /api/v1/alerts                         - List suspicious-pattern alerts");
+        info!("  GET  /api/v1/webhooks                       - List webhook subscriptions");
+        info!("  POST /api/v1/webhooks                       - Create a webhook subscription");
+        info!("  DELETE /api/v1/webhooks/{{id}}                - Delete a webhook subscription");
+        info!("  GET  /api/v1/stream/address/{{address}}       - Stream new transfers of an address (SSE)");
+        info!("  POST /api/v1/deposits/addresses             - Register a deposit address for a user");
+        info!("  DELETE /api/v1/deposits/addresses/{{address}} - Unregister a deposit address");
+        info!("  GET  /api/v1/deposits/user/{{user_id}}        - Get deposits of a user with confirmation status");
+        info!("  POST /api/v1/withdrawals                    - Register an expected withdrawal");
+        info!("  GET  /api/v1/withdrawals/{{id}}/status        - Get reconciliation status of a withdrawal");
+        info!("  GET  /api/v1/status                         - Get ingest status (full/summary mode)");
+        info!("  GET  /api/v1/slot/{{slot}}/summary            - Get per-slot summary");
+        info!("  GET  /api/v1/slot/{{slot}}/transactions       - Get transactions captured for a slot");
+        info!("  GET  /api/v1/program/{{id}}/transactions      - List captured transactions invoking a program");
+        if self.config.admin_api_key.is_some() {
+            info!("  POST /api/v1/admin/compact                  - Compact database (admin)");
+            info!("  POST /api/v1/admin/backup                   - Create a RocksDB snapshot (admin)");
+            info!("  GET  /api/v1/admin/db-stats                 - Get RocksDB properties (admin)");
+            info!("  DELETE /api/v1/admin/signature/{{signature}} - Delete signature data (admin)");
+            info!("  GET  /api/v1/admin/dead-letters             - Inspect transactions awaiting store retry (admin)");
+            info!("  POST /api/v1/admin/reload-config            - Reload monitor filters and parser thresholds from the config file (admin)");
+            info!("  GET  /api/v1/admin/ingestion-log            - Inspect subscriptions, slot gaps and skipped duplicates (admin)");
+        } else {
+            info!("  🔒 /api/v1/admin/* disabled (api.admin_api_key not set)");
+        }
 
         let listener = tokio::net::TcpListener::bind(&addr).await?;
-        axum::serve(listener, app).await?;
+        match tls {
+            Some(tls) => serve_tls(listener, app, tls).await?,
+            None => axum::serve(listener, app).await?,
+        }
 
         Ok(())
     }
@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Request, State},
+    http::HeaderName,
+    response::{IntoResponse, Response},
+    Router,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+use tracing::warn;
+
+use crate::clock::SharedClock;
+use super::error::ApiError;
+
+/// 不需要 API Key 的路径，由本进程租户的路由处理，供负载均衡健康检查和指标采集使用
+const PUBLIC_PATHS: &[&str] = &["/api/v1/health", "/api/v1/metrics"];
+
+/// 按 API Key 把请求分派给对应租户的路由
+///
+/// 每个租户一套路由和 `AppState`，数据库管理器指向该租户的命名空间，接口实现无需感知租户。
+#[derive(Clone)]
+pub struct TenantRouter {
+    header: HeaderName,
+    /// API Key → 租户路由
+    routes: Arc<HashMap<String, Router>>,
+    /// 本进程租户的路由，处理公开路径
+    default_route: Router,
+    clock: SharedClock,
+}
+
+impl TenantRouter {
+    pub fn new(header: HeaderName, routes: HashMap<String, Router>, default_route: Router, clock: SharedClock) -> Self {
+        Self {
+            header,
+            routes: Arc::new(routes),
+            default_route,
+            clock,
+        }
+    }
+
+    /// 请求对应的租户路由，缺少或无效的 API Key 时为空
+    fn route_for(&self, request: &Request) -> Option<Router> {
+        if PUBLIC_PATHS.contains(&request.uri().path()) {
+            return Some(self.default_route.clone());
+        }
+        let key = request.headers().get(&self.header)?.to_str().ok()?;
+        self.routes.get(key.trim()).cloned()
+    }
+}
+
+/// 分派请求到租户路由
+pub async fn dispatch(State(tenants): State<TenantRouter>, request: Request) -> Response {
+    let Some(router) = tenants.route_for(&request) else {
+        warn!("租户鉴权失败: {}", request.uri());
+        return ApiError::unauthorized("缺少或无效的 API Key / Missing or invalid API key", tenants.clock.as_ref())
+            .into_response();
+    };
+    match router.oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    }
+}
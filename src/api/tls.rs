@@ -0,0 +1,167 @@
+use anyhow::{Context, Result};
+use axum::Router;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::service::TowerToHyperService;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::{self, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, info, warn};
+
+use crate::config::TlsConfig;
+
+/// TLS 握手超时，避免慢客户端长期占用连接
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 可热更新的 TLS 证书
+///
+/// 定期检查证书和私钥文件的修改时间，变化后重新加载；新证书只对之后建立的连接生效。
+/// 加载失败时继续使用旧证书，便于证书轮换时两个文件先后写入。
+#[derive(Debug, Clone)]
+pub struct TlsReloader {
+    config: TlsConfig,
+    current: Arc<RwLock<Arc<ServerConfig>>>,
+    modified: Arc<RwLock<Option<(SystemTime, SystemTime)>>>,
+}
+
+impl TlsReloader {
+    /// 加载证书，文件缺失或格式无效时返回错误
+    pub fn load(config: TlsConfig) -> Result<Self> {
+        let server_config = load_server_config(&config.cert_path, &config.key_path)?;
+        let modified = Self::file_times(&config);
+        Ok(Self {
+            config,
+            current: Arc::new(RwLock::new(Arc::new(server_config))),
+            modified: Arc::new(RwLock::new(modified)),
+        })
+    }
+
+    /// 当前证书对应的 TLS 接收器
+    pub fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.current.read().unwrap().clone())
+    }
+
+    /// 证书文件有变化时重新加载，返回是否已更新
+    pub fn reload_if_changed(&self) -> Result<bool> {
+        let modified = Self::file_times(&self.config);
+        if modified.is_none() || modified == *self.modified.read().unwrap() {
+            return Ok(false);
+        }
+        let server_config = load_server_config(&self.config.cert_path, &self.config.key_path)?;
+        *self.current.write().unwrap() = Arc::new(server_config);
+        *self.modified.write().unwrap() = modified;
+        Ok(true)
+    }
+
+    /// 后台定期检查证书文件
+    pub async fn watch(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(self.config.reload_interval_secs.max(1)));
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            match self.reload_if_changed() {
+                Ok(true) => info!("🔐 TLS 证书已重新加载: {}", self.config.cert_path),
+                Ok(false) => {}
+                Err(e) => warn!("⚠️ 重新加载 TLS 证书失败，继续使用旧证书: {:#}", e),
+            }
+        }
+    }
+
+    fn file_times(config: &TlsConfig) -> Option<(SystemTime, SystemTime)> {
+        let modified = |path: &str| std::fs::metadata(path).and_then(|meta| meta.modified()).ok();
+        Some((modified(&config.cert_path)?, modified(&config.key_path)?))
+    }
+}
+
+/// 从 PEM 文件构建 rustls 服务端配置
+pub fn load_server_config(cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> Result<ServerConfig> {
+    let cert_path = cert_path.as_ref();
+    let key_path = key_path.as_ref();
+
+    let cert_file = std::fs::File::open(cert_path).with_context(|| format!("读取证书文件失败: {:?}", cert_path))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(cert_file))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .with_context(|| format!("证书文件格式无效: {:?}", cert_path))?;
+    if certs.is_empty() {
+        anyhow::bail!("证书文件中没有证书: {:?}", cert_path);
+    }
+
+    let key_file = std::fs::File::open(key_path).with_context(|| format!("读取私钥文件失败: {:?}", key_path))?;
+    let key = rustls_pemfile::private_key(&mut BufReader::new(key_file))
+        .with_context(|| format!("私钥文件格式无效: {:?}", key_path))?
+        .with_context(|| format!("私钥文件中没有私钥: {:?}", key_path))?;
+
+    let mut config = ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("证书与私钥不匹配")?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// 以 HTTPS 提供服务，每个连接使用接受时的最新证书
+pub async fn serve_tls(listener: TcpListener, app: Router, tls: TlsReloader) -> Result<()> {
+    tokio::spawn(tls.clone().watch());
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                warn!("接受连接失败: {}", e);
+                continue;
+            }
+        };
+        let acceptor = tls.acceptor();
+        let service = TowerToHyperService::new(app.clone());
+
+        tokio::spawn(async move {
+            let stream = match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => {
+                    debug!("TLS 握手失败 {}: {}", peer, e);
+                    return;
+                }
+                Err(_) => {
+                    debug!("TLS 握手超时 {}", peer);
+                    return;
+                }
+            };
+            if let Err(e) = Builder::new(TokioExecutor::new())
+                .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("HTTPS 连接结束 {}: {}", peer, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_invalid_pem_is_rejected() {
+        let dir = TempDir::new("tls-test");
+        let cert = dir.path().join("cert.pem");
+        let key = dir.path().join("key.pem");
+        std::fs::write(&cert, "not a certificate").unwrap();
+        std::fs::write(&key, "not a key").unwrap();
+
+        let error = load_server_config(&cert, &key).unwrap_err();
+        assert!(error.to_string().contains("没有证书"));
+        assert!(load_server_config(dir.path().join("missing.pem"), &key).is_err());
+        assert!(TlsReloader::load(TlsConfig {
+            cert_path: cert.to_string_lossy().to_string(),
+            key_path: key.to_string_lossy().to_string(),
+            reload_interval_secs: 60,
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,311 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::config::DatabaseConfig;
+use crate::database::{DatabaseManager, RocksDbStore, SignatureStorage, StorageManager};
+
+/// 快照清单文件名
+pub const MANIFEST_FILE: &str = "snapshot.json";
+/// 快照内数据库目录名
+pub const SNAPSHOT_DB_DIR: &str = "db";
+/// 压缩快照的扩展名
+pub const ARCHIVE_EXTENSION: &str = "tar.zst";
+
+const MANIFEST_VERSION: u32 = 1;
+const ZSTD_LEVEL: i32 = 3;
+
+/// 快照清单，随快照一起保存，恢复前用于校验
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// 清单格式版本
+    pub version: u32,
+    /// 创建时间（Unix 时间戳）
+    pub created_at: i64,
+    /// 键前缀长度
+    pub key_prefix_length: usize,
+    /// 签名数据的键前缀
+    pub signature_key_prefix: String,
+    /// 快照中的签名数量
+    pub signature_count: usize,
+}
+
+/// 创建快照的结果
+#[derive(Debug, Clone)]
+pub struct SnapshotInfo {
+    /// 快照目录，压缩时为 `.tar.zst` 文件
+    pub path: PathBuf,
+    /// 快照清单
+    pub manifest: SnapshotManifest,
+}
+
+/// 恢复快照的结果
+#[derive(Debug, Clone)]
+pub struct RestoreReport {
+    /// 被替换下来的旧数据库目录（原先不存在时为 None）
+    pub previous_db_path: Option<PathBuf>,
+    /// 已恢复快照的清单
+    pub manifest: SnapshotManifest,
+}
+
+/// 基于 RocksDB checkpoint 的备份与恢复
+///
+/// checkpoint 通过硬链接 SST 文件得到一致性快照，不需要停止写入；
+/// 快照目录结构为 `snapshot-YYYYMMDD-HHMMSS/{db, snapshot.json}`。
+pub struct Backup;
+
+impl Backup {
+    /// 在 `target_dir` 下创建快照，可选压缩为 `.tar.zst`
+    pub fn create_snapshot(db: &DatabaseManager, target_dir: &Path, compress: bool) -> Result<SnapshotInfo> {
+        let name = format!("snapshot-{}", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+        let snapshot_dir = target_dir.join(&name);
+        if snapshot_dir.exists() {
+            return Err(anyhow::anyhow!("快照目录已存在: {:?}", snapshot_dir));
+        }
+        fs::create_dir_all(&snapshot_dir).with_context(|| format!("创建快照目录失败: {:?}", snapshot_dir))?;
+
+        let storage = db.storage();
+        let db_dir = snapshot_dir.join(SNAPSHOT_DB_DIR);
+        storage.create_checkpoint(&db_dir)?;
+
+        // 清单统计以快照本身为准，避免与仍在写入的主库不一致
+        let signature_prefix = db.signature_storage().signature_prefix().to_string();
+        let signature_count = Self::count_signatures(&db_dir, storage.key_prefix_length(), &signature_prefix)?;
+        let manifest = SnapshotManifest {
+            version: MANIFEST_VERSION,
+            created_at: chrono::Utc::now().timestamp(),
+            key_prefix_length: storage.key_prefix_length(),
+            signature_key_prefix: signature_prefix,
+            signature_count,
+        };
+        let manifest_json = serde_json::to_vec_pretty(&manifest).context("序列化快照清单失败")?;
+        fs::write(snapshot_dir.join(MANIFEST_FILE), manifest_json).context("写入快照清单失败")?;
+
+        let path = if compress {
+            let archive = Self::archive_snapshot(&snapshot_dir)?;
+            fs::remove_dir_all(&snapshot_dir).with_context(|| format!("删除未压缩快照失败: {:?}", snapshot_dir))?;
+            archive
+        } else {
+            snapshot_dir
+        };
+
+        info!("💾 快照已创建: {:?}（{} 个签名）", path, manifest.signature_count);
+        Ok(SnapshotInfo { path, manifest })
+    }
+
+    /// 校验快照目录：清单与当前配置一致，且数据库可打开、签名数量与清单相符
+    pub fn validate_snapshot(snapshot_dir: &Path, db_config: &DatabaseConfig) -> Result<SnapshotManifest> {
+        let manifest_path = snapshot_dir.join(MANIFEST_FILE);
+        let manifest_json = fs::read(&manifest_path).with_context(|| format!("读取快照清单失败: {:?}", manifest_path))?;
+        let manifest: SnapshotManifest = serde_json::from_slice(&manifest_json).context("快照清单格式错误")?;
+
+        if manifest.version != MANIFEST_VERSION {
+            return Err(anyhow::anyhow!("不支持的快照清单版本: {}", manifest.version));
+        }
+        if manifest.key_prefix_length != db_config.key_prefix_length
+            || manifest.signature_key_prefix != db_config.signature_key_prefix
+        {
+            return Err(anyhow::anyhow!(
+                "快照的键前缀配置（{} / {}）与当前配置（{} / {}）不一致",
+                manifest.key_prefix_length,
+                manifest.signature_key_prefix,
+                db_config.key_prefix_length,
+                db_config.signature_key_prefix
+            ));
+        }
+
+        let db_dir = snapshot_dir.join(SNAPSHOT_DB_DIR);
+        if !db_dir.is_dir() {
+            return Err(anyhow::anyhow!("快照中缺少数据库目录: {:?}", db_dir));
+        }
+        let signature_count = Self::count_signatures(&db_dir, manifest.key_prefix_length, &manifest.signature_key_prefix)?;
+        if signature_count != manifest.signature_count {
+            return Err(anyhow::anyhow!(
+                "快照签名数量不一致: 清单 {}，实际 {}",
+                manifest.signature_count,
+                signature_count
+            ));
+        }
+
+        Ok(manifest)
+    }
+
+    /// 校验快照后替换 `db_path`，原数据库重命名为 `{db_path}.bak-时间戳` 保留
+    ///
+    /// 必须在服务停止后执行；`snapshot` 可以是快照目录或 `.tar.zst` 文件。
+    pub fn restore_snapshot(snapshot: &Path, db_config: &DatabaseConfig) -> Result<RestoreReport> {
+        let db_path = PathBuf::from(&db_config.db_path);
+        let staging = PathBuf::from(format!("{}.restore", db_config.db_path));
+        if staging.exists() {
+            fs::remove_dir_all(&staging).with_context(|| format!("清理恢复临时目录失败: {:?}", staging))?;
+        }
+        fs::create_dir_all(&staging).with_context(|| format!("创建恢复临时目录失败: {:?}", staging))?;
+
+        let result = Self::stage_and_swap(snapshot, &staging, &db_path, db_config);
+
+        if let Err(e) = fs::remove_dir_all(&staging) {
+            warn!("清理恢复临时目录失败 {:?}: {}", staging, e);
+        }
+        result
+    }
+
+    fn stage_and_swap(
+        snapshot: &Path,
+        staging: &Path,
+        db_path: &Path,
+        db_config: &DatabaseConfig,
+    ) -> Result<RestoreReport> {
+        // 先在临时目录中准备并校验快照，失败时不触碰现有数据库
+        let snapshot_dir = if snapshot.is_dir() {
+            let copy = staging.join("snapshot");
+            copy_dir(snapshot, &copy)?;
+            copy
+        } else {
+            Self::extract_archive(snapshot, staging)?
+        };
+        let manifest = Self::validate_snapshot(&snapshot_dir, db_config)?;
+        info!("✅ 快照校验通过: {} 个签名", manifest.signature_count);
+
+        let previous_db_path = if db_path.exists() {
+            Self::ensure_not_in_use(db_path)?;
+            let backup_path = PathBuf::from(format!(
+                "{}.bak-{}",
+                db_path.display(),
+                chrono::Utc::now().format("%Y%m%d-%H%M%S")
+            ));
+            fs::rename(db_path, &backup_path)
+                .with_context(|| format!("移动原数据库失败: {:?} -> {:?}", db_path, backup_path))?;
+            info!("原数据库已移动到 {:?}", backup_path);
+            Some(backup_path)
+        } else {
+            if let Some(parent) = db_path.parent() {
+                fs::create_dir_all(parent).context("创建数据库目录失败")?;
+            }
+            None
+        };
+
+        fs::rename(snapshot_dir.join(SNAPSHOT_DB_DIR), db_path)
+            .with_context(|| format!("将快照移动到数据库目录失败: {:?}", db_path))?;
+        info!("♻️ 快照已恢复到 {:?}", db_path);
+
+        Ok(RestoreReport { previous_db_path, manifest })
+    }
+
+    /// 以读写方式打开一次数据库，确认没有其他进程持有锁
+    fn ensure_not_in_use(db_path: &Path) -> Result<()> {
+        StorageManager::new(db_path, 0)
+            .map(drop)
+            .with_context(|| format!("无法锁定数据库 {:?}，请先停止正在运行的服务", db_path))
+    }
+
+    fn count_signatures(db_dir: &Path, key_prefix_length: usize, signature_prefix: &str) -> Result<usize> {
+        let storage = StorageManager::with_store(
+            std::sync::Arc::new(RocksDbStore::open_read_only(db_dir, &rocksdb::Options::default())?),
+            key_prefix_length,
+        );
+        let stats = SignatureStorage::new(storage, signature_prefix.to_string()).get_statistics()?;
+        Ok(stats.total_signatures)
+    }
+
+    /// 将快照目录打包为同级的 `.tar.zst` 文件
+    fn archive_snapshot(snapshot_dir: &Path) -> Result<PathBuf> {
+        let name = snapshot_dir
+            .file_name()
+            .context("快照目录名无效")?
+            .to_string_lossy()
+            .to_string();
+        let archive_path = snapshot_dir.with_file_name(format!("{}.{}", name, ARCHIVE_EXTENSION));
+
+        let file = File::create(&archive_path).with_context(|| format!("创建快照压缩文件失败: {:?}", archive_path))?;
+        let encoder = zstd::Encoder::new(file, ZSTD_LEVEL)?;
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_dir_all(&name, snapshot_dir).context("打包快照失败")?;
+        builder.into_inner().context("打包快照失败")?.finish().context("压缩快照失败")?;
+
+        Ok(archive_path)
+    }
+
+    /// 解压 `.tar.zst` 快照，返回其中包含清单的快照目录
+    fn extract_archive(archive: &Path, dest: &Path) -> Result<PathBuf> {
+        let file = File::open(archive).with_context(|| format!("打开快照文件失败: {:?}", archive))?;
+        let decoder = zstd::Decoder::new(file)?;
+        tar::Archive::new(decoder).unpack(dest).context("解压快照失败")?;
+
+        for entry in fs::read_dir(dest)? {
+            let path = entry?.path();
+            if path.join(MANIFEST_FILE).is_file() {
+                return Ok(path);
+            }
+        }
+        Err(anyhow::anyhow!("压缩包中没有找到快照清单: {:?}", archive))
+    }
+}
+
+/// 递归复制目录
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("创建目录失败: {:?}", to))?;
+    for entry in fs::read_dir(from).with_context(|| format!("读取目录失败: {:?}", from))? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target).with_context(|| format!("复制文件失败: {:?}", entry.path()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DiskGuardConfig, MaintenanceConfig, ReplicaConfig, RocksDbConfig, StorageBackend, StorageMode};
+    use crate::test_util::TempDir;
+
+    fn test_config(db_path: &Path) -> DatabaseConfig {
+        DatabaseConfig {
+            db_path: db_path.to_string_lossy().to_string(),
+            key_prefix_length: 6,
+            signature_key_prefix: "SIG001".to_string(),
+            address_key_prefix: "ADDR01".to_string(),
+            max_address_records: 100,
+            max_record_age_days: None,
+            store_raw_transactions: false,
+            address_filter_capacity: 0,
+            signature_filter_capacity: 0,
+            maintenance: MaintenanceConfig::default(),
+            disk_guard: DiskGuardConfig::default(),
+            backend: StorageBackend::Rocksdb,
+            storage_mode: StorageMode::Full,
+            tenant: None,
+            postgres: None,
+            replica: ReplicaConfig::default(),
+            rocksdb: RocksDbConfig::default(),
+            encryption: None,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_validates_and_rejects_mismatched_config() {
+        let dir = TempDir::new("backup-test");
+        let config = test_config(&dir.path().join("db"));
+        let storage = StorageManager::new(&config.db_path, 6).unwrap();
+        let db = DatabaseManager::with_storage(storage, "SIG001".to_string(), "ADDR01".to_string(), 100);
+
+        let snapshot = Backup::create_snapshot(&db, &dir.path().join("backups"), true).unwrap();
+        assert!(snapshot.path.to_string_lossy().ends_with(ARCHIVE_EXTENSION));
+
+        let staging = dir.path().join("staging");
+        fs::create_dir_all(&staging).unwrap();
+        let snapshot_dir = Backup::extract_archive(&snapshot.path, &staging).unwrap();
+        let manifest = Backup::validate_snapshot(&snapshot_dir, &config).unwrap();
+        assert_eq!(manifest.signature_count, snapshot.manifest.signature_count);
+
+        let mut other = config.clone();
+        other.signature_key_prefix = "SIG002".to_string();
+        assert!(Backup::validate_snapshot(&snapshot_dir, &other).is_err());
+
+    }
+}
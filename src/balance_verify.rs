@@ -0,0 +1,270 @@
+//! 地址余额对账
+//!
+//! 以余额历史中最早一个点的执行前余额为基准，累加此后存储的 SOL 转账并扣除地址支付的手续费，
+//! 与 RPC `getBalance` 返回的实时余额比较。余额历史相邻两点前后不衔接说明期间有交易未被收录
+//! （覆盖缺口），单笔交易的余额变化与转账合计不符说明转账解析有误。
+
+use std::collections::{HashMap, HashSet};
+
+use crate::database::{AddressTransactionRecord, BalanceHistory};
+
+/// 单笔交易的余额变化与存储的转账不符
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionMismatch {
+    pub signature: String,
+    pub slot: u64,
+    /// 余额历史中的变化（执行后 - 执行前，lamports）
+    pub balance_change: i128,
+    /// 存储的转账净额减去手续费（lamports）
+    pub transfer_net: i128,
+}
+
+/// 余额历史中无法由存储的交易解释的区间
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageGap {
+    /// 缺口前最后一个已收录交易的槽位
+    pub after_slot: u64,
+    /// 缺口后第一个已收录交易的槽位，为空表示缺口延续到实时余额
+    pub before_slot: Option<u64>,
+    /// 缺口内未解释的余额变化（lamports）
+    pub unexplained: i128,
+}
+
+/// 对账报告
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    pub address: String,
+    /// 参与对账的 SOL 转账记录数
+    pub records: usize,
+    /// 参与对账的转账记录槽位范围
+    pub first_slot: Option<u64>,
+    pub last_slot: Option<u64>,
+    /// 对账基准：最早余额点的执行前余额及其槽位
+    pub baseline: Option<(u64, u64)>,
+    /// 转入合计（lamports）
+    pub inflow: u64,
+    /// 转出合计（lamports）
+    pub outflow: u64,
+    /// 地址作为支付者的手续费合计（lamports）
+    pub fees_paid: u64,
+    /// 余额历史中最新的执行后余额
+    pub stored_balance: Option<u64>,
+    /// RPC 返回的实时余额
+    pub live_balance: Option<u64>,
+    pub mismatches: Vec<TransactionMismatch>,
+    pub gaps: Vec<CoverageGap>,
+}
+
+impl VerifyReport {
+    /// 按存储的转账推算的当前余额，没有余额历史时无法推算
+    pub fn expected_balance(&self) -> Option<i128> {
+        self.baseline.map(|(balance, _)| {
+            balance as i128 + self.inflow as i128 - self.outflow as i128 - self.fees_paid as i128
+        })
+    }
+
+    /// 实时余额与推算余额之差
+    pub fn discrepancy(&self) -> Option<i128> {
+        Some(self.live_balance? as i128 - self.expected_balance()?)
+    }
+
+    /// 推算余额与实时余额一致，且没有解析不符或覆盖缺口
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancy().unwrap_or(0) == 0 && self.mismatches.is_empty() && self.gaps.is_empty()
+    }
+}
+
+/// 对账
+///
+/// `fees` 为地址作为手续费支付者的交易手续费（按签名）；wSOL 合并得到的转账不改变钱包的
+/// 原生余额，不计入。
+pub fn reconcile(
+    address: &str,
+    records: &[AddressTransactionRecord],
+    history: Option<&BalanceHistory>,
+    fees: &HashMap<String, u64>,
+    live_balance: Option<u64>,
+) -> VerifyReport {
+    // 余额点按时间从早到晚
+    let mut points: Vec<_> = history.map(|history| history.points.iter().collect()).unwrap_or_default();
+    points.reverse();
+    points.sort_by_key(|point| point.slot);
+    let baseline = points.first().map(|point| (point.pre_balance, point.slot));
+    let since = baseline.map(|(_, slot)| slot).unwrap_or(0);
+
+    let mut report = VerifyReport {
+        address: address.to_string(),
+        records: 0,
+        first_slot: None,
+        last_slot: None,
+        baseline,
+        inflow: 0,
+        outflow: 0,
+        fees_paid: 0,
+        stored_balance: points.last().map(|point| point.post_balance),
+        live_balance,
+        mismatches: Vec::new(),
+        gaps: Vec::new(),
+    };
+
+    let mut net_by_signature: HashMap<&str, i128> = HashMap::new();
+    let mut signatures: HashSet<&str> = HashSet::new();
+    for record in records.iter().filter(|record| record.slot >= since) {
+        let Some(sol) = record.sol_transfer.as_ref().filter(|sol| !sol.is_wrapped && sol.from != sol.to) else {
+            continue;
+        };
+        let net = if sol.to == address {
+            report.inflow += sol.amount;
+            sol.amount as i128
+        } else if sol.from == address {
+            report.outflow += sol.amount;
+            -(sol.amount as i128)
+        } else {
+            continue;
+        };
+        *net_by_signature.entry(record.signature.as_str()).or_default() += net;
+        signatures.insert(record.signature.as_str());
+        report.records += 1;
+        report.first_slot = Some(report.first_slot.map_or(record.slot, |slot| slot.min(record.slot)));
+        report.last_slot = Some(report.last_slot.map_or(record.slot, |slot| slot.max(record.slot)));
+    }
+    signatures.extend(points.iter().map(|point| point.signature.as_str()));
+    report.fees_paid = signatures.iter().filter_map(|signature| fees.get(*signature)).sum();
+
+    for point in &points {
+        let balance_change = point.post_balance as i128 - point.pre_balance as i128;
+        let fee = fees.get(&point.signature).copied().unwrap_or(0) as i128;
+        let transfer_net = net_by_signature.get(point.signature.as_str()).copied().unwrap_or(0) - fee;
+        if balance_change != transfer_net {
+            report.mismatches.push(TransactionMismatch {
+                signature: point.signature.clone(),
+                slot: point.slot,
+                balance_change,
+                transfer_net,
+            });
+        }
+    }
+
+    for pair in points.windows(2) {
+        if pair[0].post_balance != pair[1].pre_balance {
+            report.gaps.push(CoverageGap {
+                after_slot: pair[0].slot,
+                before_slot: Some(pair[1].slot),
+                unexplained: pair[1].pre_balance as i128 - pair[0].post_balance as i128,
+            });
+        }
+    }
+    if let (Some(last), Some(live)) = (points.last(), live_balance) {
+        if last.post_balance != live {
+            report.gaps.push(CoverageGap {
+                after_slot: last.slot,
+                before_slot: None,
+                unexplained: live as i128 - last.post_balance as i128,
+            });
+        }
+    }
+
+    report
+}
+
+/// 通过 JSON-RPC `getBalance` 查询地址的实时余额（lamports）
+#[cfg(feature = "ingest")]
+pub async fn fetch_live_balance(endpoint: &str, timeout_secs: u64, address: &str) -> anyhow::Result<u64> {
+    use anyhow::Context;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_secs.max(1)))
+        .build()?;
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getBalance",
+        "params": [address, { "commitment": "confirmed" }],
+    });
+    let response = client
+        .post(endpoint)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(request.to_string())
+        .send()
+        .await?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("RPC 返回 {}: {}", status, body.chars().take(200).collect::<String>()));
+    }
+    let response: serde_json::Value = serde_json::from_str(&body).context("解析 RPC 响应失败")?;
+    if let Some(error) = response.get("error") {
+        return Err(anyhow::anyhow!("RPC 错误: {}", error));
+    }
+    response["result"]["value"].as_u64().context("RPC 响应缺少余额")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{BalancePoint, RecordType};
+    use crate::transfer_types::SolTransfer;
+
+    fn record(signature: &str, slot: u64, from: &str, to: &str, amount: u64) -> AddressTransactionRecord {
+        AddressTransactionRecord {
+            transfer_id: format!("{}:0", signature),
+            signature: signature.to_string(),
+            timestamp: slot * 10,
+            slot,
+            sol_transfer: Some(SolTransfer {
+                signature: signature.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+                amount,
+                from_index: 0,
+                to_index: 1,
+                timestamp: 0,
+                transfer_type: "SOL Transfer".to_string(),
+                is_wrapped: false,
+                instruction_path: None,
+            }),
+            token_transfer: None,
+            record_type: if from == "alice" { RecordType::Sender } else { RecordType::Receiver },
+            direction: None,
+        }
+    }
+
+    fn point(signature: &str, slot: u64, pre_balance: u64, post_balance: u64) -> BalancePoint {
+        BalancePoint { signature: signature.to_string(), slot, timestamp: slot * 10, pre_balance, post_balance }
+    }
+
+    #[test]
+    fn test_reconcile_reports_gaps_and_mismatches() {
+        let records = vec![
+            // 基准之前的转账不计入
+            record("sig0", 5, "bob", "alice", 999),
+            record("sig1", 10, "bob", "alice", 500),
+            record("sig2", 20, "alice", "carol", 200),
+        ];
+        let mut history = BalanceHistory {
+            address: "alice".to_string(),
+            points: vec![point("sig2", 20, 1_500, 1_295), point("sig1", 10, 1_000, 1_500)],
+            last_updated: 0,
+        };
+        let fees = HashMap::from([("sig2".to_string(), 5)]);
+
+        let report = reconcile("alice", &records, Some(&history), &fees, Some(1_295));
+        assert_eq!((report.records, report.inflow, report.outflow, report.fees_paid), (2, 500, 200, 5));
+        assert_eq!(report.expected_balance(), Some(1_295));
+        assert!(report.is_consistent());
+
+        // 槽位 10 与 20 之间余额多出 100，sig2 解析少了一笔转出，实时余额又多出 50
+        history.points = vec![point("sig2", 20, 1_600, 1_300), point("sig1", 10, 1_000, 1_500)];
+        let report = reconcile("alice", &records, Some(&history), &fees, Some(1_350));
+        assert_eq!(report.discrepancy(), Some(55));
+        assert_eq!(
+            report.gaps,
+            vec![
+                CoverageGap { after_slot: 10, before_slot: Some(20), unexplained: 100 },
+                CoverageGap { after_slot: 20, before_slot: None, unexplained: 50 },
+            ]
+        );
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!((report.mismatches[0].balance_change, report.mismatches[0].transfer_net), (-300, -205));
+    }
+}
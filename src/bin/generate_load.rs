@@ -0,0 +1,100 @@
+//! 合成负载生成器：不依赖 Yellowstone 端点，向临时数据库写入确定性的模拟交易并报告吞吐量
+//!
+//! ```bash
+//! cargo run --bin generate_load --features ingest -- --tps 2000 --count 100000
+//! ```
+
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+use std::time::Instant;
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
+
+use solana_transfer_ledger::config::{Config, DEFAULT_CONFIG_PATH};
+use solana_transfer_ledger::database::DatabaseManager;
+use solana_transfer_ledger::grpc_client::SolanaGrpcClient;
+use solana_transfer_ledger::mock_source::{MockSourceConfig, MockTransactionSource};
+use solana_transfer_ledger::transfer_parser::TransferParser;
+
+/// 向临时数据库写入合成交易，压测摄取与存储
+#[derive(Debug, Parser)]
+#[command(name = "generate_load", version)]
+struct Args {
+    /// 配置文件路径，数据库目录会被替换
+    #[arg(long, short, default_value = DEFAULT_CONFIG_PATH)]
+    config: PathBuf,
+    /// 每秒交易数，0 表示不限速
+    #[arg(long, default_value_t = 1_000)]
+    tps: u32,
+    /// 生成的交易总数
+    #[arg(long, default_value_t = 10_000)]
+    count: u64,
+    /// 随机种子
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    /// 参与转账的钱包数
+    #[arg(long, default_value_t = 1_000)]
+    wallets: usize,
+    /// 失败交易比例（0-1）
+    #[arg(long, default_value_t = 0.0)]
+    failure_rate: f64,
+    /// 数据库目录，默认使用临时目录并在结束后删除
+    #[arg(long)]
+    db_path: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // 默认不输出逐笔交易日志，避免日志成为瓶颈
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn,generate_load=info")))
+        .init();
+
+    let args = Args::parse();
+    let mut config = Config::load_from(&args.config)?;
+
+    let temp_dir = args.db_path.is_none().then(|| {
+        std::env::temp_dir().join(format!("ledger-load-{}", uuid::Uuid::new_v4()))
+    });
+    let db_path = args.db_path.clone().or_else(|| temp_dir.clone()).unwrap_or_default();
+    config.database.db_path = db_path.to_string_lossy().into_owned();
+    info!("🧪 合成负载: {} 笔交易, {} TPS, 种子 {}, 数据库 {:?}", args.count, args.tps, args.seed, db_path);
+
+    let result = run(&config, &args).await;
+
+    if let Some(temp_dir) = temp_dir {
+        if let Err(e) = std::fs::remove_dir_all(&temp_dir) {
+            warn!("清理临时数据库失败 {:?}: {}", temp_dir, e);
+        }
+    }
+    result
+}
+
+async fn run(config: &Config, args: &Args) -> Result<()> {
+    let db_manager = DatabaseManager::from_config(config)?;
+    let source = MockTransactionSource::new(MockSourceConfig {
+        seed: args.seed,
+        tps: args.tps,
+        wallets: args.wallets,
+        failure_rate: args.failure_rate,
+        limit: Some(args.count),
+        ..Default::default()
+    });
+
+    let mut client = SolanaGrpcClient::with_database(config.grpc.clone(), config.monitor.clone(), db_manager.clone())
+        .with_parser(TransferParser::new(config.parser.clone()))
+        .with_source(source);
+
+    let started = Instant::now();
+    let transactions = client.ingest_once().await?;
+    let elapsed = started.elapsed().as_secs_f64();
+
+    let totals = db_manager.stats_storage().get_totals()?;
+    info!("✅ 处理 {} 笔交易，耗时 {:.2} 秒，{:.1} 笔/秒", transactions, elapsed, transactions as f64 / elapsed);
+    info!(
+        "📊 已写入 {} 个签名，{} 笔 SOL 转账",
+        totals.total_signatures, totals.total_sol_transfers
+    );
+    Ok(())
+}
@@ -0,0 +1,457 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, MessageAddressTableLookup, MessageHeader,
+    SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo, TokenBalance, Transaction, TransactionError,
+    TransactionStatusMeta, UiTokenAmount,
+};
+
+use crate::database::BlockMetadata;
+
+/// 投票程序，包含该程序指令的交易视为投票交易
+const VOTE_PROGRAM_ID: &str = "Vote111111111111111111111111111111111111111";
+
+/// 从离线区块文件读取的区块
+#[derive(Debug, Clone)]
+pub struct ImportedBlock {
+    /// 区块元数据
+    pub metadata: BlockMetadata,
+    /// 按块内顺序排列的交易，格式与 gRPC 订阅收到的交易一致
+    pub transactions: Vec<SubscribeUpdateTransaction>,
+}
+
+/// RPC `getBlock` 结果（`encoding: "json"`、`transactionDetails: "full"`）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcBlock {
+    /// RPC 结果本身不含槽位，部分导出工具会补充该字段
+    slot: Option<u64>,
+    blockhash: String,
+    parent_slot: u64,
+    previous_blockhash: String,
+    block_time: Option<i64>,
+    block_height: Option<u64>,
+    #[serde(default)]
+    transactions: Vec<RpcTransactionWithMeta>,
+}
+
+/// RPC `getTransaction` 结果（`encoding: "json"`）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcConfirmedTransaction {
+    slot: u64,
+    block_time: Option<i64>,
+    #[serde(flatten)]
+    transaction: RpcTransactionWithMeta,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcTransactionWithMeta {
+    transaction: RpcTransaction,
+    meta: Option<RpcTransactionMeta>,
+    /// "legacy" 或版本号
+    version: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcTransaction {
+    signatures: Vec<String>,
+    message: RpcMessage,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcMessage {
+    header: RpcMessageHeader,
+    account_keys: Vec<String>,
+    recent_blockhash: String,
+    instructions: Vec<RpcInstruction>,
+    #[serde(default)]
+    address_table_lookups: Vec<RpcAddressTableLookup>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcMessageHeader {
+    num_required_signatures: u32,
+    num_readonly_signed_accounts: u32,
+    num_readonly_unsigned_accounts: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcInstruction {
+    program_id_index: u32,
+    accounts: Vec<u8>,
+    data: String,
+    stack_height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcAddressTableLookup {
+    account_key: String,
+    writable_indexes: Vec<u8>,
+    readonly_indexes: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransactionMeta {
+    err: Option<serde_json::Value>,
+    fee: u64,
+    pre_balances: Vec<u64>,
+    post_balances: Vec<u64>,
+    #[serde(default)]
+    inner_instructions: Option<Vec<RpcInnerInstructions>>,
+    #[serde(default)]
+    log_messages: Option<Vec<String>>,
+    #[serde(default)]
+    pre_token_balances: Option<Vec<RpcTokenBalance>>,
+    #[serde(default)]
+    post_token_balances: Option<Vec<RpcTokenBalance>>,
+    #[serde(default)]
+    loaded_addresses: Option<RpcLoadedAddresses>,
+    compute_units_consumed: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcInnerInstructions {
+    index: u32,
+    instructions: Vec<RpcInstruction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTokenBalance {
+    account_index: u32,
+    mint: String,
+    #[serde(default)]
+    owner: String,
+    #[serde(default)]
+    program_id: String,
+    ui_token_amount: RpcUiTokenAmount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcUiTokenAmount {
+    amount: String,
+    decimals: u32,
+    ui_amount: Option<f64>,
+    #[serde(default)]
+    ui_amount_string: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RpcLoadedAddresses {
+    #[serde(default)]
+    writable: Vec<String>,
+    #[serde(default)]
+    readonly: Vec<String>,
+}
+
+/// 展开导入路径：目录取其中的 `.json` / `.json.zst` 文件，按槽位（文件名）排序
+pub fn collect_block_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path).with_context(|| format!("读取目录失败: {:?}", path))? {
+                let file = entry?.path();
+                if file.is_file() && is_block_file(&file) {
+                    files.push(file);
+                }
+            }
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files.sort_by_key(|file| (slot_from_file_name(file), file.clone()));
+    Ok(files)
+}
+
+/// 读取一个区块文件（可为 zstd 压缩），支持裸结果和带 `result` 字段的 RPC 响应
+pub fn read_block_file(path: &Path) -> Result<ImportedBlock> {
+    let file = File::open(path).with_context(|| format!("打开区块文件失败: {:?}", path))?;
+    let reader: Box<dyn Read> = if path.extension().is_some_and(|ext| ext == "zst") {
+        Box::new(zstd::Decoder::new(file)?)
+    } else {
+        Box::new(BufReader::new(file))
+    };
+    let mut value: serde_json::Value =
+        serde_json::from_reader(reader).with_context(|| format!("解析区块文件失败: {:?}", path))?;
+    if let Some(result) = value.get_mut("result") {
+        value = result.take();
+    }
+
+    let block: RpcBlock = serde_json::from_value(value)
+        .with_context(|| format!("区块文件 {:?} 不是 json 编码的 getBlock 结果", path))?;
+    let slot = block
+        .slot
+        .or_else(|| slot_from_file_name(path))
+        .with_context(|| format!("无法确定区块文件 {:?} 的槽位（文件名应为槽位号）", path))?;
+    convert_block(slot, block)
+}
+
+/// 转换 json 编码的 RPC `getTransaction` 结果，同时返回区块时间
+///
+/// 单笔查询的结果不含交易在块内的位置，序号记为 0。
+pub fn convert_confirmed_transaction(value: serde_json::Value) -> Result<(SubscribeUpdateTransaction, Option<i64>)> {
+    let confirmed: RpcConfirmedTransaction =
+        serde_json::from_value(value).context("不是 json 编码的 getTransaction 结果")?;
+    let info = convert_transaction(0, confirmed.transaction)?;
+    Ok((SubscribeUpdateTransaction { transaction: Some(info), slot: confirmed.slot }, confirmed.block_time))
+}
+
+fn is_block_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    name.ends_with(".json") || name.ends_with(".json.zst")
+}
+
+/// 文件名形如 `<slot>.json` 或 `<slot>.json.zst`
+fn slot_from_file_name(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    name.split('.').next()?.parse().ok()
+}
+
+fn convert_block(slot: u64, block: RpcBlock) -> Result<ImportedBlock> {
+    let metadata = BlockMetadata {
+        slot,
+        blockhash: block.blockhash,
+        parent_slot: block.parent_slot,
+        parent_blockhash: block.previous_blockhash,
+        block_time: block.block_time,
+        block_height: block.block_height,
+        transaction_count: block.transactions.len() as u64,
+    };
+
+    let transactions = block
+        .transactions
+        .into_iter()
+        .enumerate()
+        .map(|(index, transaction)| {
+            let info = convert_transaction(index as u64, transaction)
+                .with_context(|| format!("槽位 {} 第 {} 笔交易格式错误", slot, index))?;
+            Ok(SubscribeUpdateTransaction { transaction: Some(info), slot })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ImportedBlock { metadata, transactions })
+}
+
+fn convert_transaction(index: u64, rpc: RpcTransactionWithMeta) -> Result<SubscribeUpdateTransactionInfo> {
+    let message = rpc.transaction.message;
+    let is_vote = message
+        .instructions
+        .iter()
+        .any(|instruction| message.account_keys.get(instruction.program_id_index as usize).is_some_and(|key| key == VOTE_PROGRAM_ID));
+    let signatures = rpc.transaction.signatures.iter().map(|signature| decode(signature)).collect::<Result<Vec<_>>>()?;
+
+    let transaction = Transaction {
+        signatures: signatures.clone(),
+        message: Some(Message {
+            header: Some(MessageHeader {
+                num_required_signatures: message.header.num_required_signatures,
+                num_readonly_signed_accounts: message.header.num_readonly_signed_accounts,
+                num_readonly_unsigned_accounts: message.header.num_readonly_unsigned_accounts,
+            }),
+            account_keys: message.account_keys.iter().map(|key| decode(key)).collect::<Result<_>>()?,
+            recent_blockhash: decode(&message.recent_blockhash)?,
+            instructions: message
+                .instructions
+                .iter()
+                .map(|instruction| {
+                    Ok(CompiledInstruction {
+                        program_id_index: instruction.program_id_index,
+                        accounts: instruction.accounts.clone(),
+                        data: decode(&instruction.data)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            versioned: rpc.version.as_ref().is_some_and(|version| version.is_number()),
+            address_table_lookups: message
+                .address_table_lookups
+                .into_iter()
+                .map(|lookup| {
+                    Ok(MessageAddressTableLookup {
+                        account_key: decode(&lookup.account_key)?,
+                        writable_indexes: lookup.writable_indexes,
+                        readonly_indexes: lookup.readonly_indexes,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        }),
+    };
+
+    Ok(SubscribeUpdateTransactionInfo {
+        signature: signatures.into_iter().next().context("交易缺少签名")?,
+        is_vote,
+        transaction: Some(transaction),
+        meta: rpc.meta.map(convert_meta).transpose()?,
+        index,
+    })
+}
+
+fn convert_meta(meta: RpcTransactionMeta) -> Result<TransactionStatusMeta> {
+    let loaded_addresses = meta.loaded_addresses.unwrap_or_default();
+    Ok(TransactionStatusMeta {
+        // 只关心交易是否失败，错误内容以 JSON 文本保存
+        err: meta.err.map(|err| TransactionError { err: err.to_string().into_bytes() }),
+        fee: meta.fee,
+        pre_balances: meta.pre_balances,
+        post_balances: meta.post_balances,
+        inner_instructions_none: meta.inner_instructions.is_none(),
+        inner_instructions: meta
+            .inner_instructions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|group| {
+                Ok(InnerInstructions {
+                    index: group.index,
+                    instructions: group
+                        .instructions
+                        .into_iter()
+                        .map(|instruction| {
+                            Ok(InnerInstruction {
+                                program_id_index: instruction.program_id_index,
+                                accounts: instruction.accounts,
+                                data: decode(&instruction.data)?,
+                                stack_height: instruction.stack_height,
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                })
+            })
+            .collect::<Result<_>>()?,
+        log_messages_none: meta.log_messages.is_none(),
+        log_messages: meta.log_messages.unwrap_or_default(),
+        pre_token_balances: meta.pre_token_balances.unwrap_or_default().into_iter().map(convert_token_balance).collect(),
+        post_token_balances: meta.post_token_balances.unwrap_or_default().into_iter().map(convert_token_balance).collect(),
+        loaded_writable_addresses: loaded_addresses.writable.iter().map(|address| decode(address)).collect::<Result<_>>()?,
+        loaded_readonly_addresses: loaded_addresses.readonly.iter().map(|address| decode(address)).collect::<Result<_>>()?,
+        compute_units_consumed: meta.compute_units_consumed,
+        ..Default::default()
+    })
+}
+
+fn convert_token_balance(balance: RpcTokenBalance) -> TokenBalance {
+    TokenBalance {
+        account_index: balance.account_index,
+        mint: balance.mint,
+        ui_token_amount: Some(UiTokenAmount {
+            ui_amount: balance.ui_token_amount.ui_amount.unwrap_or_default(),
+            decimals: balance.ui_token_amount.decimals,
+            amount: balance.ui_token_amount.amount,
+            ui_amount_string: balance.ui_token_amount.ui_amount_string,
+        }),
+        owner: balance.owner,
+        program_id: balance.program_id,
+    }
+}
+
+fn decode(value: &str) -> Result<Vec<u8>> {
+    bs58::decode(value).into_vec().with_context(|| format!("无效的 base58 字符串: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_read_rpc_block_file() {
+        let payer = bs58::encode([1u8; 32]).into_string();
+        let recipient = bs58::encode([2u8; 32]).into_string();
+        let system = bs58::encode([0u8; 32]).into_string();
+        let signature = bs58::encode([9u8; 64]).into_string();
+        let transfer_data = bs58::encode([2u8, 0, 0, 0, 0, 202, 154, 59, 0, 0, 0, 0]).into_string();
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "blockhash": "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG",
+                "parentSlot": 99,
+                "previousBlockhash": "4NDXRyhLaEzuHGXjbMzN1ti4tBxfqMizQBYS9GR8TT2h",
+                "blockTime": 1_704_067_200,
+                "blockHeight": 90,
+                "transactions": [{
+                    "transaction": {
+                        "signatures": [signature],
+                        "message": {
+                            "header": { "numRequiredSignatures": 1, "numReadonlySignedAccounts": 0, "numReadonlyUnsignedAccounts": 1 },
+                            "accountKeys": [payer, recipient, system],
+                            "recentBlockhash": "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG",
+                            "instructions": [{ "programIdIndex": 2, "accounts": [0, 1], "data": transfer_data, "stackHeight": null }]
+                        }
+                    },
+                    "meta": {
+                        "err": null,
+                        "fee": 5000,
+                        "preBalances": [2_000_000_000u64, 0, 1],
+                        "postBalances": [999_995_000u64, 1_000_000_000u64, 1],
+                        "innerInstructions": [],
+                        "preTokenBalances": [],
+                        "postTokenBalances": [],
+                        "loadedAddresses": { "writable": [], "readonly": [] },
+                        "computeUnitsConsumed": 150
+                    },
+                    "version": "legacy"
+                }]
+            }
+        });
+
+        let dir = TempDir::new("block-import-test");
+        std::fs::write(dir.path().join("100.json"), response.to_string()).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let files = collect_block_files(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(files, vec![dir.path().join("100.json")]);
+
+        let block = read_block_file(&files[0]).unwrap();
+        assert_eq!(block.metadata.slot, 100);
+        assert_eq!(block.metadata.block_time, Some(1_704_067_200));
+        assert_eq!(block.metadata.transaction_count, 1);
+
+        let info = block.transactions[0].transaction.as_ref().unwrap();
+        assert_eq!(info.signature, vec![9u8; 64]);
+        assert!(!info.is_vote);
+        let message = info.transaction.as_ref().unwrap().message.as_ref().unwrap();
+        assert_eq!(message.account_keys[1], vec![2u8; 32]);
+        assert_eq!(message.instructions[0].data[0], 2);
+        let meta = info.meta.as_ref().unwrap();
+        assert!(meta.err.is_none());
+        assert_eq!(meta.compute_units_consumed, Some(150));
+    }
+
+    #[test]
+    fn test_convert_confirmed_transaction() {
+        let key = |byte: u8| bs58::encode([byte; 32]).into_string();
+        let result = serde_json::json!({
+            "slot": 250,
+            "blockTime": 1_704_067_200,
+            "version": 0,
+            "transaction": {
+                "signatures": [bs58::encode([7u8; 64]).into_string()],
+                "message": {
+                    "header": { "numRequiredSignatures": 1, "numReadonlySignedAccounts": 0, "numReadonlyUnsignedAccounts": 1 },
+                    "accountKeys": [key(1), key(2), key(0)],
+                    "recentBlockhash": key(3),
+                    "instructions": [{ "programIdIndex": 2, "accounts": [0, 1], "data": "3Bxs4h24hBtQy9rw", "stackHeight": null }],
+                    "addressTableLookups": []
+                }
+            },
+            "meta": { "err": null, "fee": 5000, "preBalances": [10, 0, 1], "postBalances": [5, 0, 1] }
+        });
+
+        let (update, block_time) = convert_confirmed_transaction(result).unwrap();
+        assert_eq!(update.slot, 250);
+        assert_eq!(block_time, Some(1_704_067_200));
+        let info = update.transaction.unwrap();
+        assert_eq!(info.signature, vec![7u8; 64]);
+        assert!(info.transaction.unwrap().message.unwrap().versioned);
+        assert!(convert_confirmed_transaction(serde_json::json!({ "slot": 1 })).is_err());
+    }
+}
@@ -0,0 +1,532 @@
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::backup::Backup;
+use crate::balance_verify::{fetch_live_balance, reconcile};
+use crate::block_import::collect_block_files;
+use crate::config::{Config, RunMode, StorageBackend, DEFAULT_CONFIG_PATH};
+use crate::database::{latest_schema_version, run_migrations, run_tenant_migrations, schema_version, DatabaseManager, MigrationReport};
+use crate::diagnostics::{Diagnostics, DiagnosticsWorkload};
+use crate::graph_export::{write_graph, GraphFormat};
+use crate::grpc_client::SolanaGrpcClient;
+use crate::orchestrator::ServiceOrchestrator;
+use crate::transfer_parser::TransferParser;
+
+/// Solana 转账账本：gRPC 数据摄取、查询 API 与运维工具
+#[derive(Debug, Parser)]
+#[command(name = "ledger", version)]
+pub struct Cli {
+    /// 配置文件路径
+    #[arg(long, short, global = true, default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
+
+    /// 子命令，省略时等同于 `run`
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 启动服务（数据摄取、API 服务和维护任务）
+    Run {
+        /// 运行模式（all、api-only、ingest-only），优先于配置文件
+        #[arg(long)]
+        mode: Option<RunMode>,
+        /// 把收到的订阅消息录制到文件
+        #[arg(long, conflicts_with = "replay")]
+        record: Option<PathBuf>,
+        /// 回放录制文件代替连接 gRPC 端点
+        #[arg(long)]
+        replay: Option<PathBuf>,
+    },
+    /// 根据已有签名重新计算预计算统计（需先停止服务）
+    Backfill,
+    /// 将签名数据导出为 JSON Lines 文件
+    Export {
+        /// 输出文件路径
+        #[arg(long, short)]
+        output: PathBuf,
+        /// 起始时间戳（含）
+        #[arg(long)]
+        since: Option<i64>,
+        /// 结束时间戳（含）
+        #[arg(long)]
+        until: Option<i64>,
+    },
+    /// 从种子地址展开交易对手图，导出为 DOT 或 GraphML 文件
+    ExportGraph {
+        /// 种子地址
+        #[arg(long)]
+        root: String,
+        /// 展开深度
+        #[arg(long, default_value_t = 2)]
+        depth: usize,
+        /// 每个节点展开的交易对手数（按往来笔数从多到少）
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// 输出格式（dot、graphml）
+        #[arg(long, default_value = "dot")]
+        format: GraphFormat,
+        /// 输出文件路径
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+    /// 将地址存储的转账历史与 RPC 实时余额对账，报告解析不符和按槽位的覆盖缺口
+    Verify {
+        /// 要对账的地址
+        address: String,
+        /// RPC 端点，优先于配置文件的 `rpc.endpoint`
+        #[arg(long)]
+        rpc: Option<String>,
+    },
+    /// 执行尚未应用的数据格式迁移（需先停止服务；服务启动时也会自动执行）
+    Migrate {
+        /// 只统计需要改写的记录，不修改数据库
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 手动压缩数据库（需先停止服务）
+    Compact,
+    /// 按签名查询交易并输出 JSON
+    Query {
+        /// 交易签名
+        signature: String,
+    },
+    /// 创建或恢复数据库快照
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// 在临时数据库上运行合成负载，输出 RocksDB 写放大诊断报告
+    Diagnose,
+    /// 用当前解析器重新解析归档的原始交易，写入新的数据库目录
+    Reparse {
+        /// 新数据库目录（必须不存在）
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+    /// 离线导入 RPC getBlock JSON 区块文件，补齐历史数据（需先停止服务）
+    Import {
+        /// 区块文件或目录（文件名为槽位号，如 250000000.json，可为 .json.zst）
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BackupAction {
+    /// 创建一致性快照；服务运行中时改为通过管理接口创建
+    Create {
+        /// 目标目录
+        target: PathBuf,
+        /// 打包为 tar.zst
+        #[arg(long)]
+        compress: bool,
+    },
+    /// 校验快照后替换数据库（需先停止服务）
+    Restore {
+        /// 快照目录或 .tar.zst 文件
+        snapshot: PathBuf,
+    },
+}
+
+/// 执行子命令
+pub async fn execute(config: Config, config_path: PathBuf, command: Command) -> Result<()> {
+    match command {
+        Command::Run { mode, record, replay } => {
+            // 运行模式：命令行 --mode 优先于配置文件
+            let mode = mode.unwrap_or(config.service.mode);
+            // 转账解析器只构造一次，由编排器注入 gRPC 客户端
+            let parser = TransferParser::new(config.parser.clone());
+            info!("🚀 正在启动服务...");
+            let mut orchestrator = ServiceOrchestrator::new(config, mode, parser).with_config_path(config_path);
+            if let Some(path) = record {
+                orchestrator = orchestrator.with_record(path);
+            }
+            if let Some(path) = replay {
+                orchestrator = orchestrator.with_replay(path);
+            }
+            orchestrator.run().await
+        }
+        Command::Backfill => backfill(&config),
+        Command::Export { output, since, until } => export(&config, &output, since, until),
+        Command::ExportGraph { root, depth, limit, format, output } => {
+            export_graph(&config, &root, depth, limit, format, &output)
+        }
+        Command::Verify { address, rpc } => verify(&config, &address, rpc).await,
+        Command::Migrate { dry_run } => migrate(&config, dry_run),
+        Command::Compact => compact(&config),
+        Command::Query { signature } => query(&config, &signature),
+        Command::Backup { action: BackupAction::Create { target, compress } } => {
+            create_backup(&config, &target, compress)
+        }
+        Command::Backup { action: BackupAction::Restore { snapshot } } => restore_backup(&config, &snapshot),
+        Command::Diagnose => {
+            info!("🩺 运行 RocksDB 写放大诊断...");
+            let report = Diagnostics::run(&config.database, &DiagnosticsWorkload::default())?;
+            Diagnostics::print_report(&report);
+            Ok(())
+        }
+        Command::Reparse { output } => reparse(&config, &output),
+        Command::Import { paths } => import(&config, &paths),
+    }
+}
+
+fn backfill(config: &Config) -> Result<()> {
+    let db = DatabaseManager::from_config(config)?;
+    info!("📈 开始根据已有签名重新计算统计...");
+    let totals = db.rebuild_statistics()?;
+    info!(
+        "✅ 统计已重建: {} 个签名，{} 笔 SOL 转账，{} 笔代币转账",
+        totals.total_signatures, totals.total_sol_transfers, totals.total_token_transfers
+    );
+    Ok(())
+}
+
+/// 导出时每批读取的签名数
+const EXPORT_CHUNK_SIZE: usize = 1_000;
+
+/// 导出读取只读副本，服务运行中也可以执行；签名数据分批读取，不会一次载入内存
+fn export(config: &Config, output: &Path, since: Option<i64>, until: Option<i64>) -> Result<()> {
+    let db = DatabaseManager::open_replica(config)?;
+    let mut writer =
+        BufWriter::new(File::create(output).with_context(|| format!("创建输出文件失败: {:?}", output))?);
+
+    let mut exported = 0usize;
+    db.signature_storage().for_each_signature_chunk(EXPORT_CHUNK_SIZE, |chunk| {
+        for data in chunk {
+            if since.is_some_and(|since| data.timestamp < since) || until.is_some_and(|until| data.timestamp > until) {
+                continue;
+            }
+            serde_json::to_writer(&mut writer, &data)?;
+            writer.write_all(b"\n")?;
+            exported += 1;
+        }
+        Ok(())
+    })?;
+    writer.flush()?;
+
+    info!("✅ 已导出 {} 个签名到 {:?}", exported, output);
+    Ok(())
+}
+
+/// 读取只读副本，服务运行中也可以执行
+fn export_graph(config: &Config, root: &str, depth: usize, limit: usize, format: GraphFormat, output: &Path) -> Result<()> {
+    let db = DatabaseManager::open_replica(config)?;
+    info!("🕸️ 从 {} 展开交易对手图: 深度 {}，每个节点 {} 个交易对手", root, depth, limit);
+    let graph = db.counterparty_storage().build_graph(root, depth, limit)?;
+
+    let mut writer =
+        BufWriter::new(File::create(output).with_context(|| format!("创建输出文件失败: {:?}", output))?);
+    write_graph(&graph, format, &mut writer)?;
+    writer.flush()?;
+
+    info!("✅ 已导出 {} 个节点、{} 条边到 {:?}", graph.nodes.len(), graph.edges.len(), output);
+    Ok(())
+}
+
+/// 读取只读副本，服务运行中也可以执行；发现差异时返回错误，便于脚本判断
+async fn verify(config: &Config, address: &str, rpc: Option<String>) -> Result<()> {
+    let endpoint = rpc
+        .or_else(|| config.rpc.endpoint.clone())
+        .context("未配置 rpc.endpoint，可通过 --rpc 指定 RPC 端点")?;
+    let db = DatabaseManager::open_replica(config)?;
+    let records = db
+        .address_storage()
+        .get_address_records(address)?
+        .map(|list| list.records)
+        .unwrap_or_default();
+    let history = db.balance_storage().get_balance_history(address)?;
+
+    // 地址作为支付者的交易手续费
+    let mut fees = HashMap::new();
+    let signatures = records
+        .iter()
+        .map(|record| &record.signature)
+        .chain(history.iter().flat_map(|history| history.points.iter().map(|point| &point.signature)));
+    for signature in signatures {
+        if fees.contains_key(signature) {
+            continue;
+        }
+        if let Some(data) = db.signature_storage().get_signature_data(signature)? {
+            // 旧数据没有 fee_payer，以第一个签名者为准
+            let payer = match data.fee_payer.as_str() {
+                "" => data.extracted_addresses.signers.first().map(String::as_str).unwrap_or_default(),
+                payer => payer,
+            };
+            if payer == address {
+                fees.insert(signature.clone(), data.fee_lamports);
+            }
+        }
+    }
+
+    info!("🔎 查询 {} 的实时余额: {}", address, endpoint);
+    let live_balance = fetch_live_balance(&endpoint, config.rpc.timeout_secs, address).await?;
+    let report = reconcile(address, &records, history.as_ref(), &fees, Some(live_balance));
+
+    info!(
+        "📒 {} 笔 SOL 转账（槽位 {:?} - {:?}）: 转入 {}，转出 {}，手续费 {} lamports",
+        report.records, report.first_slot, report.last_slot, report.inflow, report.outflow, report.fees_paid
+    );
+    match (report.baseline, report.expected_balance()) {
+        (Some((balance, slot)), Some(expected)) => info!(
+            "💰 基准余额 {}（槽位 {}），推算余额 {}，存储的最新余额 {:?}，实时余额 {}",
+            balance, slot, expected, report.stored_balance, live_balance
+        ),
+        _ => warn!("地址没有余额历史，无法推算余额；实时余额 {}", live_balance),
+    }
+    for mismatch in &report.mismatches {
+        warn!(
+            "⚠️ 解析不符: {}（槽位 {}）余额变化 {}，转账合计 {}",
+            mismatch.signature, mismatch.slot, mismatch.balance_change, mismatch.transfer_net
+        );
+    }
+    for gap in &report.gaps {
+        match gap.before_slot {
+            Some(before) => warn!("⚠️ 覆盖缺口: 槽位 {} - {} 之间有 {} lamports 未解释", gap.after_slot, before, gap.unexplained),
+            None => warn!("⚠️ 覆盖缺口: 槽位 {} 之后到实时余额有 {} lamports 未解释", gap.after_slot, gap.unexplained),
+        }
+    }
+
+    if report.is_consistent() {
+        info!("✅ 对账一致");
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "地址 {} 对账不一致: 差额 {:?} lamports，{} 笔解析不符，{} 个覆盖缺口",
+            address,
+            report.discrepancy(),
+            report.mismatches.len(),
+            report.gaps.len()
+        ))
+    }
+}
+
+/// 读取只读副本，服务运行中也可以执行；确认结果后停止服务，用新目录替换 `database.db_path`
+fn reparse(config: &Config, output: &Path) -> Result<()> {
+    if output.exists() {
+        return Err(anyhow::anyhow!("输出目录已存在: {:?}", output));
+    }
+    let source = DatabaseManager::open_replica(config)?;
+
+    // 新库沿用当前配置，继续归档原始交易以便下次重新解析
+    let mut target_config = config.clone();
+    target_config.database.db_path = output.to_string_lossy().to_string();
+    target_config.database.backend = StorageBackend::Rocksdb;
+    target_config.database.store_raw_transactions = true;
+    let target = DatabaseManager::from_config(&target_config)?;
+
+    let parser = TransferParser::new(config.parser.clone())
+        .with_label_provider(Arc::new(source.label_storage().clone()));
+    info!("🔁 开始重新解析归档的原始交易 -> {:?}", output);
+    let report = SolanaGrpcClient::reparse_archived(&source, &target, &parser)?;
+    target.storage().flush()?;
+    info!(
+        "✅ 重新解析完成: {} 笔成功，{} 笔失败，{} 笔缺少签名记录被跳过",
+        report.reparsed, report.failed, report.missing_signature_data
+    );
+    info!("确认无误后停止服务，将 {:?} 替换为 {:?}", config.database.db_path, output);
+    Ok(())
+}
+
+/// 导入按槽位顺序写入，已存在的交易会被跳过，中断后可以重新执行
+fn import(config: &Config, paths: &[PathBuf]) -> Result<()> {
+    let files = collect_block_files(paths)?;
+    if files.is_empty() {
+        return Err(anyhow::anyhow!("没有找到区块文件: {:?}", paths));
+    }
+    let db = DatabaseManager::from_config(config)?;
+    let parser = TransferParser::new(config.parser.clone()).with_label_provider(Arc::new(db.label_storage().clone()));
+
+    info!("📥 开始导入 {} 个区块文件...", files.len());
+    let report = SolanaGrpcClient::import_blocks(&db, &parser, &config.monitor, files)?;
+    db.storage().flush()?;
+    info!(
+        "✅ 导入完成: {} 个区块（{} 个失败），{} 笔交易写入，{} 笔已存在，{} 笔被过滤，{} 笔失败",
+        report.blocks, report.failed_blocks, report.imported, report.duplicates, report.filtered, report.failed
+    );
+    Ok(())
+}
+
+fn migrate(config: &Config, dry_run: bool) -> Result<()> {
+    let db = DatabaseManager::from_config(config)?;
+    let version = schema_version(&db)?;
+    info!("🧬 数据库格式版本 v{}，程序支持 v{}", version, latest_schema_version());
+    let mut reports = vec![(db.tenant().unwrap_or("默认").to_string(), run_migrations(&db, dry_run)?)];
+    reports.extend(run_tenant_migrations(
+        &db,
+        config.api.tenants.api_keys.values().map(String::as_str),
+        dry_run,
+    )?);
+    if !dry_run {
+        db.storage().flush()?;
+    }
+    for (tenant, report) in &reports {
+        print_migration_report(tenant, report, dry_run);
+    }
+    Ok(())
+}
+
+fn print_migration_report(tenant: &str, report: &MigrationReport, dry_run: bool) {
+    if report.applied.is_empty() {
+        info!("✅ 租户 {}: 数据格式已是最新，无需迁移", tenant);
+    } else if dry_run {
+        for (version, affected) in &report.applied {
+            info!("📋 租户 {}: 迁移 v{}: {} 条记录需要改写", tenant, version, affected);
+        }
+        info!("✅ 租户 {}: 预演完成，未修改数据库", tenant);
+    } else {
+        info!("✅ 租户 {}: 数据格式已从 v{} 升级到 v{}", tenant, report.from_version, report.to_version);
+    }
+}
+
+fn compact(config: &Config) -> Result<()> {
+    let db = DatabaseManager::from_config(config)?;
+    info!("🗜️ 开始压缩数据库...");
+    let result = db.compact_database()?;
+    info!("✅ {}", result.message);
+    Ok(())
+}
+
+fn query(config: &Config, signature: &str) -> Result<()> {
+    let db = DatabaseManager::open_replica(config)?;
+    let data = db
+        .signature_storage()
+        .get_signature_data(signature)?
+        .with_context(|| format!("未找到签名: {}", signature))?;
+    println!("{}", serde_json::to_string_pretty(&data)?);
+    Ok(())
+}
+
+/// 创建快照；数据库被运行中的服务锁定时，改为调用服务的管理接口
+fn create_backup(config: &Config, target: &Path, compress: bool) -> Result<()> {
+    match DatabaseManager::from_config(config) {
+        Ok(db) => {
+            let snapshot = Backup::create_snapshot(&db, target, compress)?;
+            info!(
+                "✅ 快照已创建: {:?}（{} 个签名）",
+                snapshot.path, snapshot.manifest.signature_count
+            );
+            Ok(())
+        }
+        Err(e) => {
+            warn!("无法直接打开数据库（{:#}），改为通过运行中服务的管理接口创建快照", e);
+            request_remote_snapshot(config, &absolute(target)?, compress)
+        }
+    }
+}
+
+fn restore_backup(config: &Config, snapshot: &Path) -> Result<()> {
+    info!("♻️ 开始恢复快照 {:?} -> {}", snapshot, config.database.db_path);
+    let report = Backup::restore_snapshot(snapshot, &config.database)?;
+    match report.previous_db_path {
+        Some(previous) => info!("✅ 恢复完成，原数据库保留在 {:?}", previous),
+        None => info!("✅ 恢复完成"),
+    }
+    Ok(())
+}
+
+/// 通过 `POST /api/v1/admin/backup` 让运行中的服务在进程内创建快照
+fn request_remote_snapshot(config: &Config, target: &Path, compress: bool) -> Result<()> {
+    let api_key = config
+        .api
+        .admin_api_key
+        .as_deref()
+        .filter(|key| !key.is_empty())
+        .context("未配置 api.admin_api_key，无法通过管理接口创建快照")?;
+    let host = if config.api.host == "0.0.0.0" { "127.0.0.1" } else { config.api.host.as_str() };
+
+    let body = serde_json::json!({
+        "target_dir": target.to_string_lossy(),
+        "compress": compress,
+    })
+    .to_string();
+
+    let mut stream = TcpStream::connect((host, config.api.port))
+        .with_context(|| format!("连接 API 服务 {}:{} 失败", host, config.api.port))?;
+    write!(
+        stream,
+        "POST /api/v1/admin/backup HTTP/1.1\r\nHost: {}:{}\r\nx-admin-key: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        host,
+        config.api.port,
+        api_key,
+        body.len(),
+        body
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).context("读取管理接口响应失败")?;
+    let (head, payload) = response.split_once("\r\n\r\n").unwrap_or((response.as_str(), ""));
+    let status_line = head.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(anyhow::anyhow!("管理接口返回错误: {} {}", status_line, payload));
+    }
+
+    info!("✅ 服务已创建快照: {}", payload);
+    Ok(())
+}
+
+/// 服务的工作目录可能不同，相对路径先转换为绝对路径
+fn absolute(path: &Path) -> Result<PathBuf> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_subcommands() {
+        let cli = Cli::try_parse_from(["ledger"]).unwrap();
+        assert_eq!(cli.config, PathBuf::from(DEFAULT_CONFIG_PATH));
+        assert!(cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["ledger", "run", "--mode", "api-only", "--config", "/etc/ledger.toml"]).unwrap();
+        assert_eq!(cli.config, PathBuf::from("/etc/ledger.toml"));
+        assert!(matches!(cli.command, Some(Command::Run { mode: Some(RunMode::ApiOnly), .. })));
+
+        let cli = Cli::try_parse_from(["ledger", "-c", "a.toml", "query", "sig1"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Query { signature }) if signature == "sig1"));
+
+        let cli = Cli::try_parse_from(["ledger", "backup", "create", "out", "--compress"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Backup { action: BackupAction::Create { compress: true, .. } })
+        ));
+
+        assert!(Cli::try_parse_from(["ledger", "run", "--mode", "bogus"]).is_err());
+        assert!(Cli::try_parse_from(["ledger", "export"]).is_err());
+        assert!(Cli::try_parse_from(["ledger", "reparse"]).is_err());
+        let cli = Cli::try_parse_from(["ledger", "reparse", "--output", "data/reparsed"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Reparse { output }) if output == Path::new("data/reparsed")));
+
+        let cli = Cli::try_parse_from(["ledger", "export-graph", "--root", "addr", "--format", "graphml", "-o", "g.graphml"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::ExportGraph { depth: 2, limit: 10, format: GraphFormat::Graphml, .. })
+        ));
+        assert!(Cli::try_parse_from(["ledger", "export-graph", "--root", "addr", "--format", "svg", "-o", "g"]).is_err());
+
+        assert!(Cli::try_parse_from(["ledger", "verify"]).is_err());
+        let cli = Cli::try_parse_from(["ledger", "verify", "addr", "--rpc", "http://localhost:8899"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Verify { address, rpc: Some(_) }) if address == "addr"));
+
+        let cli = Cli::try_parse_from(["ledger", "migrate", "--dry-run"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Migrate { dry_run: true })));
+
+        assert!(Cli::try_parse_from(["ledger", "import"]).is_err());
+        let cli = Cli::try_parse_from(["ledger", "import", "blocks/", "250000000.json"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Import { paths }) if paths.len() == 2));
+    }
+}
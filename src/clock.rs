@@ -0,0 +1,88 @@
+use chrono::{DateTime, TimeZone, Utc};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+/// 时钟抽象
+///
+/// 存储层和 API 层通过该 trait 获取当前时间，测试中可以替换为 [`MockClock`]
+/// 以获得确定性的时间戳。
+pub trait Clock: Send + Sync + Debug {
+    /// 当前 UTC 时间
+    fn now(&self) -> DateTime<Utc>;
+
+    /// 当前 Unix 时间戳（秒）
+    fn timestamp(&self) -> i64 {
+        self.now().timestamp()
+    }
+}
+
+/// 共享时钟句柄
+pub type SharedClock = Arc<dyn Clock>;
+
+/// 系统时钟
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+impl SystemClock {
+    /// 创建共享的系统时钟
+    pub fn shared() -> SharedClock {
+        Arc::new(SystemClock)
+    }
+}
+
+/// 可手动控制的模拟时钟（秒级精度），克隆后共享同一时间
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    seconds: Arc<AtomicI64>,
+}
+
+impl MockClock {
+    /// 创建指定 Unix 时间戳的模拟时钟
+    pub fn new(timestamp: i64) -> Self {
+        Self {
+            seconds: Arc::new(AtomicI64::new(timestamp)),
+        }
+    }
+
+    /// 设置当前时间
+    pub fn set(&self, timestamp: i64) {
+        self.seconds.store(timestamp, Ordering::SeqCst);
+    }
+
+    /// 向前推进指定秒数
+    pub fn advance(&self, seconds: i64) {
+        self.seconds.fetch_add(seconds, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc.timestamp_opt(self.seconds.load(Ordering::SeqCst), 0)
+            .single()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_is_shared_between_clones() {
+        let clock = MockClock::new(1_700_000_000);
+        let handle = clock.clone();
+
+        handle.advance(60);
+        assert_eq!(clock.timestamp(), 1_700_000_060);
+
+        clock.set(42);
+        assert_eq!(handle.timestamp(), 42);
+    }
+}
@@ -1,6 +1,14 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::database::{IngestionEvent, StorageManager};
+
+/// 默认配置文件路径（相对当前目录）
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
 
 /// 完整的配置结构
 #[derive(Debug, Clone, Deserialize)]
@@ -9,6 +17,412 @@ pub struct Config {
     pub monitor: MonitorConfig,
     pub database: DatabaseConfig,
     pub api: ApiConfig,
+    #[serde(default)]
+    pub degradation: DegradationConfig,
+    #[serde(default)]
+    pub health: HealthConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub service: ServiceConfig,
+    #[serde(default)]
+    pub labels: LabelsConfig,
+    #[serde(default)]
+    pub parser: ParserConfig,
+    #[serde(default)]
+    pub rules: RulesConfig,
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+    #[serde(default)]
+    pub accounts: AccountTrackingConfig,
+    #[serde(default)]
+    pub prices: PriceConfig,
+    #[serde(default)]
+    pub rpc: RpcConfig,
+}
+
+/// 转账解析启发式参数
+///
+/// 转账由余额变化配对推断，这些阈值决定匹配的宽松程度：放宽可以识别更多转账，
+/// 收紧可以减少误配。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ParserConfig {
+    /// 转出与转入金额之差不超过该值（lamports）时视为同一笔转账，差额视为手续费
+    #[serde(default = "default_max_gas_fee_lamports")]
+    pub max_gas_fee_lamports: u64,
+    /// 大额转账允许的差额比例（百分比），与 `max_gas_fee_lamports` 取较大者
+    #[serde(default = "default_match_tolerance_percent")]
+    pub match_tolerance_percent: f64,
+    /// 一对多、多对一匹配时忽略低于该金额（lamports）的余额变化
+    #[serde(default = "default_dust_floor_lamports")]
+    pub dust_floor_lamports: u64,
+    /// 无法配对时，接收金额超过该值（lamports）才推测为转账
+    #[serde(default = "default_inferred_transfer_floor_lamports")]
+    pub inferred_transfer_floor_lamports: u64,
+    /// 代币转出与转入金额允许的最大倍数差
+    #[serde(default = "default_max_token_ratio")]
+    pub max_token_ratio: f64,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            max_gas_fee_lamports: default_max_gas_fee_lamports(),
+            match_tolerance_percent: default_match_tolerance_percent(),
+            dust_floor_lamports: default_dust_floor_lamports(),
+            inferred_transfer_floor_lamports: default_inferred_transfer_floor_lamports(),
+            max_token_ratio: default_max_token_ratio(),
+        }
+    }
+}
+
+fn default_max_gas_fee_lamports() -> u64 {
+    10_000_000
+}
+
+fn default_match_tolerance_percent() -> f64 {
+    1.0
+}
+
+fn default_dust_floor_lamports() -> u64 {
+    100_000
+}
+
+fn default_inferred_transfer_floor_lamports() -> u64 {
+    1_000_000
+}
+
+fn default_max_token_ratio() -> f64 {
+    10.0
+}
+
+/// 可疑模式检测规则配置
+///
+/// 只对成功交易中的 SOL 转账生效；将某条规则的阈值设为 0（或清空阈值列表）可单独关闭该规则。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RulesConfig {
+    /// 是否在摄取时评估规则
+    #[serde(default)]
+    pub enabled: bool,
+    /// 各规则回看的时间窗口（秒）
+    #[serde(default = "default_rules_window_secs")]
+    pub window_secs: u64,
+    /// 窗口内向不少于该数量的不同地址转出时告警
+    #[serde(default = "default_fan_out_min_recipients")]
+    pub fan_out_min_recipients: usize,
+    /// 剥离链达到该跳数时告警
+    #[serde(default = "default_peel_chain_min_hops")]
+    pub peel_chain_min_hops: u32,
+    /// 转出金额不低于最近收到金额的该比例（且未全部转出）时视为剥离链的一跳
+    #[serde(default = "default_peel_chain_forward_ratio")]
+    pub peel_chain_forward_ratio: f64,
+    /// 转回金额不低于原转出金额的该比例时视为往返转账
+    #[serde(default = "default_round_trip_return_ratio")]
+    pub round_trip_return_ratio: f64,
+    /// 申报阈值（SOL）
+    #[serde(default = "default_structuring_thresholds_sol")]
+    pub structuring_thresholds_sol: Vec<f64>,
+    /// 金额低于阈值且差距不超过该比例时告警
+    #[serde(default = "default_structuring_margin")]
+    pub structuring_margin: f64,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_secs: default_rules_window_secs(),
+            fan_out_min_recipients: default_fan_out_min_recipients(),
+            peel_chain_min_hops: default_peel_chain_min_hops(),
+            peel_chain_forward_ratio: default_peel_chain_forward_ratio(),
+            round_trip_return_ratio: default_round_trip_return_ratio(),
+            structuring_thresholds_sol: default_structuring_thresholds_sol(),
+            structuring_margin: default_structuring_margin(),
+        }
+    }
+}
+
+fn default_rules_window_secs() -> u64 {
+    3_600
+}
+
+fn default_fan_out_min_recipients() -> usize {
+    20
+}
+
+fn default_peel_chain_min_hops() -> u32 {
+    3
+}
+
+fn default_peel_chain_forward_ratio() -> f64 {
+    0.8
+}
+
+fn default_round_trip_return_ratio() -> f64 {
+    0.9
+}
+
+fn default_structuring_thresholds_sol() -> Vec<f64> {
+    vec![10_000.0]
+}
+
+fn default_structuring_margin() -> f64 {
+    0.05
+}
+
+/// Webhook 通知投递配置（订阅通过 `/api/v1/webhooks` 管理）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WebhookConfig {
+    /// 检查待投递通知的间隔（秒）
+    #[serde(default = "default_webhook_delivery_interval_secs")]
+    pub delivery_interval_secs: u64,
+    /// 单次请求超时（秒）
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 最多投递次数，之后放弃该通知
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: u32,
+    /// 运维事件通知（连接断开、槽位缺口、存储错误、磁盘空间不足等）
+    #[serde(default)]
+    pub lifecycle: LifecycleWebhookConfig,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            delivery_interval_secs: default_webhook_delivery_interval_secs(),
+            timeout_secs: default_webhook_timeout_secs(),
+            max_attempts: default_webhook_max_attempts(),
+            lifecycle: LifecycleWebhookConfig::default(),
+        }
+    }
+}
+
+/// 运维事件 Webhook：摄取审计日志中的事件同时推送到这些地址
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct LifecycleWebhookConfig {
+    /// 回调地址，为空时不推送
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// HMAC 签名密钥
+    #[serde(default)]
+    pub secret: String,
+    /// 推送的事件类型（见 `/api/v1/admin/ingestion-log` 的 kind），为空表示全部
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+impl LifecycleWebhookConfig {
+    /// 是否推送该类型的事件
+    pub fn wants(&self, kind: &str) -> bool {
+        !self.urls.is_empty() && (self.events.is_empty() || self.events.iter().any(|event| event == kind))
+    }
+}
+
+fn default_webhook_delivery_interval_secs() -> u64 {
+    5
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    8
+}
+
+/// 账户更新跟踪配置
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct AccountTrackingConfig {
+    /// 是否订阅账户更新
+    #[serde(default)]
+    pub enabled: bool,
+    /// 跟踪的账户（base58）
+    #[serde(default)]
+    pub accounts: Vec<String>,
+    /// 只接收账户数据的这些片段，为空时不保存账户数据
+    #[serde(default)]
+    pub data_slices: Vec<AccountDataSlice>,
+}
+
+/// 账户数据片段
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct AccountDataSlice {
+    pub offset: u64,
+    pub length: u64,
+}
+
+/// USD 价格估值配置
+///
+/// 启用后摄取时按缓存的价格给转账标注近似 USD 价值，缓存中没有价格的代币不标注，
+/// 后台任务按间隔刷新出现过的代币价格。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PriceConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 价格来源
+    #[serde(default)]
+    pub provider: PriceProviderKind,
+    /// 价格接口地址，未配置时使用各来源的公共地址
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// 价格接口的 API Key（CoinGecko 可选）
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// 刷新价格的间隔（秒）
+    #[serde(default = "default_price_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+    /// 价格有效期（秒），过期未刷新的价格不再用于估值
+    #[serde(default = "default_price_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    /// 单次请求超时（秒）
+    #[serde(default = "default_price_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 最多跟踪的代币数，超出后按使用频率淘汰
+    #[serde(default = "default_price_max_tracked_mints")]
+    pub max_tracked_mints: usize,
+    /// 代币多久（秒）没有出现在转账中就停止跟踪
+    #[serde(default = "default_price_tracked_mint_idle_secs")]
+    pub tracked_mint_idle_secs: u64,
+    /// Pyth 价格源 ID（mint → feed id），provider = "pyth" 时只查询这些代币
+    #[serde(default)]
+    pub pyth_feeds: BTreeMap<String, String>,
+}
+
+impl Default for PriceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: PriceProviderKind::default(),
+            endpoint: None,
+            api_key: None,
+            refresh_interval_secs: default_price_refresh_interval_secs(),
+            cache_ttl_secs: default_price_cache_ttl_secs(),
+            timeout_secs: default_price_timeout_secs(),
+            max_tracked_mints: default_price_max_tracked_mints(),
+            tracked_mint_idle_secs: default_price_tracked_mint_idle_secs(),
+            pyth_feeds: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_price_refresh_interval_secs() -> u64 {
+    60
+}
+
+fn default_price_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_price_timeout_secs() -> u64 {
+    10
+}
+
+fn default_price_max_tracked_mints() -> usize {
+    500
+}
+
+fn default_price_tracked_mint_idle_secs() -> u64 {
+    24 * 60 * 60
+}
+
+/// 价格来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceProviderKind {
+    /// CoinGecko 代币价格接口
+    #[default]
+    CoinGecko,
+    /// Pyth Hermes 价格服务
+    Pyth,
+    /// Jupiter 价格接口
+    Jupiter,
+}
+
+/// Solana RPC 配置
+///
+/// 用于按签名补查本地没有的交易（`GET /api/v1/transaction/{signature}?fetch=true`），
+/// 未配置端点时不补查。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RpcConfig {
+    /// RPC 端点地址，如 `https://api.mainnet-beta.solana.com`
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// 单次请求超时（秒）
+    #[serde(default = "default_rpc_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            timeout_secs: default_rpc_timeout_secs(),
+        }
+    }
+}
+
+fn default_rpc_timeout_secs() -> u64 {
+    10
+}
+
+/// 地址标签配置
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LabelsConfig {
+    /// 启动时导入的标签文件（JSON 或 CSV），已存在的标签不会被覆盖
+    #[serde(default)]
+    pub seed_file: Option<String>,
+}
+
+/// 服务运行配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceConfig {
+    /// 运行模式，可被命令行 `--mode` 覆盖
+    #[serde(default)]
+    pub mode: RunMode,
+    /// 检查配置文件变化的间隔（秒），变化后热更新监控过滤条件和解析参数；0 表示不检查
+    #[serde(default = "default_config_reload_interval_secs")]
+    pub config_reload_interval_secs: u64,
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        Self {
+            mode: RunMode::default(),
+            config_reload_interval_secs: default_config_reload_interval_secs(),
+        }
+    }
+}
+
+fn default_config_reload_interval_secs() -> u64 {
+    10
+}
+
+/// 运行模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunMode {
+    /// 同时运行数据摄取、API 服务和维护任务（默认）
+    #[default]
+    All,
+    /// 只读副本：以 secondary 模式打开数据库，只启动 API 服务
+    ApiOnly,
+    /// 只采集数据：启动数据摄取和维护任务，不启动 API 服务
+    IngestOnly,
+}
+
+impl FromStr for RunMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "all" => Ok(RunMode::All),
+            "api-only" => Ok(RunMode::ApiOnly),
+            "ingest-only" => Ok(RunMode::IngestOnly),
+            other => Err(anyhow::anyhow!("未知的运行模式: {}（可选 all、api-only、ingest-only）", other)),
+        }
+    }
 }
 
 /// gRPC 配置
@@ -17,14 +431,43 @@ pub struct GrpcConfig {
     pub endpoint: String,
     pub timeout: u64,
     pub connect_timeout: u64,
+    /// 订阅方式
+    #[serde(default)]
+    pub subscription: SubscriptionMode,
+    /// 通过双向订阅流向服务端发送 ping 的间隔（秒），0 表示不发送
+    #[serde(default = "default_ping_interval_secs")]
+    pub ping_interval_secs: u64,
+    /// 订阅流最长静默时间（秒），超过后主动断开并重新订阅，0 表示不检测
+    #[serde(default = "default_max_silence_secs")]
+    pub max_silence_secs: u64,
+}
+
+fn default_ping_interval_secs() -> u64 {
+    10
+}
+
+fn default_max_silence_secs() -> u64 {
+    30
+}
+
+/// gRPC 订阅方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionMode {
+    /// 逐笔订阅交易（默认）
+    #[default]
+    Transactions,
+    /// 订阅完整区块，交易带有区块时间和块内序号，并保存区块元数据
+    Blocks,
 }
 
 /// 监控配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct MonitorConfig {
     pub include_failed_transactions: bool,
     pub include_vote_transactions: bool,
-    #[allow(dead_code)]
+    /// 顶层指令全部调用这些程序的交易不解析、不存储
+    #[serde(default)]
     pub exclude_programs: Vec<String>,
 }
 
@@ -36,6 +479,312 @@ pub struct DatabaseConfig {
     pub signature_key_prefix: String,
     pub address_key_prefix: String,
     pub max_address_records: usize,
+    /// 地址记录的最长保留天数（未设置则只按条数截断）
+    #[serde(default)]
+    pub max_record_age_days: Option<u64>,
+    /// 是否归档原始交易 protobuf（zstd 压缩），用于日后重新解析
+    #[serde(default)]
+    pub store_raw_transactions: bool,
+    /// 地址布隆过滤器的预期地址数，0 表示不启用
+    #[serde(default = "default_address_filter_capacity")]
+    pub address_filter_capacity: usize,
+    /// 签名布隆过滤器的预期签名数，0 表示不启用（默认）
+    #[serde(default)]
+    pub signature_filter_capacity: usize,
+    /// 后台维护任务配置
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+    /// 磁盘用量保护
+    #[serde(default)]
+    pub disk_guard: DiskGuardConfig,
+    /// 存储后端
+    #[serde(default)]
+    pub backend: StorageBackend,
+    /// 存储模式，精简模式只保存地址转账记录
+    #[serde(default)]
+    pub storage_mode: StorageMode,
+    /// 本进程读写的租户，未设置时使用默认租户
+    #[serde(default)]
+    pub tenant: Option<String>,
+    /// PostgreSQL 后端配置（backend = "postgres" 时必填）
+    #[serde(default)]
+    pub postgres: Option<PostgresConfig>,
+    /// 只读副本配置（api-only 模式使用）
+    #[serde(default)]
+    pub replica: ReplicaConfig,
+    /// RocksDB 调优参数
+    #[serde(default)]
+    pub rocksdb: RocksDbConfig,
+    /// 存储值加密配置，未配置时不加密
+    #[serde(default)]
+    pub encryption: Option<EncryptionConfig>,
+}
+
+/// 存储值加密配置（AES-256-GCM），密钥为 64 位十六进制字符串
+///
+/// 按 `key`、`key_env`、`key_command` 的顺序使用第一个配置的来源。
+#[derive(Debug, Clone, Deserialize)]
+pub struct EncryptionConfig {
+    /// 直接配置的密钥
+    #[serde(default)]
+    pub key: Option<String>,
+    /// 保存密钥的环境变量名
+    #[serde(default)]
+    pub key_env: Option<String>,
+    /// 输出密钥的命令及参数，如调用 KMS 解密数据密钥
+    #[serde(default)]
+    pub key_command: Vec<String>,
+}
+
+/// RocksDB 调优配置（默认值与原先硬编码的参数一致）
+#[derive(Debug, Clone, Deserialize)]
+pub struct RocksDbConfig {
+    /// 单个 memtable 写缓冲大小（MB）
+    #[serde(default = "default_write_buffer_size_mb")]
+    pub write_buffer_size_mb: u64,
+    /// 内存中最多保留的写缓冲数量
+    #[serde(default = "default_max_write_buffer_number")]
+    pub max_write_buffer_number: i32,
+    /// 最多同时打开的文件数（-1 表示不限制）
+    #[serde(default = "default_max_open_files")]
+    pub max_open_files: i32,
+    /// 合并策略
+    #[serde(default)]
+    pub compaction_style: RocksDbCompactionStyle,
+    /// 数据块压缩算法
+    #[serde(default)]
+    pub compression: RocksDbCompression,
+    /// 块缓存大小（MB）
+    #[serde(default = "default_block_cache_size_mb")]
+    pub block_cache_size_mb: u64,
+}
+
+impl Default for RocksDbConfig {
+    fn default() -> Self {
+        Self {
+            write_buffer_size_mb: default_write_buffer_size_mb(),
+            max_write_buffer_number: default_max_write_buffer_number(),
+            max_open_files: default_max_open_files(),
+            compaction_style: RocksDbCompactionStyle::default(),
+            compression: RocksDbCompression::default(),
+            block_cache_size_mb: default_block_cache_size_mb(),
+        }
+    }
+}
+
+/// RocksDB 合并策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RocksDbCompactionStyle {
+    /// 分层合并，读放大小，适合机械硬盘
+    Level,
+    /// 通用合并，写放大小（默认）
+    #[default]
+    Universal,
+    /// 先进先出，只适合可丢弃旧数据的场景
+    Fifo,
+}
+
+/// RocksDB 压缩算法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RocksDbCompression {
+    /// 不压缩
+    None,
+    /// Snappy（RocksDB 默认）
+    #[default]
+    Snappy,
+    /// LZ4，速度快，适合 NVMe
+    Lz4,
+    /// Zstd，压缩率高，适合磁盘空间紧张的场景
+    Zstd,
+}
+
+/// 只读副本配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplicaConfig {
+    /// secondary 实例自己的目录（未设置时使用 `{db_path}-secondary`）
+    #[serde(default)]
+    pub secondary_path: Option<String>,
+    /// 追赶主库写入的间隔（毫秒）
+    #[serde(default = "default_catch_up_interval_ms")]
+    pub catch_up_interval_ms: u64,
+}
+
+impl Default for ReplicaConfig {
+    fn default() -> Self {
+        Self {
+            secondary_path: None,
+            catch_up_interval_ms: default_catch_up_interval_ms(),
+        }
+    }
+}
+
+/// 存储后端类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// 嵌入式 RocksDB（默认）
+    #[default]
+    Rocksdb,
+    /// PostgreSQL（需要启用 `postgres` 特性）
+    Postgres,
+}
+
+/// 存储模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageMode {
+    /// 保存完整的签名记录和所有索引（默认）
+    #[default]
+    Full,
+    /// 只保存按地址的转账记录，不保存签名记录、统计和其他索引
+    Lite,
+}
+
+/// PostgreSQL 后端配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostgresConfig {
+    /// 连接字符串，如 `host=localhost user=ledger dbname=ledger`
+    pub url: String,
+    /// 保存键值数据的表名
+    #[serde(default = "default_postgres_table")]
+    pub table: String,
+}
+
+/// 数据库后台维护配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceConfig {
+    /// 是否启用维护任务
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// cron 表达式（秒 分 时 日 月 星期）
+    #[serde(default = "default_maintenance_schedule")]
+    pub schedule: String,
+    /// 是否执行数据库压缩
+    #[serde(default)]
+    pub compact: bool,
+    /// 是否清理超过 max_record_age_days 的地址记录
+    #[serde(default = "default_true")]
+    pub prune_expired_records: bool,
+    /// 签名数据保留天数（未设置则永久保留）
+    #[serde(default)]
+    pub signature_retention_days: Option<u64>,
+    /// 地址记录汇总天数：早于该天数的原始记录汇总为每日地址汇总后删除（未设置则不汇总）
+    #[serde(default)]
+    pub rollup_after_days: Option<u64>,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            schedule: default_maintenance_schedule(),
+            compact: false,
+            prune_expired_records: true,
+            signature_retention_days: None,
+            rollup_after_days: None,
+        }
+    }
+}
+
+/// 磁盘用量保护配置
+///
+/// 数据库目录超过大小上限或磁盘可用空间低于下限时，按 `action` 删除最旧的签名数据，
+/// 或暂停写入，直到用量回到阈值以内。检查间隔为 `health.disk_check_interval_secs`。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DiskGuardConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 数据库目录大小上限（MB），0 表示不限制
+    #[serde(default)]
+    pub max_db_size_mb: u64,
+    /// 磁盘可用空间下限（MB），0 表示不检查
+    #[serde(default = "default_guard_min_free_disk_mb")]
+    pub min_free_disk_mb: u64,
+    /// 超过阈值时的处理方式
+    #[serde(default)]
+    pub action: DiskGuardAction,
+    /// 每次清理删除的最旧签名数
+    #[serde(default = "default_guard_prune_batch")]
+    pub prune_batch: usize,
+}
+
+/// 磁盘用量超过阈值时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiskGuardAction {
+    /// 暂停写入，健康检查返回 paused（默认）
+    #[default]
+    Pause,
+    /// 删除最旧的交易及其地址记录和按交易的索引并压缩，没有可删除的数据时暂停写入
+    Prune,
+}
+
+impl DiskGuardAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Prune => "prune",
+        }
+    }
+}
+
+impl Default for DiskGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_db_size_mb: 0,
+            min_free_disk_mb: default_guard_min_free_disk_mb(),
+            action: DiskGuardAction::default(),
+            prune_batch: default_guard_prune_batch(),
+        }
+    }
+}
+
+fn default_guard_min_free_disk_mb() -> u64 {
+    512
+}
+
+fn default_guard_prune_batch() -> usize {
+    100_000
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_address_filter_capacity() -> usize {
+    10_000_000
+}
+
+fn default_catch_up_interval_ms() -> u64 {
+    1000
+}
+
+fn default_write_buffer_size_mb() -> u64 {
+    512
+}
+
+fn default_max_write_buffer_number() -> i32 {
+    32
+}
+
+fn default_max_open_files() -> i32 {
+    1000
+}
+
+fn default_block_cache_size_mb() -> u64 {
+    1024
+}
+
+fn default_postgres_table() -> String {
+    "ledger_kv".to_string()
+}
+
+fn default_maintenance_schedule() -> String {
+    "0 0 * * * *".to_string()
 }
 
 /// API 服务器配置
@@ -45,13 +794,588 @@ pub struct ApiConfig {
     pub port: u16,
     pub enable_cors: bool,
     pub log_level: String,
+    /// 管理接口 API Key，未配置时管理接口不可用
+    #[serde(default)]
+    pub admin_api_key: Option<String>,
+    /// 查询接口的请求超时（秒），超时返回 408；管理接口不受限制
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// 同时处理的最大请求数，超出的请求排队等待；0 表示不限制
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+    /// 请求体大小上限（字节），超出返回 413
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// 按 Accept-Encoding 以 gzip/brotli 压缩响应
+    #[serde(default = "default_true")]
+    pub enable_compression: bool,
+    /// HTTPS 配置，未配置时使用 HTTP
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    /// 多租户 API Key 映射，未配置时所有请求访问本进程的租户
+    #[serde(default)]
+    pub tenants: TenantsConfig,
+    /// 热点查询缓存
+    #[serde(default)]
+    pub cache: QueryCacheConfig,
+}
+
+/// 热点查询缓存配置，TTL 为 0 时不缓存对应查询
+#[derive(Debug, Clone, Deserialize)]
+pub struct QueryCacheConfig {
+    /// 签名查询缓存 TTL（秒）
+    #[serde(default = "default_signature_cache_ttl_secs")]
+    pub signature_ttl_secs: u64,
+    /// 地址统计缓存 TTL（秒）
+    #[serde(default = "default_address_stats_cache_ttl_secs")]
+    pub address_stats_ttl_secs: u64,
+    /// 全局统计缓存 TTL（秒）
+    #[serde(default = "default_stats_cache_ttl_secs")]
+    pub stats_ttl_secs: u64,
+    /// 签名和地址统计缓存各自的最大条目数
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: u64,
+}
+
+impl QueryCacheConfig {
+    /// 不缓存任何查询
+    pub fn disabled() -> Self {
+        Self {
+            signature_ttl_secs: 0,
+            address_stats_ttl_secs: 0,
+            stats_ttl_secs: 0,
+            max_entries: 0,
+        }
+    }
+}
+
+impl Default for QueryCacheConfig {
+    fn default() -> Self {
+        Self {
+            signature_ttl_secs: default_signature_cache_ttl_secs(),
+            address_stats_ttl_secs: default_address_stats_cache_ttl_secs(),
+            stats_ttl_secs: default_stats_cache_ttl_secs(),
+            max_entries: default_cache_max_entries(),
+        }
+    }
+}
+
+fn default_signature_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_address_stats_cache_ttl_secs() -> u64 {
+    30
+}
+
+fn default_stats_cache_ttl_secs() -> u64 {
+    5
+}
+
+fn default_cache_max_entries() -> u64 {
+    10_000
+}
+
+/// API 多租户配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct TenantsConfig {
+    /// 携带 API Key 的请求头
+    #[serde(default = "default_tenant_header")]
+    pub header: String,
+    /// API Key → 租户名
+    #[serde(default)]
+    pub api_keys: BTreeMap<String, String>,
+}
+
+impl Default for TenantsConfig {
+    fn default() -> Self {
+        Self {
+            header: default_tenant_header(),
+            api_keys: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_tenant_header() -> String {
+    "X-Api-Key".to_string()
+}
+
+/// API HTTPS 配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsConfig {
+    /// 证书链文件（PEM）
+    pub cert_path: String,
+    /// 私钥文件（PEM，PKCS#8 / PKCS#1 / SEC1）
+    pub key_path: String,
+    /// 检查证书文件是否更新的间隔（秒），文件变化后自动重新加载
+    #[serde(default = "default_tls_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+}
+
+fn default_tls_reload_interval_secs() -> u64 {
+    60
+}
+
+fn default_request_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_concurrent_requests() -> usize {
+    512
+}
+
+fn default_max_body_bytes() -> usize {
+    1024 * 1024
+}
+
+/// 过载降级配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct DegradationConfig {
+    /// 是否启用过载降级，默认关闭
+    #[serde(default)]
+    pub enabled: bool,
+    /// 处理延迟超过该值（毫秒）视为过载
+    #[serde(default = "default_degrade_lag_ms")]
+    pub degrade_lag_ms: u64,
+    /// 处理延迟低于该值（毫秒）视为恢复
+    #[serde(default = "default_recover_lag_ms")]
+    pub recover_lag_ms: u64,
+    /// 过载或恢复状态需要持续的秒数才切换模式
+    #[serde(default = "default_sustain_secs")]
+    pub sustain_secs: u64,
+    /// 过载时的降级策略
+    #[serde(default)]
+    pub strategy: OverloadStrategy,
+    /// 抽样模式下必须保留的单笔 SOL 转账金额（lamports）
+    #[serde(default = "default_sample_keep_lamports")]
+    pub sample_keep_lamports: u64,
+    /// 抽样模式下低于阈值的交易保留的百分比（0-100）
+    #[serde(default = "default_sample_percent")]
+    pub sample_percent: u8,
+}
+
+/// 过载降级策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverloadStrategy {
+    /// 非监控地址的交易只保存槽位汇总（默认）
+    #[default]
+    Summary,
+    /// 保留大额转账，其余非监控地址的交易按比例抽样保存完整明细，未抽中的丢弃
+    Sample,
+}
+
+impl Default for DegradationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            degrade_lag_ms: default_degrade_lag_ms(),
+            recover_lag_ms: default_recover_lag_ms(),
+            sustain_secs: default_sustain_secs(),
+            strategy: OverloadStrategy::default(),
+            sample_keep_lamports: default_sample_keep_lamports(),
+            sample_percent: default_sample_percent(),
+        }
+    }
+}
+
+fn default_degrade_lag_ms() -> u64 {
+    30_000
+}
+
+fn default_recover_lag_ms() -> u64 {
+    5_000
+}
+
+fn default_sustain_secs() -> u64 {
+    30
+}
+
+fn default_sample_keep_lamports() -> u64 {
+    1_000_000_000
+}
+
+fn default_sample_percent() -> u8 {
+    10
+}
+
+/// 健康检查配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthConfig {
+    /// 超过该秒数未收到 gRPC 消息即视为摄取停滞，健康检查返回 503
+    #[serde(default = "default_stall_threshold_secs")]
+    pub stall_threshold_secs: u64,
+    /// 最近存储交易的槽位落后最新槽位超过该值时报告为 degraded（仍返回 200）
+    #[serde(default = "default_max_slot_lag")]
+    pub max_slot_lag: u64,
+    /// `storage_error_window_secs` 内存储失败达到该次数时写入 storage_errors 事件，0 表示不检测
+    #[serde(default = "default_storage_error_threshold")]
+    pub storage_error_threshold: u32,
+    /// 存储失败的统计窗口（秒）
+    #[serde(default = "default_storage_error_window_secs")]
+    pub storage_error_window_secs: u64,
+    /// 数据库所在磁盘可用空间低于该值（MB）时写入 disk_space_low 事件，0 表示不检测
+    #[serde(default = "default_min_free_disk_mb")]
+    pub min_free_disk_mb: u64,
+    /// 检查磁盘可用空间的间隔（秒）
+    #[serde(default = "default_disk_check_interval_secs")]
+    pub disk_check_interval_secs: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            stall_threshold_secs: default_stall_threshold_secs(),
+            max_slot_lag: default_max_slot_lag(),
+            storage_error_threshold: default_storage_error_threshold(),
+            storage_error_window_secs: default_storage_error_window_secs(),
+            min_free_disk_mb: default_min_free_disk_mb(),
+            disk_check_interval_secs: default_disk_check_interval_secs(),
+        }
+    }
+}
+
+fn default_stall_threshold_secs() -> u64 {
+    60
+}
+
+fn default_max_slot_lag() -> u64 {
+    150
+}
+
+fn default_storage_error_threshold() -> u32 {
+    10
+}
+
+fn default_storage_error_window_secs() -> u64 {
+    60
+}
+
+fn default_min_free_disk_mb() -> u64 {
+    1024
+}
+
+fn default_disk_check_interval_secs() -> u64 {
+    60
+}
+
+/// 日志配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoggingConfig {
+    /// 默认日志级别
+    #[serde(default = "default_log_level")]
+    pub level: String,
+    /// 输出格式
+    #[serde(default)]
+    pub format: LogFormat,
+    /// 按模块设置的日志级别，如 `tower_http = "debug"`
+    #[serde(default)]
+    pub modules: BTreeMap<String, String>,
+    /// 日志文件输出（可选）
+    #[serde(default)]
+    pub file: Option<LogFileConfig>,
+    /// OTLP 链路追踪导出（可选，需要 `otel` 特性）
+    #[serde(default)]
+    pub otlp: Option<OtlpConfig>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: default_log_level(),
+            format: LogFormat::default(),
+            modules: BTreeMap::new(),
+            file: None,
+            otlp: None,
+        }
+    }
+}
+
+/// 日志输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 便于阅读的文本格式
+    #[default]
+    Pretty,
+    /// 每行一个 JSON 对象，便于日志系统采集
+    Json,
+}
+
+/// 日志文件配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogFileConfig {
+    /// 日志目录
+    pub directory: String,
+    /// 日志文件名前缀
+    #[serde(default = "default_log_file_prefix")]
+    pub prefix: String,
+    /// 滚动周期
+    #[serde(default)]
+    pub rotation: LogRotation,
+}
+
+/// OTLP 链路追踪导出配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct OtlpConfig {
+    /// OTLP gRPC 接收端地址，如 Jaeger / Tempo 的 `http://127.0.0.1:4317`
+    pub endpoint: String,
+    /// 上报的服务名
+    #[serde(default = "default_otlp_service_name")]
+    pub service_name: String,
+}
+
+/// 日志文件滚动周期
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// 每分钟
+    Minutely,
+    /// 每小时
+    Hourly,
+    /// 每天
+    #[default]
+    Daily,
+    /// 不滚动
+    Never,
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_log_file_prefix() -> String {
+    "ledger.log".to_string()
+}
+
+fn default_otlp_service_name() -> String {
+    "solana-transfer-ledger".to_string()
 }
 
 impl Config {
-    /// 从配置文件加载配置
-    pub fn load() -> Result<Self> {
-        let config_content = fs::read_to_string("config.toml")?;
-        let config: Config = toml::from_str(&config_content)?;
+    /// 从指定路径加载配置
+    ///
+    /// 依次合并三层：基础配置文件；`LEDGER_ENV` 指定环境时同目录下的 `<文件名>.<环境>.toml`
+    /// （如 `config.production.toml`，不存在则跳过）；`LEDGER_<段>__<字段>` 环境变量
+    /// （如 `LEDGER_GRPC__ENDPOINT`、`LEDGER_DATABASE__DB_PATH`）。后者覆盖前者。
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self> {
+        Self::load_layered(path.as_ref(), std::env::vars())
+    }
+
+    fn load_layered(path: &Path, vars: impl IntoIterator<Item = (String, String)>) -> Result<Self> {
+        let vars: Vec<(String, String)> = vars.into_iter().collect();
+        let mut merged = read_table(path)?;
+
+        if let Some((_, environment)) = vars.iter().find(|(name, _)| name == ENV_NAME_VAR) {
+            let env_path = environment_file(path, environment);
+            if env_path.exists() {
+                merge_tables(&mut merged, read_table(&env_path)?);
+            }
+        }
+        apply_env_overrides(&mut merged, &vars)?;
+
+        let config: Config = serde_path_to_error::deserialize(toml::Value::Table(merged))
+            .map_err(|e| anyhow::anyhow!("配置项 {} 无效: {}", e.path(), e.inner()))?;
+        config.validate()?;
         Ok(config)
     }
-} 
\ No newline at end of file
+
+    /// 检查反序列化无法表达的约束，错误信息包含配置项名称
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |field: &str, reason: &str| Err(anyhow::anyhow!("配置项 {} 无效: {}", field, reason));
+
+        if !self.grpc.endpoint.starts_with("http://") && !self.grpc.endpoint.starts_with("https://") {
+            return invalid("grpc.endpoint", "必须以 http:// 或 https:// 开头");
+        }
+        if self.database.db_path.trim().is_empty() {
+            return invalid("database.db_path", "不能为空");
+        }
+        for (field, prefix) in [
+            ("database.signature_key_prefix", &self.database.signature_key_prefix),
+            ("database.address_key_prefix", &self.database.address_key_prefix),
+        ] {
+            if prefix.len() != self.database.key_prefix_length {
+                return invalid(
+                    field,
+                    &format!("长度应等于 database.key_prefix_length（{}）", self.database.key_prefix_length),
+                );
+            }
+        }
+        if self.database.backend == StorageBackend::Postgres && self.database.postgres.is_none() {
+            return invalid("database.postgres", "backend = \"postgres\" 时必须配置");
+        }
+        if let Err(e) = cron::Schedule::from_str(&self.database.maintenance.schedule) {
+            return invalid("database.maintenance.schedule", &format!("cron 表达式无法解析: {}", e));
+        }
+        if let (Some(rollup), Some(max_age)) =
+            (self.database.maintenance.rollup_after_days, self.database.max_record_age_days)
+        {
+            if rollup >= max_age {
+                return invalid(
+                    "database.maintenance.rollup_after_days",
+                    "必须小于 database.max_record_age_days，否则记录会在汇总前被删除",
+                );
+            }
+        }
+        if let Some(encryption) = &self.database.encryption {
+            if encryption.key.is_none() && encryption.key_env.is_none() && encryption.key_command.is_empty() {
+                return invalid("database.encryption", "必须配置 key、key_env 或 key_command 之一");
+            }
+        }
+        if self.database.tenant.as_deref().is_some_and(|tenant| !StorageManager::is_valid_tenant(tenant)) {
+            return invalid("database.tenant", "只能包含字母、数字、- 和 _，最长 64 位");
+        }
+        if self.api.tenants.api_keys.values().any(|tenant| !StorageManager::is_valid_tenant(tenant)) {
+            return invalid("api.tenants.api_keys", "租户名只能包含字母、数字、- 和 _，最长 64 位");
+        }
+        if let Some(tls) = &self.api.tls {
+            if tls.cert_path.is_empty() || tls.key_path.is_empty() {
+                return invalid("api.tls", "cert_path 和 key_path 不能为空");
+            }
+        }
+        if let Some(event) = self
+            .webhooks
+            .lifecycle
+            .events
+            .iter()
+            .find(|event| !IngestionEvent::KINDS.contains(&event.as_str()))
+        {
+            return invalid("webhooks.lifecycle.events", &format!("未知的事件类型 {}", event));
+        }
+        let guard = &self.database.disk_guard;
+        if guard.enabled && guard.max_db_size_mb == 0 && guard.min_free_disk_mb == 0 {
+            return invalid("database.disk_guard", "启用时 max_db_size_mb 和 min_free_disk_mb 至少设置一个");
+        }
+        if self.degradation.sample_percent > 100 {
+            return invalid("degradation.sample_percent", "必须在 0-100 之间");
+        }
+        if self.prices.enabled && self.prices.provider == PriceProviderKind::Pyth && self.prices.pyth_feeds.is_empty() {
+            return invalid("prices.pyth_feeds", "provider = \"pyth\" 时必须配置");
+        }
+        Ok(())
+    }
+}
+
+/// 环境变量覆盖前缀
+const ENV_PREFIX: &str = "LEDGER_";
+
+/// 环境名变量，如 `LEDGER_ENV=production`
+const ENV_NAME_VAR: &str = "LEDGER_ENV";
+
+fn read_table(path: &Path) -> Result<toml::Table> {
+    let content = fs::read_to_string(path).with_context(|| format!("读取配置文件失败: {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("解析配置文件失败: {}", path.display()))
+}
+
+/// `config.toml` + `production` -> `config.production.toml`
+fn environment_file(path: &Path, environment: &str) -> std::path::PathBuf {
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!("{}.{}.toml", stem, environment))
+}
+
+/// 递归合并，`overlay` 中的值覆盖 `base`，表按字段合并
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// 应用 `LEDGER_<段>__<字段>` 环境变量，`__` 分隔层级，名称不区分大小写
+///
+/// 值按 TOML 解析（数字、布尔、数组），解析失败或原配置项为字符串时按字符串处理；
+/// 需要强制为字符串时可加引号，如 `LEDGER_API__ADMIN_API_KEY='"123456"'`。
+fn apply_env_overrides(table: &mut toml::Table, vars: &[(String, String)]) -> Result<()> {
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(ENV_PREFIX) else { continue };
+        let segments: Vec<String> = path.split("__").map(|segment| segment.to_lowercase()).collect();
+        if segments.len() < 2 || segments.iter().any(String::is_empty) {
+            continue;
+        }
+
+        let (field, sections) = segments.split_last().unwrap();
+        let mut current = &mut *table;
+        for section in sections {
+            let entry = current
+                .entry(section.clone())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            current = match entry {
+                toml::Value::Table(next) => next,
+                _ => anyhow::bail!("环境变量 {} 无效: {} 不是配置段", name, section),
+            };
+        }
+
+        let keep_string = matches!(current.get(field), Some(toml::Value::String(_)));
+        let value = match toml::from_str::<toml::Table>(&format!("value = {}", raw)) {
+            Ok(mut parsed) if !keep_string || raw.starts_with('"') => parsed.remove("value").unwrap(),
+            _ => toml::Value::String(raw.clone()),
+        };
+        current.insert(field.clone(), value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_rocksdb_config_defaults_and_overrides() {
+        let defaults: RocksDbConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.write_buffer_size_mb, 512);
+        assert_eq!(defaults.compaction_style, RocksDbCompactionStyle::Universal);
+        assert_eq!(defaults.compression, RocksDbCompression::Snappy);
+
+        let tuned: RocksDbConfig = toml::from_str(
+            "compaction_style = \"level\"\ncompression = \"zstd\"\nmax_open_files = -1",
+        )
+        .unwrap();
+        assert_eq!(tuned.compaction_style, RocksDbCompactionStyle::Level);
+        assert_eq!(tuned.compression, RocksDbCompression::Zstd);
+        assert_eq!(tuned.max_open_files, -1);
+        assert_eq!(tuned.block_cache_size_mb, 1024);
+    }
+
+    #[test]
+    fn test_layered_overrides_and_field_errors() {
+        let dir = TempDir::new("config-test");
+        let base = dir.path().join("config.toml");
+        fs::write(&base, include_str!("../config.toml")).unwrap();
+        fs::write(dir.path().join("config.production.toml"), "[api]\nport = 8080\n[database]\ndb_path = \"/srv/prod\"\n").unwrap();
+        let vars = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+        };
+
+        let config = Config::load_layered(&base, vars(&[])).unwrap();
+        assert_eq!(config.api.port, 7777);
+
+        let config = Config::load_layered(
+            &base,
+            vars(&[
+                ("LEDGER_ENV", "production"),
+                ("LEDGER_GRPC__ENDPOINT", "http://localhost:10000"),
+                ("LEDGER_DATABASE__DB_PATH", "/data/ledger"),
+                ("LEDGER_API__ADMIN_API_KEY", "\"123456\""),
+                ("LEDGER_DATABASE__ROCKSDB__MAX_OPEN_FILES", "-1"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.api.port, 8080);
+        assert_eq!(config.grpc.endpoint, "http://localhost:10000");
+        assert_eq!(config.database.db_path, "/data/ledger");
+        assert_eq!(config.api.admin_api_key.as_deref(), Some("123456"));
+        assert_eq!(config.database.rocksdb.max_open_files, -1);
+
+        let error = Config::load_layered(&base, vars(&[("LEDGER_API__PORT", "not-a-port")])).unwrap_err();
+        assert!(error.to_string().contains("api.port"), "{}", error);
+        let error = Config::load_layered(&base, vars(&[("LEDGER_GRPC__ENDPOINT", "localhost")])).unwrap_err();
+        assert!(error.to_string().contains("grpc.endpoint"), "{}", error);
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::config::{Config, MonitorConfig, ParserConfig};
+
+/// 无需重启即可生效的配置
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeSettings {
+    /// 监控过滤条件
+    pub monitor: MonitorConfig,
+    /// 转账解析参数
+    pub parser: ParserConfig,
+}
+
+impl RuntimeSettings {
+    /// 从完整配置中取出可热更新的部分
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            monitor: config.monitor.clone(),
+            parser: config.parser.clone(),
+        }
+    }
+}
+
+/// 配置热更新
+///
+/// 重新读取配置文件（包括环境文件和环境变量覆盖），校验通过后把可热更新的部分
+/// 广播给订阅者；校验失败时保留当前配置。克隆后共享同一状态。
+#[derive(Debug, Clone)]
+pub struct ConfigReloader {
+    path: PathBuf,
+    settings: Arc<watch::Sender<RuntimeSettings>>,
+    modified: Arc<Mutex<Option<SystemTime>>>,
+}
+
+impl ConfigReloader {
+    /// 以启动时加载的配置创建
+    pub fn new(path: impl Into<PathBuf>, config: &Config) -> Self {
+        let path = path.into();
+        let (settings, _) = watch::channel(RuntimeSettings::from_config(config));
+        let modified = Self::file_time(&path);
+        Self {
+            path,
+            settings: Arc::new(settings),
+            modified: Arc::new(Mutex::new(modified)),
+        }
+    }
+
+    /// 订阅配置变化
+    pub fn subscribe(&self) -> watch::Receiver<RuntimeSettings> {
+        self.settings.subscribe()
+    }
+
+    /// 重新加载配置文件，返回发生变化的配置段
+    pub fn reload(&self) -> Result<Vec<&'static str>> {
+        *self.modified.lock().unwrap() = Self::file_time(&self.path);
+        let loaded = RuntimeSettings::from_config(&Config::load_from(&self.path)?);
+
+        let mut changed = Vec::new();
+        self.settings.send_if_modified(|current| {
+            if current.monitor != loaded.monitor {
+                changed.push("monitor");
+            }
+            if current.parser != loaded.parser {
+                changed.push("parser");
+            }
+            *current = loaded;
+            !changed.is_empty()
+        });
+        Ok(changed)
+    }
+
+    /// 后台定期检查配置文件修改时间，变化后重新加载
+    pub async fn watch(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let modified = Self::file_time(&self.path);
+            if modified.is_none() || modified == *self.modified.lock().unwrap() {
+                continue;
+            }
+            match self.reload() {
+                Ok(changed) if changed.is_empty() => info!("🔧 配置文件已变化，可热更新的配置没有改动"),
+                Ok(changed) => info!("🔧 配置已热更新: {}", changed.join(", ")),
+                Err(e) => warn!("⚠️ 重新加载配置失败，继续使用当前配置: {:#}", e),
+            }
+        }
+    }
+
+    fn file_time(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_reload_broadcasts_changed_sections() {
+        let dir = TempDir::new("config-reload-test");
+        let path = dir.path().join("config.toml");
+        let original = include_str!("../config.toml");
+        std::fs::write(&path, original).unwrap();
+
+        let reloader = ConfigReloader::new(&path, &Config::load_from(&path).unwrap());
+        let mut changes = reloader.subscribe();
+        assert!(reloader.reload().unwrap().is_empty());
+        assert!(!changes.has_changed().unwrap());
+
+        let edited = original
+            .replace("include_vote_transactions = false", "include_vote_transactions = true")
+            .replace("dust_floor_lamports = 100000", "dust_floor_lamports = 5000");
+        std::fs::write(&path, edited).unwrap();
+        assert_eq!(reloader.reload().unwrap(), vec!["monitor", "parser"]);
+        assert!(changes.has_changed().unwrap());
+        let current = changes.borrow_and_update().clone();
+        assert!(current.monitor.include_vote_transactions);
+        assert_eq!(current.parser.dust_floor_lamports, 5000);
+
+        // 无效配置不影响当前配置
+        std::fs::write(&path, "not toml [").unwrap();
+        assert!(reloader.reload().is_err());
+        assert!(reloader.subscribe().borrow().monitor.include_vote_transactions);
+    }
+}
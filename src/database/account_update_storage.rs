@@ -0,0 +1,142 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 账户更新键前缀，键为 `<账户>:<槽位>:<写入版本>`，按时间排序
+pub const ACCOUNT_UPDATE_KEY_PREFIX: &str = "ACUP01";
+
+/// 账户最新状态键前缀，用于判断更新是否有变化
+pub const ACCOUNT_STATE_KEY_PREFIX: &str = "ACST01";
+
+/// 每个账户保留的最多更新数
+pub const MAX_ACCOUNT_UPDATES: usize = 1000;
+
+/// 账户更新
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountUpdate {
+    pub pubkey: String,
+    pub slot: u64,
+    pub lamports: u64,
+    pub owner: String,
+    pub executable: bool,
+    /// 配置的数据片段（十六进制），未配置片段时为空
+    pub data: String,
+    /// 同一槽位内的写入顺序
+    pub write_version: u64,
+    /// 引起更新的交易签名（启动时的快照为空）
+    pub txn_signature: Option<String>,
+}
+
+impl AccountUpdate {
+    /// 余额、所有者或数据是否与另一个状态不同
+    pub fn changed_from(&self, previous: &AccountUpdate) -> bool {
+        self.lamports != previous.lamports
+            || self.owner != previous.owner
+            || self.executable != previous.executable
+            || self.data != previous.data
+    }
+}
+
+/// 账户更新存储管理器
+///
+/// 只保存余额、所有者或数据片段有变化的更新，每个账户最多保留 `MAX_ACCOUNT_UPDATES` 条。
+#[derive(Debug, Clone)]
+pub struct AccountUpdateStorage {
+    storage: StorageManager,
+}
+
+impl AccountUpdateStorage {
+    /// 创建新的账户更新存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 保存账户更新，返回是否有变化（无变化或旧于已保存状态时不写入）
+    pub fn record_update(&self, update: &AccountUpdate) -> Result<bool> {
+        let state_key = self.storage.make_key(ACCOUNT_STATE_KEY_PREFIX, &update.pubkey)?;
+        if let Some(previous) = self.storage.get::<AccountUpdate>(&state_key)? {
+            let newer = (update.slot, update.write_version) > (previous.slot, previous.write_version);
+            if !newer || !update.changed_from(&previous) {
+                return Ok(false);
+            }
+        }
+
+        self.storage.batch_put(vec![
+            (self.make_key(&update.pubkey, update.slot, update.write_version)?, update),
+            (state_key, update),
+        ])?;
+        self.prune(&update.pubkey)?;
+        debug!("保存账户 {} 在槽位 {} 的更新", update.pubkey, update.slot);
+        Ok(true)
+    }
+
+    /// 账户最近的更新（最新的在前）
+    pub fn get_updates(&self, pubkey: &str, limit: usize) -> Result<Vec<AccountUpdate>> {
+        Ok(self
+            .storage
+            .get_by_prefix::<AccountUpdate>(&self.make_key_prefix(pubkey)?)?
+            .into_iter()
+            .rev()
+            .take(limit)
+            .map(|item| item.value)
+            .collect())
+    }
+
+    /// 删除超出保留数量的最旧更新
+    fn prune(&self, pubkey: &str) -> Result<()> {
+        let keys = self.storage.get_keys_by_prefix(&self.make_key_prefix(pubkey)?)?;
+        for key in keys.iter().take(keys.len().saturating_sub(MAX_ACCOUNT_UPDATES)) {
+            self.storage.delete(key)?;
+        }
+        Ok(())
+    }
+
+    fn make_key_prefix(&self, pubkey: &str) -> Result<String> {
+        self.storage.make_key(ACCOUNT_UPDATE_KEY_PREFIX, &format!("{}:", pubkey))
+    }
+
+    fn make_key(&self, pubkey: &str, slot: u64, write_version: u64) -> Result<String> {
+        self.storage
+            .make_key(ACCOUNT_UPDATE_KEY_PREFIX, &format!("{}:{:020}:{:020}", pubkey, slot, write_version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    const ACCOUNT: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    fn update(slot: u64, lamports: u64) -> AccountUpdate {
+        AccountUpdate {
+            pubkey: ACCOUNT.to_string(),
+            slot,
+            lamports,
+            owner: "11111111111111111111111111111111".to_string(),
+            executable: false,
+            data: String::new(),
+            write_version: 1,
+            txn_signature: None,
+        }
+    }
+
+    #[test]
+    fn test_only_changes_are_recorded() {
+        let dir = TempDir::new("account-update-test");
+        let storage = AccountUpdateStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        assert!(storage.record_update(&update(10, 100)).unwrap());
+        // 余额不变
+        assert!(!storage.record_update(&update(11, 100)).unwrap());
+        // 旧槽位的更新
+        assert!(!storage.record_update(&update(9, 50)).unwrap());
+        assert!(storage.record_update(&update(12, 80)).unwrap());
+
+        let updates = storage.get_updates(ACCOUNT, 10).unwrap();
+        assert_eq!(updates.iter().map(|u| (u.slot, u.lamports)).collect::<Vec<_>>(), vec![(12, 80), (10, 100)]);
+        assert!(storage.get_updates("other", 10).unwrap().is_empty());
+    }
+}
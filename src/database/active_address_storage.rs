@@ -0,0 +1,104 @@
+use anyhow::Result;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 每日活跃地址集合键前缀（键为 `{日期}:{地址}`）
+pub const ACTIVE_ADDRESS_KEY_PREFIX: &str = "DAUS01";
+/// 每日活跃地址计数键前缀
+pub const ACTIVE_ADDRESS_COUNT_KEY_PREFIX: &str = "DAUC01";
+
+/// 每日活跃地址数（按交易时间的 UTC 日期统计）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyActiveAddresses {
+    /// 日期（YYYY-MM-DD，UTC）
+    pub date: String,
+    /// 当天参与过转账的不同地址数
+    pub active_addresses: u64,
+}
+
+/// 每日活跃地址存储管理器
+///
+/// 每天保存精确的地址集合，地址当天首次出现时计数加一，查询时只读计数。
+#[derive(Debug, Clone)]
+pub struct ActiveAddressStorage {
+    storage: StorageManager,
+}
+
+impl ActiveAddressStorage {
+    /// 创建新的活跃地址存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 记录交易中参与转账的地址，返回当天新出现的地址数
+    pub fn record_addresses<'a>(&self, timestamp: i64, addresses: impl IntoIterator<Item = &'a str>) -> Result<u64> {
+        let date = Self::date_of(timestamp);
+        let addresses: BTreeSet<&str> = addresses.into_iter().collect();
+
+        let mut markers = Vec::new();
+        for address in addresses {
+            let key = self.storage.make_key(ACTIVE_ADDRESS_KEY_PREFIX, &format!("{}:{}", date, address))?;
+            if !self.storage.exists(&key)? {
+                markers.push((key, ()));
+            }
+        }
+        if markers.is_empty() {
+            return Ok(0);
+        }
+
+        let added = markers.len() as u64;
+        let count_key = self.storage.make_key(ACTIVE_ADDRESS_COUNT_KEY_PREFIX, &date)?;
+        let mut stats = self
+            .storage
+            .get::<DailyActiveAddresses>(&count_key)?
+            .unwrap_or(DailyActiveAddresses { date: date.clone(), ..Default::default() });
+        stats.active_addresses += added;
+
+        self.storage.batch_put(markers)?;
+        self.storage.put(&count_key, &stats)?;
+        debug!("{} 活跃地址数更新: {}", date, stats.active_addresses);
+        Ok(added)
+    }
+
+    /// 获取最近若干天的活跃地址数（最新的在前）
+    pub fn get_recent_days(&self, days: usize) -> Result<Vec<DailyActiveAddresses>> {
+        // 日期键按字典序即时间顺序排列
+        let items = self.storage.get_by_prefix::<DailyActiveAddresses>(ACTIVE_ADDRESS_COUNT_KEY_PREFIX)?;
+        Ok(items.into_iter().rev().take(days).map(|item| item.value).collect())
+    }
+
+    fn date_of(timestamp: i64) -> String {
+        DateTime::from_timestamp(timestamp, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_unique_addresses_counted_once_per_day() {
+        let dir = TempDir::new("active-address-test");
+        let storage = ActiveAddressStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        let day = 1_704_067_200;
+
+        assert_eq!(storage.record_addresses(day, ["alice", "bob", "alice"]).unwrap(), 2);
+        assert_eq!(storage.record_addresses(day + 60, ["bob", "carol"]).unwrap(), 1);
+        assert_eq!(storage.record_addresses(day + 60, ["carol"]).unwrap(), 0);
+        assert_eq!(storage.record_addresses(day + 86_400, ["alice"]).unwrap(), 1);
+
+        let days = storage.get_recent_days(30).unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0], DailyActiveAddresses { date: "2024-01-02".to_string(), active_addresses: 1 });
+        assert_eq!(days[1].active_addresses, 3);
+        assert_eq!(storage.get_recent_days(1).unwrap().len(), 1);
+    }
+}
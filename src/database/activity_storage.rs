@@ -0,0 +1,218 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+use crate::transfer_types::{SolTransfer, TokenTransfer};
+
+/// 地址活跃度索引键前缀
+pub const ACTIVITY_KEY_PREFIX: &str = "ACTV01";
+
+/// 时间桶粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityGranularity {
+    /// 按小时
+    Hour,
+    /// 按天（UTC）
+    Day,
+}
+
+impl ActivityGranularity {
+    /// 全部粒度，摄取时每种粒度各更新一个桶
+    pub const ALL: [Self; 2] = [Self::Hour, Self::Day];
+
+    /// 解析 `hour` / `day`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "hour" => Some(Self::Hour),
+            "day" => Some(Self::Day),
+            _ => None,
+        }
+    }
+
+    /// 名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+        }
+    }
+
+    /// 桶长度（秒）
+    pub fn seconds(&self) -> u64 {
+        match self {
+            Self::Hour => 3_600,
+            Self::Day => 86_400,
+        }
+    }
+
+    /// 时间戳所在桶的起始时间
+    pub fn bucket_start(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % self.seconds()
+    }
+}
+
+/// 某个地址在一个时间桶内的活动
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActivityBucket {
+    /// 桶起始时间戳
+    pub bucket_start: u64,
+    /// 涉及该地址的交易数
+    pub transaction_count: u64,
+    /// SOL 转账笔数
+    pub sol_transfer_count: u64,
+    /// 转出 SOL 总额（lamports）
+    pub sol_sent_lamports: u64,
+    /// 转入 SOL 总额（lamports）
+    pub sol_received_lamports: u64,
+    /// 代币转账笔数（不同代币无法直接累加金额，只记笔数）
+    pub token_transfer_count: u64,
+}
+
+/// 地址活跃度时间线
+///
+/// 摄取时按小时和按天分别累加，键格式为 `ACTV01{address}:{粒度}:{桶起始时间}`，
+/// 起始时间补零到固定宽度，按前缀扫描即得到按时间排序的桶。
+#[derive(Debug, Clone)]
+pub struct ActivityStorage {
+    storage: StorageManager,
+}
+
+impl ActivityStorage {
+    /// 创建新的活跃度索引实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 将一笔交易中的转账计入双方所在的时间桶
+    pub fn record_transaction(
+        &self,
+        timestamp: u64,
+        sol_transfers: &[SolTransfer],
+        token_transfers: &[TokenTransfer],
+    ) -> Result<()> {
+        // 先在内存中按地址合并，同一交易对每个地址只计一次
+        let mut deltas: BTreeMap<&str, ActivityBucket> = BTreeMap::new();
+        for transfer in sol_transfers {
+            let sent = deltas.entry(transfer.from.as_str()).or_default();
+            sent.sol_transfer_count += 1;
+            sent.sol_sent_lamports += transfer.amount;
+            let received = deltas.entry(transfer.to.as_str()).or_default();
+            received.sol_received_lamports += transfer.amount;
+            if transfer.from != transfer.to {
+                received.sol_transfer_count += 1;
+            }
+        }
+        for transfer in token_transfers {
+            deltas.entry(transfer.sender_wallet()).or_default().token_transfer_count += 1;
+            if transfer.sender_wallet() != transfer.receiver_wallet() {
+                deltas.entry(transfer.receiver_wallet()).or_default().token_transfer_count += 1;
+            }
+        }
+
+        let mut items = Vec::with_capacity(deltas.len() * ActivityGranularity::ALL.len());
+        for (address, delta) in &deltas {
+            for granularity in ActivityGranularity::ALL {
+                let bucket_start = granularity.bucket_start(timestamp);
+                let key = self.make_key(address, granularity, bucket_start)?;
+                let mut bucket = self.storage.get::<ActivityBucket>(&key)?.unwrap_or(ActivityBucket {
+                    bucket_start,
+                    ..Default::default()
+                });
+                bucket.transaction_count += 1;
+                bucket.sol_transfer_count += delta.sol_transfer_count;
+                bucket.sol_sent_lamports += delta.sol_sent_lamports;
+                bucket.sol_received_lamports += delta.sol_received_lamports;
+                bucket.token_transfer_count += delta.token_transfer_count;
+                items.push((key, bucket));
+            }
+        }
+
+        if !items.is_empty() {
+            debug!("更新 {} 个地址的活跃度时间桶", deltas.len());
+            self.storage.batch_put(items)?;
+        }
+        Ok(())
+    }
+
+    /// 获取地址在 `[start, end]` 内的时间桶（按时间升序），最多 `limit` 个最近的桶
+    pub fn get_activity(
+        &self,
+        address: &str,
+        granularity: ActivityGranularity,
+        start: Option<u64>,
+        end: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<ActivityBucket>> {
+        let prefix = format!("{}{}:{}:", ACTIVITY_KEY_PREFIX, address, granularity.as_str());
+        let start = start.map(|start| granularity.bucket_start(start)).unwrap_or(0);
+        let end = end.unwrap_or(u64::MAX);
+        let buckets: Vec<ActivityBucket> = self
+            .storage
+            .get_by_prefix::<ActivityBucket>(&prefix)?
+            .into_iter()
+            .map(|item| item.value)
+            .filter(|bucket| bucket.bucket_start >= start && bucket.bucket_start <= end)
+            .collect();
+        let skip = buckets.len().saturating_sub(limit);
+        Ok(buckets.into_iter().skip(skip).collect())
+    }
+
+    fn make_key(&self, address: &str, granularity: ActivityGranularity, bucket_start: u64) -> Result<String> {
+        self.storage.make_key(
+            ACTIVITY_KEY_PREFIX,
+            &format!("{}:{}:{:012}", address, granularity.as_str(), bucket_start),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn sol(from: &str, to: &str, amount: u64) -> SolTransfer {
+        SolTransfer {
+            signature: "sig".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            from_index: 0,
+            to_index: 0,
+            amount,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        }
+    }
+
+    #[test]
+    fn test_hourly_and_daily_buckets() {
+        let dir = TempDir::new("activity-test");
+        let storage = ActivityStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        let day = 1_704_067_200;
+
+        storage.record_transaction(day + 60, &[sol("alice", "bob", 100), sol("alice", "carol", 5)], &[]).unwrap();
+        storage.record_transaction(day + 3_700, &[sol("bob", "alice", 40)], &[]).unwrap();
+        storage.record_transaction(day + 86_400, &[sol("alice", "bob", 1)], &[]).unwrap();
+
+        let hourly = storage.get_activity("alice", ActivityGranularity::Hour, None, None, 100).unwrap();
+        assert_eq!(hourly.len(), 3);
+        assert_eq!(hourly[0].bucket_start, day);
+        assert_eq!((hourly[0].transaction_count, hourly[0].sol_transfer_count), (1, 2));
+        assert_eq!(hourly[0].sol_sent_lamports, 105);
+        assert_eq!(hourly[1].sol_received_lamports, 40);
+
+        let daily = storage.get_activity("alice", ActivityGranularity::Day, None, None, 100).unwrap();
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].transaction_count, 2);
+        assert_eq!(daily[1].bucket_start, day + 86_400);
+
+        // 时间范围与数量限制（保留最近的桶）
+        let ranged = storage.get_activity("alice", ActivityGranularity::Hour, Some(day + 1_800), None, 100).unwrap();
+        assert_eq!(ranged.len(), 3);
+        let latest = storage.get_activity("alice", ActivityGranularity::Hour, None, None, 1).unwrap();
+        assert_eq!(latest[0].bucket_start, day + 86_400);
+    }
+}
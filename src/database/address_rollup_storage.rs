@@ -0,0 +1,226 @@
+use anyhow::Result;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::database::address_storage::{AddressTransactionRecord, RecordType};
+use crate::database::storage::StorageManager;
+
+/// 地址每日汇总键前缀
+pub const ADDRESS_ROLLUP_KEY_PREFIX: &str = "ADRL01";
+
+/// 某个地址在一天（UTC）内的转账汇总
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyAddressRollup {
+    /// 地址
+    pub address: String,
+    /// 日期（YYYY-MM-DD）
+    pub date: String,
+    /// 汇总的记录数
+    pub total_records: u64,
+    /// SOL发送次数
+    pub sol_sent_count: u64,
+    /// SOL接收次数
+    pub sol_received_count: u64,
+    /// 代币发送次数
+    pub token_sent_count: u64,
+    /// 代币接收次数
+    pub token_received_count: u64,
+    /// 总SOL发送数量（lamports）
+    pub total_sol_sent: u64,
+    /// 总SOL接收数量（lamports）
+    pub total_sol_received: u64,
+    /// 当天最早一条记录的时间戳
+    pub first_timestamp: u64,
+    /// 当天最晚一条记录的时间戳
+    pub last_timestamp: u64,
+}
+
+impl DailyAddressRollup {
+    /// 计入一条地址记录
+    pub fn add_record(&mut self, record: &AddressTransactionRecord) {
+        self.total_records += 1;
+        match (&record.sol_transfer, &record.record_type) {
+            (Some(sol), RecordType::Sender) => {
+                self.sol_sent_count += 1;
+                self.total_sol_sent += sol.amount;
+            }
+            (Some(sol), RecordType::Receiver) => {
+                self.sol_received_count += 1;
+                self.total_sol_received += sol.amount;
+            }
+            _ => {}
+        }
+        match (&record.token_transfer, &record.record_type) {
+            (Some(_), RecordType::Sender) => self.token_sent_count += 1,
+            (Some(_), RecordType::Receiver) => self.token_received_count += 1,
+            _ => {}
+        }
+        self.extend_range(record.timestamp, record.timestamp);
+    }
+
+    /// 合并同一地址同一天的另一份汇总
+    pub fn merge(&mut self, other: &DailyAddressRollup) {
+        self.total_records += other.total_records;
+        self.sol_sent_count += other.sol_sent_count;
+        self.sol_received_count += other.sol_received_count;
+        self.token_sent_count += other.token_sent_count;
+        self.token_received_count += other.token_received_count;
+        self.total_sol_sent += other.total_sol_sent;
+        self.total_sol_received += other.total_sol_received;
+        self.extend_range(other.first_timestamp, other.last_timestamp);
+    }
+
+    fn extend_range(&mut self, first: u64, last: u64) {
+        if self.first_timestamp == 0 || first < self.first_timestamp {
+            self.first_timestamp = first;
+        }
+        self.last_timestamp = self.last_timestamp.max(last);
+    }
+
+    /// 时间戳所在的日期（UTC）
+    pub fn date_of(timestamp: u64) -> String {
+        DateTime::from_timestamp(timestamp as i64, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    /// 按日期分组汇总一组地址记录
+    pub fn aggregate(address: &str, records: &[AddressTransactionRecord]) -> BTreeMap<String, DailyAddressRollup> {
+        let mut days = BTreeMap::new();
+        for record in records {
+            let date = Self::date_of(record.timestamp);
+            days.entry(date.clone())
+                .or_insert_with(|| DailyAddressRollup {
+                    address: address.to_string(),
+                    date,
+                    ..Default::default()
+                })
+                .add_record(record);
+        }
+        days
+    }
+}
+
+/// 地址每日汇总表
+///
+/// 维护任务把超过汇总期限的原始地址记录按天聚合到这里后再删除，
+/// 键格式为 `ADRL01{address}:{YYYY-MM-DD}`，按地址前缀扫描即按日期升序。
+/// 汇总表每个地址每天只有一条，历史统计可以无限回溯而存储量有界。
+#[derive(Debug, Clone)]
+pub struct AddressRollupStorage {
+    storage: StorageManager,
+}
+
+impl AddressRollupStorage {
+    /// 创建新的地址汇总存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 把记录合并进已有的每日汇总，返回待写入的键值
+    ///
+    /// 不直接写库，调用方将结果与删除原始记录放在同一个批次里提交，
+    /// 避免中途失败导致记录被重复汇总。
+    pub fn merge_records(
+        &self,
+        address: &str,
+        records: &[AddressTransactionRecord],
+    ) -> Result<Vec<(String, DailyAddressRollup)>> {
+        DailyAddressRollup::aggregate(address, records)
+            .into_values()
+            .map(|rollup| {
+                let key = self.rollup_key(address, &rollup.date)?;
+                let mut merged = self.storage.get::<DailyAddressRollup>(&key)?.unwrap_or_default();
+                if merged.date.is_empty() {
+                    merged.address = rollup.address.clone();
+                    merged.date = rollup.date.clone();
+                }
+                merged.merge(&rollup);
+                Ok((key, merged))
+            })
+            .collect()
+    }
+
+    /// 获取地址的每日汇总，按日期升序排列，日期范围两端均包含
+    pub fn get_rollups(
+        &self,
+        address: &str,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+    ) -> Result<Vec<DailyAddressRollup>> {
+        let prefix = format!("{}{}:", ADDRESS_ROLLUP_KEY_PREFIX, address);
+        Ok(self
+            .storage
+            .get_by_prefix::<DailyAddressRollup>(&prefix)?
+            .into_iter()
+            .map(|entry| entry.value)
+            .filter(|rollup| start_date.is_none_or(|start| rollup.date.as_str() >= start))
+            .filter(|rollup| end_date.is_none_or(|end| rollup.date.as_str() <= end))
+            .collect())
+    }
+
+    fn rollup_key(&self, address: &str, date: &str) -> Result<String> {
+        self.storage.make_key(ADDRESS_ROLLUP_KEY_PREFIX, &format!("{}:{}", address, date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transfer_types::SolTransfer;
+    use crate::test_util::TempDir;
+
+    fn record(signature: &str, timestamp: u64, amount: u64, record_type: RecordType) -> AddressTransactionRecord {
+        AddressTransactionRecord {
+            transfer_id: format!("{}:sol0:{}", signature, record_type.leg()),
+            signature: signature.to_string(),
+            timestamp,
+            slot: timestamp,
+            sol_transfer: Some(SolTransfer {
+                signature: signature.to_string(),
+                from: "alice".to_string(),
+                to: "bob".to_string(),
+                amount,
+                from_index: 0,
+                to_index: 1,
+                timestamp: timestamp as u32,
+                transfer_type: "SOL Transfer".to_string(),
+                is_wrapped: false,
+                instruction_path: None,
+            }),
+            token_transfer: None,
+            record_type,
+            direction: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_records_accumulates_per_day() {
+        let dir = TempDir::new("address-rollup-test");
+        let storage = StorageManager::new(dir.path(), 6).unwrap();
+        let rollups = AddressRollupStorage::new(storage.clone());
+        let day = 86_400;
+        let base = 1_704_067_200; // 2024-01-01
+
+        let first = rollups
+            .merge_records("alice", &[record("a", base + 10, 5, RecordType::Sender), record("b", base + day, 7, RecordType::Receiver)])
+            .unwrap();
+        storage.batch_put(first).unwrap();
+        // 同一天的记录分两次汇总时累加
+        let second = rollups.merge_records("alice", &[record("c", base + 5, 3, RecordType::Sender)]).unwrap();
+        storage.batch_put(second).unwrap();
+
+        let days = rollups.get_rollups("alice", None, None).unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2024-01-01");
+        assert_eq!((days[0].sol_sent_count, days[0].total_sol_sent), (2, 8));
+        assert_eq!((days[0].first_timestamp, days[0].last_timestamp), (base + 5, base + 10));
+        assert_eq!(days[1].total_sol_received, 7);
+
+        let filtered = rollups.get_rollups("alice", Some("2024-01-02"), None).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert!(rollups.get_rollups("alic", None, None).unwrap().is_empty());
+    }
+}
@@ -1,12 +1,24 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
 use tracing::{debug, info};
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::bloom_filter::BloomFilter;
+use crate::database::address_rollup_storage::AddressRollupStorage;
 use crate::database::storage::{StorageManager, StorageResult};
-use crate::transfer_parser::{SolTransfer, TokenTransfer};
+use crate::transfer_types::{SolTransfer, TokenTransfer};
+
+/// 地址统计键前缀（与记录列表分开保存，统计接口只读取这一个小值）
+pub const ADDRESS_STATS_KEY_PREFIX: &str = "ADST01";
 
 /// 地址交易记录项
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddressTransactionRecord {
+    /// 确定性转账ID（签名 + 指令路径或转账序号 + 方向），用于去重
+    #[serde(default)]
+    pub transfer_id: String,
     /// 交易签名
     pub signature: String,
     /// 交易时间戳
@@ -19,6 +31,9 @@ pub struct AddressTransactionRecord {
     pub token_transfer: Option<TokenTransfer>,
     /// 记录类型（发送还是接收）
     pub record_type: RecordType,
+    /// 转账方向，写入时确定；旧数据中为空，读取时由 `direction()` 推断
+    #[serde(default)]
+    pub direction: Option<RecordDirection>,
 }
 
 /// 记录类型
@@ -30,6 +45,92 @@ pub enum RecordType {
     Receiver,
 }
 
+/// 转账方向（相对于记录所属的地址）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordDirection {
+    /// 转入
+    In,
+    /// 转出
+    Out,
+    /// 发送方和接收方是同一地址
+    #[serde(rename = "self")]
+    SelfTransfer,
+}
+
+impl RecordDirection {
+    /// 按记录类型和转账两端确定方向
+    pub fn of(record_type: &RecordType, from: &str, to: &str) -> Self {
+        match record_type {
+            _ if !from.is_empty() && from == to => Self::SelfTransfer,
+            RecordType::Sender => Self::Out,
+            RecordType::Receiver => Self::In,
+        }
+    }
+
+    /// 解析 `in` / `out` / `self`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "in" => Some(Self::In),
+            "out" => Some(Self::Out),
+            "self" => Some(Self::SelfTransfer),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::In => "in",
+            Self::Out => "out",
+            Self::SelfTransfer => "self",
+        }
+    }
+}
+
+impl RecordType {
+    /// 转账方向标识，用于生成转账ID
+    pub fn leg(&self) -> &'static str {
+        match self {
+            RecordType::Sender => "out",
+            RecordType::Receiver => "in",
+        }
+    }
+}
+
+impl AddressTransactionRecord {
+    /// 生成确定性转账ID
+    ///
+    /// 由转账指令得到的转账按指令路径生成，格式为 `签名:类型@指令路径:方向`，例如 `5VER...:sol@1.0:out`，
+    /// 与解析结果中的转账顺序无关；没有指令路径的转账（余额匹配推断的 SOL 转账、代币转账）
+    /// 退回到转账序号，格式为 `签名:类型序号:方向`，例如 `5VER...:token0:in`。
+    /// 重复处理同一交易得到相同的ID，不会产生重复记录。
+    pub fn make_transfer_id(
+        signature: &str,
+        kind: &str,
+        transfer_index: usize,
+        instruction_path: Option<&str>,
+        record_type: &RecordType,
+    ) -> String {
+        match instruction_path {
+            Some(path) => format!("{}:{}@{}:{}", signature, kind, path, record_type.leg()),
+            None => format!("{}:{}{}:{}", signature, kind, transfer_index, record_type.leg()),
+        }
+    }
+
+    /// 转账方向，旧数据没有保存时按记录类型和转账两端推断
+    pub fn direction(&self) -> RecordDirection {
+        if let Some(direction) = self.direction {
+            return direction;
+        }
+        let (from, to) = match (&self.sol_transfer, &self.token_transfer) {
+            (Some(sol), _) => (sol.from.as_str(), sol.to.as_str()),
+            (None, Some(token)) => (token.sender_wallet(), token.receiver_wallet()),
+            (None, None) => ("", ""),
+        };
+        RecordDirection::of(&self.record_type, from, to)
+    }
+}
+
 /// 地址交易记录列表
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddressTransactionList {
@@ -41,12 +142,57 @@ pub struct AddressTransactionList {
     pub last_updated: u64,
 }
 
+/// 按键遍历地址时每批读取的键数
+const ADDRESS_SCAN_CHUNK_SIZE: usize = 10_000;
+
+/// 地址写锁分段数
+const ADDRESS_LOCK_STRIPES: usize = 64;
+
+/// 按地址哈希分段的写锁
+///
+/// 记录列表和统计的读改写都在地址所在分段的锁内完成，摄取写入和维护任务的清理、汇总
+/// 不会互相覆盖对方的修改。同时锁多个地址时按分段序号加锁，避免死锁。
+#[derive(Debug)]
+struct AddressLocks {
+    stripes: Vec<Mutex<()>>,
+}
+
+impl AddressLocks {
+    fn new() -> Self {
+        Self { stripes: (0..ADDRESS_LOCK_STRIPES).map(|_| Mutex::new(())).collect() }
+    }
+
+    fn stripe(&self, address: &str) -> usize {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        address.hash(&mut hasher);
+        (hasher.finish() % self.stripes.len() as u64) as usize
+    }
+
+    /// 锁住单个地址
+    fn lock(&self, address: &str) -> MutexGuard<'_, ()> {
+        self.stripes[self.stripe(address)].lock().unwrap()
+    }
+
+    /// 锁住多个地址，每个分段只加锁一次
+    fn lock_all<'a>(&self, addresses: impl IntoIterator<Item = &'a str>) -> Vec<MutexGuard<'_, ()>> {
+        let stripes: BTreeSet<usize> = addresses.into_iter().map(|address| self.stripe(address)).collect();
+        stripes.into_iter().map(|stripe| self.stripes[stripe].lock().unwrap()).collect()
+    }
+}
+
 /// 地址存储管理器
 #[derive(Debug, Clone)]
 pub struct AddressStorage {
     storage: StorageManager,
     address_prefix: String,
     max_records: usize,
+    /// 记录最长保留时间（秒），None 表示不按时间清理
+    max_record_age_secs: Option<u64>,
+    clock: SharedClock,
+    /// 有记录的地址的布隆过滤器，由 `build_filter` 创建，各克隆共享
+    filter: Arc<OnceLock<BloomFilter>>,
+    /// 地址写锁，各克隆共享
+    locks: Arc<AddressLocks>,
 }
 
 impl AddressStorage {
@@ -56,6 +202,57 @@ impl AddressStorage {
             storage,
             address_prefix,
             max_records,
+            max_record_age_secs: None,
+            clock: SystemClock::shared(),
+            filter: Arc::new(OnceLock::new()),
+            locks: Arc::new(AddressLocks::new()),
+        }
+    }
+
+    /// 设置记录最长保留天数
+    pub fn with_max_record_age_days(mut self, days: Option<u64>) -> Self {
+        self.max_record_age_secs = days.map(|days| days * 24 * 60 * 60);
+        self
+    }
+
+    /// 替换时钟（用于测试）
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 创建地址布隆过滤器并载入已有地址，返回载入的地址数
+    ///
+    /// 过滤器先创建再扫描，扫描期间写入的地址同样会被记录；载入完成前不做否定判断。
+    /// 只应在负责写入的进程中调用，只读副本看不到主库的新写入，过滤器会漏报。
+    pub fn build_filter(&self, capacity: usize) -> Result<usize> {
+        let filter = self.filter.get_or_init(|| BloomFilter::with_capacity(capacity));
+        let mut loaded = 0;
+        self.for_each_address_chunk(|addresses| {
+            for address in &addresses {
+                filter.insert(address);
+            }
+            loaded += addresses.len();
+            Ok(())
+        })?;
+        filter.mark_ready();
+        info!(
+            "🌸 地址布隆过滤器已就绪: {} 个地址, 占用 {} MB",
+            loaded,
+            filter.size_bytes() / (1024 * 1024)
+        );
+        Ok(loaded)
+    }
+
+    /// 地址是否可能有记录；过滤器未启用时总是返回 true
+    pub fn may_have_records(&self, address: &str) -> bool {
+        self.filter.get().is_none_or(|filter| filter.might_contain(address))
+    }
+
+    /// 写入前把地址记入过滤器，保证读到新记录的查询不会被过滤掉
+    fn remember(&self, address: &str) {
+        if let Some(filter) = self.filter.get() {
+            filter.insert(address);
         }
     }
 
@@ -66,13 +263,22 @@ impl AddressStorage {
         signature: &str,
         timestamp: u64,
         slot: u64,
+        transfer_index: usize,
         sol_transfer: SolTransfer,
         record_type: RecordType,
     ) -> Result<()> {
         let record = AddressTransactionRecord {
+            transfer_id: AddressTransactionRecord::make_transfer_id(
+                signature,
+                "sol",
+                transfer_index,
+                sol_transfer.instruction_path.as_deref(),
+                &record_type,
+            ),
             signature: signature.to_string(),
             timestamp,
             slot,
+            direction: Some(RecordDirection::of(&record_type, &sol_transfer.from, &sol_transfer.to)),
             sol_transfer: Some(sol_transfer),
             token_transfer: None,
             record_type,
@@ -88,13 +294,20 @@ impl AddressStorage {
         signature: &str,
         timestamp: u64,
         slot: u64,
+        transfer_index: usize,
         token_transfer: TokenTransfer,
         record_type: RecordType,
     ) -> Result<()> {
         let record = AddressTransactionRecord {
+            transfer_id: AddressTransactionRecord::make_transfer_id(signature, "token", transfer_index, None, &record_type),
             signature: signature.to_string(),
             timestamp,
             slot,
+            direction: Some(RecordDirection::of(
+                &record_type,
+                token_transfer.sender_wallet(),
+                token_transfer.receiver_wallet(),
+            )),
             sol_transfer: None,
             token_transfer: Some(token_transfer),
             record_type,
@@ -105,47 +318,257 @@ impl AddressStorage {
 
     /// 添加交易记录到地址
     fn add_record(&self, address: &str, record: AddressTransactionRecord) -> Result<()> {
-        let key = format!("{}{}", self.address_prefix, address);
-        
-        // 获取现有记录列表
-        let mut address_list = match self.storage.get::<AddressTransactionList>(&key)? {
-            Some(list) => list,
-            None => AddressTransactionList {
-                address: address.to_string(),
-                records: Vec::new(),
-                last_updated: 0,
-            },
+        let _guard = self.locks.lock(address);
+        let key = self.make_key(address);
+        let mut address_list = self.load_list(&key, address)?;
+        let mut stats = self.load_stats(&address_list)?;
+
+        if self.apply_record(&mut address_list, &mut stats, record) {
+            self.remember(address);
+            self.storage.batch_put(vec![
+                (key, serde_json::to_value(&address_list)?),
+                (self.make_stats_key(address)?, serde_json::to_value(&stats)?),
+            ])?;
+            debug!("地址 {} 添加了新的交易记录，当前记录数: {}", address, address_list.records.len());
+        }
+
+        Ok(())
+    }
+
+    fn make_key(&self, address: &str) -> String {
+        format!("{}{}", self.address_prefix, address)
+    }
+
+    fn make_stats_key(&self, address: &str) -> Result<String> {
+        self.storage.make_key(ADDRESS_STATS_KEY_PREFIX, address)
+    }
+
+    /// 读取地址的记录列表，不存在时返回空列表
+    fn load_list(&self, key: &str, address: &str) -> Result<AddressTransactionList> {
+        let existing = if self.may_have_records(address) {
+            self.storage.get::<AddressTransactionList>(key)?
+        } else {
+            None
         };
+        Ok(existing.unwrap_or(AddressTransactionList {
+            address: address.to_string(),
+            records: Vec::new(),
+            last_updated: 0,
+        }))
+    }
+
+    /// 读取地址的统计，旧数据没有统计时根据记录列表重算一次
+    fn load_stats(&self, address_list: &AddressTransactionList) -> Result<AddressStats> {
+        if !self.may_have_records(&address_list.address) {
+            return Ok(AddressStats::from_records(&address_list.address, &address_list.records));
+        }
+        Ok(self
+            .storage
+            .get::<AddressStats>(&self.make_stats_key(&address_list.address)?)?
+            .unwrap_or_else(|| AddressStats::from_records(&address_list.address, &address_list.records)))
+    }
+
+    /// 在内存中把记录合并进列表并同步统计，返回列表是否发生变化
+    fn apply_record(
+        &self,
+        address_list: &mut AddressTransactionList,
+        stats: &mut AddressStats,
+        record: AddressTransactionRecord,
+    ) -> bool {
+        // 同一转账已记录过则跳过，保证重复处理同一交易是幂等的
+        if !record.transfer_id.is_empty()
+            && address_list.records.iter().any(|existing| existing.transfer_id == record.transfer_id)
+        {
+            debug!("地址 {} 已存在转账 {}，跳过", address_list.address, record.transfer_id);
+            return false;
+        }
 
         // 在列表开头插入新记录（索引0是最新的）
+        stats.add_record(&record);
         address_list.records.insert(0, record);
-        address_list.last_updated = chrono::Utc::now().timestamp() as u64;
+        address_list.last_updated = self.clock.timestamp() as u64;
 
         // 如果记录数超过限制，删除最老的记录
         if address_list.records.len() > self.max_records {
-            let removed_count = address_list.records.len() - self.max_records;
-            address_list.records.truncate(self.max_records);
-            debug!("地址 {} 删除了 {} 条最老的记录", address, removed_count);
+            let evicted: Vec<_> = address_list.records.drain(self.max_records..).collect();
+            for removed in &evicted {
+                stats.remove_record(removed);
+            }
+            debug!("地址 {} 删除了 {} 条最老的记录", address_list.address, evicted.len());
         }
 
-        // 保存更新后的列表
-        self.storage.put(&key, &address_list)?;
-        debug!("地址 {} 添加了新的交易记录，当前记录数: {}", address, address_list.records.len());
+        // 删除超过保留期限的记录
+        let expired = self.remove_expired(&mut address_list.records);
+        for removed in &expired {
+            stats.remove_record(removed);
+        }
+        if !expired.is_empty() {
+            debug!("地址 {} 删除了 {} 条过期记录", address_list.address, expired.len());
+        }
 
-        Ok(())
+        true
+    }
+
+    /// 过期记录的截止时间戳，早于此时间的记录将被删除
+    fn expiry_cutoff(&self) -> Option<u64> {
+        self.max_record_age_secs
+            .map(|age| (self.clock.timestamp().max(0) as u64).saturating_sub(age))
+    }
+
+    /// 从记录列表中删除过期记录，返回被删除的记录
+    fn remove_expired(&self, records: &mut Vec<AddressTransactionRecord>) -> Vec<AddressTransactionRecord> {
+        let Some(cutoff) = self.expiry_cutoff() else {
+            return Vec::new();
+        };
+        let (kept, expired) = std::mem::take(records)
+            .into_iter()
+            .partition(|record| record.timestamp >= cutoff);
+        *records = kept;
+        expired
+    }
+
+    /// 清理所有地址中的过期记录，返回删除的记录数
+    ///
+    /// 记录全部过期的地址会被整体删除。未设置保留天数时不做任何操作。地址按键分批遍历，不会一次载入全部地址。
+    pub fn prune_expired_records(&self) -> Result<usize> {
+        if self.max_record_age_secs.is_none() {
+            return Ok(0);
+        }
+
+        let mut removed_total = 0;
+        let mut removed_addresses = 0;
+        self.for_each_address_chunk(|addresses| {
+            for address in addresses {
+                let _guard = self.locks.lock(&address);
+                let key = self.make_key(&address);
+                let Some(mut address_list) = self.storage.get::<AddressTransactionList>(&key)? else {
+                    continue;
+                };
+                let mut stats = self.load_stats(&address_list)?;
+
+                let expired = self.remove_expired(&mut address_list.records);
+                if expired.is_empty() {
+                    continue;
+                }
+                removed_total += expired.len();
+
+                if address_list.records.is_empty() {
+                    self.remove_list(&address)?;
+                    removed_addresses += 1;
+                } else {
+                    for removed in &expired {
+                        stats.remove_record(removed);
+                    }
+                    self.storage.batch_put(vec![
+                        (key, serde_json::to_value(&address_list)?),
+                        (self.make_stats_key(&address)?, serde_json::to_value(&stats)?),
+                    ])?;
+                }
+            }
+            Ok(())
+        })?;
+
+        info!("🧹 过期记录清理完成: 删除 {} 条记录, {} 个地址", removed_total, removed_addresses);
+        Ok(removed_total)
+    }
+
+    /// 从给定地址的记录列表中删除某个签名的全部记录并回退统计，返回删除的记录数
+    ///
+    /// 记录全部删除的地址会被整体删除。
+    pub fn remove_signature_records(&self, signature: &str, addresses: &[&str]) -> Result<usize> {
+        let mut removed_total = 0;
+        for address in addresses {
+            let _guard = self.locks.lock(address);
+            let key = self.make_key(address);
+            let Some(mut address_list) = self.storage.get::<AddressTransactionList>(&key)? else {
+                continue;
+            };
+            let mut stats = self.load_stats(&address_list)?;
+
+            let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut address_list.records)
+                .into_iter()
+                .partition(|record| record.signature == signature);
+            address_list.records = kept;
+            if removed.is_empty() {
+                continue;
+            }
+            removed_total += removed.len();
+
+            if address_list.records.is_empty() {
+                self.remove_list(address)?;
+            } else {
+                for record in &removed {
+                    stats.remove_record(record);
+                }
+                self.storage.batch_put(vec![
+                    (key, serde_json::to_value(&address_list)?),
+                    (self.make_stats_key(address)?, serde_json::to_value(&stats)?),
+                ])?;
+            }
+        }
+        Ok(removed_total)
+    }
+
+    /// 把早于 `cutoff` 的记录汇总进每日地址汇总表并删除原始记录，返回汇总的记录数
+    ///
+    /// 每个地址的汇总结果、剩余记录和统计在同一个批次里写入，中途失败不会重复汇总。
+    /// 地址按键分批遍历，不会一次载入全部地址。
+    pub fn roll_up_records_before(&self, cutoff: u64, rollups: &AddressRollupStorage) -> Result<usize> {
+        let mut rolled_up_total = 0;
+        let mut removed_addresses = 0;
+        self.for_each_address_chunk(|addresses| {
+            for address in addresses {
+                let _guard = self.locks.lock(&address);
+                let key = self.make_key(&address);
+                let Some(mut address_list) = self.storage.get::<AddressTransactionList>(&key)? else {
+                    continue;
+                };
+                let mut stats = self.load_stats(&address_list)?;
+
+                let (kept, old): (Vec<_>, Vec<_>) = std::mem::take(&mut address_list.records)
+                    .into_iter()
+                    .partition(|record| record.timestamp >= cutoff);
+                address_list.records = kept;
+                if old.is_empty() {
+                    continue;
+                }
+                rolled_up_total += old.len();
+                for removed in &old {
+                    stats.remove_record(removed);
+                }
+
+                let mut items = rollups
+                    .merge_records(&address, &old)?
+                    .into_iter()
+                    .map(|(key, rollup)| Ok((key, serde_json::to_value(rollup)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                items.push((key, serde_json::to_value(&address_list)?));
+                items.push((self.make_stats_key(&address)?, serde_json::to_value(&stats)?));
+                self.storage.batch_put(items)?;
+
+                if address_list.records.is_empty() {
+                    self.remove_list(&address)?;
+                    removed_addresses += 1;
+                }
+            }
+            Ok(())
+        })?;
+
+        info!("📦 地址记录汇总完成: 汇总 {} 条记录, 清空 {} 个地址", rolled_up_total, removed_addresses);
+        Ok(rolled_up_total)
     }
 
     /// 获取地址的交易记录
     pub fn get_address_records(&self, address: &str) -> Result<Option<AddressTransactionList>> {
-        let key = format!("{}{}", self.address_prefix, address);
-        self.storage.get(&key)
+        if !self.may_have_records(address) {
+            return Ok(None);
+        }
+        self.storage.get(&self.make_key(address))
     }
 
     /// 获取地址的最近N条记录
     pub fn get_recent_records(&self, address: &str, limit: usize) -> Result<Vec<AddressTransactionRecord>> {
-        let key = format!("{}{}", self.address_prefix, address);
-        
-        match self.storage.get::<AddressTransactionList>(&key)? {
+        match self.get_address_records(address)? {
             Some(list) => {
                 let limit = limit.min(list.records.len());
                 Ok(list.records[..limit].to_vec())
@@ -156,8 +579,24 @@ impl AddressStorage {
 
     /// 删除地址的所有记录
     pub fn delete_address_records(&self, address: &str) -> Result<StorageResult> {
-        let key = format!("{}{}", self.address_prefix, address);
-        self.storage.delete(&key)
+        let _guard = self.locks.lock(address);
+        self.remove_list(address)
+    }
+
+    /// 删除地址的记录列表和统计，调用方需持有该地址的写锁
+    fn remove_list(&self, address: &str) -> Result<StorageResult> {
+        self.storage.delete(&self.make_stats_key(address)?)?;
+        self.storage.delete(&self.make_key(address))
+    }
+
+    /// 按键分批遍历有记录的地址，每批最多 `ADDRESS_SCAN_CHUNK_SIZE` 个，不会一次载入全部地址
+    fn for_each_address_chunk(&self, mut f: impl FnMut(Vec<String>) -> Result<()>) -> Result<()> {
+        self.storage.for_each_key_chunk_by_prefix(&self.address_prefix, ADDRESS_SCAN_CHUNK_SIZE, |keys| {
+            f(keys
+                .into_iter()
+                .map(|key| key.strip_prefix(self.address_prefix.as_str()).unwrap_or(&key).to_string())
+                .collect())
+        })
     }
 
     /// 获取所有有记录的地址列表
@@ -173,49 +612,44 @@ impl AddressStorage {
     }
 
     /// 获取地址统计信息
+    ///
+    /// 直接读取写入时维护的统计；旧数据没有统计时退回到按记录重算。
     pub fn get_address_stats(&self, address: &str) -> Result<AddressStats> {
-        let records = self.get_recent_records(address, self.max_records)?;
-        
-        let mut sol_sent_count = 0;
-        let mut sol_received_count = 0;
-        let mut token_sent_count = 0;
-        let mut token_received_count = 0;
-        let mut total_sol_sent = 0u64;
-        let mut total_sol_received = 0u64;
-
-        for record in &records {
-            match (&record.sol_transfer, &record.record_type) {
-                (Some(sol), RecordType::Sender) => {
-                    sol_sent_count += 1;
-                    total_sol_sent += sol.amount;
-                }
-                (Some(sol), RecordType::Receiver) => {
-                    sol_received_count += 1;
-                    total_sol_received += sol.amount;
-                }
-                _ => {}
-            }
-
-            match (&record.token_transfer, &record.record_type) {
-                (Some(_), RecordType::Sender) => token_sent_count += 1,
-                (Some(_), RecordType::Receiver) => token_received_count += 1,
-                _ => {}
-            }
+        if !self.may_have_records(address) {
+            return Ok(AddressStats::from_records(address, &[]));
         }
+        if let Some(stats) = self.storage.get::<AddressStats>(&self.make_stats_key(address)?)? {
+            return Ok(stats);
+        }
+        let records = self.get_recent_records(address, self.max_records)?;
+        Ok(AddressStats::from_records(address, &records))
+    }
 
-        Ok(AddressStats {
-            address: address.to_string(),
-            total_records: records.len(),
-            sol_sent_count,
-            sol_received_count,
-            token_sent_count,
-            token_received_count,
-            total_sol_sent,
-            total_sol_received,
-        })
+    /// 同时获取地址统计和最近 `limit` 条记录，记录列表只读取一次
+    pub fn get_address_overview(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<(AddressStats, Vec<AddressTransactionRecord>)> {
+        let mut records = self
+            .get_address_records(address)?
+            .map(|list| list.records)
+            .unwrap_or_default();
+        let stored = if self.may_have_records(address) {
+            self.storage.get::<AddressStats>(&self.make_stats_key(address)?)?
+        } else {
+            None
+        };
+        let stats = stored.unwrap_or_else(|| AddressStats::from_records(address, &records));
+        records.truncate(limit);
+        Ok((stats, records))
     }
 
     /// 批量处理交易记录
+    ///
+    /// 先把所有转账腿按地址分组，每个受影响的地址只读取一次记录列表，
+    /// 合并全部记录后通过一个 WriteBatch 一次性写入。返回记录有变化的地址数，
+    /// 重复处理同一笔交易时为 0。
     pub fn batch_process_transaction(
         &self,
         signature: &str,
@@ -223,57 +657,79 @@ impl AddressStorage {
         slot: u64,
         sol_transfers: &[SolTransfer],
         token_transfers: &[TokenTransfer],
-    ) -> Result<()> {
-        // 处理SOL转账
-        for sol_transfer in sol_transfers {
-            // 为发送方添加记录
-            self.add_sol_transfer(
-                &sol_transfer.from,
-                signature,
+    ) -> Result<usize> {
+        let record = |kind: &str,
+                      index: usize,
+                      record_type: RecordType,
+                      sol_transfer: Option<&SolTransfer>,
+                      token_transfer: Option<&TokenTransfer>| {
+            let mut record = AddressTransactionRecord {
+                transfer_id: AddressTransactionRecord::make_transfer_id(
+                    signature,
+                    kind,
+                    index,
+                    sol_transfer.and_then(|transfer| transfer.instruction_path.as_deref()),
+                    &record_type,
+                ),
+                signature: signature.to_string(),
                 timestamp,
                 slot,
-                sol_transfer.clone(),
-                RecordType::Sender,
-            )?;
+                sol_transfer: sol_transfer.cloned(),
+                token_transfer: token_transfer.cloned(),
+                record_type,
+                direction: None,
+            };
+            record.direction = Some(record.direction());
+            record
+        };
 
-            // 为接收方添加记录
-            self.add_sol_transfer(
-                &sol_transfer.to,
-                signature,
-                timestamp,
-                slot,
-                sol_transfer.clone(),
-                RecordType::Receiver,
-            )?;
+        let mut legs: Vec<(&str, AddressTransactionRecord)> = Vec::new();
+        for (index, sol_transfer) in sol_transfers.iter().enumerate() {
+            legs.push((&sol_transfer.from, record("sol", index, RecordType::Sender, Some(sol_transfer), None)));
+            legs.push((&sol_transfer.to, record("sol", index, RecordType::Receiver, Some(sol_transfer), None)));
+        }
+        // 代币转账按所有者钱包索引，钱包查询即可看到其代币活动
+        for (index, token_transfer) in token_transfers.iter().enumerate() {
+            legs.push((token_transfer.sender_wallet(), record("token", index, RecordType::Sender, None, Some(token_transfer))));
+            legs.push((token_transfer.receiver_wallet(), record("token", index, RecordType::Receiver, None, Some(token_transfer))));
         }
 
-        // 处理代币转账
-        for token_transfer in token_transfers {
-            // 为发送方添加记录
-            self.add_token_transfer(
-                &token_transfer.from,
-                signature,
-                timestamp,
-                slot,
-                token_transfer.clone(),
-                RecordType::Sender,
-            )?;
+        // 按地址分组，保持转账腿的原始顺序
+        let mut grouped: Vec<(&str, Vec<AddressTransactionRecord>)> = Vec::new();
+        for (address, record) in legs {
+            match grouped.iter_mut().find(|(existing, _)| *existing == address) {
+                Some((_, records)) => records.push(record),
+                None => grouped.push((address, vec![record])),
+            }
+        }
 
-            // 为接收方添加记录
-            self.add_token_transfer(
-                &token_transfer.to,
-                signature,
-                timestamp,
-                slot,
-                token_transfer.clone(),
-                RecordType::Receiver,
-            )?;
+        // 每个地址读取一次，合并所有记录，记录列表和统计一起写入
+        let _guards = self.locks.lock_all(grouped.iter().map(|(address, _)| *address));
+        let mut updates = Vec::with_capacity(grouped.len() * 2);
+        for (address, records) in grouped {
+            let key = self.make_key(address);
+            let mut address_list = self.load_list(&key, address)?;
+            let mut stats = self.load_stats(&address_list)?;
+            let mut changed = false;
+            for record in records {
+                changed |= self.apply_record(&mut address_list, &mut stats, record);
+            }
+            if changed {
+                self.remember(address);
+                updates.push((key, serde_json::to_value(&address_list)?));
+                updates.push((self.make_stats_key(address)?, serde_json::to_value(&stats)?));
+            }
+        }
+
+        let updated_addresses = updates.len() / 2;
+        if !updates.is_empty() {
+            self.storage.batch_put(updates)?;
         }
 
-        info!("批量处理完成: 签名 {} - {} SOL转账, {} 代币转账", 
-              signature, sol_transfers.len(), token_transfers.len());
+        debug!("批量处理完成: 签名 {} - {} SOL转账, {} 代币转账, 更新 {} 个地址",
+              signature, sol_transfers.len(), token_transfers.len(), updated_addresses);
 
-        Ok(())
+        Ok(updated_addresses)
     }
 }
 
@@ -296,4 +752,337 @@ pub struct AddressStats {
     pub total_sol_sent: u64,
     /// 总SOL接收数量（lamports）
     pub total_sol_received: u64,
-} 
\ No newline at end of file
+}
+
+impl AddressStats {
+    /// 根据记录列表计算统计
+    pub fn from_records(address: &str, records: &[AddressTransactionRecord]) -> Self {
+        let mut stats = AddressStats {
+            address: address.to_string(),
+            total_records: 0,
+            sol_sent_count: 0,
+            sol_received_count: 0,
+            token_sent_count: 0,
+            token_received_count: 0,
+            total_sol_sent: 0,
+            total_sol_received: 0,
+        };
+        for record in records {
+            stats.add_record(record);
+        }
+        stats
+    }
+
+    /// 计入一条新记录
+    pub fn add_record(&mut self, record: &AddressTransactionRecord) {
+        self.update(record, true);
+    }
+
+    /// 移出一条被淘汰或过期的记录
+    pub fn remove_record(&mut self, record: &AddressTransactionRecord) {
+        self.update(record, false);
+    }
+
+    fn update(&mut self, record: &AddressTransactionRecord, add: bool) {
+        let count = |value: &mut usize| *value = if add { *value + 1 } else { value.saturating_sub(1) };
+        let amount = |value: &mut u64, delta: u64| {
+            *value = if add { *value + delta } else { value.saturating_sub(delta) }
+        };
+
+        count(&mut self.total_records);
+        match (&record.sol_transfer, &record.record_type) {
+            (Some(sol), RecordType::Sender) => {
+                count(&mut self.sol_sent_count);
+                amount(&mut self.total_sol_sent, sol.amount);
+            }
+            (Some(sol), RecordType::Receiver) => {
+                count(&mut self.sol_received_count);
+                amount(&mut self.total_sol_received, sol.amount);
+            }
+            _ => {}
+        }
+        match (&record.token_transfer, &record.record_type) {
+            (Some(_), RecordType::Sender) => count(&mut self.token_sent_count),
+            (Some(_), RecordType::Receiver) => count(&mut self.token_received_count),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::sync::Arc;
+    use crate::test_util::TempDir;
+
+    fn temp_storage(dir: &TempDir) -> StorageManager {
+        StorageManager::new(dir.path(), 6).expect("打开临时数据库失败")
+    }
+
+    fn sol_transfer(from: &str, to: &str, amount: u64) -> SolTransfer {
+        SolTransfer {
+            signature: "sig".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            from_index: 0,
+            to_index: 1,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        }
+    }
+
+    #[test]
+    fn test_last_updated_uses_injected_clock() {
+        let clock = MockClock::new(1_700_000_000);
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 10)
+            .with_clock(Arc::new(clock.clone()));
+
+        storage
+            .add_sol_transfer("alice", "sig1", 1, 1, 0, sol_transfer("alice", "bob", 10), RecordType::Sender)
+            .unwrap();
+        let list = storage.get_address_records("alice").unwrap().unwrap();
+        assert_eq!(list.last_updated, 1_700_000_000);
+
+        clock.advance(3600);
+        storage
+            .add_sol_transfer("alice", "sig2", 2, 2, 0, sol_transfer("alice", "bob", 20), RecordType::Sender)
+            .unwrap();
+        let list = storage.get_address_records("alice").unwrap().unwrap();
+        assert_eq!(list.last_updated, 1_700_003_600);
+        assert_eq!(list.records[0].signature, "sig2");
+    }
+
+    #[test]
+    fn test_address_overview_limits_records_but_not_stats() {
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 10);
+        for (i, amount) in [10, 20, 30].into_iter().enumerate() {
+            let signature = format!("sig{}", i);
+            storage
+                .add_sol_transfer("alice", &signature, i as u64, i as u64, 0, sol_transfer("alice", "bob", amount), RecordType::Sender)
+                .unwrap();
+        }
+
+        let (stats, records) = storage.get_address_overview("alice", 2).unwrap();
+        assert_eq!(stats.total_records, 3);
+        assert_eq!(stats.total_sol_sent, 60);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].signature, "sig2");
+
+        let (empty_stats, empty_records) = storage.get_address_overview("carol", 2).unwrap();
+        assert_eq!(empty_stats.total_records, 0);
+        assert!(empty_records.is_empty());
+    }
+
+    #[test]
+    fn test_direction_recorded_at_insert_including_self_transfers() {
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 10);
+        let transfers = [sol_transfer("alice", "bob", 10), sol_transfer("alice", "alice", 5)];
+        storage.batch_process_transaction("sig1", 1, 1, &transfers, &[]).unwrap();
+
+        let directions = |address: &str| -> Vec<RecordDirection> {
+            let records = storage.get_recent_records(address, 10).unwrap();
+            records.iter().map(|record| record.direction.unwrap()).collect()
+        };
+        assert_eq!(directions("bob"), vec![RecordDirection::In]);
+        // 自转账的两条腿都标记为 self
+        assert_eq!(
+            directions("alice"),
+            vec![RecordDirection::SelfTransfer, RecordDirection::SelfTransfer, RecordDirection::Out]
+        );
+
+        // 旧记录没有保存方向时按记录类型推断
+        let mut legacy = storage.get_recent_records("bob", 1).unwrap().remove(0);
+        legacy.direction = None;
+        assert_eq!(legacy.direction(), RecordDirection::In);
+        assert_eq!(RecordDirection::parse("self"), Some(RecordDirection::SelfTransfer));
+    }
+
+    #[test]
+    fn test_filter_skips_unknown_addresses_and_tracks_new_ones() {
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 10);
+        storage
+            .add_sol_transfer("alice", "sig1", 1, 1, 0, sol_transfer("alice", "bob", 10), RecordType::Sender)
+            .unwrap();
+        assert!(storage.may_have_records("carol"));
+
+        // 克隆共享过滤器，建立后写入的地址同样可见
+        assert_eq!(storage.clone().build_filter(1_000).unwrap(), 1);
+        assert!(storage.may_have_records("alice"));
+        storage
+            .add_sol_transfer("carol", "sig2", 2, 2, 0, sol_transfer("carol", "bob", 20), RecordType::Sender)
+            .unwrap();
+        assert_eq!(storage.get_recent_records("carol", 10).unwrap().len(), 1);
+        assert_eq!(storage.get_address_stats("alice").unwrap().total_sol_sent, 10);
+        assert!(storage.get_address_records("dave").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_expired_records_removed_on_insert_and_prune() {
+        let day = 24 * 60 * 60;
+        let clock = MockClock::new(100 * day);
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 10)
+            .with_max_record_age_days(Some(7))
+            .with_clock(Arc::new(clock.clone()));
+
+        let old = (100 - 10) * day as u64;
+        let recent = (100 - 1) * day as u64;
+        storage
+            .add_sol_transfer("alice", "old", old, 1, 0, sol_transfer("alice", "bob", 10), RecordType::Sender)
+            .unwrap();
+        // 插入时已过期的记录不会保留
+        assert!(storage.get_address_records("alice").unwrap().unwrap().records.is_empty());
+
+        storage
+            .add_sol_transfer("alice", "recent", recent, 2, 0, sol_transfer("alice", "bob", 20), RecordType::Sender)
+            .unwrap();
+        storage
+            .add_sol_transfer("carol", "recent", recent, 2, 0, sol_transfer("carol", "bob", 20), RecordType::Sender)
+            .unwrap();
+
+        // 时间推进后，定期清理删除过期记录，并移除空地址
+        clock.advance(7 * day);
+        storage
+            .add_sol_transfer("alice", "new", 106 * day as u64, 3, 0, sol_transfer("alice", "bob", 30), RecordType::Sender)
+            .unwrap();
+        assert_eq!(storage.prune_expired_records().unwrap(), 1);
+
+        let alice = storage.get_address_records("alice").unwrap().unwrap();
+        assert_eq!(alice.records.len(), 1);
+        assert_eq!(alice.records[0].signature, "new");
+        assert!(storage.get_address_records("carol").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_batch_groups_legs_by_address() {
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 3);
+        let transfers = [
+            sol_transfer("alice", "bob", 10),
+            sol_transfer("bob", "alice", 20),
+            sol_transfer("alice", "carol", 30),
+        ];
+
+        storage.batch_process_transaction("sig1", 1, 1, &transfers, &[]).unwrap();
+
+        // alice 涉及 3 条转账腿，与逐条写入的顺序一致（最新的在前）
+        let alice = storage.get_address_records("alice").unwrap().unwrap();
+        let ids: Vec<&str> = alice.records.iter().map(|record| record.transfer_id.as_str()).collect();
+        assert_eq!(ids, vec!["sig1:sol2:out", "sig1:sol1:in", "sig1:sol0:out"]);
+        assert_eq!(storage.get_address_records("bob").unwrap().unwrap().records.len(), 2);
+
+        // 单笔交易中的记录同样受最大记录数限制
+        let many: Vec<SolTransfer> = (0..5).map(|i| sol_transfer("dave", "erin", i)).collect();
+        storage.batch_process_transaction("sig2", 2, 2, &many, &[]).unwrap();
+        let dave = storage.get_address_records("dave").unwrap().unwrap();
+        assert_eq!(dave.records.len(), 3);
+        assert_eq!(dave.records[0].transfer_id, "sig2:sol4:out");
+
+        // 有指令路径的转账按路径生成ID，解析顺序变化时仍能去重
+        let with_path = |path: &str, amount| SolTransfer { instruction_path: Some(path.to_string()), ..sol_transfer("frank", "grace", amount) };
+        let first = [with_path("0", 1), with_path("1.0", 2)];
+        let reordered = [with_path("1.0", 2), with_path("0", 1)];
+        assert_eq!(storage.batch_process_transaction("sig3", 3, 3, &first, &[]).unwrap(), 2);
+        assert_eq!(storage.batch_process_transaction("sig3", 3, 3, &reordered, &[]).unwrap(), 0);
+        let frank = storage.get_address_records("frank").unwrap().unwrap();
+        assert_eq!(frank.records[0].transfer_id, "sig3:sol@1.0:out");
+    }
+
+    #[test]
+    fn test_token_legs_indexed_by_owner_wallet() {
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 10);
+        let transfer = |from_owner: Option<&str>| TokenTransfer {
+            signature: "sig".to_string(),
+            from: "alice-ata".to_string(),
+            to: "bob-ata".to_string(),
+            amount: 5,
+            mint: "mint".to_string(),
+            decimals: 6,
+            timestamp: 0,
+            program_id: String::new(),
+            transfer_type: "Token Transfer".to_string(),
+            from_owner: from_owner.map(str::to_string),
+            to_owner: Some("bob".to_string()),
+        };
+
+        storage.batch_process_transaction("sig1", 1, 1, &[], &[transfer(Some("alice"))]).unwrap();
+        assert_eq!(storage.get_address_records("alice").unwrap().unwrap().records.len(), 1);
+        assert_eq!(storage.get_address_records("bob").unwrap().unwrap().records.len(), 1);
+        assert!(storage.get_address_records("bob-ata").unwrap().is_none());
+
+        // 没有所有者信息时退回代币账户
+        storage.batch_process_transaction("sig2", 2, 2, &[], &[transfer(None)]).unwrap();
+        assert_eq!(storage.get_address_records("alice-ata").unwrap().unwrap().records.len(), 1);
+    }
+
+    #[test]
+    fn test_stats_follow_inserts_and_evictions() {
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 2);
+
+        storage.batch_process_transaction("sig1", 1, 1, &[sol_transfer("alice", "bob", 10)], &[]).unwrap();
+        storage.batch_process_transaction("sig2", 2, 2, &[sol_transfer("bob", "alice", 20)], &[]).unwrap();
+        let stats = storage.get_address_stats("alice").unwrap();
+        assert_eq!((stats.total_records, stats.total_sol_sent, stats.total_sol_received), (2, 10, 20));
+
+        // 第三条记录挤出最老的发送记录，统计同步扣除
+        storage.batch_process_transaction("sig3", 3, 3, &[sol_transfer("carol", "alice", 5)], &[]).unwrap();
+        let stats = storage.get_address_stats("alice").unwrap();
+        assert_eq!((stats.total_records, stats.sol_sent_count, stats.total_sol_received), (2, 0, 25));
+
+        // 与按记录重算的结果一致
+        let list = storage.get_address_records("alice").unwrap().unwrap();
+        let replayed = AddressStats::from_records("alice", &list.records);
+        assert_eq!(replayed.total_sol_received, stats.total_sol_received);
+        assert_eq!(replayed.sol_received_count, stats.sol_received_count);
+    }
+
+    #[test]
+    fn test_reprocessing_transaction_is_idempotent() {
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 10);
+        // 同一交易中两笔相同双方、相同金额的转账应各自保留
+        let transfers = [sol_transfer("alice", "bob", 10), sol_transfer("alice", "bob", 10)];
+
+        assert_eq!(storage.batch_process_transaction("sig1", 1, 1, &transfers, &[]).unwrap(), 2);
+        assert_eq!(storage.batch_process_transaction("sig1", 1, 1, &transfers, &[]).unwrap(), 0);
+
+        let alice = storage.get_address_records("alice").unwrap().unwrap();
+        assert_eq!(alice.records.len(), 2);
+        assert_eq!(alice.records[0].transfer_id, "sig1:sol1:out");
+        assert_eq!(alice.records[1].transfer_id, "sig1:sol0:out");
+        assert_eq!(storage.get_address_records("bob").unwrap().unwrap().records.len(), 2);
+    }
+
+    #[test]
+    fn test_concurrent_writes_to_same_address_are_not_lost() {
+        let dir = TempDir::new("address-storage-test");
+        let storage = AddressStorage::new(temp_storage(&dir), "ADDR01".to_string(), 100);
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let storage = storage.clone();
+                std::thread::spawn(move || {
+                    let signature = format!("sig{}", i);
+                    storage.batch_process_transaction(&signature, i, i, &[sol_transfer("alice", "bob", 10)], &[]).unwrap();
+                    storage.remove_signature_records("missing", &["alice"]).unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(storage.get_address_records("alice").unwrap().unwrap().records.len(), 8);
+        assert_eq!(storage.get_address_stats("alice").unwrap().sol_sent_count, 8);
+    }
+}
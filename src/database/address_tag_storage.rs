@@ -0,0 +1,154 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::storage::StorageManager;
+
+/// 地址标记键前缀（按地址保存标记和备注）
+pub const ADDRESS_TAG_KEY_PREFIX: &str = "ATAG01";
+
+/// 标记反向索引键前缀，键为 `<标记>:<地址>`，用于按标记查找地址
+pub const TAG_INDEX_KEY_PREFIX: &str = "TAGX01";
+
+/// 单个标记的最大长度
+pub const MAX_TAG_LENGTH: usize = 64;
+
+/// 用户为地址添加的标记和备注（调查用，与已知实体标签相互独立）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AddressTags {
+    /// 地址
+    pub address: String,
+    /// 标记（小写，按字母顺序）
+    pub tags: BTreeSet<String>,
+    /// 备注
+    #[serde(default)]
+    pub note: Option<String>,
+    /// 最后更新时间
+    pub updated_at: i64,
+}
+
+/// 地址标记存储管理器
+#[derive(Debug, Clone)]
+pub struct AddressTagStorage {
+    storage: StorageManager,
+    clock: SharedClock,
+}
+
+impl AddressTagStorage {
+    /// 创建新的地址标记存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self {
+            storage,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 规范化标记：去掉首尾空白并转为小写；空标记、过长或包含 `:` 的标记无效
+    pub fn normalize_tag(tag: &str) -> Option<String> {
+        let tag = tag.trim().to_lowercase();
+        (!tag.is_empty() && tag.chars().count() <= MAX_TAG_LENGTH && !tag.contains(':')).then_some(tag)
+    }
+
+    /// 为地址追加标记，`note` 不为空时覆盖备注；标记应已规范化
+    pub fn add_tags(&self, address: &str, tags: &[String], note: Option<String>) -> Result<AddressTags> {
+        let mut entry = self.get_tags(address)?.unwrap_or_else(|| AddressTags {
+            address: address.to_string(),
+            ..Default::default()
+        });
+
+        let mut items = Vec::new();
+        for tag in tags {
+            if entry.tags.insert(tag.clone()) {
+                items.push((self.make_index_key(tag, address)?, 1u8));
+            }
+        }
+        if note.is_some() {
+            entry.note = note;
+        }
+        entry.updated_at = self.clock.timestamp();
+
+        if !items.is_empty() {
+            self.storage.batch_put(items)?;
+        }
+        self.storage.put(&self.make_key(address)?, &entry)?;
+        Ok(entry)
+    }
+
+    /// 获取地址的标记和备注
+    pub fn get_tags(&self, address: &str) -> Result<Option<AddressTags>> {
+        self.storage.get(&self.make_key(address)?)
+    }
+
+    /// 移除地址的一个标记，返回标记是否存在
+    pub fn remove_tag(&self, address: &str, tag: &str) -> Result<bool> {
+        let Some(mut entry) = self.get_tags(address)? else {
+            return Ok(false);
+        };
+        if !entry.tags.remove(tag) {
+            return Ok(false);
+        }
+
+        self.storage.delete(&self.make_index_key(tag, address)?)?;
+        entry.updated_at = self.clock.timestamp();
+        if entry.tags.is_empty() && entry.note.is_none() {
+            self.storage.delete(&self.make_key(address)?)?;
+        } else {
+            self.storage.put(&self.make_key(address)?, &entry)?;
+        }
+        Ok(true)
+    }
+
+    /// 带有某个标记的所有地址
+    pub fn addresses_with_tag(&self, tag: &str) -> Result<HashSet<String>> {
+        let prefix = self.make_index_key(tag, "")?;
+        Ok(self
+            .storage
+            .get_keys_by_prefix(&prefix)?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&prefix).map(str::to_string))
+            .collect())
+    }
+
+    fn make_key(&self, address: &str) -> Result<String> {
+        self.storage.make_key(ADDRESS_TAG_KEY_PREFIX, address)
+    }
+
+    fn make_index_key(&self, tag: &str, address: &str) -> Result<String> {
+        self.storage.make_key(TAG_INDEX_KEY_PREFIX, &format!("{}:{}", tag, address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    const ALICE: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const BOB: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+
+    #[test]
+    fn test_tags_and_reverse_index() {
+        let dir = TempDir::new("address-tag-test");
+        let storage = AddressTagStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        assert_eq!(AddressTagStorage::normalize_tag("  Case-42 "), Some("case-42".to_string()));
+        assert!(AddressTagStorage::normalize_tag(" ").is_none());
+        assert!(AddressTagStorage::normalize_tag("a:b").is_none());
+
+        let tags = vec!["case-42".to_string(), "mixer".to_string()];
+        storage.add_tags(ALICE, &tags, Some("first hop".to_string())).unwrap();
+        let entry = storage.add_tags(ALICE, &["case-42".to_string()], None).unwrap();
+        assert_eq!(entry.tags.len(), 2);
+        assert_eq!(entry.note.as_deref(), Some("first hop"));
+        storage.add_tags(BOB, &["case-42".to_string()], None).unwrap();
+
+        assert_eq!(storage.addresses_with_tag("case-42").unwrap(), HashSet::from([ALICE.to_string(), BOB.to_string()]));
+        assert!(storage.addresses_with_tag("case-4").unwrap().is_empty());
+
+        assert!(storage.remove_tag(BOB, "case-42").unwrap());
+        assert!(!storage.remove_tag(BOB, "case-42").unwrap());
+        assert!(storage.get_tags(BOB).unwrap().is_none());
+        assert_eq!(storage.addresses_with_tag("case-42").unwrap(), HashSet::from([ALICE.to_string()]));
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 告警键前缀，键为 `<时间戳><告警ID>`，按时间排序
+pub const ALERT_KEY_PREFIX: &str = "ALRT01";
+
+/// 剥离链深度键前缀（规则引擎的状态）
+pub const PEEL_DEPTH_KEY_PREFIX: &str = "PEEL01";
+
+/// 可疑模式规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertRule {
+    /// 短时间内向大量地址分散转出
+    FanOut,
+    /// 收到资金后扣留一小部分、其余继续转出，逐跳传递
+    PeelChain,
+    /// 资金转出后又从对方转回
+    RoundTrip,
+    /// 金额略低于申报阈值
+    Structuring,
+}
+
+impl AlertRule {
+    /// 解析 `fan_out` / `peel_chain` / `round_trip` / `structuring`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "fan_out" => Some(Self::FanOut),
+            "peel_chain" => Some(Self::PeelChain),
+            "round_trip" => Some(Self::RoundTrip),
+            "structuring" => Some(Self::Structuring),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FanOut => "fan_out",
+            Self::PeelChain => "peel_chain",
+            Self::RoundTrip => "round_trip",
+            Self::Structuring => "structuring",
+        }
+    }
+}
+
+/// 规则命中产生的告警
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    /// 告警ID（签名 + 规则 + 序号）
+    pub id: String,
+    pub rule: AlertRule,
+    /// 触发告警的交易签名
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: i64,
+    /// 相关地址，第一个为主要地址
+    pub addresses: Vec<String>,
+    /// 触发告警的转账金额（lamports）
+    pub amount: u64,
+    /// 说明
+    pub description: String,
+}
+
+/// 剥离链上某个地址的深度
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PeelDepth {
+    pub depth: u32,
+    pub timestamp: i64,
+}
+
+/// 告警存储管理器
+#[derive(Debug, Clone)]
+pub struct AlertStorage {
+    storage: StorageManager,
+}
+
+impl AlertStorage {
+    /// 创建新的告警存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 保存一批告警
+    pub fn record_alerts(&self, alerts: &[Alert]) -> Result<()> {
+        if alerts.is_empty() {
+            return Ok(());
+        }
+        let items = alerts
+            .iter()
+            .map(|alert| Ok((self.make_key(alert.timestamp, &alert.id)?, alert)))
+            .collect::<Result<Vec<_>>>()?;
+        self.storage.batch_put(items)?;
+        debug!("保存 {} 条告警", alerts.len());
+        Ok(())
+    }
+
+    /// 按条件查询告警（最新的在前）
+    pub fn list_alerts(&self, rule: Option<AlertRule>, address: Option<&str>, limit: usize) -> Result<Vec<Alert>> {
+        Ok(self
+            .storage
+            .get_by_prefix::<Alert>(ALERT_KEY_PREFIX)?
+            .into_iter()
+            .rev()
+            .map(|item| item.value)
+            .filter(|alert| rule.is_none_or(|rule| alert.rule == rule))
+            .filter(|alert| address.is_none_or(|address| alert.addresses.iter().any(|a| a == address)))
+            .take(limit)
+            .collect())
+    }
+
+    /// 获取地址在剥离链上的深度
+    pub fn get_peel_depth(&self, address: &str) -> Result<Option<PeelDepth>> {
+        self.storage.get(&self.storage.make_key(PEEL_DEPTH_KEY_PREFIX, address)?)
+    }
+
+    /// 记录地址在剥离链上的深度
+    pub fn set_peel_depth(&self, address: &str, depth: PeelDepth) -> Result<()> {
+        self.storage.put(&self.storage.make_key(PEEL_DEPTH_KEY_PREFIX, address)?, &depth)?;
+        Ok(())
+    }
+
+    fn make_key(&self, timestamp: i64, id: &str) -> Result<String> {
+        self.storage.make_key(ALERT_KEY_PREFIX, &format!("{:020}{}", timestamp.max(0), id))
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::Result;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::database::signature_storage::{SolTransfer, TokenTransfer};
+use crate::database::storage::StorageManager;
+
+/// 转账金额分布键前缀
+pub const AMOUNT_HISTOGRAM_KEY_PREFIX: &str = "AMTH01";
+
+/// SOL 转账的资产名，代币转账使用 mint 地址
+pub const SOL_ASSET: &str = "SOL";
+
+/// SOL 精度
+const SOL_DECIMALS: u32 = 9;
+
+/// 金额区间（按整数单位，左闭右开）：尘埃（< 0.001）、< 0.1、0.1–1、1–10、10 及以上
+pub const AMOUNT_BUCKETS: [AmountBucket; 5] = [
+    AmountBucket { label: "dust", min: 0.0, max: Some(0.001) },
+    AmountBucket { label: "<0.1", min: 0.001, max: Some(0.1) },
+    AmountBucket { label: "0.1-1", min: 0.1, max: Some(1.0) },
+    AmountBucket { label: "1-10", min: 1.0, max: Some(10.0) },
+    AmountBucket { label: "10+", min: 10.0, max: None },
+];
+
+/// 金额区间
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmountBucket {
+    /// 区间名
+    pub label: &'static str,
+    /// 下限（含）
+    pub min: f64,
+    /// 上限（不含），最后一个区间没有上限
+    pub max: Option<f64>,
+}
+
+/// 某个资产在一天（UTC）内各金额区间的转账笔数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyAmountHistogram {
+    /// 资产（`SOL` 或代币 mint）
+    pub asset: String,
+    /// 日期（YYYY-MM-DD）
+    pub date: String,
+    /// 各区间的转账笔数，顺序与 `AMOUNT_BUCKETS` 一致
+    pub counts: [u64; 5],
+}
+
+/// 转账金额分布存储
+///
+/// 摄取时按资产和日期增量累加，键格式为 `AMTH01{asset}:{YYYY-MM-DD}`，
+/// 查询一段时间的分布只需按资产前缀扫描若干天的计数。
+#[derive(Debug, Clone)]
+pub struct AmountHistogramStorage {
+    storage: StorageManager,
+}
+
+impl AmountHistogramStorage {
+    /// 创建新的金额分布存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 把一笔交易的转账计入当天的金额分布
+    pub fn record_transfers(&self, timestamp: u64, sol_transfers: &[SolTransfer], token_transfers: &[TokenTransfer]) -> Result<()> {
+        let date = Self::date_of(timestamp);
+        let mut deltas: BTreeMap<&str, [u64; 5]> = BTreeMap::new();
+        for transfer in sol_transfers {
+            deltas.entry(SOL_ASSET).or_default()[Self::bucket_of(transfer.amount, SOL_DECIMALS)] += 1;
+        }
+        for transfer in token_transfers {
+            deltas.entry(transfer.mint.as_str()).or_default()[Self::bucket_of(transfer.amount, transfer.decimals as u32)] += 1;
+        }
+        if deltas.is_empty() {
+            return Ok(());
+        }
+
+        let items = deltas
+            .into_iter()
+            .map(|(asset, counts)| {
+                let key = self.storage.make_key(AMOUNT_HISTOGRAM_KEY_PREFIX, &format!("{}:{}", asset, date))?;
+                let mut histogram = self.storage.get::<DailyAmountHistogram>(&key)?.unwrap_or_else(|| DailyAmountHistogram {
+                    asset: asset.to_string(),
+                    date: date.clone(),
+                    ..Default::default()
+                });
+                for (total, count) in histogram.counts.iter_mut().zip(counts) {
+                    *total += count;
+                }
+                Ok((key, histogram))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.storage.batch_put(items)?;
+        Ok(())
+    }
+
+    /// 获取资产从 `since_date`（含）起每天的分布，按日期升序
+    pub fn get_daily(&self, asset: &str, since_date: &str) -> Result<Vec<DailyAmountHistogram>> {
+        let prefix = format!("{}{}:", AMOUNT_HISTOGRAM_KEY_PREFIX, asset);
+        Ok(self
+            .storage
+            .get_by_prefix::<DailyAmountHistogram>(&prefix)?
+            .into_iter()
+            .map(|entry| entry.value)
+            .filter(|histogram| histogram.date.as_str() >= since_date)
+            .collect())
+    }
+
+    /// 金额所在区间的序号，按整数单位比较，避免浮点误差
+    fn bucket_of(amount: u64, decimals: u32) -> usize {
+        let unit = 10u128.saturating_pow(decimals);
+        let amount = amount as u128;
+        if amount * 1000 < unit {
+            0
+        } else if amount * 10 < unit {
+            1
+        } else if amount < unit {
+            2
+        } else if amount < unit * 10 {
+            3
+        } else {
+            4
+        }
+    }
+
+    /// 时间戳所在的日期（UTC）
+    pub fn date_of(timestamp: u64) -> String {
+        DateTime::from_timestamp(timestamp as i64, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn sol(amount: u64) -> SolTransfer {
+        SolTransfer {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        }
+    }
+
+    #[test]
+    fn test_record_transfers_buckets_by_amount() {
+        let dir = TempDir::new("amount-histogram-test");
+        let histograms = AmountHistogramStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        let base = 1_704_067_200; // 2024-01-01
+
+        // 0.0005、0.05、0.1、9.99、10 SOL
+        let transfers = [sol(500_000), sol(50_000_000), sol(100_000_000), sol(9_990_000_000), sol(10_000_000_000)];
+        histograms.record_transfers(base, &transfers, &[]).unwrap();
+        histograms.record_transfers(base + 86_400, &[sol(2_000_000_000)], &[]).unwrap();
+
+        let days = histograms.get_daily(SOL_ASSET, "2024-01-01").unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].counts, [1, 1, 1, 1, 1]);
+        assert_eq!(days[1].counts, [0, 0, 0, 1, 0]);
+        assert_eq!(histograms.get_daily(SOL_ASSET, "2024-01-02").unwrap().len(), 1);
+
+        // 代币按自身精度换算：6 位小数的 5.0 落在 1–10
+        assert_eq!(AmountHistogramStorage::bucket_of(5_000_000, 6), 3);
+        assert_eq!(AmountHistogramStorage::bucket_of(1, 0), 3);
+    }
+}
@@ -0,0 +1,150 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::storage::StorageManager;
+use crate::transfer_types::BalanceSnapshot;
+
+/// 余额历史键前缀
+pub const BALANCE_HISTORY_KEY_PREFIX: &str = "BALH01";
+
+/// 余额历史点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalancePoint {
+    /// 交易签名
+    pub signature: String,
+    /// 交易槽位
+    pub slot: u64,
+    /// 交易时间戳
+    pub timestamp: u64,
+    /// 执行前余额（lamports）
+    pub pre_balance: u64,
+    /// 执行后余额（lamports）
+    pub post_balance: u64,
+}
+
+/// 地址余额历史
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceHistory {
+    /// 地址
+    pub address: String,
+    /// 余额历史点（索引0是最新的）
+    pub points: Vec<BalancePoint>,
+    /// 最后更新时间
+    pub last_updated: u64,
+}
+
+/// 余额历史存储管理器
+#[derive(Debug, Clone)]
+pub struct BalanceStorage {
+    storage: StorageManager,
+    max_points: usize,
+    clock: SharedClock,
+}
+
+impl BalanceStorage {
+    /// 创建新的余额历史存储实例
+    pub fn new(storage: StorageManager, max_points: usize) -> Self {
+        Self {
+            storage,
+            max_points,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 替换时钟（用于测试）
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 记录一笔交易中各地址的执行后余额
+    pub fn record_balances(
+        &self,
+        signature: &str,
+        slot: u64,
+        timestamp: u64,
+        snapshots: &[BalanceSnapshot],
+    ) -> Result<()> {
+        for snapshot in snapshots {
+            self.add_point(
+                &snapshot.address,
+                BalancePoint {
+                    signature: signature.to_string(),
+                    slot,
+                    timestamp,
+                    pre_balance: snapshot.pre_balance,
+                    post_balance: snapshot.post_balance,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 添加余额历史点，同一签名只记录一次
+    fn add_point(&self, address: &str, point: BalancePoint) -> Result<()> {
+        let key = self.make_key(address)?;
+
+        let mut history = match self.storage.get::<BalanceHistory>(&key)? {
+            Some(history) => history,
+            None => BalanceHistory {
+                address: address.to_string(),
+                points: Vec::new(),
+                last_updated: 0,
+            },
+        };
+
+        if history.points.iter().any(|existing| existing.signature == point.signature) {
+            return Ok(());
+        }
+
+        history.points.insert(0, point);
+        history.points.truncate(self.max_points);
+        history.last_updated = self.clock.timestamp() as u64;
+
+        self.storage.put(&key, &history)?;
+        debug!("地址 {} 余额历史更新，当前 {} 个点", address, history.points.len());
+        Ok(())
+    }
+
+    /// 获取地址的余额历史
+    pub fn get_balance_history(&self, address: &str) -> Result<Option<BalanceHistory>> {
+        self.storage.get(&self.make_key(address)?)
+    }
+
+    fn make_key(&self, address: &str) -> Result<String> {
+        self.storage.make_key(BALANCE_HISTORY_KEY_PREFIX, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn snapshot(address: &str, pre_balance: u64, post_balance: u64) -> BalanceSnapshot {
+        BalanceSnapshot {
+            address: address.to_string(),
+            pre_balance,
+            post_balance,
+        }
+    }
+
+    #[test]
+    fn test_balance_history_newest_first_and_bounded() {
+        let dir = TempDir::new("balance-storage-test");
+        let storage = BalanceStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 2);
+
+        storage.record_balances("sig1", 1, 100, &[snapshot("alice", 10, 7)]).unwrap();
+        storage.record_balances("sig2", 2, 200, &[snapshot("alice", 7, 5)]).unwrap();
+        // 重复处理同一交易不会新增历史点
+        storage.record_balances("sig2", 2, 200, &[snapshot("alice", 7, 5)]).unwrap();
+        storage.record_balances("sig3", 3, 300, &[snapshot("alice", 5, 9)]).unwrap();
+
+        let history = storage.get_balance_history("alice").unwrap().unwrap();
+        let balances: Vec<u64> = history.points.iter().map(|point| point.post_balance).collect();
+        assert_eq!(balances, vec![9, 5]);
+        assert_eq!(history.points[0].signature, "sig3");
+    }
+}
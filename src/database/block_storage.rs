@@ -0,0 +1,105 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 区块元数据键前缀
+pub const BLOCK_KEY_PREFIX: &str = "BLCK01";
+
+/// 区块元数据（整块订阅模式下保存）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlockMetadata {
+    /// 槽位
+    pub slot: u64,
+    /// 区块哈希
+    pub blockhash: String,
+    /// 父槽位
+    pub parent_slot: u64,
+    /// 父区块哈希
+    pub parent_blockhash: String,
+    /// 区块时间（Unix 秒），节点未提供时为空
+    pub block_time: Option<i64>,
+    /// 区块高度
+    pub block_height: Option<u64>,
+    /// 区块内执行的交易数（包括未被过滤条件选中的交易）
+    pub transaction_count: u64,
+}
+
+/// 区块元数据存储管理器
+#[derive(Debug, Clone)]
+pub struct BlockStorage {
+    storage: StorageManager,
+}
+
+impl BlockStorage {
+    /// 创建新的区块存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 保存区块元数据，同一槽位重复收到时覆盖
+    pub fn store_block(&self, block: &BlockMetadata) -> Result<()> {
+        self.storage.put(&self.make_key(block.slot)?, block)?;
+        debug!("区块 {} 元数据已保存: {} 笔交易", block.slot, block.transaction_count);
+        Ok(())
+    }
+
+    /// 在一次批量写入中保存多个区块的元数据（离线导入使用）
+    pub fn store_blocks(&self, blocks: &[BlockMetadata]) -> Result<()> {
+        let items = blocks
+            .iter()
+            .map(|block| Ok((self.make_key(block.slot)?, block)))
+            .collect::<Result<Vec<_>>>()?;
+        self.storage.batch_put(items)?;
+        Ok(())
+    }
+
+    /// 获取区块元数据
+    pub fn get_block(&self, slot: u64) -> Result<Option<BlockMetadata>> {
+        self.storage.get(&self.make_key(slot)?)
+    }
+
+    /// 获取已保存的最新区块
+    pub fn get_latest_block(&self) -> Result<Option<BlockMetadata>> {
+        let items = self.storage.get_by_prefix::<BlockMetadata>(BLOCK_KEY_PREFIX)?;
+        Ok(items.into_iter().last().map(|item| item.value))
+    }
+
+    /// 槽位使用定长十进制编码，保证键按槽位顺序排列
+    fn make_key(&self, slot: u64) -> Result<String> {
+        self.storage.make_key(BLOCK_KEY_PREFIX, &format!("{:020}", slot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_store_and_get_blocks() {
+        let dir = TempDir::new("block-storage-test");
+        let storage = BlockStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        for slot in [99, 100, 1_000] {
+            storage
+                .store_block(&BlockMetadata {
+                    slot,
+                    blockhash: format!("hash{}", slot),
+                    parent_slot: slot - 1,
+                    block_time: Some(1_704_067_200),
+                    transaction_count: 3,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let block = storage.get_block(100).unwrap().unwrap();
+        assert_eq!(block.blockhash, "hash100");
+        assert_eq!(block.parent_slot, 99);
+        assert!(storage.get_block(101).unwrap().is_none());
+        // 定长编码保证 1000 排在 100 之后
+        assert_eq!(storage.get_latest_block().unwrap().unwrap().slot, 1_000);
+    }
+}
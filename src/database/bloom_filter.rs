@@ -0,0 +1,101 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// 目标误判率
+const FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// 键存在性布隆过滤器（地址、签名等）
+///
+/// 查询前先问过滤器，判定不存在的键直接跳过 RocksDB 读取。过滤器只会误报、不会漏报：
+/// 构建完成前以及无法确定时一律回答“可能存在”，由调用方照常读库。
+/// 键被删除后位不会清除，只会增加误报，不影响正确性。
+#[derive(Debug)]
+pub struct BloomFilter {
+    bits: Vec<AtomicU64>,
+    num_bits: u64,
+    num_hashes: u32,
+    /// 已有的键是否全部载入，载入完成前不做否定判断
+    ready: AtomicBool,
+}
+
+impl BloomFilter {
+    /// 按预期键数量创建，超出容量后误判率上升但结果仍然正确
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = ((-capacity * FALSE_POSITIVE_RATE.ln()) / (ln2 * ln2)).ceil().max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / capacity) * ln2).round().clamp(1.0, 16.0) as u32;
+        let words = num_bits.div_ceil(64) as usize;
+        Self {
+            bits: (0..words).map(|_| AtomicU64::new(0)).collect(),
+            num_bits: words as u64 * 64,
+            num_hashes,
+            ready: AtomicBool::new(false),
+        }
+    }
+
+    /// 记录一个键
+    pub fn insert(&self, item: &str) {
+        for bit in self.bit_positions(item) {
+            self.bits[(bit / 64) as usize].fetch_or(1 << (bit % 64), Ordering::Relaxed);
+        }
+    }
+
+    /// 键是否可能存在；返回 false 时一定不存在
+    pub fn might_contain(&self, item: &str) -> bool {
+        if !self.is_ready() {
+            return true;
+        }
+        self.bit_positions(item)
+            .all(|bit| self.bits[(bit / 64) as usize].load(Ordering::Relaxed) & (1 << (bit % 64)) != 0)
+    }
+
+    /// 已有的键载入完成，开始做否定判断
+    pub fn mark_ready(&self) {
+        self.ready.store(true, Ordering::Release);
+    }
+
+    /// 是否已可用于否定判断
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// 过滤器占用的字节数
+    pub fn size_bytes(&self) -> usize {
+        self.bits.len() * 8
+    }
+
+    /// 双重哈希得到 k 个位置
+    fn bit_positions(&self, item: &str) -> impl Iterator<Item = u64> + '_ {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        let h1 = hasher.finish();
+        // 第二个哈希取奇数，避免步长为 0 时 k 个位置重合
+        let h2 = h1.rotate_left(32).wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..self.num_hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_false_negatives_and_few_false_positives() {
+        let filter = BloomFilter::with_capacity(10_000);
+        filter.insert("alice");
+        // 载入完成前不做否定判断
+        assert!(filter.might_contain("bob"));
+
+        for i in 0..10_000 {
+            filter.insert(&format!("addr{}", i));
+        }
+        filter.mark_ready();
+        assert!(filter.might_contain("alice"));
+        assert!((0..10_000).all(|i| filter.might_contain(&format!("addr{}", i))));
+
+        let false_positives = (0..10_000).filter(|i| filter.might_contain(&format!("other{}", i))).count();
+        assert!(false_positives < 300, "误判 {} 次", false_positives);
+    }
+}
@@ -0,0 +1,256 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+use crate::transfer_types::{SolTransfer, TokenTransfer};
+
+/// 交易对手索引键前缀
+pub const COUNTERPARTY_KEY_PREFIX: &str = "CPTY01";
+
+/// 与某个交易对手的聚合往来
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Counterparty {
+    /// 交易对手地址
+    pub address: String,
+    /// 往来转账笔数（SOL + 代币）
+    pub interaction_count: u64,
+    /// 转给对方的 SOL 总额（lamports）
+    pub sol_sent_lamports: u64,
+    /// 从对方收到的 SOL 总额（lamports）
+    pub sol_received_lamports: u64,
+    /// 代币转账笔数（不同代币无法直接累加金额，只记笔数）
+    pub token_transfer_count: u64,
+    /// 首次往来时间戳
+    pub first_seen: u64,
+    /// 最近往来时间戳
+    pub last_seen: u64,
+}
+
+impl Counterparty {
+    /// 双向 SOL 总额
+    pub fn sol_volume_lamports(&self) -> u64 {
+        self.sol_sent_lamports + self.sol_received_lamports
+    }
+}
+
+/// 转账关系图中的节点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// 地址
+    pub address: String,
+    /// 与根节点的距离
+    pub depth: usize,
+}
+
+/// 转账关系图中的边（无向，聚合双向往来）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// 离根节点较近的一端
+    pub source: String,
+    /// 另一端
+    pub target: String,
+    /// 往来转账笔数
+    pub interaction_count: u64,
+    /// 双向 SOL 总额（lamports）
+    pub sol_volume_lamports: u64,
+    /// 代币转账笔数
+    pub token_transfer_count: u64,
+}
+
+/// 以某个地址为根的转账关系图
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferGraph {
+    /// 根地址
+    pub root: String,
+    /// 节点（按展开顺序，根节点在前）
+    pub nodes: Vec<GraphNode>,
+    /// 边
+    pub edges: Vec<GraphEdge>,
+}
+
+/// 交易对手存储管理器
+///
+/// 键格式为 `CPTY01{address}:{counterparty}`，每条往来在双方名下各保存一份，
+/// 按地址前缀扫描即可得到其全部交易对手。
+#[derive(Debug, Clone)]
+pub struct CounterpartyStorage {
+    storage: StorageManager,
+}
+
+impl CounterpartyStorage {
+    /// 创建新的交易对手存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 根据一笔交易中的转账更新双方的交易对手记录
+    pub fn record_transaction(
+        &self,
+        timestamp: u64,
+        sol_transfers: &[SolTransfer],
+        token_transfers: &[TokenTransfer],
+    ) -> Result<()> {
+        // 先在内存中按 (地址, 对手) 合并，每对只读写一次
+        let mut deltas: HashMap<(String, String), Counterparty> = HashMap::new();
+        for transfer in sol_transfers.iter().filter(|t| t.from != t.to) {
+            let sent = Self::delta(&mut deltas, &transfer.from, &transfer.to);
+            sent.interaction_count += 1;
+            sent.sol_sent_lamports += transfer.amount;
+
+            let received = Self::delta(&mut deltas, &transfer.to, &transfer.from);
+            received.interaction_count += 1;
+            received.sol_received_lamports += transfer.amount;
+        }
+        for transfer in token_transfers.iter().filter(|t| t.sender_wallet() != t.receiver_wallet()) {
+            for (owner, other) in [(transfer.sender_wallet(), transfer.receiver_wallet()), (transfer.receiver_wallet(), transfer.sender_wallet())] {
+                let entry = Self::delta(&mut deltas, owner, other);
+                entry.interaction_count += 1;
+                entry.token_transfer_count += 1;
+            }
+        }
+
+        for ((owner, other), delta) in deltas {
+            let key = self.make_key(&owner, &other)?;
+            let mut counterparty = self.storage.get::<Counterparty>(&key)?.unwrap_or(Counterparty {
+                address: other.clone(),
+                first_seen: timestamp,
+                ..Default::default()
+            });
+
+            counterparty.interaction_count += delta.interaction_count;
+            counterparty.sol_sent_lamports += delta.sol_sent_lamports;
+            counterparty.sol_received_lamports += delta.sol_received_lamports;
+            counterparty.token_transfer_count += delta.token_transfer_count;
+            counterparty.first_seen = counterparty.first_seen.min(timestamp);
+            counterparty.last_seen = counterparty.last_seen.max(timestamp);
+
+            self.storage.put(&key, &counterparty)?;
+            debug!("地址 {} 与 {} 的往来更新: {} 笔", owner, other, counterparty.interaction_count);
+        }
+        Ok(())
+    }
+
+    /// 获取地址的全部交易对手，按往来笔数从多到少排序
+    pub fn get_counterparties(&self, address: &str) -> Result<Vec<Counterparty>> {
+        let prefix = format!("{}{}:", COUNTERPARTY_KEY_PREFIX, address);
+        let mut counterparties: Vec<Counterparty> = self
+            .storage
+            .get_by_prefix::<Counterparty>(&prefix)?
+            .into_iter()
+            .map(|item| item.value)
+            .collect();
+        counterparties.sort_by(|a, b| {
+            b.interaction_count
+                .cmp(&a.interaction_count)
+                .then_with(|| b.sol_volume_lamports().cmp(&a.sol_volume_lamports()))
+        });
+        Ok(counterparties)
+    }
+
+    /// 从根地址出发按广度优先展开转账关系图
+    ///
+    /// 每个节点只展开往来最多的 `max_neighbors` 个对手，避免热门地址撑爆结果。
+    pub fn build_graph(&self, root: &str, depth: usize, max_neighbors: usize) -> Result<TransferGraph> {
+        let mut nodes = vec![GraphNode { address: root.to_string(), depth: 0 }];
+        let mut edges = Vec::new();
+        let mut visited: HashSet<String> = HashSet::from([root.to_string()]);
+        let mut seen_edges: HashSet<(String, String)> = HashSet::new();
+        let mut queue = VecDeque::from([(root.to_string(), 0)]);
+
+        while let Some((address, level)) = queue.pop_front() {
+            if level >= depth {
+                continue;
+            }
+            for counterparty in self.get_counterparties(&address)?.into_iter().take(max_neighbors) {
+                let pair = if address < counterparty.address {
+                    (address.clone(), counterparty.address.clone())
+                } else {
+                    (counterparty.address.clone(), address.clone())
+                };
+                if seen_edges.insert(pair) {
+                    edges.push(GraphEdge {
+                        source: address.clone(),
+                        target: counterparty.address.clone(),
+                        interaction_count: counterparty.interaction_count,
+                        sol_volume_lamports: counterparty.sol_volume_lamports(),
+                        token_transfer_count: counterparty.token_transfer_count,
+                    });
+                }
+                if visited.insert(counterparty.address.clone()) {
+                    nodes.push(GraphNode { address: counterparty.address.clone(), depth: level + 1 });
+                    queue.push_back((counterparty.address, level + 1));
+                }
+            }
+        }
+
+        Ok(TransferGraph { root: root.to_string(), nodes, edges })
+    }
+
+    fn delta<'a>(
+        deltas: &'a mut HashMap<(String, String), Counterparty>,
+        owner: &str,
+        other: &str,
+    ) -> &'a mut Counterparty {
+        deltas
+            .entry((owner.to_string(), other.to_string()))
+            .or_insert_with(|| Counterparty {
+                address: other.to_string(),
+                ..Default::default()
+            })
+    }
+
+    fn make_key(&self, address: &str, counterparty: &str) -> Result<String> {
+        self.storage.make_key(COUNTERPARTY_KEY_PREFIX, &format!("{}:{}", address, counterparty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn sol(from: &str, to: &str, amount: u64) -> SolTransfer {
+        SolTransfer {
+            signature: "sig".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            from_index: 0,
+            to_index: 0,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        }
+    }
+
+    #[test]
+    fn test_counterparties_and_graph() {
+        let dir = TempDir::new("counterparty-test");
+        let storage = CounterpartyStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        storage.record_transaction(100, &[sol("alice", "bob", 10), sol("bob", "alice", 3)], &[]).unwrap();
+        storage.record_transaction(200, &[sol("alice", "bob", 5), sol("alice", "carol", 1)], &[]).unwrap();
+        storage.record_transaction(300, &[sol("carol", "dave", 7)], &[]).unwrap();
+
+        let counterparties = storage.get_counterparties("alice").unwrap();
+        assert_eq!(counterparties.len(), 2);
+        assert_eq!(counterparties[0].address, "bob");
+        assert_eq!(counterparties[0].interaction_count, 3);
+        assert_eq!(counterparties[0].sol_sent_lamports, 15);
+        assert_eq!(counterparties[0].sol_received_lamports, 3);
+        assert_eq!((counterparties[0].first_seen, counterparties[0].last_seen), (100, 200));
+
+        let graph = storage.build_graph("alice", 1, 10).unwrap();
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 2);
+
+        // 第二层展开到 dave，且 alice-bob 这条边不会因为从 bob 一侧再次出现而重复
+        let graph = storage.build_graph("alice", 2, 10).unwrap();
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.edges.len(), 3);
+        assert!(graph.nodes.iter().any(|node| node.address == "dave" && node.depth == 2));
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::storage::StorageManager;
+
+/// 死信队列键前缀
+pub const DEAD_LETTER_KEY_PREFIX: &str = "DLQS01";
+
+/// 首次重试前的等待时间（秒），之后每次失败翻倍
+const BASE_RETRY_DELAY_SECS: i64 = 10;
+
+/// 重试间隔上限（秒）
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+/// 存储失败、等待重试的交易
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// 交易签名
+    pub signature: String,
+    pub slot: u64,
+    /// 接收时间戳
+    pub timestamp: i64,
+    /// 区块时间（整块订阅时提供）
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// 原始 gRPC 交易更新（protobuf 编码）
+    pub payload: Vec<u8>,
+    /// 最近一次失败的错误信息
+    pub last_error: String,
+    /// 已失败次数（包括首次存储）
+    pub attempts: u32,
+    /// 首次失败时间
+    pub first_failed_at: i64,
+    /// 下次重试时间
+    pub next_retry_at: i64,
+}
+
+/// 死信队列
+///
+/// 交易写入失败时保存原始 gRPC 数据，由后台任务按指数退避重试，
+/// 成功后从队列删除。以签名为键，同一交易重复失败只保留一条。
+#[derive(Debug, Clone)]
+pub struct DeadLetterStorage {
+    storage: StorageManager,
+    clock: SharedClock,
+}
+
+impl DeadLetterStorage {
+    /// 创建新的死信队列实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self {
+            storage,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 记录一次存储失败：新交易加入队列，已在队列中的交易累加失败次数并推迟下次重试
+    pub fn record_failure(
+        &self,
+        signature: &str,
+        slot: u64,
+        timestamp: i64,
+        block_time: Option<i64>,
+        payload: Vec<u8>,
+        error: &str,
+    ) -> Result<DeadLetter> {
+        let key = self.make_key(signature)?;
+        let now = self.clock.timestamp();
+        let letter = match self.storage.get::<DeadLetter>(&key)? {
+            Some(mut existing) => {
+                existing.attempts += 1;
+                existing.last_error = error.to_string();
+                existing.next_retry_at = now + Self::retry_delay(existing.attempts);
+                existing
+            }
+            None => DeadLetter {
+                signature: signature.to_string(),
+                slot,
+                timestamp,
+                block_time,
+                payload,
+                last_error: error.to_string(),
+                attempts: 1,
+                first_failed_at: now,
+                next_retry_at: now + Self::retry_delay(1),
+            },
+        };
+        self.storage.put(&key, &letter)?;
+        debug!("死信队列记录 {}（第 {} 次失败）", signature, letter.attempts);
+        Ok(letter)
+    }
+
+    /// 获取到期需要重试的交易，最多 `limit` 条
+    pub fn get_due(&self, limit: usize) -> Result<Vec<DeadLetter>> {
+        let now = self.clock.timestamp();
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|letter| letter.next_retry_at <= now)
+            .take(limit)
+            .collect())
+    }
+
+    /// 获取队列中的所有交易
+    pub fn list(&self) -> Result<Vec<DeadLetter>> {
+        Ok(self
+            .storage
+            .get_by_prefix::<DeadLetter>(DEAD_LETTER_KEY_PREFIX)?
+            .into_iter()
+            .map(|kv| kv.value)
+            .collect())
+    }
+
+    /// 队列中的交易数
+    pub fn count(&self) -> Result<usize> {
+        Ok(self.storage.get_keys_by_prefix(DEAD_LETTER_KEY_PREFIX)?.len())
+    }
+
+    /// 从队列删除交易，返回交易是否存在
+    pub fn remove(&self, signature: &str) -> Result<bool> {
+        let key = self.make_key(signature)?;
+        if !self.storage.exists(&key)? {
+            return Ok(false);
+        }
+        self.storage.delete(&key)?;
+        Ok(true)
+    }
+
+    /// 第 `attempts` 次失败后的重试间隔
+    fn retry_delay(attempts: u32) -> i64 {
+        let exponent = attempts.saturating_sub(1).min(16);
+        (BASE_RETRY_DELAY_SECS << exponent).min(MAX_RETRY_DELAY_SECS)
+    }
+
+    fn make_key(&self, signature: &str) -> Result<String> {
+        self.storage.make_key(DEAD_LETTER_KEY_PREFIX, signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_failures_back_off_until_removed() {
+        let dir = TempDir::new("dead-letter-test");
+        let storage = DeadLetterStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        let first = storage.record_failure("sig1", 7, 100, None, vec![1, 2, 3], "disk full").unwrap();
+        assert_eq!(first.attempts, 1);
+        assert_eq!(first.next_retry_at - first.first_failed_at, BASE_RETRY_DELAY_SECS);
+        assert!(storage.get_due(10).unwrap().is_empty());
+
+        // 重复失败累加次数，保留原始数据
+        let second = storage.record_failure("sig1", 7, 100, None, Vec::new(), "still full").unwrap();
+        assert_eq!(second.attempts, 2);
+        assert_eq!(second.payload, vec![1, 2, 3]);
+        assert_eq!(second.last_error, "still full");
+        assert_eq!(storage.count().unwrap(), 1);
+
+        assert_eq!(DeadLetterStorage::retry_delay(3), BASE_RETRY_DELAY_SECS * 4);
+        assert_eq!(DeadLetterStorage::retry_delay(40), MAX_RETRY_DELAY_SECS);
+
+        assert!(storage.remove("sig1").unwrap());
+        assert!(!storage.remove("sig1").unwrap());
+        assert!(storage.list().unwrap().is_empty());
+    }
+}
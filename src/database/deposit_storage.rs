@@ -0,0 +1,323 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::signature_storage::SignatureTransactionData;
+use crate::database::slot_finality_storage::{FinalityStatus, FinalityView};
+use crate::database::storage::StorageManager;
+
+/// 充值地址键前缀（地址 → 用户）
+pub const DEPOSIT_ADDRESS_KEY_PREFIX: &str = "DEPA01";
+
+/// 用户充值地址索引键前缀，键为 `<用户ID>:<地址>`
+pub const USER_ADDRESS_KEY_PREFIX: &str = "DEPU01";
+
+/// 充值记录键前缀，键为充值ID `<签名>:<序号>`
+pub const DEPOSIT_KEY_PREFIX: &str = "DEPS01";
+
+/// 用户充值索引键前缀，键为 `<用户ID>:<槽位>:<充值ID>`，按槽位排序
+pub const USER_DEPOSIT_KEY_PREFIX: &str = "DEPX01";
+
+/// 未最终确定的充值索引键前缀，键为 `<槽位>:<充值ID>`
+pub const PENDING_DEPOSIT_KEY_PREFIX: &str = "DEPP01";
+
+/// 用户ID最大长度
+pub const MAX_USER_ID_LENGTH: usize = 128;
+
+/// 登记的充值地址
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositAddress {
+    pub address: String,
+    pub user_id: String,
+    pub created_at: i64,
+}
+
+/// 充值状态变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositTransition {
+    pub status: FinalityStatus,
+    pub at: i64,
+}
+
+/// 转入充值地址的转账
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deposit {
+    /// 充值ID `<签名>:<序号>`
+    pub id: String,
+    pub user_id: String,
+    /// 充值地址（代币为所有者钱包）
+    pub address: String,
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: i64,
+    /// 转出方
+    pub from: String,
+    /// 金额（原始单位）
+    pub amount: u64,
+    /// 代币 mint，SOL 充值为空
+    pub mint: Option<String>,
+    pub decimals: u8,
+    /// 当前状态：processed → confirmed → finalized，或 orphaned
+    pub status: FinalityStatus,
+    /// 状态变化记录（按时间顺序）
+    pub transitions: Vec<DepositTransition>,
+}
+
+impl Deposit {
+    fn set_status(&mut self, status: FinalityStatus, at: i64) {
+        self.status = status;
+        self.transitions.push(DepositTransition { status, at });
+    }
+
+    /// 是否已是最终状态
+    pub fn is_settled(&self) -> bool {
+        matches!(self.status, FinalityStatus::Finalized | FinalityStatus::Orphaned)
+    }
+}
+
+/// 充值地址登记与充值记录
+///
+/// 交易写入时，成功交易中转入已登记地址的 SOL 和代币转账记为充值（状态 processed），
+/// 之后随槽位最终性更新为 confirmed / finalized，所在槽位被丢弃时为 orphaned。
+#[derive(Debug, Clone)]
+pub struct DepositStorage {
+    storage: StorageManager,
+    clock: SharedClock,
+}
+
+impl DepositStorage {
+    /// 创建新的充值存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self {
+            storage,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 用户ID是否有效：非空、不超过最大长度且不包含 `:`
+    pub fn is_valid_user_id(user_id: &str) -> bool {
+        !user_id.is_empty() && user_id.len() <= MAX_USER_ID_LENGTH && !user_id.contains(':')
+    }
+
+    /// 登记充值地址，已登记给其他用户时改为新用户
+    pub fn register_address(&self, address: &str, user_id: &str) -> Result<DepositAddress> {
+        let address_key = self.storage.make_key(DEPOSIT_ADDRESS_KEY_PREFIX, address)?;
+        if let Some(previous) = self.storage.get::<DepositAddress>(&address_key)? {
+            self.storage.delete(&self.make_user_address_key(&previous.user_id, address)?)?;
+        }
+
+        let entry = DepositAddress {
+            address: address.to_string(),
+            user_id: user_id.to_string(),
+            created_at: self.clock.timestamp(),
+        };
+        self.storage.batch_put(vec![
+            (address_key, serde_json::to_value(&entry)?),
+            (self.make_user_address_key(user_id, address)?, serde_json::to_value(address)?),
+        ])?;
+        Ok(entry)
+    }
+
+    /// 取消登记，返回地址是否已登记
+    pub fn unregister_address(&self, address: &str) -> Result<bool> {
+        let address_key = self.storage.make_key(DEPOSIT_ADDRESS_KEY_PREFIX, address)?;
+        let Some(entry) = self.storage.get::<DepositAddress>(&address_key)? else {
+            return Ok(false);
+        };
+        self.storage.delete(&address_key)?;
+        self.storage.delete(&self.make_user_address_key(&entry.user_id, address)?)?;
+        Ok(true)
+    }
+
+    /// 获取地址的登记信息
+    pub fn get_address(&self, address: &str) -> Result<Option<DepositAddress>> {
+        self.storage.get(&self.storage.make_key(DEPOSIT_ADDRESS_KEY_PREFIX, address)?)
+    }
+
+    /// 用户登记的充值地址
+    pub fn user_addresses(&self, user_id: &str) -> Result<Vec<String>> {
+        Ok(self
+            .storage
+            .get_by_prefix::<String>(&self.make_user_address_key(user_id, "")?)?
+            .into_iter()
+            .map(|item| item.value)
+            .collect())
+    }
+
+    /// 记录交易中转入充值地址的转账，返回新记录的充值
+    pub fn record_transfers(&self, data: &SignatureTransactionData) -> Result<Vec<Deposit>> {
+        if !data.is_successful {
+            return Ok(Vec::new());
+        }
+
+        let sol = data
+            .sol_transfers
+            .iter()
+            .map(|transfer| (transfer.from.as_str(), transfer.to.as_str(), transfer.amount, None, 9));
+        let token = data.token_transfers.iter().map(|transfer| {
+            (
+                transfer.sender_wallet(),
+                transfer.receiver_wallet(),
+                transfer.amount,
+                Some(transfer.mint.clone()),
+                transfer.decimals,
+            )
+        });
+
+        let now = self.clock.timestamp();
+        let mut deposits = Vec::new();
+        for (index, (from, to, amount, mint, decimals)) in sol.chain(token).enumerate() {
+            let Some(entry) = self.get_address(to)? else {
+                continue;
+            };
+            let id = format!("{}:{}", data.signature, index);
+            if self.storage.exists(&self.storage.make_key(DEPOSIT_KEY_PREFIX, &id)?)? {
+                continue;
+            }
+            let mut deposit = Deposit {
+                id,
+                user_id: entry.user_id,
+                address: to.to_string(),
+                signature: data.signature.clone(),
+                slot: data.slot,
+                timestamp: data.timestamp,
+                from: from.to_string(),
+                amount,
+                mint,
+                decimals,
+                status: FinalityStatus::Processed,
+                transitions: Vec::new(),
+            };
+            deposit.set_status(FinalityStatus::Processed, now);
+            deposits.push(deposit);
+        }
+
+        if !deposits.is_empty() {
+            let mut items = Vec::with_capacity(deposits.len() * 3);
+            for deposit in &deposits {
+                items.push((self.storage.make_key(DEPOSIT_KEY_PREFIX, &deposit.id)?, serde_json::to_value(deposit)?));
+                items.push((self.make_user_deposit_key(deposit)?, serde_json::to_value(&deposit.id)?));
+                items.push((self.make_pending_key(deposit)?, serde_json::to_value(&deposit.id)?));
+            }
+            self.storage.batch_put(items)?;
+            debug!("记录 {} 笔充值", deposits.len());
+        }
+        Ok(deposits)
+    }
+
+    /// 按槽位最终性更新未最终确定的充值，返回状态有变化的充值
+    pub fn refresh_statuses(&self, view: &FinalityView) -> Result<Vec<Deposit>> {
+        let mut changed = Vec::new();
+        for item in self.storage.get_by_prefix::<String>(PENDING_DEPOSIT_KEY_PREFIX)? {
+            let Some(mut deposit) = self.get_deposit(&item.value)? else {
+                self.storage.delete(&item.key)?;
+                continue;
+            };
+            let status = view.status_of(deposit.slot);
+            if status == deposit.status || status == FinalityStatus::Unknown {
+                continue;
+            }
+
+            deposit.set_status(status, self.clock.timestamp());
+            self.storage.put(&self.storage.make_key(DEPOSIT_KEY_PREFIX, &deposit.id)?, &deposit)?;
+            if deposit.is_settled() {
+                self.storage.delete(&item.key)?;
+            }
+            changed.push(deposit);
+        }
+        Ok(changed)
+    }
+
+    /// 获取充值记录
+    pub fn get_deposit(&self, id: &str) -> Result<Option<Deposit>> {
+        self.storage.get(&self.storage.make_key(DEPOSIT_KEY_PREFIX, id)?)
+    }
+
+    /// 用户的充值（最新的在前），可按状态过滤
+    pub fn user_deposits(&self, user_id: &str, status: Option<FinalityStatus>, limit: usize) -> Result<Vec<Deposit>> {
+        let prefix = self.storage.make_key(USER_DEPOSIT_KEY_PREFIX, &format!("{}:", user_id))?;
+        let mut deposits = Vec::new();
+        for item in self.storage.get_by_prefix::<String>(&prefix)?.into_iter().rev() {
+            if deposits.len() >= limit {
+                break;
+            }
+            if let Some(deposit) = self.get_deposit(&item.value)? {
+                if status.is_none_or(|status| deposit.status == status) {
+                    deposits.push(deposit);
+                }
+            }
+        }
+        Ok(deposits)
+    }
+
+    fn make_user_address_key(&self, user_id: &str, address: &str) -> Result<String> {
+        self.storage.make_key(USER_ADDRESS_KEY_PREFIX, &format!("{}:{}", user_id, address))
+    }
+
+    fn make_user_deposit_key(&self, deposit: &Deposit) -> Result<String> {
+        self.storage
+            .make_key(USER_DEPOSIT_KEY_PREFIX, &format!("{}:{:020}:{}", deposit.user_id, deposit.slot, deposit.id))
+    }
+
+    fn make_pending_key(&self, deposit: &Deposit) -> Result<String> {
+        self.storage.make_key(PENDING_DEPOSIT_KEY_PREFIX, &format!("{:020}:{}", deposit.slot, deposit.id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::signature_storage::SolTransfer;
+    use std::collections::HashSet;
+    use crate::test_util::TempDir;
+
+    const EXCHANGE: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const CUSTOMER: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+
+    fn transfer(signature: &str, slot: u64, to: &str) -> SignatureTransactionData {
+        let mut data = SignatureTransactionData::new(signature.to_string(), 1_704_067_200, slot, true);
+        data.add_sol_transfer(SolTransfer {
+            from: CUSTOMER.to_string(),
+            to: to.to_string(),
+            amount: 1_000,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        });
+        data
+    }
+
+    #[test]
+    fn test_deposits_follow_finality() {
+        let dir = TempDir::new("deposit-test");
+        let storage = DepositStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        storage.register_address(EXCHANGE, "user-1").unwrap();
+        assert_eq!(storage.user_addresses("user-1").unwrap(), vec![EXCHANGE.to_string()]);
+
+        assert_eq!(storage.record_transfers(&transfer("s1", 10, EXCHANGE)).unwrap().len(), 1);
+        assert_eq!(storage.record_transfers(&transfer("s2", 11, EXCHANGE)).unwrap().len(), 1);
+        // 重复写入和非充值地址都不记录
+        assert!(storage.record_transfers(&transfer("s1", 10, EXCHANGE)).unwrap().is_empty());
+        assert!(storage.record_transfers(&transfer("s3", 12, CUSTOMER)).unwrap().is_empty());
+
+        let view = FinalityView { finalized_slot: 10, confirmed_slot: 10, orphaned_slots: HashSet::from([11]) };
+        let changed = storage.refresh_statuses(&view).unwrap();
+        assert_eq!(changed.len(), 2);
+        assert!(storage.refresh_statuses(&view).unwrap().is_empty());
+
+        let deposits = storage.user_deposits("user-1", None, 10).unwrap();
+        assert_eq!(deposits.iter().map(|d| (d.slot, d.status)).collect::<Vec<_>>(), vec![
+            (11, FinalityStatus::Orphaned),
+            (10, FinalityStatus::Finalized),
+        ]);
+        assert_eq!(deposits[1].transitions.len(), 2);
+        assert_eq!(storage.user_deposits("user-1", Some(FinalityStatus::Finalized), 10).unwrap().len(), 1);
+
+        // 改登记给其他用户
+        storage.register_address(EXCHANGE, "user-2").unwrap();
+        assert!(storage.user_addresses("user-1").unwrap().is_empty());
+        assert!(storage.unregister_address(EXCHANGE).unwrap());
+        assert!(storage.user_addresses("user-2").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::config::EncryptionConfig;
+
+/// 加密值的格式版本，写在密文开头；JSON 值不会以该字节开头，因此可与未加密的旧数据共存
+pub const ENCRYPTED_FORMAT_V1: u8 = 0x01;
+
+/// AES-256 密钥长度（字节）
+const KEY_LENGTH: usize = 32;
+
+/// AES-GCM nonce 长度（字节）
+const NONCE_LENGTH: usize = 12;
+
+/// 存储值加密器（AES-256-GCM）
+///
+/// 密文格式为 `版本(1) | nonce(12) | 密文+认证标签`，键作为附加认证数据，
+/// 值被复制到其他键下时解密失败。
+#[derive(Clone)]
+pub struct ValueCipher {
+    cipher: Aes256Gcm,
+}
+
+impl std::fmt::Debug for ValueCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ValueCipher(AES-256-GCM)")
+    }
+}
+
+impl ValueCipher {
+    /// 使用 32 字节密钥创建加密器
+    pub fn new(key: &[u8]) -> Result<Self> {
+        if key.len() != KEY_LENGTH {
+            return Err(anyhow::anyhow!("加密密钥必须为 {} 字节，实际为 {} 字节", KEY_LENGTH, key.len()));
+        }
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        })
+    }
+
+    /// 按 `[database.encryption]` 读取密钥：依次使用 `key`、`key_env` 环境变量、`key_command` 命令输出
+    pub fn from_config(config: &EncryptionConfig) -> Result<Self> {
+        let encoded = if let Some(key) = &config.key {
+            key.clone()
+        } else if let Some(name) = &config.key_env {
+            std::env::var(name).with_context(|| format!("读取加密密钥环境变量 {} 失败", name))?
+        } else if let Some((program, args)) = config.key_command.split_first() {
+            // 例如调用 KMS 命令行解密数据密钥，标准输出为十六进制密钥
+            let output = Command::new(program)
+                .args(args)
+                .output()
+                .with_context(|| format!("执行加密密钥命令 {} 失败", program))?;
+            if !output.status.success() {
+                return Err(anyhow::anyhow!("加密密钥命令 {} 退出码 {}", program, output.status));
+            }
+            String::from_utf8(output.stdout).context("加密密钥命令输出不是 UTF-8")?
+        } else {
+            return Err(anyhow::anyhow!("启用加密时必须配置 key、key_env 或 key_command 之一"));
+        };
+        let key = hex::decode(encoded.trim()).context("加密密钥必须是十六进制字符串")?;
+        Self::new(&key)
+    }
+
+    /// 加密值，`key` 为存储键
+    pub fn encrypt(&self, key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, Payload { msg: plaintext, aad: key })
+            .map_err(|_| anyhow::anyhow!("加密数据失败"))?;
+
+        let mut data = Vec::with_capacity(1 + NONCE_LENGTH + ciphertext.len());
+        data.push(ENCRYPTED_FORMAT_V1);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        Ok(data)
+    }
+
+    /// 解密值；未加密的旧数据原样返回
+    pub fn decrypt(&self, key: &[u8], data: Vec<u8>) -> Result<Vec<u8>> {
+        if !Self::is_encrypted(&data) {
+            return Ok(data);
+        }
+        if data.len() < 1 + NONCE_LENGTH {
+            return Err(anyhow::anyhow!("加密数据长度不足"));
+        }
+        let (nonce, ciphertext) = data[1..].split_at(NONCE_LENGTH);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: key })
+            .map_err(|_| anyhow::anyhow!("解密数据失败：密钥错误或数据被篡改"))
+    }
+
+    /// 值是否为加密格式
+    pub fn is_encrypted(data: &[u8]) -> bool {
+        data.first() == Some(&ENCRYPTED_FORMAT_V1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip_and_tamper_detection() {
+        let cipher = ValueCipher::new(&[7u8; KEY_LENGTH]).unwrap();
+        let encrypted = cipher.encrypt(b"SIG001abc", br#"{"slot":1}"#).unwrap();
+        assert!(ValueCipher::is_encrypted(&encrypted));
+        assert_eq!(cipher.decrypt(b"SIG001abc", encrypted.clone()).unwrap(), br#"{"slot":1}"#);
+
+        // 其他键、其他密钥或被篡改的密文都无法解密
+        assert!(cipher.decrypt(b"SIG001xyz", encrypted.clone()).is_err());
+        assert!(ValueCipher::new(&[8u8; KEY_LENGTH]).unwrap().decrypt(b"SIG001abc", encrypted.clone()).is_err());
+        let mut tampered = encrypted;
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(cipher.decrypt(b"SIG001abc", tampered).is_err());
+
+        // 未加密的旧数据原样返回
+        assert_eq!(cipher.decrypt(b"k", br#"{"slot":1}"#.to_vec()).unwrap(), br#"{"slot":1}"#);
+        assert!(ValueCipher::new(&[0u8; 16]).is_err());
+    }
+}
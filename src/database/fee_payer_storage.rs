@@ -0,0 +1,137 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 代付账户索引键前缀（按手续费支付者）
+pub const FUNDED_ACCOUNT_KEY_PREFIX: &str = "FPAY01";
+
+/// 手续费支付者索引键前缀（按被代付的账户）
+pub const FEE_PAYER_KEY_PREFIX: &str = "FPBY01";
+
+/// 两个地址之间的手续费代付关系
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeRelation {
+    /// 关系另一端的地址
+    pub address: String,
+    /// 代付的交易笔数
+    pub transaction_count: u64,
+    /// 这些交易的手续费合计（lamports）
+    pub fee_lamports: u64,
+    /// 首次代付时间戳
+    pub first_seen: u64,
+    /// 最近代付时间戳
+    pub last_seen: u64,
+}
+
+/// 手续费代付关系存储
+///
+/// 交易的第一个签名者支付手续费，其余签名者视为被代付的账户。同一实体控制的钱包
+/// 常由同一个地址统一付费，因此这是常用的钱包聚类依据。每条关系保存两份：
+/// `FPAY01{payer}:{account}` 用于查询代付了哪些账户，`FPBY01{account}:{payer}`
+/// 用于查询由谁代付。
+#[derive(Debug, Clone)]
+pub struct FeePayerStorage {
+    storage: StorageManager,
+}
+
+impl FeePayerStorage {
+    /// 创建新的代付关系存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 按一笔交易的签名者更新代付关系，`signers` 的第一个为手续费支付者
+    pub fn record_transaction(&self, timestamp: u64, fee_lamports: u64, signers: &[String]) -> Result<()> {
+        let Some((payer, others)) = signers.split_first() else {
+            return Ok(());
+        };
+        let accounts: BTreeSet<&String> = others.iter().filter(|account| *account != payer).collect();
+
+        let mut items = Vec::with_capacity(accounts.len() * 2);
+        for account in accounts {
+            for (prefix, owner, other) in [(FUNDED_ACCOUNT_KEY_PREFIX, payer, account), (FEE_PAYER_KEY_PREFIX, account, payer)] {
+                let key = self.make_key(prefix, owner, other)?;
+                let mut relation = self.storage.get::<FeeRelation>(&key)?.unwrap_or(FeeRelation {
+                    address: other.clone(),
+                    first_seen: timestamp,
+                    ..Default::default()
+                });
+                relation.transaction_count += 1;
+                relation.fee_lamports += fee_lamports;
+                relation.first_seen = relation.first_seen.min(timestamp);
+                relation.last_seen = relation.last_seen.max(timestamp);
+                items.push((key, relation));
+            }
+            debug!("地址 {} 代付 {} 的手续费", payer, account);
+        }
+        if !items.is_empty() {
+            self.storage.batch_put(items)?;
+        }
+        Ok(())
+    }
+
+    /// 地址代付过手续费的账户，按交易笔数从多到少排序
+    pub fn get_funded_accounts(&self, payer: &str) -> Result<Vec<FeeRelation>> {
+        self.get_relations(FUNDED_ACCOUNT_KEY_PREFIX, payer)
+    }
+
+    /// 为地址代付过手续费的支付者，按交易笔数从多到少排序
+    pub fn get_fee_payers(&self, account: &str) -> Result<Vec<FeeRelation>> {
+        self.get_relations(FEE_PAYER_KEY_PREFIX, account)
+    }
+
+    fn get_relations(&self, prefix: &str, address: &str) -> Result<Vec<FeeRelation>> {
+        let prefix = format!("{}{}:", prefix, address);
+        let mut relations: Vec<FeeRelation> = self
+            .storage
+            .get_by_prefix::<FeeRelation>(&prefix)?
+            .into_iter()
+            .map(|item| item.value)
+            .collect();
+        relations.sort_by(|a, b| {
+            b.transaction_count
+                .cmp(&a.transaction_count)
+                .then_with(|| b.last_seen.cmp(&a.last_seen))
+        });
+        Ok(relations)
+    }
+
+    fn make_key(&self, prefix: &str, address: &str, other: &str) -> Result<String> {
+        self.storage.make_key(prefix, &format!("{}:{}", address, other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn signers(addresses: &[&str]) -> Vec<String> {
+        addresses.iter().map(|address| address.to_string()).collect()
+    }
+
+    #[test]
+    fn test_fee_relations_recorded_in_both_directions() {
+        let dir = TempDir::new("fee-payer-test");
+        let storage = FeePayerStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        storage.record_transaction(100, 5_000, &signers(&["relayer", "alice"])).unwrap();
+        storage.record_transaction(200, 5_000, &signers(&["relayer", "alice", "bob"])).unwrap();
+        // 只有支付者自己签名的交易不产生代付关系
+        storage.record_transaction(300, 5_000, &signers(&["alice"])).unwrap();
+
+        let funded = storage.get_funded_accounts("relayer").unwrap();
+        assert_eq!(funded.len(), 2);
+        assert_eq!(funded[0].address, "alice");
+        assert_eq!((funded[0].transaction_count, funded[0].fee_lamports), (2, 10_000));
+        assert_eq!((funded[0].first_seen, funded[0].last_seen), (100, 200));
+
+        let payers = storage.get_fee_payers("bob").unwrap();
+        assert_eq!(payers.len(), 1);
+        assert_eq!((payers[0].address.as_str(), payers[0].transaction_count), ("relayer", 1));
+        assert!(storage.get_funded_accounts("alice").unwrap().is_empty());
+    }
+}
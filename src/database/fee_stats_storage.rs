@@ -0,0 +1,93 @@
+use anyhow::Result;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 每日手续费统计键前缀
+pub const DAILY_FEE_KEY_PREFIX: &str = "FEED01";
+
+/// 每日手续费统计（按交易时间的 UTC 日期汇总）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyFeeStats {
+    /// 日期（YYYY-MM-DD，UTC）
+    pub date: String,
+    /// 交易数
+    pub transaction_count: u64,
+    /// 手续费总额（lamports）
+    pub total_fee_lamports: u64,
+    /// 计算单元消耗总量
+    pub total_compute_units: u64,
+}
+
+/// 每日手续费统计存储管理器
+#[derive(Debug, Clone)]
+pub struct FeeStatsStorage {
+    storage: StorageManager,
+}
+
+impl FeeStatsStorage {
+    /// 创建新的手续费统计存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 将一笔交易的手续费累加到交易所在日期
+    pub fn add_fee(&self, timestamp: i64, fee_lamports: u64, compute_units: Option<u64>) -> Result<()> {
+        let date = Self::date_of(timestamp);
+        let key = self.storage.make_key(DAILY_FEE_KEY_PREFIX, &date)?;
+        let mut stats = self
+            .storage
+            .get::<DailyFeeStats>(&key)?
+            .unwrap_or(DailyFeeStats { date: date.clone(), ..Default::default() });
+
+        stats.transaction_count += 1;
+        stats.total_fee_lamports += fee_lamports;
+        stats.total_compute_units += compute_units.unwrap_or(0);
+
+        self.storage.put(&key, &stats)?;
+        debug!("{} 手续费统计更新: {} 笔交易", date, stats.transaction_count);
+        Ok(())
+    }
+
+    /// 获取最近若干天的统计（最新的在前）
+    pub fn get_recent_days(&self, days: usize) -> Result<Vec<DailyFeeStats>> {
+        // 日期键按字典序即时间顺序排列
+        let items = self.storage.get_by_prefix::<DailyFeeStats>(DAILY_FEE_KEY_PREFIX)?;
+        Ok(items.into_iter().rev().take(days).map(|item| item.value).collect())
+    }
+
+    fn date_of(timestamp: i64) -> String {
+        DateTime::from_timestamp(timestamp, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_fees_aggregated_per_utc_day() {
+        let dir = TempDir::new("fee-stats-test");
+        let storage = FeeStatsStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        // 2024-01-01 00:00:00 UTC 与当天最后一秒属于同一天
+        storage.add_fee(1_704_067_200, 5_000, Some(200_000)).unwrap();
+        storage.add_fee(1_704_153_599, 10_000, None).unwrap();
+        storage.add_fee(1_704_153_600, 5_000, Some(1_000)).unwrap();
+
+        let days = storage.get_recent_days(7).unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2024-01-02");
+        assert_eq!(days[0].transaction_count, 1);
+        assert_eq!(days[1].date, "2024-01-01");
+        assert_eq!(days[1].total_fee_lamports, 15_000);
+        assert_eq!(days[1].total_compute_units, 200_000);
+        assert_eq!(storage.get_recent_days(1).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,287 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::database::address_storage::{AddressStorage, AddressTransactionRecord, RecordType};
+use crate::database::amount_histogram_storage::SOL_ASSET;
+
+/// SOL 精度
+const SOL_DECIMALS: u8 = 9;
+
+/// 资金流追踪条件
+#[derive(Debug, Clone)]
+pub struct FlowTraceQuery {
+    /// 起点地址
+    pub source: String,
+    /// 追踪的资产：`SOL` 或代币 mint
+    pub asset: String,
+    /// 最多追踪的跳数
+    pub max_hops: usize,
+    /// 从该时间戳（包含）之后起点转出的资金开始追踪
+    pub start_time: Option<u64>,
+    /// 最多展开的节点数（含起点），用完后停止展开
+    pub max_nodes: usize,
+}
+
+/// 资金流中的一个节点
+#[derive(Debug, Clone)]
+pub struct FlowNode {
+    pub address: String,
+    /// 与起点的跳数
+    pub hop: usize,
+    /// 追踪到该地址的金额（原始单位），起点为其转出总额
+    pub amount: u64,
+    /// 从上一跳收到的转账笔数（起点为 0）
+    pub transfer_count: usize,
+    /// 从上一跳首次收到资金的时间戳（起点为追踪起始时间）
+    pub first_timestamp: u64,
+    /// 首笔转入的签名（起点为 None）
+    pub first_signature: Option<String>,
+    /// 下一跳，按追踪金额从大到小排列
+    pub children: Vec<FlowNode>,
+}
+
+/// 资金流追踪结果
+#[derive(Debug, Clone)]
+pub struct FlowTrace {
+    pub root: FlowNode,
+    /// 资产精度
+    pub decimals: u8,
+    /// 实际展开的节点数
+    pub nodes_visited: usize,
+    /// 是否因节点预算用完而提前停止
+    pub truncated: bool,
+}
+
+/// 资金流向追踪
+///
+/// 从起点出发按时间向后沿转出记录逐跳展开：每个地址只考虑收到资金之后的转出，
+/// 收到的金额按各笔转出占其转出总额的比例分摊到下一跳（转出总额少于收到金额时全部计入）。
+/// 按跳数逐层展开，节点预算先分给离起点近的地址；回到同一路径上已出现的地址时不再展开，
+/// 避免环路。数据来自地址记录，每个地址只保留最近的 `max_address_records` 条。
+pub struct FlowTracer<'a> {
+    address_storage: &'a AddressStorage,
+}
+
+/// 展开过程中的节点，按父节点序号在结束后组装成树
+struct PendingNode {
+    node: FlowNode,
+    parent: Option<usize>,
+}
+
+impl<'a> FlowTracer<'a> {
+    pub fn new(address_storage: &'a AddressStorage) -> Self {
+        Self { address_storage }
+    }
+
+    /// 执行追踪
+    pub fn trace(&self, query: &FlowTraceQuery) -> Result<FlowTrace> {
+        let since = query.start_time.unwrap_or(0);
+        let source_outflows = self.outflows(&query.source, &query.asset, since)?;
+        let decimals = source_outflows.first().map(|(_, decimals)| *decimals).unwrap_or(SOL_DECIMALS);
+        let source_amount = source_outflows.iter().map(|(record, _)| Self::amount_of(record)).sum();
+
+        let mut pending = vec![PendingNode {
+            node: FlowNode {
+                address: query.source.clone(),
+                hop: 0,
+                amount: source_amount,
+                transfer_count: 0,
+                first_timestamp: since,
+                first_signature: None,
+                children: Vec::new(),
+            },
+            parent: None,
+        }];
+        let mut truncated = false;
+        let mut next = 0;
+        while next < pending.len() {
+            let index = next;
+            next += 1;
+            let node = &pending[index].node;
+            if node.hop >= query.max_hops || node.amount == 0 {
+                continue;
+            }
+
+            let ancestors = Self::ancestors(&pending, index);
+            let (address, hop, amount, since) = (node.address.clone(), node.hop, node.amount, node.first_timestamp);
+            for child in self.expand(&address, &query.asset, amount, since)? {
+                if ancestors.contains(&child.address) {
+                    continue;
+                }
+                if pending.len() >= query.max_nodes {
+                    truncated = true;
+                    break;
+                }
+                pending.push(PendingNode { node: FlowNode { hop: hop + 1, ..child }, parent: Some(index) });
+            }
+            if truncated {
+                break;
+            }
+        }
+
+        let nodes_visited = pending.len();
+        Ok(FlowTrace { root: Self::assemble(pending), decimals, nodes_visited, truncated })
+    }
+
+    /// 把地址收到的 `amount` 按比例分摊到其在 `since` 之后的转出，同一接收方合并
+    fn expand(&self, address: &str, asset: &str, amount: u64, since: u64) -> Result<Vec<FlowNode>> {
+        let outflows = self.outflows(address, asset, since)?;
+        let total: u64 = outflows.iter().map(|(record, _)| Self::amount_of(record)).sum();
+        if total == 0 {
+            return Ok(Vec::new());
+        }
+        let traced = amount.min(total);
+
+        let mut children: BTreeMap<String, FlowNode> = BTreeMap::new();
+        for (record, _) in outflows {
+            let share = (Self::amount_of(&record) as u128 * traced as u128 / total as u128) as u64;
+            if share == 0 {
+                continue;
+            }
+            let child = children.entry(Self::receiver_of(&record).to_string()).or_insert_with(|| FlowNode {
+                address: Self::receiver_of(&record).to_string(),
+                hop: 0,
+                amount: 0,
+                transfer_count: 0,
+                first_timestamp: record.timestamp,
+                first_signature: Some(record.signature.clone()),
+                children: Vec::new(),
+            });
+            child.amount += share;
+            child.transfer_count += 1;
+        }
+
+        let mut children: Vec<FlowNode> = children.into_values().collect();
+        children.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.address.cmp(&b.address)));
+        Ok(children)
+    }
+
+    /// 地址在 `since`（包含）之后转出该资产的记录及其精度，按时间从早到晚
+    fn outflows(&self, address: &str, asset: &str, since: u64) -> Result<Vec<(AddressTransactionRecord, u8)>> {
+        let Some(list) = self.address_storage.get_address_records(address)? else {
+            return Ok(Vec::new());
+        };
+        let mut outflows: Vec<(AddressTransactionRecord, u8)> = list
+            .records
+            .into_iter()
+            .filter(|record| matches!(record.record_type, RecordType::Sender) && record.timestamp >= since)
+            .filter_map(|record| {
+                let decimals = match (&record.sol_transfer, &record.token_transfer) {
+                    (Some(sol), _) if asset == SOL_ASSET && sol.from != sol.to => SOL_DECIMALS,
+                    (None, Some(token)) if token.mint == asset && token.sender_wallet() != token.receiver_wallet() => {
+                        token.decimals as u8
+                    }
+                    _ => return None,
+                };
+                Some((record, decimals))
+            })
+            .collect();
+        outflows.sort_by_key(|(record, _)| (record.timestamp, record.slot));
+        Ok(outflows)
+    }
+
+    fn amount_of(record: &AddressTransactionRecord) -> u64 {
+        match (&record.sol_transfer, &record.token_transfer) {
+            (Some(sol), _) => sol.amount,
+            (None, Some(token)) => token.amount,
+            (None, None) => 0,
+        }
+    }
+
+    fn receiver_of(record: &AddressTransactionRecord) -> &str {
+        match (&record.sol_transfer, &record.token_transfer) {
+            (Some(sol), _) => &sol.to,
+            (None, Some(token)) => token.receiver_wallet(),
+            (None, None) => "",
+        }
+    }
+
+    /// 节点到起点路径上的全部地址（含自身）
+    fn ancestors(pending: &[PendingNode], mut index: usize) -> HashSet<String> {
+        let mut addresses = HashSet::new();
+        loop {
+            addresses.insert(pending[index].node.address.clone());
+            match pending[index].parent {
+                Some(parent) => index = parent,
+                None => return addresses,
+            }
+        }
+    }
+
+    /// 按父节点序号组装成树；子节点总在父节点之后，倒序移动即可
+    fn assemble(mut pending: Vec<PendingNode>) -> FlowNode {
+        while pending.len() > 1 {
+            let PendingNode { node, parent } = pending.pop().expect("至少有两个节点");
+            if let Some(parent) = parent {
+                pending[parent].node.children.insert(0, node);
+            }
+        }
+        pending.pop().expect("起点节点").node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::storage::StorageManager;
+    use crate::transfer_types::SolTransfer;
+    use crate::test_util::TempDir;
+
+    fn sol_transfer(from: &str, to: &str, amount: u64) -> SolTransfer {
+        SolTransfer {
+            signature: "sig".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            from_index: 0,
+            to_index: 1,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        }
+    }
+
+    #[test]
+    fn test_trace_splits_amounts_proportionally_forward_in_time() {
+        let dir = TempDir::new("flow-trace-test");
+        let addresses = AddressStorage::new(StorageManager::new(dir.path(), 6).unwrap(), "ADDR01".to_string(), 100);
+        let transfers = [
+            // 早于资金到达的转出不计入
+            (1, "bob", "dave", 500),
+            (2, "alice", "bob", 100),
+            (3, "bob", "carol", 300),
+            (4, "bob", "erin", 100),
+            (5, "carol", "alice", 300),
+        ];
+        for (slot, from, to, amount) in transfers {
+            addresses
+                .batch_process_transaction(&format!("sig{}", slot), slot * 10, slot, &[sol_transfer(from, to, amount)], &[])
+                .unwrap();
+        }
+        let tracer = FlowTracer::new(&addresses);
+        let mut query = FlowTraceQuery {
+            source: "alice".to_string(),
+            asset: SOL_ASSET.to_string(),
+            max_hops: 3,
+            start_time: None,
+            max_nodes: 100,
+        };
+
+        let trace = tracer.trace(&query).unwrap();
+        assert_eq!((trace.root.amount, trace.decimals, trace.truncated), (100, 9, false));
+        let bob = &trace.root.children[0];
+        assert_eq!((bob.address.as_str(), bob.amount, bob.first_timestamp), ("bob", 100, 20));
+        // bob 收到 100，之后转出 300 + 100，按 3:1 分摊
+        let split: Vec<(&str, u64)> = bob.children.iter().map(|node| (node.address.as_str(), node.amount)).collect();
+        assert_eq!(split, vec![("carol", 75), ("erin", 25)]);
+        // carol 转回 alice 形成环路，不再展开
+        assert!(bob.children[0].children.is_empty());
+        assert_eq!(trace.nodes_visited, 4);
+
+        query.max_nodes = 2;
+        let trace = tracer.trace(&query).unwrap();
+        assert!(trace.truncated);
+        assert_eq!(trace.nodes_visited, 2);
+    }
+}
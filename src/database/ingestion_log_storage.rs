@@ -0,0 +1,187 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::storage::StorageManager;
+
+/// 摄取审计日志键前缀
+pub const INGESTION_LOG_KEY_PREFIX: &str = "INGL01";
+
+/// 摄取事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IngestionEvent {
+    /// 进程启动后首次订阅成功
+    Subscribed { endpoint: String, watchlist_size: usize },
+    /// 断开后重新订阅成功
+    Reconnected { endpoint: String, watchlist_size: usize },
+    /// 订阅流结束
+    Disconnected { reason: String },
+    /// 槽位缺口：新槽位的父槽位晚于上次看到的槽位，中间槽位的交易可能缺失；
+    /// 磁盘用量保护暂停写入期间丢弃的槽位范围也记为缺口
+    SlotGap { last_seen_slot: u64, parent_slot: u64, slot: u64 },
+    /// 一次连接期间因已存在而跳过的交易
+    DuplicatesSkipped { count: u64 },
+    /// 最近存储的交易落后最新槽位超过阈值
+    SlotLag { latest_slot: u64, last_stored_slot: u64, slot_lag: u64 },
+    /// 槽位落后恢复到阈值以内
+    SlotLagRecovered { latest_slot: u64, last_stored_slot: u64, slot_lag: u64 },
+    /// 统计窗口内存储失败次数达到阈值
+    StorageErrors { count: u32, window_secs: u64, last_error: String },
+    /// 数据库所在磁盘可用空间低于阈值
+    DiskSpaceLow { path: String, available_mb: u64, min_free_mb: u64 },
+    /// 磁盘可用空间恢复到阈值以上
+    DiskSpaceRecovered { path: String, available_mb: u64, min_free_mb: u64 },
+    /// 磁盘用量保护触发，`action` 为 prune 或 pause
+    DiskGuardTriggered { action: String, reason: String, db_size_mb: u64, available_mb: u64 },
+    /// 磁盘用量回到阈值以内，恢复写入
+    DiskGuardReleased { db_size_mb: u64, available_mb: u64 },
+}
+
+impl IngestionEvent {
+    /// 全部事件类型名称
+    pub const KINDS: [&'static str; 12] = [
+        "subscribed",
+        "reconnected",
+        "disconnected",
+        "slot_gap",
+        "duplicates_skipped",
+        "slot_lag",
+        "slot_lag_recovered",
+        "storage_errors",
+        "disk_space_low",
+        "disk_space_recovered",
+        "disk_guard_triggered",
+        "disk_guard_released",
+    ];
+
+    /// 检测槽位缺口，`last_seen_slot` 为此前看到的最大槽位
+    ///
+    /// Leader 跳过的槽位不会出现在父槽位链上，因此只有父槽位本身未被看到时才算缺口。
+    pub fn slot_gap(last_seen_slot: Option<u64>, slot: u64, parent_slot: Option<u64>) -> Option<Self> {
+        match (last_seen_slot, parent_slot) {
+            (Some(last_seen_slot), Some(parent_slot)) if parent_slot > last_seen_slot => Some(Self::SlotGap {
+                last_seen_slot,
+                parent_slot,
+                slot,
+            }),
+            _ => None,
+        }
+    }
+
+    /// 事件类型名称
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Subscribed { .. } => "subscribed",
+            Self::Reconnected { .. } => "reconnected",
+            Self::Disconnected { .. } => "disconnected",
+            Self::SlotGap { .. } => "slot_gap",
+            Self::DuplicatesSkipped { .. } => "duplicates_skipped",
+            Self::SlotLag { .. } => "slot_lag",
+            Self::SlotLagRecovered { .. } => "slot_lag_recovered",
+            Self::StorageErrors { .. } => "storage_errors",
+            Self::DiskSpaceLow { .. } => "disk_space_low",
+            Self::DiskSpaceRecovered { .. } => "disk_space_recovered",
+            Self::DiskGuardTriggered { .. } => "disk_guard_triggered",
+            Self::DiskGuardReleased { .. } => "disk_guard_released",
+        }
+    }
+}
+
+/// 审计日志条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestionLogEntry {
+    /// 条目编号（键中前缀之后的部分，按时间递增）
+    pub id: String,
+    /// 记录时间
+    pub timestamp: i64,
+    /// 事件
+    pub event: IngestionEvent,
+}
+
+/// 摄取审计日志
+///
+/// 只追加不修改，键为毫秒时间戳加进程内序号，按前缀扫描即为时间顺序。
+/// 用于事故后核对订阅中断、槽位缺口等情况，判断数据是否完整。
+#[derive(Debug, Clone)]
+pub struct IngestionLogStorage {
+    storage: StorageManager,
+    clock: SharedClock,
+    sequence: Arc<AtomicU64>,
+}
+
+impl IngestionLogStorage {
+    /// 创建新的审计日志实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self {
+            storage,
+            clock: SystemClock::shared(),
+            sequence: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// 追加一条事件
+    pub fn append(&self, event: IngestionEvent) -> Result<IngestionLogEntry> {
+        let now = self.clock.now();
+        let sequence = self.sequence.fetch_add(1, Ordering::SeqCst) % 1_000_000;
+        let id = format!("{:015}:{:06}", now.timestamp_millis(), sequence);
+        let entry = IngestionLogEntry {
+            id: id.clone(),
+            timestamp: now.timestamp(),
+            event,
+        };
+        self.storage.put(&self.storage.make_key(INGESTION_LOG_KEY_PREFIX, &id)?, &entry)?;
+        debug!("摄取审计日志: {} {:?}", entry.event.kind(), entry.event);
+        Ok(entry)
+    }
+
+    /// 获取不早于 `since` 的事件（最新的在前），可按类型过滤，最多 `limit` 条
+    pub fn list(&self, since: Option<i64>, kind: Option<&str>, limit: usize) -> Result<Vec<IngestionLogEntry>> {
+        Ok(self
+            .storage
+            .get_by_prefix::<IngestionLogEntry>(INGESTION_LOG_KEY_PREFIX)?
+            .into_iter()
+            .rev()
+            .map(|item| item.value)
+            .filter(|entry| since.is_none_or(|since| entry.timestamp >= since))
+            .filter(|entry| kind.is_none_or(|kind| entry.event.kind() == kind))
+            .take(limit)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_append_list_and_gap_detection() {
+        let dir = TempDir::new("ingestion-log-test");
+        let storage = IngestionLogStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        storage
+            .append(IngestionEvent::Subscribed { endpoint: "http://grpc".to_string(), watchlist_size: 0 })
+            .unwrap();
+        storage.append(IngestionEvent::Disconnected { reason: "stream ended".to_string() }).unwrap();
+        let gap = IngestionEvent::slot_gap(Some(100), 105, Some(104)).unwrap();
+        storage.append(gap.clone()).unwrap();
+
+        let entries = storage.list(None, None, 10).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].event, gap);
+        assert_eq!(entries[2].event.kind(), "subscribed");
+        assert!(entries[0].id > entries[1].id);
+
+        assert_eq!(storage.list(None, Some("disconnected"), 10).unwrap().len(), 1);
+        assert_eq!(storage.list(None, None, 1).unwrap().len(), 1);
+
+        // 跳过的槽位、乱序和首次看到的槽位不算缺口
+        assert!(IngestionEvent::slot_gap(Some(100), 102, Some(100)).is_none());
+        assert!(IngestionEvent::slot_gap(Some(100), 99, Some(98)).is_none());
+        assert!(IngestionEvent::slot_gap(None, 105, Some(104)).is_none());
+    }
+}
@@ -0,0 +1,501 @@
+use anyhow::{Context, Result};
+use rocksdb::{Direction, IteratorMode, Options, DB};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+/// 底层键值存储后端
+///
+/// `StorageManager` 只依赖这组原始字节操作，序列化、键前缀等逻辑都在其上层完成，
+/// 因此更换后端不需要改动各业务存储和数据摄取代码。键按字节序排列，前缀扫描结果
+/// 需按键升序返回。
+pub trait KvStore: Send + Sync + Debug {
+    /// 后端名称，用于日志
+    fn backend_name(&self) -> &'static str;
+
+    /// 写入键值对
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()>;
+
+    /// 读取值
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// 删除键
+    fn delete(&self, key: &[u8]) -> Result<()>;
+
+    /// 按前缀扫描键值对（按键升序）
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// 按前缀扫描键（按键升序）
+    fn scan_prefix_keys(&self, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        Ok(self.scan_prefix(prefix)?.into_iter().map(|(key, _)| key).collect())
+    }
+
+    /// 按前缀扫描键（按键升序），最多返回 `limit` 个
+    fn scan_prefix_keys_limit(&self, prefix: &[u8], limit: usize) -> Result<Vec<Vec<u8>>> {
+        let mut keys = self.scan_prefix_keys(prefix)?;
+        keys.truncate(limit);
+        Ok(keys)
+    }
+
+    /// 按前缀分页扫描键值对（按键升序），从 `start_after` 之后开始，最多返回 `limit` 个
+    fn scan_prefix_page(
+        &self,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        Ok(self
+            .scan_prefix(prefix)?
+            .into_iter()
+            .filter(|(key, _)| start_after.is_none_or(|after| key.as_slice() > after))
+            .take(limit)
+            .collect())
+    }
+
+    /// 按前缀分页扫描键（按键升序），从 `start_after` 之后开始，最多返回 `limit` 个
+    fn scan_prefix_keys_page(&self, prefix: &[u8], start_after: Option<&[u8]>, limit: usize) -> Result<Vec<Vec<u8>>> {
+        Ok(self.scan_prefix_page(prefix, start_after, limit)?.into_iter().map(|(key, _)| key).collect())
+    }
+
+    /// 按前缀从最后一个键开始逆序扫描键（按键降序），最多返回 `limit` 个
+    fn scan_prefix_keys_rev_limit(&self, prefix: &[u8], limit: usize) -> Result<Vec<Vec<u8>>> {
+        let mut keys = self.scan_prefix_keys(prefix)?;
+        keys.reverse();
+        keys.truncate(limit);
+        Ok(keys)
+    }
+
+    /// 原子地批量写入
+    fn write_batch(&self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<()>;
+
+    /// 字符串类型的后端属性，不支持时返回 None
+    fn property_value(&self, _name: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// 整数类型的后端属性，不支持时返回 None
+    fn property_int_value(&self, _name: &str) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    /// 将缓冲数据刷写到持久化存储
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// 压缩存储空间
+    fn compact(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// 是否为只读副本
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
+    /// 只读副本追赶主库的最新写入
+    fn try_catch_up_with_primary(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// 在目标目录创建一致性快照（目录不能已存在）
+    fn create_checkpoint(&self, _target: &Path) -> Result<()> {
+        Err(anyhow::anyhow!("{} 后端不支持创建快照", self.backend_name()))
+    }
+}
+
+/// 计算大于所有以 `prefix` 开头的键的最小值；前缀全为 0xFF 时没有上界
+pub(crate) fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut upper = prefix.to_vec();
+    while let Some(last) = upper.pop() {
+        if last < u8::MAX {
+            upper.push(last + 1);
+            return Some(upper);
+        }
+    }
+    None
+}
+
+/// 嵌入式 RocksDB 后端
+#[derive(Debug)]
+pub struct RocksDbStore {
+    db: DB,
+    /// 以 secondary 模式打开，需要追赶主库写入
+    secondary: bool,
+    /// 只读打开（secondary 或只读模式）
+    read_only: bool,
+}
+
+impl RocksDbStore {
+    /// 使用指定选项打开 RocksDB
+    pub fn open<P: AsRef<Path>>(db_path: P, opts: &Options) -> Result<Self> {
+        // 创建数据库目录
+        let path = db_path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("创建数据库目录失败")?;
+        }
+
+        // 打开数据库
+        let db = DB::open(opts, path).context("打开 RocksDB 数据库失败")?;
+
+        info!("RocksDB 数据库已成功打开: {:?}", path);
+        Ok(Self { db, secondary: false, read_only: false })
+    }
+
+    /// 以只读模式打开，用于校验快照等离线场景
+    pub fn open_read_only<P: AsRef<Path>>(db_path: P, opts: &Options) -> Result<Self> {
+        let db = DB::open_for_read_only(opts, db_path.as_ref(), false)
+            .context("以只读模式打开 RocksDB 失败")?;
+        Ok(Self { db, secondary: false, read_only: true })
+    }
+
+    /// 以 secondary 模式打开主库目录，供只读副本使用
+    ///
+    /// secondary 实例需要自己的目录保存信息日志，并通过
+    /// `try_catch_up_with_primary` 定期追赶主库写入。
+    pub fn open_secondary<P: AsRef<Path>>(primary_path: P, secondary_path: P, opts: &Options) -> Result<Self> {
+        std::fs::create_dir_all(secondary_path.as_ref()).context("创建 secondary 目录失败")?;
+
+        let mut opts = opts.clone();
+        // secondary 模式要求保持所有文件打开
+        opts.set_max_open_files(-1);
+        let db = DB::open_as_secondary(&opts, primary_path.as_ref(), secondary_path.as_ref())
+            .context("以 secondary 模式打开 RocksDB 失败")?;
+
+        info!(
+            "RocksDB 已以只读副本模式打开: primary={:?}, secondary={:?}",
+            primary_path.as_ref(),
+            secondary_path.as_ref()
+        );
+        Ok(Self { db, secondary: true, read_only: true })
+    }
+
+    fn check_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(anyhow::anyhow!("只读模式下不允许写入"));
+        }
+        Ok(())
+    }
+
+    /// 从 `start_after` 之后按键升序遍历前缀，最多取 `limit` 项，每项由 `item` 转换
+    fn scan_page<T>(
+        &self,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+        item: impl Fn(&[u8], &[u8]) -> T,
+    ) -> Result<Vec<T>> {
+        let mut results = Vec::new();
+        let start = start_after.filter(|after| *after >= prefix).unwrap_or(prefix);
+        let iter = self.db.iterator(IteratorMode::From(start, Direction::Forward));
+
+        for entry in iter {
+            if results.len() >= limit {
+                break;
+            }
+            let (key, value) = entry.context("迭代数据库失败")?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            if start_after.is_some_and(|after| &*key <= after) {
+                continue;
+            }
+            results.push(item(&key, &value));
+        }
+        Ok(results)
+    }
+}
+
+impl KvStore for RocksDbStore {
+    fn backend_name(&self) -> &'static str {
+        "rocksdb"
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.check_writable()?;
+        self.db.put(key, value).context("存储数据到 RocksDB 失败")
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.db.get(key).context("从 RocksDB 读取数据失败")
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        self.check_writable()?;
+        self.db.delete(key).context("从 RocksDB 删除数据失败")
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut results = Vec::new();
+        let iter = self.db.iterator(IteratorMode::From(prefix, Direction::Forward));
+
+        for item in iter {
+            let (key, value) = item.context("迭代数据库失败")?;
+            // 检查是否仍然匹配前缀
+            if !key.starts_with(prefix) {
+                break;
+            }
+            results.push((key.to_vec(), value.to_vec()));
+        }
+        Ok(results)
+    }
+
+    fn scan_prefix_keys(&self, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        self.scan_prefix_keys_limit(prefix, usize::MAX)
+    }
+
+    fn scan_prefix_keys_limit(&self, prefix: &[u8], limit: usize) -> Result<Vec<Vec<u8>>> {
+        let mut keys = Vec::new();
+        let iter = self.db.iterator(IteratorMode::From(prefix, Direction::Forward));
+
+        for item in iter {
+            if keys.len() >= limit {
+                break;
+            }
+            let (key, _) = item.context("迭代数据库失败")?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            keys.push(key.to_vec());
+        }
+        Ok(keys)
+    }
+
+    fn scan_prefix_page(
+        &self,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.scan_page(prefix, start_after, limit, |key, value| (key.to_vec(), value.to_vec()))
+    }
+
+    fn scan_prefix_keys_page(&self, prefix: &[u8], start_after: Option<&[u8]>, limit: usize) -> Result<Vec<Vec<u8>>> {
+        self.scan_page(prefix, start_after, limit, |key, _| key.to_vec())
+    }
+
+    fn scan_prefix_keys_rev_limit(&self, prefix: &[u8], limit: usize) -> Result<Vec<Vec<u8>>> {
+        let mut keys = Vec::new();
+        let upper = prefix_upper_bound(prefix);
+        let mode = match &upper {
+            Some(upper) => IteratorMode::From(upper, Direction::Reverse),
+            None => IteratorMode::End,
+        };
+
+        for item in self.db.iterator(mode) {
+            if keys.len() >= limit {
+                break;
+            }
+            let (key, _) = item.context("迭代数据库失败")?;
+            // 逆序定位到不大于上界的最后一个键，上界本身不属于该前缀
+            if upper.as_deref().is_some_and(|upper| &*key >= upper) {
+                continue;
+            }
+            if !key.starts_with(prefix) {
+                break;
+            }
+            keys.push(key.to_vec());
+        }
+        Ok(keys)
+    }
+
+    fn write_batch(&self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<()> {
+        self.check_writable()?;
+        let mut batch = rocksdb::WriteBatch::default();
+        for (key, value) in items {
+            batch.put(key, value);
+        }
+        self.db.write(batch).context("批量写入 RocksDB 失败")
+    }
+
+    fn property_value(&self, name: &str) -> Result<Option<String>> {
+        self.db
+            .property_value(name)
+            .with_context(|| format!("获取数据库属性 {} 失败", name))
+    }
+
+    fn property_int_value(&self, name: &str) -> Result<Option<u64>> {
+        self.db
+            .property_int_value(name)
+            .with_context(|| format!("获取数据库属性 {} 失败", name))
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.db.flush().context("刷写 RocksDB 内存表失败")
+    }
+
+    fn compact(&self) -> Result<()> {
+        self.check_writable()?;
+        self.db.compact_range(Option::<&[u8]>::None, Option::<&[u8]>::None);
+        Ok(())
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn try_catch_up_with_primary(&self) -> Result<()> {
+        if self.secondary {
+            self.db.try_catch_up_with_primary().context("追赶主库写入失败")?;
+        }
+        Ok(())
+    }
+
+    fn create_checkpoint(&self, target: &Path) -> Result<()> {
+        let checkpoint = rocksdb::checkpoint::Checkpoint::new(&self.db).context("初始化 RocksDB 快照失败")?;
+        checkpoint
+            .create_checkpoint(target)
+            .with_context(|| format!("创建 RocksDB 快照失败: {:?}", target))
+    }
+}
+
+/// 写缓冲后端
+///
+/// 写入和删除先记录在内存中，读取和扫描合并缓冲与底层存储的结果，`commit` 时一次批量写入
+/// 底层存储。用于离线导入等批量写入场景：各业务存储仍按原逻辑读改写，同一键的多次写入
+/// 在缓冲中合并，提交时只写最后一次。删除在批量写入之前逐个执行，不在同一个原子批次中。
+#[derive(Debug)]
+pub struct WriteBufferStore {
+    inner: Arc<dyn KvStore>,
+    /// 待提交的写入，值为空表示删除
+    pending: Mutex<BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+impl WriteBufferStore {
+    pub fn new(inner: Arc<dyn KvStore>) -> Self {
+        Self { inner, pending: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// 把缓冲的写入提交到底层存储并清空缓冲，返回提交的键数
+    pub fn commit(&self) -> Result<usize> {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        let count = pending.len();
+        let mut puts = Vec::with_capacity(count);
+        for (key, value) in pending {
+            match value {
+                Some(value) => puts.push((key, value)),
+                None => self.inner.delete(&key)?,
+            }
+        }
+        if !puts.is_empty() {
+            self.inner.write_batch(puts)?;
+        }
+        Ok(count)
+    }
+
+    /// 底层存储的前缀扫描结果叠加缓冲中同前缀的写入和删除，按键升序
+    fn merge(&self, prefix: &[u8], base: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut merged: BTreeMap<Vec<u8>, Vec<u8>> = base.into_iter().collect();
+        let pending = self.pending.lock().unwrap();
+        for (key, value) in pending.range(prefix.to_vec()..).take_while(|(key, _)| key.starts_with(prefix)) {
+            match value {
+                Some(value) => merged.insert(key.clone(), value.clone()),
+                None => merged.remove(key),
+            };
+        }
+        merged.into_iter().collect()
+    }
+}
+
+impl KvStore for WriteBufferStore {
+    fn backend_name(&self) -> &'static str {
+        self.inner.backend_name()
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.pending.lock().unwrap().insert(key.to_vec(), Some(value.to_vec()));
+        Ok(())
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        if let Some(value) = self.pending.lock().unwrap().get(key) {
+            return Ok(value.clone());
+        }
+        self.inner.get(key)
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        self.pending.lock().unwrap().insert(key.to_vec(), None);
+        Ok(())
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        Ok(self.merge(prefix, self.inner.scan_prefix(prefix)?))
+    }
+
+    fn write_batch(&self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<()> {
+        let mut pending = self.pending.lock().unwrap();
+        for (key, value) in items {
+            pending.insert(key, Some(value));
+        }
+        Ok(())
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.inner.is_read_only()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_secondary_reads_primary_and_rejects_writes() {
+        let dir = TempDir::new("kv-store-test");
+        let primary_path = dir.path().join("primary");
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+
+        let primary = RocksDbStore::open(&primary_path, &opts).unwrap();
+        primary.put(b"SIG001a", b"1").unwrap();
+        primary.flush().unwrap();
+
+        let secondary = RocksDbStore::open_secondary(primary_path, dir.path().join("secondary"), &opts).unwrap();
+        secondary.try_catch_up_with_primary().unwrap();
+        assert!(secondary.is_read_only());
+        assert_eq!(secondary.get(b"SIG001a").unwrap(), Some(b"1".to_vec()));
+        assert!(secondary.put(b"SIG001b", b"2").is_err());
+        assert!(secondary.write_batch(vec![(b"SIG001c".to_vec(), b"3".to_vec())]).is_err());
+    }
+
+    #[test]
+    fn test_scan_prefix_keys_limit_stops_early() {
+        let dir = TempDir::new("kv-store-test");
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+
+        let store = RocksDbStore::open(dir.path(), &opts).unwrap();
+        for key in ["SIG0015VERa", "SIG0015VERb", "SIG0015VERc", "SIG0015VES", "SIG0016abc"] {
+            store.put(key.as_bytes(), b"{}").unwrap();
+        }
+
+        let keys = store.scan_prefix_keys_limit(b"SIG0015VER", 2).unwrap();
+        assert_eq!(keys, vec![b"SIG0015VERa".to_vec(), b"SIG0015VERb".to_vec()]);
+        assert_eq!(store.scan_prefix_keys_limit(b"SIG0015VE", 10).unwrap().len(), 4);
+
+        // 分页从上一页最后一个键之后继续
+        let page = store.scan_prefix_page(b"SIG0015VER", Some(b"SIG0015VERa"), 10).unwrap();
+        let keys: Vec<_> = page.into_iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, vec![b"SIG0015VERb".to_vec(), b"SIG0015VERc".to_vec()]);
+        // 逆序扫描不越过前缀的上界（SIG0015VES）
+        let keys = store.scan_prefix_keys_rev_limit(b"SIG0015VER", 2).unwrap();
+        assert_eq!(keys, vec![b"SIG0015VERc".to_vec(), b"SIG0015VERb".to_vec()]);
+
+        // 写缓冲的读取和扫描合并缓冲与底层存储，提交前底层存储不变
+        let store: Arc<dyn KvStore> = Arc::new(store);
+        let buffer = WriteBufferStore::new(store.clone());
+        buffer.put(b"SIG0015VERd", b"{}").unwrap();
+        buffer.delete(b"SIG0015VERa").unwrap();
+        assert_eq!(buffer.get(b"SIG0015VERa").unwrap(), None);
+        assert_eq!(buffer.scan_prefix_keys(b"SIG0015VER").unwrap().len(), 3);
+        assert_eq!(store.scan_prefix_keys(b"SIG0015VER").unwrap().len(), 3);
+        assert_eq!(store.get(b"SIG0015VERd").unwrap(), None);
+        assert_eq!(buffer.commit().unwrap(), 2);
+        let keys = store.scan_prefix_keys(b"SIG0015VER").unwrap();
+        assert_eq!(keys, vec![b"SIG0015VERb".to_vec(), b"SIG0015VERc".to_vec(), b"SIG0015VERd".to_vec()]);
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::storage::StorageManager;
+use crate::database::watchlist_storage::WatchlistStorage;
+
+/// 地址标签键前缀
+pub const LABEL_KEY_PREFIX: &str = "LABL01";
+
+/// 已知实体的地址标签
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressLabel {
+    /// 地址
+    pub address: String,
+    /// 显示名称，如 "Binance Hot Wallet"
+    pub name: String,
+    /// 实体类型，如 exchange / program / bridge
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// 最后更新时间
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// 种子文件中的一行
+#[derive(Debug, Clone, Deserialize)]
+pub struct LabelSeedRow {
+    pub address: String,
+    pub name: String,
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
+/// 地址标签存储管理器
+#[derive(Debug, Clone)]
+pub struct LabelStorage {
+    storage: StorageManager,
+    clock: SharedClock,
+}
+
+impl LabelStorage {
+    /// 创建新的地址标签存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self {
+            storage,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 设置（或覆盖）地址标签
+    pub fn set_label(&self, address: &str, name: &str, kind: Option<String>) -> Result<AddressLabel> {
+        let label = AddressLabel {
+            address: address.to_string(),
+            name: name.to_string(),
+            kind,
+            updated_at: self.clock.timestamp(),
+        };
+        self.storage.put(&self.make_key(address)?, &label)?;
+        Ok(label)
+    }
+
+    /// 获取地址标签
+    pub fn get_label(&self, address: &str) -> Result<Option<AddressLabel>> {
+        self.storage.get(&self.make_key(address)?)
+    }
+
+    /// 删除地址标签，返回标签是否存在
+    pub fn delete_label(&self, address: &str) -> Result<bool> {
+        let key = self.make_key(address)?;
+        if !self.storage.exists(&key)? {
+            return Ok(false);
+        }
+        self.storage.delete(&key)?;
+        Ok(true)
+    }
+
+    /// 批量查找地址的显示名称，没有标签的地址不出现在结果中
+    pub fn resolve_names<'a, I>(&self, addresses: I) -> Result<HashMap<String, String>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut names = HashMap::new();
+        let mut checked = std::collections::HashSet::new();
+        for address in addresses {
+            if address.is_empty() || !checked.insert(address) {
+                continue;
+            }
+            if let Some(label) = self.get_label(address)? {
+                names.insert(label.address, label.name);
+            }
+        }
+        Ok(names)
+    }
+
+    /// 解析 CSV 内容，每行格式为 `address,name[,kind]`，允许可选的表头行
+    pub fn parse_csv(content: &str) -> Vec<LabelSeedRow> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .enumerate()
+            .filter(|(i, line)| !(*i == 0 && line.to_ascii_lowercase().starts_with("address")))
+            .filter_map(|(_, line)| {
+                let mut parts = line.splitn(3, ',').map(|part| part.trim().trim_matches('"').to_string());
+                let address = parts.next()?;
+                let name = parts.next().filter(|name| !name.is_empty())?;
+                let kind = parts.next().filter(|kind| !kind.is_empty());
+                Some(LabelSeedRow { address, name, kind })
+            })
+            .collect()
+    }
+
+    /// 从 JSON（`[{"address", "name", "kind"}]`）或 CSV 文件导入标签
+    ///
+    /// 已存在的标签不会被覆盖，以免重启时冲掉通过接口修改的内容。返回新增数量。
+    pub fn seed_from_file<P: AsRef<Path>>(&self, path: P) -> Result<usize> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).with_context(|| format!("读取标签文件失败: {:?}", path))?;
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let rows = if is_json {
+            serde_json::from_str::<Vec<LabelSeedRow>>(&content).context("标签 JSON 格式无效")?
+        } else {
+            Self::parse_csv(&content)
+        };
+
+        let updated_at = self.clock.timestamp();
+        let mut items = Vec::new();
+        for row in rows {
+            let address = row.address.trim().to_string();
+            if !WatchlistStorage::is_valid_address(&address) {
+                warn!("跳过无效的标签地址: {}", address);
+                continue;
+            }
+            let key = self.make_key(&address)?;
+            if self.storage.exists(&key)? {
+                continue;
+            }
+            items.push((key, AddressLabel { address, name: row.name, kind: row.kind, updated_at }));
+        }
+
+        let added = items.len();
+        if !items.is_empty() {
+            self.storage.batch_put(items)?;
+        }
+        info!("🏷️ 从 {:?} 导入 {} 个地址标签", path, added);
+        Ok(added)
+    }
+
+    fn make_key(&self, address: &str) -> Result<String> {
+        self.storage.make_key(LABEL_KEY_PREFIX, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    const EXCHANGE: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+    const PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    #[test]
+    fn test_seed_keeps_existing_labels() {
+        let dir = TempDir::new("label-storage-test");
+        let storage = LabelStorage::new(StorageManager::new(dir.path().join("db"), 6).unwrap());
+        storage.set_label(EXCHANGE, "Renamed Exchange", Some("exchange".to_string())).unwrap();
+
+        let seed = dir.path().join("labels.csv");
+        std::fs::write(
+            &seed,
+            format!("address,name,kind\n{},Binance,exchange\n{},SPL Token,program\nbad,Nope\n", EXCHANGE, PROGRAM),
+        )
+        .unwrap();
+        assert_eq!(storage.seed_from_file(&seed).unwrap(), 1);
+
+        let names = storage.resolve_names([EXCHANGE, PROGRAM, "unknown"]).unwrap();
+        assert_eq!(names.get(EXCHANGE).map(String::as_str), Some("Renamed Exchange"));
+        assert_eq!(names.get(PROGRAM).map(String::as_str), Some("SPL Token"));
+        assert_eq!(names.len(), 2);
+
+        assert!(storage.delete_label(PROGRAM).unwrap());
+        assert!(storage.get_label(PROGRAM).unwrap().is_none());
+    }
+}
@@ -0,0 +1,249 @@
+use anyhow::Result;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+use crate::database::signature_storage::{SolTransfer, TokenTransfer};
+use crate::database::storage::StorageManager;
+use crate::database::transfer_search::{TransferCategory, TransferHit};
+
+/// 每日最大转账榜键前缀
+pub const LARGEST_TRANSFER_KEY_PREFIX: &str = "LRGT01";
+
+/// 每个榜单每天保留的转账数
+pub const MAX_DAILY_LARGEST_TRANSFERS: usize = 100;
+
+/// 榜单保留天数，更早的榜单由维护任务删除
+pub const LARGEST_TRANSFER_RETENTION_DAYS: i64 = 31;
+
+/// 某一天某个榜单（SOL 或单个代币）的最大转账
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyLargestTransfers {
+    /// 日期（YYYY-MM-DD，UTC）
+    pub date: String,
+    /// 代币mint地址（SOL 榜单为 None）
+    pub mint: Option<String>,
+    /// 转账记录（按金额从大到小）
+    pub transfers: Vec<LargestTransferRecord>,
+}
+
+/// 榜单中的一笔转账
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargestTransferRecord {
+    /// 确定性转账ID（签名:sol序号 / 签名:token序号）
+    pub transfer_id: String,
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+/// 按天滚动的最大转账索引
+///
+/// SOL 转账每天一个榜单；代币金额在不同 mint 之间不可比较，因此每个 mint 每天一个榜单。
+/// 键以日期开头（`日期:sol` / `日期:token:mint`），便于按日期清理。
+#[derive(Debug, Clone)]
+pub struct LargestTransferStorage {
+    storage: StorageManager,
+}
+
+impl LargestTransferStorage {
+    /// 创建新的最大转账索引实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 将一笔交易中的转账加入所在日期的榜单
+    pub fn record_transfers(
+        &self,
+        signature: &str,
+        slot: u64,
+        timestamp: u64,
+        sol_transfers: &[SolTransfer],
+        token_transfers: &[TokenTransfer],
+    ) -> Result<()> {
+        let date = Self::date_of(timestamp);
+        let record = |transfer_id: String, from: &str, to: &str, amount: u64, decimals: u8| LargestTransferRecord {
+            transfer_id,
+            signature: signature.to_string(),
+            slot,
+            timestamp,
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            decimals,
+        };
+
+        let mut boards: BTreeMap<Option<&str>, Vec<LargestTransferRecord>> = BTreeMap::new();
+        for (index, transfer) in sol_transfers.iter().enumerate() {
+            boards.entry(None).or_default().push(record(
+                format!("{}:sol{}", signature, index),
+                &transfer.from,
+                &transfer.to,
+                transfer.amount,
+                9,
+            ));
+        }
+        for (index, transfer) in token_transfers.iter().enumerate() {
+            boards.entry(Some(transfer.mint.as_str())).or_default().push(record(
+                format!("{}:token{}", signature, index),
+                &transfer.from,
+                &transfer.to,
+                transfer.amount,
+                transfer.decimals,
+            ));
+        }
+
+        let mut items = Vec::new();
+        for (mint, records) in boards {
+            let key = self.make_key(&date, mint)?;
+            let mut board = self.storage.get::<DailyLargestTransfers>(&key)?.unwrap_or(DailyLargestTransfers {
+                date: date.clone(),
+                mint: mint.map(str::to_string),
+                transfers: Vec::new(),
+            });
+
+            let floor = match board.transfers.get(MAX_DAILY_LARGEST_TRANSFERS - 1) {
+                Some(last) => last.amount,
+                None => 0,
+            };
+            let mut changed = false;
+            for record in records {
+                if record.amount <= floor
+                    || board.transfers.iter().any(|existing| existing.transfer_id == record.transfer_id)
+                {
+                    continue;
+                }
+                board.transfers.push(record);
+                changed = true;
+            }
+            if !changed {
+                continue;
+            }
+            board.transfers.sort_by_key(|record| std::cmp::Reverse(record.amount));
+            board.transfers.truncate(MAX_DAILY_LARGEST_TRANSFERS);
+            debug!("{} 最大转账榜更新: {:?}", date, mint);
+            items.push((key, board));
+        }
+
+        if !items.is_empty() {
+            self.storage.batch_put(items)?;
+        }
+        Ok(())
+    }
+
+    /// 获取 `since` 之后（包含）的最大转账，按金额从大到小
+    ///
+    /// `mint` 为 None 时返回 SOL 转账，否则返回该代币的转账。
+    pub fn get_largest(&self, mint: Option<&str>, since: i64, until: i64, limit: usize) -> Result<Vec<TransferHit>> {
+        let mut transfers = Vec::new();
+        let mut day = since - since.rem_euclid(86_400);
+        while day <= until {
+            let key = self.make_key(&Self::date_of(day as u64), mint)?;
+            if let Some(board) = self.storage.get::<DailyLargestTransfers>(&key)? {
+                transfers.extend(board.transfers.into_iter().filter(|record| record.timestamp as i64 >= since));
+            }
+            day += 86_400;
+        }
+
+        transfers.sort_by_key(|record| std::cmp::Reverse(record.amount));
+        transfers.truncate(limit);
+        Ok(transfers
+            .into_iter()
+            .map(|record| TransferHit {
+                transfer_id: record.transfer_id,
+                signature: record.signature,
+                slot: record.slot,
+                timestamp: record.timestamp,
+                category: if mint.is_some() { TransferCategory::Token } else { TransferCategory::Sol },
+                from: record.from,
+                to: record.to,
+                amount: record.amount,
+                mint: mint.map(str::to_string),
+                decimals: record.decimals,
+                direction: None,
+            })
+            .collect())
+    }
+
+    /// 删除早于 `cutoff` 所在日期的榜单，返回删除数量
+    pub fn prune_before(&self, cutoff: i64) -> Result<usize> {
+        let cutoff_date = Self::date_of(cutoff.max(0) as u64);
+        let mut deleted = 0;
+        for key in self.storage.get_keys_by_prefix(LARGEST_TRANSFER_KEY_PREFIX)? {
+            // 日期键按字典序即时间顺序排列
+            let date = &key[LARGEST_TRANSFER_KEY_PREFIX.len()..];
+            if date < cutoff_date.as_str() {
+                self.storage.delete(&key)?;
+                deleted += 1;
+            } else {
+                break;
+            }
+        }
+        Ok(deleted)
+    }
+
+    fn make_key(&self, date: &str, mint: Option<&str>) -> Result<String> {
+        let board = match mint {
+            Some(mint) => format!("{}:token:{}", date, mint),
+            None => format!("{}:sol", date),
+        };
+        self.storage.make_key(LARGEST_TRANSFER_KEY_PREFIX, &board)
+    }
+
+    fn date_of(timestamp: u64) -> String {
+        DateTime::from_timestamp(timestamp as i64, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    const DAY: u64 = 86_400;
+
+    fn sol(amount: u64) -> SolTransfer {
+        SolTransfer {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        }
+    }
+
+    #[test]
+    fn test_largest_transfers_ranked_and_pruned_by_day() {
+        let dir = TempDir::new("largest-transfer-test");
+        let storage = LargestTransferStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        // 2024-01-01 和 2024-01-02 各有转账
+        let day1 = 1_704_067_200;
+        storage.record_transfers("sig1", 1, day1 + 10, &[sol(5), sol(50)], &[]).unwrap();
+        storage.record_transfers("sig2", 2, day1 + DAY + 10, &[sol(20)], &[]).unwrap();
+        // 重复处理不会重复计入
+        storage.record_transfers("sig2", 2, day1 + DAY + 10, &[sol(20)], &[]).unwrap();
+
+        let now = (day1 + DAY + 100) as i64;
+        let all = storage.get_largest(None, now - 2 * DAY as i64, now, 10).unwrap();
+        let amounts: Vec<u64> = all.iter().map(|hit| hit.amount).collect();
+        assert_eq!(amounts, vec![50, 20, 5]);
+        assert_eq!(all[0].transfer_id, "sig1:sol1");
+
+        let last_hour = storage.get_largest(None, now - 3_600, now, 10).unwrap();
+        assert_eq!(last_hour.len(), 1);
+        assert!(storage.get_largest(Some("USDC"), now - 2 * DAY as i64, now, 10).unwrap().is_empty());
+
+        assert_eq!(storage.prune_before(now).unwrap(), 1);
+        assert_eq!(storage.get_largest(None, now - 2 * DAY as i64, now, 10).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,263 @@
+use anyhow::{Context, Result};
+use cron::Schedule;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{error, info};
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::config::MaintenanceConfig;
+use crate::database::largest_transfer_storage::LARGEST_TRANSFER_RETENTION_DAYS;
+use crate::database::DatabaseManager;
+
+/// 单次维护的执行结果
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceReport {
+    /// 汇总进每日地址汇总并删除的地址记录数
+    pub rolled_up_records: usize,
+    /// 清理的过期地址记录数
+    pub pruned_records: usize,
+    /// 删除的过期签名数
+    pub deleted_signatures: usize,
+    /// 删除的过期最大转账榜数
+    pub pruned_largest_transfer_boards: usize,
+    /// 是否执行了压缩
+    pub compacted: bool,
+}
+
+/// 数据库后台维护调度器
+///
+/// 按 `[database.maintenance]` 中的 cron 表达式定期执行：
+/// 把旧地址记录汇总为每日地址汇总、清理过期地址记录、删除超过保留期的签名数据和最大转账榜、压缩数据库。
+#[derive(Debug, Clone)]
+pub struct MaintenanceScheduler {
+    db_manager: DatabaseManager,
+    config: MaintenanceConfig,
+    schedule: Schedule,
+    clock: SharedClock,
+}
+
+impl MaintenanceScheduler {
+    /// 创建维护调度器
+    pub fn new(db_manager: DatabaseManager, config: MaintenanceConfig) -> Result<Self> {
+        let schedule = Schedule::from_str(&config.schedule)
+            .map_err(|e| anyhow::anyhow!("{}", e))
+            .with_context(|| format!("无效的维护计划 cron 表达式: {}", config.schedule))?;
+
+        Ok(Self {
+            db_manager,
+            config,
+            schedule,
+            clock: SystemClock::shared(),
+        })
+    }
+
+    /// 替换时钟（用于测试）
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 距离下一次执行的等待时间
+    pub fn next_delay(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        let next = self.schedule.after(&now).next()?;
+        (next - now).to_std().ok()
+    }
+
+    /// 立即执行一次维护（阻塞调用）
+    pub fn run_once(&self) -> Result<MaintenanceReport> {
+        let mut report = MaintenanceReport::default();
+
+        // 先汇总再清理，汇总期限早于保留期限，记录不会在汇总前被删除
+        if let Some(days) = self.config.rollup_after_days {
+            let cutoff = (self.clock.timestamp() - (days * 24 * 60 * 60) as i64).max(0) as u64;
+            report.rolled_up_records = self
+                .db_manager
+                .address_storage()
+                .roll_up_records_before(cutoff, self.db_manager.address_rollup_storage())?;
+        }
+
+        if self.config.prune_expired_records {
+            report.pruned_records = self.db_manager.address_storage().prune_expired_records()?;
+        }
+
+        if let Some(days) = self.config.signature_retention_days {
+            let cutoff = self.clock.timestamp() - (days * 24 * 60 * 60) as i64;
+            report.deleted_signatures = self.db_manager.delete_signatures_before(cutoff)?;
+        }
+
+        let largest_cutoff = self.clock.timestamp() - LARGEST_TRANSFER_RETENTION_DAYS * 24 * 60 * 60;
+        report.pruned_largest_transfer_boards =
+            self.db_manager.largest_transfer_storage().prune_before(largest_cutoff)?;
+
+        if self.config.compact {
+            self.db_manager.compact_database()?;
+            report.compacted = true;
+        }
+
+        Ok(report)
+    }
+
+    /// 按计划循环执行维护任务
+    pub async fn start(self) {
+        info!("🛠️ 数据库维护任务已启动，执行计划: {}", self.config.schedule);
+
+        while let Some(delay) = self.next_delay() {
+            tokio::time::sleep(delay).await;
+
+            let scheduler = self.clone();
+            match tokio::task::spawn_blocking(move || scheduler.run_once()).await {
+                Ok(Ok(report)) => info!(
+                    "🛠️ 数据库维护完成: 汇总 {} 条地址记录, 清理 {} 条过期记录, 删除 {} 个过期签名, 删除 {} 个过期转账榜, 压缩: {}",
+                    report.rolled_up_records,
+                    report.pruned_records,
+                    report.deleted_signatures,
+                    report.pruned_largest_transfer_boards,
+                    report.compacted
+                ),
+                Ok(Err(e)) => error!("❌ 数据库维护失败: {}", e),
+                Err(e) => error!("❌ 数据库维护任务异常退出: {}", e),
+            }
+        }
+
+        info!("🛠️ 维护计划没有后续执行时间，维护任务退出");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::database::{RecordType, SignatureTransactionData};
+    use crate::transfer_types::SolTransfer;
+    use std::sync::Arc;
+    use crate::test_util::TempDir;
+
+    fn temp_manager(dir: &TempDir) -> DatabaseManager {
+        DatabaseManager::new(dir.path().to_str().unwrap(), 6, "SIG001".to_string(), "ADDR01".to_string(), 10)
+            .expect("打开临时数据库失败")
+    }
+
+    fn config(schedule: &str) -> MaintenanceConfig {
+        MaintenanceConfig {
+            schedule: schedule.to_string(),
+            ..MaintenanceConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_invalid_schedule_rejected() {
+        let dir = TempDir::new("maintenance-test");
+        assert!(MaintenanceScheduler::new(temp_manager(&dir), config("not a cron")).is_err());
+    }
+
+    #[test]
+    fn test_next_delay_follows_schedule() {
+        // 2023-11-14 22:13:20 UTC，下一个整点为 23:00:00
+        let clock = MockClock::new(1_700_000_000);
+        let dir = TempDir::new("maintenance-test");
+        let scheduler = MaintenanceScheduler::new(temp_manager(&dir), config("0 0 * * * *"))
+            .unwrap()
+            .with_clock(Arc::new(clock));
+        assert_eq!(scheduler.next_delay(), Some(Duration::from_secs(46 * 60 + 40)));
+    }
+
+    #[test]
+    fn test_run_once_deletes_expired_signatures() {
+        let day = 24 * 60 * 60;
+        let dir = TempDir::new("maintenance-test");
+        let manager = temp_manager(&dir);
+        let signatures = manager.signature_storage();
+        let mut old = SignatureTransactionData::new("old".to_string(), 10 * day, 1, true);
+        old.add_sol_transfer(crate::database::signature_storage::SolTransfer {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: 5,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        });
+        signatures.store_signature_data("old", &old).unwrap();
+        let transfer = SolTransfer {
+            signature: "old".to_string(),
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: 5,
+            from_index: 0,
+            to_index: 1,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        };
+        manager
+            .address_storage()
+            .batch_process_transaction("old", (10 * day) as u64, 1, &[transfer], &[])
+            .unwrap();
+        signatures
+            .store_signature_data("new", &SignatureTransactionData::new("new".to_string(), 95 * day, 2, true))
+            .unwrap();
+        // 清理按时间索引从最旧的一端查找过期签名
+        let index = manager.signature_time_index_storage();
+        index.record("old", 1, 10 * day).unwrap();
+        index.record("new", 2, 95 * day).unwrap();
+
+        let mut maintenance = config("0 0 * * * *");
+        maintenance.signature_retention_days = Some(30);
+        let scheduler = MaintenanceScheduler::new(manager.clone(), maintenance)
+            .unwrap()
+            .with_clock(Arc::new(MockClock::new(100 * day)));
+
+        let report = scheduler.run_once().unwrap();
+        assert_eq!(report.deleted_signatures, 1);
+        assert!(!signatures.signature_exists("old").unwrap());
+        assert!(signatures.signature_exists("new").unwrap());
+        // 过期交易的地址记录随签名一起删除
+        assert!(manager.address_storage().get_address_records("alice").unwrap().is_none());
+        assert!(manager.address_storage().get_address_records("bob").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_run_once_rolls_up_old_address_records() {
+        let day = 24 * 60 * 60;
+        let dir = TempDir::new("maintenance-test");
+        let manager = temp_manager(&dir);
+        let addresses = manager.address_storage();
+        let transfer = |amount| SolTransfer {
+            signature: "sig".to_string(),
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount,
+            from_index: 0,
+            to_index: 1,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        };
+        addresses.add_sol_transfer("alice", "old1", 10 * day, 1, 0, transfer(5), RecordType::Sender).unwrap();
+        addresses.add_sol_transfer("alice", "old2", 10 * day + 60, 2, 0, transfer(7), RecordType::Sender).unwrap();
+        addresses.add_sol_transfer("alice", "new", 95 * day, 3, 0, transfer(1), RecordType::Sender).unwrap();
+        addresses.add_sol_transfer("bob", "old1", 10 * day, 1, 0, transfer(5), RecordType::Receiver).unwrap();
+
+        let mut maintenance = config("0 0 * * * *");
+        maintenance.rollup_after_days = Some(30);
+        let scheduler = MaintenanceScheduler::new(manager.clone(), maintenance)
+            .unwrap()
+            .with_clock(Arc::new(MockClock::new((100 * day) as i64)));
+
+        assert_eq!(scheduler.run_once().unwrap().rolled_up_records, 3);
+        assert_eq!(addresses.get_recent_records("alice", 10).unwrap().len(), 1);
+        assert_eq!(addresses.get_address_stats("alice").unwrap().total_sol_sent, 1);
+        assert!(addresses.get_address_records("bob").unwrap().is_none());
+
+        let rollups = manager.address_rollup_storage().get_rollups("alice", None, None).unwrap();
+        assert_eq!(rollups.len(), 1);
+        assert_eq!((rollups[0].sol_sent_count, rollups[0].total_sol_sent), (2, 12));
+
+        // 再次执行不会重复汇总
+        assert_eq!(scheduler.run_once().unwrap().rolled_up_records, 0);
+        let rollups = manager.address_rollup_storage().get_rollups("alice", None, None).unwrap();
+        assert_eq!(rollups[0].total_records, 2);
+    }
+}
@@ -0,0 +1,188 @@
+//! 启动时的数据格式迁移
+//!
+//! 数据库记录当前的格式版本，启动时按顺序执行尚未应用的迁移，每个迁移完成后立即更新版本号，
+//! 中途失败时下次启动从失败的迁移重新开始。迁移必须可以重复执行。
+//!
+//! 格式版本按租户命名空间分别记录。启动时除了本进程使用的命名空间，还会迁移
+//! `api.tenants.api_keys` 中配置的租户，这样 API 通过 `for_tenant` 打开的数据也是最新格式。
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use tracing::info;
+
+use crate::database::signature_storage::SignatureTransactionData;
+use crate::database::DatabaseManager;
+
+/// 格式版本键前缀
+pub const SCHEMA_VERSION_KEY_PREFIX: &str = "SCHEMA";
+
+/// 每批写入的记录数
+const MIGRATION_BATCH_SIZE: usize = 1_000;
+
+/// 每处理多少条记录输出一次进度
+const PROGRESS_LOG_INTERVAL: usize = 10_000;
+
+/// 一个格式迁移
+pub struct Migration {
+    /// 迁移完成后的版本号
+    pub version: u32,
+    /// 说明
+    pub description: &'static str,
+    /// 执行迁移，返回需要改写（预演时）或已改写的记录数
+    run: fn(&DatabaseManager, bool) -> Result<usize>,
+}
+
+/// 按版本号排列的全部迁移
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "按当前格式重写签名数据，补齐后来新增的字段",
+    run: rewrite_signature_data,
+}];
+
+/// 程序支持的最新格式版本
+pub fn latest_schema_version() -> u32 {
+    MIGRATIONS.last().map(|migration| migration.version).unwrap_or(0)
+}
+
+/// 迁移结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// 迁移前的版本
+    pub from_version: u32,
+    /// 迁移后的版本（预演时不变）
+    pub to_version: u32,
+    /// 执行或预演的迁移：版本号与影响的记录数
+    pub applied: Vec<(u32, usize)>,
+}
+
+/// 数据库当前的格式版本，没有记录时为 0
+pub fn schema_version(db: &DatabaseManager) -> Result<u32> {
+    let key = db.storage().make_key(SCHEMA_VERSION_KEY_PREFIX, "version")?;
+    Ok(db.storage().get::<u32>(&key)?.unwrap_or(0))
+}
+
+/// 执行尚未应用的迁移；`dry_run` 时只统计需要改写的记录，不写入数据也不更新版本
+pub fn run_migrations(db: &DatabaseManager, dry_run: bool) -> Result<MigrationReport> {
+    let from_version = schema_version(db)?;
+    let latest = latest_schema_version();
+    if from_version > latest {
+        return Err(anyhow::anyhow!(
+            "数据库格式版本 {} 高于程序支持的版本 {}，请升级程序",
+            from_version,
+            latest
+        ));
+    }
+
+    let mut report = MigrationReport { from_version, to_version: from_version, applied: Vec::new() };
+    let key = db.storage().make_key(SCHEMA_VERSION_KEY_PREFIX, "version")?;
+    for migration in MIGRATIONS.iter().filter(|migration| migration.version > from_version) {
+        let mode = if dry_run { "预演" } else { "执行" };
+        info!("🧬 {}数据迁移 v{}: {}", mode, migration.version, migration.description);
+        let affected = (migration.run)(db, dry_run)?;
+        info!("🧬 数据迁移 v{} 完成: {} 条记录{}", migration.version, affected, if dry_run { "需要改写" } else { "已改写" });
+        if !dry_run {
+            db.storage().put(&key, &migration.version)?;
+            report.to_version = migration.version;
+        }
+        report.applied.push((migration.version, affected));
+    }
+    Ok(report)
+}
+
+/// 对其他租户的命名空间执行迁移，跳过本进程自己的命名空间，返回各租户的结果
+pub fn run_tenant_migrations<'a>(
+    db: &DatabaseManager,
+    tenants: impl IntoIterator<Item = &'a str>,
+    dry_run: bool,
+) -> Result<Vec<(String, MigrationReport)>> {
+    let own = db.storage().tenant();
+    let tenants: BTreeSet<&str> = tenants.into_iter().filter(|tenant| Some(*tenant) != own).collect();
+    let mut reports = Vec::with_capacity(tenants.len());
+    for tenant in tenants {
+        info!("🧬 检查租户 {} 的数据格式", tenant);
+        reports.push((tenant.to_string(), run_migrations(&db.for_tenant(tenant)?, dry_run)?));
+    }
+    Ok(reports)
+}
+
+/// v1：旧记录缺少后来新增的字段，按当前结构重新序列化后写回
+///
+/// 按键分批读取，每批检查完立即写回，内存占用与数据量无关。
+fn rewrite_signature_data(db: &DatabaseManager, dry_run: bool) -> Result<usize> {
+    let mut checked = 0;
+    let mut rewritten = 0;
+    db.storage().for_each_chunk_by_prefix::<serde_json::Value>(
+        db.signature_storage().signature_prefix(),
+        MIGRATION_BATCH_SIZE,
+        |records| {
+            let mut batch = Vec::new();
+            for record in records {
+                checked += 1;
+                let data: SignatureTransactionData = serde_json::from_value(record.value.clone())?;
+                let current = serde_json::to_value(&data)?;
+                if current != record.value {
+                    batch.push((record.key, current));
+                }
+            }
+            rewritten += batch.len();
+            if !dry_run && !batch.is_empty() {
+                db.storage().batch_put(batch)?;
+            }
+            if checked % PROGRESS_LOG_INTERVAL < MIGRATION_BATCH_SIZE {
+                info!("🧬 已检查 {} 条签名数据，{} 条需要改写", checked, rewritten);
+            }
+            Ok(())
+        },
+    )?;
+    Ok(rewritten)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_run_migrations_dry_run_and_apply() {
+        let dir = TempDir::new("migrations-test");
+        let db = DatabaseManager::new(dir.path().to_str().unwrap(), 6, "SIG001".to_string(), "ADDR01".to_string(), 10).unwrap();
+
+        // 旧格式：缺少手续费等后来新增的字段
+        let old = serde_json::json!({
+            "signature": "sigOld",
+            "sol_transfers": [],
+            "token_transfers": [],
+            "extracted_addresses": { "all_addresses": [] },
+            "timestamp": 1_704_067_200,
+            "slot": 100,
+            "is_successful": true
+        });
+        let key = db.storage().make_key("SIG001", "sigOld").unwrap();
+        db.storage().put(&key, &old).unwrap();
+        let current = SignatureTransactionData::new("sigNew".to_string(), 1_704_067_300, 101, true);
+        db.signature_storage().store_signature_data("sigNew", &current).unwrap();
+
+        let report = run_migrations(&db, true).unwrap();
+        assert_eq!(report.applied, vec![(1, 1)]);
+        assert_eq!(schema_version(&db).unwrap(), 0);
+        assert_eq!(db.storage().get::<serde_json::Value>(&key).unwrap(), Some(old));
+
+        let report = run_migrations(&db, false).unwrap();
+        assert_eq!((report.from_version, report.to_version), (0, latest_schema_version()));
+        assert_eq!(schema_version(&db).unwrap(), latest_schema_version());
+        let upgraded = db.storage().get::<serde_json::Value>(&key).unwrap().unwrap();
+        assert_eq!(upgraded["fee_lamports"], 0);
+
+        // 已是最新版本时不再执行
+        assert!(run_migrations(&db, false).unwrap().applied.is_empty());
+
+        // 其他租户的命名空间单独记录版本并迁移
+        let tenant = db.for_tenant("acme").unwrap();
+        tenant.storage().put(&key, &old).unwrap();
+        let reports = run_tenant_migrations(&db, ["acme", "acme"], false).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].1.applied, vec![(1, 1)]);
+        assert_eq!(schema_version(&tenant).unwrap(), latest_schema_version());
+        assert_eq!(tenant.storage().get::<serde_json::Value>(&key).unwrap().unwrap()["fee_lamports"], 0);
+    }
+}
@@ -0,0 +1,201 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use tracing::debug;
+
+use crate::database::signature_storage::TokenTransfer;
+use crate::database::storage::StorageManager;
+
+/// 代币统计键前缀
+pub const MINT_STATS_KEY_PREFIX: &str = "MNST01";
+
+/// 代币统计键（前缀之后的部分），后接 mint
+const STATS_KEY: &str = "stats:";
+
+/// 发送方、接收方标记键（前缀之后的部分），后接 `mint:地址`
+const SENDER_KEY: &str = "from:";
+const RECEIVER_KEY: &str = "to:";
+
+/// 某个代币的累计统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MintStats {
+    /// 代币mint地址
+    pub mint: String,
+    /// 代币精度
+    pub decimals: u8,
+    /// 转账笔数
+    pub transfer_count: u64,
+    /// 不同发送方数量
+    pub unique_senders: u64,
+    /// 不同接收方数量
+    pub unique_receivers: u64,
+    /// 累计转账量（原始单位）
+    pub total_volume: u128,
+    /// 首次转账时间
+    pub first_seen: u64,
+    /// 最近转账时间
+    pub last_seen: u64,
+}
+
+/// 代币排行依据
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MintRanking {
+    /// 转账笔数
+    Transfers,
+    /// 不同发送方与接收方数量之和
+    Participants,
+}
+
+impl MintRanking {
+    /// 解析 `transfers` / `participants`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "transfers" => Some(Self::Transfers),
+            "participants" => Some(Self::Participants),
+            _ => None,
+        }
+    }
+}
+
+/// 按代币汇总的转账统计
+///
+/// 摄取时累加计数。不同发送方、接收方通过每个 (mint, 地址) 一个标记键精确去重，
+/// 不需要在统计记录中保存地址集合。不同代币的转账量单位不同，因此排行不支持按转账量。
+#[derive(Debug, Clone)]
+pub struct MintStatsStorage {
+    storage: StorageManager,
+}
+
+impl MintStatsStorage {
+    /// 创建新的代币统计实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 将一笔交易中的代币转账计入统计
+    pub fn record_transfers(&self, timestamp: u64, transfers: &[TokenTransfer]) -> Result<()> {
+        let mut by_mint: BTreeMap<&str, Vec<&TokenTransfer>> = BTreeMap::new();
+        for transfer in transfers {
+            by_mint.entry(transfer.mint.as_str()).or_default().push(transfer);
+        }
+
+        let mut stats_items = Vec::with_capacity(by_mint.len());
+        let mut marker_items = Vec::new();
+        for (mint, transfers) in by_mint {
+            let key = self.stats_key(mint)?;
+            let mut stats = self.storage.get::<MintStats>(&key)?.unwrap_or(MintStats {
+                mint: mint.to_string(),
+                first_seen: timestamp,
+                ..Default::default()
+            });
+
+            let mut seen = HashSet::new();
+            for transfer in transfers {
+                stats.decimals = transfer.decimals;
+                stats.transfer_count += 1;
+                stats.total_volume += transfer.amount as u128;
+                for (role, address, counter) in [
+                    (SENDER_KEY, transfer.sender_wallet(), &mut stats.unique_senders),
+                    (RECEIVER_KEY, transfer.receiver_wallet(), &mut stats.unique_receivers),
+                ] {
+                    let marker = self.marker_key(role, mint, address)?;
+                    if seen.insert(marker.clone()) && !self.storage.exists(&marker)? {
+                        *counter += 1;
+                        marker_items.push((marker, true));
+                    }
+                }
+            }
+            stats.first_seen = stats.first_seen.min(timestamp);
+            stats.last_seen = stats.last_seen.max(timestamp);
+            debug!("代币 {} 统计更新: {} 笔转账", mint, stats.transfer_count);
+            stats_items.push((key, stats));
+        }
+
+        if !marker_items.is_empty() {
+            self.storage.batch_put(marker_items)?;
+        }
+        if !stats_items.is_empty() {
+            self.storage.batch_put(stats_items)?;
+        }
+        Ok(())
+    }
+
+    /// 获取代币统计
+    pub fn get_stats(&self, mint: &str) -> Result<Option<MintStats>> {
+        self.storage.get(&self.stats_key(mint)?)
+    }
+
+    /// 按指定依据获取最活跃的代币
+    pub fn get_top(&self, ranking: MintRanking, limit: usize) -> Result<Vec<MintStats>> {
+        let prefix = self.stats_key("")?;
+        let mut all: Vec<MintStats> = self
+            .storage
+            .get_by_prefix::<MintStats>(&prefix)?
+            .into_iter()
+            .map(|item| item.value)
+            .collect();
+        match ranking {
+            MintRanking::Transfers => all.sort_by_key(|stats| std::cmp::Reverse(stats.transfer_count)),
+            MintRanking::Participants => {
+                all.sort_by_key(|stats| std::cmp::Reverse(stats.unique_senders + stats.unique_receivers))
+            }
+        }
+        all.truncate(limit);
+        Ok(all)
+    }
+
+    fn stats_key(&self, mint: &str) -> Result<String> {
+        self.storage.make_key(MINT_STATS_KEY_PREFIX, &format!("{}{}", STATS_KEY, mint))
+    }
+
+    fn marker_key(&self, role: &str, mint: &str, address: &str) -> Result<String> {
+        self.storage.make_key(MINT_STATS_KEY_PREFIX, &format!("{}{}:{}", role, mint, address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn transfer(mint: &str, from: &str, to: &str, amount: u64) -> TokenTransfer {
+        TokenTransfer {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            decimals: 6,
+            mint: mint.to_string(),
+            program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            transfer_type: "Token Transfer".to_string(),
+            from_owner: None,
+            to_owner: None,
+        }
+    }
+
+    #[test]
+    fn test_unique_participants_and_ranking() {
+        let dir = TempDir::new("mint-stats-test");
+        let storage = MintStatsStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        storage
+            .record_transfers(100, &[transfer("USDC", "alice", "bob", u64::MAX), transfer("USDC", "alice", "carol", 1)])
+            .unwrap();
+        storage.record_transfers(200, &[transfer("USDC", "bob", "alice", 5)]).unwrap();
+        storage
+            .record_transfers(150, &[transfer("BONK", "dave", "erin", 7), transfer("BONK", "erin", "dave", 7)])
+            .unwrap();
+
+        let usdc = storage.get_stats("USDC").unwrap().unwrap();
+        assert_eq!(usdc.transfer_count, 3);
+        assert_eq!((usdc.unique_senders, usdc.unique_receivers), (2, 3));
+        assert_eq!(usdc.total_volume, u64::MAX as u128 + 6);
+        assert_eq!((usdc.first_seen, usdc.last_seen), (100, 200));
+        assert!(storage.get_stats("unknown").unwrap().is_none());
+
+        let by_transfers: Vec<String> =
+            storage.get_top(MintRanking::Transfers, 10).unwrap().into_iter().map(|stats| stats.mint).collect();
+        assert_eq!(by_transfers, vec!["USDC", "BONK"]);
+        let by_participants = storage.get_top(MintRanking::Participants, 1).unwrap();
+        assert_eq!(by_participants[0].mint, "USDC");
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+use crate::database::signature_storage::TokenTransfer;
+use crate::database::storage::StorageManager;
+
+/// 代币转账索引键前缀
+pub const MINT_TRANSFER_KEY_PREFIX: &str = "MNTX01";
+
+/// 某个代币 mint 的转账列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintTransferList {
+    /// 代币mint地址
+    pub mint: String,
+    /// 转账记录（索引0是最新的）
+    pub transfers: Vec<MintTransferRecord>,
+}
+
+/// 代币转账索引中的一条记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintTransferRecord {
+    /// 确定性转账ID（签名:token序号）
+    pub transfer_id: String,
+    /// 交易签名
+    pub signature: String,
+    /// 交易槽位
+    pub slot: u64,
+    /// 交易时间戳
+    pub timestamp: u64,
+    /// 发送方地址
+    pub from: String,
+    /// 接收方地址
+    pub to: String,
+    /// 转账金额
+    pub amount: u64,
+    /// 代币精度
+    pub decimals: u8,
+}
+
+/// 代币 mint → 转账记录的二级索引
+///
+/// 与地址记录一样每个 mint 只保留最近的 N 条，用于按 mint 搜索转账。
+#[derive(Debug, Clone)]
+pub struct MintTransferStorage {
+    storage: StorageManager,
+    max_transfers: usize,
+}
+
+impl MintTransferStorage {
+    /// 创建新的代币转账索引实例
+    pub fn new(storage: StorageManager, max_transfers: usize) -> Self {
+        Self { storage, max_transfers }
+    }
+
+    /// 记录一笔交易中的代币转账，同一 mint 的多条转账合并为一次写入
+    pub fn record_transfers(
+        &self,
+        signature: &str,
+        slot: u64,
+        timestamp: u64,
+        transfers: &[TokenTransfer],
+    ) -> Result<()> {
+        let mut by_mint: BTreeMap<&str, Vec<MintTransferRecord>> = BTreeMap::new();
+        for (index, transfer) in transfers.iter().enumerate() {
+            by_mint.entry(transfer.mint.as_str()).or_default().push(MintTransferRecord {
+                transfer_id: format!("{}:token{}", signature, index),
+                signature: signature.to_string(),
+                slot,
+                timestamp,
+                from: transfer.from.clone(),
+                to: transfer.to.clone(),
+                amount: transfer.amount,
+                decimals: transfer.decimals,
+            });
+        }
+
+        let mut items = Vec::with_capacity(by_mint.len());
+        for (mint, records) in by_mint {
+            let key = self.make_key(mint)?;
+            let mut list = self.storage.get::<MintTransferList>(&key)?.unwrap_or(MintTransferList {
+                mint: mint.to_string(),
+                transfers: Vec::new(),
+            });
+
+            // 保持 records 在交易内的原始顺序，插入到列表最前面
+            for record in records.into_iter().rev() {
+                if list.transfers.iter().any(|existing| existing.transfer_id == record.transfer_id) {
+                    continue;
+                }
+                list.transfers.insert(0, record);
+            }
+            list.transfers.truncate(self.max_transfers);
+            debug!("代币 {} 转账索引更新，当前 {} 条", mint, list.transfers.len());
+            items.push((key, list));
+        }
+
+        if !items.is_empty() {
+            self.storage.batch_put(items)?;
+        }
+        Ok(())
+    }
+
+    /// 获取代币的转账记录（最新的在前）
+    pub fn get_transfers(&self, mint: &str) -> Result<Vec<MintTransferRecord>> {
+        Ok(self
+            .storage
+            .get::<MintTransferList>(&self.make_key(mint)?)?
+            .map(|list| list.transfers)
+            .unwrap_or_default())
+    }
+
+    fn make_key(&self, mint: &str) -> Result<String> {
+        self.storage.make_key(MINT_TRANSFER_KEY_PREFIX, mint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn token(mint: &str, amount: u64) -> TokenTransfer {
+        TokenTransfer {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount,
+            decimals: 6,
+            mint: mint.to_string(),
+            program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            transfer_type: "Token Transfer".to_string(),
+            from_owner: None,
+            to_owner: None,
+        }
+    }
+
+    #[test]
+    fn test_transfers_grouped_by_mint() {
+        let dir = TempDir::new("mint-transfer-test");
+        let storage = MintTransferStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 2);
+
+        storage.record_transfers("sig1", 1, 10, &[token("USDC", 1), token("BONK", 2), token("USDC", 3)]).unwrap();
+        // 重复处理同一交易不会重复记录
+        storage.record_transfers("sig1", 1, 10, &[token("USDC", 1), token("BONK", 2), token("USDC", 3)]).unwrap();
+
+        let usdc = storage.get_transfers("USDC").unwrap();
+        let ids: Vec<&str> = usdc.iter().map(|record| record.transfer_id.as_str()).collect();
+        assert_eq!(ids, vec!["sig1:token0", "sig1:token2"]);
+
+        storage.record_transfers("sig2", 2, 20, &[token("USDC", 4)]).unwrap();
+        let usdc = storage.get_transfers("USDC").unwrap();
+        assert_eq!(usdc.len(), 2);
+        assert_eq!(usdc[0].signature, "sig2");
+        assert_eq!(storage.get_transfers("BONK").unwrap().len(), 1);
+    }
+}
@@ -1,18 +1,128 @@
 pub mod storage;
+pub mod kv_store;
+#[cfg(feature = "postgres")]
+pub mod postgres_store;
 pub mod signature_storage;
 pub mod address_storage;
+pub mod maintenance;
+pub mod watchlist_storage;
+pub mod slot_summary_storage;
+pub mod balance_storage;
+pub mod token_balance_storage;
+pub mod fee_stats_storage;
+pub mod counterparty_storage;
+pub mod block_storage;
+pub mod slot_index_storage;
+pub mod stake_event_storage;
+pub mod swap_storage;
+pub mod stats_storage;
+pub mod mint_transfer_storage;
+pub mod transfer_search;
+pub mod label_storage;
+pub mod largest_transfer_storage;
+pub mod slot_finality_storage;
+pub mod dead_letter_storage;
+pub mod mint_stats_storage;
+pub mod activity_storage;
+pub mod ingestion_log_storage;
+pub mod active_address_storage;
+pub mod raw_transaction_storage;
+pub mod address_tag_storage;
+pub mod alert_storage;
+pub mod webhook_storage;
+pub mod account_update_storage;
+pub mod deposit_storage;
+pub mod withdrawal_storage;
+pub mod program_index_storage;
+pub mod pair_transfer_storage;
+pub mod address_rollup_storage;
+pub mod signature_time_index_storage;
+pub mod amount_histogram_storage;
+pub mod nft_transfer_storage;
+pub mod fee_payer_storage;
+pub mod flow_trace;
+pub mod migrations;
+pub mod bloom_filter;
+pub mod encryption;
+pub mod query_cache;
 
-use anyhow::Result;
-pub use storage::{StorageManager, StorageResult};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::info;
+use crate::clock::SharedClock;
+use crate::config::{Config, LifecycleWebhookConfig, QueryCacheConfig, RulesConfig, StorageMode};
+use crate::live_feed::TransferFeed;
+use crate::price_oracle::PriceOracle;
+use crate::rules::RulesEngine;
+
+/// 按键分批扫描签名数据或时间索引时每批的条数
+const SIGNATURE_SCAN_CHUNK_SIZE: usize = 1_000;
+pub use storage::{StorageManager, StorageResult, DbProperties};
+pub use kv_store::{KvStore, RocksDbStore, WriteBufferStore};
 pub use signature_storage::{
     SignatureStorage, SignatureTransactionData, SolTransfer, TokenTransfer,
-    ExtractedAddresses,
+    ExtractedAddresses, UsdValuation,
 };
 pub use address_storage::{
     AddressStorage, AddressTransactionRecord, AddressTransactionList, 
-    RecordType, AddressStats,
+    RecordType, RecordDirection, AddressStats,
+};
+pub use maintenance::{MaintenanceScheduler, MaintenanceReport};
+pub use watchlist_storage::{
+    WatchlistStorage, WatchlistEntry, WatchlistImportRow, WatchlistImportSummary,
+    WatchlistRejectedRow,
+};
+pub use slot_summary_storage::{SlotSummaryStorage, SlotSummary};
+pub use balance_storage::{BalanceStorage, BalanceHistory, BalancePoint};
+pub use token_balance_storage::{TokenBalanceStorage, TokenBalanceHistory, TokenBalancePoint};
+pub use fee_stats_storage::{FeeStatsStorage, DailyFeeStats};
+pub use counterparty_storage::{CounterpartyStorage, Counterparty, GraphNode, GraphEdge, TransferGraph};
+pub use block_storage::{BlockStorage, BlockMetadata};
+pub use slot_index_storage::{SlotIndexStorage, SlotTransactionEntry};
+pub use stake_event_storage::{StakeEventStorage, StakeEventList, StakeSlotEvent};
+pub use swap_storage::{SwapStorage, SwapList, SwapSlotRecord};
+pub use stats_storage::{StatsStorage, StatsTotals, DailyTransferStats};
+pub use mint_transfer_storage::{MintTransferStorage, MintTransferList, MintTransferRecord};
+pub use label_storage::{LabelStorage, AddressLabel, LabelSeedRow};
+pub use largest_transfer_storage::{LargestTransferStorage, DailyLargestTransfers, LargestTransferRecord};
+pub use slot_finality_storage::{SlotFinalityStorage, FinalityStatus, FinalityView, OrphanedSlot};
+pub use dead_letter_storage::{DeadLetterStorage, DeadLetter};
+pub use mint_stats_storage::{MintStatsStorage, MintStats, MintRanking};
+pub use activity_storage::{ActivityStorage, ActivityBucket, ActivityGranularity};
+pub use ingestion_log_storage::{IngestionLogStorage, IngestionLogEntry, IngestionEvent};
+pub use active_address_storage::{ActiveAddressStorage, DailyActiveAddresses};
+pub use raw_transaction_storage::RawTransactionStorage;
+pub use address_tag_storage::{AddressTagStorage, AddressTags};
+pub use alert_storage::{AlertStorage, Alert, AlertRule, PeelDepth};
+pub use webhook_storage::{WebhookStorage, WebhookSubscription, WebhookDelivery, LIFECYCLE_WEBHOOK_ADDRESS};
+pub use account_update_storage::{AccountUpdateStorage, AccountUpdate};
+pub use deposit_storage::{DepositStorage, Deposit, DepositAddress};
+pub use query_cache::{QueryCache, StatsSnapshot};
+pub use withdrawal_storage::{WithdrawalStorage, Withdrawal, ExpectedWithdrawal, WithdrawalStatus};
+pub use program_index_storage::{ProgramIndexStorage, ProgramInvocationEntry};
+pub use pair_transfer_storage::{PairTransferStorage, PairTransferRecord};
+pub use address_rollup_storage::{AddressRollupStorage, DailyAddressRollup};
+pub use signature_time_index_storage::{SignatureTimeIndexStorage, TimeIndexEntry};
+pub use migrations::{run_migrations, run_tenant_migrations, schema_version, latest_schema_version, MigrationReport};
+pub use bloom_filter::BloomFilter;
+pub use amount_histogram_storage::{AmountHistogramStorage, DailyAmountHistogram, AmountBucket, AMOUNT_BUCKETS, SOL_ASSET};
+pub use nft_transfer_storage::{NftTransferStorage, NftTransferList, NftSlotTransfer};
+pub use fee_payer_storage::{FeePayerStorage, FeeRelation};
+pub use flow_trace::{FlowTracer, FlowTraceQuery, FlowTrace, FlowNode};
+pub use transfer_search::{
+    TransferSearch, TransferQuery, TransferHit, TransferPage, TransferDirection, TransferCategory,
 };
 
+/// 创建各存储模块的参数，切换租户时沿用
+#[derive(Debug, Clone)]
+struct StorageLayout {
+    signature_prefix: String,
+    address_prefix: String,
+    max_address_records: usize,
+    max_record_age_days: Option<u64>,
+}
+
 /// 数据库管理器
 #[derive(Debug, Clone)]
 pub struct DatabaseManager {
@@ -20,25 +130,304 @@ pub struct DatabaseManager {
     storage: StorageManager,
     signature_storage: SignatureStorage,
     address_storage: AddressStorage,
+    watchlist_storage: WatchlistStorage,
+    slot_summary_storage: SlotSummaryStorage,
+    balance_storage: BalanceStorage,
+    token_balance_storage: TokenBalanceStorage,
+    fee_stats_storage: FeeStatsStorage,
+    counterparty_storage: CounterpartyStorage,
+    block_storage: BlockStorage,
+    slot_index_storage: SlotIndexStorage,
+    stake_event_storage: StakeEventStorage,
+    swap_storage: SwapStorage,
+    stats_storage: StatsStorage,
+    mint_transfer_storage: MintTransferStorage,
+    label_storage: LabelStorage,
+    largest_transfer_storage: LargestTransferStorage,
+    slot_finality_storage: SlotFinalityStorage,
+    dead_letter_storage: DeadLetterStorage,
+    mint_stats_storage: MintStatsStorage,
+    activity_storage: ActivityStorage,
+    ingestion_log_storage: IngestionLogStorage,
+    active_address_storage: ActiveAddressStorage,
+    raw_transaction_storage: RawTransactionStorage,
+    address_tag_storage: AddressTagStorage,
+    alert_storage: AlertStorage,
+    webhook_storage: WebhookStorage,
+    account_update_storage: AccountUpdateStorage,
+    deposit_storage: DepositStorage,
+    withdrawal_storage: WithdrawalStorage,
+    program_index_storage: ProgramIndexStorage,
+    pair_transfer_storage: PairTransferStorage,
+    address_rollup_storage: AddressRollupStorage,
+    signature_time_index_storage: SignatureTimeIndexStorage,
+    amount_histogram_storage: AmountHistogramStorage,
+    nft_transfer_storage: NftTransferStorage,
+    fee_payer_storage: FeePayerStorage,
+    /// 可疑模式规则引擎，未启用时为空
+    rules_engine: Option<RulesEngine>,
+    /// USD 价格估值，未启用时为空
+    price_oracle: Option<PriceOracle>,
+    /// 新写入转账的实时广播
+    transfer_feed: TransferFeed,
+    /// 热点查询缓存，默认不启用
+    query_cache: QueryCache,
+    storage_mode: StorageMode,
+    layout: StorageLayout,
 }
 
 impl DatabaseManager {
     /// 创建新的数据库管理器
     pub fn new(
         db_path: &str,
+        key_prefix_length: usize,
         signature_prefix: String,
         address_prefix: String,
         max_address_records: usize,
     ) -> Result<Self> {
         let storage = StorageManager::new(db_path, key_prefix_length)?;
+        Ok(Self::with_storage(storage, signature_prefix, address_prefix, max_address_records))
+    }
+
+    /// 在已打开的存储之上创建数据库管理器
+    pub fn with_storage(
+        storage: StorageManager,
+        signature_prefix: String,
+        address_prefix: String,
+        max_address_records: usize,
+    ) -> Self {
+        let layout = StorageLayout {
+            signature_prefix: signature_prefix.clone(),
+            address_prefix: address_prefix.clone(),
+            max_address_records,
+            max_record_age_days: None,
+        };
         let signature_storage = SignatureStorage::new(storage.clone(), signature_prefix);
         let address_storage = AddressStorage::new(storage.clone(), address_prefix, max_address_records);
+        let watchlist_storage = WatchlistStorage::new(storage.clone());
+        let slot_summary_storage = SlotSummaryStorage::new(storage.clone());
+        let balance_storage = BalanceStorage::new(storage.clone(), max_address_records);
+        let token_balance_storage = TokenBalanceStorage::new(storage.clone(), max_address_records);
+        let fee_stats_storage = FeeStatsStorage::new(storage.clone());
+        let counterparty_storage = CounterpartyStorage::new(storage.clone());
+        let block_storage = BlockStorage::new(storage.clone());
+        let slot_index_storage = SlotIndexStorage::new(storage.clone());
+        let stake_event_storage = StakeEventStorage::new(storage.clone(), max_address_records);
+        let swap_storage = SwapStorage::new(storage.clone(), max_address_records);
+        let stats_storage = StatsStorage::new(storage.clone());
+        let mint_transfer_storage = MintTransferStorage::new(storage.clone(), max_address_records);
+        let label_storage = LabelStorage::new(storage.clone());
+        let largest_transfer_storage = LargestTransferStorage::new(storage.clone());
+        let slot_finality_storage = SlotFinalityStorage::new(storage.clone());
+        let dead_letter_storage = DeadLetterStorage::new(storage.clone());
+        let mint_stats_storage = MintStatsStorage::new(storage.clone());
+        let activity_storage = ActivityStorage::new(storage.clone());
+        let ingestion_log_storage = IngestionLogStorage::new(storage.clone());
+        let active_address_storage = ActiveAddressStorage::new(storage.clone());
+        let raw_transaction_storage = RawTransactionStorage::new(storage.clone());
+        let address_tag_storage = AddressTagStorage::new(storage.clone());
+        let alert_storage = AlertStorage::new(storage.clone());
+        let webhook_storage = WebhookStorage::new(storage.clone());
+        let account_update_storage = AccountUpdateStorage::new(storage.clone());
+        let deposit_storage = DepositStorage::new(storage.clone());
+        let withdrawal_storage = WithdrawalStorage::new(storage.clone());
+        let program_index_storage = ProgramIndexStorage::new(storage.clone());
+        let pair_transfer_storage = PairTransferStorage::new(storage.clone());
+        let address_rollup_storage = AddressRollupStorage::new(storage.clone());
+        let signature_time_index_storage = SignatureTimeIndexStorage::new(storage.clone());
+        let amount_histogram_storage = AmountHistogramStorage::new(storage.clone());
+        let nft_transfer_storage = NftTransferStorage::new(storage.clone(), max_address_records);
+        let fee_payer_storage = FeePayerStorage::new(storage.clone());
 
-        Ok(Self {
+        Self {
             storage: storage.clone(),
             signature_storage,
             address_storage,
-        })
+            watchlist_storage,
+            slot_summary_storage,
+            balance_storage,
+            token_balance_storage,
+            fee_stats_storage,
+            counterparty_storage,
+            block_storage,
+            slot_index_storage,
+            stake_event_storage,
+            swap_storage,
+            stats_storage,
+            mint_transfer_storage,
+            label_storage,
+            largest_transfer_storage,
+            slot_finality_storage,
+            dead_letter_storage,
+            mint_stats_storage,
+            activity_storage,
+            ingestion_log_storage,
+            active_address_storage,
+            raw_transaction_storage,
+            address_tag_storage,
+            alert_storage,
+            webhook_storage,
+            account_update_storage,
+            deposit_storage,
+            withdrawal_storage,
+            program_index_storage,
+            pair_transfer_storage,
+            address_rollup_storage,
+            signature_time_index_storage,
+            amount_histogram_storage,
+            nft_transfer_storage,
+            fee_payer_storage,
+            rules_engine: None,
+            price_oracle: None,
+            transfer_feed: TransferFeed::default(),
+            query_cache: QueryCache::default(),
+            storage_mode: StorageMode::Full,
+            layout,
+        }
+    }
+
+    /// 根据配置创建数据库管理器
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let storage = Self::scope_tenant(StorageManager::from_config(&config.database)?, config)?;
+        info!("存储后端: {}", storage.backend_name());
+        Ok(Self::with_storage(
+            storage,
+            config.database.signature_key_prefix.clone(),
+            config.database.address_key_prefix.clone(),
+            config.database.max_address_records,
+        )
+        .with_max_record_age_days(config.database.max_record_age_days)
+        .with_raw_transactions(config.database.store_raw_transactions)
+        .with_storage_mode(config.database.storage_mode)
+        .with_query_cache(config.api.cache.clone())
+        .with_rules(&config.rules))
+    }
+
+    /// 以只读副本方式创建数据库管理器（api-only 模式）
+    pub fn open_replica(config: &Config) -> Result<Self> {
+        let storage = Self::scope_tenant(StorageManager::open_replica(&config.database)?, config)?;
+        info!("存储后端: {}（只读副本）", storage.backend_name());
+        // 副本不写入，规则配置只用于在告警接口中报告启用状态
+        Ok(Self::with_storage(
+            storage,
+            config.database.signature_key_prefix.clone(),
+            config.database.address_key_prefix.clone(),
+            config.database.max_address_records,
+        )
+        .with_query_cache(config.api.cache.clone())
+        .with_rules(&config.rules))
+    }
+
+    /// 按 `database.tenant` 切换到对应租户的命名空间
+    fn scope_tenant(storage: StorageManager, config: &Config) -> Result<StorageManager> {
+        match &config.database.tenant {
+            Some(tenant) => {
+                info!("使用租户: {}", tenant);
+                storage.for_tenant(tenant)
+            }
+            None => Ok(storage),
+        }
+    }
+
+    /// 同一存储后端上另一个租户的数据库管理器，沿用当前的存储参数和规则
+    ///
+    /// 新实例有独立的实时转账广播，不会收到其他租户的事件；就是当前租户时返回当前实例。
+    pub fn for_tenant(&self, tenant: &str) -> Result<Self> {
+        if self.tenant() == Some(tenant) {
+            return Ok(self.clone());
+        }
+        Ok(self.with_same_settings(self.storage.for_tenant(tenant)?))
+    }
+
+    /// 写入先缓冲在内存中的数据库管理器，用于离线批量导入
+    ///
+    /// 返回的缓冲需要定期 `commit`，未提交的写入只对该管理器可见。
+    pub fn buffered(&self) -> (Self, Arc<WriteBufferStore>) {
+        let (storage, buffer) = self.storage.buffered();
+        (self.with_same_settings(storage), buffer)
+    }
+
+    /// 在另一个存储视图上按当前的存储参数和规则构建管理器
+    fn with_same_settings(&self, storage: StorageManager) -> Self {
+        let mut manager = Self::with_storage(
+            storage,
+            self.layout.signature_prefix.clone(),
+            self.layout.address_prefix.clone(),
+            self.layout.max_address_records,
+        )
+        .with_max_record_age_days(self.layout.max_record_age_days)
+        .with_raw_transactions(self.raw_transaction_storage.is_enabled())
+        .with_storage_mode(self.storage_mode)
+        .with_query_cache(self.query_cache.config().clone());
+        manager.rules_engine = self.rules_engine.clone();
+        manager.price_oracle = self.price_oracle.clone();
+        manager
+    }
+
+    /// 当前租户，默认租户为空
+    pub fn tenant(&self) -> Option<&str> {
+        self.storage.tenant()
+    }
+
+    /// 是否为只读副本
+    pub fn is_read_only(&self) -> bool {
+        self.storage.is_read_only()
+    }
+
+    /// 只读副本追赶主库的最新写入
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        self.storage.try_catch_up_with_primary()
+    }
+
+    /// 设置是否在摄取时归档原始交易
+    pub fn with_raw_transactions(mut self, enabled: bool) -> Self {
+        self.raw_transaction_storage = self.raw_transaction_storage.with_enabled(enabled);
+        self
+    }
+
+    /// 设置地址记录的最长保留天数
+    pub fn with_max_record_age_days(mut self, days: Option<u64>) -> Self {
+        self.address_storage = self.address_storage.with_max_record_age_days(days);
+        self.layout.max_record_age_days = days;
+        self
+    }
+
+    /// 启用热点查询缓存
+    pub fn with_query_cache(mut self, config: QueryCacheConfig) -> Self {
+        self.query_cache = QueryCache::new(config);
+        self
+    }
+
+    /// 设置存储模式
+    pub fn with_storage_mode(mut self, mode: StorageMode) -> Self {
+        self.storage_mode = mode;
+        self
+    }
+
+    /// 存储模式
+    pub fn storage_mode(&self) -> StorageMode {
+        self.storage_mode
+    }
+
+    /// 按配置启用可疑模式规则引擎
+    pub fn with_rules(mut self, config: &RulesConfig) -> Self {
+        self.rules_engine = config.enabled.then(|| RulesEngine::new(config.clone()));
+        self
+    }
+
+    /// 启用摄取时的 USD 价格估值
+    pub fn with_price_oracle(mut self, oracle: PriceOracle) -> Self {
+        self.price_oracle = Some(oracle);
+        self
+    }
+
+    /// 替换时钟（用于测试）
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.address_storage = self.address_storage.with_clock(clock.clone());
+        self.watchlist_storage = self.watchlist_storage.with_clock(clock.clone());
+        self.balance_storage = self.balance_storage.with_clock(clock.clone());
+        self.token_balance_storage = self.token_balance_storage.with_clock(clock);
+        self
     }
 
     /// 获取签名存储实例
@@ -51,6 +440,215 @@ impl DatabaseManager {
         &self.address_storage
     }
 
+    /// 获取监控列表存储实例
+    pub fn watchlist_storage(&self) -> &WatchlistStorage {
+        &self.watchlist_storage
+    }
+
+    /// 获取槽位汇总存储实例
+    pub fn slot_summary_storage(&self) -> &SlotSummaryStorage {
+        &self.slot_summary_storage
+    }
+
+    /// 获取余额历史存储实例
+    pub fn balance_storage(&self) -> &BalanceStorage {
+        &self.balance_storage
+    }
+
+    /// 获取代币余额历史存储实例
+    pub fn token_balance_storage(&self) -> &TokenBalanceStorage {
+        &self.token_balance_storage
+    }
+
+    /// 获取手续费统计存储实例
+    pub fn fee_stats_storage(&self) -> &FeeStatsStorage {
+        &self.fee_stats_storage
+    }
+
+    /// 获取交易对手存储实例
+    pub fn counterparty_storage(&self) -> &CounterpartyStorage {
+        &self.counterparty_storage
+    }
+
+    /// 获取区块元数据存储实例
+    pub fn block_storage(&self) -> &BlockStorage {
+        &self.block_storage
+    }
+
+    /// 获取槽位交易索引存储实例
+    pub fn slot_index_storage(&self) -> &SlotIndexStorage {
+        &self.slot_index_storage
+    }
+
+    /// 获取质押操作存储实例
+    pub fn stake_event_storage(&self) -> &StakeEventStorage {
+        &self.stake_event_storage
+    }
+
+    /// 获取兑换记录存储实例
+    pub fn swap_storage(&self) -> &SwapStorage {
+        &self.swap_storage
+    }
+
+    /// 获取预计算统计存储实例
+    pub fn stats_storage(&self) -> &StatsStorage {
+        &self.stats_storage
+    }
+
+    /// 获取代币转账索引实例
+    pub fn mint_transfer_storage(&self) -> &MintTransferStorage {
+        &self.mint_transfer_storage
+    }
+
+    /// 获取地址标签存储实例
+    pub fn label_storage(&self) -> &LabelStorage {
+        &self.label_storage
+    }
+
+    /// 获取每日最大转账榜实例
+    pub fn largest_transfer_storage(&self) -> &LargestTransferStorage {
+        &self.largest_transfer_storage
+    }
+
+    /// 获取槽位最终性存储实例
+    pub fn slot_finality_storage(&self) -> &SlotFinalityStorage {
+        &self.slot_finality_storage
+    }
+
+    /// 获取死信队列实例
+    pub fn dead_letter_storage(&self) -> &DeadLetterStorage {
+        &self.dead_letter_storage
+    }
+
+    /// 获取代币统计实例
+    pub fn mint_stats_storage(&self) -> &MintStatsStorage {
+        &self.mint_stats_storage
+    }
+
+    /// 获取地址活跃度索引实例
+    pub fn activity_storage(&self) -> &ActivityStorage {
+        &self.activity_storage
+    }
+
+    /// 获取摄取审计日志实例
+    pub fn ingestion_log_storage(&self) -> &IngestionLogStorage {
+        &self.ingestion_log_storage
+    }
+
+    /// 获取每日活跃地址存储实例
+    pub fn active_address_storage(&self) -> &ActiveAddressStorage {
+        &self.active_address_storage
+    }
+
+    /// 获取原始交易归档实例
+    pub fn raw_transaction_storage(&self) -> &RawTransactionStorage {
+        &self.raw_transaction_storage
+    }
+
+    /// 获取地址标记存储实例
+    pub fn address_tag_storage(&self) -> &AddressTagStorage {
+        &self.address_tag_storage
+    }
+
+    /// 获取告警存储实例
+    pub fn alert_storage(&self) -> &AlertStorage {
+        &self.alert_storage
+    }
+
+    /// 获取规则引擎（未启用时为空）
+    pub fn rules_engine(&self) -> Option<&RulesEngine> {
+        self.rules_engine.as_ref()
+    }
+
+    /// 获取 USD 价格估值（未启用时为空）
+    pub fn price_oracle(&self) -> Option<&PriceOracle> {
+        self.price_oracle.as_ref()
+    }
+
+    /// 获取 Webhook 订阅存储实例
+    pub fn webhook_storage(&self) -> &WebhookStorage {
+        &self.webhook_storage
+    }
+
+    /// 获取账户更新存储实例
+    pub fn account_update_storage(&self) -> &AccountUpdateStorage {
+        &self.account_update_storage
+    }
+
+    /// 获取充值存储实例
+    pub fn deposit_storage(&self) -> &DepositStorage {
+        &self.deposit_storage
+    }
+
+    /// 获取提现对账存储实例
+    pub fn withdrawal_storage(&self) -> &WithdrawalStorage {
+        &self.withdrawal_storage
+    }
+
+    /// 获取程序调用索引存储实例
+    pub fn program_index_storage(&self) -> &ProgramIndexStorage {
+        &self.program_index_storage
+    }
+
+    /// 获取地址对转账索引存储实例
+    pub fn pair_transfer_storage(&self) -> &PairTransferStorage {
+        &self.pair_transfer_storage
+    }
+
+    /// 获取地址每日汇总存储实例
+    pub fn address_rollup_storage(&self) -> &AddressRollupStorage {
+        &self.address_rollup_storage
+    }
+
+    /// 获取签名时间索引存储实例
+    pub fn signature_time_index_storage(&self) -> &SignatureTimeIndexStorage {
+        &self.signature_time_index_storage
+    }
+
+    /// 获取转账金额分布存储实例
+    pub fn amount_histogram_storage(&self) -> &AmountHistogramStorage {
+        &self.amount_histogram_storage
+    }
+
+    /// 获取 NFT 转账存储实例
+    pub fn nft_transfer_storage(&self) -> &NftTransferStorage {
+        &self.nft_transfer_storage
+    }
+
+    /// 获取手续费代付关系存储实例
+    pub fn fee_payer_storage(&self) -> &FeePayerStorage {
+        &self.fee_payer_storage
+    }
+
+    /// 获取热点查询缓存
+    pub fn query_cache(&self) -> &QueryCache {
+        &self.query_cache
+    }
+
+    /// 获取实时转账广播
+    pub fn transfer_feed(&self) -> &TransferFeed {
+        &self.transfer_feed
+    }
+
+    /// 按组合条件搜索转账
+    pub fn search_transfers(&self, query: &TransferQuery) -> Result<TransferPage> {
+        TransferSearch::new(&self.address_storage, &self.mint_transfer_storage)
+            .with_tag_storage(&self.address_tag_storage)
+            .search(query)
+    }
+
+    /// 从起点地址向后追踪资金流向
+    pub fn trace_flow(&self, query: &FlowTraceQuery) -> Result<FlowTrace> {
+        FlowTracer::new(&self.address_storage).trace(query)
+    }
+
+    /// 写入摄取审计日志，并为订阅该事件的运维 Webhook 生成通知
+    pub fn record_ingestion_event(&self, event: IngestionEvent, lifecycle: &LifecycleWebhookConfig) -> Result<IngestionLogEntry> {
+        let entry = self.ingestion_log_storage.append(event)?;
+        self.webhook_storage.enqueue_lifecycle(&entry, lifecycle)?;
+        Ok(entry)
+    }
+
     /// 获取底层存储实例
     #[allow(dead_code)]
     pub fn storage(&self) -> &StorageManager {
@@ -63,9 +661,211 @@ impl DatabaseManager {
         self.storage.get_stats()
     }
 
+    /// 获取结构化的数据库属性
+    pub fn get_db_properties(&self) -> Result<DbProperties> {
+        self.storage.get_db_properties()
+    }
+
     /// 压缩数据库
-    #[allow(dead_code)]
     pub fn compact_database(&self) -> Result<StorageResult> {
         self.storage.compact()
     }
+
+    /// 删除签名数据及其地址记录、槽位索引、程序索引和地址对转账索引并回退统计计数，返回签名是否存在
+    pub fn delete_signature(&self, signature: &str) -> Result<bool> {
+        let Some(data) = self.signature_storage.get_signature_data(signature)? else {
+            return Ok(false);
+        };
+        self.signature_storage.delete_signature_data(signature)?;
+        self.signature_time_index_storage.remove(signature, data.slot, data.timestamp)?;
+        self.raw_transaction_storage.delete(signature)?;
+        self.query_cache.invalidate_signature(signature);
+        self.stats_storage.record_removals(std::slice::from_ref(&data))?;
+        self.remove_transaction_indexes(&data)?;
+        Ok(true)
+    }
+
+    /// 删除时间戳早于指定时间的签名数据并回退统计计数，返回删除数量
+    ///
+    /// 从时间索引最旧的一端分批读取，遇到不早于截止时间的条目即停止，不扫描全部签名数据。
+    /// 这些交易的地址记录、槽位索引、程序索引和地址对转账索引一并删除。
+    pub fn delete_signatures_before(&self, cutoff_timestamp: i64) -> Result<usize> {
+        let mut deleted = 0;
+        let mut address_records = 0;
+        loop {
+            let entries = self.signature_time_index_storage.oldest_entries(SIGNATURE_SCAN_CHUNK_SIZE)?;
+            let expired: Vec<TimeIndexEntry> =
+                entries.iter().take_while(|entry| entry.timestamp < cutoff_timestamp).cloned().collect();
+            let done = expired.len() < SIGNATURE_SCAN_CHUNK_SIZE;
+            let removed = self.delete_indexed_signatures(&expired)?;
+            for data in &removed {
+                address_records += self.remove_transaction_indexes(data)?;
+            }
+            deleted += removed.len();
+            if done {
+                break;
+            }
+        }
+
+        info!("删除 {} 个早于 {} 的签名数据及 {} 条地址记录", deleted, cutoff_timestamp, address_records);
+        Ok(deleted)
+    }
+
+    /// 删除时间索引条目及其签名数据并回退统计计数，返回删除的签名数据
+    ///
+    /// 签名数据已不存在时仍删除索引条目，避免按索引清理时反复读到同一批条目。
+    fn delete_indexed_signatures(&self, entries: &[TimeIndexEntry]) -> Result<Vec<SignatureTransactionData>> {
+        let mut removed = Vec::new();
+        for entry in entries {
+            if let Some(data) = self.signature_storage.get_signature_data(&entry.signature)? {
+                self.signature_storage.delete_signature_data(&entry.signature)?;
+                self.raw_transaction_storage.delete(&entry.signature)?;
+                self.query_cache.invalidate_signature(&entry.signature);
+                removed.push(data);
+            }
+            self.signature_time_index_storage.remove(&entry.signature, entry.slot, entry.timestamp)?;
+        }
+        self.stats_storage.record_removals(&removed)?;
+        Ok(removed)
+    }
+
+    /// 按时间索引删除最旧的签名数据，连同这些交易的地址记录、槽位索引、程序索引和地址对转账索引，
+    /// 返回删除数量
+    ///
+    /// 用于磁盘用量保护的清理；按容量上限或按地址聚合的数据不随单笔交易删除。
+    pub fn delete_oldest_signatures(&self, limit: usize) -> Result<usize> {
+        let entries = self.signature_time_index_storage.oldest_entries(limit)?;
+        let removed = self.delete_indexed_signatures(&entries)?;
+        let mut address_records = 0;
+        for data in &removed {
+            address_records += self.remove_transaction_indexes(data)?;
+        }
+
+        info!("删除 {} 个最旧的签名数据及 {} 条地址记录", removed.len(), address_records);
+        Ok(removed.len())
+    }
+
+    /// 删除一笔交易的地址记录、槽位索引、程序索引和地址对转账索引，返回删除的地址记录数
+    fn remove_transaction_indexes(&self, data: &SignatureTransactionData) -> Result<usize> {
+        let addresses: Vec<&str> = data
+            .sol_transfers
+            .iter()
+            .flat_map(|transfer| [transfer.from.as_str(), transfer.to.as_str()])
+            .chain(data.token_transfers.iter().flat_map(|transfer| [transfer.sender_wallet(), transfer.receiver_wallet()]))
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let address_records = self.address_storage.remove_signature_records(&data.signature, &addresses)?;
+        self.slot_index_storage.remove_transaction(data.slot, &data.signature)?;
+        self.program_index_storage
+            .remove_invocations(&data.extracted_addresses.programs, data.slot, &data.signature)?;
+        self.pair_transfer_storage
+            .remove_transfers(&data.signature, data.slot, &data.sol_transfers, &data.token_transfers)?;
+        Ok(address_records)
+    }
+
+    /// 获取地址的每日汇总：已汇总的历史与仍保留的原始记录按天合并，按日期升序排列
+    pub fn get_address_daily_summaries(
+        &self,
+        address: &str,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+    ) -> Result<Vec<DailyAddressRollup>> {
+        let mut days: BTreeMap<String, DailyAddressRollup> = self
+            .address_rollup_storage
+            .get_rollups(address, start_date, end_date)?
+            .into_iter()
+            .map(|rollup| (rollup.date.clone(), rollup))
+            .collect();
+
+        let records = self
+            .address_storage
+            .get_address_records(address)?
+            .map(|list| list.records)
+            .unwrap_or_default();
+        for (date, live) in DailyAddressRollup::aggregate(address, &records) {
+            if start_date.is_some_and(|start| date.as_str() < start) || end_date.is_some_and(|end| date.as_str() > end) {
+                continue;
+            }
+            match days.get_mut(&date) {
+                Some(rolled_up) => rolled_up.merge(&live),
+                None => {
+                    days.insert(date, live);
+                }
+            }
+        }
+        Ok(days.into_values().collect())
+    }
+
+    /// 升级前的数据库没有统计计数器时，扫描已有签名回填一次
+    pub fn ensure_statistics(&self) -> Result<()> {
+        if self.stats_storage.is_initialized()? {
+            return Ok(());
+        }
+        info!("📈 未找到预计算统计，开始根据已有签名回填...");
+        self.rebuild_statistics()?;
+        Ok(())
+    }
+
+    /// 升级前的数据库没有签名时间索引时，扫描已有签名回填一次
+    ///
+    /// 已回填时只检查一次标记，回填按键分批读取签名数据，不会一次载入全部记录。
+    pub fn ensure_signature_time_index(&self) -> Result<()> {
+        if self.signature_time_index_storage.is_backfilled()? {
+            return Ok(());
+        }
+        let mut count = 0;
+        self.storage.for_each_chunk_by_prefix::<SignatureTransactionData>(
+            self.signature_storage.signature_prefix(),
+            SIGNATURE_SCAN_CHUNK_SIZE,
+            |chunk| {
+                count += chunk.len();
+                self.signature_time_index_storage.record_batch(chunk.iter().map(|item| &item.value))
+            },
+        )?;
+        self.signature_time_index_storage.mark_backfilled()?;
+        info!("🕒 签名时间索引回填完成: {} 个签名", count);
+        Ok(())
+    }
+
+    /// 启用地址布隆过滤器并载入已有地址，返回载入的地址数
+    pub fn build_address_filter(&self, capacity: usize) -> Result<usize> {
+        self.address_storage.build_filter(capacity)
+    }
+
+    /// 启用签名布隆过滤器并载入已有签名，返回载入的签名数
+    pub fn build_signature_filter(&self, capacity: usize) -> Result<usize> {
+        self.signature_storage.build_filter(capacity)
+    }
+
+    /// 根据已有签名重新计算预计算统计
+    ///
+    /// 按键分批读取签名数据，不会一次载入全部记录。
+    pub fn rebuild_statistics(&self) -> Result<StatsTotals> {
+        self.stats_storage.rebuild(|add| {
+            self.storage.for_each_chunk_by_prefix::<SignatureTransactionData>(
+                self.signature_storage.signature_prefix(),
+                SIGNATURE_SCAN_CHUNK_SIZE,
+                |chunk| {
+                    chunk.iter().for_each(|item| add(&item.value));
+                    Ok(())
+                },
+            )
+        })
+    }
+
+    /// 在阻塞线程池中执行数据库操作
+    ///
+    /// 存储层的读写都是同步的 RocksDB I/O，异步上下文（API 处理函数、数据摄取）
+    /// 应通过此方法调用，避免阻塞 Tokio 工作线程。
+    pub async fn run_blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&DatabaseManager) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let db_manager = self.clone();
+        tokio::task::spawn_blocking(move || f(&db_manager))
+            .await
+            .context("数据库任务异常退出")?
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,151 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+use crate::transfer_types::NftTransfer;
+
+/// NFT 转账键前缀
+pub const NFT_TRANSFER_KEY_PREFIX: &str = "NFTX01";
+
+/// 地址的 NFT 转账列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftTransferList {
+    /// 地址
+    pub address: String,
+    /// NFT 转账（索引0是最新的）
+    pub transfers: Vec<NftSlotTransfer>,
+}
+
+/// 带槽位的 NFT 转账
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftSlotTransfer {
+    /// 交易槽位
+    pub slot: u64,
+    /// NFT 转账
+    #[serde(flatten)]
+    pub transfer: NftTransfer,
+}
+
+/// NFT 转账存储管理器
+///
+/// 每笔转账保存在转出方和接收方钱包名下。
+#[derive(Debug, Clone)]
+pub struct NftTransferStorage {
+    storage: StorageManager,
+    max_transfers: usize,
+}
+
+impl NftTransferStorage {
+    /// 创建新的 NFT 转账存储实例
+    pub fn new(storage: StorageManager, max_transfers: usize) -> Self {
+        Self { storage, max_transfers }
+    }
+
+    /// 记录一笔交易中的 NFT 转账
+    pub fn record_transfers(&self, slot: u64, transfers: &[NftTransfer]) -> Result<()> {
+        for transfer in transfers {
+            for address in transfer.addresses() {
+                self.add_transfer(address, NftSlotTransfer { slot, transfer: transfer.clone() })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 添加 NFT 转账，同一签名中同一 NFT 的转账只记录一次
+    fn add_transfer(&self, address: &str, transfer: NftSlotTransfer) -> Result<()> {
+        let key = self.make_key(address)?;
+
+        let mut list = self.storage.get::<NftTransferList>(&key)?.unwrap_or(NftTransferList {
+            address: address.to_string(),
+            transfers: Vec::new(),
+        });
+
+        let duplicate = list.transfers.iter().any(|existing| {
+            existing.transfer.signature == transfer.transfer.signature && existing.transfer.mint == transfer.transfer.mint
+        });
+        if duplicate {
+            return Ok(());
+        }
+
+        list.transfers.insert(0, transfer);
+        list.transfers.truncate(self.max_transfers);
+
+        self.storage.put(&key, &list)?;
+        debug!("地址 {} NFT 转账更新，当前 {} 条", address, list.transfers.len());
+        Ok(())
+    }
+
+    /// 获取地址的 NFT 转账（最新的在前）
+    pub fn get_transfers(&self, address: &str) -> Result<Vec<NftSlotTransfer>> {
+        Ok(self
+            .storage
+            .get::<NftTransferList>(&self.make_key(address)?)?
+            .map(|list| list.transfers)
+            .unwrap_or_default())
+    }
+
+    fn make_key(&self, address: &str) -> Result<String> {
+        self.storage.make_key(NFT_TRANSFER_KEY_PREFIX, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_transfers_indexed_by_both_wallets() {
+        let dir = TempDir::new("nft-transfer-test");
+        let storage = NftTransferStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 10);
+
+        let transfer = NftTransfer {
+            signature: "sig1".to_string(),
+            mint: "nftMint".to_string(),
+            collection: Some("collectionMint".to_string()),
+            from: "aliceAta".to_string(),
+            to: "bobAta".to_string(),
+            from_owner: Some("alice".to_string()),
+            to_owner: Some("bob".to_string()),
+            timestamp: 100,
+        };
+        storage.record_transfers(5, std::slice::from_ref(&transfer)).unwrap();
+        // 重复处理同一交易不会产生重复记录
+        storage.record_transfers(5, &[transfer]).unwrap();
+
+        let alice = storage.get_transfers("alice").unwrap();
+        assert_eq!(alice.len(), 1);
+        assert_eq!((alice[0].slot, alice[0].transfer.collection.as_deref()), (5, Some("collectionMint")));
+        assert_eq!(storage.get_transfers("bob").unwrap().len(), 1);
+        assert!(storage.get_transfers("aliceAta").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_transfers_capped_newest_first() {
+        let dir = TempDir::new("nft-transfer-cap-test");
+        let storage = NftTransferStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 3);
+
+        for slot in 1..=5u64 {
+            let transfer = NftTransfer {
+                signature: format!("sig{}", slot),
+                mint: format!("nftMint{}", slot),
+                collection: None,
+                from: "aliceAta".to_string(),
+                to: "bobAta".to_string(),
+                from_owner: Some("alice".to_string()),
+                to_owner: Some("bob".to_string()),
+                timestamp: slot as u32 * 100,
+            };
+            storage.record_transfers(slot, &[transfer]).unwrap();
+        }
+
+        // 超出上限时淘汰最旧的转账，最新的在前
+        for wallet in ["alice", "bob"] {
+            let transfers = storage.get_transfers(wallet).unwrap();
+            let slots: Vec<u64> = transfers.iter().map(|t| t.slot).collect();
+            assert_eq!(slots, vec![5, 4, 3]);
+            assert_eq!(transfers[0].transfer.mint, "nftMint5");
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::signature_storage::{SolTransfer, TokenTransfer};
+use crate::database::storage::StorageManager;
+use crate::database::transfer_search::{TransferCategory, TransferHit};
+
+/// 地址对转账索引键前缀
+pub const PAIR_TRANSFER_KEY_PREFIX: &str = "PAIR01";
+
+/// 地址对索引中的一笔转账
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairTransferRecord {
+    /// 确定性转账ID（签名:sol序号 / 签名:token序号）
+    pub transfer_id: String,
+    /// 交易签名
+    pub signature: String,
+    /// 交易槽位
+    pub slot: u64,
+    /// 交易时间戳
+    pub timestamp: u64,
+    /// 发送方地址
+    pub from: String,
+    /// 接收方地址
+    pub to: String,
+    /// 转账金额（最小单位）
+    pub amount: u64,
+    /// 代币mint地址（SOL 转账为 None）
+    pub mint: Option<String>,
+    /// 精度（SOL 为 9）
+    pub decimals: u8,
+}
+
+impl From<PairTransferRecord> for TransferHit {
+    fn from(record: PairTransferRecord) -> Self {
+        Self {
+            transfer_id: record.transfer_id,
+            signature: record.signature,
+            slot: record.slot,
+            timestamp: record.timestamp,
+            category: if record.mint.is_some() { TransferCategory::Token } else { TransferCategory::Sol },
+            from: record.from,
+            to: record.to,
+            amount: record.amount,
+            mint: record.mint,
+            decimals: record.decimals,
+            direction: None,
+        }
+    }
+}
+
+/// (发送方, 接收方) → 转账的复合二级索引
+///
+/// 键格式为 `PAIR01{from}:{to}:{slot:020}:{transfer_id}`，按地址对前缀扫描即按时间顺序
+/// 得到两个地址之间的全部转账。代币转账按所有者钱包索引，没有所有者信息时使用代币账户。
+#[derive(Debug, Clone)]
+pub struct PairTransferStorage {
+    storage: StorageManager,
+}
+
+impl PairTransferStorage {
+    /// 创建新的地址对转账索引实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 记录一笔交易中的全部转账
+    pub fn record_transfers(
+        &self,
+        signature: &str,
+        slot: u64,
+        timestamp: u64,
+        sol_transfers: &[SolTransfer],
+        token_transfers: &[TokenTransfer],
+    ) -> Result<()> {
+        let items = Self::build_records(signature, slot, timestamp, sol_transfers, token_transfers)
+            .into_iter()
+            .map(|record| Ok((self.record_key(&record)?, record)))
+            .collect::<Result<Vec<_>>>()?;
+        if items.is_empty() {
+            return Ok(());
+        }
+        let count = items.len();
+        self.storage.batch_put(items)?;
+        debug!("交易 {} 加入 {} 条地址对转账索引", signature, count);
+        Ok(())
+    }
+
+    /// 删除一笔交易中全部转账的索引条目
+    pub fn remove_transfers(
+        &self,
+        signature: &str,
+        slot: u64,
+        sol_transfers: &[SolTransfer],
+        token_transfers: &[TokenTransfer],
+    ) -> Result<()> {
+        for record in Self::build_records(signature, slot, 0, sol_transfers, token_transfers) {
+            self.storage.delete(&self.record_key(&record)?)?;
+        }
+        Ok(())
+    }
+
+    fn build_records(
+        signature: &str,
+        slot: u64,
+        timestamp: u64,
+        sol_transfers: &[SolTransfer],
+        token_transfers: &[TokenTransfer],
+    ) -> Vec<PairTransferRecord> {
+        let sol = sol_transfers.iter().enumerate().map(|(index, transfer)| PairTransferRecord {
+            transfer_id: format!("{}:sol{}", signature, index),
+            signature: signature.to_string(),
+            slot,
+            timestamp,
+            from: transfer.from.clone(),
+            to: transfer.to.clone(),
+            amount: transfer.amount,
+            mint: None,
+            decimals: 9,
+        });
+        let token = token_transfers.iter().enumerate().map(|(index, transfer)| PairTransferRecord {
+            transfer_id: format!("{}:token{}", signature, index),
+            signature: signature.to_string(),
+            slot,
+            timestamp,
+            from: transfer.sender_wallet().to_string(),
+            to: transfer.receiver_wallet().to_string(),
+            amount: transfer.amount,
+            mint: Some(transfer.mint.clone()),
+            decimals: transfer.decimals,
+        });
+        sol.chain(token).collect()
+    }
+
+    fn record_key(&self, record: &PairTransferRecord) -> Result<String> {
+        self.storage.make_key(
+            PAIR_TRANSFER_KEY_PREFIX,
+            &format!("{}:{}:{:020}:{}", record.from, record.to, record.slot, record.transfer_id),
+        )
+    }
+
+    /// 获取从 `from` 到 `to` 的转账，按槽位从旧到新排列
+    pub fn get_transfers_between(
+        &self,
+        from: &str,
+        to: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<PairTransferRecord>> {
+        let prefix = format!("{}{}:{}:", PAIR_TRANSFER_KEY_PREFIX, from, to);
+        let keys = self.storage.get_keys_by_prefix_limit(&prefix, offset.saturating_add(limit))?;
+        let mut records = Vec::with_capacity(keys.len().saturating_sub(offset));
+        for key in keys.into_iter().skip(offset) {
+            if let Some(record) = self.storage.get::<PairTransferRecord>(&key)? {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn sol(from: &str, to: &str, amount: u64) -> SolTransfer {
+        SolTransfer {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        }
+    }
+
+    #[test]
+    fn test_transfers_between_in_time_order() {
+        let dir = TempDir::new("pair-transfer-test");
+        let storage = PairTransferStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        let token = TokenTransfer {
+            from: "aliceUsdc".to_string(),
+            to: "bobUsdc".to_string(),
+            amount: 5_000_000,
+            decimals: 6,
+            mint: "usdc".to_string(),
+            program_id: "token".to_string(),
+            transfer_type: "Token Transfer".to_string(),
+            from_owner: Some("alice".to_string()),
+            to_owner: Some("bob".to_string()),
+        };
+
+        storage.record_transfers("sig2", 200, 1_704_067_300, &[sol("alice", "bob", 2)], &[]).unwrap();
+        storage.record_transfers("sig1", 100, 1_704_067_200, &[sol("alice", "bob", 1), sol("bob", "alice", 9)], &[]).unwrap();
+        storage.record_transfers("sig3", 300, 1_704_067_400, &[], &[token]).unwrap();
+
+        let records = storage.get_transfers_between("alice", "bob", 0, 10).unwrap();
+        let ids: Vec<&str> = records.iter().map(|record| record.transfer_id.as_str()).collect();
+        assert_eq!(ids, vec!["sig1:sol0", "sig2:sol0", "sig3:token0"]);
+        assert_eq!(records[2].mint.as_deref(), Some("usdc"));
+
+        // 方向相反的转账单独索引
+        let reverse = storage.get_transfers_between("bob", "alice", 0, 10).unwrap();
+        assert_eq!(reverse.len(), 1);
+        assert_eq!(reverse[0].amount, 9);
+
+        assert_eq!(storage.get_transfers_between("alice", "bob", 1, 1).unwrap()[0].transfer_id, "sig2:sol0");
+        assert!(storage.get_transfers_between("alice", "bo", 0, 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use anyhow::{Context, Result};
+use postgres::{Client, NoTls};
+use std::sync::Mutex;
+use tracing::info;
+
+use crate::config::PostgresConfig;
+use crate::database::kv_store::{prefix_upper_bound, KvStore};
+
+/// PostgreSQL 后端
+///
+/// 所有数据保存在一张 `(key BYTEA PRIMARY KEY, value BYTEA)` 表中，BYTEA 按字节序比较，
+/// 前缀扫描与 RocksDB 的键顺序一致。值仍是 JSON，便于直接用 SQL 查询
+/// （如 `convert_from(value, 'UTF8')::jsonb`）。
+///
+/// 使用同步的 `postgres` 客户端；在 tokio 多线程运行时中通过 `block_in_place` 调用，
+/// 不支持 current_thread 运行时。
+pub struct PostgresStore {
+    client: Mutex<Client>,
+    table: String,
+}
+
+impl std::fmt::Debug for PostgresStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostgresStore").field("table", &self.table).finish()
+    }
+}
+
+impl PostgresStore {
+    /// 连接 PostgreSQL 并确保数据表存在
+    pub fn connect(config: &PostgresConfig) -> Result<Self> {
+        if config.table.is_empty()
+            || !config.table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(anyhow::anyhow!("无效的 PostgreSQL 表名: {}", config.table));
+        }
+
+        let mut client = blocking(|| Client::connect(&config.url, NoTls)).context("连接 PostgreSQL 失败")?;
+        blocking(|| {
+            client.batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {} (key BYTEA PRIMARY KEY, value BYTEA NOT NULL)",
+                config.table
+            ))
+        })
+        .context("创建 PostgreSQL 数据表失败")?;
+
+        info!("PostgreSQL 存储已连接，数据表: {}", config.table);
+        Ok(Self {
+            client: Mutex::new(client),
+            table: config.table.clone(),
+        })
+    }
+
+    fn with_client<R>(&self, f: impl FnOnce(&mut Client) -> Result<R>) -> Result<R> {
+        let mut client = self.client.lock().map_err(|_| anyhow::anyhow!("PostgreSQL 连接锁已损坏"))?;
+        blocking(|| f(&mut client))
+    }
+
+    /// 按前缀做范围查询，`[prefix, prefix 的下一个值)` 可以直接使用主键索引
+    ///
+    /// `start_after` 用于分页，只返回大于该键的记录；`descending` 时按键降序返回。
+    fn query_prefix(
+        &self,
+        columns: &str,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: Option<usize>,
+        descending: bool,
+    ) -> Result<Vec<postgres::Row>> {
+        let upper = prefix_upper_bound(prefix);
+        let (lower, lower_op) = match start_after.filter(|after| *after >= prefix) {
+            Some(after) => (after, ">"),
+            None => (prefix, ">="),
+        };
+        let mut params: Vec<&(dyn postgres::types::ToSql + Sync)> = vec![&lower];
+        let mut condition = format!("key {} $1", lower_op);
+        if let Some(upper) = &upper {
+            condition.push_str(" AND key < $2");
+            params.push(upper);
+        }
+        let sql = format!(
+            "SELECT {} FROM {} WHERE {} ORDER BY key{}{}",
+            columns,
+            self.table,
+            condition,
+            if descending { " DESC" } else { "" },
+            limit.map(|limit| format!(" LIMIT {}", limit)).unwrap_or_default()
+        );
+        self.with_client(|client| client.query(&sql, &params).context("按前缀查询 PostgreSQL 失败"))
+    }
+}
+
+impl KvStore for PostgresStore {
+    fn backend_name(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        let sql = format!(
+            "INSERT INTO {} (key, value) VALUES ($1, $2) ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+            self.table
+        );
+        self.with_client(|client| {
+            client.execute(&sql, &[&key, &value]).context("存储数据到 PostgreSQL 失败")?;
+            Ok(())
+        })
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let sql = format!("SELECT value FROM {} WHERE key = $1", self.table);
+        self.with_client(|client| {
+            let row = client.query_opt(&sql, &[&key]).context("从 PostgreSQL 读取数据失败")?;
+            Ok(row.map(|row| row.get(0)))
+        })
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        let sql = format!("DELETE FROM {} WHERE key = $1", self.table);
+        self.with_client(|client| {
+            client.execute(&sql, &[&key]).context("从 PostgreSQL 删除数据失败")?;
+            Ok(())
+        })
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let rows = self.query_prefix("key, value", prefix, None, None, false)?;
+        Ok(rows.into_iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    fn scan_prefix_keys(&self, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let rows = self.query_prefix("key", prefix, None, None, false)?;
+        Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    }
+
+    fn scan_prefix_keys_limit(&self, prefix: &[u8], limit: usize) -> Result<Vec<Vec<u8>>> {
+        let rows = self.query_prefix("key", prefix, None, Some(limit), false)?;
+        Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    }
+
+    fn scan_prefix_page(
+        &self,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let rows = self.query_prefix("key, value", prefix, start_after, Some(limit), false)?;
+        Ok(rows.into_iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    fn scan_prefix_keys_page(&self, prefix: &[u8], start_after: Option<&[u8]>, limit: usize) -> Result<Vec<Vec<u8>>> {
+        let rows = self.query_prefix("key", prefix, start_after, Some(limit), false)?;
+        Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    }
+
+    fn scan_prefix_keys_rev_limit(&self, prefix: &[u8], limit: usize) -> Result<Vec<Vec<u8>>> {
+        let rows = self.query_prefix("key", prefix, None, Some(limit), true)?;
+        Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    }
+
+    fn write_batch(&self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<()> {
+        let sql = format!(
+            "INSERT INTO {} (key, value) VALUES ($1, $2) ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+            self.table
+        );
+        self.with_client(|client| {
+            let mut transaction = client.transaction().context("开启 PostgreSQL 事务失败")?;
+            let statement = transaction.prepare(&sql).context("准备 PostgreSQL 语句失败")?;
+            for (key, value) in &items {
+                transaction
+                    .execute(&statement, &[key, value])
+                    .context("批量写入 PostgreSQL 失败")?;
+            }
+            transaction.commit().context("提交 PostgreSQL 事务失败")
+        })
+    }
+
+    fn compact(&self) -> Result<()> {
+        let sql = format!("VACUUM ANALYZE {}", self.table);
+        self.with_client(|client| client.batch_execute(&sql).context("PostgreSQL VACUUM 失败"))
+    }
+}
+
+/// 在 tokio 多线程运行时中执行阻塞调用
+fn blocking<R>(f: impl FnOnce() -> R) -> R {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_upper_bound() {
+        assert_eq!(prefix_upper_bound(b"ADDR01"), Some(b"ADDR02".to_vec()));
+        assert_eq!(prefix_upper_bound(&[0x41, 0xFF]), Some(vec![0x42]));
+        assert_eq!(prefix_upper_bound(&[0xFF, 0xFF]), None);
+    }
+}
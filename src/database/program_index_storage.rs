@@ -0,0 +1,127 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 程序调用索引键前缀
+pub const PROGRAM_INDEX_KEY_PREFIX: &str = "PRGX01";
+
+/// 程序索引中的一笔交易
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProgramInvocationEntry {
+    /// 交易签名
+    pub signature: String,
+    /// 交易所在槽位
+    pub slot: u64,
+    /// 交易时间戳
+    pub timestamp: i64,
+    /// 交易是否成功
+    pub is_successful: bool,
+}
+
+/// 程序 ID → 交易签名的二级索引
+///
+/// 键格式为 `PRGX01{program_id}:{u64::MAX - slot:020}:{signature}`，槽位取反后
+/// 按前缀顺序扫描即为从新到旧，分页只需读取前 `offset + limit` 个键，不扫描整个程序的历史。
+#[derive(Debug, Clone)]
+pub struct ProgramIndexStorage {
+    storage: StorageManager,
+}
+
+impl ProgramIndexStorage {
+    /// 创建新的程序索引存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 记录交易调用的程序（含内部指令调用的程序）
+    pub fn record_invocations(&self, programs: &[String], entry: &ProgramInvocationEntry) -> Result<()> {
+        if programs.is_empty() {
+            return Ok(());
+        }
+        let items = programs
+            .iter()
+            .map(|program_id| Ok((self.entry_key(program_id, entry.slot, &entry.signature)?, entry.clone())))
+            .collect::<Result<Vec<_>>>()?;
+        self.storage.batch_put(items)?;
+        debug!("交易 {} 加入 {} 个程序的调用索引", entry.signature, programs.len());
+        Ok(())
+    }
+
+    /// 从交易调用过的程序的索引中删除该交易
+    pub fn remove_invocations(&self, programs: &[String], slot: u64, signature: &str) -> Result<()> {
+        for program_id in programs {
+            self.storage.delete(&self.entry_key(program_id, slot, signature)?)?;
+        }
+        Ok(())
+    }
+
+    /// 获取调用过程序的交易，按槽位从新到旧排列
+    pub fn get_program_transactions(
+        &self,
+        program_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<ProgramInvocationEntry>> {
+        let prefix = format!("{}{}:", PROGRAM_INDEX_KEY_PREFIX, program_id);
+        let keys = self.storage.get_keys_by_prefix_limit(&prefix, offset.saturating_add(limit))?;
+        let mut entries = Vec::with_capacity(keys.len().saturating_sub(offset));
+        for key in keys.into_iter().skip(offset) {
+            if let Some(entry) = self.storage.get::<ProgramInvocationEntry>(&key)? {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    fn entry_key(&self, program_id: &str, slot: u64, signature: &str) -> Result<String> {
+        self.storage.make_key(
+            PROGRAM_INDEX_KEY_PREFIX,
+            &format!("{}:{:020}:{}", program_id, u64::MAX - slot, signature),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn entry(signature: &str, slot: u64) -> ProgramInvocationEntry {
+        ProgramInvocationEntry {
+            signature: signature.to_string(),
+            slot,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_program_transactions_newest_first() {
+        let dir = TempDir::new("program-index-test");
+        let storage = ProgramIndexStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        let dex = "DexProgram1111".to_string();
+        let other = "OtherProgram1111".to_string();
+
+        storage.record_invocations(std::slice::from_ref(&dex), &entry("sigA", 100)).unwrap();
+        storage.record_invocations(&[dex.clone(), other.clone()], &entry("sigB", 1_000)).unwrap();
+        storage.record_invocations(std::slice::from_ref(&dex), &entry("sigC", 500)).unwrap();
+        // 重复写入同一签名不会产生重复条目
+        storage.record_invocations(std::slice::from_ref(&dex), &entry("sigC", 500)).unwrap();
+
+        let signatures = |offset, limit| -> Vec<String> {
+            storage
+                .get_program_transactions(&dex, offset, limit)
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.signature)
+                .collect()
+        };
+        assert_eq!(signatures(0, 10), vec!["sigB", "sigC", "sigA"]);
+        assert_eq!(signatures(1, 1), vec!["sigC"]);
+        assert!(signatures(3, 10).is_empty());
+        assert_eq!(storage.get_program_transactions(&other, 0, 10).unwrap().len(), 1);
+        // 程序 ID 互为前缀时不会混在一起
+        assert!(storage.get_program_transactions("DexProgram", 0, 10).unwrap().is_empty());
+    }
+}
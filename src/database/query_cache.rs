@@ -0,0 +1,181 @@
+use anyhow::Result;
+use moka::sync::Cache;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::QueryCacheConfig;
+use crate::database::address_storage::AddressStats;
+use crate::database::fee_stats_storage::DailyFeeStats;
+use crate::database::signature_storage::SignatureTransactionData;
+use crate::database::stats_storage::{DailyTransferStats, StatsTotals};
+
+/// 统计接口的数据快照
+#[derive(Debug, Clone)]
+pub struct StatsSnapshot {
+    pub totals: StatsTotals,
+    pub daily_transfers: Vec<DailyTransferStats>,
+    pub daily_fees: Vec<DailyFeeStats>,
+}
+
+/// 热点查询的进程内缓存
+///
+/// 缓存签名查询、地址统计和全局统计，按配置的 TTL 过期，TTL 为 0 的缓存不启用。
+/// 写入路径在交易写入后失效对应签名和涉及地址的缓存；只读副本收不到写入，只按 TTL 过期。
+#[derive(Debug, Clone)]
+pub struct QueryCache {
+    config: QueryCacheConfig,
+    signatures: Option<Cache<String, Arc<SignatureTransactionData>>>,
+    address_stats: Option<Cache<String, Arc<AddressStats>>>,
+    stats: Option<Cache<(), Arc<StatsSnapshot>>>,
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self::new(QueryCacheConfig::disabled())
+    }
+}
+
+impl QueryCache {
+    /// 按配置创建缓存
+    pub fn new(config: QueryCacheConfig) -> Self {
+        Self {
+            signatures: Self::build(config.max_entries, config.signature_ttl_secs),
+            address_stats: Self::build(config.max_entries, config.address_stats_ttl_secs),
+            stats: Self::build(1, config.stats_ttl_secs),
+            config,
+        }
+    }
+
+    /// 缓存配置
+    pub fn config(&self) -> &QueryCacheConfig {
+        &self.config
+    }
+
+    /// 按签名读取交易数据，未缓存时调用 `load`；不缓存不存在的签名
+    pub fn signature_data(
+        &self,
+        signature: &str,
+        load: impl FnOnce() -> Result<Option<SignatureTransactionData>>,
+    ) -> Result<Option<SignatureTransactionData>> {
+        let Some(cache) = &self.signatures else {
+            return load();
+        };
+        if let Some(data) = cache.get(signature) {
+            return Ok(Some(Arc::unwrap_or_clone(data)));
+        }
+        let data = load()?;
+        if let Some(data) = &data {
+            cache.insert(signature.to_string(), Arc::new(data.clone()));
+        }
+        Ok(data)
+    }
+
+    /// 读取地址统计，未缓存时调用 `load`
+    pub fn address_stats(&self, address: &str, load: impl FnOnce() -> Result<AddressStats>) -> Result<AddressStats> {
+        let Some(cache) = &self.address_stats else {
+            return load();
+        };
+        if let Some(stats) = cache.get(address) {
+            return Ok(Arc::unwrap_or_clone(stats));
+        }
+        let stats = load()?;
+        cache.insert(address.to_string(), Arc::new(stats.clone()));
+        Ok(stats)
+    }
+
+    /// 读取全局统计快照，未缓存时调用 `load`
+    pub fn stats(&self, load: impl FnOnce() -> Result<StatsSnapshot>) -> Result<StatsSnapshot> {
+        let Some(cache) = &self.stats else {
+            return load();
+        };
+        if let Some(snapshot) = cache.get(&()) {
+            return Ok(Arc::unwrap_or_clone(snapshot));
+        }
+        let snapshot = load()?;
+        cache.insert((), Arc::new(snapshot.clone()));
+        Ok(snapshot)
+    }
+
+    /// 交易写入后失效该签名及涉及地址的缓存
+    pub fn invalidate_transaction(&self, data: &SignatureTransactionData) {
+        self.invalidate_signature(&data.signature);
+        let Some(cache) = &self.address_stats else {
+            return;
+        };
+        for transfer in &data.sol_transfers {
+            cache.invalidate(&transfer.from);
+            cache.invalidate(&transfer.to);
+        }
+        for transfer in &data.token_transfers {
+            for address in [&transfer.from, &transfer.to].into_iter().chain(&transfer.from_owner).chain(&transfer.to_owner) {
+                cache.invalidate(address);
+            }
+        }
+    }
+
+    /// 失效签名的缓存
+    pub fn invalidate_signature(&self, signature: &str) {
+        if let Some(cache) = &self.signatures {
+            cache.invalidate(signature);
+        }
+    }
+
+    fn build<K, V>(max_entries: u64, ttl_secs: u64) -> Option<Cache<K, V>>
+    where
+        K: std::hash::Hash + Eq + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        (ttl_secs > 0).then(|| {
+            Cache::builder()
+                .max_capacity(max_entries)
+                .time_to_live(Duration::from_secs(ttl_secs))
+                .build()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::signature_storage::SolTransfer;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_cache_hits_and_invalidation() {
+        let cache = QueryCache::new(QueryCacheConfig::default());
+        let mut data = SignatureTransactionData::new("sig".to_string(), 1_704_067_200, 7, true);
+        data.add_sol_transfer(SolTransfer {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: 42,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        });
+
+        let loads = Cell::new(0);
+        let load = || {
+            loads.set(loads.get() + 1);
+            Ok(AddressStats::from_records("bob", &[]))
+        };
+        cache.address_stats("bob", load).unwrap();
+        cache.address_stats("bob", load).unwrap();
+        assert_eq!(loads.get(), 1);
+
+        // 不存在的签名不缓存
+        assert!(cache.signature_data("sig", || Ok(None)).unwrap().is_none());
+        assert!(cache.signature_data("sig", || Ok(Some(data.clone()))).unwrap().is_some());
+        assert!(cache.signature_data("sig", || panic!("应命中缓存")).unwrap().is_some());
+
+        cache.invalidate_transaction(&data);
+        cache.address_stats("bob", load).unwrap();
+        assert_eq!(loads.get(), 2);
+        assert!(cache.signature_data("sig", || Ok(None)).unwrap().is_none());
+
+        // TTL 为 0 时不缓存
+        let disabled = QueryCache::default();
+        disabled.address_stats("bob", load).unwrap();
+        disabled.address_stats("bob", load).unwrap();
+        assert_eq!(loads.get(), 4);
+    }
+}
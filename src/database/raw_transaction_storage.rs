@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 原始交易归档键前缀
+pub const RAW_TRANSACTION_KEY_PREFIX: &str = "RAWT01";
+
+/// zstd 压缩级别（与快照备份一致，兼顾速度和压缩率）
+const ZSTD_LEVEL: i32 = 3;
+
+/// 原始交易归档
+///
+/// 保存订阅收到的原始 protobuf 字节（zstd 压缩），解析器改进后可据此重新解析历史交易。
+/// 默认关闭，由 `database.store_raw_transactions` 开启。
+#[derive(Debug, Clone)]
+pub struct RawTransactionStorage {
+    storage: StorageManager,
+    enabled: bool,
+}
+
+impl RawTransactionStorage {
+    /// 创建新的原始交易归档实例（默认关闭）
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage, enabled: false }
+    }
+
+    /// 设置是否在摄取时归档原始交易
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// 摄取时是否归档原始交易
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// 压缩并保存原始交易字节，返回压缩后的大小
+    pub fn store(&self, signature: &str, payload: &[u8]) -> Result<usize> {
+        let compressed = zstd::encode_all(payload, ZSTD_LEVEL).context("压缩原始交易失败")?;
+        self.storage
+            .put_bytes(&self.storage.make_key(RAW_TRANSACTION_KEY_PREFIX, signature)?, &compressed)?;
+        debug!("归档原始交易 {}: {} -> {} bytes", signature, payload.len(), compressed.len());
+        Ok(compressed.len())
+    }
+
+    /// 获取解压后的原始交易字节
+    pub fn get(&self, signature: &str) -> Result<Option<Vec<u8>>> {
+        let key = self.storage.make_key(RAW_TRANSACTION_KEY_PREFIX, signature)?;
+        self.storage
+            .get_bytes(&key)?
+            .map(|compressed| zstd::decode_all(compressed.as_slice()).context("解压原始交易失败"))
+            .transpose()
+    }
+
+    /// 列出所有已归档交易的签名
+    pub fn signatures(&self) -> Result<Vec<String>> {
+        Ok(self
+            .storage
+            .get_keys_by_prefix(RAW_TRANSACTION_KEY_PREFIX)?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(RAW_TRANSACTION_KEY_PREFIX).map(str::to_string))
+            .collect())
+    }
+
+    /// 删除归档的原始交易
+    pub fn delete(&self, signature: &str) -> Result<()> {
+        self.storage.delete(&self.storage.make_key(RAW_TRANSACTION_KEY_PREFIX, signature)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_store_and_get_round_trip() {
+        let dir = TempDir::new("raw-transaction-test");
+        let storage = RawTransactionStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        assert!(!storage.is_enabled());
+
+        let payload = vec![7u8; 4096];
+        let compressed = storage.store("sig", &payload).unwrap();
+        assert!(compressed < payload.len());
+        assert_eq!(storage.get("sig").unwrap(), Some(payload));
+        assert_eq!(storage.signatures().unwrap(), vec!["sig".to_string()]);
+
+        storage.delete("sig").unwrap();
+        assert!(storage.get("sig").unwrap().is_none());
+    }
+}
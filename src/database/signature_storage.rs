@@ -1,6 +1,10 @@
 use anyhow::Result;
+use moka::sync::Cache;
 use serde::{Serialize, Deserialize};
+use std::sync::{Arc, OnceLock};
+use crate::database::bloom_filter::BloomFilter;
 use crate::database::storage::{StorageManager, StorageResult, KeyValue};
+use crate::transfer_types::{NftTransfer, StakeEvent, SwapRecord, TransactionFailure};
 
 use tracing::{info, debug};
 
@@ -13,6 +17,15 @@ pub struct SignatureTransactionData {
     pub sol_transfers: Vec<SolTransfer>,
     /// 代币转账数据
     pub token_transfers: Vec<TokenTransfer>,
+    /// 质押操作
+    #[serde(default)]
+    pub stake_events: Vec<StakeEvent>,
+    /// 兑换记录（已配对的转出腿与转入腿）
+    #[serde(default)]
+    pub swaps: Vec<SwapRecord>,
+    /// NFT 转账
+    #[serde(default)]
+    pub nft_transfers: Vec<NftTransfer>,
     /// 提取到的地址信息
     pub extracted_addresses: ExtractedAddresses,
     /// 交易时间戳
@@ -21,6 +34,40 @@ pub struct SignatureTransactionData {
     pub slot: u64,
     /// 交易是否成功
     pub is_successful: bool,
+    /// 失败交易的错误分类（成功交易和旧数据中为空）
+    #[serde(default)]
+    pub failure: Option<TransactionFailure>,
+    /// 交易手续费 (lamports)
+    #[serde(default)]
+    pub fee_lamports: u64,
+    /// 消耗的计算单元
+    #[serde(default)]
+    pub compute_units: Option<u64>,
+    /// 手续费支付者地址
+    #[serde(default)]
+    pub fee_payer: String,
+    /// 交易在区块内的序号
+    #[serde(default)]
+    pub transaction_index: Option<u64>,
+    /// 区块时间（整块订阅时提供，此时 `timestamp` 与之相同）
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// 摄取时按缓存价格估算的 USD 价值（未启用价格估值或没有可用价格时为空）
+    #[serde(default)]
+    pub usd_valuation: Option<UsdValuation>,
+}
+
+/// 交易中转账的近似 USD 价值
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsdValuation {
+    /// 与 `sol_transfers` 一一对应，没有价格时为空
+    pub sol_transfers: Vec<Option<f64>>,
+    /// 与 `token_transfers` 一一对应，没有价格时为空
+    pub token_transfers: Vec<Option<f64>>,
+    /// 有价格的转账的 USD 价值之和
+    pub total_usd: f64,
+    /// 价格来源
+    pub provider: String,
 }
 
 /// SOL 转账信息
@@ -34,14 +81,20 @@ pub struct SolTransfer {
     pub amount: u64,
     /// 转账类型（如：系统转账、质押等）
     pub transfer_type: String,
+    /// 是否为 wSOL 包装、解包或 wSOL 转账合并而来
+    #[serde(default)]
+    pub is_wrapped: bool,
+    /// 由 System 转账指令得到时的指令路径（顶层序号，内部指令为 `顶层序号.内部序号`），余额匹配推断的转账为空
+    #[serde(default)]
+    pub instruction_path: Option<String>,
 }
 
 /// 代币转账信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenTransfer {
-    /// 发送方地址
+    /// 发送方代币账户地址
     pub from: String,
-    /// 接收方地址
+    /// 接收方代币账户地址
     pub to: String,
     /// 转账金额
     pub amount: u64,
@@ -53,20 +106,65 @@ pub struct TokenTransfer {
     pub program_id: String,
     /// 转账类型
     pub transfer_type: String,
+    /// 发送方代币账户的所有者钱包
+    #[serde(default)]
+    pub from_owner: Option<String>,
+    /// 接收方代币账户的所有者钱包
+    #[serde(default)]
+    pub to_owner: Option<String>,
+}
+
+impl TokenTransfer {
+    /// 发送方钱包，没有所有者信息时为代币账户
+    pub fn sender_wallet(&self) -> &str {
+        self.from_owner.as_deref().unwrap_or(&self.from)
+    }
+
+    /// 接收方钱包，没有所有者信息时为代币账户
+    pub fn receiver_wallet(&self) -> &str {
+        self.to_owner.as_deref().unwrap_or(&self.to)
+    }
 }
 
 /// 提取到的地址信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// 角色字段按交易消息头和指令划分，旧数据中为空。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExtractedAddresses {
     /// 所有地址
     pub all_addresses: Vec<String>,
+    /// 签名者（第一个为手续费支付者）
+    #[serde(default)]
+    pub signers: Vec<String>,
+    /// 可写账户（含通过地址查找表加载的可写地址）
+    #[serde(default)]
+    pub writable: Vec<String>,
+    /// 只读账户（含通过地址查找表加载的只读地址）
+    #[serde(default)]
+    pub readonly: Vec<String>,
+    /// 顶层指令和内部指令调用的程序
+    #[serde(default)]
+    pub programs: Vec<String>,
 }
 
+/// 最近写入签名缓存的容量
+const RECENT_SIGNATURE_CAPACITY: u64 = 100_000;
+
+/// 载入签名布隆过滤器时每批读取的键数
+const FILTER_LOAD_CHUNK_SIZE: usize = 10_000;
+
 /// 签名存储管理器
+///
+/// 摄取时每笔交易都要判断签名是否已存在。最近写入的签名缓存在内存中，
+/// 重连后重复推送的交易直接命中；启用布隆过滤器后，新签名也不再读库。
 #[derive(Debug, Clone)]
 pub struct SignatureStorage {
     storage: StorageManager,
     signature_prefix: String,
+    /// 最近写入的签名
+    recent: Cache<String, ()>,
+    /// 已存储签名的布隆过滤器，由 `build_filter` 创建，各克隆共享
+    filter: Arc<OnceLock<BloomFilter>>,
 }
 
 impl SignatureStorage {
@@ -75,9 +173,40 @@ impl SignatureStorage {
         Self {
             storage,
             signature_prefix,
+            recent: Cache::new(RECENT_SIGNATURE_CAPACITY),
+            filter: Arc::new(OnceLock::new()),
         }
     }
 
+    /// 创建签名布隆过滤器并载入已有签名，返回载入的签名数
+    ///
+    /// 与地址过滤器相同，只应在负责写入的进程中调用。签名按键分批读取，不会一次载入全部签名。
+    pub fn build_filter(&self, capacity: usize) -> Result<usize> {
+        let filter = self.filter.get_or_init(|| BloomFilter::with_capacity(capacity));
+        let mut loaded = 0;
+        self.storage.for_each_key_chunk_by_prefix(&self.signature_prefix, FILTER_LOAD_CHUNK_SIZE, |keys| {
+            for key in keys {
+                if let Some(signature) = key.strip_prefix(self.signature_prefix.as_str()).filter(|signature| !signature.is_empty()) {
+                    filter.insert(signature);
+                    loaded += 1;
+                }
+            }
+            Ok(())
+        })?;
+        filter.mark_ready();
+        info!(
+            "🌸 签名布隆过滤器已就绪: {} 个签名, 占用 {} MB",
+            loaded,
+            filter.size_bytes() / (1024 * 1024)
+        );
+        Ok(loaded)
+    }
+
+    /// 签名数据的键前缀
+    pub fn signature_prefix(&self) -> &str {
+        &self.signature_prefix
+    }
+
     /// 存储签名交易数据
     pub fn store_signature_data(
         &self, 
@@ -88,7 +217,17 @@ impl SignatureStorage {
         
         debug!("存储签名数据: signature={}, key={}", signature, key);
         
-        self.storage.put(&key, data)
+        self.remember_in_filter(signature);
+        let result = self.storage.put(&key, data)?;
+        self.recent.insert(signature.to_string(), ());
+        Ok(result)
+    }
+
+    /// 写入前把签名记入过滤器，保证已写入的签名不会被判定为不存在
+    fn remember_in_filter(&self, signature: &str) {
+        if let Some(filter) = self.filter.get() {
+            filter.insert(signature);
+        }
     }
 
     /// 根据签名获取交易数据
@@ -100,18 +239,33 @@ impl SignatureStorage {
         self.storage.get(&key)
     }
 
-    /// 检查签名是否已存在
+    /// 检查签名是否已存在，先查最近写入缓存和布隆过滤器，无法确定时才读库
     pub fn signature_exists(&self, signature: &str) -> Result<bool> {
+        if self.recent.contains_key(signature) {
+            return Ok(true);
+        }
+        if self.filter.get().is_some_and(|filter| !filter.might_contain(signature)) {
+            return Ok(false);
+        }
         let key = self.storage.make_key(&self.signature_prefix, signature)?;
         self.storage.exists(&key)
     }
 
+    /// 把签名记入最近签名缓存，返回签名此前是否已在缓存中
+    ///
+    /// 供不写入签名数据的路径（过载降级时只累加槽位汇总）识别重连后重复推送的交易；
+    /// 记入后 `signature_exists` 同样视其为已存在，同一笔交易不会再按完整模式写入一次。
+    pub fn remember_recent(&self, signature: &str) -> bool {
+        !self.recent.entry_by_ref(signature).or_insert(()).is_fresh()
+    }
+
     /// 删除签名数据
     pub fn delete_signature_data(&self, signature: &str) -> Result<StorageResult> {
         let key = self.storage.make_key(&self.signature_prefix, signature)?;
         
         debug!("删除签名数据: signature={}, key={}", signature, key);
         
+        self.recent.invalidate(signature);
         self.storage.delete(&key)
     }
 
@@ -122,6 +276,17 @@ impl SignatureStorage {
         self.storage.get_by_prefix(&self.signature_prefix)
     }
 
+    /// 按签名分批遍历签名数据，每批最多 `chunk_size` 条，不会一次载入全部记录
+    pub fn for_each_signature_chunk(
+        &self,
+        chunk_size: usize,
+        mut f: impl FnMut(Vec<SignatureTransactionData>) -> Result<()>,
+    ) -> Result<()> {
+        self.storage.for_each_chunk_by_prefix::<SignatureTransactionData>(&self.signature_prefix, chunk_size, |chunk| {
+            f(chunk.into_iter().map(|item| item.value).collect())
+        })
+    }
+
     /// 获取所有签名键
     pub fn get_all_signature_keys(&self) -> Result<Vec<String>> {
         let keys = self.storage.get_keys_by_prefix(&self.signature_prefix)?;
@@ -142,21 +307,44 @@ impl SignatureStorage {
         Ok(signatures)
     }
 
+    /// 按签名前缀查找完整签名（按字典序，最多 `limit` 个）
+    ///
+    /// 用于根据从浏览器复制的部分签名定位交易，只做有界的前缀迭代。
+    pub fn search_signatures_by_prefix(&self, partial: &str, limit: usize) -> Result<Vec<String>> {
+        let key_prefix = self.storage.make_key(&self.signature_prefix, partial)?;
+        let keys = self.storage.get_keys_by_prefix_limit(&key_prefix, limit)?;
+
+        let signatures: Vec<String> = keys
+            .into_iter()
+            .map(|key| key[self.signature_prefix.len()..].to_string())
+            .collect();
+
+        debug!("签名前缀 {} 匹配到 {} 个签名", partial, signatures.len());
+        Ok(signatures)
+    }
+
     /// 批量存储签名数据
     pub fn batch_store_signatures(
         &self, 
         signatures_data: Vec<(String, SignatureTransactionData)>
     ) -> Result<StorageResult> {
         let mut items = Vec::new();
+        let mut signatures = Vec::with_capacity(signatures_data.len());
         
         for (signature, data) in signatures_data {
             let key = self.storage.make_key(&self.signature_prefix, &signature)?;
+            self.remember_in_filter(&signature);
             items.push((key, data));
+            signatures.push(signature);
         }
         
         info!("批量存储 {} 个签名数据", items.len());
         
-        self.storage.batch_put(items)
+        let result = self.storage.batch_put(items)?;
+        for signature in signatures {
+            self.recent.insert(signature, ());
+        }
+        Ok(result)
     }
 
     /// 根据时间范围查找签名
@@ -225,12 +413,20 @@ impl SignatureTransactionData {
             signature,
             sol_transfers: Vec::new(),
             token_transfers: Vec::new(),
-            extracted_addresses: ExtractedAddresses {
-                all_addresses: Vec::new(),
-            },
+            stake_events: Vec::new(),
+            swaps: Vec::new(),
+            nft_transfers: Vec::new(),
+            extracted_addresses: ExtractedAddresses::default(),
             timestamp,
             slot,
             is_successful,
+            failure: None,
+            fee_lamports: 0,
+            compute_units: None,
+            fee_payer: String::new(),
+            transaction_index: None,
+            block_time: None,
+            usd_valuation: None,
         }
     }
 
@@ -244,8 +440,80 @@ impl SignatureTransactionData {
         self.token_transfers.push(transfer);
     }
 
+    /// 设置质押操作
+    pub fn set_stake_events(&mut self, events: Vec<StakeEvent>) {
+        self.stake_events = events;
+    }
+
+    /// 设置兑换记录
+    pub fn set_swaps(&mut self, swaps: Vec<SwapRecord>) {
+        self.swaps = swaps;
+    }
+
+    /// 设置 NFT 转账
+    pub fn set_nft_transfers(&mut self, transfers: Vec<NftTransfer>) {
+        self.nft_transfers = transfers;
+    }
+
+    /// 设置手续费信息
+    pub fn set_fee_info(&mut self, fee_lamports: u64, compute_units: Option<u64>, fee_payer: String) {
+        self.fee_lamports = fee_lamports;
+        self.compute_units = compute_units;
+        self.fee_payer = fee_payer;
+    }
+
+    /// 记录失败原因
+    pub fn set_failure(&mut self, failure: TransactionFailure) {
+        self.is_successful = false;
+        self.failure = Some(failure);
+    }
+
+    /// 设置块内序号与区块时间
+    pub fn set_block_position(&mut self, transaction_index: u64, block_time: Option<i64>) {
+        self.transaction_index = Some(transaction_index);
+        self.block_time = block_time;
+    }
+
     /// 设置提取的地址信息
     pub fn set_extracted_addresses(&mut self, addresses: ExtractedAddresses) {
         self.extracted_addresses = addresses;
     }
-} 
\ No newline at end of file
+
+    /// 设置 USD 估值
+    pub fn set_usd_valuation(&mut self, valuation: Option<UsdValuation>) {
+        self.usd_valuation = valuation;
+    }
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_signature_exists_uses_recent_cache_and_filter() {
+        let dir = TempDir::new("signature-storage-test");
+        let signatures = SignatureStorage::new(StorageManager::new(dir.path(), 6).unwrap(), "SIG001".to_string());
+        let data = |signature: &str| SignatureTransactionData::new(signature.to_string(), 1_704_067_200, 1, true);
+
+        signatures.store_signature_data("sigA", &data("sigA")).unwrap();
+        assert_eq!(signatures.build_filter(1_000).unwrap(), 1);
+        assert!(signatures.signature_exists("sigA").unwrap());
+        assert!(!signatures.signature_exists("sigB").unwrap());
+
+        // 过滤器建立后写入的签名同样可见，删除后缓存失效
+        signatures.batch_store_signatures(vec![("sigB".to_string(), data("sigB"))]).unwrap();
+        assert!(signatures.signature_exists("sigB").unwrap());
+        signatures.delete_signature_data("sigB").unwrap();
+        assert!(!signatures.signature_exists("sigB").unwrap());
+    }
+
+    #[test]
+    fn test_remember_recent_reports_replays() {
+        let dir = TempDir::new("signature-storage-test");
+        let signatures = SignatureStorage::new(StorageManager::new(dir.path(), 6).unwrap(), "SIG001".to_string());
+
+        assert!(!signatures.remember_recent("sigA"));
+        assert!(signatures.remember_recent("sigA"));
+        assert!(signatures.signature_exists("sigA").unwrap());
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::Result;
+
+use crate::database::signature_storage::SignatureTransactionData;
+use crate::database::storage::StorageManager;
+
+/// 签名时间索引键前缀
+pub const SIGNATURE_TIME_INDEX_KEY_PREFIX: &str = "SIGT01";
+
+/// 回填完成标记（不以数字开头，不会出现在索引扫描结果中）
+const BACKFILL_MARKER: &str = "backfilled";
+
+/// 时间索引条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeIndexEntry {
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// 按时间排序的签名二级索引
+///
+/// 键格式为 `SIGT01{u64::MAX - timestamp:020}:{u64::MAX - slot:020}:{signature}`，
+/// 时间戳和槽位都取反后按前缀顺序扫描即为从新到旧。槽位编码在键里，
+/// 按槽位过滤时只需扫描键，不读取签名数据。
+#[derive(Debug, Clone)]
+pub struct SignatureTimeIndexStorage {
+    storage: StorageManager,
+}
+
+impl SignatureTimeIndexStorage {
+    /// 创建新的签名时间索引实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 记录一笔交易
+    pub fn record(&self, signature: &str, slot: u64, timestamp: i64) -> Result<()> {
+        self.storage.put(&self.entry_key(signature, slot, timestamp)?, &slot)?;
+        Ok(())
+    }
+
+    /// 删除一笔交易的索引
+    pub fn remove(&self, signature: &str, slot: u64, timestamp: i64) -> Result<()> {
+        self.storage.delete(&self.entry_key(signature, slot, timestamp)?)?;
+        Ok(())
+    }
+
+    /// 最近的签名，从新到旧，只读取前 `offset + limit` 个键
+    pub fn recent_signatures(&self, offset: usize, limit: usize) -> Result<Vec<String>> {
+        let keys = self
+            .storage
+            .get_keys_by_prefix_limit(SIGNATURE_TIME_INDEX_KEY_PREFIX, offset.saturating_add(limit))?;
+        Ok(keys
+            .iter()
+            .skip(offset)
+            .filter_map(|key| Self::parse_key(key).map(|entry| entry.signature))
+            .collect())
+    }
+
+    /// 最旧的索引条目，从旧到新，只逆序读取 `limit` 个键
+    pub fn oldest_entries(&self, limit: usize) -> Result<Vec<TimeIndexEntry>> {
+        let keys = self.storage.get_keys_by_prefix_rev_limit(SIGNATURE_TIME_INDEX_KEY_PREFIX, limit)?;
+        Ok(keys.iter().filter_map(|key| Self::parse_key(key)).collect())
+    }
+
+    /// 槽位范围内（两端均包含）的全部签名，从新到旧
+    pub fn signatures_in_slot_range(&self, min_slot: Option<u64>, max_slot: Option<u64>) -> Result<Vec<String>> {
+        let keys = self.storage.get_keys_by_prefix(SIGNATURE_TIME_INDEX_KEY_PREFIX)?;
+        Ok(keys
+            .iter()
+            .filter_map(|key| Self::parse_key(key))
+            .filter(|entry| min_slot.is_none_or(|min| entry.slot >= min) && max_slot.is_none_or(|max| entry.slot <= max))
+            .map(|entry| entry.signature)
+            .collect())
+    }
+
+    /// 升级前写入的签名是否已回填时间索引
+    pub fn is_backfilled(&self) -> Result<bool> {
+        self.storage.exists(&self.storage.make_key(SIGNATURE_TIME_INDEX_KEY_PREFIX, BACKFILL_MARKER)?)
+    }
+
+    /// 批量记录一批签名，用于回填
+    pub fn record_batch<'a>(&self, signatures: impl Iterator<Item = &'a SignatureTransactionData>) -> Result<()> {
+        let items = signatures
+            .map(|data| Ok((self.entry_key(&data.signature, data.slot, data.timestamp)?, data.slot)))
+            .collect::<Result<Vec<_>>>()?;
+        if !items.is_empty() {
+            self.storage.batch_put(items)?;
+        }
+        Ok(())
+    }
+
+    /// 标记回填完成，之后启动时不再扫描签名数据
+    pub fn mark_backfilled(&self) -> Result<()> {
+        self.storage.put(&self.storage.make_key(SIGNATURE_TIME_INDEX_KEY_PREFIX, BACKFILL_MARKER)?, &true)?;
+        Ok(())
+    }
+
+    fn entry_key(&self, signature: &str, slot: u64, timestamp: i64) -> Result<String> {
+        self.storage.make_key(
+            SIGNATURE_TIME_INDEX_KEY_PREFIX,
+            &format!("{:020}:{:020}:{}", u64::MAX - timestamp.max(0) as u64, u64::MAX - slot, signature),
+        )
+    }
+
+    /// 解析索引键，标记键等非索引键返回 None
+    fn parse_key(key: &str) -> Option<TimeIndexEntry> {
+        let mut parts = key.strip_prefix(SIGNATURE_TIME_INDEX_KEY_PREFIX)?.splitn(3, ':');
+        let timestamp = (u64::MAX - parts.next()?.parse::<u64>().ok()?) as i64;
+        let slot = u64::MAX - parts.next()?.parse::<u64>().ok()?;
+        Some(TimeIndexEntry { signature: parts.next()?.to_string(), slot, timestamp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_recent_signatures_and_slot_filter() {
+        let dir = TempDir::new("signature-time-index-test");
+        let index = SignatureTimeIndexStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        index.record("sigB", 200, 1_704_067_300).unwrap();
+        index.record("sigA", 100, 1_704_067_200).unwrap();
+        index.record("sigC", 300, 1_704_067_400).unwrap();
+        let backfilled = [SignatureTransactionData::new("sigD".to_string(), 1_704_067_500, 400, true)];
+        assert!(!index.is_backfilled().unwrap());
+        index.record_batch(backfilled.iter()).unwrap();
+        index.mark_backfilled().unwrap();
+        assert!(index.is_backfilled().unwrap());
+
+        assert_eq!(index.recent_signatures(0, 2).unwrap(), vec!["sigD", "sigC"]);
+        assert_eq!(index.recent_signatures(3, 10).unwrap(), vec!["sigA"]);
+        assert_eq!(
+            index.oldest_entries(2).unwrap(),
+            vec![
+                TimeIndexEntry { signature: "sigA".to_string(), slot: 100, timestamp: 1_704_067_200 },
+                TimeIndexEntry { signature: "sigB".to_string(), slot: 200, timestamp: 1_704_067_300 },
+            ]
+        );
+        assert_eq!(index.signatures_in_slot_range(Some(150), Some(300)).unwrap(), vec!["sigC", "sigB"]);
+
+        index.remove("sigC", 300, 1_704_067_400).unwrap();
+        assert_eq!(index.signatures_in_slot_range(None, None).unwrap(), vec!["sigD", "sigB", "sigA"]);
+    }
+}
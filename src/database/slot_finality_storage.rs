@@ -0,0 +1,163 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 槽位最终性键前缀
+pub const SLOT_FINALITY_KEY_PREFIX: &str = "SLFN01";
+
+const FINALIZED_KEY: &str = "finalized";
+const CONFIRMED_KEY: &str = "confirmed";
+const ORPHAN_KEY_PREFIX: &str = "orphan:";
+
+/// 交易所在槽位的最终性状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FinalityStatus {
+    /// 尚未开始跟踪槽位状态
+    Unknown,
+    /// 已处理，尚未确认
+    Processed,
+    /// 已被超级多数确认
+    Confirmed,
+    /// 已最终确定
+    Finalized,
+    /// 槽位不在最终链上（分叉被丢弃或槽位死亡），其中的交易不会生效
+    Orphaned,
+}
+
+impl FinalityStatus {
+    /// 解析 `unknown` / `processed` / `confirmed` / `finalized` / `orphaned`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "unknown" => Some(Self::Unknown),
+            "processed" => Some(Self::Processed),
+            "confirmed" => Some(Self::Confirmed),
+            "finalized" => Some(Self::Finalized),
+            "orphaned" => Some(Self::Orphaned),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Processed => "processed",
+            Self::Confirmed => "confirmed",
+            Self::Finalized => "finalized",
+            Self::Orphaned => "orphaned",
+        }
+    }
+}
+
+/// 被判定为孤立的槽位
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedSlot {
+    pub slot: u64,
+    pub parent: Option<u64>,
+    /// 判定原因：dead（槽位死亡）或 skipped（被最终链跳过）
+    pub reason: String,
+    /// 该槽位中已存储的交易数
+    pub transaction_count: usize,
+    /// 判定时间
+    pub detected_at: i64,
+}
+
+/// 某一时刻的槽位最终性快照，用于批量判断交易的最终性
+#[derive(Debug, Clone, Default)]
+pub struct FinalityView {
+    pub finalized_slot: u64,
+    pub confirmed_slot: u64,
+    pub orphaned_slots: HashSet<u64>,
+}
+
+impl FinalityView {
+    /// 判断槽位的最终性状态
+    pub fn status_of(&self, slot: u64) -> FinalityStatus {
+        if self.orphaned_slots.contains(&slot) {
+            FinalityStatus::Orphaned
+        } else if self.finalized_slot == 0 && self.confirmed_slot == 0 {
+            FinalityStatus::Unknown
+        } else if slot <= self.finalized_slot {
+            FinalityStatus::Finalized
+        } else if slot <= self.confirmed_slot {
+            FinalityStatus::Confirmed
+        } else {
+            FinalityStatus::Processed
+        }
+    }
+}
+
+/// 槽位最终性存储
+///
+/// 只保存最新的最终确定槽位、最新的确认槽位和孤立槽位列表：
+/// 不在孤立列表中且不超过最终确定槽位的槽位即视为已最终确定。
+#[derive(Debug, Clone)]
+pub struct SlotFinalityStorage {
+    storage: StorageManager,
+}
+
+impl SlotFinalityStorage {
+    /// 创建新的槽位最终性存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 更新最新的最终确定槽位（只会前进）
+    pub fn set_finalized_slot(&self, slot: u64) -> Result<()> {
+        self.advance(FINALIZED_KEY, slot)
+    }
+
+    /// 更新最新的确认槽位（只会前进）
+    pub fn set_confirmed_slot(&self, slot: u64) -> Result<()> {
+        self.advance(CONFIRMED_KEY, slot)
+    }
+
+    /// 获取最新的最终确定槽位
+    pub fn get_finalized_slot(&self) -> Result<u64> {
+        Ok(self.storage.get::<u64>(&self.make_key(FINALIZED_KEY)?)?.unwrap_or(0))
+    }
+
+    /// 标记孤立槽位
+    pub fn mark_orphaned(&self, orphan: &OrphanedSlot) -> Result<()> {
+        self.storage.put(&self.orphan_key(orphan.slot)?, orphan)?;
+        Ok(())
+    }
+
+    /// 读取当前的最终性快照
+    pub fn view(&self) -> Result<FinalityView> {
+        let prefix = self.make_key(ORPHAN_KEY_PREFIX)?;
+        let orphaned_slots = self
+            .storage
+            .get_keys_by_prefix(&prefix)?
+            .iter()
+            .filter_map(|key| key[prefix.len()..].parse().ok())
+            .collect();
+        Ok(FinalityView {
+            finalized_slot: self.get_finalized_slot()?,
+            confirmed_slot: self.storage.get::<u64>(&self.make_key(CONFIRMED_KEY)?)?.unwrap_or(0),
+            orphaned_slots,
+        })
+    }
+
+    fn advance(&self, name: &str, slot: u64) -> Result<()> {
+        let key = self.make_key(name)?;
+        if self.storage.get::<u64>(&key)?.is_some_and(|current| current >= slot) {
+            return Ok(());
+        }
+        self.storage.put(&key, &slot)?;
+        debug!("{} 槽位前进到 {}", name, slot);
+        Ok(())
+    }
+
+    fn orphan_key(&self, slot: u64) -> Result<String> {
+        // 补零使键的字典序与槽位顺序一致
+        self.make_key(&format!("{}{:020}", ORPHAN_KEY_PREFIX, slot))
+    }
+
+    fn make_key(&self, key: &str) -> Result<String> {
+        self.storage.make_key(SLOT_FINALITY_KEY_PREFIX, key)
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 槽位交易索引键前缀
+pub const SLOT_INDEX_KEY_PREFIX: &str = "SLTX01";
+
+/// 槽位索引中的一笔交易
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlotTransactionEntry {
+    /// 交易签名
+    pub signature: String,
+    /// 交易在区块内的序号
+    pub transaction_index: Option<u64>,
+    /// 交易时间戳
+    pub timestamp: i64,
+    /// 交易是否成功
+    pub is_successful: bool,
+    /// SOL转账笔数
+    pub sol_transfer_count: usize,
+    /// 代币转账笔数
+    pub token_transfer_count: usize,
+}
+
+/// 槽位 → 交易签名的二级索引
+///
+/// 键格式为 `SLTX01{slot:020}:{signature}`，按槽位前缀扫描即可得到该槽位
+/// 采集到的全部交易，写入时不需要读改写。
+#[derive(Debug, Clone)]
+pub struct SlotIndexStorage {
+    storage: StorageManager,
+}
+
+impl SlotIndexStorage {
+    /// 创建新的槽位索引存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 将交易加入所在槽位的索引
+    pub fn add_transaction(&self, slot: u64, entry: &SlotTransactionEntry) -> Result<()> {
+        let key = self
+            .storage
+            .make_key(SLOT_INDEX_KEY_PREFIX, &format!("{:020}:{}", slot, entry.signature))?;
+        self.storage.put(&key, entry)?;
+        debug!("槽位 {} 索引加入交易 {}", slot, entry.signature);
+        Ok(())
+    }
+
+    /// 从槽位索引中删除交易
+    pub fn remove_transaction(&self, slot: u64, signature: &str) -> Result<()> {
+        let key = self.storage.make_key(SLOT_INDEX_KEY_PREFIX, &format!("{:020}:{}", slot, signature))?;
+        self.storage.delete(&key)?;
+        Ok(())
+    }
+
+    /// 获取槽位内的全部交易，按块内序号排序（无序号的排在最后）
+    pub fn get_slot_transactions(&self, slot: u64) -> Result<Vec<SlotTransactionEntry>> {
+        let prefix = format!("{}{:020}:", SLOT_INDEX_KEY_PREFIX, slot);
+        let mut entries: Vec<SlotTransactionEntry> = self
+            .storage
+            .get_by_prefix::<SlotTransactionEntry>(&prefix)?
+            .into_iter()
+            .map(|item| item.value)
+            .collect();
+        entries.sort_by_key(|entry| (entry.transaction_index.unwrap_or(u64::MAX), entry.signature.clone()));
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn entry(signature: &str, transaction_index: Option<u64>) -> SlotTransactionEntry {
+        SlotTransactionEntry {
+            signature: signature.to_string(),
+            transaction_index,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_slot_transactions_ordered_by_index() {
+        let dir = TempDir::new("slot-index-test");
+        let storage = SlotIndexStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        storage.add_transaction(100, &entry("sigB", Some(7))).unwrap();
+        storage.add_transaction(100, &entry("sigA", Some(12))).unwrap();
+        storage.add_transaction(100, &entry("sigC", None)).unwrap();
+        storage.add_transaction(1_000, &entry("sigD", Some(0))).unwrap();
+        // 重复写入同一签名不会产生重复条目
+        storage.add_transaction(100, &entry("sigB", Some(7))).unwrap();
+
+        let signatures: Vec<String> = storage
+            .get_slot_transactions(100)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.signature)
+            .collect();
+        assert_eq!(signatures, vec!["sigB", "sigA", "sigC"]);
+        assert_eq!(storage.get_slot_transactions(1_000).unwrap().len(), 1);
+        assert!(storage.get_slot_transactions(10).unwrap().is_empty());
+    }
+}
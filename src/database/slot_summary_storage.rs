@@ -0,0 +1,94 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+
+/// 槽位汇总键前缀
+pub const SLOT_SUMMARY_KEY_PREFIX: &str = "SLTS01";
+
+/// 槽位汇总（过载降级时代替交易明细保存）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlotSummary {
+    /// 槽位
+    pub slot: u64,
+    /// 汇总的交易数
+    pub transaction_count: u64,
+    /// SOL转账笔数
+    pub sol_transfer_count: u64,
+    /// 代币转账笔数
+    pub token_transfer_count: u64,
+    /// SOL转账总金额（lamports）
+    pub total_lamports: u64,
+}
+
+/// 槽位汇总存储管理器
+#[derive(Debug, Clone)]
+pub struct SlotSummaryStorage {
+    storage: StorageManager,
+}
+
+impl SlotSummaryStorage {
+    /// 创建新的槽位汇总存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 将一笔交易累加到所在槽位的汇总中
+    pub fn add_transaction(
+        &self,
+        slot: u64,
+        sol_transfer_count: usize,
+        token_transfer_count: usize,
+        lamports: u64,
+    ) -> Result<()> {
+        let key = self.make_key(slot)?;
+        let mut summary = self
+            .storage
+            .get::<SlotSummary>(&key)?
+            .unwrap_or(SlotSummary { slot, ..Default::default() });
+
+        summary.transaction_count += 1;
+        summary.sol_transfer_count += sol_transfer_count as u64;
+        summary.token_transfer_count += token_transfer_count as u64;
+        summary.total_lamports += lamports;
+
+        self.storage.put(&key, &summary)?;
+        debug!("槽位 {} 汇总更新: {} 笔交易", slot, summary.transaction_count);
+        Ok(())
+    }
+
+    /// 获取槽位汇总
+    pub fn get_summary(&self, slot: u64) -> Result<Option<SlotSummary>> {
+        self.storage.get(&self.make_key(slot)?)
+    }
+
+    /// 槽位使用定长十进制编码，保证键按槽位顺序排列
+    fn make_key(&self, slot: u64) -> Result<String> {
+        self.storage.make_key(SLOT_SUMMARY_KEY_PREFIX, &format!("{:020}", slot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_add_transaction_accumulates_per_slot() {
+        let dir = TempDir::new("slot-summary-test");
+        let storage = SlotSummaryStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+
+        storage.add_transaction(100, 2, 0, 3_000).unwrap();
+        storage.add_transaction(100, 1, 1, 500).unwrap();
+        storage.add_transaction(101, 0, 1, 0).unwrap();
+
+        let summary = storage.get_summary(100).unwrap().unwrap();
+        assert_eq!(summary.transaction_count, 2);
+        assert_eq!(summary.sol_transfer_count, 3);
+        assert_eq!(summary.token_transfer_count, 1);
+        assert_eq!(summary.total_lamports, 3_500);
+        assert_eq!(storage.get_summary(101).unwrap().unwrap().transaction_count, 1);
+        assert!(storage.get_summary(102).unwrap().is_none());
+    }
+}
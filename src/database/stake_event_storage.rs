@@ -0,0 +1,166 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+use crate::transfer_types::StakeEvent;
+
+/// 质押操作键前缀
+pub const STAKE_EVENT_KEY_PREFIX: &str = "STKE01";
+
+/// 地址的质押操作列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeEventList {
+    /// 地址
+    pub address: String,
+    /// 质押操作（索引0是最新的）
+    pub events: Vec<StakeSlotEvent>,
+}
+
+/// 带槽位的质押操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeSlotEvent {
+    /// 交易槽位
+    pub slot: u64,
+    /// 质押操作
+    #[serde(flatten)]
+    pub event: StakeEvent,
+}
+
+/// 质押操作存储管理器
+///
+/// 每个事件保存在其涉及的每个地址（质押账户、授权者、投票账户、接收方）名下。
+#[derive(Debug, Clone)]
+pub struct StakeEventStorage {
+    storage: StorageManager,
+    max_events: usize,
+}
+
+impl StakeEventStorage {
+    /// 创建新的质押操作存储实例
+    pub fn new(storage: StorageManager, max_events: usize) -> Self {
+        Self { storage, max_events }
+    }
+
+    /// 记录一笔交易中的质押操作
+    pub fn record_events(&self, slot: u64, events: &[StakeEvent]) -> Result<()> {
+        for event in events {
+            for address in event.addresses() {
+                self.add_event(address, StakeSlotEvent { slot, event: event.clone() })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 添加质押操作，同一签名中的同一操作只记录一次
+    fn add_event(&self, address: &str, event: StakeSlotEvent) -> Result<()> {
+        let key = self.make_key(address)?;
+
+        let mut list = self.storage.get::<StakeEventList>(&key)?.unwrap_or(StakeEventList {
+            address: address.to_string(),
+            events: Vec::new(),
+        });
+
+        let duplicate = list.events.iter().any(|existing| {
+            existing.event.signature == event.event.signature
+                && existing.event.kind == event.event.kind
+                && existing.event.stake_account == event.event.stake_account
+        });
+        if duplicate {
+            return Ok(());
+        }
+
+        list.events.insert(0, event);
+        list.events.truncate(self.max_events);
+
+        self.storage.put(&key, &list)?;
+        debug!("地址 {} 质押操作更新，当前 {} 条", address, list.events.len());
+        Ok(())
+    }
+
+    /// 获取地址的质押操作（最新的在前）
+    pub fn get_events(&self, address: &str) -> Result<Vec<StakeSlotEvent>> {
+        Ok(self
+            .storage
+            .get::<StakeEventList>(&self.make_key(address)?)?
+            .map(|list| list.events)
+            .unwrap_or_default())
+    }
+
+    fn make_key(&self, address: &str) -> Result<String> {
+        self.storage.make_key(STAKE_EVENT_KEY_PREFIX, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transfer_types::StakeEventKind;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_events_indexed_by_every_address() {
+        let dir = TempDir::new("stake-event-test");
+        let storage = StakeEventStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 10);
+
+        let delegate = StakeEvent {
+            signature: "sig1".to_string(),
+            kind: StakeEventKind::Delegate,
+            stake_account: "stake".to_string(),
+            authority: "owner".to_string(),
+            vote_account: Some("vote".to_string()),
+            recipient: None,
+            lamports: None,
+            timestamp: 100,
+        };
+        let withdraw = StakeEvent {
+            signature: "sig2".to_string(),
+            kind: StakeEventKind::Withdraw,
+            vote_account: None,
+            recipient: Some("owner".to_string()),
+            lamports: Some(5_000),
+            timestamp: 200,
+            ..delegate.clone()
+        };
+
+        storage.record_events(1, &[delegate.clone()]).unwrap();
+        storage.record_events(2, &[withdraw]).unwrap();
+        // 重复处理同一交易不会重复记录
+        storage.record_events(1, &[delegate]).unwrap();
+
+        let owner_events = storage.get_events("owner").unwrap();
+        assert_eq!(owner_events.len(), 2);
+        assert_eq!(owner_events[0].event.kind, StakeEventKind::Withdraw);
+        assert_eq!(owner_events[0].slot, 2);
+        assert_eq!(storage.get_events("vote").unwrap().len(), 1);
+        assert_eq!(storage.get_events("stake").unwrap().len(), 2);
+        assert!(storage.get_events("nobody").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_events_capped_newest_first() {
+        let dir = TempDir::new("stake-event-cap-test");
+        let storage = StakeEventStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 3);
+
+        for slot in 1..=5u64 {
+            let event = StakeEvent {
+                signature: format!("sig{}", slot),
+                kind: StakeEventKind::Delegate,
+                stake_account: "stake".to_string(),
+                authority: "owner".to_string(),
+                vote_account: None,
+                recipient: None,
+                lamports: None,
+                timestamp: slot as u32 * 100,
+            };
+            storage.record_events(slot, &[event]).unwrap();
+        }
+
+        // 超出上限时淘汰最旧的操作，最新的在前
+        let events = storage.get_events("stake").unwrap();
+        let slots: Vec<u64> = events.iter().map(|e| e.slot).collect();
+        assert_eq!(slots, vec![5, 4, 3]);
+        assert_eq!(events[0].event.signature, "sig5");
+        assert_eq!(storage.get_events("owner").unwrap().len(), 3);
+    }
+}
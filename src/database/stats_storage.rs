@@ -0,0 +1,284 @@
+use anyhow::Result;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{debug, info};
+
+use crate::database::signature_storage::SignatureTransactionData;
+use crate::database::storage::StorageManager;
+
+/// 预计算统计键前缀
+pub const STATS_KEY_PREFIX: &str = "STAT01";
+
+/// 全局计数器键（前缀之后的部分）
+const TOTALS_KEY: &str = "totals";
+
+/// 每日计数键前缀（前缀之后的部分），后接 YYYY-MM-DD
+const DAILY_KEY: &str = "day:";
+
+/// 全局统计计数器
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsTotals {
+    /// 签名总数
+    pub total_signatures: u64,
+    /// SOL转账总数
+    pub total_sol_transfers: u64,
+    /// 代币转账总数
+    pub total_token_transfers: u64,
+    /// 成功交易数
+    pub successful_transactions: u64,
+    /// 失败交易数
+    pub failed_transactions: u64,
+    /// 已估值转账的 USD 成交额（启用价格估值后才累计）
+    #[serde(default)]
+    pub total_usd_volume: f64,
+    /// 失败交易按错误分类计数（见 `TransactionFailure::category`），没有记录错误的旧数据不计入
+    #[serde(default)]
+    pub failures_by_category: BTreeMap<String, u64>,
+}
+
+/// 每日转账计数（按交易时间的 UTC 日期汇总，只增不减）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyTransferStats {
+    /// 日期（YYYY-MM-DD，UTC）
+    pub date: String,
+    /// 交易数
+    pub transaction_count: u64,
+    /// SOL转账笔数
+    pub sol_transfer_count: u64,
+    /// 代币转账笔数
+    pub token_transfer_count: u64,
+    /// 已估值转账的 USD 成交额
+    #[serde(default)]
+    pub usd_volume: f64,
+}
+
+/// 预计算统计存储管理器
+///
+/// 摄取时递增计数器，统计接口直接读取，不再扫描签名数据。
+/// 删除签名时只回退全局计数，每日计数反映的是历史摄取量。
+#[derive(Debug, Clone)]
+pub struct StatsStorage {
+    storage: StorageManager,
+}
+
+impl StatsStorage {
+    /// 创建新的统计存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self { storage }
+    }
+
+    /// 记录一笔新存储的交易
+    pub fn record_transaction(&self, data: &SignatureTransactionData) -> Result<()> {
+        let mut totals = self.get_totals()?;
+        Self::apply(&mut totals, data, true);
+        self.storage.put(&self.totals_key()?, &totals)?;
+
+        let date = Self::date_of(data.timestamp);
+        let key = self.daily_key(&date)?;
+        let mut daily = self
+            .storage
+            .get::<DailyTransferStats>(&key)?
+            .unwrap_or(DailyTransferStats { date: date.clone(), ..Default::default() });
+        daily.transaction_count += 1;
+        daily.sol_transfer_count += data.sol_transfers.len() as u64;
+        daily.token_transfer_count += data.token_transfers.len() as u64;
+        daily.usd_volume += Self::usd_volume_of(data);
+        self.storage.put(&key, &daily)?;
+
+        debug!("统计计数更新: {} 个签名, {} 当天 {} 笔交易", totals.total_signatures, date, daily.transaction_count);
+        Ok(())
+    }
+
+    /// 记录被删除的交易，回退全局计数
+    pub fn record_removals(&self, removed: &[SignatureTransactionData]) -> Result<()> {
+        if removed.is_empty() {
+            return Ok(());
+        }
+        let mut totals = self.get_totals()?;
+        for data in removed {
+            Self::apply(&mut totals, data, false);
+        }
+        self.storage.put(&self.totals_key()?, &totals)?;
+        Ok(())
+    }
+
+    /// 获取全局计数，尚未记录时全部为 0
+    pub fn get_totals(&self) -> Result<StatsTotals> {
+        Ok(self.storage.get(&self.totals_key()?)?.unwrap_or_default())
+    }
+
+    /// 是否已经有计数器（旧数据库升级后需要先回填）
+    pub fn is_initialized(&self) -> Result<bool> {
+        self.storage.exists(&self.totals_key()?)
+    }
+
+    /// 获取最近若干天的转账计数（最新的在前）
+    pub fn get_recent_days(&self, days: usize) -> Result<Vec<DailyTransferStats>> {
+        // 日期键按字典序即时间顺序排列
+        let prefix = self.daily_key("")?;
+        let items = self.storage.get_by_prefix::<DailyTransferStats>(&prefix)?;
+        Ok(items.into_iter().rev().take(days).map(|item| item.value).collect())
+    }
+
+    /// 根据已有签名数据重建全部计数器
+    ///
+    /// `scan` 把每笔签名数据交给传入的回调累加，调用方可以分批读取，不必一次载入全部签名；
+    /// 内存中只保留全局计数和每日计数。
+    pub fn rebuild(
+        &self,
+        scan: impl FnOnce(&mut dyn FnMut(&SignatureTransactionData)) -> Result<()>,
+    ) -> Result<StatsTotals> {
+        let mut totals = StatsTotals::default();
+        let mut days: BTreeMap<String, DailyTransferStats> = BTreeMap::new();
+        scan(&mut |data| {
+            Self::apply(&mut totals, data, true);
+            let date = Self::date_of(data.timestamp);
+            let daily = days
+                .entry(date.clone())
+                .or_insert_with(|| DailyTransferStats { date, ..Default::default() });
+            daily.transaction_count += 1;
+            daily.sol_transfer_count += data.sol_transfers.len() as u64;
+            daily.token_transfer_count += data.token_transfers.len() as u64;
+            daily.usd_volume += Self::usd_volume_of(data);
+        })?;
+
+        let mut items = Vec::with_capacity(days.len());
+        for (date, daily) in days {
+            items.push((self.daily_key(&date)?, daily));
+        }
+        self.storage.batch_put(items)?;
+        self.storage.put(&self.totals_key()?, &totals)?;
+
+        info!("📈 统计计数重建完成: {} 个签名", totals.total_signatures);
+        Ok(totals)
+    }
+
+    /// 把一笔交易计入（`add = true`）或移出全局计数
+    fn apply(totals: &mut StatsTotals, data: &SignatureTransactionData, add: bool) {
+        let update = |value: &mut u64, delta: u64| {
+            *value = if add { *value + delta } else { value.saturating_sub(delta) };
+        };
+        update(&mut totals.total_signatures, 1);
+        update(&mut totals.total_sol_transfers, data.sol_transfers.len() as u64);
+        update(&mut totals.total_token_transfers, data.token_transfers.len() as u64);
+        if data.is_successful {
+            update(&mut totals.successful_transactions, 1);
+        } else {
+            update(&mut totals.failed_transactions, 1);
+            if let Some(failure) = &data.failure {
+                update(totals.failures_by_category.entry(failure.category()).or_default(), 1);
+            }
+        }
+        let usd_volume = Self::usd_volume_of(data);
+        totals.total_usd_volume = if add {
+            totals.total_usd_volume + usd_volume
+        } else {
+            (totals.total_usd_volume - usd_volume).max(0.0)
+        };
+    }
+
+    /// 交易中已估值转账的 USD 价值
+    fn usd_volume_of(data: &SignatureTransactionData) -> f64 {
+        data.usd_valuation.as_ref().map_or(0.0, |valuation| valuation.total_usd)
+    }
+
+    fn totals_key(&self) -> Result<String> {
+        self.storage.make_key(STATS_KEY_PREFIX, TOTALS_KEY)
+    }
+
+    fn daily_key(&self, date: &str) -> Result<String> {
+        self.storage.make_key(STATS_KEY_PREFIX, &format!("{}{}", DAILY_KEY, date))
+    }
+
+    fn date_of(timestamp: i64) -> String {
+        DateTime::from_timestamp(timestamp, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::signature_storage::{SolTransfer, UsdValuation};
+    use crate::test_util::TempDir;
+
+    fn transaction(signature: &str, timestamp: i64, is_successful: bool, sol_transfers: usize) -> SignatureTransactionData {
+        let mut data = SignatureTransactionData::new(signature.to_string(), timestamp, 1, is_successful);
+        for _ in 0..sol_transfers {
+            data.add_sol_transfer(SolTransfer {
+                from: "alice".to_string(),
+                to: "bob".to_string(),
+                amount: 1,
+                transfer_type: "SOL Transfer".to_string(),
+                is_wrapped: false,
+                instruction_path: None,
+            });
+        }
+        data
+    }
+
+    #[test]
+    fn test_counters_follow_ingestion_and_removal() {
+        let dir = TempDir::new("stats-storage-test");
+        let storage = StatsStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        assert!(!storage.is_initialized().unwrap());
+
+        let mut first = transaction("sig1", 1_704_067_200, true, 2);
+        first.set_usd_valuation(Some(UsdValuation { total_usd: 12.5, ..Default::default() }));
+        let failed = transaction("sig2", 1_704_067_300, false, 0);
+        storage.record_transaction(&first).unwrap();
+        storage.record_transaction(&failed).unwrap();
+        storage.record_transaction(&transaction("sig3", 1_704_153_600, true, 1)).unwrap();
+
+        let totals = storage.get_totals().unwrap();
+        assert_eq!(totals.total_signatures, 3);
+        assert_eq!(totals.total_sol_transfers, 3);
+        assert_eq!(totals.successful_transactions, 2);
+        assert_eq!(totals.failed_transactions, 1);
+        assert_eq!(totals.total_usd_volume, 12.5);
+
+        let days = storage.get_recent_days(7).unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2024-01-02");
+        assert_eq!(days[1].transaction_count, 2);
+        assert_eq!(days[1].sol_transfer_count, 2);
+        assert_eq!(days[1].usd_volume, 12.5);
+
+        storage.record_removals(&[first, failed]).unwrap();
+        let totals = storage.get_totals().unwrap();
+        assert_eq!(totals.total_signatures, 1);
+        assert_eq!(totals.failed_transactions, 0);
+        assert_eq!(totals.total_usd_volume, 0.0);
+        // 每日计数保留历史摄取量
+        assert_eq!(storage.get_recent_days(7).unwrap()[1].transaction_count, 2);
+    }
+
+    #[test]
+    fn test_rebuild_accumulates_batches() {
+        let dir = TempDir::new("stats-storage-test");
+        let storage = StatsStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        let batches = [
+            vec![transaction("sig1", 1_704_067_200, true, 2), transaction("sig2", 1_704_067_300, false, 0)],
+            vec![transaction("sig3", 1_704_153_600, true, 1)],
+        ];
+
+        let totals = storage
+            .rebuild(|add| {
+                batches.iter().flatten().for_each(|data| add(data));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(totals.total_signatures, 3);
+        assert_eq!(totals.total_sol_transfers, 3);
+        assert_eq!(totals.failed_transactions, 1);
+        assert!(storage.is_initialized().unwrap());
+
+        let days = storage.get_recent_days(7).unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!((days[0].date.as_str(), days[0].transaction_count), ("2024-01-02", 1));
+        assert_eq!(days[1].transaction_count, 2);
+    }
+}
@@ -1,16 +1,64 @@
 use anyhow::{Result, Context};
-use rocksdb::{DB, Options, Direction, IteratorMode};
+use rocksdb::{DBCompactionStyle, DBCompressionType, Options};
 use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use std::borrow::Cow;
 use std::path::Path;
 use std::sync::Arc;
 use tracing::{info, debug};
 
-/// RocksDB 存储管理器
+use crate::config::{DatabaseConfig, RocksDbCompactionStyle, RocksDbCompression, RocksDbConfig, StorageBackend};
+use crate::database::encryption::ValueCipher;
+use crate::database::kv_store::{KvStore, RocksDbStore, WriteBufferStore};
+
+/// 租户命名空间键前缀的起始字符，不会出现在默认租户的键前缀中
+const TENANT_KEY_MARKER: char = '@';
+
+/// 租户名最大长度
+pub const MAX_TENANT_NAME_LENGTH: usize = 64;
+
+/// 当前写入的值格式版本
+pub const VALUE_FORMAT_VERSION: u32 = 1;
+
+/// 信封序列化后的固定开头，用来区分信封和引入信封之前写入的裸值
+const ENVELOPE_MARKER: &[u8] = b"{\"version\":";
+
+/// 写入时的值信封：`{"version": 1, "payload": ...}`
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    version: u32,
+    payload: &'a T,
+}
+
+/// 读取时的值信封
+#[derive(Deserialize)]
+struct Envelope<T> {
+    version: u32,
+    payload: T,
+}
+
+/// 只读取信封的版本号，载荷解析失败时用来给出更准确的错误
+#[derive(Deserialize)]
+struct EnvelopeVersion {
+    version: u32,
+}
+
+/// 存储管理器
+///
+/// 在 `KvStore` 后端之上提供 JSON 序列化和键前缀管理，默认使用嵌入式 RocksDB。
+/// 通过 `for_tenant` 得到的实例把所有键放在 `@<租户>/` 命名空间下，
+/// 对调用方透明：传入和返回的键都不带命名空间，各租户的数据互相隔离。
+/// 启用加密后所有值以 AES-256-GCM 加密写入，读取时透明解密，未加密的旧数据仍可读取。
+/// JSON 值包在 `{version, payload}` 信封中写入，读取时按版本解析；没有信封的旧值按裸值读取，
+/// 结构新增字段配合 `#[serde(default)]` 即可兼容已有数据。
 #[derive(Clone)]
 #[derive(Debug)]
 pub struct StorageManager {
-    db: Arc<DB>,
+    db: Arc<dyn KvStore>,
     key_prefix_length: usize,
+    /// 租户命名空间，默认租户为空（键不变）
+    namespace: Option<Arc<str>>,
+    /// 值加密器，未启用加密时为空
+    cipher: Option<Arc<ValueCipher>>,
 }
 
 /// 键值对结构
@@ -30,39 +78,253 @@ pub struct StorageResult {
 impl StorageManager {
     /// 创建新的存储管理器实例
     pub fn new<P: AsRef<Path>>(db_path: P, key_prefix_length: usize) -> Result<Self> {
-        // 创建数据库目录
-        let path = db_path.as_ref();
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent).context("创建数据库目录失败")?;
-        }
+        Self::open_with_options(db_path, key_prefix_length, &Self::default_options())
+    }
+
+    /// 默认的 RocksDB 选项
+    pub fn default_options() -> Options {
+        Self::options_from_config(&RocksDbConfig::default())
+    }
+
+    /// 根据 `[database.rocksdb]` 配置构建 RocksDB 选项
+    pub fn options_from_config(config: &RocksDbConfig) -> Options {
+        const MB: u64 = 1024 * 1024;
 
-        // 配置 RocksDB 选项
         let mut opts = Options::default();
         opts.create_if_missing(true);
-        opts.set_max_open_files(1000);
+        opts.set_max_open_files(config.max_open_files);
         opts.set_use_fsync(false);
         opts.set_bytes_per_sync(8388608);
-        opts.optimize_for_point_lookup(1024);
+        opts.optimize_for_point_lookup(config.block_cache_size_mb);
         opts.set_table_cache_num_shard_bits(6);
-        opts.set_max_write_buffer_number(32);
-        opts.set_write_buffer_size(536870912);
+        opts.set_max_write_buffer_number(config.max_write_buffer_number);
+        opts.set_write_buffer_size((config.write_buffer_size_mb * MB) as usize);
         opts.set_target_file_size_base(1073741824);
         opts.set_min_write_buffer_number_to_merge(4);
         opts.set_level_zero_stop_writes_trigger(2000);
         opts.set_level_zero_slowdown_writes_trigger(0);
-        opts.set_compaction_style(rocksdb::DBCompactionStyle::Universal);
+        opts.set_compaction_style(match config.compaction_style {
+            RocksDbCompactionStyle::Level => DBCompactionStyle::Level,
+            RocksDbCompactionStyle::Universal => DBCompactionStyle::Universal,
+            RocksDbCompactionStyle::Fifo => DBCompactionStyle::Fifo,
+        });
+        opts.set_compression_type(match config.compression {
+            RocksDbCompression::None => DBCompressionType::None,
+            RocksDbCompression::Snappy => DBCompressionType::Snappy,
+            RocksDbCompression::Lz4 => DBCompressionType::Lz4,
+            RocksDbCompression::Zstd => DBCompressionType::Zstd,
+        });
+        opts
+    }
 
-        // 打开数据库
-        let db = DB::open(&opts, path).context("打开 RocksDB 数据库失败")?;
-        
-        info!("RocksDB 数据库已成功打开: {:?}", path);
-        
-        Ok(StorageManager {
-            db: Arc::new(db),
+    /// 使用指定的 RocksDB 选项打开存储管理器
+    pub fn open_with_options<P: AsRef<Path>>(
+        db_path: P,
+        key_prefix_length: usize,
+        opts: &Options,
+    ) -> Result<Self> {
+        let store = RocksDbStore::open(db_path, opts)?;
+        Ok(Self::with_store(Arc::new(store), key_prefix_length))
+    }
+
+    /// 使用指定的后端创建存储管理器
+    pub fn with_store(store: Arc<dyn KvStore>, key_prefix_length: usize) -> Self {
+        StorageManager {
+            db: store,
             key_prefix_length,
+            namespace: None,
+            cipher: None,
+        }
+    }
+
+    /// 启用值加密
+    pub fn with_encryption(mut self, cipher: ValueCipher) -> Self {
+        self.cipher = Some(Arc::new(cipher));
+        self
+    }
+
+    /// 按 `[database.encryption]` 启用值加密
+    fn with_config_encryption(self, config: &DatabaseConfig) -> Result<Self> {
+        match &config.encryption {
+            Some(encryption) => {
+                let cipher = ValueCipher::from_config(encryption).context("加载存储加密密钥失败")?;
+                info!("🔐 已启用存储值加密（AES-256-GCM）");
+                Ok(self.with_encryption(cipher))
+            }
+            None => Ok(self),
+        }
+    }
+
+    /// 写入前加密值，`key` 为实际存储键
+    fn seal(&self, key: &[u8], value: Vec<u8>) -> Result<Vec<u8>> {
+        match &self.cipher {
+            Some(cipher) => cipher.encrypt(key, &value),
+            None => Ok(value),
+        }
+    }
+
+    /// 读取后解密值，`key` 为实际存储键
+    fn open(&self, key: &[u8], value: Vec<u8>) -> Result<Vec<u8>> {
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt(key, value),
+            None if ValueCipher::is_encrypted(&value) => Err(anyhow::anyhow!(
+                "数据已加密，需要配置 [database.encryption] 才能读取"
+            )),
+            None => Ok(value),
+        }
+    }
+
+    /// 序列化值并包上当前版本的信封
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+        serde_json::to_vec(&EnvelopeRef { version: VALUE_FORMAT_VERSION, payload: value })
+            .context("序列化值失败")
+    }
+
+    /// 解析信封中的值，兼容引入信封之前写入的裸值
+    fn decode<T: DeserializeOwned>(data: &[u8]) -> Result<T> {
+        if !data.starts_with(ENVELOPE_MARKER) {
+            return serde_json::from_slice(data).context("反序列化数据失败");
+        }
+        match serde_json::from_slice::<Envelope<T>>(data) {
+            Ok(envelope) if envelope.version > VALUE_FORMAT_VERSION => Err(Self::newer_version_error(envelope.version)),
+            Ok(envelope) => Ok(envelope.payload),
+            Err(e) => match serde_json::from_slice::<EnvelopeVersion>(data) {
+                Ok(envelope) if envelope.version > VALUE_FORMAT_VERSION => Err(Self::newer_version_error(envelope.version)),
+                _ => Err(anyhow::Error::new(e).context("反序列化数据失败")),
+            },
+        }
+    }
+
+    fn newer_version_error(version: u32) -> anyhow::Error {
+        anyhow::anyhow!("数据格式版本 {} 高于程序支持的版本 {}，请升级程序", version, VALUE_FORMAT_VERSION)
+    }
+
+    /// 租户名是否有效：1-64 位字母、数字、`-` 或 `_`
+    pub fn is_valid_tenant(tenant: &str) -> bool {
+        !tenant.is_empty()
+            && tenant.len() <= MAX_TENANT_NAME_LENGTH
+            && tenant.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// 指定租户的存储视图，与当前实例共享同一后端
+    pub fn for_tenant(&self, tenant: &str) -> Result<Self> {
+        if !Self::is_valid_tenant(tenant) {
+            return Err(anyhow::anyhow!("租户名无效: {:?}（只能包含字母、数字、- 和 _）", tenant));
+        }
+        Ok(StorageManager {
+            db: self.db.clone(),
+            key_prefix_length: self.key_prefix_length,
+            namespace: Some(format!("{}{}/", TENANT_KEY_MARKER, tenant).into()),
+            cipher: self.cipher.clone(),
         })
     }
 
+    /// 带写缓冲的存储视图，写入在 `WriteBufferStore::commit` 时才落到当前后端
+    pub fn buffered(&self) -> (Self, Arc<WriteBufferStore>) {
+        let buffer = Arc::new(WriteBufferStore::new(self.db.clone()));
+        let storage = StorageManager {
+            db: buffer.clone(),
+            key_prefix_length: self.key_prefix_length,
+            namespace: self.namespace.clone(),
+            cipher: self.cipher.clone(),
+        };
+        (storage, buffer)
+    }
+
+    /// 当前租户，默认租户为空
+    pub fn tenant(&self) -> Option<&str> {
+        self.namespace
+            .as_deref()
+            .map(|namespace| namespace.trim_start_matches(TENANT_KEY_MARKER).trim_end_matches('/'))
+    }
+
+    /// 加上租户命名空间后的实际键
+    fn scoped<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        match &self.namespace {
+            Some(namespace) => Cow::Owned(format!("{}{}", namespace, key)),
+            None => Cow::Borrowed(key),
+        }
+    }
+
+    /// 去掉租户命名空间，还原调用方使用的键
+    fn unscoped(&self, mut key: String) -> String {
+        if let Some(namespace) = &self.namespace {
+            key.drain(..namespace.len());
+        }
+        key
+    }
+
+    /// 根据 `database.backend` 配置打开存储
+    pub fn from_config(config: &DatabaseConfig) -> Result<Self> {
+        let storage = match config.backend {
+            StorageBackend::Rocksdb => Self::open_with_options(
+                &config.db_path,
+                config.key_prefix_length,
+                &Self::options_from_config(&config.rocksdb),
+            )?,
+            StorageBackend::Postgres => Self::open_postgres(config)?,
+        };
+        storage.with_config_encryption(config)
+    }
+
+    #[cfg(feature = "postgres")]
+    fn open_postgres(config: &DatabaseConfig) -> Result<Self> {
+        let postgres = config
+            .postgres
+            .as_ref()
+            .context("backend = \"postgres\" 时必须配置 [database.postgres]")?;
+        let store = crate::database::postgres_store::PostgresStore::connect(postgres)?;
+        Ok(Self::with_store(Arc::new(store), config.key_prefix_length))
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    fn open_postgres(_config: &DatabaseConfig) -> Result<Self> {
+        Err(anyhow::anyhow!("未启用 postgres 特性，无法使用 PostgreSQL 后端（请使用 --features postgres 编译）"))
+    }
+
+    /// 以只读副本方式打开存储
+    ///
+    /// RocksDB 以 secondary 模式挂载主库目录；PostgreSQL 本身支持多进程访问，按正常方式连接。
+    pub fn open_replica(config: &DatabaseConfig) -> Result<Self> {
+        let storage = match config.backend {
+            StorageBackend::Rocksdb => {
+                let secondary_path = config
+                    .replica
+                    .secondary_path
+                    .clone()
+                    .unwrap_or_else(|| format!("{}-secondary", config.db_path));
+                let store = RocksDbStore::open_secondary(
+                    Path::new(&config.db_path),
+                    Path::new(&secondary_path),
+                    &Self::options_from_config(&config.rocksdb),
+                )?;
+                Self::with_store(Arc::new(store), config.key_prefix_length)
+            }
+            StorageBackend::Postgres => Self::open_postgres(config)?,
+        };
+        storage.with_config_encryption(config)
+    }
+
+    /// 键前缀长度
+    pub fn key_prefix_length(&self) -> usize {
+        self.key_prefix_length
+    }
+
+    /// 后端名称
+    pub fn backend_name(&self) -> &'static str {
+        self.db.backend_name()
+    }
+
+    /// 是否为只读副本
+    pub fn is_read_only(&self) -> bool {
+        self.db.is_read_only()
+    }
+
+    /// 只读副本追赶主库的最新写入
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        self.db.try_catch_up_with_primary()
+    }
+
     /// 生成带前缀的键
     pub fn make_key(&self, prefix: &str, key: &str) -> Result<String> {
         if prefix.len() != self.key_prefix_length {
@@ -91,12 +353,11 @@ impl StorageManager {
     /// 存储键值对（通用方法）
     pub fn put<T: Serialize>(&self, key: &str, value: &T) -> Result<StorageResult> {
         // 序列化值
-        let serialized_value = serde_json::to_vec(value)
-            .context("序列化值失败")?;
+        let serialized_value = Self::encode(value)?;
 
         // 存储到数据库
-        self.db.put(key.as_bytes(), serialized_value)
-            .context("存储数据到 RocksDB 失败")?;
+        let key_bytes = self.scoped(key);
+        self.db.put(key_bytes.as_bytes(), &self.seal(key_bytes.as_bytes(), serialized_value)?)?;
 
         debug!("成功存储数据: key={}", key);
         
@@ -108,10 +369,11 @@ impl StorageManager {
 
     /// 获取值（通用方法）
     pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
-        match self.db.get(key.as_bytes()).context("从 RocksDB 读取数据失败")? {
+        let key_bytes = self.scoped(key);
+        match self.db.get(key_bytes.as_bytes())? {
             Some(data) => {
-                let value: T = serde_json::from_slice(&data)
-                    .context("反序列化数据失败")?;
+                let data = self.open(key_bytes.as_bytes(), data)?;
+                let value: T = Self::decode(&data)?;
                 debug!("成功读取数据: key={}", key);
                 Ok(Some(value))
             }
@@ -122,10 +384,31 @@ impl StorageManager {
         }
     }
 
+    /// 存储原始字节（不经过 JSON 序列化）
+    pub fn put_bytes(&self, key: &str, value: &[u8]) -> Result<StorageResult> {
+        let key_bytes = self.scoped(key);
+        self.db.put(key_bytes.as_bytes(), &self.seal(key_bytes.as_bytes(), value.to_vec())?)?;
+
+        debug!("成功存储数据: key={}, {} bytes", key, value.len());
+
+        Ok(StorageResult {
+            success: true,
+            message: format!("成功存储键: {}", key),
+        })
+    }
+
+    /// 获取原始字节
+    pub fn get_bytes(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let key_bytes = self.scoped(key);
+        match self.db.get(key_bytes.as_bytes())? {
+            Some(data) => Ok(Some(self.open(key_bytes.as_bytes(), data)?)),
+            None => Ok(None),
+        }
+    }
+
     /// 删除键值对
     pub fn delete(&self, key: &str) -> Result<StorageResult> {
-        self.db.delete(key.as_bytes())
-            .context("从 RocksDB 删除数据失败")?;
+        self.db.delete(self.scoped(key).as_bytes())?;
 
         debug!("成功删除数据: key={}", key);
         
@@ -137,7 +420,7 @@ impl StorageManager {
 
     /// 检查键是否存在
     pub fn exists(&self, key: &str) -> Result<bool> {
-        match self.db.get(key.as_bytes()).context("检查键是否存在失败")? {
+        match self.db.get(self.scoped(key).as_bytes()).context("检查键是否存在失败")? {
             Some(_) => Ok(true),
             None => Ok(false),
         }
@@ -146,22 +429,13 @@ impl StorageManager {
     /// 按前缀获取所有键值对
     pub fn get_by_prefix<T: DeserializeOwned>(&self, prefix: &str) -> Result<Vec<KeyValue<T>>> {
         let mut results = Vec::new();
-        let prefix_bytes = prefix.as_bytes();
 
-        let iter = self.db.iterator(IteratorMode::From(prefix_bytes, Direction::Forward));
-        
-        for item in iter {
-            let (key_bytes, value_bytes) = item.context("迭代数据库失败")?;
-            let key_str = String::from_utf8(key_bytes.to_vec())
-                .context("键不是有效的 UTF-8 字符串")?;
-
-            // 检查是否仍然匹配前缀
-            if !key_str.starts_with(prefix) {
-                break;
-            }
+        for (key_bytes, value_bytes) in self.db.scan_prefix(self.scoped(prefix).as_bytes())? {
+            let value_bytes = self.open(&key_bytes, value_bytes)?;
+            let key_str = self.unscoped(String::from_utf8(key_bytes)
+                .context("键不是有效的 UTF-8 字符串")?);
 
-            let value: T = serde_json::from_slice(&value_bytes)
-                .context("反序列化数据失败")?;
+            let value: T = Self::decode(&value_bytes)?;
 
             results.push(KeyValue {
                 key: key_str,
@@ -176,41 +450,125 @@ impl StorageManager {
     /// 获取所有键（按前缀过滤）
     pub fn get_keys_by_prefix(&self, prefix: &str) -> Result<Vec<String>> {
         let mut keys = Vec::new();
-        let prefix_bytes = prefix.as_bytes();
 
-        let iter = self.db.iterator(IteratorMode::From(prefix_bytes, Direction::Forward));
-        
-        for item in iter {
-            let (key_bytes, _) = item.context("迭代数据库失败")?;
-            let key_str = String::from_utf8(key_bytes.to_vec())
-                .context("键不是有效的 UTF-8 字符串")?;
-
-            // 检查是否仍然匹配前缀
-            if !key_str.starts_with(prefix) {
-                break;
+        for key_bytes in self.db.scan_prefix_keys(self.scoped(prefix).as_bytes())? {
+            let key_str = self.unscoped(String::from_utf8(key_bytes)
+                .context("键不是有效的 UTF-8 字符串")?);
+            keys.push(key_str);
+        }
+
+        debug!("查询到 {} 个键: prefix={}", keys.len(), prefix);
+        Ok(keys)
+    }
+
+    /// 按前缀获取键，最多返回 `limit` 个（找到足够的键后即停止迭代）
+    pub fn get_keys_by_prefix_limit(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+
+        for key_bytes in self.db.scan_prefix_keys_limit(self.scoped(prefix).as_bytes(), limit)? {
+            let key_str = self.unscoped(String::from_utf8(key_bytes)
+                .context("键不是有效的 UTF-8 字符串")?);
+            keys.push(key_str);
+        }
+
+        debug!("查询到 {} 个键: prefix={}, limit={}", keys.len(), prefix, limit);
+        Ok(keys)
+    }
+
+    /// 按前缀分批遍历键值对（按键升序），每批最多 `chunk_size` 条，不会一次载入全部数据
+    pub fn for_each_chunk_by_prefix<T: DeserializeOwned>(
+        &self,
+        prefix: &str,
+        chunk_size: usize,
+        mut f: impl FnMut(Vec<KeyValue<T>>) -> Result<()>,
+    ) -> Result<()> {
+        let chunk_size = chunk_size.max(1);
+        let scoped_prefix = self.scoped(prefix);
+        let mut start_after: Option<Vec<u8>> = None;
+        loop {
+            let page = self
+                .db
+                .scan_prefix_page(scoped_prefix.as_bytes(), start_after.as_deref(), chunk_size)?;
+            let Some((last_key, _)) = page.last() else {
+                return Ok(());
+            };
+            start_after = Some(last_key.clone());
+            let exhausted = page.len() < chunk_size;
+
+            let mut chunk = Vec::with_capacity(page.len());
+            for (key_bytes, value_bytes) in page {
+                let value_bytes = self.open(&key_bytes, value_bytes)?;
+                let key = self.unscoped(String::from_utf8(key_bytes).context("键不是有效的 UTF-8 字符串")?);
+                chunk.push(KeyValue { key, value: Self::decode(&value_bytes)? });
+            }
+            f(chunk)?;
+            if exhausted {
+                return Ok(());
+            }
+        }
+    }
+
+    /// 按前缀分批遍历键（按键升序），每批最多 `chunk_size` 个，不读取值
+    pub fn for_each_key_chunk_by_prefix(
+        &self,
+        prefix: &str,
+        chunk_size: usize,
+        mut f: impl FnMut(Vec<String>) -> Result<()>,
+    ) -> Result<()> {
+        let chunk_size = chunk_size.max(1);
+        let scoped_prefix = self.scoped(prefix);
+        let mut start_after: Option<Vec<u8>> = None;
+        loop {
+            let page = self
+                .db
+                .scan_prefix_keys_page(scoped_prefix.as_bytes(), start_after.as_deref(), chunk_size)?;
+            let Some(last_key) = page.last() else {
+                return Ok(());
+            };
+            start_after = Some(last_key.clone());
+            let exhausted = page.len() < chunk_size;
+
+            let keys = page
+                .into_iter()
+                .map(|key_bytes| Ok(self.unscoped(String::from_utf8(key_bytes).context("键不是有效的 UTF-8 字符串")?)))
+                .collect::<Result<Vec<_>>>()?;
+            f(keys)?;
+            if exhausted {
+                return Ok(());
             }
+        }
+    }
+
+    /// 按前缀从最后一个键开始逆序获取键，最多返回 `limit` 个
+    pub fn get_keys_by_prefix_rev_limit(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
 
+        for key_bytes in self.db.scan_prefix_keys_rev_limit(self.scoped(prefix).as_bytes(), limit)? {
+            let key_str = self.unscoped(String::from_utf8(key_bytes)
+                .context("键不是有效的 UTF-8 字符串")?);
             keys.push(key_str);
         }
 
-        debug!("查询到 {} 个键: prefix={}", keys.len(), prefix);
+        debug!("逆序查询到 {} 个键: prefix={}, limit={}", keys.len(), prefix, limit);
         Ok(keys)
     }
 
     /// 批量存储
     pub fn batch_put<T: Serialize>(&self, items: Vec<(String, T)>) -> Result<StorageResult> {
-        let mut batch = rocksdb::WriteBatch::default();
-        
+        let count = items.len();
+        let mut batch = Vec::with_capacity(count);
+
         for (key, value) in items.iter() {
-            let serialized_value = serde_json::to_vec(value)
-                .context("序列化值失败")?;
-            batch.put(key.as_bytes(), serialized_value);
+            let serialized_value = Self::encode(value)?;
+            let key_bytes = self.scoped(key).as_bytes().to_vec();
+            let serialized_value = self.seal(&key_bytes, serialized_value)?;
+            batch.push((key_bytes, serialized_value));
         }
 
-        self.db.write(batch).context("批量写入 RocksDB 失败")?;
+        self.db.write_batch(batch)?;
 
-        let message = format!("成功批量存储 {} 条记录", items.len());
-        info!("{}", message);
+        let message = format!("成功批量存储 {} 条记录", count);
+        debug!("{}", message);
         
         Ok(StorageResult {
             success: true,
@@ -228,36 +586,79 @@ impl StorageManager {
 
     /// 获取压缩相关统计信息
     pub fn get_compaction_stats(&self) -> Result<String> {
+        let properties = self.get_db_properties()?;
+
         let mut stats_info = String::new();
-        
-        // 获取各种压缩相关统计
-        if let Ok(Some(compaction_pending)) = self.db.property_value("rocksdb.compaction-pending") {
-            stats_info.push_str(&format!("压缩等待中: {}\n", compaction_pending));
-        }
-        
-        if let Ok(Some(num_running_compactions)) = self.db.property_value("rocksdb.num-running-compactions") {
-            stats_info.push_str(&format!("运行中的压缩: {}\n", num_running_compactions));
-        }
-        
-        if let Ok(Some(level0_files)) = self.db.property_value("rocksdb.num-files-at-level0") {
+        stats_info.push_str(&format!("压缩等待中: {}\n", properties.compaction_pending));
+        stats_info.push_str(&format!("运行中的压缩: {}\n", properties.num_running_compactions));
+        if let Some(level0_files) = properties.num_files_at_level.first() {
             stats_info.push_str(&format!("Level 0 文件数: {}\n", level0_files));
         }
-        
-        if let Ok(Some(total_sst_files)) = self.db.property_value("rocksdb.total-sst-files-size") {
-            stats_info.push_str(&format!("SST 文件总大小: {} bytes\n", total_sst_files));
-        }
-        
-        if let Ok(Some(live_sst_files)) = self.db.property_value("rocksdb.live-sst-files-size") {
-            stats_info.push_str(&format!("活跃 SST 文件大小: {} bytes\n", live_sst_files));
-        }
-        
+        stats_info.push_str(&format!("SST 文件总大小: {} bytes\n", properties.total_sst_files_size));
+        stats_info.push_str(&format!("活跃 SST 文件大小: {} bytes\n", properties.live_sst_files_size));
+
         Ok(stats_info)
     }
 
+    /// 获取结构化的 RocksDB 属性
+    pub fn get_db_properties(&self) -> Result<DbProperties> {
+        let int = |name: &str| -> Result<u64> { Ok(self.get_property_int(name)?.unwrap_or(0)) };
+
+        let mut num_files_at_level = Vec::with_capacity(DB_LEVELS);
+        for level in 0..DB_LEVELS {
+            let files = self
+                .db
+                .property_value(&format!("rocksdb.num-files-at-level{}", level))
+                .with_context(|| format!("获取 Level {} 文件数失败", level))?
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(0);
+            num_files_at_level.push(files);
+        }
+
+        Ok(DbProperties {
+            estimate_num_keys: int("rocksdb.estimate-num-keys")?,
+            total_sst_files_size: int("rocksdb.total-sst-files-size")?,
+            live_sst_files_size: int("rocksdb.live-sst-files-size")?,
+            estimate_live_data_size: int("rocksdb.estimate-live-data-size")?,
+            cur_size_all_mem_tables: int("rocksdb.cur-size-all-mem-tables")?,
+            estimate_pending_compaction_bytes: int("rocksdb.estimate-pending-compaction-bytes")?,
+            compaction_pending: int("rocksdb.compaction-pending")?,
+            num_running_compactions: int("rocksdb.num-running-compactions")?,
+            num_running_flushes: int("rocksdb.num-running-flushes")?,
+            num_files_at_level,
+        })
+    }
+
+    /// 获取整数类型的 RocksDB 属性值
+    pub fn get_property_int(&self, name: &str) -> Result<Option<u64>> {
+        self.db.property_int_value(name)
+    }
+
+    /// 将内存表刷写到磁盘
+    pub fn flush(&self) -> Result<StorageResult> {
+        self.db.flush()?;
+
+        Ok(StorageResult {
+            success: true,
+            message: "内存表刷写完成".to_string(),
+        })
+    }
+
+    /// 在目标目录创建一致性快照，服务运行期间也可调用
+    pub fn create_checkpoint<P: AsRef<Path>>(&self, target: P) -> Result<()> {
+        self.db.create_checkpoint(target.as_ref())
+    }
+
+    /// 以只读方式打开快照等离线数据库
+    pub fn open_read_only<P: AsRef<Path>>(db_path: P, key_prefix_length: usize) -> Result<Self> {
+        let store = RocksDbStore::open_read_only(db_path, &Options::default())?;
+        Ok(Self::with_store(Arc::new(store), key_prefix_length))
+    }
+
     /// 压缩数据库
     pub fn compact(&self) -> Result<StorageResult> {
-        self.db.compact_range(Option::<&[u8]>::None, Option::<&[u8]>::None);
-        
+        self.db.compact()?;
+
         let message = "数据库压缩完成".to_string();
         info!("{}", message);
         
@@ -268,8 +669,93 @@ impl StorageManager {
     }
 }
 
+/// RocksDB 默认层数
+const DB_LEVELS: usize = 7;
+
+/// 结构化的 RocksDB 属性
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DbProperties {
+    /// 估算的键数量
+    pub estimate_num_keys: u64,
+    /// SST 文件总大小
+    pub total_sst_files_size: u64,
+    /// 当前版本引用的 SST 文件大小
+    pub live_sst_files_size: u64,
+    /// 估算的有效数据大小
+    pub estimate_live_data_size: u64,
+    /// 所有内存表的大小
+    pub cur_size_all_mem_tables: u64,
+    /// 估算的待压缩字节数
+    pub estimate_pending_compaction_bytes: u64,
+    /// 是否有等待中的压缩（1 表示有）
+    pub compaction_pending: u64,
+    /// 运行中的压缩数量
+    pub num_running_compactions: u64,
+    /// 运行中的刷写数量
+    pub num_running_flushes: u64,
+    /// 每层的文件数量
+    pub num_files_at_level: Vec<u64>,
+}
+
 impl Drop for StorageManager {
     fn drop(&mut self) {
-        info!("RocksDB 存储管理器正在关闭");
+        // 各存储模块和阻塞任务都持有克隆，只在最后一个实例释放时记录
+        if Arc::strong_count(&self.db) == 1 {
+            info!("{} 存储管理器正在关闭", self.db.backend_name());
+        }
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_tenants_are_isolated() {
+        let dir = TempDir::new("tenant-test");
+        let storage = StorageManager::new(dir.path(), 6).unwrap();
+        let acme = storage.for_tenant("acme").unwrap();
+        let globex = storage.for_tenant("globex").unwrap();
+        assert!(storage.for_tenant("bad/name").is_err());
+        assert_eq!((storage.tenant(), acme.tenant()), (None, Some("acme")));
+
+        storage.put("TEST01a", &0).unwrap();
+        acme.put("TEST01a", &1).unwrap();
+        acme.batch_put(vec![("TEST01b".to_string(), 2)]).unwrap();
+        globex.put("TEST01a", &3).unwrap();
+
+        assert_eq!(storage.get::<i32>("TEST01a").unwrap(), Some(0));
+        assert_eq!(acme.get::<i32>("TEST01a").unwrap(), Some(1));
+        assert_eq!(globex.get::<i32>("TEST01a").unwrap(), Some(3));
+        // 前缀查询只返回本租户的键，且不带命名空间
+        assert_eq!(acme.get_keys_by_prefix("TEST01").unwrap(), vec!["TEST01a", "TEST01b"]);
+        assert_eq!(storage.get_keys_by_prefix("TEST01").unwrap(), vec!["TEST01a"]);
+
+        let key = acme.get_by_prefix::<i32>("TEST01").unwrap().remove(1).key;
+        acme.delete(&key).unwrap();
+        assert!(!acme.exists("TEST01b").unwrap());
+        assert!(globex.get::<i32>("TEST01b").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_values_are_versioned_and_legacy_values_readable() {
+        let dir = TempDir::new("envelope-test");
+        let storage = StorageManager::new(dir.path(), 6).unwrap();
+
+        storage.put("TEST01new", &vec![1, 2]).unwrap();
+        assert_eq!(storage.get_bytes("TEST01new").unwrap().unwrap(), br#"{"version":1,"payload":[1,2]}"#);
+        assert_eq!(storage.get::<Vec<i32>>("TEST01new").unwrap(), Some(vec![1, 2]));
+
+        // 引入信封之前写入的裸值
+        storage.put_bytes("TEST01old", b"[3,4]").unwrap();
+        assert_eq!(storage.get::<Vec<i32>>("TEST01old").unwrap(), Some(vec![3, 4]));
+        let values = storage.get_by_prefix::<Vec<i32>>("TEST01").unwrap();
+        assert_eq!(values.into_iter().map(|entry| entry.value).collect::<Vec<_>>(), vec![vec![1, 2], vec![3, 4]]);
+
+        // 更新版本写入的数据给出明确的错误
+        storage.put_bytes("TEST01future", br#"{"version":2,"payload":{"items":[5]}}"#).unwrap();
+        let error = storage.get::<Vec<i32>>("TEST01future").unwrap_err();
+        assert!(error.to_string().contains("数据格式版本 2"));
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::database::storage::StorageManager;
+use crate::transfer_types::SwapRecord;
+
+/// 兑换记录键前缀
+pub const SWAP_KEY_PREFIX: &str = "SWAP01";
+
+/// 地址的兑换记录列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapList {
+    /// 所有者地址
+    pub address: String,
+    /// 兑换记录（索引0是最新的）
+    pub swaps: Vec<SwapSlotRecord>,
+}
+
+/// 带槽位的兑换记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapSlotRecord {
+    /// 交易槽位
+    pub slot: u64,
+    /// 兑换记录
+    #[serde(flatten)]
+    pub swap: SwapRecord,
+}
+
+/// 兑换记录存储管理器（按所有者地址保存）
+#[derive(Debug, Clone)]
+pub struct SwapStorage {
+    storage: StorageManager,
+    max_swaps: usize,
+}
+
+impl SwapStorage {
+    /// 创建新的兑换记录存储实例
+    pub fn new(storage: StorageManager, max_swaps: usize) -> Self {
+        Self { storage, max_swaps }
+    }
+
+    /// 记录一笔交易中的兑换
+    pub fn record_swaps(&self, slot: u64, swaps: &[SwapRecord]) -> Result<()> {
+        for swap in swaps {
+            self.add_swap(SwapSlotRecord { slot, swap: swap.clone() })?;
+        }
+        Ok(())
+    }
+
+    /// 添加兑换记录，同一所有者在同一签名中只记录一次
+    fn add_swap(&self, record: SwapSlotRecord) -> Result<()> {
+        let address = record.swap.owner.clone();
+        let key = self.make_key(&address)?;
+
+        let mut list = self.storage.get::<SwapList>(&key)?.unwrap_or(SwapList {
+            address: address.clone(),
+            swaps: Vec::new(),
+        });
+
+        if list.swaps.iter().any(|existing| existing.swap.signature == record.swap.signature) {
+            return Ok(());
+        }
+
+        list.swaps.insert(0, record);
+        list.swaps.truncate(self.max_swaps);
+
+        self.storage.put(&key, &list)?;
+        debug!("地址 {} 兑换记录更新，当前 {} 条", address, list.swaps.len());
+        Ok(())
+    }
+
+    /// 获取地址的兑换记录（最新的在前）
+    pub fn get_swaps(&self, address: &str) -> Result<Vec<SwapSlotRecord>> {
+        Ok(self
+            .storage
+            .get::<SwapList>(&self.make_key(address)?)?
+            .map(|list| list.swaps)
+            .unwrap_or_default())
+    }
+
+    fn make_key(&self, address: &str) -> Result<String> {
+        self.storage.make_key(SWAP_KEY_PREFIX, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn swap(signature: &str, owner: &str) -> SwapRecord {
+        SwapRecord {
+            signature: signature.to_string(),
+            owner: owner.to_string(),
+            program_id: "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".to_string(),
+            program: "Jupiter v6".to_string(),
+            token_in: "So11111111111111111111111111111111111111112".to_string(),
+            amount_in: 1_000_000_000,
+            decimals_in: 9,
+            token_out: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            amount_out: 150_000_000,
+            decimals_out: 6,
+            timestamp: 100,
+        }
+    }
+
+    #[test]
+    fn test_swaps_stored_per_owner() {
+        let dir = TempDir::new("swap-storage-test");
+        let storage = SwapStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 2);
+
+        storage.record_swaps(1, &[swap("sig1", "alice"), swap("sig1", "bob")]).unwrap();
+        storage.record_swaps(2, &[swap("sig2", "alice")]).unwrap();
+        storage.record_swaps(3, &[swap("sig3", "alice")]).unwrap();
+        // 重复处理同一交易不会重复记录
+        storage.record_swaps(3, &[swap("sig3", "alice")]).unwrap();
+
+        let alice = storage.get_swaps("alice").unwrap();
+        assert_eq!(alice.len(), 2);
+        assert_eq!(alice[0].swap.signature, "sig3");
+        assert_eq!(alice[0].slot, 3);
+        assert_eq!(storage.get_swaps("bob").unwrap().len(), 1);
+        assert!(storage.get_swaps("carol").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_swaps_evicted_oldest_first() {
+        let dir = TempDir::new("swap-evict-test");
+        let storage = SwapStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 2);
+
+        for slot in 1..=4u64 {
+            storage.record_swaps(slot, &[swap(&format!("sig{}", slot), "alice")]).unwrap();
+        }
+        // 其他地址的记录不受上限影响
+        storage.record_swaps(5, &[swap("sig5", "bob")]).unwrap();
+
+        let alice = storage.get_swaps("alice").unwrap();
+        let slots: Vec<u64> = alice.iter().map(|r| r.slot).collect();
+        assert_eq!(slots, vec![4, 3]);
+        assert_eq!(alice[1].swap.signature, "sig3");
+        assert_eq!(storage.get_swaps("bob").unwrap()[0].slot, 5);
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::storage::StorageManager;
+use crate::transfer_types::TokenBalanceSnapshot;
+
+/// 代币余额历史键前缀
+pub const TOKEN_BALANCE_KEY_PREFIX: &str = "TBAL01";
+
+/// 代币余额历史点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBalancePoint {
+    /// 交易签名
+    pub signature: String,
+    /// 交易槽位
+    pub slot: u64,
+    /// 交易时间戳
+    pub timestamp: u64,
+    /// 执行后余额（最小代币单位），为该交易涉及的所有者代币账户余额之和
+    pub amount: u64,
+}
+
+/// 某个所有者持有某种代币的余额历史
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBalanceHistory {
+    /// 所有者地址
+    pub owner: String,
+    /// 代币mint地址
+    pub mint: String,
+    /// 代币小数位数
+    pub decimals: u32,
+    /// 余额历史点（索引0是最新的）
+    pub points: Vec<TokenBalancePoint>,
+    /// 最后更新时间
+    pub last_updated: u64,
+}
+
+/// 代币余额历史存储管理器
+///
+/// 键格式为 `TBAL01{owner}:{mint}`，按所有者前缀扫描即可得到其持有的全部代币。
+#[derive(Debug, Clone)]
+pub struct TokenBalanceStorage {
+    storage: StorageManager,
+    max_points: usize,
+    clock: SharedClock,
+}
+
+impl TokenBalanceStorage {
+    /// 创建新的代币余额历史存储实例
+    pub fn new(storage: StorageManager, max_points: usize) -> Self {
+        Self {
+            storage,
+            max_points,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 替换时钟（用于测试）
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 记录一笔交易中各 (所有者, mint) 的执行后余额
+    pub fn record_balances(
+        &self,
+        signature: &str,
+        slot: u64,
+        timestamp: u64,
+        snapshots: &[TokenBalanceSnapshot],
+    ) -> Result<()> {
+        for snapshot in snapshots {
+            let key = self.make_key(&snapshot.owner, &snapshot.mint)?;
+            let mut history = match self.storage.get::<TokenBalanceHistory>(&key)? {
+                Some(history) => history,
+                None => TokenBalanceHistory {
+                    owner: snapshot.owner.clone(),
+                    mint: snapshot.mint.clone(),
+                    decimals: snapshot.decimals,
+                    points: Vec::new(),
+                    last_updated: 0,
+                },
+            };
+
+            // 同一签名只记录一次
+            if history.points.iter().any(|point| point.signature == signature) {
+                continue;
+            }
+
+            history.points.insert(
+                0,
+                TokenBalancePoint {
+                    signature: signature.to_string(),
+                    slot,
+                    timestamp,
+                    amount: snapshot.amount,
+                },
+            );
+            history.points.truncate(self.max_points);
+            history.last_updated = self.clock.timestamp() as u64;
+
+            self.storage.put(&key, &history)?;
+            debug!("所有者 {} 代币 {} 余额历史更新", snapshot.owner, snapshot.mint);
+        }
+        Ok(())
+    }
+
+    /// 获取所有者持有的全部代币余额历史
+    pub fn get_token_balances(&self, owner: &str) -> Result<Vec<TokenBalanceHistory>> {
+        let prefix = format!("{}{}:", TOKEN_BALANCE_KEY_PREFIX, owner);
+        let items = self.storage.get_by_prefix::<TokenBalanceHistory>(&prefix)?;
+        Ok(items.into_iter().map(|item| item.value).collect())
+    }
+
+    /// 获取所有者持有某种代币的余额历史
+    pub fn get_token_balance(&self, owner: &str, mint: &str) -> Result<Option<TokenBalanceHistory>> {
+        self.storage.get(&self.make_key(owner, mint)?)
+    }
+
+    fn make_key(&self, owner: &str, mint: &str) -> Result<String> {
+        self.storage.make_key(TOKEN_BALANCE_KEY_PREFIX, &format!("{}:{}", owner, mint))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn snapshot(owner: &str, mint: &str, amount: u64) -> TokenBalanceSnapshot {
+        TokenBalanceSnapshot {
+            owner: owner.to_string(),
+            mint: mint.to_string(),
+            amount,
+            decimals: 6,
+        }
+    }
+
+    #[test]
+    fn test_token_balances_grouped_by_owner_and_mint() {
+        let dir = TempDir::new("token-balance-test");
+        let storage = TokenBalanceStorage::new(StorageManager::new(dir.path(), 6).unwrap(), 10);
+
+        storage
+            .record_balances("sig1", 1, 100, &[snapshot("alice", "USDC", 5), snapshot("bob", "USDC", 1)])
+            .unwrap();
+        storage.record_balances("sig2", 2, 200, &[snapshot("alice", "USDC", 3)]).unwrap();
+        storage.record_balances("sig2", 2, 200, &[snapshot("alice", "USDC", 3)]).unwrap();
+        storage.record_balances("sig3", 3, 300, &[snapshot("alice", "BONK", 7)]).unwrap();
+
+        let mut balances = storage.get_token_balances("alice").unwrap();
+        balances.sort_by(|a, b| a.mint.cmp(&b.mint));
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].mint, "BONK");
+        let usdc: Vec<u64> = balances[1].points.iter().map(|point| point.amount).collect();
+        assert_eq!(usdc, vec![3, 5]);
+        assert_eq!(storage.get_token_balance("bob", "USDC").unwrap().unwrap().points.len(), 1);
+    }
+}
@@ -0,0 +1,321 @@
+use anyhow::{Context, Result};
+
+use crate::database::address_storage::{AddressStorage, AddressTransactionRecord, RecordType};
+use crate::database::address_tag_storage::AddressTagStorage;
+use crate::database::mint_transfer_storage::{MintTransferRecord, MintTransferStorage};
+
+/// 转账方向（相对于查询地址）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    /// 转入
+    In,
+    /// 转出
+    Out,
+}
+
+impl TransferDirection {
+    /// 解析 `in` / `out`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "in" => Some(Self::In),
+            "out" => Some(Self::Out),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::In => "in",
+            Self::Out => "out",
+        }
+    }
+}
+
+/// 转账类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferCategory {
+    /// SOL 转账
+    Sol,
+    /// 代币转账
+    Token,
+}
+
+impl TransferCategory {
+    /// 解析 `sol` / `token`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "sol" => Some(Self::Sol),
+            "token" => Some(Self::Token),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sol => "sol",
+            Self::Token => "token",
+        }
+    }
+}
+
+/// 转账搜索条件，各条件之间为“与”关系
+///
+/// 必须指定 `address` 或 `mint`，分别使用地址记录和代币转账索引作为数据源。
+#[derive(Debug, Clone, Default)]
+pub struct TransferQuery {
+    pub address: Option<String>,
+    pub mint: Option<String>,
+    /// 最小金额（原始单位，包含）
+    pub min_amount: Option<u64>,
+    /// 最大金额（原始单位，包含）
+    pub max_amount: Option<u64>,
+    /// 起始时间戳（包含）
+    pub start_time: Option<u64>,
+    /// 结束时间戳（包含）
+    pub end_time: Option<u64>,
+    /// 方向，只在指定 `address` 时有效
+    pub direction: Option<TransferDirection>,
+    pub category: Option<TransferCategory>,
+    /// 地址标记（已规范化），转出方或接收方带有该标记时匹配
+    pub tag: Option<String>,
+    pub limit: usize,
+    /// 上一页返回的游标
+    pub cursor: Option<String>,
+}
+
+/// 搜索命中的一笔转账
+#[derive(Debug, Clone)]
+pub struct TransferHit {
+    pub transfer_id: String,
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub category: TransferCategory,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    /// 代币mint地址（SOL 转账为 None）
+    pub mint: Option<String>,
+    pub decimals: u8,
+    /// 相对于查询地址的方向（按 mint 搜索时为 None）
+    pub direction: Option<TransferDirection>,
+}
+
+/// 一页搜索结果
+#[derive(Debug, Clone)]
+pub struct TransferPage {
+    pub transfers: Vec<TransferHit>,
+    /// 还有更多结果时返回下一页游标
+    pub next_cursor: Option<String>,
+}
+
+/// 基于二级索引的转账搜索
+///
+/// 结果按索引顺序（最新的在前）返回。游标记录上一页最后一条的槽位和转账ID：
+/// 翻页期间有新转账写入时按转账ID定位，不会重复或遗漏；若该记录已被淘汰，
+/// 则从槽位更早的记录继续。
+pub struct TransferSearch<'a> {
+    address_storage: &'a AddressStorage,
+    mint_transfer_storage: &'a MintTransferStorage,
+    tag_storage: Option<&'a AddressTagStorage>,
+}
+
+impl<'a> TransferSearch<'a> {
+    pub fn new(address_storage: &'a AddressStorage, mint_transfer_storage: &'a MintTransferStorage) -> Self {
+        Self { address_storage, mint_transfer_storage, tag_storage: None }
+    }
+
+    /// 设置地址标记存储，用于按标记过滤
+    pub fn with_tag_storage(mut self, tag_storage: &'a AddressTagStorage) -> Self {
+        self.tag_storage = Some(tag_storage);
+        self
+    }
+
+    /// 执行搜索
+    pub fn search(&self, query: &TransferQuery) -> Result<TransferPage> {
+        let candidates: Vec<TransferHit> = if let Some(address) = &query.address {
+            self.address_storage
+                .get_address_records(address)?
+                .map(|list| list.records.iter().filter_map(hit_from_address_record).collect())
+                .unwrap_or_default()
+        } else if let Some(mint) = &query.mint {
+            self.mint_transfer_storage
+                .get_transfers(mint)?
+                .iter()
+                .map(|record| hit_from_mint_record(mint, record))
+                .collect()
+        } else {
+            return Err(anyhow::anyhow!("转账搜索必须指定 address 或 mint"));
+        };
+
+        let start = match &query.cursor {
+            Some(cursor) => {
+                let (slot, transfer_id) = Self::decode_cursor(cursor)?;
+                match candidates.iter().position(|hit| hit.transfer_id == transfer_id) {
+                    Some(index) => index + 1,
+                    None => candidates
+                        .iter()
+                        .position(|hit| hit.slot < slot)
+                        .unwrap_or(candidates.len()),
+                }
+            }
+            None => 0,
+        };
+
+        let tagged = match (&query.tag, self.tag_storage) {
+            (Some(tag), Some(tag_storage)) => Some(tag_storage.addresses_with_tag(tag)?),
+            (Some(_), None) => return Err(anyhow::anyhow!("未设置地址标记存储，无法按标记搜索")),
+            (None, _) => None,
+        };
+
+        let mut matches = candidates[start..].iter().filter(|hit| {
+            is_match(query, hit)
+                && tagged.as_ref().is_none_or(|tagged| tagged.contains(&hit.from) || tagged.contains(&hit.to))
+        });
+        let transfers: Vec<TransferHit> = matches.by_ref().take(query.limit).cloned().collect();
+        let next_cursor = match (transfers.last(), matches.next()) {
+            (Some(last), Some(_)) => Some(encode_cursor(last.slot, &last.transfer_id)),
+            _ => None,
+        };
+
+        Ok(TransferPage { transfers, next_cursor })
+    }
+
+    /// 解析游标，返回 (槽位, 转账ID)
+    pub fn decode_cursor(cursor: &str) -> Result<(u64, String)> {
+        let bytes = bs58::decode(cursor).into_vec().context("游标格式无效")?;
+        let text = String::from_utf8(bytes).context("游标格式无效")?;
+        let (slot, transfer_id) = text.split_once(':').context("游标格式无效")?;
+        Ok((slot.parse().context("游标格式无效")?, transfer_id.to_string()))
+    }
+}
+
+fn is_match(query: &TransferQuery, hit: &TransferHit) -> bool {
+    query.mint.as_ref().is_none_or(|mint| hit.mint.as_ref() == Some(mint))
+        && query.min_amount.is_none_or(|min| hit.amount >= min)
+        && query.max_amount.is_none_or(|max| hit.amount <= max)
+        && query.start_time.is_none_or(|start| hit.timestamp >= start)
+        && query.end_time.is_none_or(|end| hit.timestamp <= end)
+        && query.direction.is_none_or(|direction| hit.direction == Some(direction))
+        && query.category.is_none_or(|category| hit.category == category)
+}
+
+fn hit_from_address_record(record: &AddressTransactionRecord) -> Option<TransferHit> {
+    let direction = match record.record_type {
+        RecordType::Sender => TransferDirection::Out,
+        RecordType::Receiver => TransferDirection::In,
+    };
+    let (category, from, to, amount, mint, decimals) = if let Some(sol) = &record.sol_transfer {
+        (TransferCategory::Sol, sol.from.clone(), sol.to.clone(), sol.amount, None, 9)
+    } else {
+        let token = record.token_transfer.as_ref()?;
+        (
+            TransferCategory::Token,
+            token.sender_wallet().to_string(),
+            token.receiver_wallet().to_string(),
+            token.amount,
+            Some(token.mint.clone()),
+            token.decimals as u8,
+        )
+    };
+
+    Some(TransferHit {
+        transfer_id: record.transfer_id.clone(),
+        signature: record.signature.clone(),
+        slot: record.slot,
+        timestamp: record.timestamp,
+        category,
+        from,
+        to,
+        amount,
+        mint,
+        decimals,
+        direction: Some(direction),
+    })
+}
+
+fn hit_from_mint_record(mint: &str, record: &MintTransferRecord) -> TransferHit {
+    TransferHit {
+        transfer_id: record.transfer_id.clone(),
+        signature: record.signature.clone(),
+        slot: record.slot,
+        timestamp: record.timestamp,
+        category: TransferCategory::Token,
+        from: record.from.clone(),
+        to: record.to.clone(),
+        amount: record.amount,
+        mint: Some(mint.to_string()),
+        decimals: record.decimals,
+        direction: None,
+    }
+}
+
+fn encode_cursor(slot: u64, transfer_id: &str) -> String {
+    bs58::encode(format!("{}:{}", slot, transfer_id)).into_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::storage::StorageManager;
+    use crate::transfer_types::SolTransfer;
+    use crate::test_util::TempDir;
+
+    fn sol_transfer(from: &str, to: &str, amount: u64) -> SolTransfer {
+        SolTransfer {
+            signature: "sig".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            from_index: 0,
+            to_index: 1,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        }
+    }
+
+    #[test]
+    fn test_cursor_pages_are_stable_across_new_writes() {
+        let dir = TempDir::new("transfer-search-test");
+        let storage = StorageManager::new(dir.path(), 6).unwrap();
+        let addresses = AddressStorage::new(storage.clone(), "ADDR01".to_string(), 100);
+        let mints = MintTransferStorage::new(storage, 100);
+        for slot in 1..=5u64 {
+            let (from, to) = if slot % 2 == 0 { ("bob", "alice") } else { ("alice", "bob") };
+            addresses
+                .batch_process_transaction(&format!("sig{}", slot), slot * 10, slot, &[sol_transfer(from, to, slot * 100)], &[])
+                .unwrap();
+        }
+        let search = TransferSearch::new(&addresses, &mints);
+
+        let mut query = TransferQuery {
+            address: Some("alice".to_string()),
+            direction: Some(TransferDirection::Out),
+            limit: 2,
+            ..Default::default()
+        };
+        let first = search.search(&query).unwrap();
+        let signatures: Vec<&str> = first.transfers.iter().map(|hit| hit.signature.as_str()).collect();
+        assert_eq!(signatures, vec!["sig5", "sig3"]);
+
+        // 翻页前写入的新转账不影响下一页
+        addresses
+            .batch_process_transaction("sig7", 70, 7, &[sol_transfer("alice", "bob", 700)], &[])
+            .unwrap();
+        query.cursor = first.next_cursor;
+        let second = search.search(&query).unwrap();
+        assert_eq!(second.transfers.len(), 1);
+        assert_eq!(second.transfers[0].signature, "sig1");
+        assert!(second.next_cursor.is_none());
+
+        query.cursor = None;
+        query.min_amount = Some(400);
+        query.end_time = Some(60);
+        let filtered = search.search(&query).unwrap();
+        assert_eq!(filtered.transfers.len(), 1);
+        assert_eq!(filtered.transfers[0].amount, 500);
+    }
+}
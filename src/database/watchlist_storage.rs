@@ -0,0 +1,237 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tracing::{debug, info};
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::storage::StorageManager;
+
+/// 监控列表键前缀
+pub const WATCHLIST_KEY_PREFIX: &str = "WLST01";
+
+/// 监控列表条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchlistEntry {
+    /// 地址
+    pub address: String,
+    /// 标签
+    pub label: Option<String>,
+    /// 加入时间
+    pub added_at: i64,
+}
+
+/// 待导入的一行数据
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchlistImportRow {
+    /// 地址
+    pub address: String,
+    /// 标签（可选）
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// 被拒绝的导入行
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchlistRejectedRow {
+    /// 行号（从1开始）
+    pub row: usize,
+    /// 原始地址
+    pub address: String,
+    /// 拒绝原因
+    pub reason: String,
+}
+
+/// 导入结果汇总
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WatchlistImportSummary {
+    /// 总行数
+    pub total_rows: usize,
+    /// 新增的地址数
+    pub accepted: usize,
+    /// 重复（文件内重复或已在列表中）的地址数
+    pub duplicates: usize,
+    /// 被拒绝的行
+    pub rejected: Vec<WatchlistRejectedRow>,
+}
+
+/// 监控列表存储管理器
+///
+/// 列表变化时通过 watch 通道通知订阅者（如 gRPC 客户端重新订阅）。
+#[derive(Debug, Clone)]
+pub struct WatchlistStorage {
+    storage: StorageManager,
+    clock: SharedClock,
+    changes: Arc<watch::Sender<u64>>,
+}
+
+impl WatchlistStorage {
+    /// 创建新的监控列表存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        let (changes, _) = watch::channel(0);
+        Self {
+            storage,
+            clock: SystemClock::shared(),
+            changes: Arc::new(changes),
+        }
+    }
+
+    /// 替换时钟（用于测试）
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 订阅监控列表变化通知
+    pub fn subscribe_changes(&self) -> watch::Receiver<u64> {
+        self.changes.subscribe()
+    }
+
+    /// 检查地址是否为合法的 base58 公钥
+    pub fn is_valid_address(address: &str) -> bool {
+        matches!(bs58::decode(address).into_vec(), Ok(bytes) if bytes.len() == 32)
+    }
+
+    /// 解析 CSV 内容，每行格式为 `address[,label]`，允许可选的表头行
+    pub fn parse_csv(content: &str) -> Vec<WatchlistImportRow> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .enumerate()
+            .filter(|(i, line)| !(*i == 0 && Self::is_header(line)))
+            .map(|(_, line)| {
+                let mut parts = line.splitn(2, ',');
+                let address = parts.next().unwrap_or_default().trim().trim_matches('"').to_string();
+                let label = parts
+                    .next()
+                    .map(|label| label.trim().trim_matches('"').to_string())
+                    .filter(|label| !label.is_empty());
+                WatchlistImportRow { address, label }
+            })
+            .collect()
+    }
+
+    fn is_header(line: &str) -> bool {
+        let first = line.split(',').next().unwrap_or_default().trim().trim_matches('"');
+        first.eq_ignore_ascii_case("address")
+    }
+
+    /// 解析 JSON 内容：`[{"address": "...", "label": "..."}]`
+    pub fn parse_json(content: &str) -> Result<Vec<WatchlistImportRow>> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// 导入地址：校验、去重后写入，并通知订阅者
+    pub fn import(&self, rows: Vec<WatchlistImportRow>) -> Result<WatchlistImportSummary> {
+        let mut summary = WatchlistImportSummary {
+            total_rows: rows.len(),
+            ..Default::default()
+        };
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        let added_at = self.clock.timestamp();
+
+        for (i, row) in rows.into_iter().enumerate() {
+            let address = row.address.trim().to_string();
+            if !Self::is_valid_address(&address) {
+                summary.rejected.push(WatchlistRejectedRow {
+                    row: i + 1,
+                    address,
+                    reason: "无效的地址格式 / Invalid address format".to_string(),
+                });
+                continue;
+            }
+
+            let key = self.make_key(&address)?;
+            if !seen.insert(address.clone()) || self.storage.exists(&key)? {
+                summary.duplicates += 1;
+                continue;
+            }
+
+            entries.push((key, WatchlistEntry { address, label: row.label, added_at }));
+        }
+
+        summary.accepted = entries.len();
+        if !entries.is_empty() {
+            self.storage.batch_put(entries)?;
+            self.changes.send_modify(|version| *version += 1);
+        }
+
+        info!(
+            "监控列表导入完成: 共 {} 行, 新增 {}, 重复 {}, 拒绝 {}",
+            summary.total_rows, summary.accepted, summary.duplicates, summary.rejected.len()
+        );
+        Ok(summary)
+    }
+
+    /// 获取所有监控列表条目
+    pub fn get_all_entries(&self) -> Result<Vec<WatchlistEntry>> {
+        let items = self.storage.get_by_prefix::<WatchlistEntry>(WATCHLIST_KEY_PREFIX)?;
+        Ok(items.into_iter().map(|item| item.value).collect())
+    }
+
+    /// 获取所有监控地址
+    pub fn get_addresses(&self) -> Result<Vec<String>> {
+        let keys = self.storage.get_keys_by_prefix(WATCHLIST_KEY_PREFIX)?;
+        let addresses: Vec<String> = keys
+            .into_iter()
+            .map(|key| key.strip_prefix(WATCHLIST_KEY_PREFIX).unwrap_or(&key).to_string())
+            .collect();
+
+        debug!("监控列表中有 {} 个地址", addresses.len());
+        Ok(addresses)
+    }
+
+    fn make_key(&self, address: &str) -> Result<String> {
+        self.storage.make_key(WATCHLIST_KEY_PREFIX, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    const ADDRESS_A: &str = "11111111111111111111111111111111";
+    const ADDRESS_B: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn temp_storage(dir: &TempDir) -> WatchlistStorage {
+        WatchlistStorage::new(StorageManager::new(dir.path(), 6).expect("打开临时数据库失败"))
+    }
+
+    #[test]
+    fn test_parse_csv_with_header_and_labels() {
+        let csv = format!("address,label\n{},System\n\n{}\n", ADDRESS_A, ADDRESS_B);
+        let rows = WatchlistStorage::parse_csv(&csv);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].label.as_deref(), Some("System"));
+        assert_eq!(rows[1].address, ADDRESS_B);
+        assert!(rows[1].label.is_none());
+    }
+
+    #[test]
+    fn test_import_validates_and_deduplicates() {
+        let dir = TempDir::new("watchlist-test");
+        let storage = temp_storage(&dir);
+        let mut changes = storage.subscribe_changes();
+        let rows = WatchlistStorage::parse_csv(&format!("{}\n{}\nnot-an-address\n{}\n", ADDRESS_A, ADDRESS_B, ADDRESS_A));
+
+        let summary = storage.import(rows).unwrap();
+        assert_eq!(summary.total_rows, 4);
+        assert_eq!(summary.accepted, 2);
+        assert_eq!(summary.duplicates, 1);
+        assert_eq!(summary.rejected.len(), 1);
+        assert_eq!(summary.rejected[0].row, 3);
+        assert!(changes.has_changed().unwrap());
+        changes.borrow_and_update();
+
+        // 再次导入已存在的地址只计为重复，不触发变化通知
+        let again = storage.import(WatchlistStorage::parse_csv(ADDRESS_A)).unwrap();
+        assert_eq!(again.accepted, 0);
+        assert_eq!(again.duplicates, 1);
+        assert!(!changes.has_changed().unwrap());
+        assert_eq!(storage.get_addresses().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,365 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::config::LifecycleWebhookConfig;
+use crate::database::alert_storage::Alert;
+use crate::database::ingestion_log_storage::IngestionLogEntry;
+use crate::database::signature_storage::SignatureTransactionData;
+use crate::database::storage::StorageManager;
+use crate::database::transfer_search::TransferDirection;
+
+/// Webhook 订阅键前缀，键为 `<地址>:<订阅ID>`，按地址前缀查找
+pub const WEBHOOK_SUBSCRIPTION_KEY_PREFIX: &str = "WHSB01";
+
+/// 待投递通知键前缀
+pub const WEBHOOK_DELIVERY_KEY_PREFIX: &str = "WHDL01";
+
+/// 运维事件通知的订阅地址占位，订阅ID为配置中的回调地址
+pub const LIFECYCLE_WEBHOOK_ADDRESS: &str = "lifecycle";
+
+/// 首次重试前的等待时间（秒），之后每次失败翻倍
+const BASE_RETRY_DELAY_SECS: i64 = 10;
+
+/// 重试间隔上限（秒）
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+/// 按地址订阅的 Webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub id: String,
+    /// 订阅的地址（代币转账按所有者钱包匹配）
+    pub address: String,
+    /// 最小金额（原始单位，包含）
+    pub min_amount: u64,
+    /// 方向，未设置时转入转出都通知
+    pub direction: Option<TransferDirection>,
+    /// 回调地址
+    pub callback_url: String,
+    /// HMAC 签名密钥
+    pub secret: String,
+    pub created_at: i64,
+}
+
+/// 等待投递的通知
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub subscription_id: String,
+    /// 订阅的地址，用于查找订阅
+    pub address: String,
+    /// JSON 请求体
+    pub payload: String,
+    /// 已失败次数
+    pub attempts: u32,
+    /// 最近一次失败的错误信息
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    /// 下次投递时间
+    pub next_attempt_at: i64,
+}
+
+/// Webhook 订阅与投递队列
+///
+/// 交易写入时为匹配的订阅生成通知放入队列，由后台任务投递，失败按指数退避重试。
+/// 删除订阅后，队列中属于该订阅的通知在投递时丢弃。
+#[derive(Debug, Clone)]
+pub struct WebhookStorage {
+    storage: StorageManager,
+    clock: SharedClock,
+}
+
+impl WebhookStorage {
+    /// 创建新的 Webhook 存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self {
+            storage,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 创建订阅
+    pub fn subscribe(
+        &self,
+        address: &str,
+        min_amount: u64,
+        direction: Option<TransferDirection>,
+        callback_url: &str,
+        secret: &str,
+    ) -> Result<WebhookSubscription> {
+        let subscription = WebhookSubscription {
+            id: uuid::Uuid::new_v4().to_string(),
+            address: address.to_string(),
+            min_amount,
+            direction,
+            callback_url: callback_url.to_string(),
+            secret: secret.to_string(),
+            created_at: self.clock.timestamp(),
+        };
+        self.storage.put(&self.make_subscription_key(address, &subscription.id)?, &subscription)?;
+        Ok(subscription)
+    }
+
+    /// 获取订阅
+    pub fn get_subscription(&self, address: &str, id: &str) -> Result<Option<WebhookSubscription>> {
+        self.storage.get(&self.make_subscription_key(address, id)?)
+    }
+
+    /// 列出订阅，指定地址时只返回该地址的订阅
+    pub fn list_subscriptions(&self, address: Option<&str>) -> Result<Vec<WebhookSubscription>> {
+        let prefix = match address {
+            Some(address) => self.make_subscription_key(address, "")?,
+            None => WEBHOOK_SUBSCRIPTION_KEY_PREFIX.to_string(),
+        };
+        Ok(self
+            .storage
+            .get_by_prefix::<WebhookSubscription>(&prefix)?
+            .into_iter()
+            .map(|item| item.value)
+            .collect())
+    }
+
+    /// 删除订阅，返回订阅是否存在
+    pub fn unsubscribe(&self, id: &str) -> Result<bool> {
+        let suffix = format!(":{}", id);
+        match self
+            .storage
+            .get_keys_by_prefix(WEBHOOK_SUBSCRIPTION_KEY_PREFIX)?
+            .into_iter()
+            .find(|key| key.ends_with(&suffix))
+        {
+            Some(key) => {
+                self.storage.delete(&key)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// 为交易中的转账生成通知，返回生成的数量
+    pub fn enqueue_transfers(&self, data: &SignatureTransactionData) -> Result<usize> {
+        if !data.is_successful {
+            return Ok(0);
+        }
+
+        let sol = data
+            .sol_transfers
+            .iter()
+            .map(|transfer| (transfer.from.as_str(), transfer.to.as_str(), transfer.amount, None, 9));
+        let token = data.token_transfers.iter().map(|transfer| {
+            (
+                transfer.sender_wallet(),
+                transfer.receiver_wallet(),
+                transfer.amount,
+                Some(transfer.mint.as_str()),
+                transfer.decimals,
+            )
+        });
+
+        let mut deliveries = Vec::new();
+        for (from, to, amount, mint, decimals) in sol.chain(token) {
+            for (address, direction) in [(from, TransferDirection::Out), (to, TransferDirection::In)] {
+                for subscription in self.list_subscriptions(Some(address))? {
+                    if amount < subscription.min_amount || subscription.direction.is_some_and(|d| d != direction) {
+                        continue;
+                    }
+                    let payload = serde_json::json!({
+                        "event": "transfer",
+                        "subscription_id": subscription.id,
+                        "address": address,
+                        "direction": direction,
+                        "signature": data.signature,
+                        "slot": data.slot,
+                        "timestamp": data.timestamp,
+                        "from": from,
+                        "to": to,
+                        "amount": amount,
+                        "mint": mint,
+                        "decimals": decimals,
+                    });
+                    deliveries.push(self.new_delivery(&subscription.id, &subscription.address, payload));
+                }
+            }
+        }
+        self.store_deliveries(deliveries)
+    }
+
+    /// 为运维事件生成通知，每个配置的回调地址一条，返回生成的数量
+    pub fn enqueue_lifecycle(&self, entry: &IngestionLogEntry, config: &LifecycleWebhookConfig) -> Result<usize> {
+        let kind = entry.event.kind();
+        if !config.wants(kind) {
+            return Ok(0);
+        }
+        let payload = serde_json::json!({
+            "event": "lifecycle",
+            "kind": kind,
+            "id": entry.id,
+            "timestamp": entry.timestamp,
+            "details": entry.event,
+        });
+        let deliveries = config
+            .urls
+            .iter()
+            .map(|url| self.new_delivery(url, LIFECYCLE_WEBHOOK_ADDRESS, payload.clone()))
+            .collect();
+        self.store_deliveries(deliveries)
+    }
+
+    /// 为告警涉及的地址生成通知，返回生成的数量
+    pub fn enqueue_alerts(&self, alerts: &[Alert]) -> Result<usize> {
+        let mut deliveries = Vec::new();
+        for alert in alerts {
+            for address in &alert.addresses {
+                for subscription in self.list_subscriptions(Some(address))? {
+                    if alert.amount < subscription.min_amount {
+                        continue;
+                    }
+                    let payload = serde_json::json!({
+                        "event": "alert",
+                        "subscription_id": subscription.id,
+                        "address": address,
+                        "alert": alert,
+                    });
+                    deliveries.push(self.new_delivery(&subscription.id, &subscription.address, payload));
+                }
+            }
+        }
+        self.store_deliveries(deliveries)
+    }
+
+    /// 获取到期需要投递的通知，最多 `limit` 条
+    pub fn get_due(&self, limit: usize) -> Result<Vec<WebhookDelivery>> {
+        let now = self.clock.timestamp();
+        Ok(self
+            .storage
+            .get_by_prefix::<WebhookDelivery>(WEBHOOK_DELIVERY_KEY_PREFIX)?
+            .into_iter()
+            .map(|item| item.value)
+            .filter(|delivery| delivery.next_attempt_at <= now)
+            .take(limit)
+            .collect())
+    }
+
+    /// 记录一次投递失败并按退避推迟下次投递
+    pub fn record_failure(&self, mut delivery: WebhookDelivery, error: &str) -> Result<WebhookDelivery> {
+        delivery.attempts += 1;
+        delivery.last_error = Some(error.to_string());
+        delivery.next_attempt_at = self.clock.timestamp() + Self::retry_delay(delivery.attempts);
+        self.storage.put(&self.make_delivery_key(&delivery.id)?, &delivery)?;
+        Ok(delivery)
+    }
+
+    /// 从队列删除通知（投递成功或放弃）
+    pub fn remove_delivery(&self, id: &str) -> Result<()> {
+        self.storage.delete(&self.make_delivery_key(id)?)?;
+        Ok(())
+    }
+
+    fn new_delivery(&self, subscription_id: &str, address: &str, payload: serde_json::Value) -> WebhookDelivery {
+        let now = self.clock.timestamp();
+        WebhookDelivery {
+            id: uuid::Uuid::new_v4().to_string(),
+            subscription_id: subscription_id.to_string(),
+            address: address.to_string(),
+            payload: payload.to_string(),
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            next_attempt_at: now,
+        }
+    }
+
+    fn store_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<usize> {
+        let count = deliveries.len();
+        if count == 0 {
+            return Ok(0);
+        }
+        let items = deliveries
+            .into_iter()
+            .map(|delivery| Ok((self.make_delivery_key(&delivery.id)?, delivery)))
+            .collect::<Result<Vec<_>>>()?;
+        self.storage.batch_put(items)?;
+        debug!("生成 {} 条 Webhook 通知", count);
+        Ok(count)
+    }
+
+    /// 第 `attempts` 次失败后的重试间隔
+    fn retry_delay(attempts: u32) -> i64 {
+        let exponent = attempts.saturating_sub(1).min(16);
+        (BASE_RETRY_DELAY_SECS << exponent).min(MAX_RETRY_DELAY_SECS)
+    }
+
+    fn make_subscription_key(&self, address: &str, id: &str) -> Result<String> {
+        self.storage.make_key(WEBHOOK_SUBSCRIPTION_KEY_PREFIX, &format!("{}:{}", address, id))
+    }
+
+    fn make_delivery_key(&self, id: &str) -> Result<String> {
+        self.storage.make_key(WEBHOOK_DELIVERY_KEY_PREFIX, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::ingestion_log_storage::IngestionEvent;
+    use crate::database::signature_storage::SolTransfer;
+    use crate::test_util::TempDir;
+
+    const ALICE: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const BOB: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+
+    #[test]
+    fn test_matching_subscriptions_enqueue_deliveries() {
+        let dir = TempDir::new("webhook-test");
+        let storage = WebhookStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        let incoming = storage.subscribe(BOB, 0, Some(TransferDirection::In), "http://127.0.0.1/hook", "s1").unwrap();
+        storage.subscribe(ALICE, 1_000_000, None, "http://127.0.0.1/hook", "s2").unwrap();
+        storage.subscribe(ALICE, 0, Some(TransferDirection::In), "http://127.0.0.1/hook", "s3").unwrap();
+
+        let mut data = SignatureTransactionData::new("sig".to_string(), 1_704_067_200, 1, true);
+        data.add_sol_transfer(SolTransfer {
+            from: ALICE.to_string(),
+            to: BOB.to_string(),
+            amount: 500_000,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        });
+
+        // 只有 BOB 的转入订阅匹配：ALICE 的订阅分别被金额和方向过滤
+        assert_eq!(storage.enqueue_transfers(&data).unwrap(), 1);
+        let due = storage.get_due(10).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].subscription_id, incoming.id);
+        let payload: serde_json::Value = serde_json::from_str(&due[0].payload).unwrap();
+        assert_eq!(payload["direction"], "in");
+        assert_eq!(payload["amount"], 500_000);
+
+        // 失败后推迟投递
+        let retry = storage.record_failure(due[0].clone(), "timeout").unwrap();
+        assert_eq!(retry.attempts, 1);
+        assert!(storage.get_due(10).unwrap().is_empty());
+
+        assert!(storage.unsubscribe(&incoming.id).unwrap());
+        assert!(!storage.unsubscribe(&incoming.id).unwrap());
+        assert_eq!(storage.list_subscriptions(None).unwrap().len(), 2);
+        assert!(storage.list_subscriptions(Some(BOB)).unwrap().is_empty());
+
+        // 运维事件只为订阅的事件类型生成通知
+        let lifecycle = LifecycleWebhookConfig {
+            urls: vec!["http://127.0.0.1/ops".to_string()],
+            secret: "s4".to_string(),
+            events: vec!["disconnected".to_string()],
+        };
+        let entry = |event| IngestionLogEntry { id: "1".to_string(), timestamp: 1_704_067_200, event };
+        let disconnected = entry(IngestionEvent::Disconnected { reason: "stream ended".to_string() });
+        assert_eq!(storage.enqueue_lifecycle(&disconnected, &lifecycle).unwrap(), 1);
+        let lag = entry(IngestionEvent::SlotLag { latest_slot: 200, last_stored_slot: 10, slot_lag: 190 });
+        assert_eq!(storage.enqueue_lifecycle(&lag, &lifecycle).unwrap(), 0);
+        let due = storage.get_due(10).unwrap();
+        assert_eq!((due[0].address.as_str(), due[0].subscription_id.as_str()), (LIFECYCLE_WEBHOOK_ADDRESS, "http://127.0.0.1/ops"));
+        let payload: serde_json::Value = serde_json::from_str(&due[0].payload).unwrap();
+        assert_eq!(payload["kind"], "disconnected");
+    }
+}
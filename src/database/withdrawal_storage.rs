@@ -0,0 +1,327 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::debug;
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::signature_storage::SignatureTransactionData;
+use crate::database::slot_finality_storage::{FinalityStatus, FinalityView};
+use crate::database::storage::StorageManager;
+
+/// 提现记录键前缀，键为提现ID
+pub const WITHDRAWAL_KEY_PREFIX: &str = "WDRL01";
+
+/// 按签名匹配的待观察提现键前缀，键为 `<签名>:<提现ID>`
+pub const SIGNATURE_MATCH_KEY_PREFIX: &str = "WDSG01";
+
+/// 按转账匹配的待观察提现键前缀，键为 `<转出方>:<接收方>:<金额>:<mint 或 SOL>:<登记时间>:<提现ID>`
+pub const TRANSFER_MATCH_KEY_PREFIX: &str = "WDMT01";
+
+/// 已观察、未最终确定的提现索引键前缀，键为 `<槽位>:<提现ID>`
+pub const OBSERVED_WITHDRAWAL_KEY_PREFIX: &str = "WDPD01";
+
+/// 预期提现的匹配方式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ExpectedWithdrawal {
+    /// 按交易签名匹配
+    Signature { signature: String },
+    /// 按转出方、接收方和金额匹配（代币转账按所有者钱包）
+    Transfer {
+        from: String,
+        to: String,
+        amount: u64,
+        /// 代币 mint，SOL 提现为空
+        mint: Option<String>,
+    },
+}
+
+/// 提现状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WithdrawalStatus {
+    /// 已登记，尚未在链上观察到
+    Pending,
+    /// 已观察到，尚未确认
+    Observed,
+    Confirmed,
+    Finalized,
+    /// 所在槽位被丢弃，提现未生效
+    Orphaned,
+}
+
+impl WithdrawalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Observed => "observed",
+            Self::Confirmed => "confirmed",
+            Self::Finalized => "finalized",
+            Self::Orphaned => "orphaned",
+        }
+    }
+
+    /// 由所在槽位的最终性得到的状态，槽位状态未知时为空
+    fn from_finality(status: FinalityStatus) -> Option<Self> {
+        match status {
+            FinalityStatus::Unknown => None,
+            FinalityStatus::Processed => Some(Self::Observed),
+            FinalityStatus::Confirmed => Some(Self::Confirmed),
+            FinalityStatus::Finalized => Some(Self::Finalized),
+            FinalityStatus::Orphaned => Some(Self::Orphaned),
+        }
+    }
+}
+
+/// 提现状态变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalTransition {
+    pub status: WithdrawalStatus,
+    pub at: i64,
+}
+
+/// 登记的预期提现
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Withdrawal {
+    pub id: String,
+    /// 运营方自己的提现编号
+    pub reference: Option<String>,
+    pub expected: ExpectedWithdrawal,
+    pub status: WithdrawalStatus,
+    /// 观察到的交易签名
+    pub signature: Option<String>,
+    /// 观察到的交易所在槽位
+    pub slot: Option<u64>,
+    pub created_at: i64,
+    /// 状态变化记录（按时间顺序）
+    pub transitions: Vec<WithdrawalTransition>,
+}
+
+impl Withdrawal {
+    fn set_status(&mut self, status: WithdrawalStatus, at: i64) {
+        self.status = status;
+        self.transitions.push(WithdrawalTransition { status, at });
+    }
+
+    /// 是否已是最终状态
+    pub fn is_settled(&self) -> bool {
+        matches!(self.status, WithdrawalStatus::Finalized | WithdrawalStatus::Orphaned)
+    }
+}
+
+/// 提现对账存储
+///
+/// 运营方登记预期提现后，交易写入时按签名或转账匹配并标记为 observed，
+/// 之后随槽位最终性更新为 confirmed / finalized，所在槽位被丢弃时为 orphaned。
+/// 每笔预期提现只匹配一次；同一转账条件登记多笔时按登记顺序匹配。
+#[derive(Debug, Clone)]
+pub struct WithdrawalStorage {
+    storage: StorageManager,
+    clock: SharedClock,
+}
+
+impl WithdrawalStorage {
+    /// 创建新的提现存储实例
+    pub fn new(storage: StorageManager) -> Self {
+        Self {
+            storage,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 登记预期提现
+    pub fn register(&self, reference: Option<String>, expected: ExpectedWithdrawal) -> Result<Withdrawal> {
+        let now = self.clock.timestamp();
+        let mut withdrawal = Withdrawal {
+            id: uuid::Uuid::new_v4().to_string(),
+            reference,
+            expected,
+            status: WithdrawalStatus::Pending,
+            signature: None,
+            slot: None,
+            created_at: now,
+            transitions: Vec::new(),
+        };
+        withdrawal.set_status(WithdrawalStatus::Pending, now);
+
+        self.storage.batch_put(vec![
+            (self.storage.make_key(WITHDRAWAL_KEY_PREFIX, &withdrawal.id)?, serde_json::to_value(&withdrawal)?),
+            (self.make_match_key(&withdrawal)?, serde_json::to_value(&withdrawal.id)?),
+        ])?;
+        Ok(withdrawal)
+    }
+
+    /// 获取提现记录
+    pub fn get(&self, id: &str) -> Result<Option<Withdrawal>> {
+        self.storage.get(&self.storage.make_key(WITHDRAWAL_KEY_PREFIX, id)?)
+    }
+
+    /// 匹配交易中的预期提现并标记为 observed，返回新观察到的提现
+    pub fn record_transfers(&self, data: &SignatureTransactionData) -> Result<Vec<Withdrawal>> {
+        if !data.is_successful {
+            return Ok(Vec::new());
+        }
+
+        let mut matched: Vec<(String, String)> = self
+            .storage
+            .get_by_prefix::<String>(&self.storage.make_key(SIGNATURE_MATCH_KEY_PREFIX, &format!("{}:", data.signature))?)?
+            .into_iter()
+            .map(|item| (item.key, item.value))
+            .collect();
+
+        let sol = data
+            .sol_transfers
+            .iter()
+            .map(|transfer| (transfer.from.as_str(), transfer.to.as_str(), transfer.amount, None));
+        let token = data.token_transfers.iter().map(|transfer| {
+            (transfer.sender_wallet(), transfer.receiver_wallet(), transfer.amount, Some(transfer.mint.as_str()))
+        });
+        let mut used: HashSet<String> = matched.iter().map(|(key, _)| key.clone()).collect();
+        for (from, to, amount, mint) in sol.chain(token) {
+            let prefix = self.make_transfer_prefix(from, to, amount, mint)?;
+            // 同一交易中相同的转账依次匹配不同的预期提现
+            let candidate = self
+                .storage
+                .get_by_prefix::<String>(&prefix)?
+                .into_iter()
+                .find(|item| !used.contains(&item.key));
+            if let Some(item) = candidate {
+                used.insert(item.key.clone());
+                matched.push((item.key, item.value));
+            }
+        }
+
+        let now = self.clock.timestamp();
+        let mut observed = Vec::new();
+        for (match_key, id) in matched {
+            self.storage.delete(&match_key)?;
+            let Some(mut withdrawal) = self.get(&id)? else {
+                continue;
+            };
+            if withdrawal.status != WithdrawalStatus::Pending {
+                continue;
+            }
+            withdrawal.signature = Some(data.signature.clone());
+            withdrawal.slot = Some(data.slot);
+            withdrawal.set_status(WithdrawalStatus::Observed, now);
+            self.storage.batch_put(vec![
+                (self.storage.make_key(WITHDRAWAL_KEY_PREFIX, &withdrawal.id)?, serde_json::to_value(&withdrawal)?),
+                (self.make_observed_key(data.slot, &withdrawal.id)?, serde_json::to_value(&withdrawal.id)?),
+            ])?;
+            debug!("提现 {} 在交易 {} 中被观察到", withdrawal.id, data.signature);
+            observed.push(withdrawal);
+        }
+        Ok(observed)
+    }
+
+    /// 按槽位最终性更新已观察的提现，返回状态有变化的提现
+    pub fn refresh_statuses(&self, view: &FinalityView) -> Result<Vec<Withdrawal>> {
+        let mut changed = Vec::new();
+        for item in self.storage.get_by_prefix::<String>(OBSERVED_WITHDRAWAL_KEY_PREFIX)? {
+            let Some(mut withdrawal) = self.get(&item.value)? else {
+                self.storage.delete(&item.key)?;
+                continue;
+            };
+            let Some(slot) = withdrawal.slot else {
+                continue;
+            };
+            let Some(status) = WithdrawalStatus::from_finality(view.status_of(slot)) else {
+                continue;
+            };
+            if status == withdrawal.status {
+                continue;
+            }
+
+            withdrawal.set_status(status, self.clock.timestamp());
+            self.storage.put(&self.storage.make_key(WITHDRAWAL_KEY_PREFIX, &withdrawal.id)?, &withdrawal)?;
+            if withdrawal.is_settled() {
+                self.storage.delete(&item.key)?;
+            }
+            changed.push(withdrawal);
+        }
+        Ok(changed)
+    }
+
+    fn make_match_key(&self, withdrawal: &Withdrawal) -> Result<String> {
+        match &withdrawal.expected {
+            ExpectedWithdrawal::Signature { signature } => self
+                .storage
+                .make_key(SIGNATURE_MATCH_KEY_PREFIX, &format!("{}:{}", signature, withdrawal.id)),
+            ExpectedWithdrawal::Transfer { from, to, amount, mint } => {
+                let prefix = self.make_transfer_prefix(from, to, *amount, mint.as_deref())?;
+                Ok(format!("{}{:020}:{}", prefix, withdrawal.created_at, withdrawal.id))
+            }
+        }
+    }
+
+    fn make_transfer_prefix(&self, from: &str, to: &str, amount: u64, mint: Option<&str>) -> Result<String> {
+        self.storage.make_key(
+            TRANSFER_MATCH_KEY_PREFIX,
+            &format!("{}:{}:{}:{}:", from, to, amount, mint.unwrap_or("SOL")),
+        )
+    }
+
+    fn make_observed_key(&self, slot: u64, id: &str) -> Result<String> {
+        self.storage.make_key(OBSERVED_WITHDRAWAL_KEY_PREFIX, &format!("{:020}:{}", slot, id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::signature_storage::SolTransfer;
+    use crate::test_util::TempDir;
+
+    const HOT_WALLET: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const CUSTOMER: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+
+    fn transfer(signature: &str, slot: u64, amount: u64) -> SignatureTransactionData {
+        let mut data = SignatureTransactionData::new(signature.to_string(), 1_704_067_200, slot, true);
+        data.add_sol_transfer(SolTransfer {
+            from: HOT_WALLET.to_string(),
+            to: CUSTOMER.to_string(),
+            amount,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        });
+        data
+    }
+
+    #[test]
+    fn test_withdrawals_are_matched_and_follow_finality() {
+        let dir = TempDir::new("withdrawal-test");
+        let storage = WithdrawalStorage::new(StorageManager::new(dir.path(), 6).unwrap());
+        let by_transfer = storage
+            .register(
+                Some("wd-1".to_string()),
+                ExpectedWithdrawal::Transfer {
+                    from: HOT_WALLET.to_string(),
+                    to: CUSTOMER.to_string(),
+                    amount: 1_000,
+                    mint: None,
+                },
+            )
+            .unwrap();
+        let by_signature = storage
+            .register(None, ExpectedWithdrawal::Signature { signature: "s2".to_string() })
+            .unwrap();
+
+        // 金额不符不匹配
+        assert!(storage.record_transfers(&transfer("s1", 10, 999)).unwrap().is_empty());
+        let observed = storage.record_transfers(&transfer("s3", 10, 1_000)).unwrap();
+        assert_eq!(observed.iter().map(|w| w.id.as_str()).collect::<Vec<_>>(), vec![by_transfer.id.as_str()]);
+        // 只匹配一次
+        assert!(storage.record_transfers(&transfer("s4", 10, 1_000)).unwrap().is_empty());
+        assert_eq!(storage.record_transfers(&transfer("s2", 11, 5)).unwrap().len(), 1);
+
+        let view = FinalityView { finalized_slot: 10, confirmed_slot: 11, orphaned_slots: HashSet::new() };
+        assert_eq!(storage.refresh_statuses(&view).unwrap().len(), 2);
+        assert!(storage.refresh_statuses(&view).unwrap().is_empty());
+
+        let first = storage.get(&by_transfer.id).unwrap().unwrap();
+        assert_eq!((first.status, first.signature.as_deref(), first.slot), (WithdrawalStatus::Finalized, Some("s3"), Some(10)));
+        assert_eq!(first.transitions.len(), 3);
+        assert_eq!(storage.get(&by_signature.id).unwrap().unwrap().status, WithdrawalStatus::Confirmed);
+    }
+}
@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::config::{DegradationConfig, OverloadStrategy};
+
+/// 数据摄取模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IngestMode {
+    /// 完整模式：保存所有交易明细
+    Full,
+    /// 汇总模式：非监控地址的交易只保存槽位汇总
+    Summary,
+    /// 抽样模式：非监控地址的交易只保留大额转账和按比例抽中的部分
+    Sampled,
+}
+
+/// 摄取状态快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestStatus {
+    /// 当前模式
+    pub mode: IngestMode,
+    /// 进入当前模式的时间戳
+    pub mode_since: i64,
+    /// 模式切换次数
+    pub mode_changes: u64,
+    /// 以汇总形式保存的交易数
+    pub summarized_transactions: u64,
+    /// 抽样模式下丢弃的交易数
+    pub sampled_out_transactions: u64,
+    /// 丢弃的交易中的转账笔数
+    pub sampled_out_transfers: u64,
+    /// 丢弃的交易中的 SOL 转账金额（lamports）
+    pub sampled_out_lamports: u64,
+    /// 最近一次观测到的处理延迟（毫秒）
+    pub last_lag_ms: u64,
+}
+
+#[derive(Debug)]
+struct DegradationState {
+    status: IngestStatus,
+    /// 延迟开始越过阈值的时间，用于判断是否持续过载或持续恢复
+    crossing_since: Option<i64>,
+}
+
+/// 过载降级控制器
+///
+/// 根据交易从服务端产生到本地处理之间的延迟判断是否过载：订阅流按需拉取，积压留在服务端，
+/// 延迟即积压的长度。延迟持续超过 `degrade_lag_ms` 时按 `strategy` 切换到汇总或抽样模式，
+/// 持续低于 `recover_lag_ms` 时恢复完整模式。
+/// 克隆后共享同一状态，供 gRPC 客户端更新、API 查询。
+#[derive(Debug, Clone)]
+pub struct DegradationController {
+    config: DegradationConfig,
+    clock: SharedClock,
+    state: Arc<Mutex<DegradationState>>,
+}
+
+impl DegradationController {
+    /// 创建降级控制器
+    pub fn new(config: DegradationConfig) -> Self {
+        Self::with_clock(config, SystemClock::shared())
+    }
+
+    /// 使用指定时钟创建降级控制器
+    pub fn with_clock(config: DegradationConfig, clock: SharedClock) -> Self {
+        let state = DegradationState {
+            status: IngestStatus {
+                mode: IngestMode::Full,
+                mode_since: clock.timestamp(),
+                mode_changes: 0,
+                summarized_transactions: 0,
+                sampled_out_transactions: 0,
+                sampled_out_transfers: 0,
+                sampled_out_lamports: 0,
+                last_lag_ms: 0,
+            },
+            crossing_since: None,
+        };
+        Self {
+            config,
+            clock,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// 根据更新的产生时间（毫秒时间戳）观测处理延迟，返回当前应使用的模式
+    pub fn observe(&self, created_at_ms: i64) -> IngestMode {
+        let now = self.clock.now();
+        let lag_ms = (now.timestamp_millis() - created_at_ms).max(0) as u64;
+        let now = now.timestamp();
+
+        let mut state = self.state.lock().unwrap();
+        state.status.last_lag_ms = lag_ms;
+        if !self.config.enabled {
+            return state.status.mode;
+        }
+
+        let crossing = match state.status.mode {
+            IngestMode::Full => lag_ms >= self.config.degrade_lag_ms,
+            IngestMode::Summary | IngestMode::Sampled => lag_ms <= self.config.recover_lag_ms,
+        };
+        if !crossing {
+            state.crossing_since = None;
+            return state.status.mode;
+        }
+
+        let since = *state.crossing_since.get_or_insert(now);
+        if now - since >= self.config.sustain_secs as i64 {
+            let next = match (state.status.mode, self.config.strategy) {
+                (IngestMode::Full, OverloadStrategy::Summary) => IngestMode::Summary,
+                (IngestMode::Full, OverloadStrategy::Sample) => IngestMode::Sampled,
+                (IngestMode::Summary | IngestMode::Sampled, _) => IngestMode::Full,
+            };
+            match next {
+                IngestMode::Summary => warn!("⚠️ 持续过载（延迟 {} ms），切换到汇总模式", lag_ms),
+                IngestMode::Sampled => warn!(
+                    "⚠️ 持续过载（延迟 {} ms），切换到抽样模式：保留 {} lamports 以上的转账和 {}% 的其余交易",
+                    lag_ms, self.config.sample_keep_lamports, self.config.sample_percent
+                ),
+                IngestMode::Full => info!("✅ 负载恢复（延迟 {} ms），切换回完整模式", lag_ms),
+            }
+            state.status.mode = next;
+            state.status.mode_since = now;
+            state.status.mode_changes += 1;
+            state.crossing_since = None;
+        }
+
+        state.status.mode
+    }
+
+    /// 记录一笔以汇总形式保存的交易
+    pub fn record_summarized(&self) {
+        self.state.lock().unwrap().status.summarized_transactions += 1;
+    }
+
+    /// 抽样模式下交易是否落在保留比例内
+    ///
+    /// 按签名字节决定（签名本身近似均匀分布），同一交易重放时结果一致。
+    pub fn sampled_in(&self, signature: &[u8]) -> bool {
+        let bucket = signature.iter().take(8).fold(0u64, |acc, byte| (acc << 8) | *byte as u64) % 100;
+        bucket < self.config.sample_percent as u64
+    }
+
+    /// 单笔 SOL 转账金额是否达到抽样模式下必须保留的阈值
+    pub fn keeps_amount(&self, lamports: u64) -> bool {
+        lamports >= self.config.sample_keep_lamports
+    }
+
+    /// 记录一笔抽样模式下丢弃的交易
+    pub fn record_sampled_out(&self, transfers: usize, lamports: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.status.sampled_out_transactions += 1;
+        state.status.sampled_out_transfers += transfers as u64;
+        state.status.sampled_out_lamports += lamports;
+    }
+
+    /// 当前模式
+    pub fn mode(&self) -> IngestMode {
+        self.state.lock().unwrap().status.mode
+    }
+
+    /// 当前状态快照
+    pub fn status(&self) -> IngestStatus {
+        self.state.lock().unwrap().status.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+
+    fn controller(clock: &MockClock) -> DegradationController {
+        let config = DegradationConfig {
+            enabled: true,
+            degrade_lag_ms: 10_000,
+            recover_lag_ms: 1_000,
+            sustain_secs: 5,
+            ..Default::default()
+        };
+        DegradationController::with_clock(config, Arc::new(clock.clone()))
+    }
+
+    fn created_ms_ago(clock: &MockClock, lag_ms: i64) -> i64 {
+        clock.timestamp() * 1000 - lag_ms
+    }
+
+    #[test]
+    fn test_degrades_only_after_sustained_overload_and_recovers() {
+        let clock = MockClock::new(1_700_000_000);
+        let controller = controller(&clock);
+
+        // 短暂的延迟尖峰不会触发降级
+        assert_eq!(controller.observe(created_ms_ago(&clock, 20_000)), IngestMode::Full);
+        clock.advance(2);
+        assert_eq!(controller.observe(created_ms_ago(&clock, 500)), IngestMode::Full);
+
+        // 持续过载超过 sustain_secs 后降级
+        assert_eq!(controller.observe(created_ms_ago(&clock, 20_000)), IngestMode::Full);
+        clock.advance(5);
+        assert_eq!(controller.observe(created_ms_ago(&clock, 20_000)), IngestMode::Summary);
+
+        // 延迟介于两个阈值之间时保持汇总模式
+        clock.advance(10);
+        assert_eq!(controller.observe(created_ms_ago(&clock, 5_000)), IngestMode::Summary);
+
+        // 持续恢复后切换回完整模式
+        assert_eq!(controller.observe(created_ms_ago(&clock, 200)), IngestMode::Summary);
+        clock.advance(5);
+        assert_eq!(controller.observe(created_ms_ago(&clock, 200)), IngestMode::Full);
+
+        let status = controller.status();
+        assert_eq!(status.mode_changes, 2);
+        assert_eq!(status.mode_since, clock.timestamp());
+        assert_eq!(status.last_lag_ms, 200);
+    }
+
+    #[test]
+    fn test_sample_strategy_keeps_large_transfers_and_a_fixed_share() {
+        let clock = MockClock::new(1_700_000_000);
+        let config = DegradationConfig {
+            degrade_lag_ms: 10_000,
+            sustain_secs: 0,
+            strategy: OverloadStrategy::Sample,
+            sample_keep_lamports: 1_000,
+            sample_percent: 25,
+            ..Default::default()
+        };
+        let controller = DegradationController::with_clock(config, Arc::new(clock.clone()));
+        assert_eq!(controller.observe(created_ms_ago(&clock, 20_000)), IngestMode::Sampled);
+
+        assert!(controller.keeps_amount(1_000));
+        assert!(!controller.keeps_amount(999));
+        // 按签名前 8 字节取模，同一签名结果固定
+        let kept = (0u8..100).filter(|byte| controller.sampled_in(&[0, 0, 0, 0, 0, 0, 0, *byte, 0xff])).count();
+        assert_eq!(kept, 25);
+
+        controller.record_sampled_out(2, 500);
+        controller.record_sampled_out(1, 0);
+        let status = controller.status();
+        assert_eq!(
+            (status.sampled_out_transactions, status.sampled_out_transfers, status.sampled_out_lamports),
+            (2, 3, 500)
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::config::DatabaseConfig;
+use crate::database::{
+    AddressStorage, ExtractedAddresses, SignatureStorage, SignatureTransactionData,
+    StorageManager,
+};
+use crate::database::signature_storage::SolTransfer;
+use crate::transfer_types;
+
+/// 诊断工作负载参数
+#[derive(Debug, Clone)]
+pub struct DiagnosticsWorkload {
+    /// 写入的交易数量
+    pub transactions: usize,
+    /// 参与转账的地址池大小
+    pub address_pool: usize,
+    /// 点查询次数
+    pub lookups: usize,
+}
+
+impl Default for DiagnosticsWorkload {
+    fn default() -> Self {
+        Self {
+            transactions: 20_000,
+            address_pool: 2_000,
+            lookups: 5_000,
+        }
+    }
+}
+
+/// 诊断报告
+#[derive(Debug, Clone)]
+pub struct DiagnosticsReport {
+    /// 写入的交易数量
+    pub transactions_written: usize,
+    /// 用户写入的字节数（键 + 值）
+    pub user_bytes_written: u64,
+    /// 刷写与压缩写入磁盘的字节数
+    pub disk_bytes_written: u64,
+    /// 写放大系数
+    pub write_amplification: f64,
+    /// SST 文件总大小
+    pub total_sst_bytes: u64,
+    /// 估算的有效数据大小
+    pub live_data_bytes: u64,
+    /// 空间放大系数
+    pub space_amplification: f64,
+    /// 写入阶段耗时
+    pub write_duration: Duration,
+    /// 点查询延迟 p50
+    pub lookup_p50: Duration,
+    /// 点查询延迟 p99
+    pub lookup_p99: Duration,
+    /// 点查询延迟最大值
+    pub lookup_max: Duration,
+}
+
+/// 写放大 / 空间放大诊断工具
+///
+/// 在临时目录中使用当前配置打开一个 RocksDB 实例，写入合成的交易数据，
+/// 然后根据 RocksDB 的统计信息计算写放大、空间放大以及点查询延迟。
+pub struct Diagnostics;
+
+impl Diagnostics {
+    /// 运行诊断并返回报告
+    pub fn run(db_config: &DatabaseConfig, workload: &DiagnosticsWorkload) -> Result<DiagnosticsReport> {
+        let temp_dir = std::env::temp_dir()
+            .join(format!("ledger-diagnose-{}", uuid::Uuid::new_v4()));
+        info!("🩺 在临时目录中运行诊断: {:?}", temp_dir);
+
+        let result = Self::run_in_dir(&temp_dir, db_config, workload);
+
+        if let Err(e) = std::fs::remove_dir_all(&temp_dir) {
+            warn!("清理诊断临时目录失败 {:?}: {}", temp_dir, e);
+        }
+
+        result
+    }
+
+    fn run_in_dir(
+        dir: &Path,
+        db_config: &DatabaseConfig,
+        workload: &DiagnosticsWorkload,
+    ) -> Result<DiagnosticsReport> {
+        let mut opts = StorageManager::options_from_config(&db_config.rocksdb);
+        opts.enable_statistics();
+
+        let storage = StorageManager::open_with_options(dir, db_config.key_prefix_length, &opts)?;
+        let signature_storage = SignatureStorage::new(storage.clone(), db_config.signature_key_prefix.clone());
+        let address_storage = AddressStorage::new(
+            storage.clone(),
+            db_config.address_key_prefix.clone(),
+            db_config.max_address_records,
+        );
+
+        let mut rng = XorShift::new(0x005E_ED1E_D6E2_u64);
+        let addresses: Vec<String> = (0..workload.address_pool.max(2))
+            .map(|_| rng.next_base58(32))
+            .collect();
+
+        // 1. 写入阶段：走与实时摄取相同的写入路径
+        let write_start = Instant::now();
+        let mut signatures = Vec::with_capacity(workload.transactions);
+        for i in 0..workload.transactions {
+            let signature = rng.next_base58(64);
+            let from = &addresses[rng.next_index(addresses.len())];
+            let to = &addresses[rng.next_index(addresses.len())];
+            let amount = 1_000_000 + rng.next_u64() % 10_000_000_000;
+            let timestamp = 1_700_000_000 + i as i64;
+            let slot = 250_000_000 + i as u64;
+
+            let mut data = SignatureTransactionData::new(signature.clone(), timestamp, slot, true);
+            data.add_sol_transfer(SolTransfer {
+                from: from.clone(),
+                to: to.clone(),
+                amount,
+                transfer_type: "SOL Transfer".to_string(),
+                is_wrapped: false,
+                instruction_path: None,
+            });
+            data.set_extracted_addresses(ExtractedAddresses {
+                all_addresses: vec![from.clone(), to.clone()],
+                ..Default::default()
+            });
+            signature_storage.store_signature_data(&signature, &data)?;
+
+            let parsed = transfer_types::SolTransfer {
+                signature: signature.clone(),
+                from: from.clone(),
+                to: to.clone(),
+                amount,
+                from_index: 0,
+                to_index: 1,
+                timestamp: timestamp as u32,
+                transfer_type: "SOL Transfer".to_string(),
+                is_wrapped: false,
+                instruction_path: None,
+            };
+            address_storage.batch_process_transaction(&signature, timestamp as u64, slot, &[parsed], &[])?;
+
+            signatures.push(signature);
+        }
+        storage.flush()?;
+        storage.compact()?;
+        let write_duration = write_start.elapsed();
+
+        // 2. 统计写放大与空间放大
+        let statistics = opts.get_statistics().context("RocksDB 统计信息未启用")?;
+        let user_bytes_written = parse_ticker(&statistics, "rocksdb.bytes.written");
+        let disk_bytes_written = parse_ticker(&statistics, "rocksdb.flush.write.bytes")
+            + parse_ticker(&statistics, "rocksdb.compact.write.bytes");
+        let total_sst_bytes = storage.get_property_int("rocksdb.total-sst-files-size")?.unwrap_or(0);
+        let live_data_bytes = storage.get_property_int("rocksdb.estimate-live-data-size")?.unwrap_or(0);
+
+        // 3. 点查询延迟
+        let mut latencies = Vec::with_capacity(workload.lookups);
+        for _ in 0..workload.lookups {
+            if signatures.is_empty() {
+                break;
+            }
+            let signature = &signatures[rng.next_index(signatures.len())];
+            let start = Instant::now();
+            signature_storage.get_signature_data(signature)?;
+            latencies.push(start.elapsed());
+        }
+        latencies.sort();
+
+        Ok(DiagnosticsReport {
+            transactions_written: signatures.len(),
+            user_bytes_written,
+            disk_bytes_written,
+            write_amplification: ratio(disk_bytes_written, user_bytes_written),
+            total_sst_bytes,
+            live_data_bytes,
+            space_amplification: ratio(total_sst_bytes, live_data_bytes),
+            write_duration,
+            lookup_p50: percentile(&latencies, 0.50),
+            lookup_p99: percentile(&latencies, 0.99),
+            lookup_max: latencies.last().copied().unwrap_or_default(),
+        })
+    }
+
+    /// 打印诊断报告
+    pub fn print_report(report: &DiagnosticsReport) {
+        info!("📋 RocksDB 诊断报告:");
+        info!("  - 写入交易数: {}", report.transactions_written);
+        info!("  - 写入耗时: {:.2?}", report.write_duration);
+        info!("  - 用户写入字节: {}", report.user_bytes_written);
+        info!("  - 磁盘写入字节（刷写 + 压缩）: {}", report.disk_bytes_written);
+        info!("  - 写放大: {:.2}x", report.write_amplification);
+        info!("  - SST 文件总大小: {} bytes", report.total_sst_bytes);
+        info!("  - 有效数据大小: {} bytes", report.live_data_bytes);
+        info!("  - 空间放大: {:.2}x", report.space_amplification);
+        info!("  - 点查询延迟 p50: {:.2?}", report.lookup_p50);
+        info!("  - 点查询延迟 p99: {:.2?}", report.lookup_p99);
+        info!("  - 点查询延迟 max: {:.2?}", report.lookup_max);
+    }
+}
+
+/// 从 RocksDB 统计字符串中解析计数器，格式如 `rocksdb.bytes.written COUNT : 123`
+fn parse_ticker(statistics: &str, name: &str) -> u64 {
+    statistics
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some(name))
+        .and_then(|line| line.rsplit(':').next())
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::default();
+    }
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index.min(sorted.len() - 1)]
+}
+
+/// 诊断用的确定性伪随机数生成器
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    fn next_index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+
+    fn next_base58(&mut self, byte_len: usize) -> String {
+        let bytes: Vec<u8> = (0..byte_len).map(|_| self.next_u64() as u8).collect();
+        bs58::encode(bytes).into_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ticker() {
+        let stats = "rocksdb.bytes.written COUNT : 1024\nrocksdb.flush.write.bytes COUNT : 2048\n";
+        assert_eq!(parse_ticker(stats, "rocksdb.bytes.written"), 1024);
+        assert_eq!(parse_ticker(stats, "rocksdb.flush.write.bytes"), 2048);
+        assert_eq!(parse_ticker(stats, "rocksdb.compact.write.bytes"), 0);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<Duration> = (1..=100).map(Duration::from_micros).collect();
+        assert_eq!(percentile(&samples, 0.50), Duration::from_micros(51));
+        assert_eq!(percentile(&samples, 0.99), Duration::from_micros(99));
+        assert_eq!(percentile(&[], 0.5), Duration::default());
+    }
+}
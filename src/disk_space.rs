@@ -0,0 +1,222 @@
+//! 磁盘可用空间检查与磁盘用量保护
+//!
+//! 定期检查数据库目录所在磁盘的可用空间，低于 `health.min_free_disk_mb` 和恢复时各写入一条
+//! 摄取审计日志，并推送给订阅了该事件的运维 Webhook。启用 `database.disk_guard` 时还会统计
+//! 数据库目录大小，超过阈值后删除最旧的签名数据或暂停写入，而不是一直写到磁盘满。
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::config::{DiskGuardAction, DiskGuardConfig, LifecycleWebhookConfig};
+use crate::database::{DatabaseManager, IngestionEvent};
+use crate::health::HealthMonitor;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// 路径所在文件系统中非特权进程可用的字节数
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)] // statvfs 字段类型随平台不同
+pub fn available_bytes(path: &Path) -> io::Result<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: path 是以 NUL 结尾的有效 C 字符串，stat 由调用方持有并可写
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+/// 非 Unix 平台不支持检查
+#[cfg(not(unix))]
+pub fn available_bytes(_path: &Path) -> io::Result<u64> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "仅支持 Unix 平台"))
+}
+
+/// 目录下全部文件的总字节数（递归，不跟随符号链接）
+pub fn directory_size(path: &Path) -> io::Result<u64> {
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            total += directory_size(&entry.path())?;
+        } else if file_type.is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+/// 磁盘用量超过保护阈值时返回原因
+pub fn guard_exceeded(config: &DiskGuardConfig, db_size_mb: u64, available_mb: u64) -> Option<String> {
+    if config.max_db_size_mb > 0 && db_size_mb > config.max_db_size_mb {
+        return Some(format!("数据库目录 {} MB 超过上限 {} MB", db_size_mb, config.max_db_size_mb));
+    }
+    if config.min_free_disk_mb > 0 && available_mb < config.min_free_disk_mb {
+        return Some(format!("磁盘可用空间 {} MB 低于下限 {} MB", available_mb, config.min_free_disk_mb));
+    }
+    None
+}
+
+/// 磁盘可用空间监视器，启用磁盘用量保护时同时执行保护动作
+pub struct DiskSpaceWatcher {
+    db_manager: DatabaseManager,
+    health: HealthMonitor,
+    lifecycle: LifecycleWebhookConfig,
+    path: PathBuf,
+    min_free_mb: u64,
+    guard: DiskGuardConfig,
+    interval: Duration,
+    /// 当前生效的保护动作
+    guard_action: Option<DiskGuardAction>,
+}
+
+impl DiskSpaceWatcher {
+    pub fn new(
+        db_manager: DatabaseManager,
+        health: HealthMonitor,
+        lifecycle: LifecycleWebhookConfig,
+        path: impl Into<PathBuf>,
+        min_free_mb: u64,
+        guard: DiskGuardConfig,
+        interval: Duration,
+    ) -> Self {
+        Self { db_manager, health, lifecycle, path: path.into(), min_free_mb, guard, interval, guard_action: None }
+    }
+
+    /// 持续检查，可用空间低于阈值或恢复时写入事件，磁盘用量超过保护阈值时执行保护动作
+    pub async fn run(mut self) {
+        if self.min_free_mb > 0 {
+            info!("💽 磁盘空间检查: {}，可用空间低于 {} MB 时告警", self.path.display(), self.min_free_mb);
+        }
+        if self.guard.enabled {
+            info!(
+                "🛡️ 磁盘用量保护: 数据库目录上限 {} MB，可用空间下限 {} MB，超过时 {}",
+                self.guard.max_db_size_mb,
+                self.guard.min_free_disk_mb,
+                self.guard.action.as_str()
+            );
+        }
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            ticker.tick().await;
+            let available_mb = match available_bytes(&self.path) {
+                Ok(bytes) => bytes / BYTES_PER_MB,
+                Err(e) => {
+                    error!("❌ 读取磁盘可用空间失败 {}: {}", self.path.display(), e);
+                    continue;
+                }
+            };
+            if let Some(low) = self.health.disk_space_change(available_mb) {
+                let (path, min_free_mb) = (self.path.display().to_string(), self.min_free_mb);
+                let event = if low {
+                    warn!(available_mb, min_free_mb, "⚠️ 磁盘可用空间不足: {} 仅剩 {} MB", path, available_mb);
+                    IngestionEvent::DiskSpaceLow { path, available_mb, min_free_mb }
+                } else {
+                    info!(available_mb, min_free_mb, "✅ 磁盘可用空间已恢复: {} 可用 {} MB", path, available_mb);
+                    IngestionEvent::DiskSpaceRecovered { path, available_mb, min_free_mb }
+                };
+                self.record(event).await;
+            }
+
+            if self.guard.enabled {
+                let db_size_mb = match directory_size(&self.path) {
+                    Ok(bytes) => bytes / BYTES_PER_MB,
+                    Err(e) => {
+                        error!("❌ 统计数据库目录大小失败 {}: {}", self.path.display(), e);
+                        continue;
+                    }
+                };
+                self.health.record_db_size(db_size_mb);
+                self.enforce_guard(db_size_mb, available_mb).await;
+            }
+        }
+    }
+
+    /// 超过阈值时先按配置删除最旧的数据，没有可删除的数据或配置为暂停时暂停写入；回到阈值以内后恢复
+    async fn enforce_guard(&mut self, db_size_mb: u64, available_mb: u64) {
+        let Some(reason) = guard_exceeded(&self.guard, db_size_mb, available_mb) else {
+            if self.guard_action.take().is_some() {
+                self.health.set_ingestion_paused(false);
+                info!(db_size_mb, available_mb, "✅ 磁盘用量已回到阈值以内，恢复写入");
+                self.record(IngestionEvent::DiskGuardReleased { db_size_mb, available_mb }).await;
+            }
+            return;
+        };
+
+        let mut action = self.guard.action;
+        if action == DiskGuardAction::Prune {
+            let batch = self.guard.prune_batch.max(1);
+            let pruned = self
+                .db_manager
+                .run_blocking(move |db| {
+                    let pruned = db.delete_oldest_signatures(batch)?;
+                    if pruned > 0 {
+                        db.compact_database()?;
+                    }
+                    Ok(pruned)
+                })
+                .await;
+            match pruned {
+                Ok(0) => {
+                    warn!("⚠️ 没有可删除的签名数据，改为暂停写入");
+                    action = DiskGuardAction::Pause;
+                }
+                Ok(pruned) => warn!(pruned, "🧹 磁盘用量保护: {}，已删除 {} 个最旧的签名", reason, pruned),
+                Err(e) => {
+                    error!("❌ 磁盘用量保护删除旧数据失败，改为暂停写入: {}", e);
+                    action = DiskGuardAction::Pause;
+                }
+            }
+        }
+        self.health.set_ingestion_paused(action == DiskGuardAction::Pause);
+        if self.guard_action == Some(action) {
+            return;
+        }
+        self.guard_action = Some(action);
+        if action == DiskGuardAction::Pause {
+            warn!(db_size_mb, available_mb, "⏸️ 磁盘用量保护: {}，暂停写入", reason);
+        }
+        let action = action.as_str().to_string();
+        self.record(IngestionEvent::DiskGuardTriggered { action, reason, db_size_mb, available_mb }).await;
+    }
+
+    async fn record(&self, event: IngestionEvent) {
+        let lifecycle = self.lifecycle.clone();
+        if let Err(e) = self
+            .db_manager
+            .run_blocking(move |db| db.record_ingestion_event(event, &lifecycle).map(|_| ()))
+            .await
+        {
+            error!("❌ 写入磁盘空间事件失败: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_guard_thresholds_and_directory_size() {
+        let config = DiskGuardConfig { enabled: true, max_db_size_mb: 1_000, min_free_disk_mb: 512, ..Default::default() };
+        assert!(guard_exceeded(&config, 1_000, 512).is_none());
+        assert!(guard_exceeded(&config, 1_001, 10_000).unwrap().contains("上限"));
+        assert!(guard_exceeded(&config, 10, 511).unwrap().contains("下限"));
+        // 阈值为 0 表示不检查
+        let unlimited = DiskGuardConfig { max_db_size_mb: 0, min_free_disk_mb: 0, ..config };
+        assert!(guard_exceeded(&unlimited, u64::MAX, 0).is_none());
+
+        let dir = TempDir::new("disk-space-test");
+        std::fs::create_dir_all(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("a"), [0u8; 100]).unwrap();
+        std::fs::write(dir.path().join("nested").join("b"), [0u8; 50]).unwrap();
+        assert_eq!(directory_size(dir.path()).unwrap(), 150);
+    }
+}
@@ -0,0 +1,134 @@
+//! 转账关系图导出
+//!
+//! 把从种子地址展开的交易对手图写成 Graphviz DOT 或 GraphML 文件，
+//! 供调查时在 Graphviz / Gephi 中查看。节点属性为展开深度，边属性为往来笔数和 SOL 总额。
+
+use anyhow::Result;
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::database::TransferGraph;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT（默认）
+    #[default]
+    Dot,
+    /// GraphML（Gephi、yEd 等）
+    Graphml,
+}
+
+impl FromStr for GraphFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "dot" => Ok(GraphFormat::Dot),
+            "graphml" => Ok(GraphFormat::Graphml),
+            other => Err(anyhow::anyhow!("未知的图格式: {}（可选 dot、graphml）", other)),
+        }
+    }
+}
+
+/// 按格式写出关系图
+pub fn write_graph(graph: &TransferGraph, format: GraphFormat, writer: &mut impl Write) -> Result<()> {
+    match format {
+        GraphFormat::Dot => write_dot(graph, writer),
+        GraphFormat::Graphml => write_graphml(graph, writer),
+    }
+}
+
+/// 无向图，根节点加粗；边宽按往来笔数取对数，避免大户把图撑满
+fn write_dot(graph: &TransferGraph, writer: &mut impl Write) -> Result<()> {
+    writeln!(writer, "graph transfers {{")?;
+    writeln!(writer, "  node [shape=box, fontname=\"monospace\"];")?;
+    for node in &graph.nodes {
+        let style = if node.address == graph.root { ", style=bold" } else { "" };
+        writeln!(writer, "  \"{}\" [depth={}{}];", node.address, node.depth, style)?;
+    }
+    for edge in &graph.edges {
+        let width = 1.0 + (edge.interaction_count.max(1) as f64).ln();
+        writeln!(
+            writer,
+            "  \"{}\" -- \"{}\" [label=\"{}\", interaction_count={}, sol_volume_lamports={}, token_transfer_count={}, penwidth={:.2}];",
+            edge.source,
+            edge.target,
+            edge.interaction_count,
+            edge.interaction_count,
+            edge.sol_volume_lamports,
+            edge.token_transfer_count,
+            width
+        )?;
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+fn write_graphml(graph: &TransferGraph, writer: &mut impl Write) -> Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    writeln!(writer, r#"  <key id="depth" for="node" attr.name="depth" attr.type="int"/>"#)?;
+    writeln!(writer, r#"  <key id="root" for="node" attr.name="root" attr.type="boolean"/>"#)?;
+    writeln!(writer, r#"  <key id="interaction_count" for="edge" attr.name="interaction_count" attr.type="long"/>"#)?;
+    writeln!(writer, r#"  <key id="sol_volume_lamports" for="edge" attr.name="sol_volume_lamports" attr.type="long"/>"#)?;
+    writeln!(writer, r#"  <key id="token_transfer_count" for="edge" attr.name="token_transfer_count" attr.type="long"/>"#)?;
+    writeln!(writer, r#"  <graph id="transfers" edgedefault="undirected">"#)?;
+    // base58 地址只含字母数字，可以直接作为 XML 属性值
+    for node in &graph.nodes {
+        writeln!(writer, r#"    <node id="{}">"#, node.address)?;
+        writeln!(writer, r#"      <data key="depth">{}</data>"#, node.depth)?;
+        writeln!(writer, r#"      <data key="root">{}</data>"#, node.address == graph.root)?;
+        writeln!(writer, "    </node>")?;
+    }
+    for (index, edge) in graph.edges.iter().enumerate() {
+        writeln!(writer, r#"    <edge id="e{}" source="{}" target="{}">"#, index, edge.source, edge.target)?;
+        writeln!(writer, r#"      <data key="interaction_count">{}</data>"#, edge.interaction_count)?;
+        writeln!(writer, r#"      <data key="sol_volume_lamports">{}</data>"#, edge.sol_volume_lamports)?;
+        writeln!(writer, r#"      <data key="token_transfer_count">{}</data>"#, edge.token_transfer_count)?;
+        writeln!(writer, "    </edge>")?;
+    }
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{GraphEdge, GraphNode};
+
+    #[test]
+    fn test_write_dot_and_graphml() {
+        let graph = TransferGraph {
+            root: "alice".to_string(),
+            nodes: vec![
+                GraphNode { address: "alice".to_string(), depth: 0 },
+                GraphNode { address: "bob".to_string(), depth: 1 },
+            ],
+            edges: vec![GraphEdge {
+                source: "alice".to_string(),
+                target: "bob".to_string(),
+                interaction_count: 3,
+                sol_volume_lamports: 1_500_000_000,
+                token_transfer_count: 1,
+            }],
+        };
+
+        let mut dot = Vec::new();
+        write_graph(&graph, "dot".parse().unwrap(), &mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("graph transfers {"));
+        assert!(dot.contains("\"alice\" [depth=0, style=bold];"));
+        assert!(dot.contains("\"alice\" -- \"bob\" [label=\"3\""));
+
+        let mut graphml = Vec::new();
+        write_graph(&graph, GraphFormat::Graphml, &mut graphml).unwrap();
+        let graphml = String::from_utf8(graphml).unwrap();
+        assert!(graphml.contains(r#"<edge id="e0" source="alice" target="bob">"#));
+        assert!(graphml.contains(r#"<data key="sol_volume_lamports">1500000000</data>"#));
+        assert!(graphml.trim_end().ends_with("</graphml>"));
+
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+}
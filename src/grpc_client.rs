@@ -1,59 +1,238 @@
 use anyhow::Result;
 use futures::stream::StreamExt;
-use std::{collections::HashMap, time::Duration};
-use tonic::transport::ClientTlsConfig;
-use tracing::{error, info, warn};
-use yellowstone_grpc_client::GeyserGrpcClient;
+use std::{collections::{HashMap, HashSet}, path::PathBuf, time::Duration};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, error, info, info_span, warn, Instrument};
+use yellowstone_grpc_proto::prost::Message;
 use yellowstone_grpc_proto::prelude::{
-    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
-    SubscribeRequestFilterTransactions, SubscribeUpdate,
+    subscribe_update::UpdateOneof, CommitmentLevel, SlotStatus, SubscribeRequest,
+    SubscribeRequestAccountsDataSlice, SubscribeRequestFilterAccounts,
+    SubscribeRequestFilterBlocks, SubscribeRequestFilterTransactions, SubscribeUpdate,
+    SubscribeUpdateBlock, SubscribeUpdateTransaction,
 };
 
-use crate::config::{GrpcConfig, MonitorConfig};
+use crate::amount::LAMPORTS_PER_SOL;
+use crate::config::{AccountTrackingConfig, GrpcConfig, LifecycleWebhookConfig, MonitorConfig, StorageMode, SubscriptionMode};
+use crate::config_reload::RuntimeSettings;
 use crate::transfer_parser::TransferParser;
+use crate::transaction_source::{TransactionSource, UpdateStream, YellowstoneSource};
+use crate::stream_record::RecordingSource;
 use crate::address_extractor::AddressExtractor;
-use crate::database::{DatabaseManager, SignatureTransactionData, ExtractedAddresses};
+use crate::block_import::read_block_file;
+use crate::database::{
+    AccountUpdate, BlockMetadata, DatabaseManager, SignatureTransactionData, SlotTransactionEntry, IngestionEvent,
+    ProgramInvocationEntry,
+};
+use crate::degradation::{DegradationController, IngestMode};
+use crate::health::HealthMonitor;
+use crate::throughput::ThroughputMeter;
+use crate::slot_tracker::{SlotEvent, SlotTracker};
 use crate::database::signature_storage::{SolTransfer, TokenTransfer};
+use crate::transfer_types::TransactionFailure;
+
+/// 死信队列重试检查间隔
+const DEAD_LETTER_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 每轮最多重试的死信数
+const DEAD_LETTER_RETRY_BATCH: usize = 100;
+
+/// 吞吐量日志输出间隔
+const THROUGHPUT_LOG_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 离线导入时预读的区块数
+const IMPORT_QUEUE_BLOCKS: usize = 16;
+
+/// 离线导入时每批写入的区块元数据数
+const IMPORT_BLOCK_BATCH: usize = 100;
+
+/// 重新解析归档交易的结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReparseReport {
+    /// 成功重新解析并写入的交易数
+    pub reparsed: usize,
+    /// 原始数据无法解码或写入失败的交易数
+    pub failed: usize,
+    /// 有原始数据但没有签名记录（无法确定时间）而跳过的交易数
+    pub missing_signature_data: usize,
+}
+
+/// 离线导入区块文件的结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// 成功读取的区块数
+    pub blocks: usize,
+    /// 无法读取或格式错误的区块文件数
+    pub failed_blocks: usize,
+    /// 新写入的交易数
+    pub imported: usize,
+    /// 已存在而跳过的交易数
+    pub duplicates: usize,
+    /// 按监控配置过滤掉的投票、失败或只调用排除程序的交易数
+    pub filtered: usize,
+    /// 写入失败的交易数
+    pub failed: usize,
+}
 
 /// Solana gRPC 客户端
 pub struct SolanaGrpcClient {
     grpc_config: GrpcConfig,
     monitor_config: MonitorConfig,
+    /// 更新流的数据源，默认为配置的 Yellowstone 端点
+    source: Box<dyn TransactionSource>,
+    parser: TransferParser,
     db_manager: Option<DatabaseManager>,
+    degradation: Option<DegradationController>,
+    health: Option<HealthMonitor>,
+    throughput: ThroughputMeter,
+    account_tracking: AccountTrackingConfig,
+    /// 审计日志事件同时推送的运维 Webhook
+    lifecycle_webhooks: LifecycleWebhookConfig,
+    slot_tracker: Option<SlotTracker>,
+    settings: Option<tokio::sync::watch::Receiver<RuntimeSettings>>,
+    /// 是否曾经订阅成功，用于区分首次订阅和重连
+    subscribed_once: bool,
+    /// 当前连接是否已订阅成功
+    session_active: bool,
+    /// 看到的最大槽位（0 表示尚未看到），用于检测槽位缺口
+    last_seen_slot: AtomicU64,
+    /// 当前连接期间跳过的重复交易数
+    duplicates_skipped: AtomicU64,
+    /// 磁盘用量保护暂停期间丢弃的首个和最后一个槽位（0 表示没有丢弃）
+    paused_first_slot: AtomicU64,
+    paused_last_slot: AtomicU64,
 }
 
 impl SolanaGrpcClient {
     /// 创建新的 gRPC 客户端
     pub fn new(grpc_config: GrpcConfig, monitor_config: MonitorConfig) -> Self {
         Self {
+            source: Box::new(YellowstoneSource::new(grpc_config.clone())),
             grpc_config,
             monitor_config,
+            parser: TransferParser::default(),
             db_manager: None,
+            degradation: None,
+            health: None,
+            throughput: ThroughputMeter::default(),
+            account_tracking: AccountTrackingConfig::default(),
+            lifecycle_webhooks: LifecycleWebhookConfig::default(),
+            slot_tracker: None,
+            settings: None,
+            subscribed_once: false,
+            session_active: false,
+            last_seen_slot: AtomicU64::new(0),
+            duplicates_skipped: AtomicU64::new(0),
+            paused_first_slot: AtomicU64::new(0),
+            paused_last_slot: AtomicU64::new(0),
         }
     }
 
     /// 创建带数据库管理器的 gRPC 客户端
     pub fn with_database(grpc_config: GrpcConfig, monitor_config: MonitorConfig, db_manager: DatabaseManager) -> Self {
         Self {
+            source: Box::new(YellowstoneSource::new(grpc_config.clone())),
             grpc_config,
             monitor_config,
+            parser: TransferParser::default(),
+            slot_tracker: Some(SlotTracker::new(db_manager.clone())),
             db_manager: Some(db_manager),
+            degradation: None,
+            health: None,
+            throughput: ThroughputMeter::default(),
+            account_tracking: AccountTrackingConfig::default(),
+            lifecycle_webhooks: LifecycleWebhookConfig::default(),
+            settings: None,
+            subscribed_once: false,
+            session_active: false,
+            last_seen_slot: AtomicU64::new(0),
+            duplicates_skipped: AtomicU64::new(0),
+            paused_first_slot: AtomicU64::new(0),
+            paused_last_slot: AtomicU64::new(0),
         }
     }
 
+    /// 替换数据源（模拟生成器、文件回放等）
+    pub fn with_source(mut self, source: impl TransactionSource + 'static) -> Self {
+        self.source = Box::new(source);
+        self
+    }
+
+    /// 把当前数据源收到的消息录制到文件
+    pub fn with_recording(mut self, path: PathBuf) -> Self {
+        self.source = Box::new(RecordingSource::new(self.source, path));
+        self
+    }
+
+    /// 注入转账解析器
+    pub fn with_parser(mut self, parser: TransferParser) -> Self {
+        self.parser = parser;
+        self
+    }
+
+    /// 启用过载降级控制
+    pub fn with_degradation(mut self, degradation: DegradationController) -> Self {
+        self.degradation = Some(degradation);
+        self
+    }
+
+    /// 向健康监视器报告连接、消息与写入
+    pub fn with_health(mut self, health: HealthMonitor) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// 共享吞吐量统计，用于吞吐量接口
+    pub fn with_throughput(mut self, throughput: ThroughputMeter) -> Self {
+        self.throughput = throughput;
+        self
+    }
+
+    /// 订阅并保存指定账户的更新
+    pub fn with_account_tracking(mut self, account_tracking: AccountTrackingConfig) -> Self {
+        self.account_tracking = account_tracking;
+        self
+    }
+
+    /// 把连接断开、槽位缺口、存储错误等审计事件推送到运维 Webhook
+    pub fn with_lifecycle_webhooks(mut self, lifecycle_webhooks: LifecycleWebhookConfig) -> Self {
+        self.lifecycle_webhooks = lifecycle_webhooks;
+        self
+    }
+
+    /// 订阅配置热更新：监控过滤条件变化时重新订阅，解析参数立即生效
+    pub fn with_settings(mut self, settings: tokio::sync::watch::Receiver<RuntimeSettings>) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
     /// 开始监听并打印 gRPC 数据
-    pub async fn start_monitoring(&self) -> Result<()> {
+    pub async fn start_monitoring(&mut self) -> Result<()> {
         info!("🚀 开始启动 Solana gRPC 客户端");
         info!("📝 配置信息:");
-        info!("  - gRPC 端点: {}", self.grpc_config.endpoint);
+        info!("  - 数据源: {}", self.source.describe());
         info!("  - 连接超时: {}秒", self.grpc_config.connect_timeout);
         info!("  - 请求超时: {}秒", self.grpc_config.timeout);
+        info!("  - 订阅方式: {:?}", self.grpc_config.subscription);
+        info!("  - Ping 间隔: {}秒, 最长静默: {}秒", self.grpc_config.ping_interval_secs, self.grpc_config.max_silence_secs);
         info!("  - 包含失败交易: {}", self.monitor_config.include_failed_transactions);
         info!("  - 包含投票交易: {}", self.monitor_config.include_vote_transactions);
+        info!("  - 排除程序: {:?}", self.monitor_config.exclude_programs);
 
 
         loop {
-            match self.connect_and_subscribe().await {
+            let result = self.connect_and_subscribe().await;
+            if let Some(ref health) = self.health {
+                health.set_connected(false);
+            }
+            if self.session_active {
+                self.session_active = false;
+                let reason = match &result {
+                    Ok(reason) => reason.to_string(),
+                    Err(e) => e.to_string(),
+                };
+                self.audit_disconnect(reason).await;
+            }
+            match result {
                 Ok(_) => {
                     info!("🔄 连接断开，准备重连...");
                 }
@@ -66,66 +245,212 @@ impl SolanaGrpcClient {
         }
     }
 
-    /// 尝试连接并订阅数据
-    async fn connect_and_subscribe(&self) -> Result<()> {
-        info!("🔗 正在连接到 gRPC 端点: {}", self.grpc_config.endpoint);
+    /// 尝试连接并订阅数据，订阅流正常结束时返回结束原因
+    async fn connect_and_subscribe(&mut self) -> Result<&'static str> {
+        info!("🔗 正在连接到数据源: {}", self.source.describe());
+
+        // 每次订阅前应用最新配置
+        let mut settings_changes = self.settings.clone();
+        if let Some(changes) = settings_changes.as_mut() {
+            let settings = changes.borrow_and_update().clone();
+            self.apply_settings(settings);
+        }
 
-        // 配置 TLS
-        let tls_config = ClientTlsConfig::new().with_native_roots();
+        // 监控列表非空时只订阅涉及这些地址的交易
+        let (account_include, watchlist_changes) = match &self.db_manager {
+            Some(db_manager) => {
+                let watchlist = db_manager.watchlist_storage();
+                let mut changes = watchlist.subscribe_changes();
+                changes.borrow_and_update();
+                (watchlist.get_addresses()?, Some(changes))
+            }
+            None => (vec![], None),
+        };
+        if !account_include.is_empty() {
+            info!("👀 按监控列表过滤交易: {} 个地址", account_include.len());
+        }
+        let watchlist: HashSet<String> = account_include.iter().cloned().collect();
+
+        let subscribe_request = self.subscribe_request(account_include);
+
+        // 建立连接并订阅
+        let stream = self.source.subscribe(subscribe_request).await?;
+        info!("✅ 成功连接到数据源，开始订阅数据...");
+
+        info!("📡 开始监听 Solana 数据流...");
+        if let Some(ref health) = self.health {
+            health.set_connected(true);
+        }
+        let endpoint = self.source.describe();
+        let watchlist_size = watchlist.len();
+        let event = if self.subscribed_once {
+            IngestionEvent::Reconnected { endpoint, watchlist_size }
+        } else {
+            IngestionEvent::Subscribed { endpoint, watchlist_size }
+        };
+        self.subscribed_once = true;
+        self.session_active = true;
+        self.audit(event).await;
+        let mut transaction_count = 0u64;
+        self.consume_stream(stream, watchlist, &mut transaction_count, watchlist_changes, settings_changes)
+            .await
+    }
+
+    /// 订阅一次数据源并摄取到流结束，返回处理的交易数
+    ///
+    /// 不跟随监控列表和配置变化重新订阅，用于压测、集成测试和离线回放。
+    pub async fn ingest_once(&mut self) -> Result<u64> {
+        info!("🔗 正在连接到数据源: {}", self.source.describe());
+        let stream = self.source.subscribe(self.subscribe_request(vec![])).await?;
+        let mut transaction_count = 0u64;
+        self.consume_stream(stream, HashSet::new(), &mut transaction_count, None, None)
+            .await?;
+        Ok(transaction_count)
+    }
+
+    /// 构造订阅请求，`account_include` 为空时不限制账户
+    fn subscribe_request(&self, account_include: Vec<String>) -> SubscribeRequest {
+        // 按订阅方式选择逐笔交易或整块订阅，监控列表为空时不限制账户
+        let (transactions, blocks) = match self.grpc_config.subscription {
+            SubscriptionMode::Transactions => (
+                HashMap::from([(
+                    "txn".to_string(),
+                    SubscribeRequestFilterTransactions {
+                        // Some(false) 表示排除，None 表示不过滤
+                        vote: (!self.monitor_config.include_vote_transactions).then_some(false),
+                        failed: (!self.monitor_config.include_failed_transactions).then_some(false),
+                        signature: None,
+                        account_include,
+                        account_exclude: vec![],
+                        account_required: vec![],
+                    },
+                )]),
+                HashMap::new(),
+            ),
+            SubscriptionMode::Blocks => (
+                HashMap::new(),
+                HashMap::from([(
+                    "block".to_string(),
+                    SubscribeRequestFilterBlocks {
+                        account_include,
+                        include_transactions: Some(true),
+                        include_accounts: Some(false),
+                        include_entries: Some(false),
+                    },
+                )]),
+            ),
+        };
+
+        // 跟踪的账户；未配置数据片段时请求空片段，不接收账户数据
+        let (accounts, accounts_data_slice) = if self.account_tracking.enabled && !self.account_tracking.accounts.is_empty() {
+            info!("👤 跟踪账户更新: {} 个账户", self.account_tracking.accounts.len());
+            let slices = match self.account_tracking.data_slices.as_slice() {
+                [] => vec![SubscribeRequestAccountsDataSlice { offset: 0, length: 0 }],
+                slices => slices
+                    .iter()
+                    .map(|slice| SubscribeRequestAccountsDataSlice { offset: slice.offset, length: slice.length })
+                    .collect(),
+            };
+            let filter = SubscribeRequestFilterAccounts {
+                account: self.account_tracking.accounts.clone(),
+                ..Default::default()
+            };
+            (HashMap::from([("accounts".to_string(), filter)]), slices)
+        } else {
+            (HashMap::new(), vec![])
+        };
 
         // 创建订阅请求 - 修改为更简单的配置来获取更多数据
-        let subscribe_request = SubscribeRequest {
-            accounts: HashMap::new(),
+        SubscribeRequest {
+            accounts,
             slots: HashMap::from([(
                 "slot".to_string(),
                 yellowstone_grpc_proto::prelude::SubscribeRequestFilterSlots {
-                    filter_by_commitment: Some(true),
+                    // 接收所有承诺级别的槽位状态，用于跟踪最终性
+                    filter_by_commitment: Some(false),
                     interslot_updates: Some(false),
                 },
             )]),
-            transactions: HashMap::from([(
-                "txn".to_string(),
-                SubscribeRequestFilterTransactions {
-                    vote: Some(false), // 不包含投票交易以减少噪音
-                    failed: Some(false), // 不包含失败交易
-                    signature: None,
-                    account_include: vec![], // 移除特定账户限制以获取更多交易
-                    account_exclude: vec![],
-                    account_required: vec![],
-                },
-            )]),
+            transactions,
             transactions_status: HashMap::new(),
-            blocks: HashMap::new(),
+            blocks,
             blocks_meta: HashMap::new(),
             entry: HashMap::new(),
-            accounts_data_slice: vec![],
+            accounts_data_slice,
             commitment: Some(CommitmentLevel::Processed as i32),
             from_slot: None,
             ping: None,
-        };
+        }
+    }
 
-        info!("✅ 成功连接到 gRPC 服务器，开始订阅数据...");
+    /// 逐条处理更新流，流结束或需要重新订阅时返回原因
+    async fn consume_stream(
+        &mut self,
+        mut stream: UpdateStream,
+        mut watchlist: HashSet<String>,
+        transaction_count: &mut u64,
+        mut watchlist_changes: Option<tokio::sync::watch::Receiver<u64>>,
+        mut settings_changes: Option<tokio::sync::watch::Receiver<RuntimeSettings>>,
+    ) -> Result<&'static str> {
+        let mut throughput_log = tokio::time::interval_at(
+            tokio::time::Instant::now() + THROUGHPUT_LOG_INTERVAL,
+            THROUGHPUT_LOG_INTERVAL,
+        );
 
-        // 建立连接并订阅
-        let mut stream = GeyserGrpcClient::build_from_shared(self.grpc_config.endpoint.clone())?
-            .tls_config(tls_config)?
-            .timeout(Duration::from_secs(self.grpc_config.timeout))
-            .connect_timeout(Duration::from_secs(self.grpc_config.connect_timeout))
-            .connect()
-            .await?
-            .subscribe_once(subscribe_request)
-            .await?;
+        // 超过最长静默时间没有任何消息时认为连接已失效
+        let max_silence = (self.grpc_config.max_silence_secs > 0)
+            .then(|| Duration::from_secs(self.grpc_config.max_silence_secs));
+        let mut silence_deadline = max_silence.map(|silence| tokio::time::Instant::now() + silence);
 
-        info!("📡 开始监听 Solana 数据流...");
-        let mut message_count = 0u64;
-        let mut transaction_count = 0u64;
+        loop {
+            let message = tokio::select! {
+                message = stream.next() => message,
+                _ = Self::silence_elapsed(silence_deadline) => {
+                    warn!("⚠️ 订阅流 {} 秒没有消息，主动断开并重新订阅", self.grpc_config.max_silence_secs);
+                    return Ok("订阅流静默超时，重新订阅");
+                }
+                _ = throughput_log.tick() => {
+                    self.log_throughput();
+                    continue;
+                }
+                Some(_) = Self::watchlist_changed(&mut watchlist_changes) => {
+                    let account_include = match &self.db_manager {
+                        Some(db_manager) => db_manager.watchlist_storage().get_addresses()?,
+                        None => vec![],
+                    };
+                    // 优先在当前订阅流上推送新的过滤条件，数据源不支持时才重新订阅
+                    if !self.source.update_subscription(self.subscribe_request(account_include.clone())) {
+                        info!("👀 监控列表已更新，重新订阅以应用新的过滤条件");
+                        return Ok("监控列表已更新，重新订阅");
+                    }
+                    info!("👀 监控列表已更新，已推送新的过滤条件: {} 个地址", account_include.len());
+                    watchlist = account_include.into_iter().collect();
+                    continue;
+                }
+                Some(settings) = Self::settings_changed(&mut settings_changes) => {
+                    if self.apply_settings(settings) {
+                        let account_include = watchlist.iter().cloned().collect();
+                        if !self.source.update_subscription(self.subscribe_request(account_include)) {
+                            info!("🔧 监控过滤条件已更新，重新订阅");
+                            return Ok("监控过滤条件已更新，重新订阅");
+                        }
+                        info!("🔧 监控过滤条件已更新，已推送新的订阅请求");
+                    }
+                    continue;
+                }
+            };
+            let Some(message) = message else {
+                return Ok("订阅流结束");
+            };
+            silence_deadline = max_silence.map(|silence| tokio::time::Instant::now() + silence);
 
-        while let Some(message) = stream.next().await {
             match message {
                 Ok(update) => {
-                    message_count += 1;
-                    self.handle_update(update, &mut transaction_count, &mut message_count)
-                        .await?;
+                    self.throughput.record_message();
+                    if let Some(ref health) = self.health {
+                        health.record_message();
+                    }
+                    self.handle_update(update, &watchlist, transaction_count).await?;
                 }
                 Err(e) => {
                     error!("❌ 接收消息时出错: {:?}", e);
@@ -133,54 +458,132 @@ impl SolanaGrpcClient {
                 }
             }
         }
+    }
 
-        Ok(())
+    /// 输出各窗口的吞吐量和降级状态
+    fn log_throughput(&self) {
+        for rate in self.throughput.rates() {
+            info!(
+                "📊 最近 {} 分钟: {:.1} 条消息/秒, {:.1} 笔转账/秒, {:.3} SOL/秒",
+                rate.window_secs / 60,
+                rate.messages_per_sec,
+                rate.transfers_per_sec,
+                rate.lamports_per_sec / LAMPORTS_PER_SOL as f64
+            );
+        }
+        if let Some(ref degradation) = self.degradation {
+            let status = degradation.status();
+            info!("📊 摄取模式: {:?}, 延迟: {} ms, 已汇总交易: {}, 抽样丢弃交易: {}",
+                  status.mode, status.last_lag_ms, status.summarized_transactions, status.sampled_out_transactions);
+        }
+    }
+
+    /// 写入摄取审计日志并推送运维 Webhook，失败只记录错误
+    async fn audit(&self, event: IngestionEvent) {
+        let Some(ref db_manager) = self.db_manager else {
+            return;
+        };
+        let lifecycle = self.lifecycle_webhooks.clone();
+        if let Err(e) = db_manager
+            .run_blocking(move |db| db.record_ingestion_event(event, &lifecycle).map(|_| ()))
+            .await
+        {
+            error!("❌ 写入摄取审计日志失败: {}", e);
+        }
+    }
+
+    /// 记录连接断开，并汇总本次连接期间跳过的重复交易
+    async fn audit_disconnect(&self, reason: String) {
+        let duplicates = self.duplicates_skipped.swap(0, Ordering::SeqCst);
+        if duplicates > 0 {
+            self.audit(IngestionEvent::DuplicatesSkipped { count: duplicates }).await;
+        }
+        self.audit(IngestionEvent::Disconnected { reason }).await;
+    }
+
+    /// 等待静默截止时间；未启用静默检测时永不返回
+    async fn silence_elapsed(deadline: Option<tokio::time::Instant>) {
+        match deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// 等待监控列表变化；没有数据库时永不返回
+    async fn watchlist_changed(changes: &mut Option<tokio::sync::watch::Receiver<u64>>) -> Option<()> {
+        match changes {
+            Some(changes) => changes.changed().await.ok(),
+            None => std::future::pending().await,
+        }
+    }
+
+    /// 等待配置热更新；未订阅配置时永不返回
+    async fn settings_changed(
+        changes: &mut Option<tokio::sync::watch::Receiver<RuntimeSettings>>,
+    ) -> Option<RuntimeSettings> {
+        match changes {
+            Some(changes) => {
+                changes.changed().await.ok()?;
+                Some(changes.borrow_and_update().clone())
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    /// 应用新配置，返回是否需要重新订阅
+    fn apply_settings(&mut self, settings: RuntimeSettings) -> bool {
+        if settings.parser != *self.parser.config() {
+            info!("🔧 转账解析参数已更新");
+            self.parser.set_config(settings.parser);
+        }
+        let resubscribe = settings.monitor.include_vote_transactions != self.monitor_config.include_vote_transactions
+            || settings.monitor.include_failed_transactions != self.monitor_config.include_failed_transactions;
+        self.monitor_config = settings.monitor;
+        resubscribe
     }
 
     /// 处理接收到的更新消息
     async fn handle_update(
         &self,
         update: SubscribeUpdate,
+        watchlist: &HashSet<String>,
         transaction_count: &mut u64,
-        message_count: &mut u64,
     ) -> Result<()> {
-        // 每1000条消息打印一次统计
-        if *message_count % 1000 == 0 {
-            info!("📊 已处理 {} 条消息，其中 {} 条交易", message_count, transaction_count);
-        }
-
         match update.update_oneof {
             Some(UpdateOneof::Transaction(transaction_update)) => {
-                *transaction_count += 1;
-                self.print_transaction_info(&transaction_update, *transaction_count);
-                
                 // 获取时间戳
                 let timestamp = update.created_at
                     .as_ref()
                     .map(|ts| ts.seconds as u32)
                     .unwrap_or_else(|| std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as u32);
-                    
-                // 解析SOL转账
-                self.parse_and_print_transfers(&transaction_update, timestamp);
-                
-                // 提取并打印所有相关地址
-                self.extract_and_print_addresses(&transaction_update);
+                let created_at_ms = update.created_at
+                    .as_ref()
+                    .map(|ts| ts.seconds * 1000 + ts.nanos as i64 / 1_000_000);
 
-                // 如果有数据库管理器，存储交易数据
-                if let Some(ref db_manager) = self.db_manager {
-                    if let Err(e) = self.store_transaction_to_database(db_manager, &transaction_update, timestamp as i64).await {
-                        error!("❌ 存储交易数据到数据库失败: {}", e);
-                    }
-                }
+                self.process_transaction(&transaction_update, timestamp, None, created_at_ms, watchlist, transaction_count)
+                    .await;
             }
             Some(UpdateOneof::Account(account_update)) => {
                 self.print_account_info(&account_update);
+                self.store_account_update(&account_update).await;
             }
             Some(UpdateOneof::Slot(slot_update)) => {
                 self.print_slot_info(&slot_update);
+                if let Some(ref health) = self.health {
+                    health.record_slot(slot_update.slot);
+                }
+                self.check_slot_lag().await;
+                self.detect_slot_gap(&slot_update).await;
+                self.track_slot(&slot_update).await;
             }
             Some(UpdateOneof::Block(block_update)) => {
                 self.print_block_info(&block_update);
+                if self.grpc_config.subscription == SubscriptionMode::Blocks {
+                    let created_at_ms = update.created_at
+                        .as_ref()
+                        .map(|ts| ts.seconds * 1000 + ts.nanos as i64 / 1_000_000);
+                    self.process_block(block_update, created_at_ms, watchlist, transaction_count).await;
+                }
             }
             Some(UpdateOneof::Ping(_)) => {
                 // info!("🏓 收到 Ping 消息");
@@ -190,6 +593,10 @@ impl SolanaGrpcClient {
             }
             Some(UpdateOneof::BlockMeta(block_meta)) => {
                 self.print_block_meta_info(&block_meta);
+                if let Some(ref health) = self.health {
+                    health.record_slot(block_meta.slot);
+                }
+                self.check_slot_lag().await;
             }
             Some(UpdateOneof::Entry(entry_update)) => {
                 self.print_entry_info(&entry_update);
@@ -205,6 +612,447 @@ impl SolanaGrpcClient {
         Ok(())
     }
 
+    /// 处理一笔交易：过载时只保存汇总，否则解析、打印并存储完整明细
+    ///
+    /// `block_time` 仅在整块订阅时提供，此时 `timestamp` 即为区块时间。
+    async fn process_transaction(
+        &self,
+        transaction_update: &SubscribeUpdateTransaction,
+        timestamp: u32,
+        block_time: Option<i64>,
+        created_at_ms: Option<i64>,
+        watchlist: &HashSet<String>,
+        transaction_count: &mut u64,
+    ) {
+        *transaction_count += 1;
+        let transaction_number = *transaction_count;
+
+        // 每笔交易一个 span，解析与存储为其子 span，便于在链路追踪中分析端到端耗时
+        let signature = transaction_update
+            .transaction
+            .as_ref()
+            .map(|tx| bs58::encode(&tx.signature).into_string())
+            .unwrap_or_default();
+        let span = info_span!("transaction", slot = transaction_update.slot, signature = %signature);
+
+        async {
+            // 磁盘用量保护暂停写入期间丢弃交易，只计数并记下丢弃的槽位范围，恢复后写入槽位缺口事件
+            if let Some(ref health) = self.health {
+                if health.ingestion_paused() {
+                    health.record_paused();
+                    let _ = self.paused_first_slot.compare_exchange(0, transaction_update.slot, Ordering::SeqCst, Ordering::SeqCst);
+                    self.paused_last_slot.fetch_max(transaction_update.slot, Ordering::SeqCst);
+                    return;
+                }
+            }
+            self.audit_paused_gap(transaction_update.slot).await;
+
+            // 只调用排除程序的交易不解析、不存储
+            if AddressExtractor::only_invokes(transaction_update, &self.monitor_config.exclude_programs) {
+                if let Some(ref health) = self.health {
+                    health.record_excluded();
+                }
+                return;
+            }
+
+            // 过载降级时，非监控地址的交易只保存槽位汇总，或只保留大额和抽中的交易
+            match self.degraded_mode(created_at_ms, transaction_update, watchlist) {
+                IngestMode::Summary => {
+                    if let Some(ref db_manager) = self.db_manager {
+                        match self.store_transaction_summary(db_manager, transaction_update, timestamp).await {
+                            Ok(true) => {
+                                self.duplicates_skipped.fetch_add(1, Ordering::SeqCst);
+                            }
+                            Ok(false) => {}
+                            Err(e) => error!("❌ 存储槽位汇总失败: {}", e),
+                        }
+                    }
+                    return;
+                }
+                IngestMode::Sampled if !self.keep_sampled(transaction_update, timestamp) => return,
+                _ => {}
+            }
+
+            info_span!("parse").in_scope(|| {
+                self.print_transaction_info(transaction_update, transaction_number);
+
+                // 解析SOL转账
+                self.parse_and_print_transfers(transaction_update, timestamp);
+
+                // 提取并打印所有相关地址
+                self.extract_and_print_addresses(transaction_update);
+            });
+
+            // 如果有数据库管理器，存储交易数据
+            if let Some(ref db_manager) = self.db_manager {
+                match self
+                    .store_transaction_to_database(db_manager, transaction_update, timestamp as i64, block_time)
+                    .await
+                {
+                    Ok(duplicate) => {
+                        if duplicate {
+                            self.duplicates_skipped.fetch_add(1, Ordering::SeqCst);
+                        }
+                        if let Some(ref health) = self.health {
+                            health.record_write(transaction_update.slot);
+                        }
+                    }
+                    Err(e) => {
+                        error!("❌ 存储交易数据到数据库失败: {}", e);
+                        self.dead_letter(db_manager, transaction_update, timestamp as i64, block_time, &e).await;
+                        if let Some(health) = &self.health {
+                            if let Some(count) = health.record_storage_error() {
+                                let window_secs = health.config().storage_error_window_secs;
+                                warn!("⚠️ {} 秒内存储失败 {} 次", window_secs, count);
+                                self.audit(IngestionEvent::StorageErrors { count, window_secs, last_error: e.to_string() }).await;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// 将存储失败的交易放入死信队列，由后台任务重试
+    async fn dead_letter(
+        &self,
+        db_manager: &DatabaseManager,
+        transaction_update: &SubscribeUpdateTransaction,
+        timestamp: i64,
+        block_time: Option<i64>,
+        error: &anyhow::Error,
+    ) {
+        let Some(transaction) = &transaction_update.transaction else {
+            return;
+        };
+        let signature = bs58::encode(&transaction.signature).into_string();
+        let slot = transaction_update.slot;
+        let payload = transaction_update.encode_to_vec();
+        let error = error.to_string();
+        let result = db_manager
+            .run_blocking(move |db| {
+                db.dead_letter_storage()
+                    .record_failure(&signature, slot, timestamp, block_time, payload, &error)
+            })
+            .await;
+        match result {
+            Ok(letter) => warn!("📮 交易 {} 已放入死信队列，下次重试时间 {}", letter.signature, letter.next_retry_at),
+            Err(e) => error!("❌ 写入死信队列失败，交易数据丢失: {}", e),
+        }
+    }
+
+    /// 后台重试死信队列中到期的交易，成功后移出队列，失败则按退避推迟
+    pub async fn retry_dead_letters(db_manager: DatabaseManager, parser: TransferParser) {
+        let mut ticker = tokio::time::interval(DEAD_LETTER_RETRY_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let parser = parser.clone();
+            let result = db_manager
+                .run_blocking(move |db| {
+                    let mut recovered = 0;
+                    for letter in db.dead_letter_storage().get_due(DEAD_LETTER_RETRY_BATCH)? {
+                        let stored = SubscribeUpdateTransaction::decode(letter.payload.as_slice())
+                            .map_err(anyhow::Error::from)
+                            .and_then(|update| {
+                                Self::write_transaction_to_database(db, &parser, &update, letter.timestamp, letter.block_time)
+                            });
+                        match stored {
+                            Ok(_) => {
+                                db.dead_letter_storage().remove(&letter.signature)?;
+                                recovered += 1;
+                            }
+                            Err(e) => {
+                                let retry = db.dead_letter_storage().record_failure(
+                                    &letter.signature,
+                                    letter.slot,
+                                    letter.timestamp,
+                                    letter.block_time,
+                                    Vec::new(),
+                                    &e.to_string(),
+                                )?;
+                                warn!("⚠️ 死信交易 {} 第 {} 次重试失败: {}", letter.signature, retry.attempts, e);
+                            }
+                        }
+                    }
+                    Ok(recovered)
+                })
+                .await;
+            match result {
+                Ok(0) => {}
+                Ok(recovered) => info!("📮 死信队列重试成功 {} 笔交易", recovered),
+                Err(e) => error!("❌ 重试死信队列失败: {}", e),
+            }
+        }
+    }
+
+    /// 用当前解析器重新解析 `source` 中归档的原始交易，写入全新的 `target` 数据库
+    ///
+    /// 派生记录（地址索引、统计、活跃度等）都是累加写入的，原地重写会重复计数，
+    /// 因此写入新库，由运维人员确认后替换原数据库。没有归档原始数据或签名记录的交易不会出现在新库中。
+    pub fn reparse_archived(
+        source: &DatabaseManager,
+        target: &DatabaseManager,
+        parser: &TransferParser,
+    ) -> Result<ReparseReport> {
+        let mut report = ReparseReport::default();
+        for signature in source.raw_transaction_storage().signatures()? {
+            let Some(payload) = source.raw_transaction_storage().get(&signature)? else {
+                continue;
+            };
+            let update = match SubscribeUpdateTransaction::decode(payload.as_slice()) {
+                Ok(update) => update,
+                Err(e) => {
+                    warn!("⚠️ 交易 {} 的原始数据无法解码: {}", signature, e);
+                    report.failed += 1;
+                    continue;
+                }
+            };
+
+            // 沿用首次摄取时记录的时间
+            let Some(data) = source.signature_storage().get_signature_data(&signature)? else {
+                report.missing_signature_data += 1;
+                continue;
+            };
+
+            match Self::write_transaction_to_database(target, parser, &update, data.timestamp, data.block_time) {
+                Ok(_) => report.reparsed += 1,
+                Err(e) => {
+                    warn!("⚠️ 重新解析交易 {} 失败: {}", signature, e);
+                    report.failed += 1;
+                }
+            }
+            if (report.reparsed + report.failed).is_multiple_of(1_000) {
+                info!("🔁 已重新解析 {} 笔交易", report.reparsed);
+            }
+        }
+        Ok(report)
+    }
+
+    /// 离线导入区块文件（RPC `getBlock` JSON），用于在实时订阅前补齐历史数据
+    ///
+    /// 后台线程读取并转换文件，当前线程按槽位顺序写入。交易走与实时摄取相同的解析和索引逻辑，
+    /// 但写入先缓冲在内存中，每批区块的签名、地址、索引数据和区块元数据一起批量提交。
+    /// 已存在的签名会被跳过，因此可以重复执行；中途失败时最多丢失未提交的一批区块。
+    pub fn import_blocks(
+        db: &DatabaseManager,
+        parser: &TransferParser,
+        monitor_config: &MonitorConfig,
+        files: Vec<std::path::PathBuf>,
+    ) -> Result<ImportReport> {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(IMPORT_QUEUE_BLOCKS);
+        let reader = std::thread::spawn(move || {
+            for file in files {
+                let block = read_block_file(&file);
+                if sender.send((file, block)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let (db_manager, buffer) = db.buffered();
+        let db_manager = &db_manager;
+        let mut report = ImportReport::default();
+        let mut pending_blocks = Vec::with_capacity(IMPORT_BLOCK_BATCH);
+        for (file, block) in receiver {
+            let block = match block {
+                Ok(block) => block,
+                Err(e) => {
+                    warn!("⚠️ 跳过区块文件 {:?}: {:#}", file, e);
+                    report.failed_blocks += 1;
+                    continue;
+                }
+            };
+
+            // 区块时间缺失时（很早期的区块）退回到当前时间
+            let block_time = block.metadata.block_time;
+            let timestamp = block_time.unwrap_or_else(|| {
+                std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64
+            });
+            for transaction_update in &block.transactions {
+                let Some(transaction) = &transaction_update.transaction else {
+                    continue;
+                };
+                let failed = transaction.meta.as_ref().map(|meta| meta.err.is_some()).unwrap_or(false);
+                if (transaction.is_vote && !monitor_config.include_vote_transactions)
+                    || (failed && !monitor_config.include_failed_transactions)
+                    || AddressExtractor::only_invokes(transaction_update, &monitor_config.exclude_programs)
+                {
+                    report.filtered += 1;
+                    continue;
+                }
+
+                match Self::write_transaction_to_database(db_manager, parser, transaction_update, timestamp, block_time) {
+                    Ok(true) => report.duplicates += 1,
+                    Ok(false) => report.imported += 1,
+                    Err(e) => {
+                        warn!("⚠️ 导入槽位 {} 的交易失败: {}", transaction_update.slot, e);
+                        report.failed += 1;
+                    }
+                }
+            }
+
+            pending_blocks.push(block.metadata);
+            report.blocks += 1;
+            if pending_blocks.len() >= IMPORT_BLOCK_BATCH {
+                db_manager.block_storage().store_blocks(&pending_blocks)?;
+                pending_blocks.clear();
+                buffer.commit()?;
+                info!("📥 已导入 {} 个区块，{} 笔交易", report.blocks, report.imported);
+            }
+        }
+        if !pending_blocks.is_empty() {
+            db_manager.block_storage().store_blocks(&pending_blocks)?;
+        }
+        buffer.commit()?;
+
+        reader.join().map_err(|_| anyhow::anyhow!("区块文件读取线程异常退出"))?;
+        Ok(report)
+    }
+
+    /// 处理整块更新：保存区块元数据，并按块内顺序处理其中的交易
+    async fn process_block(
+        &self,
+        block_update: SubscribeUpdateBlock,
+        created_at_ms: Option<i64>,
+        watchlist: &HashSet<String>,
+        transaction_count: &mut u64,
+    ) {
+        let block_time = block_update.block_time.as_ref().map(|time| time.timestamp);
+
+        if let Some(ref db_manager) = self.db_manager {
+            let metadata = BlockMetadata {
+                slot: block_update.slot,
+                blockhash: block_update.blockhash.clone(),
+                parent_slot: block_update.parent_slot,
+                parent_blockhash: block_update.parent_blockhash.clone(),
+                block_time,
+                block_height: block_update.block_height.as_ref().map(|height| height.block_height),
+                transaction_count: block_update.executed_transaction_count,
+            };
+            if let Err(e) = db_manager.run_blocking(move |db| db.block_storage().store_block(&metadata)).await {
+                error!("❌ 存储区块 {} 元数据失败: {}", block_update.slot, e);
+            }
+        }
+
+        // 区块时间缺失时退回到消息创建时间
+        let timestamp = block_time
+            .or_else(|| created_at_ms.map(|ms| ms / 1000))
+            .unwrap_or_else(|| std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64)
+            as u32;
+
+        let slot = block_update.slot;
+        let mut transactions = block_update.transactions;
+        transactions.sort_by_key(|transaction| transaction.index);
+        for transaction in transactions {
+            // 整块订阅无法在服务端过滤投票和失败交易，按监控配置在本地过滤
+            if transaction.is_vote && !self.monitor_config.include_vote_transactions {
+                continue;
+            }
+            let failed = transaction.meta.as_ref().map(|meta| meta.err.is_some()).unwrap_or(false);
+            if failed && !self.monitor_config.include_failed_transactions {
+                continue;
+            }
+
+            let transaction_update = SubscribeUpdateTransaction {
+                transaction: Some(transaction),
+                slot,
+            };
+            self.process_transaction(&transaction_update, timestamp, block_time, created_at_ms, watchlist, transaction_count)
+                .await;
+        }
+    }
+
+    /// 交易适用的降级模式：处于汇总或抽样模式且不涉及监控地址时返回该模式，否则为完整模式
+    fn degraded_mode(
+        &self,
+        created_at_ms: Option<i64>,
+        transaction_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction,
+        watchlist: &HashSet<String>,
+    ) -> IngestMode {
+        let Some(ref degradation) = self.degradation else {
+            return IngestMode::Full;
+        };
+
+        let mode = match created_at_ms {
+            Some(created_at_ms) => degradation.observe(created_at_ms),
+            None => degradation.mode(),
+        };
+        if mode == IngestMode::Full {
+            return mode;
+        }
+
+        // 监控地址保留完整明细
+        match AddressExtractor::extract_all_addresses(transaction_update) {
+            Ok(addresses) if addresses.iter().any(|address| watchlist.contains(address)) => IngestMode::Full,
+            _ => mode,
+        }
+    }
+
+    /// 抽样模式下判断是否保留交易：抽中或含有达到阈值的 SOL 转账时保留，否则计入丢弃计数
+    fn keep_sampled(
+        &self,
+        transaction_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction,
+        timestamp: u32,
+    ) -> bool {
+        let (Some(degradation), Some(transaction)) = (&self.degradation, &transaction_update.transaction) else {
+            return true;
+        };
+        if degradation.sampled_in(&transaction.signature) {
+            return true;
+        }
+
+        let (sol_transfers, token_transfers) = self.parser.parse_transfers(transaction_update, timestamp).unwrap_or_default();
+        if sol_transfers.iter().any(|transfer| degradation.keeps_amount(transfer.amount)) {
+            return true;
+        }
+        let lamports = sol_transfers.iter().map(|transfer| transfer.amount).sum();
+        degradation.record_sampled_out(sol_transfers.len() + token_transfers.len(), lamports);
+        false
+    }
+
+    /// 将交易累加到槽位汇总，返回交易是否因重复推送而被跳过
+    ///
+    /// 汇总模式不写签名数据，重连后重复推送的交易靠最近签名缓存识别，避免重复累加。
+    async fn store_transaction_summary(
+        &self,
+        db_manager: &DatabaseManager,
+        transaction_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction,
+        timestamp: u32,
+    ) -> Result<bool> {
+        if let Some(transaction) = &transaction_update.transaction {
+            let signature = bs58::encode(&transaction.signature).into_string();
+            if db_manager.signature_storage().remember_recent(&signature) {
+                return Ok(true);
+            }
+        }
+
+        let (sol_transfers, token_transfers) = self.parser.parse_transfers(transaction_update, timestamp).unwrap_or_default();
+        let token_transfer_count = token_transfers.len();
+        let lamports = sol_transfers.iter().map(|transfer| transfer.amount).sum();
+        let sol_transfer_count = sol_transfers.len();
+        self.throughput.record_transfers(sol_transfer_count + token_transfer_count, lamports);
+        let slot = transaction_update.slot;
+        let fee_info = self.parser.parse_fee_info(transaction_update);
+
+        db_manager
+            .run_blocking(move |db| {
+                db.slot_summary_storage().add_transaction(slot, sol_transfer_count, token_transfer_count, lamports)?;
+                if let Some(fee_info) = fee_info {
+                    db.fee_stats_storage()
+                        .add_fee(timestamp as i64, fee_info.fee_lamports, fee_info.compute_units)?;
+                }
+                Ok(())
+            })
+            .await?;
+
+        if let Some(ref degradation) = self.degradation {
+            degradation.record_summarized();
+        }
+        Ok(false)
+    }
+
     /// 打印交易信息
     fn print_transaction_info(
         &self,
@@ -267,6 +1115,106 @@ impl SolanaGrpcClient {
         }
     }
 
+    /// 保存跟踪账户的更新
+    async fn store_account_update(&self, account_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateAccount) {
+        let (Some(db_manager), Some(account)) = (&self.db_manager, &account_update.account) else {
+            return;
+        };
+        if !self.account_tracking.enabled {
+            return;
+        }
+
+        let update = AccountUpdate {
+            pubkey: bs58::encode(&account.pubkey).into_string(),
+            slot: account_update.slot,
+            lamports: account.lamports,
+            owner: bs58::encode(&account.owner).into_string(),
+            executable: account.executable,
+            data: account.data.iter().map(|byte| format!("{:02x}", byte)).collect(),
+            write_version: account.write_version,
+            txn_signature: account.txn_signature.as_ref().map(|signature| bs58::encode(signature).into_string()),
+        };
+        let pubkey = update.pubkey.clone();
+        if let Err(e) = db_manager.run_blocking(move |db| db.account_update_storage().record_update(&update)).await {
+            error!("❌ 保存账户 {} 的更新失败: {}", pubkey, e);
+        }
+    }
+
+    /// 暂停写入结束后，把暂停期间丢弃的槽位范围记为槽位缺口
+    async fn audit_paused_gap(&self, slot: u64) {
+        let first = self.paused_first_slot.swap(0, Ordering::SeqCst);
+        if first == 0 {
+            return;
+        }
+        let last = self.paused_last_slot.swap(0, Ordering::SeqCst);
+        warn!("⚠️ 磁盘用量保护暂停期间丢弃了槽位 {} 到 {} 的交易", first, last);
+        self.audit(IngestionEvent::SlotGap { last_seen_slot: first.saturating_sub(1), parent_slot: last, slot }).await;
+    }
+
+    /// 根据父槽位检测中断期间遗漏的槽位
+    async fn detect_slot_gap(&self, slot_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateSlot) {
+        if slot_update.status() != SlotStatus::SlotProcessed {
+            return;
+        }
+        let previous = self.last_seen_slot.fetch_max(slot_update.slot, Ordering::SeqCst);
+        if let Some(gap) = IngestionEvent::slot_gap((previous > 0).then_some(previous), slot_update.slot, slot_update.parent) {
+            warn!("⚠️ 检测到槽位缺口: 上次槽位 {}，新槽位 {} 的父槽位 {:?}", previous, slot_update.slot, slot_update.parent);
+            self.audit(gap).await;
+        }
+    }
+
+    /// 槽位落后超过阈值或恢复时输出结构化日志并写入摄取审计日志
+    async fn check_slot_lag(&self) {
+        let Some(health) = self.health.as_ref().and_then(|health| health.lag_change()) else {
+            return;
+        };
+        let (latest_slot, last_stored_slot, slot_lag) = (health.latest_slot, health.last_stored_slot, health.slot_lag);
+        let event = if health.lagging {
+            warn!(latest_slot, last_stored_slot, slot_lag, "⚠️ 摄取落后最新槽位 {} 个", slot_lag);
+            IngestionEvent::SlotLag { latest_slot, last_stored_slot, slot_lag }
+        } else {
+            info!(latest_slot, last_stored_slot, slot_lag, "✅ 摄取槽位落后已恢复");
+            IngestionEvent::SlotLagRecovered { latest_slot, last_stored_slot, slot_lag }
+        };
+        self.audit(event).await;
+    }
+
+    /// 将槽位状态变化交给最终性跟踪器
+    async fn track_slot(&self, slot_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateSlot) {
+        let (Some(db_manager), Some(tracker)) = (&self.db_manager, &self.slot_tracker) else {
+            return;
+        };
+        let event = match slot_update.status() {
+            SlotStatus::SlotProcessed => SlotEvent::Processed,
+            SlotStatus::SlotConfirmed => SlotEvent::Confirmed,
+            SlotStatus::SlotFinalized => SlotEvent::Finalized,
+            SlotStatus::SlotDead => SlotEvent::Dead,
+            _ => return,
+        };
+
+        let tracker = tracker.clone();
+        let (slot, parent) = (slot_update.slot, slot_update.parent);
+        let result = db_manager
+            .run_blocking(move |db| {
+                tracker.observe(slot, parent, event)?;
+                // 确认、最终确定或死亡时更新充值和提现状态
+                if event != SlotEvent::Processed {
+                    let view = db.slot_finality_storage().view()?;
+                    for deposit in db.deposit_storage().refresh_statuses(&view)? {
+                        info!("💰 充值 {} 状态更新为 {}", deposit.id, deposit.status.as_str());
+                    }
+                    for withdrawal in db.withdrawal_storage().refresh_statuses(&view)? {
+                        info!("🏧 提现 {} 状态更新为 {}", withdrawal.id, withdrawal.status.as_str());
+                    }
+                }
+                Ok(())
+            })
+            .await;
+        if let Err(e) = result {
+            error!("❌ 更新槽位 {} 最终性失败: {}", slot, e);
+        }
+    }
+
     /// 打印槽位信息
     fn print_slot_info(&self, slot_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateSlot) {
         info!("🎯 槽位更新 - 槽位: {}", slot_update.slot);
@@ -318,13 +1266,15 @@ impl SolanaGrpcClient {
         }
     }
 
-    /// 解析并打印转账信息
+    /// 解析并打印转账信息，同时计入吞吐量统计
     fn parse_and_print_transfers(&self, transaction_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction, timestamp: u32) {
         // 解析SOL转账
-        match TransferParser::parse_sol_transfers(transaction_update, timestamp) {
+        match self.parser.parse_sol_transfers(transaction_update, timestamp) {
             Ok(sol_transfers) => {
+                let lamports = sol_transfers.iter().map(|transfer| transfer.amount).sum();
+                self.throughput.record_transfers(sol_transfers.len(), lamports);
                 if !sol_transfers.is_empty() {
-                    TransferParser::print_transfers(&sol_transfers);
+                    self.parser.print_transfers(&sol_transfers);
                     
                     // // 统计信息
                     // let total_amount = TransferParser::get_total_transfer_amount(&sol_transfers);
@@ -342,11 +1292,12 @@ impl SolanaGrpcClient {
             }
         }
 
-        // 解析代币转账
-        match TransferParser::parse_token_transfers(transaction_update, timestamp) {
+        // 记录代币转账
+        match self.parser.parse_token_transfers(transaction_update, timestamp) {
             Ok(token_transfers) => {
+                self.throughput.record_transfers(token_transfers.len(), 0);
                 if !token_transfers.is_empty() {
-                    TransferParser::print_token_transfers(&token_transfers);
+                    self.parser.print_token_transfers(&token_transfers);
                     
                     // // 统计信息
                     // let token_count = TransferParser::get_total_token_transfer_count(&token_transfers);
@@ -386,29 +1337,51 @@ impl SolanaGrpcClient {
         }
     }
 
-    /// 将交易数据存储到数据库
+    /// 将交易数据存储到数据库（在阻塞线程池中执行，避免 RocksDB I/O 阻塞运行时）
     async fn store_transaction_to_database(
         &self,
         db_manager: &DatabaseManager,
         transaction_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction,
         timestamp: i64,
-    ) -> Result<()> {
+        block_time: Option<i64>,
+    ) -> Result<bool> {
+        let transaction_update = transaction_update.clone();
+        let parser = self.parser.clone();
+        // 在阻塞线程中进入 span，写入耗时计入当前交易
+        let span = info_span!("store");
+        db_manager
+            .run_blocking(move |db| {
+                span.in_scope(|| Self::write_transaction_to_database(db, &parser, &transaction_update, timestamp, block_time))
+            })
+            .await
+    }
+
+    /// 解析交易并写入各存储（同步执行），返回交易是否因已存在而被跳过
+    pub(crate) fn write_transaction_to_database(
+        db_manager: &DatabaseManager,
+        parser: &TransferParser,
+        transaction_update: &yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction,
+        timestamp: i64,
+        block_time: Option<i64>,
+    ) -> Result<bool> {
         let transaction = match &transaction_update.transaction {
             Some(tx) => tx,
             None => {
                 warn!("交易数据为空，跳过存储");
-                return Ok(());
+                return Ok(false);
             }
         };
 
         // 获取交易签名
         let signature = bs58::encode(&transaction.signature).into_string();
 
-        // 检查是否已存在
-        if let Ok(exists) = db_manager.signature_storage().signature_exists(&signature) {
-            if exists {
-                // 交易已存在，跳过
-                return Ok(());
+        // 检查是否已存在（精简模式不写签名记录，查了也总是不存在）
+        if db_manager.storage_mode() != StorageMode::Lite {
+            if let Ok(exists) = db_manager.signature_storage().signature_exists(&signature) {
+                if exists {
+                    // 交易已存在，跳过
+                    return Ok(true);
+                }
             }
         }
 
@@ -423,61 +1396,112 @@ impl SolanaGrpcClient {
                 .unwrap_or(false),
         );
 
-        // 解析 SOL 转账
-        if let Ok(sol_transfers) = TransferParser::parse_sol_transfers(transaction_update, timestamp as u32) {
-            for transfer in sol_transfers {
-                signature_data.add_sol_transfer(SolTransfer {
-                    from: transfer.from,
-                    to: transfer.to,
-                    amount: transfer.amount,
-                    transfer_type: "SOL Transfer".to_string(),
-                });
-            }
+        // 解码失败交易的错误
+        if let Some(err) = transaction.meta.as_ref().and_then(|meta| meta.err.as_ref()) {
+            signature_data.set_failure(TransactionFailure::decode(&err.err));
+        }
+
+        // 记录块内序号与区块时间
+        signature_data.set_block_position(transaction.index, block_time);
+
+        // 记录手续费与计算单元
+        let fee_info = parser.parse_fee_info(transaction_update);
+        if let Some(fee_info) = &fee_info {
+            signature_data.set_fee_info(fee_info.fee_lamports, fee_info.compute_units, fee_info.fee_payer.clone());
+        }
+
+        // 解析 SOL 与代币转账（wSOL 已并入 SOL 转账）
+        let (sol_transfers, token_transfers) = parser.parse_transfers(transaction_update, timestamp as u32).unwrap_or_default();
+        for transfer in sol_transfers {
+            signature_data.add_sol_transfer(SolTransfer {
+                from: transfer.from,
+                to: transfer.to,
+                amount: transfer.amount,
+                transfer_type: "SOL Transfer".to_string(),
+                is_wrapped: transfer.is_wrapped,
+                instruction_path: transfer.instruction_path,
+            });
         }
 
-        // 解析代币转账
+        // 记录代币转账
         let mut parsed_token_transfers = Vec::new();
-        if let Ok(token_transfers) = TransferParser::parse_token_transfers(transaction_update, timestamp as u32) {
-            for transfer in token_transfers {
-                let token_transfer = TokenTransfer {
-                    from: transfer.from.clone(),
-                    to: transfer.to.clone(),
-                    amount: transfer.amount,
-                    decimals: transfer.decimals as u8,
-                    mint: transfer.mint.clone(),
-                    program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
-                    transfer_type: "Token Transfer".to_string(),
-                };
-                signature_data.add_token_transfer(token_transfer.clone());
-                
-                // 为地址存储创建带有完整字段的transfer_parser::TokenTransfer
-                let parser_token_transfer = crate::transfer_parser::TokenTransfer {
-                    signature: signature.clone(),
-                    from: transfer.from,
-                    to: transfer.to,
-                    amount: transfer.amount,
-                    mint: transfer.mint,
-                    decimals: transfer.decimals,
-                    timestamp: timestamp as u32,
-                    program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
-                    transfer_type: "Token Transfer".to_string(),
-                };
-                parsed_token_transfers.push(parser_token_transfer);
+        for transfer in token_transfers {
+            let token_transfer = TokenTransfer {
+                from: transfer.from.clone(),
+                to: transfer.to.clone(),
+                amount: transfer.amount,
+                decimals: transfer.decimals as u8,
+                mint: transfer.mint.clone(),
+                program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+                transfer_type: "Token Transfer".to_string(),
+                from_owner: transfer.from_owner.clone(),
+                to_owner: transfer.to_owner.clone(),
+            };
+            signature_data.add_token_transfer(token_transfer.clone());
+            
+            // 为地址存储创建带有完整字段的transfer_parser::TokenTransfer
+            let parser_token_transfer = crate::transfer_parser::TokenTransfer {
+                signature: signature.clone(),
+                from: transfer.from,
+                to: transfer.to,
+                amount: transfer.amount,
+                mint: transfer.mint,
+                decimals: transfer.decimals,
+                timestamp: timestamp as u32,
+                program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+                transfer_type: "Token Transfer".to_string(),
+                from_owner: transfer.from_owner,
+                to_owner: transfer.to_owner,
+            };
+            parsed_token_transfers.push(parser_token_transfer);
+        }
+
+        // 精简模式只写入地址转账记录，重复交易由地址记录按转账ID去重
+        if db_manager.storage_mode() == StorageMode::Lite {
+            let parsed_sol_transfers = Self::address_sol_transfers(&signature, timestamp, &signature_data.sol_transfers);
+            let updated_addresses = db_manager.address_storage().batch_process_transaction(
+                &signature,
+                timestamp as u64,
+                transaction_update.slot,
+                &parsed_sol_transfers,
+                &parsed_token_transfers,
+            )?;
+            // 有转账却没有任何地址记录变化，说明这笔交易已经写入过
+            if updated_addresses == 0 && (!parsed_sol_transfers.is_empty() || !parsed_token_transfers.is_empty()) {
+                return Ok(true);
             }
+            debug!("🏠 成功存储交易 {} 到地址数据库（精简模式）", &signature[..8]);
+            db_manager.query_cache().invalidate_transaction(&signature_data);
+            db_manager.transfer_feed().publish(&signature_data);
+            return Ok(false);
         }
 
+        // 解析质押操作
+        let stake_events = parser.parse_stake_events(transaction_update, timestamp as u32).unwrap_or_default();
+        signature_data.set_stake_events(stake_events.clone());
+
+        // 配对兑换交易的转出腿与转入腿
+        let swaps = parser.parse_swaps(transaction_update, timestamp as u32).unwrap_or_default();
+        signature_data.set_swaps(swaps.clone());
+
+        // 识别 NFT 转账
+        let nft_transfers = parser.parse_nft_transfers(transaction_update, &parsed_token_transfers).unwrap_or_default();
+        signature_data.set_nft_transfers(nft_transfers.clone());
+
         // 提取地址信息
-        if let Ok(addresses) = AddressExtractor::extract_all_addresses(transaction_update) {
-            let extracted_addresses = ExtractedAddresses {
-                all_addresses: addresses,
-            };
+        if let Ok(extracted_addresses) = AddressExtractor::extract(transaction_update) {
             signature_data.set_extracted_addresses(extracted_addresses);
         }
 
+        // 按缓存价格估算 USD 价值
+        if let Some(oracle) = db_manager.price_oracle() {
+            signature_data.set_usd_valuation(oracle.valuate(&signature_data));
+        }
+
         // 存储到签名数据库
         match db_manager.signature_storage().store_signature_data(&signature, &signature_data) {
             Ok(_) => {
-                info!("💾 成功存储交易 {} 到签名数据库", &signature[..8]);
+                debug!("💾 成功存储交易 {} 到签名数据库", &signature[..8]);
             }
             Err(e) => {
                 error!("❌ 存储交易 {} 到签名数据库失败: {}", &signature[..8], e);
@@ -485,19 +1509,173 @@ impl SolanaGrpcClient {
             }
         }
 
-        // 同时存储到地址数据库
-        let parsed_sol_transfers: Vec<crate::transfer_parser::SolTransfer> = signature_data.sol_transfers.iter().map(|st| {
-            crate::transfer_parser::SolTransfer {
-                signature: signature.clone(),
-                from: st.from.clone(),
-                to: st.to.clone(),
-                from_index: 0, // 这些字段在地址存储中不使用
-                to_index: 0,
-                amount: st.amount,
-                timestamp: timestamp as u32,
-                transfer_type: st.transfer_type.clone(),
+        // 推送给实时订阅者
+        db_manager.transfer_feed().publish(&signature_data);
+
+        // 为匹配的 Webhook 订阅生成通知
+        if let Err(e) = db_manager.webhook_storage().enqueue_transfers(&signature_data) {
+            error!("❌ 生成交易 {} 的 Webhook 通知失败: {}", &signature[..8], e);
+        }
+
+        // 记录转入充值地址的转账
+        match db_manager.deposit_storage().record_transfers(&signature_data) {
+            Ok(deposits) => {
+                for deposit in deposits {
+                    info!("💰 交易 {} 充值 {} 到用户 {} 的地址 {}", &signature[..8], deposit.amount, deposit.user_id, deposit.address);
+                }
+            }
+            Err(e) => error!("❌ 记录交易 {} 的充值失败: {}", &signature[..8], e),
+        }
+
+        // 匹配登记的预期提现
+        match db_manager.withdrawal_storage().record_transfers(&signature_data) {
+            Ok(withdrawals) => {
+                for withdrawal in withdrawals {
+                    info!("🏧 交易 {} 对应提现 {}", &signature[..8], withdrawal.id);
+                }
+            }
+            Err(e) => error!("❌ 匹配交易 {} 的提现失败: {}", &signature[..8], e),
+        }
+
+        // 归档原始交易，供解析器改进后重新解析
+        let raw_transaction_storage = db_manager.raw_transaction_storage();
+        if raw_transaction_storage.is_enabled() {
+            if let Err(e) = raw_transaction_storage.store(&signature, &transaction_update.encode_to_vec()) {
+                error!("❌ 归档交易 {} 的原始数据失败: {}", &signature[..8], e);
+            }
+        }
+
+        // 更新预计算统计
+        if let Err(e) = db_manager.stats_storage().record_transaction(&signature_data) {
+            error!("❌ 更新交易 {} 的统计计数失败: {}", &signature[..8], e);
+        }
+
+        // 按涉及的地址保存质押操作
+        if !stake_events.is_empty() {
+            if let Err(e) = db_manager.stake_event_storage().record_events(transaction_update.slot, &stake_events) {
+                error!("❌ 存储交易 {} 的质押操作失败: {}", &signature[..8], e);
+            }
+        }
+
+        // 按所有者保存兑换记录
+        if !swaps.is_empty() {
+            if let Err(e) = db_manager.swap_storage().record_swaps(transaction_update.slot, &swaps) {
+                error!("❌ 存储交易 {} 的兑换记录失败: {}", &signature[..8], e);
+            }
+        }
+
+        // 按钱包保存 NFT 转账
+        if !nft_transfers.is_empty() {
+            if let Err(e) = db_manager.nft_transfer_storage().record_transfers(transaction_update.slot, &nft_transfers) {
+                error!("❌ 存储交易 {} 的 NFT 转账失败: {}", &signature[..8], e);
+            }
+        }
+
+        // 更新代币转账索引
+        if !signature_data.token_transfers.is_empty() {
+            if let Err(e) = db_manager.mint_transfer_storage().record_transfers(
+                &signature,
+                transaction_update.slot,
+                timestamp as u64,
+                &signature_data.token_transfers,
+            ) {
+                error!("❌ 更新交易 {} 的代币转账索引失败: {}", &signature[..8], e);
+            }
+            if let Err(e) = db_manager
+                .mint_stats_storage()
+                .record_transfers(timestamp as u64, &signature_data.token_transfers)
+            {
+                error!("❌ 更新交易 {} 的代币统计失败: {}", &signature[..8], e);
+            }
+        }
+
+        // 更新每日最大转账榜、地址对转账索引和金额分布
+        if !signature_data.sol_transfers.is_empty() || !signature_data.token_transfers.is_empty() {
+            if let Err(e) = db_manager.largest_transfer_storage().record_transfers(
+                &signature,
+                transaction_update.slot,
+                timestamp as u64,
+                &signature_data.sol_transfers,
+                &signature_data.token_transfers,
+            ) {
+                error!("❌ 更新交易 {} 的最大转账榜失败: {}", &signature[..8], e);
+            }
+            if let Err(e) = db_manager.pair_transfer_storage().record_transfers(
+                &signature,
+                transaction_update.slot,
+                timestamp as u64,
+                &signature_data.sol_transfers,
+                &signature_data.token_transfers,
+            ) {
+                error!("❌ 更新交易 {} 的地址对转账索引失败: {}", &signature[..8], e);
+            }
+            if let Err(e) = db_manager.amount_histogram_storage().record_transfers(
+                timestamp as u64,
+                &signature_data.sol_transfers,
+                &signature_data.token_transfers,
+            ) {
+                error!("❌ 更新交易 {} 的金额分布失败: {}", &signature[..8], e);
+            }
+        }
+
+        // 更新槽位交易索引
+        let slot_entry = SlotTransactionEntry {
+            signature: signature.clone(),
+            transaction_index: signature_data.transaction_index,
+            timestamp,
+            is_successful: signature_data.is_successful,
+            sol_transfer_count: signature_data.sol_transfers.len(),
+            token_transfer_count: signature_data.token_transfers.len(),
+        };
+        if let Err(e) = db_manager.slot_index_storage().add_transaction(transaction_update.slot, &slot_entry) {
+            error!("❌ 更新交易 {} 的槽位索引失败: {}", &signature[..8], e);
+        }
+
+        // 更新签名时间索引
+        if let Err(e) = db_manager.signature_time_index_storage().record(&signature, transaction_update.slot, timestamp) {
+            error!("❌ 更新交易 {} 的签名时间索引失败: {}", &signature[..8], e);
+        }
+
+        // 更新程序调用索引
+        let invocation = ProgramInvocationEntry {
+            signature: signature.clone(),
+            slot: transaction_update.slot,
+            timestamp,
+            is_successful: signature_data.is_successful,
+        };
+        if let Err(e) = db_manager
+            .program_index_storage()
+            .record_invocations(&signature_data.extracted_addresses.programs, &invocation)
+        {
+            error!("❌ 更新交易 {} 的程序调用索引失败: {}", &signature[..8], e);
+        }
+
+        if let Some(fee_info) = &fee_info {
+            if let Err(e) = db_manager.fee_stats_storage().add_fee(timestamp, fee_info.fee_lamports, fee_info.compute_units) {
+                error!("❌ 更新交易 {} 的手续费统计失败: {}", &signature[..8], e);
+            }
+        }
+
+        // 评估可疑模式规则（需在写入地址索引之前，历史记录不含当前交易）
+        if let Some(rules_engine) = db_manager.rules_engine() {
+            match rules_engine.evaluate(db_manager, &signature_data) {
+                Ok(alerts) => {
+                    for alert in &alerts {
+                        warn!("🚨 交易 {} 命中规则 {}: {}", &signature[..8], alert.rule.as_str(), alert.description);
+                    }
+                    if let Err(e) = db_manager.alert_storage().record_alerts(&alerts) {
+                        error!("❌ 保存交易 {} 的告警失败: {}", &signature[..8], e);
+                    }
+                    if let Err(e) = db_manager.webhook_storage().enqueue_alerts(&alerts) {
+                        error!("❌ 生成交易 {} 的告警通知失败: {}", &signature[..8], e);
+                    }
+                }
+                Err(e) => error!("❌ 评估交易 {} 的规则失败: {}", &signature[..8], e),
             }
-        }).collect();
+        }
+
+        // 同时存储到地址数据库
+        let parsed_sol_transfers = Self::address_sol_transfers(&signature, timestamp, &signature_data.sol_transfers);
 
         if let Err(e) = db_manager.address_storage().batch_process_transaction(
             &signature,
@@ -509,9 +1687,101 @@ impl SolanaGrpcClient {
             error!("❌ 存储交易 {} 到地址数据库失败: {}", &signature[..8], e);
             // 不返回错误，因为主要存储已成功
         } else {
-            info!("🏠 成功存储交易 {} 到地址数据库", &signature[..8]);
+            debug!("🏠 成功存储交易 {} 到地址数据库", &signature[..8]);
         }
+        db_manager.query_cache().invalidate_transaction(&signature_data);
 
-        Ok(())
+        // 更新交易对手索引
+        if let Err(e) = db_manager.counterparty_storage().record_transaction(
+            timestamp as u64,
+            &parsed_sol_transfers,
+            &parsed_token_transfers,
+        ) {
+            error!("❌ 更新交易 {} 的交易对手索引失败: {}", &signature[..8], e);
+        }
+
+        // 更新手续费代付关系
+        if let Err(e) = db_manager.fee_payer_storage().record_transaction(
+            timestamp as u64,
+            signature_data.fee_lamports,
+            &signature_data.extracted_addresses.signers,
+        ) {
+            error!("❌ 更新交易 {} 的手续费代付关系失败: {}", &signature[..8], e);
+        }
+
+        // 更新地址活跃度时间线
+        if let Err(e) = db_manager.activity_storage().record_transaction(
+            timestamp as u64,
+            &parsed_sol_transfers,
+            &parsed_token_transfers,
+        ) {
+            error!("❌ 更新交易 {} 的活跃度时间线失败: {}", &signature[..8], e);
+        }
+
+        // 更新每日活跃地址
+        let participants = parsed_sol_transfers
+            .iter()
+            .flat_map(|transfer| [transfer.from.as_str(), transfer.to.as_str()])
+            .chain(parsed_token_transfers.iter().flat_map(|transfer| [transfer.sender_wallet(), transfer.receiver_wallet()]));
+        if let Err(e) = db_manager.active_address_storage().record_addresses(timestamp, participants) {
+            error!("❌ 更新交易 {} 的活跃地址统计失败: {}", &signature[..8], e);
+        }
+
+        // 记录转账相关地址的执行后余额
+        if let Ok(snapshots) = parser.parse_balance_snapshots(transaction_update) {
+            let tracked: HashSet<&str> = parsed_sol_transfers
+                .iter()
+                .flat_map(|t| [t.from.as_str(), t.to.as_str()])
+                .chain(parsed_token_transfers.iter().flat_map(|t| [t.from.as_str(), t.to.as_str()]))
+                .collect();
+            let snapshots: Vec<_> = snapshots
+                .into_iter()
+                .filter(|snapshot| tracked.contains(snapshot.address.as_str()))
+                .collect();
+
+            if let Err(e) = db_manager.balance_storage().record_balances(
+                &signature,
+                transaction_update.slot,
+                timestamp as u64,
+                &snapshots,
+            ) {
+                error!("❌ 存储交易 {} 的余额快照失败: {}", &signature[..8], e);
+            }
+        }
+
+        // 记录交易涉及的 (所有者, mint) 代币余额
+        let token_snapshots = parser.parse_token_balance_snapshots(transaction_update);
+        if let Err(e) = db_manager.token_balance_storage().record_balances(
+            &signature,
+            transaction_update.slot,
+            timestamp as u64,
+            &token_snapshots,
+        ) {
+            error!("❌ 存储交易 {} 的代币余额快照失败: {}", &signature[..8], e);
+        }
+
+        Ok(false)
+    }
+
+    /// 转换为地址存储使用的 SOL 转账
+    fn address_sol_transfers(
+        signature: &str,
+        timestamp: i64,
+        sol_transfers: &[SolTransfer],
+    ) -> Vec<crate::transfer_parser::SolTransfer> {
+        sol_transfers.iter().map(|st| {
+            crate::transfer_parser::SolTransfer {
+                signature: signature.to_string(),
+                from: st.from.clone(),
+                to: st.to.clone(),
+                from_index: 0, // 这些字段在地址存储中不使用
+                to_index: 0,
+                amount: st.amount,
+                timestamp: timestamp as u32,
+                transfer_type: st.transfer_type.clone(),
+                is_wrapped: st.is_wrapped,
+                instruction_path: st.instruction_path.clone(),
+            }
+        }).collect()
     }
 } 
\ No newline at end of file
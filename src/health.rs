@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::config::HealthConfig;
+
+/// 摄取链路健康状况快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestHealth {
+    /// 本进程是否运行数据摄取（api-only 模式下为 false）
+    pub enabled: bool,
+    /// gRPC 订阅流是否已连接
+    pub connected: bool,
+    /// 最近一次收到 gRPC 消息的时间
+    pub last_message_at: Option<i64>,
+    /// 最近一次成功存储交易的时间
+    pub last_write_at: Option<i64>,
+    /// 槽位订阅中看到的最新槽位
+    pub latest_slot: u64,
+    /// 最近一次存储的交易所在槽位
+    pub last_stored_slot: u64,
+    /// 最近存储的交易落后最新槽位的数量
+    pub slot_lag: u64,
+    /// 槽位落后阈值
+    pub max_slot_lag: u64,
+    /// 是否停滞（超过阈值未收到任何 gRPC 消息）
+    pub stalled: bool,
+    /// 槽位落后是否超过阈值
+    pub lagging: bool,
+    /// 因只调用排除的程序而跳过的交易数
+    pub excluded_transactions: u64,
+    /// 数据库所在磁盘最近一次检查的可用空间（MB），未检查时为空
+    pub disk_available_mb: Option<u64>,
+    /// 磁盘可用空间是否低于阈值
+    pub disk_low: bool,
+    /// 数据库目录最近一次检查的大小（MB），未检查时为空
+    pub db_size_mb: Option<u64>,
+    /// 是否因磁盘用量保护暂停写入
+    pub ingestion_paused: bool,
+    /// 暂停期间丢弃的交易数
+    pub paused_transactions: u64,
+}
+
+#[derive(Debug)]
+struct HealthState {
+    started_at: i64,
+    connected: bool,
+    last_message_at: Option<i64>,
+    last_write_at: Option<i64>,
+    latest_slot: u64,
+    last_stored_slot: u64,
+    excluded_transactions: u64,
+    /// 上次报告的是否落后，用于只在状态变化时告警
+    lag_reported: bool,
+    /// 统计窗口内存储失败的时间
+    storage_errors: VecDeque<i64>,
+    /// 本轮存储失败是否已报告，失败次数回落到阈值以下后重新报告
+    storage_errors_reported: bool,
+    disk_available_mb: Option<u64>,
+    /// 上次报告的磁盘空间是否不足
+    disk_low_reported: bool,
+    db_size_mb: Option<u64>,
+    ingestion_paused: bool,
+    paused_transactions: u64,
+}
+
+/// 摄取健康监视器
+///
+/// gRPC 客户端记录连接状态、消息和写入，健康检查接口读取快照。
+/// 克隆后共享同一状态。启动后尚未收到消息时，从启动时间开始计算停滞时长。
+#[derive(Debug, Clone)]
+pub struct HealthMonitor {
+    config: HealthConfig,
+    ingest_enabled: bool,
+    clock: SharedClock,
+    state: Arc<Mutex<HealthState>>,
+}
+
+impl HealthMonitor {
+    /// 创建健康监视器，`ingest_enabled` 为 false 时不检查摄取链路
+    pub fn new(config: HealthConfig, ingest_enabled: bool) -> Self {
+        Self::with_clock(config, ingest_enabled, SystemClock::shared())
+    }
+
+    /// 使用指定时钟创建健康监视器
+    pub fn with_clock(config: HealthConfig, ingest_enabled: bool, clock: SharedClock) -> Self {
+        let state = HealthState {
+            started_at: clock.timestamp(),
+            connected: false,
+            last_message_at: None,
+            last_write_at: None,
+            latest_slot: 0,
+            last_stored_slot: 0,
+            excluded_transactions: 0,
+            lag_reported: false,
+            storage_errors: VecDeque::new(),
+            storage_errors_reported: false,
+            disk_available_mb: None,
+            disk_low_reported: false,
+            db_size_mb: None,
+            ingestion_paused: false,
+            paused_transactions: 0,
+        };
+        Self {
+            config,
+            ingest_enabled,
+            clock,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// 健康检查配置
+    pub fn config(&self) -> &HealthConfig {
+        &self.config
+    }
+
+    /// 记录订阅流连接或断开
+    pub fn set_connected(&self, connected: bool) {
+        self.state.lock().unwrap().connected = connected;
+    }
+
+    /// 记录收到一条 gRPC 消息
+    pub fn record_message(&self) {
+        self.state.lock().unwrap().last_message_at = Some(self.clock.timestamp());
+    }
+
+    /// 记录槽位订阅中的槽位
+    pub fn record_slot(&self, slot: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.latest_slot = state.latest_slot.max(slot);
+    }
+
+    /// 记录一次成功的交易写入
+    pub fn record_write(&self, slot: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.last_write_at = Some(self.clock.timestamp());
+        state.last_stored_slot = state.last_stored_slot.max(slot);
+    }
+
+    /// 记录一笔因只调用排除的程序而跳过的交易
+    pub fn record_excluded(&self) {
+        self.state.lock().unwrap().excluded_transactions += 1;
+    }
+
+    /// 记录一次存储失败，统计窗口内的失败次数达到阈值时返回该次数（每轮只返回一次）
+    pub fn record_storage_error(&self) -> Option<u32> {
+        let threshold = self.config.storage_error_threshold as usize;
+        if threshold == 0 {
+            return None;
+        }
+        let now = self.clock.timestamp();
+        let window_start = now - self.config.storage_error_window_secs as i64;
+        let mut state = self.state.lock().unwrap();
+        state.storage_errors.push_back(now);
+        while state.storage_errors.len() > threshold
+            || state.storage_errors.front().is_some_and(|at| *at <= window_start)
+        {
+            state.storage_errors.pop_front();
+        }
+        if state.storage_errors.len() < threshold {
+            state.storage_errors_reported = false;
+            return None;
+        }
+        if state.storage_errors_reported {
+            return None;
+        }
+        state.storage_errors_reported = true;
+        Some(threshold as u32)
+    }
+
+    /// 记录磁盘可用空间，是否低于阈值相对上次报告发生变化时返回当前状态
+    pub fn disk_space_change(&self, available_mb: u64) -> Option<bool> {
+        let low = self.config.min_free_disk_mb > 0 && available_mb < self.config.min_free_disk_mb;
+        let mut state = self.state.lock().unwrap();
+        state.disk_available_mb = Some(available_mb);
+        if low == state.disk_low_reported {
+            return None;
+        }
+        state.disk_low_reported = low;
+        Some(low)
+    }
+
+    /// 记录数据库目录大小
+    pub fn record_db_size(&self, db_size_mb: u64) {
+        self.state.lock().unwrap().db_size_mb = Some(db_size_mb);
+    }
+
+    /// 暂停或恢复写入
+    pub fn set_ingestion_paused(&self, paused: bool) {
+        self.state.lock().unwrap().ingestion_paused = paused;
+    }
+
+    /// 写入是否已暂停
+    pub fn ingestion_paused(&self) -> bool {
+        self.state.lock().unwrap().ingestion_paused
+    }
+
+    /// 记录一笔因暂停写入而丢弃的交易
+    pub fn record_paused(&self) {
+        self.state.lock().unwrap().paused_transactions += 1;
+    }
+
+    /// 槽位落后状态相对上次报告发生变化时返回当前快照，用于边沿触发的告警
+    pub fn lag_change(&self) -> Option<IngestHealth> {
+        let health = self.ingest();
+        let mut state = self.state.lock().unwrap();
+        if health.lagging == state.lag_reported {
+            return None;
+        }
+        state.lag_reported = health.lagging;
+        Some(health)
+    }
+
+    /// 当前摄取健康快照
+    pub fn ingest(&self) -> IngestHealth {
+        let state = self.state.lock().unwrap();
+        let slot_lag = if state.last_stored_slot > 0 {
+            state.latest_slot.saturating_sub(state.last_stored_slot)
+        } else {
+            0
+        };
+        let silent_since = state.last_message_at.unwrap_or(state.started_at);
+        let stalled = self.ingest_enabled
+            && self.clock.timestamp() - silent_since > self.config.stall_threshold_secs as i64;
+
+        IngestHealth {
+            enabled: self.ingest_enabled,
+            connected: state.connected,
+            last_message_at: state.last_message_at,
+            last_write_at: state.last_write_at,
+            latest_slot: state.latest_slot,
+            last_stored_slot: state.last_stored_slot,
+            slot_lag,
+            max_slot_lag: self.config.max_slot_lag,
+            stalled,
+            lagging: self.ingest_enabled && slot_lag > self.config.max_slot_lag,
+            excluded_transactions: state.excluded_transactions,
+            disk_available_mb: state.disk_available_mb,
+            disk_low: state.disk_low_reported,
+            db_size_mb: state.db_size_mb,
+            ingestion_paused: state.ingestion_paused,
+            paused_transactions: state.paused_transactions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_stall_and_lag_detection() {
+        let clock = MockClock::new(1_704_067_200);
+        let config = HealthConfig {
+            stall_threshold_secs: 60,
+            max_slot_lag: 10,
+            storage_error_threshold: 3,
+            storage_error_window_secs: 60,
+            min_free_disk_mb: 1024,
+            ..Default::default()
+        };
+        let monitor = HealthMonitor::with_clock(config.clone(), true, Arc::new(clock.clone()));
+
+        // 启动后一段时间内没有消息不算停滞
+        clock.advance(30);
+        assert!(!monitor.ingest().stalled);
+        clock.advance(31);
+        assert!(monitor.ingest().stalled);
+
+        monitor.set_connected(true);
+        monitor.record_message();
+        monitor.record_slot(120);
+        monitor.record_write(100);
+        let health = monitor.ingest();
+        assert!(health.connected && !health.stalled);
+        assert_eq!(health.slot_lag, 20);
+        assert!(health.lagging);
+
+        // 落后状态只在变化时报告
+        assert!(monitor.lag_change().unwrap().lagging);
+        assert!(monitor.lag_change().is_none());
+        monitor.record_write(118);
+        assert!(!monitor.lag_change().unwrap().lagging);
+        assert!(monitor.lag_change().is_none());
+
+        // 窗口内第 3 次存储失败时报告一次，窗口过去后重新计数
+        assert_eq!(monitor.record_storage_error(), None);
+        assert_eq!(monitor.record_storage_error(), None);
+        assert_eq!(monitor.record_storage_error(), Some(3));
+        assert_eq!(monitor.record_storage_error(), None);
+        clock.advance(61);
+        assert_eq!(monitor.record_storage_error(), None);
+
+        // 磁盘空间不足与恢复各报告一次
+        assert_eq!(monitor.disk_space_change(500), Some(true));
+        assert_eq!(monitor.disk_space_change(400), None);
+        assert!(monitor.ingest().disk_low);
+        assert_eq!(monitor.disk_space_change(2048), Some(false));
+
+        // 不运行摄取时不判断停滞
+        let api_only = HealthMonitor::with_clock(config, false, Arc::new(clock.clone()));
+        clock.advance(3_600);
+        assert!(!api_only.ingest().stalled);
+    }
+}
@@ -1,9 +1,45 @@
 pub mod config;
+pub mod config_reload;
+pub mod clock;
+#[cfg(feature = "ingest")]
 pub mod grpc_client;
+#[cfg(feature = "ingest")]
 pub mod transfer_parser;
+#[cfg(feature = "ingest")]
 pub mod address_extractor;
+#[cfg(feature = "ingest")]
+pub mod block_import;
+#[cfg(feature = "ingest")]
+pub mod webhook;
+#[cfg(feature = "ingest")]
+pub mod transaction_source;
+#[cfg(feature = "ingest")]
+pub mod mock_source;
+#[cfg(feature = "ingest")]
+pub mod stream_record;
+pub mod transfer_types;
+pub mod amount;
 pub mod database;
+#[cfg(feature = "api")]
 pub mod api;
+pub mod backup;
+pub mod diagnostics;
+pub mod degradation;
+pub mod health;
+pub mod disk_space;
+pub mod throughput;
+pub mod rules;
+pub mod price_oracle;
+pub mod graph_export;
+pub mod balance_verify;
+pub mod rpc_fetch;
+pub mod live_feed;
+pub mod slot_tracker;
+pub mod logging;
+#[cfg(all(feature = "api", feature = "ingest"))]
+pub mod orchestrator;
+#[cfg(test)]
+mod test_util;
 
 pub use config::*;
-pub use database::*; 
\ No newline at end of file
+pub use database::*;
@@ -0,0 +1,121 @@
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::database::SignatureTransactionData;
+
+/// 广播队列容量，订阅者落后超过该数量的事件会被跳过
+const FEED_CAPACITY: usize = 1024;
+
+/// 实时转账事件
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferEvent {
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: i64,
+    /// 转出方（代币转账为所有者钱包）
+    pub from: String,
+    /// 接收方（代币转账为所有者钱包）
+    pub to: String,
+    /// 金额（原始单位）
+    pub amount: u64,
+    /// 代币 mint，SOL 转账为空
+    pub mint: Option<String>,
+    pub decimals: u8,
+}
+
+impl TransferEvent {
+    /// 转账是否涉及该地址
+    pub fn involves(&self, address: &str) -> bool {
+        self.from == address || self.to == address
+    }
+}
+
+/// 新写入转账的进程内广播
+///
+/// 写入路径发布，SSE 等实时推送接口订阅；克隆后共享同一通道。
+/// 没有订阅者时发布直接丢弃，只读副本进程不写入，因此不会收到事件。
+#[derive(Debug, Clone)]
+pub struct TransferFeed {
+    sender: broadcast::Sender<Arc<TransferEvent>>,
+}
+
+impl Default for TransferFeed {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(FEED_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl TransferFeed {
+    /// 订阅之后发布的转账
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<TransferEvent>> {
+        self.sender.subscribe()
+    }
+
+    /// 发布成功交易中的转账，返回发布的数量
+    pub fn publish(&self, data: &SignatureTransactionData) -> usize {
+        if !data.is_successful || self.sender.receiver_count() == 0 {
+            return 0;
+        }
+
+        let sol = data.sol_transfers.iter().map(|transfer| TransferEvent {
+            signature: data.signature.clone(),
+            slot: data.slot,
+            timestamp: data.timestamp,
+            from: transfer.from.clone(),
+            to: transfer.to.clone(),
+            amount: transfer.amount,
+            mint: None,
+            decimals: 9,
+        });
+        let token = data.token_transfers.iter().map(|transfer| TransferEvent {
+            signature: data.signature.clone(),
+            slot: data.slot,
+            timestamp: data.timestamp,
+            from: transfer.sender_wallet().to_string(),
+            to: transfer.receiver_wallet().to_string(),
+            amount: transfer.amount,
+            mint: Some(transfer.mint.clone()),
+            decimals: transfer.decimals,
+        });
+
+        let mut published = 0;
+        for event in sol.chain(token) {
+            if self.sender.send(Arc::new(event)).is_ok() {
+                published += 1;
+            }
+        }
+        published
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::SolTransfer;
+
+    #[test]
+    fn test_publish_reaches_subscribers() {
+        let feed = TransferFeed::default();
+        let mut data = SignatureTransactionData::new("sig".to_string(), 1_704_067_200, 7, true);
+        data.add_sol_transfer(SolTransfer {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: 42,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        });
+
+        // 没有订阅者时不发布
+        assert_eq!(feed.publish(&data), 0);
+
+        let mut receiver = feed.clone().subscribe();
+        assert_eq!(feed.publish(&data), 1);
+        let event = receiver.try_recv().unwrap();
+        assert!(event.involves("bob"));
+        assert!(!event.involves("carol"));
+        assert_eq!((event.slot, event.amount, event.mint.as_deref()), (7, 42, None));
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::{Context, Result};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+#[cfg(feature = "otel")]
+use crate::config::OtlpConfig;
+use crate::config::{LogFormat, LogRotation, LoggingConfig};
+
+/// 日志系统的后台资源，调用方需要持有它直到程序退出
+///
+/// 释放时刷新文件日志缓冲，并把尚未导出的 span 发送到 OTLP 接收端，提前释放会丢失日志。
+pub struct LogGuard {
+    _file: Option<WorkerGuard>,
+    #[cfg(feature = "otel")]
+    tracer_provider: Option<opentelemetry_sdk::trace::TracerProvider>,
+}
+
+#[cfg(feature = "otel")]
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.tracer_provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("导出剩余链路追踪数据失败: {}", e);
+            }
+        }
+    }
+}
+
+/// 根据 `[logging]` 配置初始化全局日志
+///
+/// 配置 `[logging.otlp]` 时同时通过 OTLP 导出 span，需要以 `otel` 特性编译。
+pub fn init(config: &LoggingConfig) -> Result<LogGuard> {
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(filter) => filter,
+        Err(_) => EnvFilter::try_new(filter_directives(config))
+            .with_context(|| format!("无效的日志级别配置: {}", filter_directives(config)))?,
+    };
+
+    let mut layers: Vec<Box<dyn Layer<Registry> + Send + Sync>> = vec![format_layer(config.format, std::io::stdout, true)];
+
+    let file_guard = match &config.file {
+        Some(file) => {
+            let rotation = match file.rotation {
+                LogRotation::Minutely => Rotation::MINUTELY,
+                LogRotation::Hourly => Rotation::HOURLY,
+                LogRotation::Daily => Rotation::DAILY,
+                LogRotation::Never => Rotation::NEVER,
+            };
+            let appender = RollingFileAppender::new(rotation, &file.directory, &file.prefix);
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            layers.push(format_layer(config.format, writer, false));
+            Some(guard)
+        }
+        None => None,
+    };
+
+    #[cfg(feature = "otel")]
+    let tracer_provider = match &config.otlp {
+        Some(otlp) => {
+            let (layer, provider) = otlp_layer(otlp)?;
+            layers.push(layer);
+            Some(provider)
+        }
+        None => None,
+    };
+    #[cfg(not(feature = "otel"))]
+    if config.otlp.is_some() {
+        return Err(anyhow::anyhow!("未启用 otel 特性，无法导出链路追踪（请使用 --features otel 编译或删除 [logging.otlp]）"));
+    }
+
+    tracing_subscriber::registry()
+        .with(layers)
+        .with(filter)
+        .try_init()
+        .context("日志系统初始化失败")?;
+
+    Ok(LogGuard {
+        _file: file_guard,
+        #[cfg(feature = "otel")]
+        tracer_provider,
+    })
+}
+
+/// 创建 OTLP 导出层，span 在后台批量发送
+#[cfg(feature = "otel")]
+fn otlp_layer(
+    config: &OtlpConfig,
+) -> Result<(Box<dyn Layer<Registry> + Send + Sync>, opentelemetry_sdk::trace::TracerProvider)> {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(config.endpoint.clone())
+        .build()
+        .with_context(|| format!("创建 OTLP 导出器失败: {}", config.endpoint))?;
+    let provider = opentelemetry_sdk::trace::TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_resource(opentelemetry_sdk::Resource::new([opentelemetry::KeyValue::new(
+            "service.name",
+            config.service_name.clone(),
+        )]))
+        .build();
+    let tracer = provider.tracer(config.service_name.clone());
+    Ok((tracing_opentelemetry::layer().with_tracer(tracer).boxed(), provider))
+}
+
+/// 将默认级别与按模块级别合并为 EnvFilter 指令，如 `info,tower_http=debug`
+fn filter_directives(config: &LoggingConfig) -> String {
+    std::iter::once(config.level.clone())
+        .chain(config.modules.iter().map(|(module, level)| format!("{}={}", module, level)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn format_layer<W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'writer> fmt::MakeWriter<'writer> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Pretty => fmt::layer().with_ansi(ansi).with_writer(writer).boxed(),
+        LogFormat::Json => fmt::layer().json().with_writer(writer).boxed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_directives_include_module_levels() {
+        let mut config = LoggingConfig::default();
+        assert_eq!(filter_directives(&config), "info");
+
+        config.level = "warn".to_string();
+        config.modules.insert("tower_http".to_string(), "debug".to_string());
+        config.modules.insert("solana_transfer_ledger::grpc_client".to_string(), "error".to_string());
+        assert_eq!(
+            filter_directives(&config),
+            "warn,solana_transfer_ledger::grpc_client=error,tower_http=debug"
+        );
+        assert!(EnvFilter::try_new(filter_directives(&config)).is_ok());
+    }
+
+    #[cfg(not(feature = "otel"))]
+    #[test]
+    fn test_otlp_requires_feature() {
+        let config = LoggingConfig {
+            otlp: Some(crate::config::OtlpConfig {
+                endpoint: "http://127.0.0.1:4317".to_string(),
+                service_name: "ledger".to_string(),
+            }),
+            ..LoggingConfig::default()
+        };
+        assert!(init(&config).is_err());
+    }
+}
@@ -1,103 +1,71 @@
 mod config;
+mod config_reload;
+mod clock;
 mod grpc_client;
+mod transaction_source;
+mod stream_record;
 mod transfer_parser;
+mod transfer_types;
+mod amount;
 mod address_extractor;
+mod block_import;
+mod webhook;
 mod database;
 mod api;
+mod backup;
+mod cli;
+mod diagnostics;
+mod degradation;
+mod health;
+mod disk_space;
+mod throughput;
+mod rules;
+mod price_oracle;
+mod graph_export;
+mod balance_verify;
+mod rpc_fetch;
+mod live_feed;
+mod slot_tracker;
+mod logging;
+mod orchestrator;
+#[cfg(test)]
+mod test_util;
 
 use anyhow::Result;
+use clap::Parser;
 use tracing::{error, info};
-use tracing_subscriber;
-use tokio::signal;
 
+use cli::{Cli, Command};
 use config::Config;
-use grpc_client::SolanaGrpcClient;
-use database::DatabaseManager;
-use api::ApiServer;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 初始化日志 - 设置为INFO级别避免过多调试信息
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .init();
-
-    info!("🌟 欢迎使用 Solana 地址账本 gRPC 客户端与 API 服务器！");
+    let cli = Cli::parse();
 
     // 加载配置
-    let config = match Config::load() {
-        Ok(config) => {
-            info!("✅ 成功加载配置文件");
-            config
-        }
-        Err(e) => {
-            error!("❌ 加载配置文件失败: {}", e);
-            error!("请确保项目根目录下存在 config.toml 文件");
-            return Err(e);
-        }
-    };
-
-    // 创建数据库管理器
-    let db_manager = match DatabaseManager::new(
-        &config.database.db_path,
-        config.database.signature_key_prefix.clone(),
-        config.database.address_key_prefix.clone(),
-        config.database.max_address_records,
-    ) {
-        Ok(db_manager) => {
-            info!("✅ 数据库管理器初始化成功");
-            db_manager
-        }
+    let config = match Config::load_from(&cli.config) {
+        Ok(config) => config,
         Err(e) => {
-            error!("❌ 数据库管理器初始化失败: {}", e);
+            // 配置加载失败时使用默认日志输出错误信息
+            tracing_subscriber::fmt()
+                .with_max_level(tracing::Level::INFO)
+                .init();
+            error!("❌ 加载配置文件失败: {:#}", e);
+            error!("请确保配置文件 {} 存在，或通过 --config 指定路径", cli.config.display());
             return Err(e);
         }
     };
 
-    // 创建 gRPC 客户端（带数据库管理器）
-    let grpc_client = SolanaGrpcClient::with_database(config.grpc, config.monitor, db_manager.clone());
-
-    // 创建 API 服务器
-    let api_server = ApiServer::new(db_manager.clone(), config.api);
+    // 根据 [logging] 配置初始化日志，guard 需持有到程序退出以保证文件日志完整写入
+    let _log_guard = logging::init(&config.logging)?;
 
-    info!("🚀 正在启动服务...");
-    info!("📊 gRPC 客户端将监听 Solana 数据并存储到数据库");
-    info!("🌐 API 服务器将提供数据查询接口");
-
-    // 使用 tokio::spawn 来并行运行任务，避免阻塞
-    let grpc_handle = tokio::spawn(async move {
-        info!("🔄 启动 Solana gRPC 数据监听...");
-        if let Err(e) = grpc_client.start_monitoring().await {
-            error!("❌ gRPC 客户端运行失败: {}", e);
-        }
-    });
-
-    let api_handle = tokio::spawn(async move {
-        info!("🔌 启动 API 服务器...");
-        if let Err(e) = api_server.start().await {
-            error!("❌ API 服务器运行失败: {}", e);
-        }
-    });
-
-    // 等待 Ctrl+C 信号
-    let ctrl_c = tokio::spawn(async {
-        signal::ctrl_c().await.expect("无法监听 Ctrl+C 信号");
-        info!("📟 收到 Ctrl+C 信号，正在关闭服务...");
-    });
+    info!("🌟 欢迎使用 Solana 地址账本 gRPC 客户端与 API 服务器！");
+    info!("✅ 成功加载配置文件: {}", cli.config.display());
 
-    // 等待任何一个任务完成或收到关闭信号
-    tokio::select! {
-        _ = grpc_handle => {
-            info!("gRPC 客户端已停止");
-        }
-        _ = api_handle => {
-            info!("API 服务器已停止");
-        }
-        _ = ctrl_c => {
-            info!("收到关闭信号");
-        }
+    let command = cli.command.unwrap_or(Command::Run { mode: None, record: None, replay: None });
+    let result = cli::execute(config, cli.config, command).await;
+    if let Err(e) = &result {
+        error!("❌ {:#}", e);
     }
-
-    info!("🛑 所有服务已停止");
-    Ok(())
+    result
 }
@@ -0,0 +1,310 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use futures::Stream;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::MissedTickBehavior;
+use tonic::Status;
+use yellowstone_grpc_proto::prelude::{
+    subscribe_update::UpdateOneof, CompiledInstruction, Message, MessageHeader, SlotStatus, SubscribeRequest,
+    SubscribeUpdate, SubscribeUpdateSlot, SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo, Transaction,
+    TransactionError, TransactionStatusMeta,
+};
+use yellowstone_grpc_proto::prost_types::Timestamp;
+
+use crate::transaction_source::{TransactionSource, UpdateStream};
+
+/// 模拟交易的固定手续费（lamports）
+const MOCK_FEE: u64 = 5_000;
+
+/// 槽位从确认到最终确定的深度
+const FINALITY_DEPTH: u64 = 32;
+
+/// System Program 转账指令编号
+const SYSTEM_TRANSFER_INSTRUCTION: u32 = 2;
+
+/// 模拟交易源配置
+#[derive(Debug, Clone)]
+pub struct MockSourceConfig {
+    /// 随机种子，相同种子生成相同的交易序列
+    pub seed: u64,
+    /// 每秒生成的交易数，0 表示不限速
+    pub tps: u32,
+    /// 参与转账的钱包数
+    pub wallets: usize,
+    /// 每个槽位的交易数
+    pub transactions_per_slot: u64,
+    /// 失败交易比例（0-1）
+    pub failure_rate: f64,
+    /// 起始槽位
+    pub start_slot: u64,
+    /// 生成的交易总数，为空时不结束
+    pub limit: Option<u64>,
+}
+
+impl Default for MockSourceConfig {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            tps: 1_000,
+            wallets: 1_000,
+            transactions_per_slot: 400,
+            failure_rate: 0.0,
+            start_slot: 300_000_000,
+            limit: None,
+        }
+    }
+}
+
+/// 合成交易源
+///
+/// 按固定种子生成确定性的 SOL 转账交易和槽位状态更新，消息格式与 Yellowstone 订阅流一致，
+/// 用于在没有 gRPC 端点时压测和集成测试摄取与存储。
+#[derive(Debug, Clone)]
+pub struct MockTransactionSource {
+    config: MockSourceConfig,
+    rng: XorShift,
+    wallets: Vec<[u8; 32]>,
+    slot: u64,
+    /// 当前槽位已生成的交易数
+    slot_transactions: u64,
+    generated: u64,
+    /// 待发送的槽位更新
+    pending: VecDeque<SubscribeUpdate>,
+}
+
+impl MockTransactionSource {
+    pub fn new(config: MockSourceConfig) -> Self {
+        let mut rng = XorShift::new(config.seed);
+        let wallets = (0..config.wallets.max(2)).map(|_| rng.next_bytes()).collect();
+        let mut source = Self {
+            rng,
+            wallets,
+            slot: config.start_slot,
+            slot_transactions: 0,
+            generated: 0,
+            pending: VecDeque::new(),
+            config,
+        };
+        source.pending.push_back(slot_update(source.slot, SlotStatus::SlotProcessed));
+        source
+    }
+
+    /// 已生成的交易数
+    pub fn generated(&self) -> u64 {
+        self.generated
+    }
+
+    /// 下一条更新，达到交易总数后为空；不设置 `created_at`，序列只由种子决定
+    pub fn next_update(&mut self) -> Option<SubscribeUpdate> {
+        if let Some(update) = self.pending.pop_front() {
+            return Some(update);
+        }
+        if self.config.limit.is_some_and(|limit| self.generated >= limit) {
+            return None;
+        }
+        if self.slot_transactions >= self.config.transactions_per_slot.max(1) {
+            self.advance_slot();
+            return self.pending.pop_front();
+        }
+        let transaction = self.next_transaction();
+        Some(SubscribeUpdate {
+            filters: vec!["txn".to_string()],
+            created_at: None,
+            update_oneof: Some(UpdateOneof::Transaction(transaction)),
+        })
+    }
+
+    /// 转换为按配置限速的更新流，`created_at` 为发送时间
+    pub fn into_stream(self) -> impl Stream<Item = Result<SubscribeUpdate, Status>> {
+        let interval = (self.config.tps > 0).then(|| {
+            let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / self.config.tps as f64));
+            // 落后时立即补发，保持平均速率
+            interval.set_missed_tick_behavior(MissedTickBehavior::Burst);
+            interval
+        });
+        futures::stream::unfold((self, interval), |(mut source, mut interval)| async move {
+            if source.pending.is_empty() {
+                if let Some(interval) = interval.as_mut() {
+                    interval.tick().await;
+                }
+            }
+            let mut update = source.next_update()?;
+            update.created_at = Some(now_timestamp());
+            Some((Ok(update), (source, interval)))
+        })
+    }
+
+    /// 进入下一个槽位，上一个槽位确认，`FINALITY_DEPTH` 之前的槽位最终确定
+    fn advance_slot(&mut self) {
+        self.slot += 1;
+        self.slot_transactions = 0;
+        let slot = self.slot;
+        self.pending.push_back(slot_update(slot, SlotStatus::SlotProcessed));
+        self.pending.push_back(slot_update(slot - 1, SlotStatus::SlotConfirmed));
+        if slot >= self.config.start_slot + FINALITY_DEPTH {
+            self.pending.push_back(slot_update(slot - FINALITY_DEPTH, SlotStatus::SlotFinalized));
+        }
+    }
+
+    fn next_transaction(&mut self) -> SubscribeUpdateTransaction {
+        let index = self.slot_transactions;
+        self.slot_transactions += 1;
+        self.generated += 1;
+
+        let from_index = self.rng.below(self.wallets.len() as u64) as usize;
+        let to_index = (from_index + 1 + self.rng.below(self.wallets.len() as u64 - 1) as usize) % self.wallets.len();
+        let (from, to) = (self.wallets[from_index], self.wallets[to_index]);
+        let amount = 1_000 + self.rng.below(10_000_000_000);
+        let failed = self.rng.chance(self.config.failure_rate);
+        let from_balance = amount + MOCK_FEE + self.rng.below(100_000_000_000);
+        let to_balance = self.rng.below(100_000_000_000);
+
+        let signature: Vec<u8> = [self.rng.next_bytes(), self.rng.next_bytes()].concat();
+        let mut data = SYSTEM_TRANSFER_INSTRUCTION.to_le_bytes().to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let (post_from, post_to) = if failed {
+            (from_balance - MOCK_FEE, to_balance)
+        } else {
+            (from_balance - MOCK_FEE - amount, to_balance + amount)
+        };
+
+        SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                signature: signature.clone(),
+                is_vote: false,
+                transaction: Some(Transaction {
+                    signatures: vec![signature],
+                    message: Some(Message {
+                        header: Some(MessageHeader {
+                            num_required_signatures: 1,
+                            num_readonly_signed_accounts: 0,
+                            num_readonly_unsigned_accounts: 1,
+                        }),
+                        // System Program 地址为全零
+                        account_keys: vec![from.to_vec(), to.to_vec(), vec![0u8; 32]],
+                        recent_blockhash: self.rng.next_bytes().to_vec(),
+                        instructions: vec![CompiledInstruction { program_id_index: 2, accounts: vec![0, 1], data }],
+                        versioned: false,
+                        address_table_lookups: vec![],
+                    }),
+                }),
+                meta: Some(TransactionStatusMeta {
+                    err: failed.then(|| TransactionError { err: br#"{"InstructionError":[0,"Custom"]}"#.to_vec() }),
+                    fee: MOCK_FEE,
+                    pre_balances: vec![from_balance, to_balance, 1],
+                    post_balances: vec![post_from, post_to, 1],
+                    ..Default::default()
+                }),
+                index,
+            }),
+            slot: self.slot,
+        }
+    }
+}
+
+impl TransactionSource for MockTransactionSource {
+    fn describe(&self) -> String {
+        format!("mock://seed={}?tps={}", self.config.seed, self.config.tps)
+    }
+
+    /// 忽略过滤条件；交易序列只生成一次，流结束后再次订阅得到空流
+    fn subscribe(&mut self, _request: SubscribeRequest) -> BoxFuture<'_, Result<UpdateStream>> {
+        let source = self.clone();
+        self.config.limit = Some(0);
+        self.pending.clear();
+        Box::pin(async move { Ok(Box::pin(source.into_stream()) as UpdateStream) })
+    }
+}
+
+fn slot_update(slot: u64, status: SlotStatus) -> SubscribeUpdate {
+    SubscribeUpdate {
+        filters: vec!["slot".to_string()],
+        created_at: None,
+        update_oneof: Some(UpdateOneof::Slot(SubscribeUpdateSlot {
+            slot,
+            parent: slot.checked_sub(1),
+            status: status as i32,
+            dead_error: None,
+        })),
+    }
+}
+
+fn now_timestamp() -> Timestamp {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    Timestamp {
+        seconds: now.as_secs() as i64,
+        nanos: now.subsec_nanos() as i32,
+    }
+}
+
+/// xorshift 伪随机数生成器，只用于生成可复现的测试数据
+#[derive(Debug, Clone)]
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound.max(1)
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / ((1u64 << 53) as f64) < probability
+    }
+
+    fn next_bytes(&mut self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for chunk in bytes.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transfer_parser::TransferParser;
+
+    fn collect(seed: u64) -> Vec<SubscribeUpdate> {
+        let config = MockSourceConfig { seed, tps: 0, limit: Some(10), transactions_per_slot: 4, ..Default::default() };
+        let mut source = MockTransactionSource::new(config);
+        std::iter::from_fn(|| source.next_update()).collect()
+    }
+
+    #[test]
+    fn test_mock_source_is_deterministic_and_parseable() {
+        let updates = collect(7);
+        assert_eq!(updates, collect(7));
+        assert_ne!(updates, collect(8));
+
+        let transactions: Vec<_> = updates
+            .iter()
+            .filter_map(|update| match &update.update_oneof {
+                Some(UpdateOneof::Transaction(transaction)) => Some(transaction),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(transactions.len(), 10);
+        assert_eq!(transactions.last().unwrap().slot, 300_000_002);
+
+        let parser = TransferParser::default();
+        let transfers = parser.parse_sol_transfers(transactions[0], 1_704_067_200).unwrap();
+        assert_eq!(transfers.len(), 1);
+        let meta = transactions[0].transaction.as_ref().unwrap().meta.as_ref().unwrap();
+        assert_eq!(meta.pre_balances[1] + transfers[0].amount, meta.post_balances[1]);
+    }
+}
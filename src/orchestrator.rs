@@ -0,0 +1,357 @@
+//! 服务编排
+//!
+//! 根据运行模式组合 gRPC 数据摄取、API 服务、数据库维护和只读副本追赶等后台任务，
+//! 任一核心任务退出或收到 Ctrl+C 时结束运行。
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::signal;
+use tokio::task::JoinSet;
+use tracing::{error, info};
+
+use crate::api::ApiServer;
+use crate::config::{Config, RunMode, StorageBackend};
+use crate::config_reload::ConfigReloader;
+use crate::database::{run_migrations, run_tenant_migrations, DatabaseManager, MaintenanceScheduler};
+use crate::degradation::DegradationController;
+use crate::disk_space::DiskSpaceWatcher;
+use crate::grpc_client::SolanaGrpcClient;
+use crate::health::HealthMonitor;
+use crate::price_oracle::{provider_from_config, PriceOracle};
+use crate::rpc_fetch::RpcTransactionFetcher;
+use crate::stream_record::ReplaySource;
+use crate::throughput::ThroughputMeter;
+use crate::transfer_parser::TransferParser;
+use crate::webhook::WebhookNotifier;
+
+/// 某个运行模式下需要启动的组件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServicePlan {
+    /// gRPC 数据摄取
+    pub ingest: bool,
+    /// 查询 API
+    pub api: bool,
+    /// 数据库后台维护（需要写权限）
+    pub maintenance: bool,
+    /// 以只读副本方式打开数据库并定期追赶主库
+    pub replica: bool,
+}
+
+impl ServicePlan {
+    /// 根据运行模式确定组件
+    pub fn for_mode(mode: RunMode) -> Self {
+        match mode {
+            RunMode::All => Self { ingest: true, api: true, maintenance: true, replica: false },
+            RunMode::ApiOnly => Self { ingest: false, api: true, maintenance: false, replica: true },
+            RunMode::IngestOnly => Self { ingest: true, api: false, maintenance: true, replica: false },
+        }
+    }
+}
+
+/// 服务编排器
+pub struct ServiceOrchestrator {
+    config: Config,
+    mode: RunMode,
+    parser: TransferParser,
+    config_path: Option<PathBuf>,
+    /// 订阅消息录制文件
+    record: Option<PathBuf>,
+    /// 回放的录制文件，设置后不连接 gRPC 端点
+    replay: Option<PathBuf>,
+}
+
+impl ServiceOrchestrator {
+    /// 创建服务编排器，`parser` 会注入 gRPC 客户端
+    pub fn new(config: Config, mode: RunMode, parser: TransferParser) -> Self {
+        Self { config, mode, parser, config_path: None, record: None, replay: None }
+    }
+
+    /// 配置文件路径，设置后支持热更新监控过滤条件和解析参数
+    pub fn with_config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(path.into());
+        self
+    }
+
+    /// 把收到的订阅消息录制到文件
+    pub fn with_record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record = Some(path.into());
+        self
+    }
+
+    /// 回放录制文件代替连接 gRPC 端点
+    pub fn with_replay(mut self, path: impl Into<PathBuf>) -> Self {
+        self.replay = Some(path.into());
+        self
+    }
+
+    /// 启动当前模式下的所有组件，直到任一核心组件退出或收到 Ctrl+C
+    pub async fn run(self) -> Result<()> {
+        let plan = ServicePlan::for_mode(self.mode);
+        let config = self.config;
+        info!("🧭 运行模式: {:?} ({:?})", self.mode, plan);
+
+        // 创建数据库管理器
+        let opened = if plan.replica {
+            DatabaseManager::open_replica(&config)
+        } else {
+            DatabaseManager::from_config(&config)
+        };
+        let db_manager = match opened {
+            Ok(db_manager) => {
+                info!("✅ 数据库管理器初始化成功");
+                db_manager
+            }
+            Err(e) => {
+                error!("❌ 数据库管理器初始化失败: {}", e);
+                return Err(e);
+            }
+        };
+
+        // 旧数据库首次启动时升级数据格式、回填预计算统计
+        if !plan.replica {
+            // 迁移失败时停止启动，避免新代码读写未升级的数据
+            match db_manager.run_blocking(|db| run_migrations(db, false)).await {
+                Ok(report) if !report.applied.is_empty() => {
+                    info!("🧬 数据格式已从 v{} 升级到 v{}", report.from_version, report.to_version);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!("❌ 数据迁移失败: {}", e);
+                    return Err(e);
+                }
+            }
+            // API 通过 for_tenant 打开的其他租户数据也需要升级
+            let tenants: Vec<String> = config.api.tenants.api_keys.values().cloned().collect();
+            match db_manager
+                .run_blocking(move |db| run_tenant_migrations(db, tenants.iter().map(String::as_str), false))
+                .await
+            {
+                Ok(reports) => {
+                    for (tenant, report) in reports.iter().filter(|(_, report)| !report.applied.is_empty()) {
+                        info!("🧬 租户 {} 的数据格式已从 v{} 升级到 v{}", tenant, report.from_version, report.to_version);
+                    }
+                }
+                Err(e) => {
+                    error!("❌ 租户数据迁移失败: {}", e);
+                    return Err(e);
+                }
+            }
+
+            if let Err(e) = db_manager.run_blocking(|db| db.ensure_statistics()).await {
+                error!("❌ 回填统计计数失败: {}", e);
+            }
+            if let Err(e) = db_manager.run_blocking(|db| db.ensure_signature_time_index()).await {
+                error!("❌ 回填签名时间索引失败: {}", e);
+            }
+
+            // 过滤器只记录本进程的写入：副本和可能多进程写入的 PostgreSQL 后端不启用
+            if config.database.backend == StorageBackend::Rocksdb {
+                let address_capacity = config.database.address_filter_capacity;
+                if address_capacity > 0 {
+                    if let Err(e) = db_manager.run_blocking(move |db| db.build_address_filter(address_capacity)).await {
+                        error!("❌ 构建地址布隆过滤器失败: {}", e);
+                    }
+                }
+                let signature_capacity = config.database.signature_filter_capacity;
+                if signature_capacity > 0 {
+                    if let Err(e) = db_manager.run_blocking(move |db| db.build_signature_filter(signature_capacity)).await {
+                        error!("❌ 构建签名布隆过滤器失败: {}", e);
+                    }
+                }
+            }
+
+            // 导入已知实体标签
+            if let Some(seed_file) = config.labels.seed_file.clone() {
+                if let Err(e) = db_manager.run_blocking(move |db| db.label_storage().seed_from_file(&seed_file)).await {
+                    error!("❌ 导入地址标签失败: {}", e);
+                }
+            }
+        }
+
+        // 过载降级控制器，由 gRPC 客户端更新、API 状态接口读取
+        let degradation = DegradationController::new(config.degradation.clone());
+
+        // 健康监视器，由 gRPC 客户端更新、健康检查接口读取
+        let health = HealthMonitor::new(config.health.clone(), plan.ingest);
+
+        // 吞吐量统计，由 gRPC 客户端更新、吞吐量接口读取
+        let throughput = ThroughputMeter::default();
+
+        // 核心任务：任一退出即停止服务
+        let mut core_tasks: JoinSet<&'static str> = JoinSet::new();
+        // 辅助任务：随服务结束一起取消
+        let mut background_tasks: JoinSet<()> = JoinSet::new();
+
+        // 配置热更新，由 gRPC 客户端订阅、管理接口触发
+        let reloader = self.config_path.map(|path| ConfigReloader::new(path, &config));
+        if let Some(reloader) = &reloader {
+            let interval = config.service.config_reload_interval_secs;
+            if interval > 0 {
+                background_tasks.spawn(reloader.clone().watch(Duration::from_secs(interval)));
+            }
+        }
+
+        // 未指定标签提供者时使用数据库中的地址标签
+        let mut parser = self.parser;
+        if !parser.has_label_provider() {
+            parser = parser.with_label_provider(Arc::new(db_manager.label_storage().clone()));
+        }
+
+        if plan.ingest {
+            // 启用价格估值时由后台任务刷新价格，摄取路径只读缓存
+            let db_manager = if config.prices.enabled {
+                let oracle = PriceOracle::new(provider_from_config(&config.prices)?, &config.prices);
+                background_tasks.spawn(oracle.clone().run());
+                db_manager.clone().with_price_oracle(oracle)
+            } else {
+                db_manager.clone()
+            };
+
+            // 存储失败的交易进入死信队列，由后台任务重试
+            background_tasks.spawn(SolanaGrpcClient::retry_dead_letters(db_manager.clone(), parser.clone()));
+            // 投递 Webhook 通知
+            background_tasks.spawn(WebhookNotifier::new(db_manager.clone(), config.webhooks.clone())?.run());
+            // 检查数据库所在磁盘的可用空间，启用磁盘用量保护时同时执行保护动作
+            if config.health.min_free_disk_mb > 0 || config.database.disk_guard.enabled {
+                background_tasks.spawn(
+                    DiskSpaceWatcher::new(
+                        db_manager.clone(),
+                        health.clone(),
+                        config.webhooks.lifecycle.clone(),
+                        &config.database.db_path,
+                        config.health.min_free_disk_mb,
+                        config.database.disk_guard.clone(),
+                        Duration::from_secs(config.health.disk_check_interval_secs.max(1)),
+                    )
+                    .run(),
+                );
+            }
+
+            let mut grpc_client = SolanaGrpcClient::with_database(
+                config.grpc.clone(),
+                config.monitor.clone(),
+                db_manager.clone(),
+            )
+            .with_parser(parser.clone())
+            .with_degradation(degradation.clone())
+            .with_health(health.clone())
+            .with_throughput(throughput.clone())
+            .with_account_tracking(config.accounts.clone())
+            .with_lifecycle_webhooks(config.webhooks.lifecycle.clone());
+            if let Some(reloader) = &reloader {
+                grpc_client = grpc_client.with_settings(reloader.subscribe());
+            }
+            if let Some(path) = self.record {
+                grpc_client = grpc_client.with_recording(path);
+            }
+
+            if let Some(path) = self.replay {
+                grpc_client = grpc_client.with_source(ReplaySource::new(path));
+                let keep_running = plan.api;
+                core_tasks.spawn(async move {
+                    match grpc_client.ingest_once().await {
+                        Ok(count) => info!("⏪ 回放完成: {} 笔交易", count),
+                        Err(e) => error!("❌ 回放录制文件失败: {}", e),
+                    }
+                    // 同时提供 API 时保持运行，便于查询回放结果
+                    if keep_running {
+                        std::future::pending::<()>().await;
+                    }
+                    "数据回放"
+                });
+            } else {
+                info!("📊 gRPC 客户端将监听 Solana 数据并存储到数据库");
+                core_tasks.spawn(async move {
+                    info!("🔄 启动 Solana gRPC 数据监听...");
+                    if let Err(e) = grpc_client.start_monitoring().await {
+                        error!("❌ gRPC 客户端运行失败: {}", e);
+                    }
+                    "gRPC 客户端"
+                });
+            }
+        }
+
+        if plan.api {
+            let mut api_server = ApiServer::new(db_manager.clone(), config.api.clone())
+                .with_degradation(degradation)
+                .with_health(health)
+                .with_throughput(throughput);
+            if let Some(reloader) = reloader {
+                api_server = api_server.with_config_reloader(reloader);
+            }
+            // 配置 RPC 端点时支持按签名补查本地没有的交易
+            if let Some(fetcher) = RpcTransactionFetcher::from_config(&config.rpc, parser)? {
+                info!("🔎 启用 RPC 交易补查: {}", config.rpc.endpoint.as_deref().unwrap_or_default());
+                api_server = api_server.with_transaction_fetcher(Arc::new(fetcher));
+            }
+
+            info!("🌐 API 服务器将提供数据查询接口");
+            core_tasks.spawn(async move {
+                info!("🔌 启动 API 服务器...");
+                if let Err(e) = api_server.start().await {
+                    error!("❌ API 服务器运行失败: {}", e);
+                }
+                "API 服务器"
+            });
+        }
+
+        // 启动数据库后台维护任务（清理过期记录、删除过期签名、压缩）
+        if plan.maintenance && config.database.maintenance.enabled {
+            let scheduler = MaintenanceScheduler::new(db_manager.clone(), config.database.maintenance.clone())?;
+            background_tasks.spawn(scheduler.start());
+        }
+
+        if plan.replica {
+            let interval = Duration::from_millis(config.database.replica.catch_up_interval_ms.max(100));
+            background_tasks.spawn(Self::catch_up_with_primary(db_manager.clone(), interval));
+        }
+
+        // 等待任何一个核心任务结束或收到关闭信号
+        tokio::select! {
+            Some(finished) = core_tasks.join_next() => {
+                match finished {
+                    Ok(name) => info!("{}已停止", name),
+                    Err(e) => error!("❌ 服务任务异常退出: {}", e),
+                }
+            }
+            _ = signal::ctrl_c() => {
+                info!("📟 收到 Ctrl+C 信号，正在关闭服务...");
+            }
+        }
+
+        core_tasks.shutdown().await;
+        background_tasks.shutdown().await;
+        info!("🛑 所有服务已停止");
+        Ok(())
+    }
+
+    /// 只读副本定期追赶主库写入
+    async fn catch_up_with_primary(db_manager: DatabaseManager, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = db_manager.run_blocking(|db| db.try_catch_up_with_primary()).await {
+                error!("❌ 追赶主库写入失败: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_plan_per_mode() {
+        let all = ServicePlan::for_mode(RunMode::All);
+        assert!(all.ingest && all.api && all.maintenance && !all.replica);
+
+        let api_only = ServicePlan::for_mode(RunMode::ApiOnly);
+        assert!(!api_only.ingest && api_only.api && !api_only.maintenance && api_only.replica);
+
+        let ingest_only = ServicePlan::for_mode(RunMode::IngestOnly);
+        assert!(ingest_only.ingest && !ingest_only.api && ingest_only.maintenance && !ingest_only.replica);
+    }
+}
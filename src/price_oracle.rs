@@ -0,0 +1,448 @@
+//! USD 价格估值
+//!
+//! 摄取路径是同步的，不能等待价格接口：估值只读取缓存，缓存中没有的代币记为待查询，
+//! 由后台任务按间隔批量刷新。因此刚出现的代币在首次刷新前的转账不会被标注。
+
+use anyhow::Result;
+use moka::sync::Cache;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+use crate::amount::{TokenAmount, SOL_DECIMALS};
+use crate::config::PriceConfig;
+use crate::database::signature_storage::{SignatureTransactionData, UsdValuation};
+
+/// wSOL mint，SOL 转账按它的价格估值
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// 单次请求查询的代币数
+const PRICE_BATCH_SIZE: usize = 50;
+
+/// 价格查询结果，mint → USD 单价
+pub type PriceFuture<'a> = Pin<Box<dyn Future<Output = Result<HashMap<String, f64>>> + Send + 'a>>;
+
+/// 价格来源
+///
+/// 返回的结果可以缺少部分代币（来源不支持或暂无报价），缺少的代币下次刷新时重新查询。
+pub trait PriceProvider: Send + Sync {
+    /// 来源名称，记录在估值结果中
+    fn name(&self) -> &'static str;
+
+    /// 查询一批代币的 USD 单价
+    fn fetch_prices<'a>(&'a self, mints: &'a [String]) -> PriceFuture<'a>;
+}
+
+/// 带缓存的价格查询
+#[derive(Clone)]
+pub struct PriceOracle {
+    provider: Arc<dyn PriceProvider>,
+    prices: Cache<String, f64>,
+    /// 需要刷新价格的代币，长时间没有出现的代币自动淘汰
+    tracked: Cache<String, ()>,
+    refresh_interval: Duration,
+}
+
+impl std::fmt::Debug for PriceOracle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PriceOracle")
+            .field("provider", &self.provider.name())
+            .field("cached_prices", &self.prices.entry_count())
+            .finish()
+    }
+}
+
+impl PriceOracle {
+    pub fn new(provider: Arc<dyn PriceProvider>, config: &PriceConfig) -> Self {
+        Self {
+            provider,
+            prices: Cache::builder()
+                .max_capacity(config.max_tracked_mints as u64)
+                .time_to_live(Duration::from_secs(config.cache_ttl_secs.max(1)))
+                .build(),
+            tracked: Cache::builder()
+                .max_capacity(config.max_tracked_mints as u64)
+                .time_to_idle(Duration::from_secs(config.tracked_mint_idle_secs.max(1)))
+                .build(),
+            refresh_interval: Duration::from_secs(config.refresh_interval_secs.max(1)),
+        }
+    }
+
+    /// 读取缓存的 USD 单价，并把代币记为待查询（已跟踪时刷新其空闲时间）
+    pub fn price_usd(&self, mint: &str) -> Option<f64> {
+        self.tracked.get_with_by_ref(mint, || ());
+        self.prices.get(mint)
+    }
+
+    /// 估算交易中转账的 USD 价值，没有任何转账有价格时为空
+    pub fn valuate(&self, data: &SignatureTransactionData) -> Option<UsdValuation> {
+        let value = |amount: u64, decimals: u8, price: Option<f64>| {
+            price.map(|price| TokenAmount::new(amount, decimals).ui_amount() * price)
+        };
+        let sol_price = (!data.sol_transfers.is_empty()).then(|| self.price_usd(WSOL_MINT)).flatten();
+        let valuation = UsdValuation {
+            sol_transfers: data
+                .sol_transfers
+                .iter()
+                .map(|transfer| value(transfer.amount, SOL_DECIMALS, sol_price))
+                .collect(),
+            token_transfers: data
+                .token_transfers
+                .iter()
+                .map(|transfer| value(transfer.amount, transfer.decimals, self.price_usd(&transfer.mint)))
+                .collect(),
+            total_usd: 0.0,
+            provider: self.provider.name().to_string(),
+        };
+        let priced: Vec<f64> = valuation.sol_transfers.iter().chain(&valuation.token_transfers).flatten().copied().collect();
+        if priced.is_empty() {
+            return None;
+        }
+        Some(UsdValuation { total_usd: priced.iter().sum(), ..valuation })
+    }
+
+    /// 刷新全部跟踪代币的价格，返回得到价格的代币数
+    ///
+    /// 单批查询失败只记录日志，其余批次照常刷新；所有批次都失败时返回最后一个错误。
+    pub async fn refresh(&self) -> Result<usize> {
+        let mints: Vec<String> = self.tracked.iter().map(|(mint, _)| mint.as_ref().clone()).collect();
+        let mut updated = 0;
+        let mut succeeded = false;
+        let mut last_error = None;
+        for batch in mints.chunks(PRICE_BATCH_SIZE) {
+            match self.provider.fetch_prices(batch).await {
+                Ok(prices) => {
+                    succeeded = true;
+                    for (mint, price) in prices {
+                        if price.is_finite() && price >= 0.0 {
+                            self.prices.insert(mint, price);
+                            updated += 1;
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("⚠️ 查询 {} 个代币的 USD 价格失败，继续刷新其余代币: {}", batch.len(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) if !succeeded => Err(e),
+            _ => Ok(updated),
+        }
+    }
+
+    /// 持续按间隔刷新价格
+    pub async fn run(self) {
+        info!("💵 USD 价格估值已启用，来源: {}", self.provider.name());
+        // 先跟踪 SOL，首批 SOL 转账即可估值
+        self.price_usd(WSOL_MINT);
+        let mut ticker = tokio::time::interval(self.refresh_interval);
+        loop {
+            ticker.tick().await;
+            match self.refresh().await {
+                Ok(updated) => debug!("刷新 {} 个代币的 USD 价格", updated),
+                Err(e) => error!("❌ 刷新 USD 价格失败: {}", e),
+            }
+        }
+    }
+}
+
+/// 按配置创建价格来源
+#[cfg(feature = "ingest")]
+pub fn provider_from_config(config: &PriceConfig) -> Result<Arc<dyn PriceProvider>> {
+    use crate::config::PriceProviderKind;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+        .build()?;
+    let endpoint = |default: &str| config.endpoint.clone().unwrap_or_else(|| default.to_string());
+    Ok(match config.provider {
+        PriceProviderKind::CoinGecko => Arc::new(providers::CoinGecko {
+            client,
+            endpoint: endpoint("https://api.coingecko.com/api/v3"),
+            api_key: config.api_key.clone(),
+        }),
+        PriceProviderKind::Jupiter => Arc::new(providers::Jupiter {
+            client,
+            endpoint: endpoint("https://lite-api.jup.ag/price/v2"),
+        }),
+        PriceProviderKind::Pyth => Arc::new(providers::Pyth {
+            client,
+            endpoint: endpoint("https://hermes.pyth.network"),
+            feeds: config.pyth_feeds.clone(),
+        }),
+    })
+}
+
+#[cfg(feature = "ingest")]
+mod providers {
+    use anyhow::{Context, Result};
+    use serde::Deserialize;
+    use std::collections::{BTreeMap, HashMap};
+    use tracing::warn;
+
+    use super::{PriceFuture, PriceProvider};
+
+    async fn get_json<T: serde::de::DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T> {
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("价格接口返回 {}: {}", status, body.chars().take(200).collect::<String>()));
+        }
+        serde_json::from_str(&body).context("解析价格接口响应失败")
+    }
+
+    /// CoinGecko `simple/token_price/solana` 接口
+    pub struct CoinGecko {
+        pub client: reqwest::Client,
+        pub endpoint: String,
+        pub api_key: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct CoinGeckoPrice {
+        usd: Option<f64>,
+    }
+
+    impl PriceProvider for CoinGecko {
+        fn name(&self) -> &'static str {
+            "coingecko"
+        }
+
+        fn fetch_prices<'a>(&'a self, mints: &'a [String]) -> PriceFuture<'a> {
+            Box::pin(async move {
+                let mut request = self
+                    .client
+                    .get(format!("{}/simple/token_price/solana", self.endpoint.trim_end_matches('/')))
+                    .query(&[("contract_addresses", mints.join(",").as_str()), ("vs_currencies", "usd")]);
+                if let Some(api_key) = &self.api_key {
+                    // 付费接口与演示接口使用不同的请求头
+                    let header = if self.endpoint.contains("pro-api") { "x-cg-pro-api-key" } else { "x-cg-demo-api-key" };
+                    request = request.header(header, api_key);
+                }
+                let prices: HashMap<String, CoinGeckoPrice> = get_json(request).await?;
+                Ok(prices.into_iter().filter_map(|(mint, price)| Some((mint, price.usd?))).collect())
+            })
+        }
+    }
+
+    /// Jupiter Price API v2
+    pub struct Jupiter {
+        pub client: reqwest::Client,
+        pub endpoint: String,
+    }
+
+    #[derive(Deserialize)]
+    struct JupiterResponse {
+        data: HashMap<String, Option<JupiterPrice>>,
+    }
+
+    #[derive(Deserialize)]
+    struct JupiterPrice {
+        price: String,
+    }
+
+    impl PriceProvider for Jupiter {
+        fn name(&self) -> &'static str {
+            "jupiter"
+        }
+
+        fn fetch_prices<'a>(&'a self, mints: &'a [String]) -> PriceFuture<'a> {
+            Box::pin(async move {
+                let request = self.client.get(&self.endpoint).query(&[("ids", mints.join(","))]);
+                let response: JupiterResponse = get_json(request).await?;
+                Ok(response
+                    .data
+                    .into_iter()
+                    .filter_map(|(mint, price)| Some((mint, price?.price.parse().ok()?)))
+                    .collect())
+            })
+        }
+    }
+
+    /// Pyth Hermes 最新价格接口，只查询配置了价格源的代币
+    pub struct Pyth {
+        pub client: reqwest::Client,
+        pub endpoint: String,
+        /// mint → feed id
+        pub feeds: BTreeMap<String, String>,
+    }
+
+    #[derive(Deserialize)]
+    struct PythResponse {
+        parsed: Vec<PythUpdate>,
+    }
+
+    #[derive(Deserialize)]
+    struct PythUpdate {
+        id: String,
+        price: PythPrice,
+    }
+
+    #[derive(Deserialize)]
+    struct PythPrice {
+        price: String,
+        expo: i32,
+    }
+
+    impl PriceProvider for Pyth {
+        fn name(&self) -> &'static str {
+            "pyth"
+        }
+
+        fn fetch_prices<'a>(&'a self, mints: &'a [String]) -> PriceFuture<'a> {
+            Box::pin(async move {
+                // feed id 不区分大小写，响应中不带 0x 前缀
+                let normalize = |id: &str| id.trim_start_matches("0x").to_lowercase();
+                let by_feed: HashMap<String, &String> = mints
+                    .iter()
+                    .filter_map(|mint| Some((normalize(self.feeds.get(mint)?), mint)))
+                    .collect();
+                if by_feed.is_empty() {
+                    return Ok(HashMap::new());
+                }
+                let query: Vec<(&str, &str)> = by_feed.keys().map(|id| ("ids[]", id.as_str())).collect();
+                let request = self
+                    .client
+                    .get(format!("{}/v2/updates/price/latest", self.endpoint.trim_end_matches('/')))
+                    .query(&query)
+                    .query(&[("parsed", "true")]);
+                let response: PythResponse = get_json(request).await?;
+
+                let mut prices = HashMap::new();
+                for update in response.parsed {
+                    let Some(mint) = by_feed.get(&normalize(&update.id)) else {
+                        continue;
+                    };
+                    match update.price.price.parse::<i64>() {
+                        Ok(raw) => {
+                            prices.insert(mint.to_string(), raw as f64 * 10_f64.powi(update.price.expo));
+                        }
+                        Err(e) => warn!("⚠️ Pyth 价格源 {} 返回无效价格: {}", update.id, e),
+                    }
+                }
+                Ok(prices)
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::signature_storage::{SolTransfer, TokenTransfer};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FixedPrices(HashMap<String, f64>);
+
+    /// 第一次查询失败，之后每个代币都报价 1.0
+    struct FailingFirstBatch(AtomicUsize);
+
+    impl PriceProvider for FailingFirstBatch {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        fn fetch_prices<'a>(&'a self, mints: &'a [String]) -> PriceFuture<'a> {
+            let call = self.0.fetch_add(1, Ordering::SeqCst);
+            let prices = mints.iter().map(|mint| (mint.clone(), 1.0)).collect();
+            Box::pin(async move {
+                if call == 0 {
+                    return Err(anyhow::anyhow!("rate limited"));
+                }
+                Ok(prices)
+            })
+        }
+    }
+
+    impl PriceProvider for FixedPrices {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn fetch_prices<'a>(&'a self, mints: &'a [String]) -> PriceFuture<'a> {
+            let prices = mints.iter().filter_map(|mint| Some((mint.clone(), *self.0.get(mint)?))).collect();
+            Box::pin(async move { Ok(prices) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_valuation_uses_refreshed_prices() {
+        let provider = FixedPrices(HashMap::from([(WSOL_MINT.to_string(), 150.0), ("usdc".to_string(), 1.0)]));
+        let oracle = PriceOracle::new(Arc::new(provider), &PriceConfig { enabled: true, ..Default::default() });
+
+        let mut data = SignatureTransactionData::new("sig".to_string(), 1_704_067_200, 1, true);
+        data.add_sol_transfer(SolTransfer {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: 2_000_000_000,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        });
+        for mint in ["usdc", "unknown"] {
+            data.add_token_transfer(TokenTransfer {
+                from: "aliceToken".to_string(),
+                to: "bobToken".to_string(),
+                amount: 5_000_000,
+                decimals: 6,
+                mint: mint.to_string(),
+                program_id: "token".to_string(),
+                transfer_type: "Token Transfer".to_string(),
+                from_owner: None,
+                to_owner: None,
+            });
+        }
+
+        // 首次估值时缓存为空，代币被记为待查询
+        assert!(oracle.valuate(&data).is_none());
+        assert_eq!(oracle.refresh().await.unwrap(), 2);
+
+        let valuation = oracle.valuate(&data).unwrap();
+        assert_eq!(valuation.sol_transfers, vec![Some(300.0)]);
+        assert_eq!(valuation.token_transfers, vec![Some(5.0), None]);
+        assert_eq!(valuation.total_usd, 305.0);
+        assert_eq!(valuation.provider, "fixed");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_continues_past_failed_batch() {
+        let oracle = PriceOracle::new(
+            Arc::new(FailingFirstBatch(AtomicUsize::new(0))),
+            &PriceConfig { enabled: true, ..Default::default() },
+        );
+        for index in 0..PRICE_BATCH_SIZE + 10 {
+            oracle.price_usd(&format!("mint{}", index));
+        }
+
+        // 第一批失败，第二批的 10 个代币仍然得到价格
+        assert_eq!(oracle.refresh().await.unwrap(), 10);
+        assert_eq!(oracle.refresh().await.unwrap(), PRICE_BATCH_SIZE + 10);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_fails_when_every_batch_fails() {
+        let oracle = PriceOracle::new(
+            Arc::new(FailingFirstBatch(AtomicUsize::new(0))),
+            &PriceConfig { enabled: true, ..Default::default() },
+        );
+        oracle.price_usd("usdc");
+        assert!(oracle.refresh().await.is_err());
+    }
+
+    #[test]
+    fn test_tracked_mints_are_bounded() {
+        let config = PriceConfig { enabled: true, max_tracked_mints: 2, ..Default::default() };
+        let oracle = PriceOracle::new(Arc::new(FixedPrices(HashMap::new())), &config);
+        for index in 0..20 {
+            oracle.price_usd(&format!("mint{}", index));
+        }
+        oracle.tracked.run_pending_tasks();
+        assert!(oracle.tracked.entry_count() <= 2);
+    }
+}
@@ -0,0 +1,110 @@
+//! 按签名从 Solana RPC 补查交易
+//!
+//! 订阅断线期间错过的交易不会出现在本地数据库中。查询接口带 `fetch=true` 时，
+//! 本地没有的签名通过 RPC `getTransaction` 补查，按实时摄取的写入路径解析入库后再返回。
+
+use anyhow::Result;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::database::DatabaseManager;
+
+/// 补查结果，交易存在并已入库时为 true
+pub type FetchFuture<'a> = Pin<Box<dyn Future<Output = Result<bool>> + Send + 'a>>;
+
+/// 交易补查来源
+pub trait TransactionFetcher: Send + Sync {
+    /// 获取签名对应的交易并写入数据库；远端也没有该交易时返回 false
+    fn fetch_and_store<'a>(&'a self, db_manager: &'a DatabaseManager, signature: &'a str) -> FetchFuture<'a>;
+}
+
+#[cfg(feature = "ingest")]
+pub use rpc::RpcTransactionFetcher;
+
+#[cfg(feature = "ingest")]
+mod rpc {
+    use anyhow::{Context, Result};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use tracing::info;
+
+    use super::{FetchFuture, TransactionFetcher};
+    use crate::block_import::convert_confirmed_transaction;
+    use crate::config::RpcConfig;
+    use crate::database::DatabaseManager;
+    use crate::grpc_client::SolanaGrpcClient;
+    use crate::transfer_parser::TransferParser;
+
+    /// 通过 JSON-RPC `getTransaction` 补查交易
+    pub struct RpcTransactionFetcher {
+        client: reqwest::Client,
+        endpoint: String,
+        parser: TransferParser,
+    }
+
+    impl RpcTransactionFetcher {
+        /// 按配置创建，未配置端点时返回 None
+        pub fn from_config(config: &RpcConfig, parser: TransferParser) -> Result<Option<Self>> {
+            let Some(endpoint) = config.endpoint.clone() else {
+                return Ok(None);
+            };
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+                .build()?;
+            Ok(Some(Self { client, endpoint, parser }))
+        }
+
+        /// 请求 `getTransaction`，交易不存在时返回 None
+        async fn get_transaction(&self, signature: &str) -> Result<Option<serde_json::Value>> {
+            let request = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getTransaction",
+                "params": [signature, {
+                    "encoding": "json",
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0,
+                }],
+            });
+            let response = self
+                .client
+                .post(&self.endpoint)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(request.to_string())
+                .send()
+                .await?;
+            let status = response.status();
+            let body = response.text().await?;
+            if !status.is_success() {
+                return Err(anyhow::anyhow!("RPC 返回 {}: {}", status, body.chars().take(200).collect::<String>()));
+            }
+            let mut response: serde_json::Value = serde_json::from_str(&body).context("解析 RPC 响应失败")?;
+            if let Some(error) = response.get("error") {
+                return Err(anyhow::anyhow!("RPC 错误: {}", error));
+            }
+            Ok(Some(response["result"].take()).filter(|result| !result.is_null()))
+        }
+    }
+
+    impl TransactionFetcher for RpcTransactionFetcher {
+        fn fetch_and_store<'a>(&'a self, db_manager: &'a DatabaseManager, signature: &'a str) -> FetchFuture<'a> {
+            Box::pin(async move {
+                let Some(result) = self.get_transaction(signature).await? else {
+                    return Ok(false);
+                };
+                let (update, block_time) = convert_confirmed_transaction(result)?;
+                // 区块时间缺失时退回到当前时间，与离线导入一致
+                let timestamp = block_time.unwrap_or_else(|| {
+                    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+                });
+                let parser = self.parser.clone();
+                db_manager
+                    .run_blocking(move |db| {
+                        SolanaGrpcClient::write_transaction_to_database(db, &parser, &update, timestamp, block_time)
+                    })
+                    .await?;
+                info!("📥 从 RPC 补查并保存交易: {}", signature);
+                Ok(true)
+            })
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use anyhow::Result;
+use std::collections::HashSet;
+
+use crate::amount::Lamports;
+use crate::config::RulesConfig;
+use crate::database::{Alert, AlertRule, DatabaseManager, PeelDepth, RecordType, SignatureTransactionData};
+
+/// 可疑模式规则引擎
+///
+/// 在交易写入地址索引之前评估，历史数据来自各地址最近的转账记录（不含当前交易）。
+/// 规则：
+/// - 分散转出：窗口内转出对象首次达到 `fan_out_min_recipients` 个
+/// - 剥离链：地址把最近收到的资金大部分（未全部）转给下一个地址，跳数达到 `peel_chain_min_hops`
+/// - 往返转账：A 转给 B 后，窗口内 B 又把相近金额转回 A
+/// - 拆分规避：金额略低于申报阈值
+#[derive(Debug, Clone)]
+pub struct RulesEngine {
+    config: RulesConfig,
+}
+
+impl RulesEngine {
+    pub fn new(config: RulesConfig) -> Self {
+        Self { config }
+    }
+
+    /// 评估一笔交易，返回命中的告警（剥离链深度会同时更新）
+    pub fn evaluate(&self, db: &DatabaseManager, data: &SignatureTransactionData) -> Result<Vec<Alert>> {
+        if !data.is_successful || data.sol_transfers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut hits = Vec::new();
+        self.check_fan_out(db, data, &mut hits)?;
+        for transfer in &data.sol_transfers {
+            self.check_peel_chain(db, data, &transfer.from, &transfer.to, transfer.amount, &mut hits)?;
+            self.check_round_trip(db, data, &transfer.from, &transfer.to, transfer.amount, &mut hits)?;
+            self.check_structuring(&transfer.from, &transfer.to, transfer.amount, &mut hits);
+        }
+
+        Ok(hits
+            .into_iter()
+            .enumerate()
+            .map(|(index, (rule, addresses, amount, description))| Alert {
+                id: format!("{}:{}:{}", data.signature, rule.as_str(), index),
+                rule,
+                signature: data.signature.clone(),
+                slot: data.slot,
+                timestamp: data.timestamp,
+                addresses,
+                amount,
+                description,
+            })
+            .collect())
+    }
+
+    fn window_start(&self, data: &SignatureTransactionData) -> u64 {
+        (data.timestamp.max(0) as u64).saturating_sub(self.config.window_secs)
+    }
+
+    fn check_fan_out(&self, db: &DatabaseManager, data: &SignatureTransactionData, hits: &mut Vec<Hit>) -> Result<()> {
+        let min_recipients = self.config.fan_out_min_recipients;
+        if min_recipients == 0 {
+            return Ok(());
+        }
+
+        let senders: HashSet<&str> = data.sol_transfers.iter().map(|transfer| transfer.from.as_str()).collect();
+        for sender in senders {
+            let earlier: HashSet<String> = self
+                .recent_sol_records(db, sender, data)?
+                .filter(|(record_type, _, _, _)| matches!(record_type, RecordType::Sender))
+                .map(|(_, _, to, _)| to)
+                .collect();
+            let mut recipients = earlier.clone();
+            let mut amount = 0;
+            for transfer in data.sol_transfers.iter().filter(|transfer| transfer.from == sender) {
+                recipients.insert(transfer.to.clone());
+                amount += transfer.amount;
+            }
+
+            // 只在首次达到阈值时告警
+            if earlier.len() < min_recipients && recipients.len() >= min_recipients {
+                hits.push((
+                    AlertRule::FanOut,
+                    vec![sender.to_string()],
+                    amount,
+                    format!("{} 秒内向 {} 个不同地址转出", self.config.window_secs, recipients.len()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_peel_chain(
+        &self,
+        db: &DatabaseManager,
+        data: &SignatureTransactionData,
+        from: &str,
+        to: &str,
+        amount: u64,
+        hits: &mut Vec<Hit>,
+    ) -> Result<()> {
+        if self.config.peel_chain_min_hops == 0 {
+            return Ok(());
+        }
+
+        // 最近一次收到的资金
+        let Some((_, sender, _, received)) = self
+            .recent_sol_records(db, from, data)?
+            .find(|(record_type, _, _, _)| matches!(record_type, RecordType::Receiver))
+        else {
+            return Ok(());
+        };
+        if received == 0 {
+            return Ok(());
+        }
+        let ratio = amount as f64 / received as f64;
+        if ratio < self.config.peel_chain_forward_ratio || ratio >= 1.0 {
+            return Ok(());
+        }
+
+        let window_start = self.window_start(data) as i64;
+        let previous = db
+            .alert_storage()
+            .get_peel_depth(from)?
+            .filter(|depth| depth.timestamp >= window_start)
+            .map(|depth| depth.depth)
+            .unwrap_or(0);
+        let depth = previous + 1;
+        db.alert_storage().set_peel_depth(to, PeelDepth { depth, timestamp: data.timestamp })?;
+
+        if depth == self.config.peel_chain_min_hops {
+            hits.push((
+                AlertRule::PeelChain,
+                vec![from.to_string(), to.to_string(), sender],
+                amount,
+                format!("剥离链第 {} 跳：收到 {} lamports 后转出 {:.1}%", depth, received, ratio * 100.0),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_round_trip(
+        &self,
+        db: &DatabaseManager,
+        data: &SignatureTransactionData,
+        from: &str,
+        to: &str,
+        amount: u64,
+        hits: &mut Vec<Hit>,
+    ) -> Result<()> {
+        if self.config.round_trip_return_ratio <= 0.0 {
+            return Ok(());
+        }
+
+        // 当前转出方之前从当前接收方收到过资金
+        let original = self
+            .recent_sol_records(db, from, data)?
+            .find(|(record_type, sender, _, _)| matches!(record_type, RecordType::Receiver) && sender == to);
+        if let Some((_, _, _, original_amount)) = original {
+            let ratio = amount as f64 / original_amount as f64;
+            if original_amount > 0 && ratio >= self.config.round_trip_return_ratio {
+                hits.push((
+                    AlertRule::RoundTrip,
+                    vec![to.to_string(), from.to_string()],
+                    amount,
+                    format!("{} lamports 转出后 {} 秒内转回 {} lamports", original_amount, self.config.window_secs, amount),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_structuring(&self, from: &str, to: &str, amount: u64, hits: &mut Vec<Hit>) {
+        let sol = Lamports(amount).as_sol();
+        let threshold = self
+            .config
+            .structuring_thresholds_sol
+            .iter()
+            .find(|&&threshold| sol < threshold && sol >= threshold * (1.0 - self.config.structuring_margin));
+        if let Some(threshold) = threshold {
+            hits.push((
+                AlertRule::Structuring,
+                vec![from.to_string(), to.to_string()],
+                amount,
+                format!("转账 {} SOL 略低于阈值 {} SOL", sol, threshold),
+            ));
+        }
+    }
+
+    /// 地址在窗口内的 SOL 转账记录（最新的在前）：(方向, 转出方, 接收方, 金额)
+    fn recent_sol_records(
+        &self,
+        db: &DatabaseManager,
+        address: &str,
+        data: &SignatureTransactionData,
+    ) -> Result<impl Iterator<Item = (RecordType, String, String, u64)>> {
+        let window_start = self.window_start(data);
+        let signature = data.signature.clone();
+        let records = db.address_storage().get_address_records(address)?.map(|list| list.records).unwrap_or_default();
+        Ok(records
+            .into_iter()
+            .take_while(move |record| record.timestamp >= window_start)
+            .filter(move |record| record.signature != signature)
+            .filter_map(|record| {
+                let transfer = record.sol_transfer?;
+                Some((record.record_type, transfer.from, transfer.to, transfer.amount))
+            }))
+    }
+}
+
+/// 命中的规则：(规则, 相关地址, 金额, 说明)
+type Hit = (AlertRule, Vec<String>, u64, String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::SolTransfer;
+    use crate::transfer_types;
+    use crate::test_util::TempDir;
+
+    fn record(db: &DatabaseManager, signature: &str, timestamp: u64, from: &str, to: &str, amount: u64) {
+        let transfer = transfer_types::SolTransfer {
+            signature: signature.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            from_index: 0,
+            to_index: 1,
+            timestamp: timestamp as u32,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        };
+        db.address_storage().batch_process_transaction(signature, timestamp, 1, &[transfer], &[]).unwrap();
+    }
+
+    fn transaction(signature: &str, timestamp: i64, transfers: &[(&str, &str, u64)]) -> SignatureTransactionData {
+        let mut data = SignatureTransactionData::new(signature.to_string(), timestamp, 1, true);
+        for (from, to, amount) in transfers {
+            data.add_sol_transfer(SolTransfer {
+                from: from.to_string(),
+                to: to.to_string(),
+                amount: *amount,
+                transfer_type: "SOL Transfer".to_string(),
+                is_wrapped: false,
+                instruction_path: None,
+            });
+        }
+        data
+    }
+
+    fn rules(alerts: &[Alert]) -> Vec<AlertRule> {
+        alerts.iter().map(|alert| alert.rule).collect()
+    }
+
+    #[test]
+    fn test_rules_detect_patterns() {
+        let dir = TempDir::new("rules-test");
+        let db = DatabaseManager::new(dir.path().to_str().unwrap(), 6, "SIGN01".to_string(), "ADDR01".to_string(), 100).unwrap();
+        let engine = RulesEngine::new(RulesConfig {
+            enabled: true,
+            fan_out_min_recipients: 3,
+            peel_chain_min_hops: 2,
+            ..Default::default()
+        });
+
+        // 拆分规避：9,600 SOL 略低于 10,000 SOL
+        let alerts = engine.evaluate(&db, &transaction("s1", 1_000, &[("a", "b", 9_600_000_000_000)])).unwrap();
+        assert_eq!(rules(&alerts), vec![AlertRule::Structuring]);
+        record(&db, "s1", 1_000, "a", "b", 9_600_000_000_000);
+
+        // 往返 + 剥离链第一跳：b 把收到的 95% 转回 a
+        let alerts = engine.evaluate(&db, &transaction("s2", 1_100, &[("b", "a", 9_120_000_000_000)])).unwrap();
+        assert_eq!(rules(&alerts), vec![AlertRule::RoundTrip]);
+        record(&db, "s2", 1_100, "b", "a", 9_120_000_000_000);
+
+        // 剥离链第二跳：a 把收到的 90% 转给 c
+        let alerts = engine.evaluate(&db, &transaction("s3", 1_200, &[("a", "c", 8_208_000_000_000)])).unwrap();
+        assert_eq!(rules(&alerts), vec![AlertRule::PeelChain]);
+        record(&db, "s3", 1_200, "a", "c", 8_208_000_000_000);
+
+        // 分散转出：a 累计转给 b、c、d 三个地址，第四个地址不再告警
+        let alerts = engine.evaluate(&db, &transaction("s4", 1_300, &[("a", "d", 1_000)])).unwrap();
+        assert_eq!(rules(&alerts), vec![AlertRule::FanOut]);
+        record(&db, "s4", 1_300, "a", "d", 1_000);
+        db.alert_storage().record_alerts(&alerts).unwrap();
+        let alerts = engine.evaluate(&db, &transaction("s5", 1_400, &[("a", "e", 1_000)])).unwrap();
+        assert!(alerts.is_empty());
+
+        // 超出窗口的历史不参与评估
+        let alerts = engine.evaluate(&db, &transaction("s6", 10_000, &[("a", "b", 1_000)])).unwrap();
+        assert!(alerts.is_empty());
+
+        let stored = db.alert_storage().list_alerts(Some(AlertRule::FanOut), Some("a"), 10).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].signature, "s4");
+        assert!(db.alert_storage().list_alerts(Some(AlertRule::RoundTrip), None, 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::clock::{SharedClock, SystemClock};
+use crate::database::slot_finality_storage::OrphanedSlot;
+use crate::database::DatabaseManager;
+
+/// 最多跟踪的未最终确定槽位数，长时间收不到最终确定通知时丢弃最旧的槽位
+const MAX_PENDING_SLOTS: usize = 4096;
+
+/// 槽位状态变化事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotEvent {
+    Processed,
+    Confirmed,
+    Finalized,
+    /// 槽位死亡，不会被确认
+    Dead,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    /// 尚未最终确定的槽位 → 父槽位
+    pending: BTreeMap<u64, Option<u64>>,
+    /// 本次运行中最近一次最终确定的槽位
+    last_finalized: Option<u64>,
+}
+
+/// 槽位最终性跟踪器
+///
+/// 根据槽位订阅的 processed → confirmed → finalized 状态变化维护最终性：
+/// 某个槽位最终确定时，沿父槽位回溯出最终链，同期处理过但不在链上的槽位
+/// 判定为孤立；死亡槽位直接判定为孤立。孤立槽位中已存储的交易在 API 中
+/// 以 `finality: orphaned` 标出。
+///
+/// 只有回溯能连到上一个最终确定槽位时才判定孤立，避免在重启或漏收消息后误判。
+#[derive(Debug, Clone)]
+pub struct SlotTracker {
+    db_manager: DatabaseManager,
+    clock: SharedClock,
+    state: Arc<Mutex<TrackerState>>,
+}
+
+impl SlotTracker {
+    /// 创建槽位跟踪器
+    pub fn new(db_manager: DatabaseManager) -> Self {
+        Self {
+            db_manager,
+            clock: SystemClock::shared(),
+            state: Arc::new(Mutex::new(TrackerState::default())),
+        }
+    }
+
+    /// 处理一次槽位状态变化，返回新判定的孤立槽位（阻塞调用）
+    pub fn observe(&self, slot: u64, parent: Option<u64>, event: SlotEvent) -> Result<Vec<u64>> {
+        let finality = self.db_manager.slot_finality_storage();
+        let mut state = self.state.lock().unwrap();
+
+        match event {
+            SlotEvent::Processed | SlotEvent::Confirmed => {
+                if state.last_finalized.is_some_and(|finalized| slot <= finalized) {
+                    return Ok(Vec::new());
+                }
+                let known_parent = state.pending.entry(slot).or_insert(parent);
+                if known_parent.is_none() {
+                    *known_parent = parent;
+                }
+                while state.pending.len() > MAX_PENDING_SLOTS {
+                    state.pending.pop_first();
+                }
+                if event == SlotEvent::Confirmed {
+                    finality.set_confirmed_slot(slot)?;
+                }
+                Ok(Vec::new())
+            }
+            SlotEvent::Dead => {
+                let parent = state.pending.remove(&slot).flatten().or(parent);
+                drop(state);
+                self.orphan(slot, parent, "dead")?;
+                Ok(vec![slot])
+            }
+            SlotEvent::Finalized => {
+                let parent = parent.or_else(|| state.pending.get(&slot).copied().flatten());
+                let previous = state.last_finalized;
+
+                // 沿父槽位回溯最终链，直到上一个最终确定槽位
+                let mut chain = HashSet::from([slot]);
+                let mut connected = false;
+                let mut next = parent;
+                while let Some(ancestor) = next {
+                    if previous.is_some_and(|finalized| ancestor <= finalized) {
+                        connected = true;
+                        break;
+                    }
+                    chain.insert(ancestor);
+                    next = state.pending.get(&ancestor).copied().flatten();
+                }
+
+                let settled: Vec<(u64, Option<u64>)> = state
+                    .pending
+                    .range(..=slot)
+                    .map(|(slot, parent)| (*slot, *parent))
+                    .collect();
+                for (settled_slot, _) in &settled {
+                    state.pending.remove(settled_slot);
+                }
+                if previous.is_none_or(|finalized| slot > finalized) {
+                    state.last_finalized = Some(slot);
+                }
+                drop(state);
+
+                finality.set_finalized_slot(slot)?;
+                if !connected {
+                    return Ok(Vec::new());
+                }
+
+                let mut orphaned = Vec::new();
+                for (settled_slot, settled_parent) in settled {
+                    if !chain.contains(&settled_slot) {
+                        self.orphan(settled_slot, settled_parent, "skipped")?;
+                        orphaned.push(settled_slot);
+                    }
+                }
+                Ok(orphaned)
+            }
+        }
+    }
+
+    fn orphan(&self, slot: u64, parent: Option<u64>, reason: &str) -> Result<()> {
+        let transaction_count = self.db_manager.slot_index_storage().get_slot_transactions(slot)?.len();
+        self.db_manager.slot_finality_storage().mark_orphaned(&OrphanedSlot {
+            slot,
+            parent,
+            reason: reason.to_string(),
+            transaction_count,
+            detected_at: self.clock.timestamp(),
+        })?;
+        if transaction_count > 0 {
+            warn!("⚠️ 槽位 {} 未被最终确定（{}），其中 {} 笔已存储交易标记为孤立", slot, reason, transaction_count);
+        } else {
+            info!("🪦 槽位 {} 未被最终确定（{}）", slot, reason);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::slot_finality_storage::FinalityStatus;
+    use crate::test_util::TempDir;
+
+    fn tracker(dir: &TempDir) -> SlotTracker {
+        let db = DatabaseManager::new(dir.path().to_str().unwrap(), 6, "SIG001".to_string(), "ADDR01".to_string(), 10)
+            .expect("打开临时数据库失败");
+        SlotTracker::new(db)
+    }
+
+    #[test]
+    fn test_fork_slots_orphaned_when_sibling_finalizes() {
+        let dir = TempDir::new("slot-tracker-test");
+        let tracker = tracker(&dir);
+        tracker.observe(100, Some(99), SlotEvent::Finalized).unwrap();
+
+        // 101 和 102 在同一父槽位上分叉，103 建在 102 上
+        tracker.observe(101, Some(100), SlotEvent::Processed).unwrap();
+        tracker.observe(102, Some(100), SlotEvent::Processed).unwrap();
+        tracker.observe(103, Some(102), SlotEvent::Confirmed).unwrap();
+        tracker.observe(104, Some(103), SlotEvent::Processed).unwrap();
+        tracker.observe(105, Some(104), SlotEvent::Dead).unwrap();
+
+        let view = tracker.db_manager.slot_finality_storage().view().unwrap();
+        assert_eq!(view.status_of(103), FinalityStatus::Confirmed);
+        assert_eq!(view.status_of(104), FinalityStatus::Processed);
+        assert_eq!(view.status_of(105), FinalityStatus::Orphaned);
+
+        assert_eq!(tracker.observe(103, Some(102), SlotEvent::Finalized).unwrap(), vec![101]);
+        let view = tracker.db_manager.slot_finality_storage().view().unwrap();
+        assert_eq!(view.status_of(101), FinalityStatus::Orphaned);
+        assert_eq!(view.status_of(102), FinalityStatus::Finalized);
+        assert_eq!(view.status_of(104), FinalityStatus::Processed);
+    }
+
+    #[test]
+    fn test_no_orphans_without_connected_chain() {
+        let dir = TempDir::new("slot-tracker-test");
+        let tracker = tracker(&dir);
+        assert_eq!(
+            tracker.db_manager.slot_finality_storage().view().unwrap().status_of(1),
+            FinalityStatus::Unknown
+        );
+
+        // 重启后第一次最终确定时无法回溯到上一个最终槽位，不判定孤立
+        tracker.observe(10, Some(9), SlotEvent::Processed).unwrap();
+        tracker.observe(12, Some(11), SlotEvent::Processed).unwrap();
+        assert!(tracker.observe(12, Some(11), SlotEvent::Finalized).unwrap().is_empty());
+        let view = tracker.db_manager.slot_finality_storage().view().unwrap();
+        assert_eq!(view.status_of(10), FinalityStatus::Finalized);
+    }
+}
@@ -0,0 +1,215 @@
+//! 订阅流录制与回放
+//!
+//! 录制文件以 8 字节魔数开头，之后每条记录为 `长度(u32 小端) | SubscribeUpdate protobuf`。
+//! 用户报告解析问题时录制原始消息，之后通过正常的摄取流程回放复现。
+
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use futures::{StreamExt, TryStreamExt};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use tonic::Status;
+use tracing::{error, info};
+use yellowstone_grpc_proto::prelude::{SubscribeRequest, SubscribeUpdate};
+use yellowstone_grpc_proto::prost::Message;
+
+use crate::transaction_source::{TransactionSource, UpdateStream};
+
+/// 录制文件魔数
+pub const RECORD_FILE_MAGIC: &[u8; 8] = b"SLGREC01";
+
+/// 单条记录的最大长度，防止损坏的长度字段导致分配过大内存
+const MAX_RECORD_LENGTH: usize = 256 * 1024 * 1024;
+
+/// 录制文件写入器，追加到已有文件末尾
+pub struct RecordWriter {
+    writer: BufWriter<File>,
+}
+
+impl RecordWriter {
+    /// 打开录制文件，新文件写入魔数
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("打开录制文件 {} 失败", path.display()))?;
+        let is_new = file.metadata()?.len() == 0;
+        let mut writer = BufWriter::new(file);
+        if is_new {
+            writer.write_all(RECORD_FILE_MAGIC)?;
+        }
+        Ok(Self { writer })
+    }
+
+    /// 写入一条消息
+    pub fn write(&mut self, update: &SubscribeUpdate) -> Result<()> {
+        let payload = update.encode_to_vec();
+        self.writer.write_all(&(payload.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&payload)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// 录制文件读取器，按顺序返回消息
+pub struct RecordReader<R: Read> {
+    reader: R,
+}
+
+impl RecordReader<BufReader<File>> {
+    /// 打开录制文件
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("打开录制文件 {} 失败", path.display()))?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> RecordReader<R> {
+    /// 校验魔数后创建读取器
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).context("录制文件缺少文件头")?;
+        if &magic != RECORD_FILE_MAGIC {
+            return Err(anyhow::anyhow!("不是订阅流录制文件"));
+        }
+        Ok(Self { reader })
+    }
+}
+
+impl<R: Read> Iterator for RecordReader<R> {
+    type Item = Result<SubscribeUpdate>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut length = [0u8; 4];
+        match self.reader.read_exact(&mut length) {
+            Ok(()) => {}
+            // 在记录边界结束是正常的文件结尾
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return None,
+            Err(e) => return Some(Err(e.into())),
+        }
+        let length = u32::from_le_bytes(length) as usize;
+        if length > MAX_RECORD_LENGTH {
+            return Some(Err(anyhow::anyhow!("记录长度 {} 超出上限，文件可能已损坏", length)));
+        }
+        let mut payload = vec![0u8; length];
+        if let Err(e) = self.reader.read_exact(&mut payload) {
+            return Some(Err(anyhow::Error::new(e).context("录制文件在记录中间截断")));
+        }
+        Some(SubscribeUpdate::decode(payload.as_slice()).context("解码录制的消息失败"))
+    }
+}
+
+/// 把内层数据源收到的消息录制到文件，消息照常交给摄取流程
+pub struct RecordingSource {
+    inner: Box<dyn TransactionSource>,
+    path: PathBuf,
+}
+
+impl RecordingSource {
+    pub fn new(inner: Box<dyn TransactionSource>, path: PathBuf) -> Self {
+        Self { inner, path }
+    }
+}
+
+impl TransactionSource for RecordingSource {
+    fn describe(&self) -> String {
+        format!("{}（录制到 {}）", self.inner.describe(), self.path.display())
+    }
+
+    fn subscribe(&mut self, request: SubscribeRequest) -> BoxFuture<'_, Result<UpdateStream>> {
+        Box::pin(async move {
+            let mut writer = RecordWriter::open(&self.path)?;
+            let stream = self.inner.subscribe(request).await?;
+            info!("⏺️ 录制订阅流到 {}", self.path.display());
+            let mut failed = false;
+            let recorded = stream.inspect(move |message| {
+                if let (Ok(update), false) = (message, failed) {
+                    // 写入失败只记录一次错误，不影响摄取
+                    if let Err(e) = writer.write(update).and_then(|_| writer.flush()) {
+                        error!("❌ 录制订阅流失败，停止录制: {}", e);
+                        failed = true;
+                    }
+                }
+            });
+            Ok(Box::pin(recorded) as UpdateStream)
+        })
+    }
+    fn update_subscription(&mut self, request: SubscribeRequest) -> bool {
+        self.inner.update_subscription(request)
+    }
+}
+
+/// 从录制文件回放消息；文件只回放一次，再次订阅得到空流
+pub struct ReplaySource {
+    path: PathBuf,
+    replayed: bool,
+}
+
+impl ReplaySource {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, replayed: false }
+    }
+}
+
+impl TransactionSource for ReplaySource {
+    fn describe(&self) -> String {
+        format!("replay://{}", self.path.display())
+    }
+
+    /// 忽略过滤条件，按录制顺序返回全部消息
+    fn subscribe(&mut self, _request: SubscribeRequest) -> BoxFuture<'_, Result<UpdateStream>> {
+        Box::pin(async move {
+            if std::mem::replace(&mut self.replayed, true) {
+                return Ok(Box::pin(futures::stream::empty()) as UpdateStream);
+            }
+            let reader = RecordReader::open(&self.path)?;
+            info!("⏪ 回放录制文件 {}", self.path.display());
+            let stream = futures::stream::iter(reader).map_err(|e| Status::data_loss(format!("{:#}", e)));
+            Ok(Box::pin(stream) as UpdateStream)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use yellowstone_grpc_proto::prelude::{subscribe_update::UpdateOneof, SubscribeUpdateSlot};
+
+    #[test]
+    fn test_record_and_read_back() {
+        let dir = TempDir::new("stream-record-test");
+        let path = dir.path().join("updates.rec");
+        let updates: Vec<_> = (100..105)
+            .map(|slot| SubscribeUpdate {
+                filters: vec!["slot".to_string()],
+                created_at: None,
+                update_oneof: Some(UpdateOneof::Slot(SubscribeUpdateSlot { slot, parent: Some(slot - 1), status: 0, dead_error: None })),
+            })
+            .collect();
+
+        // 分两次写入，追加时不重复写文件头
+        let (first, second) = updates.split_at(2);
+        for batch in [first, second] {
+            let mut writer = RecordWriter::open(&path).unwrap();
+            for update in batch {
+                writer.write(update).unwrap();
+            }
+            writer.flush().unwrap();
+        }
+
+        let replayed: Vec<_> = RecordReader::open(&path).unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(replayed, updates);
+
+        // 记录中间截断时报错
+        let length = std::fs::metadata(&path).unwrap().len();
+        OpenOptions::new().write(true).open(&path).unwrap().set_len(length - 3).unwrap();
+        assert!(RecordReader::open(&path).unwrap().last().unwrap().is_err());
+    }
+}
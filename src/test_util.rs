@@ -0,0 +1,29 @@
+//! 测试辅助工具
+
+use std::path::{Path, PathBuf};
+
+/// 测试用临时目录，释放时连同其中的内容一起删除
+///
+/// 在系统临时目录下创建 `{name}-{uuid}` 目录。打开在其中的数据库需要先于本实例释放，
+/// 按先声明目录、后打开数据库的顺序写即可。
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).expect("创建临时目录失败");
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::clock::{SharedClock, SystemClock};
+
+/// 统计窗口长度（秒）：1 分钟、5 分钟、15 分钟
+pub const THROUGHPUT_WINDOWS: [u64; 3] = [60, 300, 900];
+
+/// 某个滑动窗口内的平均速率
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputRate {
+    /// 窗口长度（秒）
+    pub window_secs: u64,
+    /// 每秒 gRPC 消息数
+    pub messages_per_sec: f64,
+    /// 每秒转账笔数（SOL 与代币）
+    pub transfers_per_sec: f64,
+    /// 每秒转账的 lamports
+    pub lamports_per_sec: f64,
+}
+
+/// 一秒内的计数
+#[derive(Debug, Clone, Copy, Default)]
+struct SecondBucket {
+    second: i64,
+    messages: u64,
+    transfers: u64,
+    lamports: u64,
+}
+
+/// 摄取吞吐量统计
+///
+/// 按秒累加计数，只保留最长窗口内的桶，读取时按窗口求平均。
+/// gRPC 客户端记录，日志和吞吐量接口读取，克隆后共享同一状态。
+/// 进程启动不足一个窗口时按实际运行时长计算，避免速率被低估。
+#[derive(Debug, Clone)]
+pub struct ThroughputMeter {
+    clock: SharedClock,
+    started_at: i64,
+    buckets: Arc<Mutex<VecDeque<SecondBucket>>>,
+}
+
+impl Default for ThroughputMeter {
+    fn default() -> Self {
+        Self::with_clock(SystemClock::shared())
+    }
+}
+
+impl ThroughputMeter {
+    /// 使用指定时钟创建吞吐量统计
+    pub fn with_clock(clock: SharedClock) -> Self {
+        Self {
+            started_at: clock.timestamp(),
+            clock,
+            buckets: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// 记录收到一条 gRPC 消息
+    pub fn record_message(&self) {
+        self.record(|bucket| bucket.messages += 1);
+    }
+
+    /// 记录一笔交易中的转账笔数和 SOL 金额
+    pub fn record_transfers(&self, transfers: usize, lamports: u64) {
+        if transfers == 0 {
+            return;
+        }
+        self.record(|bucket| {
+            bucket.transfers += transfers as u64;
+            bucket.lamports += lamports;
+        });
+    }
+
+    /// 各窗口的平均速率（按窗口从短到长）
+    pub fn rates(&self) -> Vec<ThroughputRate> {
+        let now = self.clock.timestamp();
+        let buckets = self.buckets.lock().unwrap();
+        THROUGHPUT_WINDOWS
+            .iter()
+            .map(|&window_secs| {
+                let since = now - window_secs as i64;
+                let (messages, transfers, lamports) = buckets
+                    .iter()
+                    .filter(|bucket| bucket.second > since)
+                    .fold((0, 0, 0), |(messages, transfers, lamports), bucket| {
+                        (messages + bucket.messages, transfers + bucket.transfers, lamports + bucket.lamports)
+                    });
+                let elapsed = (now - self.started_at).clamp(1, window_secs as i64) as f64;
+                ThroughputRate {
+                    window_secs,
+                    messages_per_sec: messages as f64 / elapsed,
+                    transfers_per_sec: transfers as f64 / elapsed,
+                    lamports_per_sec: lamports as f64 / elapsed,
+                }
+            })
+            .collect()
+    }
+
+    fn record(&self, update: impl FnOnce(&mut SecondBucket)) {
+        let now = self.clock.timestamp();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.back().is_none_or(|bucket| bucket.second != now) {
+            buckets.push_back(SecondBucket { second: now, ..Default::default() });
+        }
+        if let Some(bucket) = buckets.back_mut() {
+            update(bucket);
+        }
+
+        let oldest = now - THROUGHPUT_WINDOWS[THROUGHPUT_WINDOWS.len() - 1] as i64;
+        while buckets.front().is_some_and(|bucket| bucket.second <= oldest) {
+            buckets.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_sliding_window_rates() {
+        let clock = MockClock::new(1_704_067_200);
+        let meter = ThroughputMeter::with_clock(Arc::new(clock.clone()));
+
+        // 运行满 15 分钟：最早 5 分钟每秒 2 条消息，之后每秒 1 条
+        for second in 0..900 {
+            clock.advance(1);
+            meter.record_message();
+            if second < 300 {
+                meter.record_message();
+            }
+        }
+        meter.record_transfers(6, 600);
+        meter.record_transfers(0, 0);
+
+        let rates = meter.rates();
+        assert_eq!(rates.iter().map(|rate| rate.window_secs).collect::<Vec<_>>(), vec![60, 300, 900]);
+        assert_eq!(rates[0].messages_per_sec, 1.0);
+        assert_eq!(rates[0].transfers_per_sec, 0.1);
+        assert_eq!(rates[0].lamports_per_sec, 10.0);
+        assert_eq!(rates[2].messages_per_sec, 1200.0 / 900.0);
+
+        // 超出最长窗口的桶被丢弃
+        clock.advance(900);
+        assert!(meter.rates().iter().all(|rate| rate.messages_per_sec == 0.0));
+    }
+}
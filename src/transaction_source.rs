@@ -0,0 +1,104 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::SinkExt;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tonic::transport::ClientTlsConfig;
+use tonic::Status;
+use tracing::debug;
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::prelude::{SubscribeRequest, SubscribeRequestPing, SubscribeUpdate};
+
+use crate::config::GrpcConfig;
+
+/// 订阅更新流，元素与 Yellowstone 订阅流一致
+pub type UpdateStream = BoxStream<'static, Result<SubscribeUpdate, Status>>;
+
+/// 交易数据源
+///
+/// `SolanaGrpcClient` 只通过该接口获取更新流，解析与存储逻辑与数据来源无关；
+/// 流正常结束视为断开，客户端会重新订阅。
+pub trait TransactionSource: Send + Sync {
+    /// 数据源描述（如端点地址），用于日志和摄取审计
+    fn describe(&self) -> String;
+
+    /// 按订阅请求打开更新流；不支持服务端过滤的数据源可以忽略请求中的过滤条件
+    fn subscribe(&mut self, request: SubscribeRequest) -> BoxFuture<'_, Result<UpdateStream>>;
+
+    /// 在不断开当前订阅流的情况下推送新的订阅请求
+    ///
+    /// 返回 false 表示数据源不支持或当前没有可用的订阅流，调用方需要重新订阅。
+    fn update_subscription(&mut self, _request: SubscribeRequest) -> bool {
+        false
+    }
+}
+
+/// Yellowstone gRPC 数据源
+#[derive(Debug, Clone)]
+pub struct YellowstoneSource {
+    config: GrpcConfig,
+    /// 当前订阅流的请求通道，由转发任务写入双向流
+    requests: Option<mpsc::UnboundedSender<SubscribeRequest>>,
+}
+
+impl YellowstoneSource {
+    pub fn new(config: GrpcConfig) -> Self {
+        Self { config, requests: None }
+    }
+}
+
+impl TransactionSource for YellowstoneSource {
+    fn describe(&self) -> String {
+        self.config.endpoint.clone()
+    }
+
+    /// 使用双向订阅：请求流保持打开，用于推送新的过滤条件和按配置的间隔发送 ping
+    fn subscribe(&mut self, request: SubscribeRequest) -> BoxFuture<'_, Result<UpdateStream>> {
+        Box::pin(async move {
+            let (mut sink, stream) = GeyserGrpcClient::build_from_shared(self.config.endpoint.clone())?
+                .tls_config(ClientTlsConfig::new().with_native_roots())?
+                .timeout(Duration::from_secs(self.config.timeout))
+                .connect_timeout(Duration::from_secs(self.config.connect_timeout))
+                .connect()
+                .await?
+                .subscribe_with_request(Some(request))
+                .await?;
+
+            // 替换旧的请求通道后，上一个订阅流的转发任务随之退出
+            let (requests, mut pending) = mpsc::unbounded_channel();
+            self.requests = Some(requests);
+            let ping_enabled = self.config.ping_interval_secs > 0;
+            let interval = Duration::from_secs(self.config.ping_interval_secs.max(1));
+            // 订阅流被丢弃后请求流随之关闭，发送失败时任务退出
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+                let mut ping_id = 0;
+                loop {
+                    let request = tokio::select! {
+                        request = pending.recv() => match request {
+                            Some(request) => request,
+                            None => break,
+                        },
+                        _ = ticker.tick(), if ping_enabled => {
+                            ping_id += 1;
+                            debug!("🏓 发送 ping {}", ping_id);
+                            SubscribeRequest {
+                                ping: Some(SubscribeRequestPing { id: ping_id }),
+                                ..Default::default()
+                            }
+                        }
+                    };
+                    if sink.send(request).await.is_err() {
+                        break;
+                    }
+                }
+            });
+            Ok(Box::pin(stream) as UpdateStream)
+        })
+    }
+
+    fn update_subscription(&mut self, request: SubscribeRequest) -> bool {
+        self.requests.as_ref().is_some_and(|requests| requests.send(request).is_ok())
+    }
+}
@@ -1,56 +1,63 @@
 use anyhow::Result;
+use sha2::{Digest, Sha256};
 use tracing::{debug, warn};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use yellowstone_grpc_proto::prelude::{
     SubscribeUpdateTransaction, TransactionStatusMeta, Message
 };
 use yellowstone_grpc_proto::solana::storage::confirmed_block::TokenBalance;
 
+use std::fmt;
+use std::sync::Arc;
+
+use crate::amount::{Lamports, TokenAmount};
+use crate::config::ParserConfig;
+use crate::database::LabelStorage;
+
+pub use crate::transfer_types::{
+    BalanceSnapshot, FeeInfo, NftTransfer, SolTransfer, StakeEvent, StakeEventKind, SwapRecord, TokenBalanceSnapshot, TokenTransfer,
+};
+
 /// 控制是否显示详细调试信息
 const SHOW_DEBUG_INFO: bool = false;
 
-/// SOL转账记录
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct SolTransfer {
-    /// 交易签名
-    pub signature: String,
-    /// 转出方账户地址
-    pub from: String,
-    /// 接收方账户地址
-    pub to: String,
-    /// 转账金额（lamports单位）
-    pub amount: u64,
-    /// 转出方账户索引
-    pub from_index: usize,
-    /// 接收方账户索引
-    pub to_index: usize,
-    /// 交易时间戳（秒级）
-    pub timestamp: u32,
-    /// 转账类型（如：系统转账、质押等）
-    pub transfer_type: String,
-}
-
-/// 代币转账记录
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct TokenTransfer {
-    /// 交易签名
-    pub signature: String,
-    /// 转出方账户地址
-    pub from: String,
-    /// 接收方账户地址
-    pub to: String,
-    /// 转账金额（最小代币单位）
-    pub amount: u64,
-    /// 代币mint地址
-    pub mint: String,
-    /// 代币小数位数
-    pub decimals: u32,
-    /// 交易时间戳（秒级）
-    pub timestamp: u32,
-    /// 代币程序ID
-    pub program_id: String,
-    /// 转账类型
-    pub transfer_type: String,
+/// System 程序 ID
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+/// Stake 程序 ID
+pub const STAKE_PROGRAM_ID: &str = "Stake11111111111111111111111111111111111111";
+
+/// Metaplex Token Metadata 程序 ID
+pub const TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// 设置或验证集合的 Token Metadata 指令：(指令序号, 元数据账户位置, 集合 mint 位置)
+const COLLECTION_INSTRUCTIONS: &[(u8, usize, usize)] = &[
+    (18, 0, 3), // VerifyCollection
+    (25, 0, 4), // SetAndVerifyCollection
+    (30, 0, 3), // VerifySizedCollectionItem
+    (32, 0, 4), // SetAndVerifySizedCollectionItem
+    (52, 2, 3), // Verify（仅 CollectionV1）
+];
+
+/// Wrapped SOL mint，兑换记录中用它表示原生 SOL
+pub const NATIVE_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// 识别为兑换交易的 DEX 程序（程序ID, 名称）
+pub const DEX_PROGRAMS: &[(&str, &str)] = &[
+    ("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4", "Jupiter v6"),
+    ("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wzSUuXaj5", "Raydium AMM v4"),
+    ("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK", "Raydium CLMM"),
+    ("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", "Orca Whirlpool"),
+    ("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo", "Meteora DLMM"),
+    ("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P", "Pump.fun"),
+];
+
+/// 兑换中的一条腿
+#[derive(Debug, Clone, PartialEq)]
+struct SwapLeg {
+    mint: String,
+    amount: u64,
+    decimals: u32,
 }
 
 /// 账户余额变化信息
@@ -68,10 +75,83 @@ struct AccountBalanceChange {
     post_balance: u64,
 }
 
+/// 转账分类器：为解析出的转账给出更具体的类型（如 "Exchange Deposit"）
+///
+/// 返回 None 时保留默认类型。
+pub trait TransferClassifier: Send + Sync {
+    fn classify_sol(&self, transfer: &SolTransfer) -> Option<String>;
+    fn classify_token(&self, transfer: &TokenTransfer) -> Option<String>;
+}
+
+/// 地址标签提供者，用于在输出中显示已知实体的名称
+pub trait LabelProvider: Send + Sync {
+    fn label(&self, address: &str) -> Option<String>;
+}
+
+impl LabelProvider for LabelStorage {
+    fn label(&self, address: &str) -> Option<String> {
+        self.get_label(address).ok().flatten().map(|label| label.name)
+    }
+}
+
 /// 转账解析器
-pub struct TransferParser;
+///
+/// 持有匹配参数和可选的分类器、标签提供者，启动时构造一次后注入 gRPC 客户端。
+/// 克隆开销很小，分类器和标签提供者在克隆之间共享。
+#[derive(Clone, Default)]
+pub struct TransferParser {
+    config: ParserConfig,
+    classifier: Option<Arc<dyn TransferClassifier>>,
+    labels: Option<Arc<dyn LabelProvider>>,
+}
+
+impl fmt::Debug for TransferParser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransferParser")
+            .field("config", &self.config)
+            .field("classifier", &self.classifier.is_some())
+            .field("labels", &self.labels.is_some())
+            .finish()
+    }
+}
 
 impl TransferParser {
+    /// 使用指定的匹配参数创建解析器
+    pub fn new(config: ParserConfig) -> Self {
+        Self {
+            config,
+            classifier: None,
+            labels: None,
+        }
+    }
+
+    /// 设置转账分类器
+    pub fn with_classifier(mut self, classifier: Arc<dyn TransferClassifier>) -> Self {
+        self.classifier = Some(classifier);
+        self
+    }
+
+    /// 设置地址标签提供者
+    pub fn with_label_provider(mut self, labels: Arc<dyn LabelProvider>) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    /// 是否已设置地址标签提供者
+    pub fn has_label_provider(&self) -> bool {
+        self.labels.is_some()
+    }
+
+    /// 匹配参数
+    pub fn config(&self) -> &ParserConfig {
+        &self.config
+    }
+
+    /// 替换匹配参数（配置热更新），保留分类器和标签提供者
+    pub fn set_config(&mut self, config: ParserConfig) {
+        self.config = config;
+    }
+
     /// 解析交易中的SOL转账
     /// 
     /// # 参数
@@ -80,7 +160,7 @@ impl TransferParser {
     /// 
     /// # 返回
     /// 返回解析出的所有SOL转账记录
-    pub fn parse_sol_transfers(transaction_update: &SubscribeUpdateTransaction, timestamp: u32) -> Result<Vec<SolTransfer>> {
+    pub fn parse_sol_transfers(&self, transaction_update: &SubscribeUpdateTransaction, timestamp: u32) -> Result<Vec<SolTransfer>> {
         let Some(tx_info) = &transaction_update.transaction else {
             debug!("交易信息为空，跳过解析");
             return Ok(vec![]);
@@ -105,10 +185,23 @@ impl TransferParser {
         let account_addresses = Self::build_complete_account_list(message, meta)?;
         
         // 分析余额变化
-        let balance_changes = Self::analyze_balance_changes(&account_addresses, meta)?;
-        
-        // 解析转账
-        let transfers = Self::extract_transfers(&balance_changes, &tx_info.signature, timestamp)?;
+        let mut balance_changes = Self::analyze_balance_changes(&account_addresses, meta)?;
+
+        // 先解码顶层和内部指令中的 System 转账，已确定的金额不再参与余额匹配
+        let signature_str = bs58::encode(&tx_info.signature).into_string();
+        let mut transfers = Self::parse_system_transfers(message, meta, &account_addresses, &signature_str, timestamp);
+        Self::subtract_transfers(&mut balance_changes, &transfers);
+
+        // 解析剩余余额变化中的转账
+        transfers.extend(self.extract_transfers(&balance_changes, &tx_info.signature, timestamp)?);
+
+        if let Some(classifier) = &self.classifier {
+            for transfer in &mut transfers {
+                if let Some(transfer_type) = classifier.classify_sol(transfer) {
+                    transfer.transfer_type = transfer_type;
+                }
+            }
+        }
         
         Ok(transfers)
     }
@@ -121,7 +214,7 @@ impl TransferParser {
     /// 
     /// # 返回
     /// 返回解析出的所有代币转账记录
-    pub fn parse_token_transfers(transaction_update: &SubscribeUpdateTransaction, timestamp: u32) -> Result<Vec<TokenTransfer>> {
+    pub fn parse_token_transfers(&self, transaction_update: &SubscribeUpdateTransaction, timestamp: u32) -> Result<Vec<TokenTransfer>> {
         let Some(tx_info) = &transaction_update.transaction else {
             debug!("交易信息为空，跳过代币转账解析");
             return Ok(vec![]);
@@ -158,17 +251,561 @@ impl TransferParser {
         }
         
         // 分析代币余额变化
-        let token_transfers = Self::analyze_token_balance_changes(
+        let mut token_transfers = self.analyze_token_balance_changes(
             &account_addresses, 
             &meta.pre_token_balances, 
             &meta.post_token_balances, 
             &tx_info.signature,
-            timestamp
+            timestamp,
         )?;
+
+        if let Some(classifier) = &self.classifier {
+            for transfer in &mut token_transfers {
+                if let Some(transfer_type) = classifier.classify_token(transfer) {
+                    transfer.transfer_type = transfer_type;
+                }
+            }
+        }
         
         Ok(token_transfers)
     }
 
+    /// 解析交易中的 SOL 与代币转账，并合并 wSOL 与原生 SOL 的重复记录
+    pub fn parse_transfers(
+        &self,
+        transaction_update: &SubscribeUpdateTransaction,
+        timestamp: u32,
+    ) -> Result<(Vec<SolTransfer>, Vec<TokenTransfer>)> {
+        let mut sol_transfers = self.parse_sol_transfers(transaction_update, timestamp)?;
+        let token_transfers = self.parse_token_transfers(transaction_update, timestamp)?;
+        let token_transfers = Self::unify_wrapped_sol(&mut sol_transfers, token_transfers);
+        Ok((sol_transfers, token_transfers))
+    }
+
+    /// 把 wSOL 代币转账并入 SOL 转账
+    ///
+    /// 包装时 SOL 先转入 wSOL 账户再同步为代币余额，解包时关闭 wSOL 账户把 lamports 退回，
+    /// 两侧会分别解析出一笔 SOL 转账和一笔铸造/销毁，这里只保留 SOL 一侧并标记 `is_wrapped`。
+    /// 不同账户之间的 wSOL 转账改记为所有者之间的 SOL 转账，无法配对的铸造/销毁保持原样。
+    fn unify_wrapped_sol(sol_transfers: &mut Vec<SolTransfer>, token_transfers: Vec<TokenTransfer>) -> Vec<TokenTransfer> {
+        let mut remaining = Vec::with_capacity(token_transfers.len());
+        for transfer in token_transfers {
+            if transfer.mint != NATIVE_SOL_MINT {
+                remaining.push(transfer);
+                continue;
+            }
+
+            let wraps = transfer.from == "MINT/AIRDROP";
+            let unwraps = transfer.to == "BURN/DESTROY";
+            if !wraps && !unwraps {
+                sol_transfers.push(SolTransfer {
+                    from: transfer.sender_wallet().to_string(),
+                    to: transfer.receiver_wallet().to_string(),
+                    signature: transfer.signature,
+                    amount: transfer.amount,
+                    from_index: 0,
+                    to_index: 0,
+                    timestamp: transfer.timestamp,
+                    transfer_type: "Wrapped SOL Transfer".to_string(),
+                    is_wrapped: true,
+                    instruction_path: None,
+                });
+                continue;
+            }
+
+            // SOL 一侧包含新建或关闭账户的租金，金额不小于代币一侧
+            let paired = sol_transfers.iter_mut().find(|sol| {
+                !sol.is_wrapped
+                    && sol.amount >= transfer.amount
+                    && ((wraps && sol.to == transfer.to) || (unwraps && sol.from == transfer.from))
+            });
+            match paired {
+                Some(sol) => sol.is_wrapped = true,
+                None => remaining.push(transfer),
+            }
+        }
+        remaining
+    }
+
+    /// 解析交易的手续费、计算单元消耗和手续费支付者
+    pub fn parse_fee_info(&self, transaction_update: &SubscribeUpdateTransaction) -> Option<FeeInfo> {
+        let tx_info = transaction_update.transaction.as_ref()?;
+        let meta = tx_info.meta.as_ref()?;
+        let fee_payer = tx_info
+            .transaction
+            .as_ref()
+            .and_then(|raw_tx| raw_tx.message.as_ref())
+            .and_then(|message| message.account_keys.first())
+            .map(|key| bs58::encode(key).into_string())
+            .unwrap_or_default();
+
+        Some(FeeInfo {
+            fee_lamports: meta.fee,
+            compute_units: meta.compute_units_consumed,
+            fee_payer,
+        })
+    }
+
+    /// 解析交易中每个账户执行前后的 lamports 余额
+    pub fn parse_balance_snapshots(&self, transaction_update: &SubscribeUpdateTransaction) -> Result<Vec<BalanceSnapshot>> {
+        let Some(tx_info) = &transaction_update.transaction else {
+            return Ok(vec![]);
+        };
+        let (Some(meta), Some(message)) = (
+            &tx_info.meta,
+            tx_info.transaction.as_ref().and_then(|raw_tx| raw_tx.message.as_ref()),
+        ) else {
+            return Ok(vec![]);
+        };
+
+        let account_addresses = Self::build_complete_account_list(message, meta)?;
+        let snapshots = account_addresses
+            .into_iter()
+            .zip(meta.pre_balances.iter().zip(meta.post_balances.iter()))
+            .map(|(address, (pre_balance, post_balance))| BalanceSnapshot {
+                address,
+                pre_balance: *pre_balance,
+                post_balance: *post_balance,
+            })
+            .collect();
+
+        Ok(snapshots)
+    }
+
+    /// 解析交易中每个 (所有者, mint) 的执行后代币余额
+    ///
+    /// 余额取自交易的 `post_token_balances`，只包含本交易涉及的代币账户：同一所有者在本交易中
+    /// 涉及的同一代币的多个账户余额相加，未涉及的其他账户不计入，因此不一定是所有者的全部持仓。
+    /// 交易中被关闭的代币账户记为 0。
+    pub fn parse_token_balance_snapshots(&self, transaction_update: &SubscribeUpdateTransaction) -> Vec<TokenBalanceSnapshot> {
+        let Some(meta) = transaction_update.transaction.as_ref().and_then(|tx| tx.meta.as_ref()) else {
+            return vec![];
+        };
+
+        let raw_amount = |balance: &TokenBalance| {
+            balance
+                .ui_token_amount
+                .as_ref()
+                .map(|ui| (ui.amount.parse::<u64>().unwrap_or(0), ui.decimals))
+                .unwrap_or((0, 0))
+        };
+
+        let mut totals: HashMap<(String, String), (u64, u32)> = HashMap::new();
+        for balance in &meta.post_token_balances {
+            if balance.owner.is_empty() {
+                continue;
+            }
+            let (amount, decimals) = raw_amount(balance);
+            let entry = totals.entry((balance.owner.clone(), balance.mint.clone())).or_insert((0, decimals));
+            entry.0 = entry.0.saturating_add(amount);
+        }
+        for balance in &meta.pre_token_balances {
+            if balance.owner.is_empty() {
+                continue;
+            }
+            let (_, decimals) = raw_amount(balance);
+            totals.entry((balance.owner.clone(), balance.mint.clone())).or_insert((0, decimals));
+        }
+
+        totals
+            .into_iter()
+            .map(|((owner, mint), (amount, decimals))| TokenBalanceSnapshot {
+                owner,
+                mint,
+                amount,
+                decimals,
+            })
+            .collect()
+    }
+
+    /// 解析交易中 Stake 程序的 delegate/deactivate/withdraw 指令（仅顶层指令）
+    pub fn parse_stake_events(&self, transaction_update: &SubscribeUpdateTransaction, timestamp: u32) -> Result<Vec<StakeEvent>> {
+        let Some(tx_info) = &transaction_update.transaction else {
+            return Ok(vec![]);
+        };
+        let (Some(meta), Some(message)) = (
+            &tx_info.meta,
+            tx_info.transaction.as_ref().and_then(|raw_tx| raw_tx.message.as_ref()),
+        ) else {
+            return Ok(vec![]);
+        };
+
+        let account_addresses = Self::build_complete_account_list(message, meta)?;
+        let signature = bs58::encode(&tx_info.signature).into_string();
+
+        let events = message
+            .instructions
+            .iter()
+            .filter(|instruction| {
+                account_addresses
+                    .get(instruction.program_id_index as usize)
+                    .is_some_and(|program_id| program_id == STAKE_PROGRAM_ID)
+            })
+            .filter_map(|instruction| {
+                let accounts: Vec<String> = instruction
+                    .accounts
+                    .iter()
+                    .filter_map(|index| account_addresses.get(*index as usize).cloned())
+                    .collect();
+                Self::decode_stake_instruction(&instruction.data, &accounts, &signature, timestamp)
+            })
+            .collect::<Vec<_>>();
+
+        if !events.is_empty() {
+            debug!("解析到 {} 个质押操作，签名: {}", events.len(), signature);
+        }
+        Ok(events)
+    }
+
+    /// 解码顶层指令和内部指令（CPI）中的 System 转账
+    ///
+    /// 失败交易的指令没有生效，不产生转账。
+    fn parse_system_transfers(
+        message: &Message,
+        meta: &TransactionStatusMeta,
+        account_addresses: &[String],
+        signature: &str,
+        timestamp: u32,
+    ) -> Vec<SolTransfer> {
+        if meta.err.is_some() {
+            return vec![];
+        }
+
+        let top_level = message
+            .instructions
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| (index.to_string(), instruction.program_id_index, &instruction.accounts, &instruction.data));
+        let inner = meta.inner_instructions.iter().flat_map(|group| {
+            group.instructions.iter().enumerate().map(move |(index, instruction)| {
+                (
+                    format!("{}.{}", group.index, index),
+                    instruction.program_id_index,
+                    &instruction.accounts,
+                    &instruction.data,
+                )
+            })
+        });
+
+        top_level
+            .chain(inner)
+            .filter(|(_, program_id_index, _, _)| {
+                account_addresses
+                    .get(*program_id_index as usize)
+                    .is_some_and(|program_id| program_id == SYSTEM_PROGRAM_ID)
+            })
+            .filter_map(|(path, _, accounts, data)| {
+                let (from_index, to_index, amount) = Self::decode_system_transfer(data, accounts)?;
+                Some(SolTransfer {
+                    signature: signature.to_string(),
+                    from: account_addresses.get(from_index)?.clone(),
+                    to: account_addresses.get(to_index)?.clone(),
+                    amount,
+                    from_index,
+                    to_index,
+                    timestamp,
+                    transfer_type: "SOL Transfer".to_string(),
+                    is_wrapped: false,
+                    instruction_path: Some(path),
+                })
+            })
+            .collect()
+    }
+
+    /// 解码单条 System 转账指令，返回 (转出方索引, 接收方索引, lamports)
+    ///
+    /// 前 4 字节为小端 u32 指令序号，Transfer = 2（账户 [转出方, 接收方]），
+    /// TransferWithSeed = 11（账户 [转出方, base, 接收方]），后跟 u64 lamports。
+    fn decode_system_transfer(data: &[u8], accounts: &[u8]) -> Option<(usize, usize, u64)> {
+        let discriminant = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+        let amount = u64::from_le_bytes(data.get(4..12)?.try_into().ok()?);
+        let (from, to) = match discriminant {
+            2 => (*accounts.first()?, *accounts.get(1)?),
+            11 => (*accounts.first()?, *accounts.get(2)?),
+            _ => return None,
+        };
+        (amount > 0 && from != to).then_some((from as usize, to as usize, amount))
+    }
+
+    /// 从余额变化中扣除已由指令确定的转账，去掉扣除后没有变化的账户
+    fn subtract_transfers(balance_changes: &mut Vec<AccountBalanceChange>, transfers: &[SolTransfer]) {
+        for transfer in transfers {
+            for change in balance_changes.iter_mut() {
+                if change.index == transfer.from_index {
+                    change.change += transfer.amount as i64;
+                } else if change.index == transfer.to_index {
+                    change.change -= transfer.amount as i64;
+                }
+            }
+        }
+        balance_changes.retain(|change| change.change != 0);
+    }
+
+    /// 解码单条 Stake 指令
+    ///
+    /// 指令数据以 bincode 编码：前 4 字节为小端 u32 指令序号，
+    /// DelegateStake = 2、Withdraw = 4（后跟 u64 lamports）、Deactivate = 5。
+    /// 账户顺序：
+    /// - DelegateStake: [质押账户, 投票账户, clock, stake_history, config, 质押授权]
+    /// - Withdraw: [质押账户, 接收方, clock, stake_history, 提取授权, ...]
+    /// - Deactivate: [质押账户, clock, 质押授权]
+    fn decode_stake_instruction(data: &[u8], accounts: &[String], signature: &str, timestamp: u32) -> Option<StakeEvent> {
+        let discriminant = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+        let account = |index: usize| accounts.get(index).cloned();
+
+        let (kind, authority, vote_account, recipient, lamports) = match discriminant {
+            2 => (StakeEventKind::Delegate, account(5)?, Some(account(1)?), None, None),
+            4 => {
+                let lamports = u64::from_le_bytes(data.get(4..12)?.try_into().ok()?);
+                (StakeEventKind::Withdraw, account(4)?, None, Some(account(1)?), Some(lamports))
+            }
+            5 => (StakeEventKind::Deactivate, account(2)?, None, None, None),
+            _ => return None,
+        };
+
+        Some(StakeEvent {
+            signature: signature.to_string(),
+            kind,
+            stake_account: account(0)?,
+            authority,
+            vote_account,
+            recipient,
+            lamports,
+            timestamp,
+        })
+    }
+
+    /// 从已解析的代币转账中识别 NFT 转账
+    ///
+    /// 数量为 1、精度为 0 的转账中，交易账户里出现该 mint 的 Metaplex 元数据账户时视为 NFT；
+    /// 同一交易中有集合验证指令（顶层或内部）时记录所属集合。
+    pub fn parse_nft_transfers(
+        &self,
+        transaction_update: &SubscribeUpdateTransaction,
+        token_transfers: &[TokenTransfer],
+    ) -> Result<Vec<NftTransfer>> {
+        let candidates: Vec<&TokenTransfer> = token_transfers
+            .iter()
+            .filter(|transfer| transfer.amount == 1 && transfer.decimals == 0)
+            .collect();
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+        let Some(tx_info) = &transaction_update.transaction else {
+            return Ok(vec![]);
+        };
+        let (Some(meta), Some(message)) = (
+            &tx_info.meta,
+            tx_info.transaction.as_ref().and_then(|raw_tx| raw_tx.message.as_ref()),
+        ) else {
+            return Ok(vec![]);
+        };
+
+        let account_addresses = Self::build_complete_account_list(message, meta)?;
+        let accounts: HashSet<&str> = account_addresses.iter().map(String::as_str).collect();
+
+        // 元数据账户 -> 集合 mint
+        let top_level = message.instructions.iter().map(|instruction| (instruction.program_id_index, &instruction.accounts, &instruction.data));
+        let inner = meta.inner_instructions.iter().flat_map(|group| {
+            group.instructions.iter().map(|instruction| (instruction.program_id_index, &instruction.accounts, &instruction.data))
+        });
+        let collections: HashMap<String, String> = top_level
+            .chain(inner)
+            .filter(|(program_index, _, _)| {
+                account_addresses
+                    .get(*program_index as usize)
+                    .is_some_and(|program_id| program_id == TOKEN_METADATA_PROGRAM_ID)
+            })
+            .filter_map(|(_, account_indexes, data)| {
+                let accounts: Vec<String> = account_indexes
+                    .iter()
+                    .filter_map(|index| account_addresses.get(*index as usize).cloned())
+                    .collect();
+                Self::decode_collection_instruction(data, &accounts)
+            })
+            .collect();
+
+        let nft_transfers: Vec<NftTransfer> = candidates
+            .into_iter()
+            .filter_map(|transfer| {
+                let metadata = Self::find_metadata_account(&transfer.mint, &accounts)?;
+                Some(NftTransfer {
+                    signature: transfer.signature.clone(),
+                    mint: transfer.mint.clone(),
+                    collection: collections.get(&metadata).cloned(),
+                    from: transfer.from.clone(),
+                    to: transfer.to.clone(),
+                    from_owner: transfer.from_owner.clone(),
+                    to_owner: transfer.to_owner.clone(),
+                    timestamp: transfer.timestamp,
+                })
+            })
+            .collect();
+
+        if !nft_transfers.is_empty() {
+            debug!("解析到 {} 笔 NFT 转账", nft_transfers.len());
+        }
+        Ok(nft_transfers)
+    }
+
+    /// 在交易账户中查找 mint 的元数据账户（种子为 `["metadata", 程序ID, mint]` 的 PDA）
+    ///
+    /// 没有曲线运算无法判断哪个 bump 得到的地址不在曲线上，这里从 255 向下逐个计算，
+    /// 出现在交易账户中的候选地址即为元数据账户。
+    fn find_metadata_account(mint: &str, accounts: &HashSet<&str>) -> Option<String> {
+        let program_id = bs58::decode(TOKEN_METADATA_PROGRAM_ID).into_vec().ok()?;
+        let mint = bs58::decode(mint).into_vec().ok()?;
+        (0..=u8::MAX).rev().find_map(|bump| {
+            let hash = Sha256::new()
+                .chain_update(b"metadata")
+                .chain_update(&program_id)
+                .chain_update(&mint)
+                .chain_update([bump])
+                .chain_update(&program_id)
+                .chain_update(b"ProgramDerivedAddress")
+                .finalize();
+            let address = bs58::encode(hash).into_string();
+            accounts.contains(address.as_str()).then_some(address)
+        })
+    }
+
+    /// 解码设置或验证集合的 Token Metadata 指令，返回 (元数据账户, 集合 mint)
+    fn decode_collection_instruction(data: &[u8], accounts: &[String]) -> Option<(String, String)> {
+        let (discriminator, metadata_index, collection_index) =
+            *COLLECTION_INSTRUCTIONS.iter().find(|(discriminator, _, _)| data.first() == Some(discriminator))?;
+        // Verify 的第二个字节为 VerificationArgs，1 表示 CollectionV1
+        if discriminator == 52 && data.get(1) != Some(&1) {
+            return None;
+        }
+        Some((accounts.get(metadata_index)?.clone(), accounts.get(collection_index)?.clone()))
+    }
+
+    /// 解析兑换交易，把同一所有者的转出腿和转入腿配对为兑换记录
+    ///
+    /// 只有调用了 [`DEX_PROGRAMS`] 中程序（顶层或内部指令）的交易才视为兑换，
+    /// 只为交易签名者生成记录，避免把资金池账户的反向变化也当作兑换。
+    pub fn parse_swaps(&self, transaction_update: &SubscribeUpdateTransaction, timestamp: u32) -> Result<Vec<SwapRecord>> {
+        let Some(tx_info) = &transaction_update.transaction else {
+            return Ok(vec![]);
+        };
+        let (Some(meta), Some(message)) = (
+            &tx_info.meta,
+            tx_info.transaction.as_ref().and_then(|raw_tx| raw_tx.message.as_ref()),
+        ) else {
+            return Ok(vec![]);
+        };
+
+        let account_addresses = Self::build_complete_account_list(message, meta)?;
+
+        // 顶层和内部指令中调用到的第一个已知 DEX 程序
+        let invoked_programs = message
+            .instructions
+            .iter()
+            .map(|instruction| instruction.program_id_index)
+            .chain(
+                meta.inner_instructions
+                    .iter()
+                    .flat_map(|inner| inner.instructions.iter().map(|instruction| instruction.program_id_index)),
+            );
+        let Some((program_id, program)) = invoked_programs
+            .filter_map(|index| account_addresses.get(index as usize))
+            .find_map(|address| DEX_PROGRAMS.iter().find(|(id, _)| id == address))
+        else {
+            return Ok(vec![]);
+        };
+
+        let signature = bs58::encode(&tx_info.signature).into_string();
+
+        // 按 (所有者, mint) 汇总代币余额变化
+        let mut token_deltas: HashMap<&str, HashMap<String, (i128, u32)>> = HashMap::new();
+        for (balances, sign) in [(&meta.post_token_balances, 1i128), (&meta.pre_token_balances, -1i128)] {
+            for balance in balances.iter() {
+                let Some(ui) = balance.ui_token_amount.as_ref() else {
+                    continue;
+                };
+                let amount = ui.amount.parse::<u64>().unwrap_or(0) as i128;
+                let entry = token_deltas
+                    .entry(balance.owner.as_str())
+                    .or_default()
+                    .entry(balance.mint.clone())
+                    .or_insert((0, ui.decimals));
+                entry.0 += sign * amount;
+            }
+        }
+
+        let num_signers = message
+            .header
+            .as_ref()
+            .map(|header| header.num_required_signatures as usize)
+            .unwrap_or(1);
+        let empty = HashMap::new();
+
+        let swaps = account_addresses
+            .iter()
+            .take(num_signers)
+            .enumerate()
+            .filter_map(|(index, owner)| {
+                let pre = *meta.pre_balances.get(index)? as i128;
+                let post = *meta.post_balances.get(index)? as i128;
+                // 手续费支付者的 SOL 变化扣除手续费的影响
+                let fee = if index == 0 { meta.fee as i128 } else { 0 };
+                let legs = token_deltas.get(owner.as_str()).unwrap_or(&empty);
+                let (input, output) = Self::pair_swap_legs(legs, post - pre + fee)?;
+                Some(SwapRecord {
+                    signature: signature.clone(),
+                    owner: owner.clone(),
+                    program_id: program_id.to_string(),
+                    program: program.to_string(),
+                    token_in: input.mint,
+                    amount_in: input.amount,
+                    decimals_in: input.decimals,
+                    token_out: output.mint,
+                    amount_out: output.amount,
+                    decimals_out: output.decimals,
+                    timestamp,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if !swaps.is_empty() {
+            debug!("解析到 {} 笔兑换（{}），签名: {}", swaps.len(), program, signature);
+        }
+        Ok(swaps)
+    }
+
+    /// 将同一所有者的余额变化配对为 (付出, 收到) 两条腿
+    ///
+    /// 代币余额中恰好一个 mint 减少、一个 mint 增加时直接配对；只有一侧代币腿时，
+    /// 用原生 SOL 余额变化补齐另一侧（临时 wSOL 账户在交易内创建并关闭，
+    /// 不会出现在代币余额中）。中间代币净变化为 0，不影响多跳路由；
+    /// 其余多腿情况无法确定配对关系，不生成记录。
+    fn pair_swap_legs(token_deltas: &HashMap<String, (i128, u32)>, sol_delta: i128) -> Option<(SwapLeg, SwapLeg)> {
+        let leg = |mint: &str, delta: i128, decimals: u32| SwapLeg {
+            mint: mint.to_string(),
+            amount: delta.unsigned_abs().min(u64::MAX as u128) as u64,
+            decimals,
+        };
+        let outgoing: Vec<SwapLeg> = token_deltas
+            .iter()
+            .filter(|(_, (delta, _))| *delta < 0)
+            .map(|(mint, (delta, decimals))| leg(mint, *delta, *decimals))
+            .collect();
+        let incoming: Vec<SwapLeg> = token_deltas
+            .iter()
+            .filter(|(_, (delta, _))| *delta > 0)
+            .map(|(mint, (delta, decimals))| leg(mint, *delta, *decimals))
+            .collect();
+        let has_wsol_leg = outgoing.iter().chain(incoming.iter()).any(|leg| leg.mint == NATIVE_SOL_MINT);
+        let sol_leg = || leg(NATIVE_SOL_MINT, sol_delta, 9);
+
+        match (outgoing.as_slice(), incoming.as_slice()) {
+            ([input], [output]) => Some((input.clone(), output.clone())),
+            ([input], []) if sol_delta > 0 && !has_wsol_leg => Some((input.clone(), sol_leg())),
+            ([], [output]) if sol_delta < 0 && !has_wsol_leg => Some((sol_leg(), output.clone())),
+            _ => None,
+        }
+    }
+
     /// 构建完整的账户地址列表
     /// 
     /// 将 accountKeys 和通过地址查找表加载的地址合并
@@ -250,6 +887,7 @@ impl TransferParser {
 
     /// 从余额变化中提取转账信息
     fn extract_transfers(
+        &self,
         balance_changes: &[AccountBalanceChange],
         signature: &[u8],
         timestamp: u32,
@@ -303,8 +941,8 @@ impl TransferParser {
                 
                 let receive_amount = receiver.change as u64;
                 
-                // 精确匹配：允许5%的误差（考虑手续费）
-                if Self::is_matching_transfer(send_amount, receive_amount) {
+                // 精确匹配：允许手续费造成的误差
+                if self.is_matching_transfer(send_amount, receive_amount) {
                     transfers.push(SolTransfer {
                         signature: signature_str.clone(),
                         from: sender.address.clone(),
@@ -313,6 +951,9 @@ impl TransferParser {
                         from_index: sender.index,
                         to_index: receiver.index,
                         timestamp,
+                        transfer_type: "SOL Transfer".to_string(),
+                        is_wrapped: false,
+                        instruction_path: None,
                     });
 
                     used_senders[i] = true;
@@ -320,10 +961,10 @@ impl TransferParser {
 
                     if SHOW_DEBUG_INFO {
                         debug!(
-                            "精确匹配转账: {} -> {} ({:.9} SOL)",
+                            "精确匹配转账: {} -> {} ({} SOL)",
                             &sender.address[..8],
                             &receiver.address[..8],
-                            receive_amount as f64 / 1_000_000_000.0
+                            Lamports(receive_amount)
                         );
                     }
                     break;
@@ -346,7 +987,7 @@ impl TransferParser {
                 if !used_receivers[j] {
                     let receive_amount = receiver.change as u64;
                     // 接收金额不能超过发送金额的150%（考虑可能的利息、奖励等）
-                    if receive_amount <= send_amount * 15 / 10 && receive_amount >= 100_000 { // 至少0.0001 SOL
+                    if receive_amount <= send_amount * 15 / 10 && receive_amount >= self.config.dust_floor_lamports {
                         candidate_receivers.push((j, receiver, receive_amount));
                     }
                 }
@@ -357,7 +998,7 @@ impl TransferParser {
             
             // 贪心匹配：尽量用完发送金额
             for (j, receiver, receive_amount) in candidate_receivers {
-                if remaining_amount < 100_000 { // 剩余金额太少就停止
+                if remaining_amount < self.config.dust_floor_lamports { // 剩余金额太少就停止
                     break;
                 }
                 
@@ -370,6 +1011,9 @@ impl TransferParser {
                         from_index: sender.index,
                         to_index: receiver.index,
                         timestamp,
+                        transfer_type: "SOL Transfer".to_string(),
+                        is_wrapped: false,
+                        instruction_path: None,
                     });
 
                     used_receivers[j] = true;
@@ -377,11 +1021,11 @@ impl TransferParser {
 
                     if SHOW_DEBUG_INFO {
                         debug!(
-                            "一对多转账: {} -> {} ({:.9} SOL, 剩余{:.9} SOL)",
+                            "一对多转账: {} -> {} ({} SOL, 剩余{} SOL)",
                             &sender.address[..8],
                             &receiver.address[..8],
-                            receive_amount as f64 / 1_000_000_000.0,
-                            remaining_amount as f64 / 1_000_000_000.0
+                            Lamports(receive_amount),
+                            Lamports(remaining_amount)
                         );
                     }
                 }
@@ -406,7 +1050,7 @@ impl TransferParser {
             for (i, sender) in senders.iter().enumerate() {
                 if !used_senders[i] {
                     let send_amount = (-sender.change) as u64;
-                    if send_amount >= 100_000 { // 至少0.0001 SOL
+                    if send_amount >= self.config.dust_floor_lamports {
                         candidate_senders.push((i, sender, send_amount));
                     }
                 }
@@ -417,7 +1061,7 @@ impl TransferParser {
             
             // 尝试用多个发送方组合成这个接收金额
             for (i, sender, send_amount) in candidate_senders {
-                if remaining_needed < 100_000 {
+                if remaining_needed < self.config.dust_floor_lamports {
                     break;
                 }
                 
@@ -431,17 +1075,20 @@ impl TransferParser {
                     from_index: sender.index,
                     to_index: receiver.index,
                     timestamp,
+                    transfer_type: "SOL Transfer".to_string(),
+                    is_wrapped: false,
+                    instruction_path: None,
                 });
 
                 remaining_needed = remaining_needed.saturating_sub(used_amount.min(remaining_needed));
 
                                     if SHOW_DEBUG_INFO {
                         debug!(
-                            "多对一转账: {} -> {} ({:.9} SOL, 还需{:.9} SOL)",
+                            "多对一转账: {} -> {} ({} SOL, 还需{} SOL)",
                             &sender.address[..8],
                             &receiver.address[..8],
-                            used_amount.min(remaining_needed) as f64 / 1_000_000_000.0,
-                            remaining_needed as f64 / 1_000_000_000.0
+                            Lamports(used_amount.min(remaining_needed)),
+                            Lamports(remaining_needed)
                         );
                     }
                 
@@ -458,10 +1105,10 @@ impl TransferParser {
         
         // 4. 处理完全无法匹配的情况：记录所有剩余的显著变化
         for (j, receiver) in receivers.iter().enumerate() {
-            if !used_receivers[j] && receiver.change > 1_000_000 { // 超过0.001 SOL
+            if !used_receivers[j] && receiver.change > self.config.inferred_transfer_floor_lamports as i64 {
                 // 寻找任意一个未完全使用的发送方
                 if let Some((_i, sender)) = senders.iter().enumerate()
-                    .find(|(i, s)| !used_senders[*i] && (-s.change) as u64 > 100_000) {
+                    .find(|(i, s)| !used_senders[*i] && (-s.change) as u64 > self.config.dust_floor_lamports) {
                     
                     transfers.push(SolTransfer {
                         signature: signature_str.clone(),
@@ -471,14 +1118,17 @@ impl TransferParser {
                         from_index: sender.index,
                         to_index: receiver.index,
                         timestamp,
+                        transfer_type: "SOL Transfer".to_string(),
+                        is_wrapped: false,
+                        instruction_path: None,
                     });
 
                     if SHOW_DEBUG_INFO {
                         debug!(
-                            "推测转账: {} -> {} ({:.9} SOL)",
+                            "推测转账: {} -> {} ({} SOL)",
                             &sender.address[..8],
                             &receiver.address[..8],
-                            receiver.change as f64 / 1_000_000_000.0
+                            Lamports(receiver.change as u64)
                         );
                     }
                 }
@@ -491,24 +1141,22 @@ impl TransferParser {
     /// 判断两个余额变化是否为匹配的转账对
     /// 
     /// 考虑到gas费用的影响，允许一定的偏差
-    fn is_matching_transfer(send_amount: u64, receive_amount: u64) -> bool {
+    fn is_matching_transfer(&self, send_amount: u64, receive_amount: u64) -> bool {
         // 完全匹配
         if send_amount == receive_amount {
             return true;
         }
 
         // 发送金额大于接收金额（考虑gas费用）
-        // 允许的gas费用范围：最多0.01 SOL
-        const MAX_GAS_FEE: u64 = 10_000_000; // 0.01 SOL in lamports
-        
-        if send_amount > receive_amount && (send_amount - receive_amount) <= MAX_GAS_FEE {
+        if send_amount > receive_amount && (send_amount - receive_amount) <= self.config.max_gas_fee_lamports {
             return true;
         }
 
-        // 对于大额转账，允许更大的gas费用偏差（但比例不超过1%）
+        // 对于大额转账，允许更大的gas费用偏差（按比例）
         if send_amount > receive_amount {
             let difference = send_amount - receive_amount;
-            let max_allowed_diff = (send_amount / 100).max(MAX_GAS_FEE); // 最大1%或0.01 SOL
+            let proportional = (send_amount as f64 * self.config.match_tolerance_percent / 100.0) as u64;
+            let max_allowed_diff = proportional.max(self.config.max_gas_fee_lamports);
             return difference <= max_allowed_diff;
         }
 
@@ -517,6 +1165,7 @@ impl TransferParser {
 
     /// 分析代币余额变化
     fn analyze_token_balance_changes(
+        &self,
         account_addresses: &[String],
         pre_token_balances: &[TokenBalance],
         post_token_balances: &[TokenBalance],
@@ -544,6 +1193,23 @@ impl TransferParser {
             }
         }
 
+        // 记录每种代币所属的代币程序（Token / Token-2022）
+        let token_program_ids: HashMap<String, String> = pre_token_balances
+            .iter()
+            .chain(post_token_balances.iter())
+            .filter(|tb| !tb.program_id.is_empty())
+            .map(|tb| (tb.mint.clone(), tb.program_id.clone()))
+            .collect();
+
+        // 代币账户索引到所有者钱包的映射（账户关闭后只有 pre 中有所有者）
+        let owners: HashMap<u32, String> = pre_token_balances
+            .iter()
+            .chain(post_token_balances.iter())
+            .filter(|tb| !tb.owner.is_empty())
+            .map(|tb| (tb.account_index, tb.owner.clone()))
+            .collect();
+        let owner_of = |index: u32| owners.get(&index).cloned();
+
         // 创建映射表便于比较
         let pre_map: HashMap<(u32, String), &TokenBalance> = pre_token_balances
             .iter()
@@ -652,8 +1318,9 @@ impl TransferParser {
                 let to_amount = *to_change as u64;
                 let from_amount = (-from_change) as u64;
                 
-                // 允许最多10倍的误差（考虑复杂的DeFi操作、手续费、slippage等）
-                if to_amount >= (from_amount / 10) && to_amount <= (from_amount * 10) {
+                // 允许一定倍数的误差（考虑复杂的DeFi操作、手续费、slippage等）
+                let ratio = to_amount as f64 / from_amount as f64;
+                if ratio >= 1.0 / self.config.max_token_ratio && ratio <= self.config.max_token_ratio {
                     let from_address = account_addresses
                         .get(*from_index as usize)
                         .map(|s| s.clone())
@@ -673,6 +1340,10 @@ impl TransferParser {
                         mint: mint.clone(),
                         decimals: *decimals,
                         timestamp,
+                        program_id: token_program_ids.get(&mint).cloned().unwrap_or_default(),
+                        transfer_type: "Token Transfer".to_string(),
+                        from_owner: owner_of(*from_index),
+                        to_owner: owner_of(*to_index),
                     });
 
                     if SHOW_DEBUG_INFO {
@@ -705,8 +1376,8 @@ impl TransferParser {
                             to_amount as f64 / from_amount as f64
                         };
                         
-                        // 允许最多10倍的差异（非常宽松）
-                        if ratio <= 10.0 && ratio < best_ratio {
+                        // 允许一定倍数的差异（默认10倍，非常宽松）
+                        if ratio <= self.config.max_token_ratio && ratio < best_ratio {
                             best_ratio = ratio;
                             best_match = Some((i, *from_index, from_amount));
                         }
@@ -733,6 +1404,10 @@ impl TransferParser {
                             mint: mint.clone(),
                             decimals: *decimals,
                             timestamp,
+                            program_id: token_program_ids.get(&mint).cloned().unwrap_or_default(),
+                            transfer_type: "Token Transfer".to_string(),
+                            from_owner: owner_of(from_index),
+                            to_owner: owner_of(*to_index),
                         });
 
                         if SHOW_DEBUG_INFO {
@@ -768,6 +1443,10 @@ impl TransferParser {
                                 mint: mint.clone(),
                                 decimals: *decimals,
                                 timestamp,
+                                program_id: token_program_ids.get(&mint).cloned().unwrap_or_default(),
+                                transfer_type: "Token Transfer".to_string(),
+                                from_owner: None,
+                                to_owner: owner_of(*to_index),
                             });
                         }
                     }
@@ -799,6 +1478,10 @@ impl TransferParser {
                                 mint: mint.clone(),
                                 decimals: *decimals,
                                 timestamp,
+                                program_id: token_program_ids.get(&mint).cloned().unwrap_or_default(),
+                                transfer_type: "Token Transfer".to_string(),
+                                from_owner: owner_of(*from_index),
+                                to_owner: None,
                             });
                         }
                     }
@@ -809,8 +1492,16 @@ impl TransferParser {
         Ok(transfers)
     }
 
+    /// 输出中显示的地址：有标签时显示标签，否则显示地址前 8 位
+    fn display_name(&self, address: &str) -> String {
+        self.labels
+            .as_ref()
+            .and_then(|labels| labels.label(address))
+            .unwrap_or_else(|| address.chars().take(8).collect())
+    }
+
     /// 打印转账信息（用于调试）
-    pub fn print_transfers(transfers: &[SolTransfer]) {
+    pub fn print_transfers(&self, transfers: &[SolTransfer]) {
         if transfers.is_empty() {
             if SHOW_DEBUG_INFO {
                 debug!("该交易中未发现SOL转账");
@@ -820,15 +1511,15 @@ impl TransferParser {
 
         println!("🔄 发现 {} 笔SOL转账:", transfers.len());
         for (i, transfer) in transfers.iter().enumerate() {
-            let sol_amount = transfer.amount as f64 / 1_000_000_000.0;
+            let sol_amount = Lamports(transfer.amount);
             let timestamp = chrono::DateTime::from_timestamp(transfer.timestamp as i64, 0)
                 .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
                 .unwrap_or_else(|| "未知时间".to_string());
             println!(
-                "  {}. {} -> {} : {:.9} SOL (时间: {})",
+                "  {}. {} -> {} : {} SOL (时间: {})",
                 i + 1,
-                &transfer.from[..8],
-                &transfer.to[..8],
+                self.display_name(&transfer.from),
+                self.display_name(&transfer.to),
                 sol_amount,
                 timestamp
             );
@@ -842,12 +1533,12 @@ impl TransferParser {
 
     /// 检查是否包含大额转账（超过指定阈值，以SOL为单位）
     pub fn has_large_transfer(transfers: &[SolTransfer], threshold_sol: f64) -> bool {
-        let threshold_lamports = (threshold_sol * 1_000_000_000.0) as u64;
-        transfers.iter().any(|t| t.amount >= threshold_lamports)
+        let threshold = Lamports::from_sol(threshold_sol);
+        transfers.iter().any(|t| Lamports(t.amount) >= threshold)
     }
 
     /// 打印代币转账信息
-    pub fn print_token_transfers(transfers: &[TokenTransfer]) {
+    pub fn print_token_transfers(&self, transfers: &[TokenTransfer]) {
         if transfers.is_empty() {
             if SHOW_DEBUG_INFO {
                 debug!("该交易中未发现代币转账");
@@ -857,7 +1548,7 @@ impl TransferParser {
 
         println!("🪙 发现 {} 笔代币转账:", transfers.len());
         for (i, transfer) in transfers.iter().enumerate() {
-            let token_amount = transfer.amount as f64 / 10_u64.pow(transfer.decimals) as f64;
+            let token_amount = TokenAmount::new(transfer.amount, transfer.decimals as u8);
             let timestamp = chrono::DateTime::from_timestamp(transfer.timestamp as i64, 0)
                 .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
                 .unwrap_or_else(|| "未知时间".to_string());
@@ -865,26 +1556,26 @@ impl TransferParser {
             // 判断转账类型
             if transfer.from == "MINT/AIRDROP" {
                 println!(
-                    "  {}. 💰 MINT/空投 -> {} : {:.9} tokens (时间: {})",
+                    "  {}. 💰 MINT/空投 -> {} : {} tokens (时间: {})",
                     i + 1,
-                    &transfer.to[..8],
+                    self.display_name(&transfer.to),
                     token_amount,
                     timestamp
                 );
             } else if transfer.to == "BURN/DESTROY" {
                 println!(
-                    "  {}. 🔥 {} -> BURN/销毁 : {:.9} tokens (时间: {})",
+                    "  {}. 🔥 {} -> BURN/销毁 : {} tokens (时间: {})",
                     i + 1,
-                    &transfer.from[..8],
+                    self.display_name(&transfer.from),
                     token_amount,
                     timestamp
                 );
             } else {
                 println!(
-                    "  {}. {} -> {} : {:.9} tokens (时间: {})",
+                    "  {}. {} -> {} : {} tokens (时间: {})",
                     i + 1,
-                    &transfer.from[..8],
-                    &transfer.to[..8],
+                    self.display_name(&transfer.from),
+                    self.display_name(&transfer.to),
                     token_amount,
                     timestamp
                 );
@@ -915,17 +1606,240 @@ mod tests {
 
     #[test]
     fn test_is_matching_transfer() {
+        let parser = TransferParser::default();
+
         // 完全匹配
-        assert!(TransferParser::is_matching_transfer(1_000_000_000, 1_000_000_000));
+        assert!(parser.is_matching_transfer(1_000_000_000, 1_000_000_000));
         
         // 考虑gas费用的匹配
-        assert!(TransferParser::is_matching_transfer(1_005_000, 1_000_000)); // 0.005 SOL gas
+        assert!(parser.is_matching_transfer(1_005_000, 1_000_000)); // 0.005 SOL gas
         
         // gas费用过高，不匹配
-        assert!(!TransferParser::is_matching_transfer(1_020_000_000, 1_000_000_000)); // 0.02 SOL gas
+        assert!(!parser.is_matching_transfer(1_020_000_000, 1_000_000_000)); // 0.02 SOL gas
         
         // 接收金额大于发送金额，不匹配
-        assert!(!TransferParser::is_matching_transfer(1_000_000, 1_005_000));
+        assert!(!parser.is_matching_transfer(1_000_000, 1_005_000));
+
+        // 放宽比例后允许更大的差额
+        let loose = TransferParser::new(ParserConfig { match_tolerance_percent: 5.0, ..ParserConfig::default() });
+        assert!(loose.is_matching_transfer(1_020_000_000, 1_000_000_000));
+    }
+
+    struct FixedLabels;
+
+    impl LabelProvider for FixedLabels {
+        fn label(&self, address: &str) -> Option<String> {
+            (address == "exchange-wallet").then(|| "Exchange".to_string())
+        }
+    }
+
+    #[test]
+    fn test_injected_label_provider() {
+        let parser = TransferParser::default();
+        assert!(!parser.has_label_provider());
+        assert_eq!(parser.display_name("exchange-wallet"), "exchange");
+
+        let parser = parser.with_label_provider(Arc::new(FixedLabels));
+        assert!(parser.has_label_provider());
+        assert_eq!(parser.display_name("exchange-wallet"), "Exchange");
+        assert_eq!(parser.display_name("someone-else"), "someone-");
+    }
+
+    #[test]
+    fn test_unify_wrapped_sol() {
+        let sol = |from: &str, to: &str, amount: u64| SolTransfer {
+            signature: "sig".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            from_index: 0,
+            to_index: 1,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
+        };
+        let token = |from: &str, to: &str, amount: u64, mint: &str| TokenTransfer {
+            signature: "sig".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            mint: mint.to_string(),
+            decimals: 9,
+            timestamp: 0,
+            program_id: String::new(),
+            transfer_type: "Token Transfer".to_string(),
+            from_owner: None,
+            to_owner: None,
+        };
+        let usdc = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+        // 包装（新建账户含租金）和解包各只保留 SOL 一侧
+        let mut sol_transfers = vec![sol("wallet", "wsol-account", 1_002_039_280), sol("wsol-account2", "wallet", 502_039_280)];
+        let remaining = TransferParser::unify_wrapped_sol(
+            &mut sol_transfers,
+            vec![
+                token("MINT/AIRDROP", "wsol-account", 1_000_000_000, NATIVE_SOL_MINT),
+                token("wsol-account2", "BURN/DESTROY", 500_000_000, NATIVE_SOL_MINT),
+                token("a", "b", 10, usdc),
+            ],
+        );
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].mint, usdc);
+        assert!(sol_transfers.iter().all(|transfer| transfer.is_wrapped));
+
+        // 账户之间的 wSOL 转账改记为 SOL 转账，无法配对的铸造保持原样
+        let mut sol_transfers = Vec::new();
+        let remaining = TransferParser::unify_wrapped_sol(
+            &mut sol_transfers,
+            vec![
+                token("wsol-a", "wsol-b", 7, NATIVE_SOL_MINT),
+                token("MINT/AIRDROP", "wsol-c", 9, NATIVE_SOL_MINT),
+            ],
+        );
+        assert_eq!(sol_transfers.len(), 1);
+        assert_eq!((sol_transfers[0].amount, sol_transfers[0].is_wrapped), (7, true));
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].to, "wsol-c");
+    }
+
+    #[test]
+    fn test_pair_swap_legs() {
+        let usdc = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+        let bonk = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+        let deltas = |legs: &[(&str, i128, u32)]| -> HashMap<String, (i128, u32)> {
+            legs.iter().map(|(mint, delta, decimals)| (mint.to_string(), (*delta, *decimals))).collect()
+        };
+
+        // 代币换代币，多跳路由的中间代币净变化为 0
+        let (input, output) =
+            TransferParser::pair_swap_legs(&deltas(&[(usdc, -5_000_000, 6), (bonk, 120_000, 5), ("mid", 0, 9)]), -5_000)
+                .unwrap();
+        assert_eq!((input.mint.as_str(), input.amount), (usdc, 5_000_000));
+        assert_eq!((output.mint.as_str(), output.amount), (bonk, 120_000));
+
+        // 用 SOL 买入代币：临时 wSOL 账户不在代币余额中，用 SOL 余额变化补齐
+        let (input, output) = TransferParser::pair_swap_legs(&deltas(&[(usdc, 1_000_000, 6)]), -10_000_000).unwrap();
+        assert_eq!((input.mint.as_str(), input.amount, input.decimals), (NATIVE_SOL_MINT, 10_000_000, 9));
+        assert_eq!(output.mint, usdc);
+
+        // 卖出代币换 SOL
+        let (input, output) = TransferParser::pair_swap_legs(&deltas(&[(usdc, -1_000_000, 6)]), 9_000_000).unwrap();
+        assert_eq!(input.mint, usdc);
+        assert_eq!((output.mint.as_str(), output.amount), (NATIVE_SOL_MINT, 9_000_000));
+
+        // 只有单边变化或多腿无法配对
+        assert!(TransferParser::pair_swap_legs(&deltas(&[(usdc, 1_000_000, 6)]), 0).is_none());
+        assert!(TransferParser::pair_swap_legs(&deltas(&[(usdc, -1, 6), (bonk, -1, 5), ("other", 2, 9)]), 0).is_none());
+    }
+
+    #[test]
+    fn test_decode_stake_instructions() {
+        let accounts: Vec<String> = (0..6).map(|i| format!("account{}", i)).collect();
+
+        let delegate = TransferParser::decode_stake_instruction(&2u32.to_le_bytes(), &accounts, "sig", 1).unwrap();
+        assert_eq!(delegate.kind, StakeEventKind::Delegate);
+        assert_eq!(delegate.stake_account, "account0");
+        assert_eq!(delegate.vote_account.as_deref(), Some("account1"));
+        assert_eq!(delegate.authority, "account5");
+
+        let mut withdraw_data = 4u32.to_le_bytes().to_vec();
+        withdraw_data.extend_from_slice(&2_000_000_000u64.to_le_bytes());
+        let withdraw = TransferParser::decode_stake_instruction(&withdraw_data, &accounts, "sig", 1).unwrap();
+        assert_eq!(withdraw.kind, StakeEventKind::Withdraw);
+        assert_eq!(withdraw.recipient.as_deref(), Some("account1"));
+        assert_eq!(withdraw.authority, "account4");
+        assert_eq!(withdraw.lamports, Some(2_000_000_000));
+
+        let deactivate = TransferParser::decode_stake_instruction(&5u32.to_le_bytes(), &accounts[..3], "sig", 1).unwrap();
+        assert_eq!(deactivate.kind, StakeEventKind::Deactivate);
+        assert_eq!(deactivate.authority, "account2");
+
+        // 其他指令、数据或账户不完整时忽略
+        assert!(TransferParser::decode_stake_instruction(&0u32.to_le_bytes(), &accounts, "sig", 1).is_none());
+        assert!(TransferParser::decode_stake_instruction(&4u32.to_le_bytes(), &accounts, "sig", 1).is_none());
+        assert!(TransferParser::decode_stake_instruction(&2u32.to_le_bytes(), &accounts[..2], "sig", 1).is_none());
+    }
+
+    #[test]
+    fn test_decode_system_transfer() {
+        let data = |discriminant: u32, lamports: u64| {
+            let mut data = discriminant.to_le_bytes().to_vec();
+            data.extend_from_slice(&lamports.to_le_bytes());
+            data
+        };
+
+        assert_eq!(TransferParser::decode_system_transfer(&data(2, 500), &[3, 7]), Some((3, 7, 500)));
+        assert_eq!(TransferParser::decode_system_transfer(&data(11, 500), &[3, 4, 7]), Some((3, 7, 500)));
+
+        // 其他指令、零金额、自转账和数据不完整时忽略
+        assert!(TransferParser::decode_system_transfer(&data(0, 500), &[3, 7]).is_none());
+        assert!(TransferParser::decode_system_transfer(&data(2, 0), &[3, 7]).is_none());
+        assert!(TransferParser::decode_system_transfer(&data(2, 500), &[3, 3]).is_none());
+        assert!(TransferParser::decode_system_transfer(&2u32.to_le_bytes(), &[3, 7]).is_none());
+        assert!(TransferParser::decode_system_transfer(&data(11, 500), &[3, 4]).is_none());
+    }
+
+    #[test]
+    fn test_subtract_decoded_transfers() {
+        let change = |index: usize, change: i64| AccountBalanceChange {
+            index,
+            address: format!("account{}", index),
+            change,
+            pre_balance: 0,
+            post_balance: 0,
+        };
+        let mut changes = vec![change(0, -1_005_000), change(1, 1_000_000), change(2, 3_000)];
+        let decoded = SolTransfer {
+            signature: "sig".to_string(),
+            from: "account0".to_string(),
+            to: "account1".to_string(),
+            amount: 1_000_000,
+            from_index: 0,
+            to_index: 1,
+            timestamp: 0,
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: Some("1.0".to_string()),
+        };
+
+        TransferParser::subtract_transfers(&mut changes, &[decoded]);
+        // 接收方被完全解释，转出方只剩手续费和未解释的部分
+        assert_eq!(changes.iter().map(|c| (c.index, c.change)).collect::<Vec<_>>(), vec![(0, -5_000), (2, 3_000)]);
+    }
+
+    #[test]
+    fn test_find_metadata_account_and_collection() {
+        let mint = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+        // 随便取一个 bump 构造候选地址，不在交易账户中时找不到
+        let program_id = bs58::decode(TOKEN_METADATA_PROGRAM_ID).into_vec().unwrap();
+        let hash = Sha256::new()
+            .chain_update(b"metadata")
+            .chain_update(&program_id)
+            .chain_update(bs58::decode(mint).into_vec().unwrap())
+            .chain_update([254u8])
+            .chain_update(&program_id)
+            .chain_update(b"ProgramDerivedAddress")
+            .finalize();
+        let metadata = bs58::encode(hash).into_string();
+        assert_eq!(TransferParser::find_metadata_account(mint, &HashSet::from(["other"])), None);
+        assert_eq!(
+            TransferParser::find_metadata_account(mint, &HashSet::from(["other", metadata.as_str()])),
+            Some(metadata.clone())
+        );
+
+        let accounts: Vec<String> = ["meta", "authority", "payer", "collection", "x", "y"].iter().map(|a| a.to_string()).collect();
+        assert_eq!(
+            TransferParser::decode_collection_instruction(&[18], &accounts),
+            Some(("meta".to_string(), "collection".to_string()))
+        );
+        // Verify 只接受 CollectionV1
+        assert_eq!(
+            TransferParser::decode_collection_instruction(&[52, 1], &accounts),
+            Some(("payer".to_string(), "collection".to_string()))
+        );
+        assert_eq!(TransferParser::decode_collection_instruction(&[52, 0], &accounts), None);
+        assert_eq!(TransferParser::decode_collection_instruction(&[3], &accounts), None);
     }
 
     #[test]
@@ -938,6 +1852,9 @@ mod tests {
             from_index: 0,
             to_index: 1,
             timestamp: 1640995200, // 2022-01-01 00:00:00 UTC
+            transfer_type: "SOL Transfer".to_string(),
+            is_wrapped: false,
+            instruction_path: None,
         };
 
         println!("{:?}", transfer);
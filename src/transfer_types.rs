@@ -0,0 +1,404 @@
+//! 转账记录类型
+//!
+//! 与 gRPC 解析逻辑解耦的纯数据结构，存储层和 API 层可以在不启用
+//! `ingest` 特性的情况下直接使用。
+
+/// SOL转账记录
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SolTransfer {
+    /// 交易签名
+    pub signature: String,
+    /// 转出方账户地址
+    pub from: String,
+    /// 接收方账户地址
+    pub to: String,
+    /// 转账金额（lamports单位）
+    pub amount: u64,
+    /// 转出方账户索引
+    pub from_index: usize,
+    /// 接收方账户索引
+    pub to_index: usize,
+    /// 交易时间戳（秒级）
+    pub timestamp: u32,
+    /// 转账类型（如：系统转账、质押等）
+    pub transfer_type: String,
+    /// 是否为 wSOL 包装、解包或 wSOL 转账合并而来
+    #[serde(default)]
+    pub is_wrapped: bool,
+    /// 由 System 转账指令得到时的指令路径（顶层序号，内部指令为 `顶层序号.内部序号`），余额匹配推断的转账为空
+    #[serde(default)]
+    pub instruction_path: Option<String>,
+}
+
+/// 代币转账记录
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TokenTransfer {
+    /// 交易签名
+    pub signature: String,
+    /// 转出方代币账户地址
+    pub from: String,
+    /// 接收方代币账户地址
+    pub to: String,
+    /// 转账金额（最小代币单位）
+    pub amount: u64,
+    /// 代币mint地址
+    pub mint: String,
+    /// 代币小数位数
+    pub decimals: u32,
+    /// 交易时间戳（秒级）
+    pub timestamp: u32,
+    /// 代币程序ID
+    pub program_id: String,
+    /// 转账类型
+    pub transfer_type: String,
+    /// 转出方代币账户的所有者钱包（铸造或余额信息中没有所有者时为空）
+    #[serde(default)]
+    pub from_owner: Option<String>,
+    /// 接收方代币账户的所有者钱包（销毁或余额信息中没有所有者时为空）
+    #[serde(default)]
+    pub to_owner: Option<String>,
+}
+
+impl TokenTransfer {
+    /// 转出方钱包，无法解析所有者时为代币账户
+    pub fn sender_wallet(&self) -> &str {
+        self.from_owner.as_deref().unwrap_or(&self.from)
+    }
+
+    /// 接收方钱包，无法解析所有者时为代币账户
+    pub fn receiver_wallet(&self) -> &str {
+        self.to_owner.as_deref().unwrap_or(&self.to)
+    }
+}
+
+/// 交易手续费信息
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FeeInfo {
+    /// 手续费（lamports）
+    pub fee_lamports: u64,
+    /// 消耗的计算单元（旧交易可能没有该字段）
+    pub compute_units: Option<u64>,
+    /// 手续费支付者（交易的第一个账户）
+    pub fee_payer: String,
+}
+
+/// 账户余额快照（交易执行前后的 lamports 余额）
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BalanceSnapshot {
+    /// 账户地址
+    pub address: String,
+    /// 执行前余额（lamports）
+    pub pre_balance: u64,
+    /// 执行后余额（lamports）
+    pub post_balance: u64,
+}
+
+/// 代币余额快照（交易执行后某个所有者持有的某种代币余额）
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TokenBalanceSnapshot {
+    /// 代币账户所有者地址
+    pub owner: String,
+    /// 代币mint地址
+    pub mint: String,
+    /// 执行后余额（最小代币单位）
+    pub amount: u64,
+    /// 代币小数位数
+    pub decimals: u32,
+}
+
+/// 质押操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StakeEventKind {
+    /// 委托给验证者
+    Delegate,
+    /// 取消委托
+    Deactivate,
+    /// 从质押账户提取 lamports
+    Withdraw,
+}
+
+impl StakeEventKind {
+    /// 操作类型名称（与序列化名称一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StakeEventKind::Delegate => "delegate",
+            StakeEventKind::Deactivate => "deactivate",
+            StakeEventKind::Withdraw => "withdraw",
+        }
+    }
+}
+
+/// 质押操作记录（Stake 程序的 delegate/deactivate/withdraw 指令）
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StakeEvent {
+    /// 交易签名
+    pub signature: String,
+    /// 操作类型
+    pub kind: StakeEventKind,
+    /// 质押账户地址
+    pub stake_account: String,
+    /// 签名授权者（质押授权或提取授权）
+    pub authority: String,
+    /// 委托的投票账户（仅 delegate）
+    pub vote_account: Option<String>,
+    /// 提取的接收方（仅 withdraw）
+    pub recipient: Option<String>,
+    /// 提取金额（lamports，仅 withdraw）
+    pub lamports: Option<u64>,
+    /// 交易时间戳（秒级）
+    pub timestamp: u32,
+}
+
+impl StakeEvent {
+    /// 事件涉及的所有地址（去重）
+    pub fn addresses(&self) -> Vec<&str> {
+        let mut addresses = vec![self.stake_account.as_str(), self.authority.as_str()];
+        addresses.extend(self.vote_account.as_deref());
+        addresses.extend(self.recipient.as_deref());
+        addresses.sort_unstable();
+        addresses.dedup();
+        addresses
+    }
+}
+
+/// DEX 兑换记录（同一所有者在一笔交易中的转出腿与转入腿配对）
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SwapRecord {
+    /// 交易签名
+    pub signature: String,
+    /// 发起兑换的所有者地址（交易签名者）
+    pub owner: String,
+    /// 兑换所用的 DEX 程序ID
+    pub program_id: String,
+    /// DEX 名称
+    pub program: String,
+    /// 付出的代币 mint（原生 SOL 记为 wSOL mint）
+    pub token_in: String,
+    /// 付出的数量（最小代币单位）
+    pub amount_in: u64,
+    /// 付出代币的小数位数
+    pub decimals_in: u32,
+    /// 收到的代币 mint（原生 SOL 记为 wSOL mint）
+    pub token_out: String,
+    /// 收到的数量（最小代币单位）
+    pub amount_out: u64,
+    /// 收到代币的小数位数
+    pub decimals_out: u32,
+    /// 交易时间戳（秒级）
+    pub timestamp: u32,
+}
+
+/// NFT 转账记录
+///
+/// 数量为 1、精度为 0，且交易中出现该 mint 的 Metaplex 元数据账户的代币转账。
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NftTransfer {
+    /// 交易签名
+    pub signature: String,
+    /// NFT 的 mint 地址
+    pub mint: String,
+    /// 所属集合的 mint，交易中有集合验证指令时才能确定
+    pub collection: Option<String>,
+    /// 转出方代币账户
+    pub from: String,
+    /// 接收方代币账户
+    pub to: String,
+    /// 转出方代币账户的所有者
+    pub from_owner: Option<String>,
+    /// 接收方代币账户的所有者
+    pub to_owner: Option<String>,
+    /// 交易时间戳（秒级）
+    pub timestamp: u32,
+}
+
+impl NftTransfer {
+    /// 转出方钱包，无法解析所有者时为代币账户
+    pub fn sender_wallet(&self) -> &str {
+        self.from_owner.as_deref().unwrap_or(&self.from)
+    }
+
+    /// 接收方钱包，无法解析所有者时为代币账户
+    pub fn receiver_wallet(&self) -> &str {
+        self.to_owner.as_deref().unwrap_or(&self.to)
+    }
+
+    /// 转账涉及的钱包（去重）
+    pub fn addresses(&self) -> Vec<&str> {
+        let mut addresses = vec![self.sender_wallet(), self.receiver_wallet()];
+        addresses.dedup();
+        addresses
+    }
+}
+
+/// 失败交易的错误分类，由 `meta.err` 的 bincode 编码解码而来
+///
+/// 常见错误单独列出，其余交易级错误保留 Solana 的错误名。
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind")]
+pub enum TransactionFailure {
+    /// 第 `index` 条顶层指令执行失败
+    InstructionError { index: u8, error: InstructionFailure },
+    /// 余额不足以支付手续费
+    InsufficientFundsForFee,
+    /// 执行后账户余额低于免租金额
+    InsufficientFundsForRent { account_index: u8 },
+    /// 区块哈希已过期或不存在
+    BlockhashNotFound,
+    /// 交易已被处理过
+    AlreadyProcessed,
+    /// 账户被同一区块内的其他交易锁定
+    AccountInUse,
+    /// 付费账户不存在
+    AccountNotFound,
+    /// 第 `index` 条指令重复
+    DuplicateInstruction { index: u8 },
+    /// 其他交易级错误
+    Other { name: String },
+    /// 无法解码的错误
+    Unknown,
+}
+
+/// 指令执行失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind")]
+pub enum InstructionFailure {
+    /// 程序返回的自定义错误码（如 SPL Token 的 `0x1` 余额不足）
+    Custom { code: u32 },
+    /// 账户余额不足
+    InsufficientFunds,
+    /// 计算单元耗尽
+    ComputationalBudgetExceeded,
+    /// 其他指令错误
+    Other { name: String },
+}
+
+/// `TransactionError` 各变体名，下标即 bincode 编码的变体序号
+const TRANSACTION_ERROR_NAMES: [&str; 39] = [
+    "AccountInUse", "AccountLoadedTwice", "AccountNotFound", "ProgramAccountNotFound", "InsufficientFundsForFee",
+    "InvalidAccountForFee", "AlreadyProcessed", "BlockhashNotFound", "InstructionError", "CallChainTooDeep",
+    "MissingSignatureForFee", "InvalidAccountIndex", "SignatureFailure", "InvalidProgramForExecution",
+    "SanitizeFailure", "ClusterMaintenance", "AccountBorrowOutstanding", "WouldExceedMaxBlockCostLimit",
+    "UnsupportedVersion", "InvalidWritableAccount", "WouldExceedMaxAccountCostLimit",
+    "WouldExceedAccountDataBlockLimit", "TooManyAccountLocks", "AddressLookupTableNotFound",
+    "InvalidAddressLookupTableOwner", "InvalidAddressLookupTableData", "InvalidAddressLookupTableIndex",
+    "InvalidRentPayingAccount", "WouldExceedMaxVoteCostLimit", "WouldExceedAccountDataTotalLimit",
+    "DuplicateInstruction", "InsufficientFundsForRent", "MaxLoadedAccountsDataSizeExceeded",
+    "InvalidLoadedAccountsDataSizeLimit", "ResanitizationNeeded", "ProgramExecutionTemporarilyRestricted",
+    "UnbalancedTransaction", "ProgramCacheHitMaxLimit", "CommitCancelled",
+];
+
+/// `InstructionError` 各变体名，下标即 bincode 编码的变体序号
+const INSTRUCTION_ERROR_NAMES: [&str; 54] = [
+    "GenericError", "InvalidArgument", "InvalidInstructionData", "InvalidAccountData", "AccountDataTooSmall",
+    "InsufficientFunds", "IncorrectProgramId", "MissingRequiredSignature", "AccountAlreadyInitialized",
+    "UninitializedAccount", "UnbalancedInstruction", "ModifiedProgramId", "ExternalAccountLamportSpend",
+    "ExternalAccountDataModified", "ReadonlyLamportChange", "ReadonlyDataModified", "DuplicateAccountIndex",
+    "ExecutableModified", "RentEpochModified", "NotEnoughAccountKeys", "AccountDataSizeChanged",
+    "AccountNotExecutable", "AccountBorrowFailed", "AccountBorrowOutstanding", "DuplicateAccountOutOfSync",
+    "Custom", "InvalidError", "ExecutableDataModified", "ExecutableLamportChange",
+    "ExecutableAccountNotRentExempt", "UnsupportedProgramId", "CallDepth", "MissingAccount",
+    "ReentrancyNotAllowed", "MaxSeedLengthExceeded", "InvalidSeeds", "InvalidRealloc",
+    "ComputationalBudgetExceeded", "PrivilegeEscalation", "ProgramEnvironmentSetupFailure",
+    "ProgramFailedToComplete", "ProgramFailedToCompile", "Immutable", "IncorrectAuthority", "BorshIoError",
+    "AccountNotRentExempt", "InvalidAccountOwner", "ArithmeticOverflow", "UnsupportedSysvar", "IllegalOwner",
+    "MaxAccountsDataAllocationsExceeded", "MaxAccountsExceeded", "MaxInstructionTraceLengthExceeded",
+    "BuiltinProgramsMustConsumeComputeUnits",
+];
+
+impl TransactionFailure {
+    /// 解码 bincode 编码的 `TransactionError`（u32 小端变体序号，后接变体字段）
+    pub fn decode(bytes: &[u8]) -> Self {
+        let u8_at = |offset: usize| bytes.get(offset).copied();
+        let u32_at = |offset: usize| {
+            bytes.get(offset..offset + 4).map(|raw| u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]))
+        };
+        let Some(variant) = u32_at(0) else {
+            return Self::Unknown;
+        };
+
+        let decoded = match variant {
+            0 => Some(Self::AccountInUse),
+            2 => Some(Self::AccountNotFound),
+            4 => Some(Self::InsufficientFundsForFee),
+            6 => Some(Self::AlreadyProcessed),
+            7 => Some(Self::BlockhashNotFound),
+            8 => u8_at(4).zip(u32_at(5)).map(|(index, error)| Self::InstructionError {
+                index,
+                error: match error {
+                    5 => InstructionFailure::InsufficientFunds,
+                    25 => match u32_at(9) {
+                        Some(code) => InstructionFailure::Custom { code },
+                        None => InstructionFailure::Other { name: "Custom".to_string() },
+                    },
+                    37 => InstructionFailure::ComputationalBudgetExceeded,
+                    other => InstructionFailure::Other {
+                        name: INSTRUCTION_ERROR_NAMES
+                            .get(other as usize)
+                            .map_or_else(|| format!("InstructionError#{}", other), |name| name.to_string()),
+                    },
+                },
+            }),
+            30 => u8_at(4).map(|index| Self::DuplicateInstruction { index }),
+            31 => u8_at(4).map(|account_index| Self::InsufficientFundsForRent { account_index }),
+            other => TRANSACTION_ERROR_NAMES
+                .get(other as usize)
+                .map(|name| Self::Other { name: name.to_string() }),
+        };
+        decoded.unwrap_or(Self::Unknown)
+    }
+
+    /// 统计分类名：交易级错误名，指令错误细分为 `InstructionError/<原因>`
+    pub fn category(&self) -> String {
+        match self {
+            Self::InstructionError { error, .. } => format!("InstructionError/{}", error.name()),
+            Self::InsufficientFundsForFee => "InsufficientFundsForFee".to_string(),
+            Self::InsufficientFundsForRent { .. } => "InsufficientFundsForRent".to_string(),
+            Self::BlockhashNotFound => "BlockhashNotFound".to_string(),
+            Self::AlreadyProcessed => "AlreadyProcessed".to_string(),
+            Self::AccountInUse => "AccountInUse".to_string(),
+            Self::AccountNotFound => "AccountNotFound".to_string(),
+            Self::DuplicateInstruction { .. } => "DuplicateInstruction".to_string(),
+            Self::Other { name } => name.clone(),
+            Self::Unknown => "Unknown".to_string(),
+        }
+    }
+}
+
+impl InstructionFailure {
+    /// 错误名
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Custom { .. } => "Custom",
+            Self::InsufficientFunds => "InsufficientFunds",
+            Self::ComputationalBudgetExceeded => "ComputationalBudgetExceeded",
+            Self::Other { name } => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_transaction_failure() {
+        // InstructionError(2, Custom(1))
+        let custom = [8, 0, 0, 0, 2, 25, 0, 0, 0, 1, 0, 0, 0];
+        assert_eq!(
+            TransactionFailure::decode(&custom),
+            TransactionFailure::InstructionError { index: 2, error: InstructionFailure::Custom { code: 1 } }
+        );
+        assert_eq!(TransactionFailure::decode(&custom).category(), "InstructionError/Custom");
+
+        assert_eq!(TransactionFailure::decode(&[7, 0, 0, 0]), TransactionFailure::BlockhashNotFound);
+        assert_eq!(
+            TransactionFailure::decode(&[31, 0, 0, 0, 3]),
+            TransactionFailure::InsufficientFundsForRent { account_index: 3 }
+        );
+        assert_eq!(TransactionFailure::decode(&[17, 0, 0, 0]).category(), "WouldExceedMaxBlockCostLimit");
+        // 截断或未知的变体
+        assert_eq!(TransactionFailure::decode(&[8, 0, 0, 0]), TransactionFailure::Unknown);
+        assert_eq!(TransactionFailure::decode(&[200, 0, 0, 0]), TransactionFailure::Unknown);
+    }
+}
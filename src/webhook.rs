@@ -0,0 +1,164 @@
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::config::{LifecycleWebhookConfig, WebhookConfig};
+use crate::database::{DatabaseManager, WebhookDelivery, WebhookSubscription, LIFECYCLE_WEBHOOK_ADDRESS};
+
+/// 请求体签名头，值为 `sha256=<十六进制 HMAC-SHA256>`
+pub const SIGNATURE_HEADER: &str = "X-Ledger-Signature";
+
+/// 通知ID头，重试时不变，接收方可据此去重
+pub const DELIVERY_HEADER: &str = "X-Ledger-Delivery";
+
+/// 每轮最多投递的通知数
+const DELIVERY_BATCH: usize = 100;
+
+/// Webhook 通知投递器
+///
+/// 后台定期取出到期的通知并发 POST 到订阅的回调地址，2xx 视为成功；
+/// 失败按指数退避重试，达到 `max_attempts` 后放弃。运维事件通知的回调地址和密钥取自
+/// `[webhooks.lifecycle]`，地址已从配置中移除的通知直接丢弃。
+pub struct WebhookNotifier {
+    db_manager: DatabaseManager,
+    config: WebhookConfig,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(db_manager: DatabaseManager, config: WebhookConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+            .build()?;
+        Ok(Self { db_manager, config, client })
+    }
+
+    /// 计算请求体签名
+    pub fn sign(secret: &str, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC 接受任意长度的密钥");
+        mac.update(body.as_bytes());
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// 持续投递到期的通知
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(self.config.delivery_interval_secs.max(1)));
+        loop {
+            ticker.tick().await;
+            match self.deliver_due().await {
+                Ok(0) => {}
+                Ok(delivered) => info!("📨 投递 Webhook 通知 {} 条", delivered),
+                Err(e) => error!("❌ 投递 Webhook 通知失败: {}", e),
+            }
+        }
+    }
+
+    /// 投递一轮到期的通知，返回成功数
+    async fn deliver_due(&self) -> Result<usize> {
+        let lifecycle = self.config.lifecycle.clone();
+        let pending = self
+            .db_manager
+            .run_blocking(move |db| {
+                let storage = db.webhook_storage();
+                storage
+                    .get_due(DELIVERY_BATCH)?
+                    .into_iter()
+                    .map(|delivery| {
+                        let subscription = if delivery.address == LIFECYCLE_WEBHOOK_ADDRESS {
+                            Self::lifecycle_subscription(&lifecycle, &delivery.subscription_id)
+                        } else {
+                            storage.get_subscription(&delivery.address, &delivery.subscription_id)?
+                        };
+                        Ok((delivery, subscription))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .await?;
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let results = futures::future::join_all(pending.into_iter().map(|(delivery, subscription)| async move {
+            let result = match &subscription {
+                Some(subscription) => self.post(subscription, &delivery).await,
+                None => Ok(()),
+            };
+            (delivery, subscription.is_some(), result)
+        }))
+        .await;
+
+        let max_attempts = self.config.max_attempts;
+        self.db_manager
+            .run_blocking(move |db| {
+                let storage = db.webhook_storage();
+                let mut delivered = 0;
+                for (delivery, subscribed, result) in results {
+                    match result {
+                        Ok(()) => {
+                            // 订阅已删除的通知直接丢弃
+                            storage.remove_delivery(&delivery.id)?;
+                            if subscribed {
+                                delivered += 1;
+                            }
+                        }
+                        Err(e) => {
+                            let retry = storage.record_failure(delivery, &e.to_string())?;
+                            if retry.attempts >= max_attempts {
+                                warn!("⚠️ Webhook 通知 {} 投递 {} 次失败，放弃: {}", retry.id, retry.attempts, e);
+                                storage.remove_delivery(&retry.id)?;
+                            } else {
+                                warn!("⚠️ Webhook 通知 {} 第 {} 次投递失败: {}", retry.id, retry.attempts, e);
+                            }
+                        }
+                    }
+                }
+                Ok(delivered)
+            })
+            .await
+    }
+
+    /// 运维事件通知对应的订阅，回调地址已不在配置中时为空
+    fn lifecycle_subscription(config: &LifecycleWebhookConfig, url: &str) -> Option<WebhookSubscription> {
+        config.urls.iter().any(|configured| configured == url).then(|| WebhookSubscription {
+            id: url.to_string(),
+            address: LIFECYCLE_WEBHOOK_ADDRESS.to_string(),
+            min_amount: 0,
+            direction: None,
+            callback_url: url.to_string(),
+            secret: config.secret.clone(),
+            created_at: 0,
+        })
+    }
+
+    async fn post(&self, subscription: &WebhookSubscription, delivery: &WebhookDelivery) -> Result<()> {
+        let response = self
+            .client
+            .post(&subscription.callback_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, Self::sign(&subscription.secret, &delivery.payload))
+            .header(DELIVERY_HEADER, &delivery.id)
+            .body(delivery.payload.clone())
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("回调返回 HTTP {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_matches_reference_hmac() {
+        // RFC 4231 测试用例 2
+        assert_eq!(
+            WebhookNotifier::sign("Jefe", "what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}